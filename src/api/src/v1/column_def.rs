@@ -14,9 +14,12 @@
 
 use std::collections::HashMap;
 
+use datatypes::data_type::{ConcreteDataType, DataType};
 use datatypes::schema::{
     ColumnDefaultConstraint, ColumnSchema, FulltextOptions, COMMENT_KEY, FULLTEXT_KEY,
+    SKIPPING_INDEX_KEY, VECTOR_INDEX_KEY,
 };
+use datatypes::types::{VectorType, JSON_TYPE_NAME};
 use snafu::ResultExt;
 
 use crate::error::{self, Result};
@@ -25,6 +28,13 @@ use crate::v1::{ColumnDef, ColumnOptions, SemanticType};
 
 /// Key used to store fulltext options in gRPC column options.
 const FULLTEXT_GRPC_KEY: &str = "fulltext";
+/// Key used to store vector index options in gRPC column options.
+const VECTOR_INDEX_GRPC_KEY: &str = "vector_index";
+/// Key used to store skipping index options in gRPC column options.
+const SKIPPING_INDEX_GRPC_KEY: &str = "skipping_index";
+/// Key used to store the logical type of the column in gRPC column options,
+/// for types sharing the same `ColumnDataType` with others (e.g. json is sent as binary).
+const TYPE_GRPC_KEY: &str = "type";
 
 /// Tries to construct a `ColumnSchema` from the given  `ColumnDef`.
 pub fn try_as_column_schema(column_def: &ColumnDef) -> Result<ColumnSchema> {
@@ -45,6 +55,17 @@ pub fn try_as_column_schema(column_def: &ColumnDef) -> Result<ColumnSchema> {
         )
     };
 
+    let mut data_type: ConcreteDataType = data_type.into();
+    if let Some(options) = column_def.options.as_ref()
+        && let Some(type_name) = options.options.get(TYPE_GRPC_KEY)
+    {
+        if type_name == JSON_TYPE_NAME {
+            data_type = ConcreteDataType::json_datatype();
+        } else if let Some(vector_type) = VectorType::from_name(type_name) {
+            data_type = ConcreteDataType::Vector(vector_type);
+        }
+    }
+
     let mut metadata = HashMap::new();
     if !column_def.comment.is_empty() {
         metadata.insert(COMMENT_KEY.to_string(), column_def.comment.clone());
@@ -54,8 +75,18 @@ pub fn try_as_column_schema(column_def: &ColumnDef) -> Result<ColumnSchema> {
     {
         metadata.insert(FULLTEXT_KEY.to_string(), fulltext.to_string());
     }
+    if let Some(options) = column_def.options.as_ref()
+        && let Some(vector_index) = options.options.get(VECTOR_INDEX_GRPC_KEY)
+    {
+        metadata.insert(VECTOR_INDEX_KEY.to_string(), vector_index.to_string());
+    }
+    if let Some(options) = column_def.options.as_ref()
+        && let Some(skipping_index) = options.options.get(SKIPPING_INDEX_GRPC_KEY)
+    {
+        metadata.insert(SKIPPING_INDEX_KEY.to_string(), skipping_index.to_string());
+    }
 
-    ColumnSchema::new(&column_def.name, data_type.into(), column_def.is_nullable)
+    ColumnSchema::new(&column_def.name, data_type, column_def.is_nullable)
         .with_metadata(metadata)
         .with_time_index(column_def.semantic_type() == SemanticType::Timestamp)
         .with_default_constraint(constraint)
@@ -72,6 +103,22 @@ pub fn options_from_column_schema(column_schema: &ColumnSchema) -> Option<Column
             .options
             .insert(FULLTEXT_GRPC_KEY.to_string(), fulltext.to_string());
     }
+    if let Some(vector_index) = column_schema.metadata().get(VECTOR_INDEX_KEY) {
+        options
+            .options
+            .insert(VECTOR_INDEX_GRPC_KEY.to_string(), vector_index.to_string());
+    }
+    if let Some(skipping_index) = column_schema.metadata().get(SKIPPING_INDEX_KEY) {
+        options.options.insert(
+            SKIPPING_INDEX_GRPC_KEY.to_string(),
+            skipping_index.to_string(),
+        );
+    }
+    if column_schema.data_type.is_json() || column_schema.data_type.is_vector() {
+        options
+            .options
+            .insert(TYPE_GRPC_KEY.to_string(), column_schema.data_type.name());
+    }
 
     (!options.options.is_empty()).then_some(options)
 }
@@ -97,7 +144,7 @@ pub fn options_from_fulltext(fulltext: &FulltextOptions) -> Result<Option<Column
 mod tests {
 
     use datatypes::data_type::ConcreteDataType;
-    use datatypes::schema::FulltextAnalyzer;
+    use datatypes::schema::{FulltextAnalyzer, SkippingIndexOptions, VectorIndexOptions};
 
     use super::*;
     use crate::v1::ColumnDataType;
@@ -152,6 +199,7 @@ mod tests {
                 enable: true,
                 analyzer: FulltextAnalyzer::English,
                 case_sensitive: false,
+                ..Default::default()
             })
             .unwrap();
         let options = options_from_column_schema(&schema).unwrap();
@@ -161,12 +209,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_column_def() {
+        let schema = ColumnSchema::new("test", ConcreteDataType::json_datatype(), true);
+        let options = options_from_column_schema(&schema);
+        assert_eq!(
+            options
+                .as_ref()
+                .unwrap()
+                .options
+                .get(TYPE_GRPC_KEY)
+                .unwrap(),
+            JSON_TYPE_NAME
+        );
+
+        let column_def = ColumnDef {
+            name: "test".to_string(),
+            data_type: ColumnDataType::Binary as i32,
+            is_nullable: true,
+            default_constraint: vec![],
+            semantic_type: SemanticType::Field as i32,
+            comment: String::new(),
+            datatype_extension: None,
+            options,
+        };
+        let schema = try_as_column_schema(&column_def).unwrap();
+        assert_eq!(schema.data_type, ConcreteDataType::json_datatype());
+    }
+
+    #[test]
+    fn test_vector_column_def() {
+        let schema = ColumnSchema::new("test", ConcreteDataType::vector_datatype(3), true)
+            .with_vector_index_options(VectorIndexOptions::default())
+            .unwrap();
+        let options = options_from_column_schema(&schema);
+        assert_eq!(
+            options
+                .as_ref()
+                .unwrap()
+                .options
+                .get(TYPE_GRPC_KEY)
+                .unwrap(),
+            "Vector(3)"
+        );
+
+        let column_def = ColumnDef {
+            name: "test".to_string(),
+            data_type: ColumnDataType::Binary as i32,
+            is_nullable: true,
+            default_constraint: vec![],
+            semantic_type: SemanticType::Field as i32,
+            comment: String::new(),
+            datatype_extension: None,
+            options,
+        };
+        let new_schema = try_as_column_schema(&column_def).unwrap();
+        assert_eq!(schema, new_schema);
+    }
+
+    #[test]
+    fn test_skipping_index_column_def() {
+        let schema = ColumnSchema::new("test", ConcreteDataType::string_datatype(), true)
+            .with_skipping_index_options(SkippingIndexOptions::default())
+            .unwrap();
+        let options = options_from_column_schema(&schema);
+        assert!(options
+            .as_ref()
+            .unwrap()
+            .options
+            .contains_key(SKIPPING_INDEX_GRPC_KEY));
+
+        let column_def = ColumnDef {
+            name: "test".to_string(),
+            data_type: ColumnDataType::String as i32,
+            is_nullable: true,
+            default_constraint: vec![],
+            semantic_type: SemanticType::Field as i32,
+            comment: String::new(),
+            datatype_extension: None,
+            options,
+        };
+        let new_schema = try_as_column_schema(&column_def).unwrap();
+        assert_eq!(schema, new_schema);
+    }
+
     #[test]
     fn test_options_with_fulltext() {
         let fulltext = FulltextOptions {
             enable: true,
             analyzer: FulltextAnalyzer::English,
             case_sensitive: false,
+            ..Default::default()
         };
         let options = options_from_fulltext(&fulltext).unwrap().unwrap();
         assert_eq!(
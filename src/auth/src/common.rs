@@ -14,12 +14,15 @@
 
 use std::sync::Arc;
 
-use common_base::secrets::SecretString;
+use common_base::secrets::{ExposeSecret, SecretString};
 use digest::Digest;
 use sha1::Sha1;
 use snafu::{ensure, OptionExt};
 
-use crate::error::{IllegalParamSnafu, InvalidConfigSnafu, Result, UserPasswordMismatchSnafu};
+use crate::error::{
+    IllegalParamSnafu, InvalidConfigSnafu, Result, UnsupportedPasswordTypeSnafu,
+    UserPasswordMismatchSnafu,
+};
 use crate::user_info::DefaultUserInfo;
 use crate::user_provider::static_user_provider::{StaticUserProvider, STATIC_USER_PROVIDER};
 use crate::user_provider::watch_file_user_provider::{
@@ -80,6 +83,55 @@ pub fn auth_mysql(
     salt: Salt,
     username: &str,
     save_pwd: &[u8],
+) -> Result<()> {
+    auth_mysql_with_hash_stage_2(auth_data, salt, username, &double_sha1(save_pwd))
+}
+
+/// Hashes the password like MySQL's `mysql_native_password` does, i.e. `SHA1(SHA1(password))`.
+///
+/// The hash is enough to authenticate both plain text and mysql native passwords, so
+/// the password itself doesn't have to be stored.
+pub fn hash_password(password: &[u8]) -> Vec<u8> {
+    double_sha1(password)
+}
+
+/// Authenticates the password sent by the client against the hash from [hash_password].
+pub fn auth_with_hashed_password(
+    username: &str,
+    password: Password<'_>,
+    hashed_password: &[u8],
+) -> Result<()> {
+    match password {
+        Password::PlainText(pwd) => {
+            ensure!(
+                !pwd.expose_secret().is_empty(),
+                IllegalParamSnafu {
+                    msg: "blank password"
+                }
+            );
+            ensure!(
+                hash_password(pwd.expose_secret().as_bytes()) == hashed_password,
+                UserPasswordMismatchSnafu {
+                    username: username.to_string(),
+                }
+            );
+            Ok(())
+        }
+        Password::MysqlNativePassword(auth_data, salt) => {
+            auth_mysql_with_hash_stage_2(auth_data, salt, username, hashed_password)
+        }
+        Password::PgMD5(_, _) => UnsupportedPasswordTypeSnafu {
+            password_type: "pg_md5",
+        }
+        .fail(),
+    }
+}
+
+fn auth_mysql_with_hash_stage_2(
+    auth_data: HashedPassword,
+    salt: Salt,
+    username: &str,
+    hash_stage_2: &[u8],
 ) -> Result<()> {
     ensure!(
         auth_data.len() == 20,
@@ -88,8 +140,7 @@ pub fn auth_mysql(
         }
     );
     // ref: https://github.com/mysql/mysql-server/blob/a246bad76b9271cb4333634e954040a970222e0a/sql/auth/password.cc#L62
-    let hash_stage_2 = double_sha1(save_pwd);
-    let tmp = sha1_two(salt, &hash_stage_2);
+    let tmp = sha1_two(salt, hash_stage_2);
     // xor auth_data and tmp
     let mut xor_result = [0u8; 20];
     for i in 0..20 {
@@ -150,4 +201,42 @@ mod tests {
         let sha1_2 = sha1_two("123456".as_bytes(), "654321".as_bytes());
         assert_eq!(sha1_2, sha1_2_answer);
     }
+
+    #[test]
+    fn test_auth_with_hashed_password() {
+        let hashed = hash_password(b"123456");
+
+        auth_with_hashed_password(
+            "greptime",
+            Password::PlainText("123456".to_string().into()),
+            &hashed,
+        )
+        .unwrap();
+        assert!(auth_with_hashed_password(
+            "greptime",
+            Password::PlainText("654321".to_string().into()),
+            &hashed,
+        )
+        .is_err());
+
+        let salt = b"01234567890123456789";
+        // auth_data = SHA1(password) XOR SHA1(salt + SHA1(SHA1(password)))
+        let auth_data = sha1_one(b"123456")
+            .iter()
+            .zip(sha1_two(salt, &hashed))
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        auth_with_hashed_password(
+            "greptime",
+            Password::MysqlNativePassword(&auth_data, salt),
+            &hashed,
+        )
+        .unwrap();
+        assert!(auth_with_hashed_password(
+            "greptime",
+            Password::MysqlNativePassword(&auth_data, b"98765432109876543210"),
+            &hashed,
+        )
+        .is_err());
+    }
 }
@@ -57,6 +57,30 @@ pub enum Error {
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to list region moves in cluster: {source}"))]
+    ListRegionMoves {
+        #[snafu(implicit)]
+        location: Location,
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to list processes on frontend {frontend}"))]
+    ListProcesses {
+        frontend: String,
+        #[snafu(implicit)]
+        location: Location,
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to kill process {id} on frontend {frontend}"))]
+    KillProcess {
+        frontend: String,
+        id: u64,
+        #[snafu(implicit)]
+        location: Location,
+        source: BoxedError,
+    },
+
     #[snafu(display("Failed to list flows in catalog {catalog}"))]
     ListFlows {
         #[snafu(implicit)]
@@ -297,6 +321,9 @@ impl ErrorExt for Error {
             Error::TableNotExist { .. } => StatusCode::TableNotFound,
             Error::ListCatalogs { source, .. }
             | Error::ListNodes { source, .. }
+            | Error::ListRegionMoves { source, .. }
+            | Error::ListProcesses { source, .. }
+            | Error::KillProcess { source, .. }
             | Error::ListSchemas { source, .. }
             | Error::ListTables { source, .. }
             | Error::ListFlows { source, .. } => source.status_code(),
@@ -48,6 +48,7 @@ use crate::error::{
 };
 use crate::information_schema::InformationSchemaProvider;
 use crate::kvbackend::TableCacheRef;
+use crate::process_manager::ProcessManagerRef;
 use crate::system_schema::pg_catalog::PGCatalogProvider;
 use crate::system_schema::SystemSchemaProvider;
 use crate::CatalogManager;
@@ -66,6 +67,7 @@ pub struct KvBackendCatalogManager {
     /// A sub-CatalogManager that handles system tables
     system_catalog: SystemCatalog,
     cache_registry: LayeredCacheRegistryRef,
+    process_manager: Option<ProcessManagerRef>,
 }
 
 const CATALOG_CACHE_MAX_CAPACITY: u64 = 128;
@@ -76,6 +78,7 @@ impl KvBackendCatalogManager {
         meta_client: Option<Arc<MetaClient>>,
         backend: KvBackendRef,
         cache_registry: LayeredCacheRegistryRef,
+        process_manager: Option<ProcessManagerRef>,
     ) -> Arc<Self> {
         Arc::new_cyclic(|me| Self {
             mode,
@@ -103,6 +106,7 @@ impl KvBackendCatalogManager {
                 backend,
             },
             cache_registry,
+            process_manager,
         })
     }
 
@@ -122,6 +126,11 @@ impl KvBackendCatalogManager {
         self.meta_client.clone()
     }
 
+    /// Returns the `[ProcessManager]` that tracks the running queries of the frontend.
+    pub fn process_manager(&self) -> Option<ProcessManagerRef> {
+        self.process_manager.clone()
+    }
+
     pub fn partition_manager(&self) -> PartitionRuleManagerRef {
         self.partition_manager.clone()
     }
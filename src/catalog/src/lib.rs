@@ -31,6 +31,7 @@ pub mod error;
 pub mod kvbackend;
 pub mod memory;
 mod metrics;
+pub mod process_manager;
 pub mod system_schema;
 pub mod information_schema {
     // TODO(j0hn50n133): re-export to make it compatible with the legacy code, migrate to the new path later
@@ -0,0 +1,499 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+use common_error::ext::BoxedError;
+use common_recordbatch::adapter::RecordBatchMetrics;
+use common_recordbatch::error::StreamCancelledSnafu;
+use common_recordbatch::{OrderOption, RecordBatch, RecordBatchStream, SendableRecordBatchStream};
+use common_telemetry::{info, warn};
+use common_time::util::current_time_millis;
+use datatypes::schema::SchemaRef;
+use futures::future::{BoxFuture, FutureExt};
+use futures::{Stream, StreamExt};
+use snafu::ResultExt;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{KillProcessSnafu, ListNodesSnafu, ListProcessesSnafu, Result};
+
+pub type ProcessManagerRef = Arc<ProcessManager>;
+pub type FrontendClientRef = Arc<dyn FrontendClient>;
+
+/// The information of a query running on a frontend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    /// The id of the query, unique in the frontend.
+    pub id: u64,
+    /// The address of the frontend that runs the query.
+    pub frontend: String,
+    pub catalog: String,
+    pub schema: String,
+    pub user: String,
+    /// The protocol the query comes from, e.g. `mysql`, `http`.
+    pub channel: String,
+    pub query: String,
+    pub start_timestamp_ms: i64,
+}
+
+impl ProcessInfo {
+    /// Returns the id that identifies the query in the cluster, in the form of `{frontend}/{id}`.
+    pub fn display_id(&self) -> String {
+        format_process_id(&self.frontend, self.id)
+    }
+}
+
+/// Formats the cluster-wide process id.
+pub fn format_process_id(frontend: &str, id: u64) -> String {
+    format!("{frontend}/{id}")
+}
+
+/// Parses the process id in the form of `{frontend}/{id}`, or `{id}` for the queries on
+/// the current frontend.
+pub fn parse_process_id(process_id: &str) -> Option<(Option<&str>, u64)> {
+    match process_id.rsplit_once('/') {
+        Some((frontend, id)) if !frontend.is_empty() => {
+            id.parse().ok().map(|id| (Some(frontend), id))
+        }
+        Some(_) => None,
+        None => process_id.parse().ok().map(|id| (None, id)),
+    }
+}
+
+/// The client to access the processes running on other frontends.
+#[async_trait::async_trait]
+pub trait FrontendClient: Send + Sync {
+    /// Returns the addresses of all frontends in the cluster.
+    async fn frontends(&self) -> std::result::Result<Vec<String>, BoxedError>;
+
+    /// Lists the queries of the `catalog` running on the `frontend`.
+    async fn list_processes(
+        &self,
+        frontend: &str,
+        catalog: &str,
+    ) -> std::result::Result<Vec<ProcessInfo>, BoxedError>;
+
+    /// Kills the query `id` of the `catalog` running on the `frontend`.
+    async fn kill_process(
+        &self,
+        frontend: &str,
+        catalog: &str,
+        id: u64,
+    ) -> std::result::Result<(), BoxedError>;
+}
+
+struct RunningProcess {
+    info: ProcessInfo,
+    cancellation_token: CancellationToken,
+}
+
+/// Tracks the queries running on this frontend, and accesses the queries on
+/// other frontends by the [FrontendClient] in distributed mode.
+pub struct ProcessManager {
+    server_addr: String,
+    next_id: AtomicU64,
+    processes: RwLock<HashMap<u64, RunningProcess>>,
+    frontend_client: Option<FrontendClientRef>,
+}
+
+impl ProcessManager {
+    pub fn new(server_addr: String, frontend_client: Option<FrontendClientRef>) -> Self {
+        Self {
+            server_addr,
+            next_id: AtomicU64::new(0),
+            processes: RwLock::new(HashMap::new()),
+            frontend_client,
+        }
+    }
+
+    /// Returns the address of this frontend.
+    pub fn server_addr(&self) -> &str {
+        &self.server_addr
+    }
+
+    /// Registers a running query, the query is deregistered when the returned
+    /// [ProcessTicket] is dropped.
+    pub fn register_query(
+        self: &Arc<Self>,
+        catalog: String,
+        schema: String,
+        user: String,
+        channel: String,
+        query: String,
+    ) -> ProcessTicket {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let info = ProcessInfo {
+            id,
+            frontend: self.server_addr.clone(),
+            catalog,
+            schema,
+            user,
+            channel,
+            query,
+            start_timestamp_ms: current_time_millis(),
+        };
+        let cancellation_token = CancellationToken::new();
+        let process = RunningProcess {
+            info,
+            cancellation_token: cancellation_token.clone(),
+        };
+        let _ = self.processes.write().unwrap().insert(id, process);
+
+        ProcessTicket {
+            id,
+            manager: self.clone(),
+            cancellation_token,
+        }
+    }
+
+    fn deregister_query(&self, id: u64) {
+        let _ = self.processes.write().unwrap().remove(&id);
+    }
+
+    /// Returns the queries of the `catalog` running on this frontend.
+    pub fn local_processes(&self, catalog: &str) -> Vec<ProcessInfo> {
+        self.processes
+            .read()
+            .unwrap()
+            .values()
+            .filter(|process| process.info.catalog == catalog)
+            .map(|process| process.info.clone())
+            .collect()
+    }
+
+    /// Returns the queries of the `catalog` running on the other frontends whose
+    /// addresses are accepted by the `filter`.
+    pub async fn remote_processes<F>(&self, catalog: &str, filter: F) -> Result<Vec<ProcessInfo>>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let Some(client) = &self.frontend_client else {
+            return Ok(vec![]);
+        };
+
+        let frontends = client.frontends().await.context(ListNodesSnafu)?;
+        let mut processes = vec![];
+        for frontend in frontends {
+            if frontend == self.server_addr || !filter(&frontend) {
+                continue;
+            }
+            let remote =
+                client
+                    .list_processes(&frontend, catalog)
+                    .await
+                    .context(ListProcessesSnafu {
+                        frontend: &frontend,
+                    })?;
+            processes.extend(remote);
+        }
+        Ok(processes)
+    }
+
+    /// Cancels the query `id` of the `catalog` running on this frontend,
+    /// returns false if the query is not found.
+    pub fn kill_local(&self, catalog: &str, id: u64) -> bool {
+        let processes = self.processes.read().unwrap();
+        match processes.get(&id) {
+            Some(process) if process.info.catalog == catalog => {
+                info!(
+                    "Killing query {}, query: {}",
+                    process.info.display_id(),
+                    process.info.query
+                );
+                process.cancellation_token.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancels the query `id` of the `catalog` running on the `frontend`, returns false
+    /// if the query is not found on this frontend. Killing a query on other frontends
+    /// fails if the query is not found.
+    pub async fn kill(&self, frontend: &str, catalog: &str, id: u64) -> Result<bool> {
+        if frontend == self.server_addr {
+            return Ok(self.kill_local(catalog, id));
+        }
+
+        let Some(client) = &self.frontend_client else {
+            warn!("Unable to kill query {id} on frontend {frontend} without a frontend client");
+            return Ok(false);
+        };
+        client
+            .kill_process(frontend, catalog, id)
+            .await
+            .context(KillProcessSnafu { frontend, id })?;
+        Ok(true)
+    }
+}
+
+/// The registration of a running query, deregisters the query on drop.
+pub struct ProcessTicket {
+    id: u64,
+    manager: ProcessManagerRef,
+    cancellation_token: CancellationToken,
+}
+
+impl ProcessTicket {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns true if the query is killed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+
+    /// Waits until the query is killed.
+    pub async fn cancelled(&self) {
+        self.cancellation_token.cancelled().await
+    }
+
+    /// Wraps the output `stream` of the query, so that the `stream` is dropped once the
+    /// query is killed, and the query is deregistered when the returned stream is dropped.
+    pub fn wrap_stream(self, stream: SendableRecordBatchStream) -> SendableRecordBatchStream {
+        Box::pin(CancellableStream {
+            schema: stream.schema(),
+            stream: Some(stream),
+            cancelled: self.cancellation_token.clone().cancelled_owned().boxed(),
+            ticket: self,
+        })
+    }
+}
+
+impl Drop for ProcessTicket {
+    fn drop(&mut self) {
+        self.manager.deregister_query(self.id);
+    }
+}
+
+/// The output stream of a registered query.
+struct CancellableStream {
+    schema: SchemaRef,
+    /// The inner stream, dropped once the query is killed.
+    stream: Option<SendableRecordBatchStream>,
+    cancelled: BoxFuture<'static, ()>,
+    ticket: ProcessTicket,
+}
+
+impl RecordBatchStream for CancellableStream {
+    fn name(&self) -> &str {
+        "CancellableStream"
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.stream.as_ref().and_then(|s| s.output_ordering())
+    }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        self.stream.as_ref().and_then(|s| s.metrics())
+    }
+}
+
+impl Stream for CancellableStream {
+    type Item = common_recordbatch::error::Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.stream.is_none() {
+            return Poll::Ready(None);
+        }
+
+        if self.cancelled.poll_unpin(cx).is_ready() {
+            // Dropping the inner stream aborts the execution of the query, including
+            // the requests to datanodes.
+            self.stream = None;
+            let reason = format!(
+                "query {} is killed",
+                format_process_id(self.ticket.manager.server_addr(), self.ticket.id)
+            );
+            return Poll::Ready(Some(StreamCancelledSnafu { reason }.fail()));
+        }
+
+        // Safety: checked above.
+        self.stream.as_mut().unwrap().poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use common_recordbatch::RecordBatches;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::UInt32Vector;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockFrontendClient {
+        processes: Vec<ProcessInfo>,
+        killed: Mutex<Vec<(String, u64)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl FrontendClient for MockFrontendClient {
+        async fn frontends(&self) -> std::result::Result<Vec<String>, BoxedError> {
+            Ok(vec!["fe1:4001".to_string(), "fe2:4001".to_string()])
+        }
+
+        async fn list_processes(
+            &self,
+            frontend: &str,
+            catalog: &str,
+        ) -> std::result::Result<Vec<ProcessInfo>, BoxedError> {
+            Ok(self
+                .processes
+                .iter()
+                .filter(|p| p.frontend == frontend && p.catalog == catalog)
+                .cloned()
+                .collect())
+        }
+
+        async fn kill_process(
+            &self,
+            frontend: &str,
+            _catalog: &str,
+            id: u64,
+        ) -> std::result::Result<(), BoxedError> {
+            self.killed.lock().unwrap().push((frontend.to_string(), id));
+            Ok(())
+        }
+    }
+
+    fn register(manager: &ProcessManagerRef, catalog: &str, query: &str) -> ProcessTicket {
+        manager.register_query(
+            catalog.to_string(),
+            "public".to_string(),
+            "greptime".to_string(),
+            "mysql".to_string(),
+            query.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_parse_process_id() {
+        assert_eq!(Some((None, 42)), parse_process_id("42"));
+        assert_eq!(
+            Some((Some("127.0.0.1:4001"), 42)),
+            parse_process_id("127.0.0.1:4001/42")
+        );
+        assert_eq!(None, parse_process_id("/42"));
+        assert_eq!(None, parse_process_id("127.0.0.1:4001/abc"));
+        assert_eq!(None, parse_process_id("abc"));
+    }
+
+    #[test]
+    fn test_register_and_kill_local() {
+        let manager = Arc::new(ProcessManager::new("fe1:4001".to_string(), None));
+        let ticket1 = register(&manager, "greptime", "SELECT 1");
+        let ticket2 = register(&manager, "other", "SELECT 2");
+
+        let processes = manager.local_processes("greptime");
+        assert_eq!(1, processes.len());
+        assert_eq!("SELECT 1", processes[0].query);
+        assert_eq!(
+            format!("fe1:4001/{}", ticket1.id()),
+            processes[0].display_id()
+        );
+
+        // Queries in other catalogs can't be killed.
+        assert!(!manager.kill_local("greptime", ticket2.id()));
+        assert!(!ticket2.is_cancelled());
+        assert!(manager.kill_local("greptime", ticket1.id()));
+        assert!(ticket1.is_cancelled());
+
+        drop(ticket1);
+        assert!(manager.local_processes("greptime").is_empty());
+        assert_eq!(1, manager.local_processes("other").len());
+    }
+
+    #[tokio::test]
+    async fn test_remote_processes() {
+        let remote = ProcessInfo {
+            id: 7,
+            frontend: "fe2:4001".to_string(),
+            catalog: "greptime".to_string(),
+            schema: "public".to_string(),
+            user: "greptime".to_string(),
+            channel: "http".to_string(),
+            query: "SELECT 7".to_string(),
+            start_timestamp_ms: 0,
+        };
+        let client = Arc::new(MockFrontendClient {
+            processes: vec![remote.clone()],
+            ..Default::default()
+        });
+        let manager = Arc::new(ProcessManager::new(
+            "fe1:4001".to_string(),
+            Some(client.clone()),
+        ));
+
+        let processes = manager
+            .remote_processes("greptime", |_| true)
+            .await
+            .unwrap();
+        assert_eq!(vec![remote], processes);
+        let processes = manager
+            .remote_processes("greptime", |addr| addr != "fe2:4001")
+            .await
+            .unwrap();
+        assert!(processes.is_empty());
+
+        assert!(manager.kill("fe2:4001", "greptime", 7).await.unwrap());
+        assert_eq!(
+            vec![("fe2:4001".to_string(), 7)],
+            *client.killed.lock().unwrap()
+        );
+        // Kills the local query without the client.
+        assert!(!manager.kill("fe1:4001", "greptime", 0).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stream() {
+        let manager = Arc::new(ProcessManager::new("fe1:4001".to_string(), None));
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "v",
+            ConcreteDataType::uint32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(UInt32Vector::from_slice([1, 2])) as _],
+        )
+        .unwrap();
+        let batches = RecordBatches::try_new(schema, vec![batch.clone(), batch]).unwrap();
+
+        let ticket = register(&manager, "greptime", "SELECT v FROM t");
+        let id = ticket.id();
+        let mut stream = ticket.wrap_stream(batches.as_stream());
+        assert!(stream.next().await.unwrap().is_ok());
+
+        assert!(manager.kill_local("greptime", id));
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("fe1:4001/0 is killed"), "{err}");
+        assert!(stream.next().await.is_none());
+
+        drop(stream);
+        assert!(manager.local_processes("greptime").is_empty());
+    }
+}
@@ -18,6 +18,8 @@ pub mod flows;
 mod information_memory_table;
 pub mod key_column_usage;
 mod partitions;
+pub mod process_list;
+mod region_move_plans;
 mod region_peers;
 mod runtime_metrics;
 pub mod schemata;
@@ -49,6 +51,8 @@ use crate::system_schema::information_schema::flows::InformationSchemaFlows;
 use crate::system_schema::information_schema::information_memory_table::get_schema_columns;
 use crate::system_schema::information_schema::key_column_usage::InformationSchemaKeyColumnUsage;
 use crate::system_schema::information_schema::partitions::InformationSchemaPartitions;
+use crate::system_schema::information_schema::process_list::InformationSchemaProcessList;
+use crate::system_schema::information_schema::region_move_plans::InformationSchemaRegionMovePlans;
 use crate::system_schema::information_schema::region_peers::InformationSchemaRegionPeers;
 use crate::system_schema::information_schema::runtime_metrics::InformationSchemaMetrics;
 use crate::system_schema::information_schema::schemata::InformationSchemaSchemata;
@@ -180,10 +184,17 @@ impl SystemSchemaProviderInner for InformationSchemaProvider {
             CLUSTER_INFO => Some(Arc::new(InformationSchemaClusterInfo::new(
                 self.catalog_manager.clone(),
             )) as _),
+            REGION_MOVE_PLANS => Some(Arc::new(InformationSchemaRegionMovePlans::new(
+                self.catalog_manager.clone(),
+            )) as _),
             VIEWS => Some(Arc::new(InformationSchemaViews::new(
                 self.catalog_name.clone(),
                 self.catalog_manager.clone(),
             )) as _),
+            PROCESS_LIST => Some(Arc::new(InformationSchemaProcessList::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _),
             FLOWS => Some(Arc::new(InformationSchemaFlows::new(
                 self.catalog_name.clone(),
                 self.flow_metadata_manager.clone(),
@@ -235,10 +246,18 @@ impl InformationSchemaProvider {
                 CLUSTER_INFO.to_string(),
                 self.build_table(CLUSTER_INFO).unwrap(),
             );
+            tables.insert(
+                REGION_MOVE_PLANS.to_string(),
+                self.build_table(REGION_MOVE_PLANS).unwrap(),
+            );
         }
 
         tables.insert(TABLES.to_string(), self.build_table(TABLES).unwrap());
         tables.insert(VIEWS.to_string(), self.build_table(VIEWS).unwrap());
+        tables.insert(
+            PROCESS_LIST.to_string(),
+            self.build_table(PROCESS_LIST).unwrap(),
+        );
         tables.insert(SCHEMATA.to_string(), self.build_table(SCHEMATA).unwrap());
         tables.insert(COLUMNS.to_string(), self.build_table(COLUMNS).unwrap());
         tables.insert(
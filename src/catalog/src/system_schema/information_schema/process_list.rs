@@ -0,0 +1,266 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_PROCESS_LIST_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use common_time::timestamp::Timestamp;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::timestamp::TimestampMillisecond;
+use datatypes::value::Value;
+use datatypes::vectors::{StringVectorBuilder, TimestampMillisecondVectorBuilder};
+use snafu::ResultExt;
+use store_api::storage::{ScanRequest, TableId};
+
+use super::PROCESS_LIST;
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, Result};
+use crate::process_manager::ProcessInfo;
+use crate::system_schema::information_schema::{InformationTable, Predicates};
+use crate::system_schema::utils;
+use crate::CatalogManager;
+
+pub const ID: &str = "id";
+pub const FRONTEND: &str = "frontend";
+pub const CATALOG: &str = "catalog";
+pub const SCHEMA_NAME: &str = "schema_name";
+pub const USER_NAME: &str = "user_name";
+pub const CHANNEL: &str = "channel";
+pub const QUERY: &str = "query";
+pub const START_TIME: &str = "start_time";
+
+const INIT_CAPACITY: usize = 42;
+
+/// The `PROCESS_LIST` table shows the queries of the catalog running on all frontends.
+///
+/// - `id`: the id of the query in the form of `{frontend}/{id}`, which is used by `KILL QUERY`.
+/// - `frontend`: the address of the frontend that runs the query.
+/// - `catalog`: the catalog of the query.
+/// - `schema_name`: the current schema of the query.
+/// - `user_name`: the user who runs the query.
+/// - `channel`: the protocol the query comes from.
+/// - `query`: the statement of the query.
+/// - `start_time`: the time the query starts.
+///
+pub(super) struct InformationSchemaProcessList {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl InformationSchemaProcessList {
+    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        Self {
+            schema: Self::schema(),
+            catalog_name,
+            catalog_manager,
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new(ID, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(FRONTEND, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(CATALOG, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(SCHEMA_NAME, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(USER_NAME, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(CHANNEL, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(QUERY, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                START_TIME,
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaProcessListBuilder {
+        InformationSchemaProcessListBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+        )
+    }
+}
+
+impl InformationTable for InformationSchemaProcessList {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_PROCESS_LIST_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        PROCESS_LIST
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self, request: ScanRequest) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_process_list(Some(request))
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+struct InformationSchemaProcessListBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+
+    ids: StringVectorBuilder,
+    frontends: StringVectorBuilder,
+    catalogs: StringVectorBuilder,
+    schema_names: StringVectorBuilder,
+    user_names: StringVectorBuilder,
+    channels: StringVectorBuilder,
+    queries: StringVectorBuilder,
+    start_times: TimestampMillisecondVectorBuilder,
+}
+
+impl InformationSchemaProcessListBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            ids: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            frontends: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            catalogs: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            schema_names: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            user_names: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            channels: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            queries: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            start_times: TimestampMillisecondVectorBuilder::with_capacity(INIT_CAPACITY),
+        }
+    }
+
+    /// Construct the `information_schema.process_list` virtual table
+    async fn make_process_list(&mut self, request: Option<ScanRequest>) -> Result<RecordBatch> {
+        let predicates = Predicates::from_scan_request(&request);
+
+        if let Some(process_manager) = utils::process_manager(&self.catalog_manager)? {
+            let local_addr = Value::from(process_manager.server_addr());
+            if predicates.eval(&[(FRONTEND, &local_addr)]) {
+                for process in process_manager.local_processes(&self.catalog_name) {
+                    self.add_process(&predicates, &process);
+                }
+            }
+
+            // Only requests the frontends that may match the predicates, the remote frontends
+            // are requested with the predicate on their own addresses, so they never
+            // request others in turn.
+            let remote_processes = process_manager
+                .remote_processes(&self.catalog_name, |addr| {
+                    predicates.eval(&[(FRONTEND, &Value::from(addr))])
+                })
+                .await?;
+            for process in remote_processes {
+                self.add_process(&predicates, &process);
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_process(&mut self, predicates: &Predicates, process: &ProcessInfo) {
+        let id = process.display_id();
+
+        let row = [
+            (ID, &Value::from(id.as_str())),
+            (FRONTEND, &Value::from(process.frontend.as_str())),
+            (CATALOG, &Value::from(process.catalog.as_str())),
+            (SCHEMA_NAME, &Value::from(process.schema.as_str())),
+            (USER_NAME, &Value::from(process.user.as_str())),
+            (CHANNEL, &Value::from(process.channel.as_str())),
+        ];
+
+        if !predicates.eval(&row) {
+            return;
+        }
+
+        self.ids.push(Some(&id));
+        self.frontends.push(Some(&process.frontend));
+        self.catalogs.push(Some(&process.catalog));
+        self.schema_names.push(Some(&process.schema));
+        self.user_names.push(Some(&process.user));
+        self.channels.push(Some(&process.channel));
+        self.queries.push(Some(&process.query));
+        self.start_times
+            .push(Some(TimestampMillisecond(Timestamp::new_millisecond(
+                process.start_timestamp_ms,
+            ))));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.ids.finish()),
+            Arc::new(self.frontends.finish()),
+            Arc::new(self.catalogs.finish()),
+            Arc::new(self.schema_names.finish()),
+            Arc::new(self.user_names.finish()),
+            Arc::new(self.channels.finish()),
+            Arc::new(self.queries.finish()),
+            Arc::new(self.start_times.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaProcessList {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_process_list(None)
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
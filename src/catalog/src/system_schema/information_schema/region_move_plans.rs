@@ -0,0 +1,263 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_REGION_MOVE_PLANS_TABLE_ID;
+use common_config::Mode;
+use common_error::ext::BoxedError;
+use common_meta::cluster::{ClusterInfo, RegionMove};
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use common_telemetry::warn;
+use common_time::timestamp::Timestamp;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::timestamp::TimestampMillisecond;
+use datatypes::value::Value;
+use datatypes::vectors::{
+    StringVectorBuilder, TimestampMillisecondVectorBuilder, UInt32VectorBuilder,
+    UInt64VectorBuilder,
+};
+use snafu::ResultExt;
+use store_api::storage::{RegionId, ScanRequest, TableId};
+
+use super::REGION_MOVE_PLANS;
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, ListRegionMovesSnafu, Result};
+use crate::system_schema::information_schema::{InformationTable, Predicates};
+use crate::system_schema::utils;
+use crate::CatalogManager;
+
+const REGION_ID: &str = "region_id";
+const TABLE_ID: &str = "table_id";
+const FROM_PEER_ID: &str = "from_peer_id";
+const TO_PEER_ID: &str = "to_peer_id";
+const REASON: &str = "reason";
+const STATUS: &str = "status";
+const PROCEDURE_ID: &str = "procedure_id";
+const PLAN_TIME: &str = "plan_time";
+
+const INIT_CAPACITY: usize = 42;
+
+/// The `REGION_MOVE_PLANS` table shows region moves planned by the region balancer of metasrv
+/// in its latest balancing round.
+///
+/// - `region_id`: the id of the region to move.
+/// - `table_id`: the id of the table the region belongs to.
+/// - `from_peer_id`: the datanode the region moves from.
+/// - `to_peer_id`: the datanode the region moves to.
+/// - `reason`: why the region is moved.
+/// - `status`: `DRY_RUN`, `SUBMITTED` or `FAILED`.
+/// - `procedure_id`: the id of the region migration procedure if the move is submitted.
+/// - `plan_time`: the time the move is planned.
+///
+pub(super) struct InformationSchemaRegionMovePlans {
+    schema: SchemaRef,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl InformationSchemaRegionMovePlans {
+    pub(super) fn new(catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        Self {
+            schema: Self::schema(),
+            catalog_manager,
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new(REGION_ID, ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new(TABLE_ID, ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new(FROM_PEER_ID, ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new(TO_PEER_ID, ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new(REASON, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(STATUS, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(PROCEDURE_ID, ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                PLAN_TIME,
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaRegionMovePlansBuilder {
+        InformationSchemaRegionMovePlansBuilder::new(
+            self.schema.clone(),
+            self.catalog_manager.clone(),
+        )
+    }
+}
+
+impl InformationTable for InformationSchemaRegionMovePlans {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_REGION_MOVE_PLANS_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        REGION_MOVE_PLANS
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self, request: ScanRequest) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_move_plans(Some(request))
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+struct InformationSchemaRegionMovePlansBuilder {
+    schema: SchemaRef,
+    catalog_manager: Weak<dyn CatalogManager>,
+
+    region_ids: UInt64VectorBuilder,
+    table_ids: UInt32VectorBuilder,
+    from_peer_ids: UInt64VectorBuilder,
+    to_peer_ids: UInt64VectorBuilder,
+    reasons: StringVectorBuilder,
+    statuses: StringVectorBuilder,
+    procedure_ids: StringVectorBuilder,
+    plan_times: TimestampMillisecondVectorBuilder,
+}
+
+impl InformationSchemaRegionMovePlansBuilder {
+    fn new(schema: SchemaRef, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        Self {
+            schema,
+            catalog_manager,
+            region_ids: UInt64VectorBuilder::with_capacity(INIT_CAPACITY),
+            table_ids: UInt32VectorBuilder::with_capacity(INIT_CAPACITY),
+            from_peer_ids: UInt64VectorBuilder::with_capacity(INIT_CAPACITY),
+            to_peer_ids: UInt64VectorBuilder::with_capacity(INIT_CAPACITY),
+            reasons: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            statuses: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            procedure_ids: StringVectorBuilder::with_capacity(INIT_CAPACITY),
+            plan_times: TimestampMillisecondVectorBuilder::with_capacity(INIT_CAPACITY),
+        }
+    }
+
+    /// Construct the `information_schema.region_move_plans` virtual table
+    async fn make_region_move_plans(
+        &mut self,
+        request: Option<ScanRequest>,
+    ) -> Result<RecordBatch> {
+        let predicates = Predicates::from_scan_request(&request);
+        let mode = utils::running_mode(&self.catalog_manager)?.unwrap_or(Mode::Standalone);
+
+        // Regions are never moved in standalone mode.
+        if mode == Mode::Distributed {
+            if let Some(meta_client) = utils::meta_client(&self.catalog_manager)? {
+                let region_moves = meta_client
+                    .list_region_moves()
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(ListRegionMovesSnafu)?;
+
+                for region_move in region_moves {
+                    self.add_region_move(&predicates, region_move);
+                }
+            } else {
+                warn!("Could not find meta client in distributed mode.");
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_region_move(&mut self, predicates: &Predicates, region_move: RegionMove) {
+        let table_id = RegionId::from_u64(region_move.region_id).table_id();
+        let status = region_move.status.as_str();
+
+        let row = [
+            (REGION_ID, &Value::from(region_move.region_id)),
+            (TABLE_ID, &Value::from(table_id)),
+            (FROM_PEER_ID, &Value::from(region_move.from_peer_id)),
+            (TO_PEER_ID, &Value::from(region_move.to_peer_id)),
+            (STATUS, &Value::from(status)),
+        ];
+
+        if !predicates.eval(&row) {
+            return;
+        }
+
+        self.region_ids.push(Some(region_move.region_id));
+        self.table_ids.push(Some(table_id));
+        self.from_peer_ids.push(Some(region_move.from_peer_id));
+        self.to_peer_ids.push(Some(region_move.to_peer_id));
+        self.reasons.push(Some(&region_move.reason));
+        self.statuses.push(Some(status));
+        self.procedure_ids.push(region_move.procedure_id.as_deref());
+        self.plan_times
+            .push(Some(TimestampMillisecond(Timestamp::new_millisecond(
+                region_move.plan_time_ms,
+            ))));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.region_ids.finish()),
+            Arc::new(self.table_ids.finish()),
+            Arc::new(self.from_peer_ids.finish()),
+            Arc::new(self.to_peer_ids.finish()),
+            Arc::new(self.reasons.finish()),
+            Arc::new(self.statuses.finish()),
+            Arc::new(self.procedure_ids.finish()),
+            Arc::new(self.plan_times.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaRegionMovePlans {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_move_plans(None)
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
@@ -45,3 +45,5 @@ pub const TABLE_CONSTRAINTS: &str = "table_constraints";
 pub const CLUSTER_INFO: &str = "cluster_info";
 pub const VIEWS: &str = "views";
 pub const FLOWS: &str = "flows";
+pub const REGION_MOVE_PLANS: &str = "region_move_plans";
+pub const PROCESS_LIST: &str = "process_list";
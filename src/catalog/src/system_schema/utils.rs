@@ -23,6 +23,7 @@ use snafu::OptionExt;
 
 use crate::error::{Result, UpgradeWeakCatalogManagerRefSnafu};
 use crate::kvbackend::KvBackendCatalogManager;
+use crate::process_manager::ProcessManagerRef;
 use crate::CatalogManager;
 
 /// Try to get the server running mode from `[CatalogManager]` weak reference.
@@ -55,6 +56,20 @@ pub fn meta_client(catalog_manager: &Weak<dyn CatalogManager>) -> Result<Option<
     Ok(meta_client)
 }
 
+/// Try to get the `[ProcessManagerRef]` from `[CatalogManager]` weak reference.
+pub fn process_manager(
+    catalog_manager: &Weak<dyn CatalogManager>,
+) -> Result<Option<ProcessManagerRef>> {
+    let catalog_manager = catalog_manager
+        .upgrade()
+        .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+    Ok(catalog_manager
+        .as_any()
+        .downcast_ref::<KvBackendCatalogManager>()
+        .and_then(|manager| manager.process_manager()))
+}
+
 /// Try to get the `[TableMetadataManagerRef]` from `[CatalogManager]` weak reference.
 pub fn table_meta_manager(
     catalog_manager: &Weak<dyn CatalogManager>,
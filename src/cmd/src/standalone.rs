@@ -18,6 +18,7 @@ use std::{fs, path};
 use async_trait::async_trait;
 use cache::{build_fundamental_cache_registry, with_default_composite_cache_registry};
 use catalog::kvbackend::KvBackendCatalogManager;
+use catalog::process_manager::ProcessManager;
 use clap::Parser;
 use common_base::Plugins;
 use common_catalog::consts::{MIN_USER_FLOW_ID, MIN_USER_TABLE_ID};
@@ -60,7 +61,7 @@ use servers::export_metrics::ExportMetricsOption;
 use servers::grpc::GrpcOptions;
 use servers::http::HttpOptions;
 use servers::tls::{TlsMode, TlsOption};
-use servers::Mode;
+use servers::{addrs, Mode};
 use snafu::ResultExt;
 use tokio::sync::broadcast;
 use tracing_appender::non_blocking::WorkerGuard;
@@ -460,11 +461,16 @@ impl StartCommand {
             .build(),
         );
 
+        let process_manager = Arc::new(ProcessManager::new(
+            addrs::resolve_addr(&fe_opts.grpc.addr, Some(&fe_opts.grpc.hostname)),
+            None,
+        ));
         let catalog_manager = KvBackendCatalogManager::new(
             dn_opts.mode,
             None,
             kv_backend.clone(),
             layered_cache_registry.clone(),
+            Some(process_manager),
         );
 
         let table_metadata_manager =
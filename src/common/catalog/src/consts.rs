@@ -98,6 +98,10 @@ pub const INFORMATION_SCHEMA_CLUSTER_INFO_TABLE_ID: u32 = 31;
 pub const INFORMATION_SCHEMA_VIEW_TABLE_ID: u32 = 32;
 /// id for information_schema.FLOWS
 pub const INFORMATION_SCHEMA_FLOW_TABLE_ID: u32 = 33;
+/// id for information_schema.region_move_plans
+pub const INFORMATION_SCHEMA_REGION_MOVE_PLANS_TABLE_ID: u32 = 34;
+/// id for information_schema.process_list
+pub const INFORMATION_SCHEMA_PROCESS_LIST_TABLE_ID: u32 = 35;
 /// ----- End of information_schema tables -----
 
 /// ----- Begin of pg_catalog tables -----
@@ -22,10 +22,12 @@ use crate::function::FunctionRef;
 use crate::scalars::aggregate::{AggregateFunctionMetaRef, AggregateFunctions};
 use crate::scalars::date::DateFunction;
 use crate::scalars::expression::ExpressionFunction;
-use crate::scalars::matches::MatchesFunction;
+use crate::scalars::json::JsonFunction;
+use crate::scalars::matches::{MatchesFunction, MatchesScoreFunction};
 use crate::scalars::math::MathFunction;
 use crate::scalars::numpy::NumpyFunction;
 use crate::scalars::timestamp::TimestampFunction;
+use crate::scalars::vector::VectorFunction;
 use crate::system::SystemFunction;
 use crate::table::TableFunction;
 
@@ -89,6 +91,13 @@ pub static FUNCTION_REGISTRY: Lazy<Arc<FunctionRegistry>> = Lazy::new(|| {
 
     // Full text search function
     MatchesFunction::register(&function_registry);
+    MatchesScoreFunction::register(&function_registry);
+
+    // Json related functions
+    JsonFunction::register(&function_registry);
+
+    // Vector related functions
+    VectorFunction::register(&function_registry);
 
     // System and administration functions
     SystemFunction::register(&function_registry);
@@ -15,6 +15,7 @@
 pub mod aggregate;
 pub(crate) mod date;
 pub mod expression;
+pub(crate) mod json;
 pub mod matches;
 pub mod math;
 pub mod numpy;
@@ -22,3 +23,4 @@ pub mod numpy;
 pub(crate) mod test;
 pub(crate) mod timestamp;
 pub mod udf;
+pub mod vector;
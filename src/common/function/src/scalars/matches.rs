@@ -12,13 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
 use common_query::error::{
     GeneralDataFusionSnafu, IntoVectorSnafu, InvalidFuncArgsSnafu, InvalidInputTypeSnafu, Result,
 };
+use common_query::prelude::TypeSignature;
 use datafusion::common::tree_node::{Transformed, TreeNode, TreeNodeIterator, TreeNodeRecursion};
 use datafusion::common::{DFSchema, Result as DfResult};
 use datafusion::execution::context::SessionState;
@@ -27,17 +28,30 @@ use datafusion::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
 use datafusion::prelude::SessionConfig;
 use datatypes::arrow::array::RecordBatch;
 use datatypes::arrow::datatypes::{DataType, Field};
-use datatypes::prelude::VectorRef;
-use datatypes::vectors::BooleanVector;
+use datatypes::prelude::{MutableVector, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{FulltextAnalyzer, FulltextOptions};
+use datatypes::vectors::{BooleanVector, BooleanVectorBuilder, Float64VectorBuilder};
+use index::fulltext_index::analyzer::FulltextTokenizer;
+use index::fulltext_index::{Analyzer, Config};
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::ConcreteDataType;
 
 use crate::function::{Function, FunctionContext};
 use crate::function_registry::FunctionRegistry;
 
+pub const MATCHES_NAME: &str = "matches";
+pub const MATCHES_SCORE_NAME: &str = "matches_score";
+
+/// The `k1` parameter of BM25, which controls the term frequency saturation.
+const BM25_K1: f64 = 1.2;
+
 /// `matches` for full text search.
 ///
-/// Usage: matches(`<col>`, `<pattern>`) -> boolean
+/// Usage: matches(`<col>`, `<pattern>`[, `<fulltext options>`]) -> boolean
+///
+/// The optional third argument is the JSON of the [`FulltextOptions`] of the column,
+/// which is filled by the query planner. With it, the text is tokenized by the same
+/// analyzer as the fulltext index, otherwise the pattern is matched as substrings.
 #[derive(Clone, Debug, Default)]
 pub(crate) struct MatchesFunction;
 
@@ -53,9 +67,53 @@ impl fmt::Display for MatchesFunction {
     }
 }
 
+/// Signature of `matches(<col>, <pattern>[, <fulltext options>])`.
+fn matches_signature() -> common_query::prelude::Signature {
+    common_query::prelude::Signature::one_of(
+        vec![
+            TypeSignature::Exact(vec![
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ]),
+            TypeSignature::Exact(vec![
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ]),
+        ],
+        Volatility::Immutable,
+    )
+}
+
+/// Returns the pattern and the fulltext options from the arguments of `matches`.
+fn matches_args(columns: &[VectorRef]) -> Result<(String, Option<FulltextOptions>)> {
+    ensure!(
+        columns.len() == 2 || columns.len() == 3,
+        InvalidFuncArgsSnafu {
+            err_msg: format!(
+                "The length of the args is not correct, expect 2 or 3, have: {}",
+                columns.len()
+            ),
+        }
+    );
+    let pattern_vector = &columns[1]
+        .cast(&ConcreteDataType::string_datatype())
+        .context(InvalidInputTypeSnafu {
+            err_msg: "cannot cast `pattern` to string",
+        })?;
+    // Safety: both length and type are checked before
+    let pattern = pattern_vector.get(0).as_string().unwrap();
+
+    let options = match columns.get(2) {
+        Some(options) => MatchesFunction::parse_options(options)?,
+        None => None,
+    };
+    Ok((pattern, options))
+}
+
 impl Function for MatchesFunction {
     fn name(&self) -> &str {
-        "matches"
+        MATCHES_NAME
     }
 
     fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
@@ -63,38 +121,93 @@ impl Function for MatchesFunction {
     }
 
     fn signature(&self) -> common_query::prelude::Signature {
-        common_query::prelude::Signature::exact(
-            vec![
-                ConcreteDataType::string_datatype(),
-                ConcreteDataType::string_datatype(),
-            ],
-            Volatility::Immutable,
-        )
+        matches_signature()
     }
 
-    // TODO: read case-sensitive config
     fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
-        ensure!(
-            columns.len() == 2,
-            InvalidFuncArgsSnafu {
-                err_msg: format!(
-                    "The length of the args is not correct, expect exactly 2, have: {}",
-                    columns.len()
-                ),
+        let (pattern, options) = matches_args(columns)?;
+        match options {
+            Some(options) if options.enable => {
+                self.eval_with_analyzer(columns[0].clone(), pattern, &options)
             }
-        );
-        let pattern_vector = &columns[1]
-            .cast(&ConcreteDataType::string_datatype())
-            .context(InvalidInputTypeSnafu {
-                err_msg: "cannot cast `pattern` to string",
-            })?;
-        // Safety: both length and type are checked before
-        let pattern = pattern_vector.get(0).as_string().unwrap();
-        self.eval(columns[0].clone(), pattern)
+            _ => self.eval(columns[0].clone(), pattern),
+        }
     }
 }
 
 impl MatchesFunction {
+    fn parse_options(options: &VectorRef) -> Result<Option<FulltextOptions>> {
+        let options_vector =
+            options
+                .cast(&ConcreteDataType::string_datatype())
+                .context(InvalidInputTypeSnafu {
+                    err_msg: "cannot cast `options` to string",
+                })?;
+        let Some(options) = options_vector.get(0).as_string() else {
+            return Ok(None);
+        };
+        let options = serde_json::from_str(&options).map_err(|e| {
+            InvalidFuncArgsSnafu {
+                err_msg: format!("Invalid fulltext options {options}: {e}"),
+            }
+            .build()
+        })?;
+        Ok(Some(options))
+    }
+
+    /// Tokenizes both the pattern and the data by the analyzer of the column, a literal
+    /// in the pattern matches the text if its tokens appear in the text in order.
+    fn eval_with_analyzer(
+        &self,
+        data: VectorRef,
+        pattern: String,
+        options: &FulltextOptions,
+    ) -> Result<VectorRef> {
+        let parser_context = ParserContext::default();
+        let raw_ast = parser_context.parse_pattern(&pattern)?;
+        let ast = raw_ast.transform_ast()?;
+
+        let mut tokenizer = FulltextTokenizer::new(&Self::index_config(options));
+        let mut phrases = HashMap::new();
+        ast.collect_phrases(&mut tokenizer, &mut phrases);
+
+        let mut results = BooleanVectorBuilder::with_capacity(data.len());
+        for i in 0..data.len() {
+            let result = match data.get_ref(i).as_string() {
+                Ok(Some(text)) => {
+                    let tokens = tokenizer.tokenize(text);
+                    let tokens = tokens
+                        .iter()
+                        .map(|(position, token)| (*position, token.as_str()))
+                        .collect::<HashSet<_>>();
+                    Some(ast.matches_tokens(&phrases, &tokens))
+                }
+                _ => None,
+            };
+            results.push(result);
+        }
+
+        Ok(results.to_vector())
+    }
+
+    /// Converts the fulltext options to the config of the fulltext index.
+    fn index_config(options: &FulltextOptions) -> Config {
+        let analyzer = match options.analyzer {
+            FulltextAnalyzer::English => Analyzer::English,
+            FulltextAnalyzer::Chinese => Analyzer::Chinese,
+            FulltextAnalyzer::Standard => Analyzer::Standard,
+            FulltextAnalyzer::Whitespace => Analyzer::Whitespace,
+            FulltextAnalyzer::Ngram => Analyzer::Ngram,
+            FulltextAnalyzer::Japanese => Analyzer::Japanese,
+            FulltextAnalyzer::Korean => Analyzer::Korean,
+        };
+        Config {
+            analyzer,
+            case_sensitive: options.case_sensitive,
+            stop_words: options.stop_words.clone(),
+        }
+    }
+
     fn eval(&self, data: VectorRef, pattern: String) -> Result<VectorRef> {
         let col_name = "data";
         let parser_context = ParserContext::default();
@@ -166,7 +279,205 @@ enum BinaryOp {
     Or,
 }
 
+/// `matches_score` for ranking the results of full text search.
+///
+/// Usage: matches_score(`<col>`, `<pattern>`[, `<fulltext options>`]) -> double
+///
+/// Returns 0 if the text doesn't match the pattern, otherwise the sum of the BM25 term
+/// frequency weights of the literals in the pattern. The document frequency and length
+/// statistics of BM25 are only known by the fulltext index, so they are not involved.
+///
+/// The fulltext index ranks rows by the full BM25 score, which may disagree with this
+/// function, so `ORDER BY matches_score(...) LIMIT k` doesn't prune rows by the index.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MatchesScoreFunction;
+
+impl MatchesScoreFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(MatchesScoreFunction));
+    }
+
+    fn eval(
+        &self,
+        data: VectorRef,
+        pattern: String,
+        options: Option<&FulltextOptions>,
+    ) -> Result<VectorRef> {
+        let parser_context = ParserContext::default();
+        let raw_ast = parser_context.parse_pattern(&pattern)?;
+        let ast = raw_ast.transform_ast()?;
+
+        let config = options
+            .filter(|options| options.enable)
+            .map(MatchesFunction::index_config)
+            .unwrap_or_default();
+        let mut tokenizer = FulltextTokenizer::new(&config);
+        let mut phrases = HashMap::new();
+        ast.collect_phrases(&mut tokenizer, &mut phrases);
+
+        let mut results = Float64VectorBuilder::with_capacity(data.len());
+        for i in 0..data.len() {
+            let score = match data.get_ref(i).as_string() {
+                Ok(Some(text)) => {
+                    let tokens = tokenizer.tokenize(text);
+                    let tokens = tokens
+                        .iter()
+                        .map(|(position, token)| (*position, token.as_str()))
+                        .collect::<HashSet<_>>();
+                    if ast.matches_tokens(&phrases, &tokens) {
+                        ast.score_tokens(&phrases, &tokens)
+                    } else {
+                        0.0
+                    }
+                }
+                // Rows without text are never ranked before the matched rows.
+                _ => 0.0,
+            };
+            results.push(Some(score));
+        }
+
+        Ok(results.to_vector())
+    }
+}
+
+impl fmt::Display for MatchesScoreFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MATCHES_SCORE")
+    }
+}
+
+impl Function for MatchesScoreFunction {
+    fn name(&self) -> &str {
+        MATCHES_SCORE_NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn signature(&self) -> common_query::prelude::Signature {
+        matches_signature()
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        let (pattern, options) = matches_args(columns)?;
+        self.eval(columns[0].clone(), pattern, options.as_ref())
+    }
+}
+
+/// Tokens of a phrase with their positions.
+type Phrase = Vec<(usize, String)>;
+
 impl PatternAst {
+    /// Tokenizes the patterns of all literals in this AST.
+    fn collect_phrases(
+        &self,
+        tokenizer: &mut FulltextTokenizer,
+        phrases: &mut HashMap<String, Phrase>,
+    ) {
+        match self {
+            PatternAst::Literal { pattern, .. } => {
+                if !phrases.contains_key(pattern) {
+                    phrases.insert(pattern.clone(), tokenizer.tokenize(pattern));
+                }
+            }
+            PatternAst::Binary { children, .. } => {
+                for child in children {
+                    child.collect_phrases(tokenizer, phrases);
+                }
+            }
+            PatternAst::Group { child, .. } => child.collect_phrases(tokenizer, phrases),
+        }
+    }
+
+    /// Evaluates this AST against the tokens of a text. The literals are looked up in
+    /// `phrases` collected by [`PatternAst::collect_phrases`].
+    fn matches_tokens(
+        &self,
+        phrases: &HashMap<String, Phrase>,
+        tokens: &HashSet<(usize, &str)>,
+    ) -> bool {
+        match self {
+            PatternAst::Literal { op, pattern } => {
+                // Safety: phrases of all literals are collected before.
+                let matched = Self::contains_phrase(&phrases[pattern], tokens);
+                match op {
+                    UnaryOp::Must | UnaryOp::Optional => matched,
+                    UnaryOp::Negative => !matched,
+                }
+            }
+            PatternAst::Binary { op, children } => match op {
+                BinaryOp::And => children
+                    .iter()
+                    .all(|child| child.matches_tokens(phrases, tokens)),
+                BinaryOp::Or => {
+                    children.is_empty()
+                        || children
+                            .iter()
+                            .any(|child| child.matches_tokens(phrases, tokens))
+                }
+            },
+            PatternAst::Group { op, child } => {
+                let matched = child.matches_tokens(phrases, tokens);
+                match op {
+                    UnaryOp::Must | UnaryOp::Optional => matched,
+                    UnaryOp::Negative => !matched,
+                }
+            }
+        }
+    }
+
+    /// Sums up the BM25 term frequency weights of the literals that aren't negative.
+    fn score_tokens(
+        &self,
+        phrases: &HashMap<String, Phrase>,
+        tokens: &HashSet<(usize, &str)>,
+    ) -> f64 {
+        match self {
+            PatternAst::Literal { op, pattern } => match op {
+                UnaryOp::Must | UnaryOp::Optional => {
+                    // Safety: phrases of all literals are collected before.
+                    let freq = Self::count_phrase(&phrases[pattern], tokens) as f64;
+                    freq * (BM25_K1 + 1.0) / (freq + BM25_K1)
+                }
+                UnaryOp::Negative => 0.0,
+            },
+            PatternAst::Binary { children, .. } => children
+                .iter()
+                .map(|child| child.score_tokens(phrases, tokens))
+                .sum(),
+            PatternAst::Group { op, child } => match op {
+                UnaryOp::Must | UnaryOp::Optional => child.score_tokens(phrases, tokens),
+                UnaryOp::Negative => 0.0,
+            },
+        }
+    }
+
+    /// Returns whether the tokens of `phrase` appear in `tokens` with the same relative
+    /// positions. An empty phrase, e.g. consisting of stop words only, matches nothing
+    /// as in the fulltext index.
+    fn contains_phrase(phrase: &Phrase, tokens: &HashSet<(usize, &str)>) -> bool {
+        Self::count_phrase(phrase, tokens) > 0
+    }
+
+    /// Returns the number of occurrences of `phrase` in `tokens`.
+    fn count_phrase(phrase: &Phrase, tokens: &HashSet<(usize, &str)>) -> usize {
+        let Some((first_position, first_token)) = phrase.first() else {
+            return 0;
+        };
+        tokens
+            .iter()
+            .filter(|(start, token)| {
+                *token == first_token.as_str()
+                    && phrase.iter().all(|(position, token)| {
+                        (position - first_position)
+                            .checked_add(*start)
+                            .is_some_and(|position| tokens.contains(&(position, token.as_str())))
+                    })
+            })
+            .count()
+    }
+
     fn into_like_expr(self, column: &str) -> Expr {
         match self {
             PatternAst::Literal { op, pattern } => {
@@ -852,7 +1163,7 @@ impl Tokenizer {
 
 #[cfg(test)]
 mod test {
-    use datatypes::vectors::StringVector;
+    use datatypes::vectors::{Float64Vector, StringVector};
 
     use super::*;
 
@@ -1340,4 +1651,102 @@ mod test {
             assert_eq!(expected, actual, "{pattern}");
         }
     }
+
+    #[test]
+    fn matches_with_analyzer() {
+        let input_vector: VectorRef = Arc::new(StringVector::from(vec![
+            "GET /api/v1/users from 10.0.0.1",
+            "POST /API/v1/users/1 from 10.0.0.2",
+            "ConnectionRefused by 10.0.0.1",
+            "the quick brown fox",
+        ]));
+        let cases = [
+            (
+                FulltextOptions {
+                    enable: true,
+                    analyzer: FulltextAnalyzer::Whitespace,
+                    ..Default::default()
+                },
+                vec![
+                    ("/api/v1/users", vec![true, false, false, false]),
+                    ("10.0.0.1 -get", vec![false, false, true, false]),
+                    ("api", vec![false, false, false, false]),
+                ],
+            ),
+            (
+                FulltextOptions {
+                    enable: true,
+                    analyzer: FulltextAnalyzer::Standard,
+                    case_sensitive: true,
+                    ..Default::default()
+                },
+                vec![
+                    ("api", vec![true, false, false, false]),
+                    ("\"10.0.0.1\"", vec![true, false, true, false]),
+                    ("\"v1 users 1\"", vec![false, true, false, false]),
+                ],
+            ),
+            (
+                FulltextOptions {
+                    enable: true,
+                    analyzer: FulltextAnalyzer::Ngram,
+                    ..Default::default()
+                },
+                vec![
+                    ("refused", vec![false, false, true, false]),
+                    ("user", vec![true, true, false, false]),
+                ],
+            ),
+            (
+                FulltextOptions {
+                    enable: true,
+                    stop_words: vec!["_english_".to_string()],
+                    ..Default::default()
+                },
+                vec![
+                    ("\"the quick\"", vec![false, false, false, true]),
+                    ("\"quick fox\"", vec![false, false, false, false]),
+                    ("the", vec![false, false, false, false]),
+                ],
+            ),
+        ];
+
+        let f = MatchesFunction;
+        for (options, patterns) in cases {
+            for (pattern, expected) in patterns {
+                let actual = f
+                    .eval_with_analyzer(input_vector.clone(), pattern.to_string(), &options)
+                    .unwrap();
+                let expected: VectorRef = Arc::new(BooleanVector::from(expected)) as _;
+                assert_eq!(expected, actual, "{pattern}");
+            }
+        }
+    }
+
+    #[test]
+    fn matches_score() {
+        let input_vector: VectorRef = Arc::new(StringVector::from(vec![
+            Some("error: disk error"),
+            Some("error: timeout"),
+            Some("warn: disk is full"),
+            None,
+        ]));
+        let one = (BM25_K1 + 1.0) / (1.0 + BM25_K1);
+        let two = 2.0 * (BM25_K1 + 1.0) / (2.0 + BM25_K1);
+        let cases = [
+            ("error", vec![two, one, 0.0, 0.0]),
+            ("error disk", vec![two + one, one, one, 0.0]),
+            ("+error -timeout", vec![two, 0.0, 0.0, 0.0]),
+            ("\"disk error\"", vec![one, 0.0, 0.0, 0.0]),
+        ];
+
+        let f = MatchesScoreFunction;
+        for (pattern, expected) in cases {
+            let actual = f
+                .eval(input_vector.clone(), pattern.to_string(), None)
+                .unwrap();
+            let expected: VectorRef = Arc::new(Float64Vector::from_vec(expected)) as _;
+            assert_eq!(expected, actual, "{pattern}");
+        }
+    }
 }
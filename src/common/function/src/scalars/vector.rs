@@ -0,0 +1,112 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod distance;
+
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use datatypes::types::binlit_as_veclit;
+use datatypes::value::ValueRef;
+use distance::{CosDistanceFunction, DotProductFunction, L2SqDistanceFunction};
+pub use distance::{VEC_COS_DISTANCE_NAME, VEC_DOT_PRODUCT_NAME, VEC_L2SQ_DISTANCE_NAME};
+
+use crate::function_registry::FunctionRegistry;
+
+pub(crate) struct VectorFunction;
+
+impl VectorFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(L2SqDistanceFunction));
+        registry.register(Arc::new(CosDistanceFunction));
+        registry.register(Arc::new(DotProductFunction));
+    }
+}
+
+/// Converts an argument of vector functions to the elements of the vector.
+///
+/// The argument can be either a vector value (stored as binary) or a string
+/// literal like `[1.0, 2.0, 3.0]`.
+fn as_veclit(arg: ValueRef<'_>) -> Result<Option<Vec<f32>>> {
+    match arg {
+        ValueRef::Binary(bytes) => binlit_as_veclit(bytes).map(Some).map_err(|e| {
+            InvalidFuncArgsSnafu {
+                err_msg: e.to_string(),
+            }
+            .build()
+        }),
+        ValueRef::String(s) => parse_veclit_from_strlit(s).map(Some),
+        ValueRef::Null => Ok(None),
+        _ => InvalidFuncArgsSnafu {
+            err_msg: format!("Unsupported vector argument: {arg:?}"),
+        }
+        .fail(),
+    }
+}
+
+/// Parses a string literal like `[1.0, 2.0, 3.0]` to the elements of the vector.
+fn parse_veclit_from_strlit(s: &str) -> Result<Vec<f32>> {
+    let content = s
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| {
+            InvalidFuncArgsSnafu {
+                err_msg: format!("Failed to parse {s} to vector, expect the form of [e1, e2, ...]"),
+            }
+            .build()
+        })?
+        .trim();
+    if content.is_empty() {
+        return Ok(vec![]);
+    }
+
+    content
+        .split(',')
+        .map(|e| {
+            e.trim().parse::<f32>().map_err(|_| {
+                InvalidFuncArgsSnafu {
+                    err_msg: format!("Failed to parse {e} in {s} to float"),
+                }
+                .build()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_veclit() {
+        let bytes = datatypes::types::veclit_to_binlit(&[1.0, 2.0]);
+        assert_eq!(
+            vec![1.0, 2.0],
+            as_veclit(ValueRef::Binary(&bytes)).unwrap().unwrap()
+        );
+        assert_eq!(
+            vec![1.0, 2.0],
+            as_veclit(ValueRef::String("[1.0, 2]")).unwrap().unwrap()
+        );
+        assert!(as_veclit(ValueRef::String("[]"))
+            .unwrap()
+            .unwrap()
+            .is_empty());
+        assert!(as_veclit(ValueRef::Null).unwrap().is_none());
+        assert!(as_veclit(ValueRef::String("1.0, 2.0")).is_err());
+        assert!(as_veclit(ValueRef::String("[1.0, a]")).is_err());
+        assert!(as_veclit(ValueRef::Int64(1)).is_err());
+    }
+}
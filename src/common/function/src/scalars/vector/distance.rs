@@ -0,0 +1,267 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display};
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use datatypes::data_type::ConcreteDataType;
+use datatypes::prelude::{MutableVector, ScalarVectorBuilder, VectorRef};
+use datatypes::vectors::Float32VectorBuilder;
+use snafu::ensure;
+
+use crate::function::{Function, FunctionContext};
+use crate::scalars::vector::as_veclit;
+
+pub const VEC_L2SQ_DISTANCE_NAME: &str = "vec_l2sq_distance";
+pub const VEC_COS_DISTANCE_NAME: &str = "vec_cos_distance";
+pub const VEC_DOT_PRODUCT_NAME: &str = "vec_dot_product";
+
+/// Squared euclidean distance of two vectors.
+fn l2sq(lhs: &[f32], rhs: &[f32]) -> f32 {
+    lhs.iter().zip(rhs).map(|(l, r)| (l - r) * (l - r)).sum()
+}
+
+/// Cosine distance of two vectors, i.e. `1 - cosine similarity`.
+///
+/// The distance to a zero vector is 1, as the cosine similarity is undefined.
+fn cos(lhs: &[f32], rhs: &[f32]) -> f32 {
+    let dot = dot(lhs, rhs);
+    let norm = (dot_self(lhs) * dot_self(rhs)).sqrt();
+    if norm == 0.0 {
+        1.0
+    } else {
+        1.0 - dot / norm
+    }
+}
+
+/// Dot product of two vectors.
+fn dot(lhs: &[f32], rhs: &[f32]) -> f32 {
+    lhs.iter().zip(rhs).map(|(l, r)| l * r).sum()
+}
+
+fn dot_self(v: &[f32]) -> f32 {
+    dot(v, v)
+}
+
+/// Evaluates `f` on each pair of vectors in `columns`.
+fn eval_distance(
+    name: &str,
+    columns: &[VectorRef],
+    f: impl Fn(&[f32], &[f32]) -> f32,
+) -> Result<VectorRef> {
+    ensure!(
+        columns.len() == 2,
+        InvalidFuncArgsSnafu {
+            err_msg: format!(
+                "The length of the args is not correct, expect exactly two, have: {}",
+                columns.len()
+            ),
+        }
+    );
+    let lhs = &columns[0];
+    let rhs = &columns[1];
+    ensure!(
+        lhs.len() == rhs.len(),
+        InvalidFuncArgsSnafu {
+            err_msg: format!(
+                "The lengths of the vector are not aligned, args 0: {}, args 1: {}",
+                lhs.len(),
+                rhs.len()
+            ),
+        }
+    );
+
+    // Avoids parsing constant vectors (usually the query vector) for each row.
+    let lhs_const = if lhs.is_const() && !lhs.is_empty() {
+        Some(as_veclit(lhs.get_ref(0))?)
+    } else {
+        None
+    };
+    let rhs_const = if rhs.is_const() && !rhs.is_empty() {
+        Some(as_veclit(rhs.get_ref(0))?)
+    } else {
+        None
+    };
+
+    let size = lhs.len();
+    let mut results = Float32VectorBuilder::with_capacity(size);
+    for i in 0..size {
+        let l = match &lhs_const {
+            Some(v) => v.clone(),
+            None => as_veclit(lhs.get_ref(i))?,
+        };
+        let r = match &rhs_const {
+            Some(v) => v.clone(),
+            None => as_veclit(rhs.get_ref(i))?,
+        };
+        let (Some(l), Some(r)) = (l, r) else {
+            results.push_null();
+            continue;
+        };
+        ensure!(
+            l.len() == r.len(),
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The dimensions of the vectors of {name} are not equal, left: {}, right: {}",
+                    l.len(),
+                    r.len()
+                ),
+            }
+        );
+        results.push(Some(f(&l, &r)));
+    }
+
+    Ok(results.to_vector())
+}
+
+/// Arguments of vector functions can be vector values (binary) or string literals.
+fn vector_args_signature() -> Signature {
+    let types = [
+        ConcreteDataType::binary_datatype(),
+        ConcreteDataType::string_datatype(),
+    ];
+    let signatures = types
+        .iter()
+        .flat_map(|l| {
+            types
+                .iter()
+                .map(|r| TypeSignature::Exact(vec![l.clone(), r.clone()]))
+        })
+        .collect();
+    Signature::one_of(signatures, Volatility::Immutable)
+}
+
+macro_rules! define_distance_function {
+    ($func: ident, $name: expr, $display: expr, $f: expr) => {
+        #[derive(Clone, Debug, Default)]
+        pub struct $func;
+
+        impl Function for $func {
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+                Ok(ConcreteDataType::float32_datatype())
+            }
+
+            fn signature(&self) -> Signature {
+                vector_args_signature()
+            }
+
+            fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+                eval_distance($name, columns, $f)
+            }
+        }
+
+        impl Display for $func {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, $display)
+            }
+        }
+    };
+}
+
+define_distance_function!(
+    L2SqDistanceFunction,
+    VEC_L2SQ_DISTANCE_NAME,
+    "VEC_L2SQ_DISTANCE",
+    l2sq
+);
+define_distance_function!(
+    CosDistanceFunction,
+    VEC_COS_DISTANCE_NAME,
+    "VEC_COS_DISTANCE",
+    cos
+);
+define_distance_function!(
+    DotProductFunction,
+    VEC_DOT_PRODUCT_NAME,
+    "VEC_DOT_PRODUCT",
+    dot
+);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datatypes::types::veclit_to_binlit;
+    use datatypes::vectors::{BinaryVector, ConstantVector, StringVector};
+
+    use super::*;
+
+    fn assert_f32_eq(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-6,
+            "expected: {expected}, actual: {actual}"
+        );
+    }
+
+    #[test]
+    fn test_distance_kernels() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [4.0, 5.0, 6.0];
+        assert_f32_eq(27.0, l2sq(&a, &b));
+        assert_f32_eq(32.0, dot(&a, &b));
+        assert_f32_eq(1.0 - 32.0 / (14.0f32 * 77.0).sqrt(), cos(&a, &b));
+        assert_f32_eq(0.0, cos(&a, &a));
+        assert_f32_eq(1.0, cos(&a, &[0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_distance_functions() {
+        let vectors: VectorRef = Arc::new(BinaryVector::from(vec![
+            Some(veclit_to_binlit(&[1.0, 2.0, 3.0])),
+            None,
+            Some(veclit_to_binlit(&[0.0, 0.0, 1.0])),
+        ]));
+        let query: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec!["[1.0, 2.0, 4.0]"])),
+            3,
+        ));
+
+        let args = [vectors.clone(), query.clone()];
+        let result = L2SqDistanceFunction
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_eq!(3, result.len());
+        assert_f32_eq(1.0, result.get_ref(0).as_f32().unwrap().unwrap());
+        assert!(result.is_null(1));
+        assert_f32_eq(13.0, result.get_ref(2).as_f32().unwrap().unwrap());
+
+        let result = DotProductFunction
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_f32_eq(17.0, result.get_ref(0).as_f32().unwrap().unwrap());
+        assert_f32_eq(4.0, result.get_ref(2).as_f32().unwrap().unwrap());
+
+        let result = CosDistanceFunction
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_f32_eq(
+            1.0 - 17.0 / (14.0f32 * 21.0).sqrt(),
+            result.get_ref(0).as_f32().unwrap().unwrap(),
+        );
+
+        // dimension mismatch
+        let query: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec!["[1.0, 2.0]"])),
+            3,
+        ));
+        assert!(L2SqDistanceFunction
+            .eval(FunctionContext::default(), &[vectors, query])
+            .is_err());
+    }
+}
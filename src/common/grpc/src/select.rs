@@ -132,6 +132,18 @@ pub fn values(arrays: &[VectorRef]) -> Result<Values> {
             binary_values,
             |x| { x.into() }
         ),
+        (
+            ConcreteDataType::Json(_),
+            BinaryVector,
+            binary_values,
+            |x| { x.into() }
+        ),
+        (
+            ConcreteDataType::Vector(_),
+            BinaryVector,
+            binary_values,
+            |x| { x.into() }
+        ),
         (
             ConcreteDataType::String(_),
             StringVector,
@@ -28,6 +28,7 @@ use crate::peer::Peer;
 use crate::ClusterId;
 
 const CLUSTER_NODE_INFO_PREFIX: &str = "__meta_cluster_node_info";
+const CLUSTER_REGION_MOVE_PREFIX: &str = "__meta_cluster_region_move";
 
 lazy_static! {
     static ref CLUSTER_NODE_INFO_PREFIX_PATTERN: Regex = Regex::new(&format!(
@@ -47,6 +48,9 @@ pub trait ClusterInfo {
         role: Option<Role>,
     ) -> std::result::Result<Vec<NodeInfo>, Self::Error>;
 
+    /// List region moves planned by the region balancer of metasrv.
+    async fn list_region_moves(&self) -> std::result::Result<Vec<RegionMove>, Self::Error>;
+
     // TODO(jeremy): Other info, like region status, etc.
 }
 
@@ -237,6 +241,86 @@ impl TryFrom<NodeInfo> for Vec<u8> {
     }
 }
 
+/// The key of [RegionMove] in the storage. The format is `__meta_cluster_region_move-{cluster_id}-{region_id}`.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub struct RegionMoveKey {
+    /// The cluster id.
+    pub cluster_id: ClusterId,
+    /// The id of the region to move.
+    pub region_id: u64,
+}
+
+impl RegionMoveKey {
+    pub fn key_prefix_with_cluster_id(cluster_id: ClusterId) -> String {
+        format!("{}-{}-", CLUSTER_REGION_MOVE_PREFIX, cluster_id)
+    }
+}
+
+impl From<RegionMoveKey> for Vec<u8> {
+    fn from(key: RegionMoveKey) -> Self {
+        format!(
+            "{}-{}-{}",
+            CLUSTER_REGION_MOVE_PREFIX, key.cluster_id, key.region_id
+        )
+        .into_bytes()
+    }
+}
+
+/// A region move planned by the region balancer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionMove {
+    /// The id of the region to move.
+    pub region_id: u64,
+    /// The datanode the region moves from.
+    pub from_peer_id: u64,
+    /// The datanode the region moves to.
+    pub to_peer_id: u64,
+    /// Why the region is moved.
+    pub reason: String,
+    /// The status of the move.
+    pub status: RegionMoveStatus,
+    /// The id of the region migration procedure if the move is submitted.
+    pub procedure_id: Option<String>,
+    /// The time the move is planned in milliseconds.
+    pub plan_time_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegionMoveStatus {
+    /// The move is planned but not executed because the balancer runs in dry-run mode.
+    DryRun,
+    /// The region migration procedure of the move is submitted.
+    Submitted,
+    /// Failed to submit the region migration procedure.
+    Failed,
+}
+
+impl RegionMoveStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegionMoveStatus::DryRun => "DRY_RUN",
+            RegionMoveStatus::Submitted => "SUBMITTED",
+            RegionMoveStatus::Failed => "FAILED",
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for RegionMove {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self> {
+        serde_json::from_slice(&bytes).context(DecodeJsonSnafu)
+    }
+}
+
+impl TryFrom<RegionMove> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(region_move: RegionMove) -> Result<Self> {
+        serde_json::to_vec(&region_move).context(EncodeJsonSnafu)
+    }
+}
+
 impl From<Role> for i32 {
     fn from(role: Role) -> Self {
         match role {
@@ -267,7 +351,10 @@ mod tests {
     use std::assert_matches::assert_matches;
 
     use crate::cluster::Role::{Datanode, Frontend};
-    use crate::cluster::{DatanodeStatus, NodeInfo, NodeInfoKey, NodeStatus};
+    use crate::cluster::{
+        DatanodeStatus, NodeInfo, NodeInfoKey, NodeStatus, RegionMove, RegionMoveKey,
+        RegionMoveStatus,
+    };
     use crate::peer::Peer;
 
     #[test]
@@ -333,4 +420,31 @@ mod tests {
         let prefix = NodeInfoKey::key_prefix_with_role(2, Frontend);
         assert_eq!(prefix, "__meta_cluster_node_info-2-1-");
     }
+
+    #[test]
+    fn test_region_move_round_trip() {
+        let key = RegionMoveKey {
+            cluster_id: 1,
+            region_id: 4398046511104,
+        };
+        let key_bytes: Vec<u8> = key.into();
+        assert_eq!(
+            b"__meta_cluster_region_move-1-4398046511104".to_vec(),
+            key_bytes
+        );
+        assert!(key_bytes.starts_with(RegionMoveKey::key_prefix_with_cluster_id(1).as_bytes()));
+
+        let region_move = RegionMove {
+            region_id: 4398046511104,
+            from_peer_id: 1,
+            to_peer_id: 2,
+            reason: "load 2.00 > 1.20".to_string(),
+            status: RegionMoveStatus::Submitted,
+            procedure_id: Some("procedure".to_string()),
+            plan_time_ms: 123,
+        };
+        let value: Vec<u8> = region_move.clone().try_into().unwrap();
+        let new_region_move: RegionMove = value.try_into().unwrap();
+        assert_eq!(region_move, new_region_move);
+    }
 }
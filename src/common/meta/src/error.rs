@@ -406,6 +406,13 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Invalid user info, err: {}", err_msg))]
+    InvalidUserInfo {
+        err_msg: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Failed to get kv cache, err: {}", err_msg))]
     GetKvCache { err_msg: String },
 
@@ -742,6 +749,7 @@ impl ErrorExt for Error {
 
             ProcedureNotFound { .. }
             | InvalidViewInfo { .. }
+            | InvalidUserInfo { .. }
             | PrimaryKeyNotFound { .. }
             | CatalogAlreadyExists { .. }
             | EmptyKey { .. }
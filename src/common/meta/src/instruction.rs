@@ -155,6 +155,51 @@ pub struct UpgradeRegion {
     pub wait_for_replay_timeout: Option<Duration>,
 }
 
+/// Moves data of source regions into target regions on the same datanode.
+///
+/// Without `cutover`, target regions are (re)created with the same metadata and rows of
+/// source regions are written into the target region whose partition expression matches,
+/// while source regions stay writable and writes to them are replayed on target regions.
+///
+/// With `cutover`, source regions are set to read-only and the rest of writes to them are
+/// replayed on target regions.
+#[serde_with::serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepartitionRegions {
+    /// Regions to read data from.
+    pub from_regions: Vec<RegionId>,
+    /// Regions to write data to.
+    pub to_regions: Vec<RegionId>,
+    /// The partition columns of the table.
+    pub partition_columns: Vec<String>,
+    /// The json encoded partition expressions of `to_regions`.
+    pub partition_exprs: Vec<String>,
+    pub region_storage_path: String,
+    pub region_options: HashMap<String, String>,
+    #[serde(default)]
+    #[serde_as(as = "HashMap<serde_with::DisplayFromStr, _>")]
+    pub region_wal_options: HashMap<RegionNumber, String>,
+    /// Whether to stop writing to source regions.
+    #[serde(default)]
+    pub cutover: bool,
+    /// The timeout of waiting for the data to be moved.
+    ///
+    /// `None` stands for no wait,
+    /// it's helpful to verify whether the data has been moved.
+    #[serde(with = "humantime_serde", default)]
+    pub wait_for_move_timeout: Option<Duration>,
+}
+
+impl Display for RepartitionRegions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RepartitionRegions(from_regions={:?}, to_regions={:?}, cutover={})",
+            self.from_regions, self.to_regions, self.cutover
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 /// The identifier of cache.
 pub enum CacheIdent {
@@ -195,6 +240,8 @@ pub enum Instruction {
     DowngradeRegion(DowngradeRegion),
     /// Invalidates batch cache.
     InvalidateCaches(Vec<CacheIdent>),
+    /// Moves data of regions into new regions.
+    RepartitionRegions(RepartitionRegions),
 }
 
 /// The reply of [UpgradeRegion].
@@ -218,6 +265,21 @@ impl Display for UpgradeRegionReply {
     }
 }
 
+/// The reply of [RepartitionRegions].
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct RepartitionRegionsReply {
+    /// Returns true if the data of source regions has been moved.
+    pub ready: bool,
+    /// Returns error if any.
+    pub error: Option<String>,
+}
+
+impl Display for RepartitionRegionsReply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(ready={}, error={:?})", self.ready, self.error)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum InstructionReply {
@@ -225,6 +287,7 @@ pub enum InstructionReply {
     CloseRegion(SimpleReply),
     UpgradeRegion(UpgradeRegionReply),
     DowngradeRegion(DowngradeRegionReply),
+    RepartitionRegions(RepartitionRegionsReply),
 }
 
 impl Display for InstructionReply {
@@ -236,6 +299,9 @@ impl Display for InstructionReply {
             Self::DowngradeRegion(reply) => {
                 write!(f, "InstructionReply::DowngradeRegion({})", reply)
             }
+            Self::RepartitionRegions(reply) => {
+                write!(f, "InstructionReply::RepartitionRegions({})", reply)
+            }
         }
     }
 }
@@ -55,6 +55,11 @@
 //!     - The value is a [ViewInfoValue] struct; it contains the encoded logical plan.
 //!     - This key is mainly used in constructing the view in Datanode and Frontend.
 //!
+//! 12. User key: `__user/{username}`
+//!     - The value is a [UserValue](crate::key::user::UserValue) struct; it contains the
+//!       password hash and the privileges of the user.
+//!     - This key is mainly used in authenticating and authorizing users in Frontend.
+//!
 //! All keys have related managers. The managers take care of the serialization and deserialization
 //! of keys and values, and the interaction with the underlying KV store backend.
 //!
@@ -98,6 +103,7 @@ pub mod table_route;
 pub mod test_utils;
 mod tombstone;
 pub(crate) mod txn_helper;
+pub mod user;
 pub mod view_info;
 
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -123,6 +129,7 @@ use table::metadata::{RawTableInfo, TableId};
 use table::table_name::TableName;
 use table_info::{TableInfoKey, TableInfoManager, TableInfoValue};
 use table_name::{TableNameKey, TableNameManager, TableNameValue};
+use user::UserValue;
 use view_info::{ViewInfoKey, ViewInfoManager, ViewInfoValue};
 
 use self::catalog_name::{CatalogManager, CatalogNameKey, CatalogNameValue};
@@ -152,6 +159,7 @@ pub const TABLE_NAME_KEY_PREFIX: &str = "__table_name";
 pub const CATALOG_NAME_KEY_PREFIX: &str = "__catalog_name";
 pub const SCHEMA_NAME_KEY_PREFIX: &str = "__schema_name";
 pub const TABLE_ROUTE_PREFIX: &str = "__table_route";
+pub const USER_KEY_PREFIX: &str = "__user";
 
 pub const CACHE_KEY_PREFIXES: [&str; 4] = [
     TABLE_NAME_KEY_PREFIX,
@@ -177,6 +185,10 @@ lazy_static! {
         Regex::new(&format!("^{VIEW_INFO_KEY_PREFIX}/([0-9]+)$")).unwrap();
 }
 
+lazy_static! {
+    static ref USER_KEY_PATTERN: Regex = Regex::new(&format!("^{USER_KEY_PREFIX}/(.+)$")).unwrap();
+}
+
 lazy_static! {
     static ref TABLE_ROUTE_KEY_PATTERN: Regex =
         Regex::new(&format!("^{TABLE_ROUTE_PREFIX}/([0-9]+)$")).unwrap();
@@ -1230,7 +1242,8 @@ impl_table_meta_value! {
     FlowInfoValue,
     FlowNameValue,
     FlowRouteValue,
-    TableFlowValue
+    TableFlowValue,
+    UserValue
 }
 
 impl_optional_meta_value! {
@@ -0,0 +1,366 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::error::{InvalidUserInfoSnafu, Result};
+use crate::key::{
+    DeserializedValueWithBytes, MetaKey, TableMetaValue, USER_KEY_PATTERN, USER_KEY_PREFIX,
+};
+use crate::kv_backend::KvBackendRef;
+use crate::rpc::store::{CompareAndPutRequest, RangeRequest};
+
+/// The key stores the password and privileges of a user.
+///
+/// The layout: `__user/{username}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UserKey<'a> {
+    pub username: &'a str,
+}
+
+impl<'a> UserKey<'a> {
+    pub fn new(username: &'a str) -> Self {
+        Self { username }
+    }
+}
+
+impl Display for UserKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", USER_KEY_PREFIX, self.username)
+    }
+}
+
+impl<'a> MetaKey<'a, UserKey<'a>> for UserKey<'_> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    fn from_bytes(bytes: &'a [u8]) -> Result<UserKey<'a>> {
+        let key = std::str::from_utf8(bytes).map_err(|e| {
+            InvalidUserInfoSnafu {
+                err_msg: format!(
+                    "UserKey '{}' is not a valid UTF8 string: {e}",
+                    String::from_utf8_lossy(bytes)
+                ),
+            }
+            .build()
+        })?;
+        let captures = USER_KEY_PATTERN
+            .captures(key)
+            .context(InvalidUserInfoSnafu {
+                err_msg: format!("Invalid UserKey '{key}'"),
+            })?;
+        // Safety: pass the regex check above
+        Ok(UserKey {
+            username: captures.get(1).unwrap().as_str(),
+        })
+    }
+}
+
+/// The privilege that can be granted to users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Privilege {
+    /// Allows reading data and metadata.
+    Read,
+    /// Allows writing data and altering metadata.
+    Write,
+}
+
+/// The object that privileges are granted on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GrantObject {
+    /// All databases.
+    Global,
+    /// A database and all tables in it.
+    Database { catalog: String, schema: String },
+    /// A table.
+    Table {
+        catalog: String,
+        schema: String,
+        table: String,
+    },
+}
+
+impl GrantObject {
+    /// Returns true if the privileges on this object also apply to the `table`
+    /// (or the database itself if `table` is `None`) in the database `catalog.schema`.
+    fn covers(&self, catalog: &str, schema: &str, table: Option<&str>) -> bool {
+        match self {
+            GrantObject::Global => true,
+            GrantObject::Database {
+                catalog: c,
+                schema: s,
+            } => c == catalog && s == schema,
+            GrantObject::Table {
+                catalog: c,
+                schema: s,
+                table: t,
+            } => c == catalog && s == schema && Some(t.as_str()) == table,
+        }
+    }
+
+    /// Returns true if this object is the database `catalog.schema` or something in it.
+    fn within(&self, catalog: &str, schema: &str) -> bool {
+        match self {
+            GrantObject::Global => true,
+            GrantObject::Database {
+                catalog: c,
+                schema: s,
+            }
+            | GrantObject::Table {
+                catalog: c,
+                schema: s,
+                ..
+            } => c == catalog && s == schema,
+        }
+    }
+}
+
+/// A privilege granted on an object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grant {
+    pub object: GrantObject,
+    pub privilege: Privilege,
+}
+
+/// The user value that keeps the password hash and the privileges of a user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserValue {
+    // The hex encoded password hash.
+    password_hash: String,
+    pub grants: Vec<Grant>,
+}
+
+impl UserValue {
+    /// Returns a new `[UserValue]` without any privileges.
+    pub fn new(password_hash: &[u8]) -> Self {
+        Self {
+            password_hash: hex::encode(password_hash),
+            grants: vec![],
+        }
+    }
+
+    pub fn password_hash(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.password_hash).map_err(|e| {
+            InvalidUserInfoSnafu {
+                err_msg: format!("Invalid password hash: {e}"),
+            }
+            .build()
+        })
+    }
+
+    pub fn set_password_hash(&mut self, password_hash: &[u8]) {
+        self.password_hash = hex::encode(password_hash);
+    }
+
+    /// Grants the `privileges` on the `object`, ignores the privileges already granted.
+    pub fn grant(&mut self, object: &GrantObject, privileges: &[Privilege]) {
+        for privilege in privileges {
+            let grant = Grant {
+                object: object.clone(),
+                privilege: *privilege,
+            };
+            if !self.grants.contains(&grant) {
+                self.grants.push(grant);
+            }
+        }
+    }
+
+    /// Revokes the `privileges` on the `object`.
+    ///
+    /// Only the privileges granted on exactly the same object are revoked, e.g. revoking
+    /// privileges on a table doesn't affect the privileges on its database.
+    pub fn revoke(&mut self, object: &GrantObject, privileges: &[Privilege]) {
+        self.grants
+            .retain(|grant| &grant.object != object || !privileges.contains(&grant.privilege));
+    }
+
+    /// Returns true if the user has the `privilege` on the `table` in `catalog.schema`,
+    /// or on the database itself if `table` is `None`.
+    pub fn has_privilege(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: Option<&str>,
+        privilege: Privilege,
+    ) -> bool {
+        self.grants.iter().any(|grant| {
+            grant.privilege == privilege && grant.object.covers(catalog, schema, table)
+        })
+    }
+
+    /// Returns true if the user has any privilege on the database `catalog.schema`
+    /// or any table in it.
+    pub fn has_any_privilege(&self, catalog: &str, schema: &str) -> bool {
+        self.grants
+            .iter()
+            .any(|grant| grant.object.within(catalog, schema))
+    }
+}
+
+/// The `[UserValue]` manager.
+///
+/// Reads are done by range requests, which are never cached by the kv backend of frontends,
+/// so that users altered in one frontend are visible to the others immediately.
+pub struct UserManager {
+    kv_backend: KvBackendRef,
+}
+
+pub type UserManagerRef = Arc<UserManager>;
+
+impl UserManager {
+    pub fn new(kv_backend: KvBackendRef) -> Self {
+        Self { kv_backend }
+    }
+
+    /// Creates the user, returns false if the user already exists.
+    pub async fn create(&self, username: &str, value: &UserValue) -> Result<bool> {
+        let raw_key = UserKey::new(username).to_bytes();
+        self.kv_backend
+            .put_conditionally(raw_key, value.try_as_raw_value()?, true)
+            .await
+    }
+
+    /// Gets the `[UserValue]` by the username.
+    pub async fn get(
+        &self,
+        username: &str,
+    ) -> Result<Option<DeserializedValueWithBytes<UserValue>>> {
+        let raw_key = UserKey::new(username).to_bytes();
+        let resp = self
+            .kv_backend
+            .range(RangeRequest::new().with_key(raw_key))
+            .await?;
+        resp.kvs
+            .first()
+            .map(|kv| DeserializedValueWithBytes::from_inner_slice(&kv.value))
+            .transpose()
+    }
+
+    /// Updates the user if the remote value equals the `current_value`,
+    /// returns false if the comparing failed.
+    pub async fn update(
+        &self,
+        username: &str,
+        current_value: &DeserializedValueWithBytes<UserValue>,
+        new_value: &UserValue,
+    ) -> Result<bool> {
+        let req = CompareAndPutRequest::new()
+            .with_key(UserKey::new(username).to_bytes())
+            .with_expect(current_value.get_raw_bytes())
+            .with_value(new_value.try_as_raw_value()?);
+        let resp = self.kv_backend.compare_and_put(req).await?;
+        Ok(resp.success)
+    }
+
+    /// Deletes the user, returns false if the user doesn't exist.
+    pub async fn delete(&self, username: &str) -> Result<bool> {
+        let raw_key = UserKey::new(username).to_bytes();
+        let prev = self.kv_backend.delete(&raw_key, true).await?;
+        Ok(prev.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kv_backend::memory::MemoryKvBackend;
+
+    #[test]
+    fn test_key_serialization() {
+        let key = UserKey::new("alice");
+        assert_eq!(b"__user/alice".to_vec(), key.to_bytes());
+
+        let parsed = UserKey::from_bytes(b"__user/alice").unwrap();
+        assert_eq!(key, parsed);
+
+        assert!(UserKey::from_bytes(b"__user/").is_err());
+    }
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let mut value = UserValue::new(b"hash");
+        let database = GrantObject::Database {
+            catalog: "greptime".to_string(),
+            schema: "public".to_string(),
+        };
+        let table = GrantObject::Table {
+            catalog: "greptime".to_string(),
+            schema: "db1".to_string(),
+            table: "t1".to_string(),
+        };
+
+        value.grant(&database, &[Privilege::Read]);
+        value.grant(&database, &[Privilege::Read, Privilege::Write]);
+        value.grant(&table, &[Privilege::Read]);
+        assert_eq!(3, value.grants.len());
+
+        assert!(value.has_privilege("greptime", "public", None, Privilege::Write));
+        assert!(value.has_privilege("greptime", "public", Some("t2"), Privilege::Read));
+        assert!(!value.has_privilege("greptime", "db1", None, Privilege::Read));
+        assert!(value.has_privilege("greptime", "db1", Some("t1"), Privilege::Read));
+        assert!(!value.has_privilege("greptime", "db1", Some("t1"), Privilege::Write));
+        assert!(!value.has_privilege("greptime", "db1", Some("t2"), Privilege::Read));
+        assert!(value.has_any_privilege("greptime", "db1"));
+        assert!(!value.has_any_privilege("greptime", "db2"));
+
+        value.revoke(&database, &[Privilege::Write]);
+        assert!(!value.has_privilege("greptime", "public", None, Privilege::Write));
+        assert!(value.has_privilege("greptime", "public", None, Privilege::Read));
+        // Revoking privileges on the database doesn't affect the privileges on tables.
+        value.revoke(
+            &GrantObject::Database {
+                catalog: "greptime".to_string(),
+                schema: "db1".to_string(),
+            },
+            &[Privilege::Read],
+        );
+        assert!(value.has_privilege("greptime", "db1", Some("t1"), Privilege::Read));
+
+        value.grant(&GrantObject::Global, &[Privilege::Write]);
+        assert!(value.has_privilege("greptime", "db2", None, Privilege::Write));
+        assert!(!value.has_privilege("greptime", "db2", None, Privilege::Read));
+    }
+
+    #[tokio::test]
+    async fn test_user_manager() {
+        let manager = UserManager::new(Arc::new(MemoryKvBackend::default()));
+        assert!(manager.get("alice").await.unwrap().is_none());
+
+        let value = UserValue::new(b"hash");
+        assert!(manager.create("alice", &value).await.unwrap());
+        assert!(!manager.create("alice", &value).await.unwrap());
+
+        let current = manager.get("alice").await.unwrap().unwrap();
+        assert_eq!(b"hash".to_vec(), current.password_hash().unwrap());
+
+        let mut new_value = current.get_inner_ref().clone();
+        new_value.grant(&GrantObject::Global, &[Privilege::Read]);
+        assert!(manager.update("alice", &current, &new_value).await.unwrap());
+        // The value has been changed.
+        assert!(!manager.update("alice", &current, &new_value).await.unwrap());
+        assert_eq!(
+            new_value,
+            manager.get("alice").await.unwrap().unwrap().into_inner()
+        );
+
+        assert!(manager.delete("alice").await.unwrap());
+        assert!(!manager.delete("alice").await.unwrap());
+        assert!(manager.get("alice").await.unwrap().is_none());
+    }
+}
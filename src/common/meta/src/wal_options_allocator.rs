@@ -32,6 +32,7 @@ pub enum WalOptionsAllocator {
     #[default]
     RaftEngine,
     Kafka(KafkaTopicManager),
+    ObjectStore,
 }
 
 /// Arc wrapper of WalOptionsAllocator.
@@ -45,6 +46,7 @@ impl WalOptionsAllocator {
             MetasrvWalConfig::Kafka(kafka_config) => {
                 Self::Kafka(KafkaTopicManager::new(kafka_config, kv_backend))
             }
+            MetasrvWalConfig::ObjectStore => Self::ObjectStore,
         }
     }
 
@@ -53,6 +55,7 @@ impl WalOptionsAllocator {
         match self {
             Self::RaftEngine => Ok(()),
             Self::Kafka(kafka_topic_manager) => kafka_topic_manager.start().await,
+            Self::ObjectStore => Ok(()),
         }
     }
 
@@ -66,6 +69,7 @@ impl WalOptionsAllocator {
                     topic: topic.clone(),
                 }))
             }
+            Self::ObjectStore => Ok(WalOptions::ObjectStore),
         }
     }
 
@@ -85,12 +89,16 @@ impl WalOptionsAllocator {
                     .collect();
                 Ok(options_batch)
             }
+            WalOptionsAllocator::ObjectStore => Ok(vec![WalOptions::ObjectStore; num_regions]),
         }
     }
 
     /// Returns true if it's the remote WAL.
     pub fn is_remote_wal(&self) -> bool {
-        matches!(&self, WalOptionsAllocator::Kafka(_))
+        matches!(
+            &self,
+            WalOptionsAllocator::Kafka(_) | WalOptionsAllocator::ObjectStore
+        )
     }
 }
 
@@ -151,6 +159,26 @@ mod tests {
         assert_eq!(got, expected);
     }
 
+    // Tests that the wal options allocator could successfully allocate object store wal options.
+    #[tokio::test]
+    async fn test_allocator_with_object_store() {
+        let kv_backend = Arc::new(MemoryKvBackend::new()) as KvBackendRef;
+        let allocator = WalOptionsAllocator::new(MetasrvWalConfig::ObjectStore, kv_backend);
+        allocator.start().await.unwrap();
+        assert!(allocator.is_remote_wal());
+
+        let num_regions = 32;
+        let regions = (0..num_regions).collect::<Vec<_>>();
+        let got = allocate_region_wal_options(regions.clone(), &allocator).unwrap();
+
+        let encoded_wal_options = serde_json::to_string(&WalOptions::ObjectStore).unwrap();
+        let expected = regions
+            .into_iter()
+            .zip(vec![encoded_wal_options; num_regions as usize])
+            .collect();
+        assert_eq!(got, expected);
+    }
+
     // Tests that the wal options allocator could successfully allocate Kafka wal options.
     #[tokio::test]
     async fn test_allocator_with_kafka() {
@@ -148,6 +148,13 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Stream is cancelled: {reason}"))]
+    StreamCancelled {
+        reason: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Cannot construct an empty stream"))]
     EmptyStream {
         #[snafu(implicit)]
@@ -186,6 +193,8 @@ impl ErrorExt for Error {
 
             Error::UnsupportedOperation { .. } => StatusCode::Unsupported,
 
+            Error::StreamCancelled { .. } => StatusCode::Cancelled,
+
             Error::SchemaConversion { source, .. } | Error::CastVector { source, .. } => {
                 source.status_code()
             }
@@ -13,11 +13,13 @@
 // limitations under the License.
 
 pub mod kafka;
+pub mod object_store;
 pub mod raft_engine;
 
 use serde::{Deserialize, Serialize};
 
 use crate::config::kafka::{DatanodeKafkaConfig, MetasrvKafkaConfig};
+use crate::config::object_store::ObjectStoreWalConfig;
 use crate::config::raft_engine::RaftEngineConfig;
 
 /// Wal configurations for metasrv.
@@ -28,6 +30,7 @@ pub enum MetasrvWalConfig {
     #[default]
     RaftEngine,
     Kafka(MetasrvKafkaConfig),
+    ObjectStore,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -37,6 +40,7 @@ pub enum MetasrvWalConfig {
 pub enum DatanodeWalConfig {
     RaftEngine(RaftEngineConfig),
     Kafka(DatanodeKafkaConfig),
+    ObjectStore(ObjectStoreWalConfig),
 }
 
 impl Default for DatanodeWalConfig {
@@ -54,6 +58,7 @@ impl From<DatanodeWalConfig> for MetasrvWalConfig {
                 backoff: config.backoff,
                 kafka_topic: config.kafka_topic,
             }),
+            DatanodeWalConfig::ObjectStore(_) => Self::ObjectStore,
         }
     }
 }
@@ -68,6 +73,7 @@ impl From<MetasrvWalConfig> for DatanodeWalConfig {
                 kafka_topic: config.kafka_topic,
                 ..Default::default()
             }),
+            MetasrvWalConfig::ObjectStore => Self::ObjectStore(ObjectStoreWalConfig::default()),
         }
     }
 }
@@ -228,4 +234,34 @@ mod tests {
         };
         assert_eq!(datanode_wal_config, DatanodeWalConfig::Kafka(expected));
     }
+
+    #[test]
+    fn test_toml_object_store() {
+        let toml_str = r#"
+            provider = "object_store"
+            segment_dir = "wal/"
+            max_group_bytes = "8MB"
+        "#;
+        let metasrv_wal_config: MetasrvWalConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(metasrv_wal_config, MetasrvWalConfig::ObjectStore);
+
+        let datanode_wal_config: DatanodeWalConfig = toml::from_str(toml_str).unwrap();
+        let expected = ObjectStoreWalConfig {
+            segment_dir: "wal/".to_string(),
+            max_group_bytes: ReadableSize::mb(8),
+        };
+        assert_eq!(
+            datanode_wal_config,
+            DatanodeWalConfig::ObjectStore(expected)
+        );
+
+        let toml_str = r#"
+            provider = "object_store"
+        "#;
+        let datanode_wal_config: DatanodeWalConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            datanode_wal_config,
+            DatanodeWalConfig::ObjectStore(ObjectStoreWalConfig::default())
+        );
+    }
 }
@@ -0,0 +1,35 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::readable_size::ReadableSize;
+use serde::{Deserialize, Serialize};
+
+/// Configurations for the wal that stores segments in the object store.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ObjectStoreWalConfig {
+    /// The directory of wal segments, relative to the root of the object store.
+    pub segment_dir: String,
+    /// The max size of entries committed in a group.
+    pub max_group_bytes: ReadableSize,
+}
+
+impl Default for ObjectStoreWalConfig {
+    fn default() -> Self {
+        Self {
+            segment_dir: "__wal/".to_string(),
+            max_group_bytes: ReadableSize::mb(4),
+        }
+    }
+}
@@ -28,12 +28,14 @@ use common_meta::wal_options_allocator::prepare_wal_options;
 pub use common_procedure::options::ProcedureConfig;
 use common_telemetry::{error, info, warn};
 use common_wal::config::kafka::DatanodeKafkaConfig;
+use common_wal::config::object_store::ObjectStoreWalConfig;
 use common_wal::config::raft_engine::RaftEngineConfig;
 use common_wal::config::DatanodeWalConfig;
 use file_engine::engine::FileRegionEngine;
 use futures_util::TryStreamExt;
 use log_store::kafka::log_store::KafkaLogStore;
 use log_store::kafka::{default_index_file, GlobalIndexCollector};
+use log_store::object_store::ObjectStoreLogStore;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use meta_client::MetaClientRef;
 use metric_engine::engine::MetricEngine;
@@ -431,6 +433,22 @@ impl DatanodeBuilder {
                 .await
                 .context(BuildMitoEngineSnafu)?
             }
+            DatanodeWalConfig::ObjectStore(object_store_config) => {
+                // Writes the WAL to the default object store without the read cache, so that
+                // any datanode sharing the object store can replay it.
+                let object_store =
+                    new_object_store_without_cache(&opts.storage.store, &opts.storage.data_home)
+                        .await?;
+                MitoEngine::new(
+                    &opts.storage.data_home,
+                    config,
+                    Self::build_object_store_log_store(object_store_config, object_store),
+                    object_store_manager,
+                    plugins,
+                )
+                .await
+                .context(BuildMitoEngineSnafu)?
+            }
         };
         Ok(mito_engine)
     }
@@ -474,6 +492,15 @@ impl DatanodeBuilder {
             .map(Arc::new)
     }
 
+    /// Builds [`ObjectStoreLogStore`].
+    fn build_object_store_log_store(
+        config: &ObjectStoreWalConfig,
+        object_store: object_store::ObjectStore,
+    ) -> Arc<ObjectStoreLogStore> {
+        info!("Creating object store logstore with config: {:?}", config);
+        Arc::new(ObjectStoreLogStore::new(config, object_store))
+    }
+
     /// Builds [`GlobalIndexCollector`]
     fn build_global_index_collector(
         dump_index_interval: Duration,
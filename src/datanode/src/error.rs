@@ -388,6 +388,46 @@ pub enum Error {
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to read region {}", region_id))]
+    ReadRegion {
+        region_id: RegionId,
+        #[snafu(implicit)]
+        location: Location,
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Failed to decode partition expr: {}", expr))]
+    DecodePartitionExpr {
+        expr: String,
+        #[snafu(source)]
+        error: serde_json::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to route row to target region"))]
+    RouteRow {
+        #[snafu(implicit)]
+        location: Location,
+        source: partition::error::Error,
+    },
+
+    #[snafu(display("Failed to convert bulk inserts of region {}", region_id))]
+    ConvertBulkInserts {
+        region_id: RegionId,
+        #[snafu(implicit)]
+        location: Location,
+        source: datatypes::error::Error,
+    },
+
+    #[snafu(display("Failed to convert data type of column {}", column))]
+    ConvertColumnDataType {
+        column: String,
+        #[snafu(implicit)]
+        location: Location,
+        source: api::error::Error,
+    },
+
     #[snafu(display("DataFusion"))]
     DataFusion {
         #[snafu(source)]
@@ -448,6 +488,7 @@ impl ErrorExt for Error {
             | RegionEngineNotFound { .. }
             | ParseAddr { .. }
             | MissingKvBackend { .. }
+            | DecodePartitionExpr { .. }
             | TomlFormat { .. } => StatusCode::InvalidArguments,
 
             PayloadNotExist { .. } | Unexpected { .. } | WatchAsyncTaskChange { .. } => {
@@ -483,6 +524,10 @@ impl ErrorExt for Error {
             StopRegionEngine { source, .. } => source.status_code(),
 
             FindLogicalRegions { source, .. } => source.status_code(),
+            ReadRegion { source, .. } => source.status_code(),
+            RouteRow { source, .. } => source.status_code(),
+            ConvertBulkInserts { source, .. } => source.status_code(),
+            ConvertColumnDataType { source, .. } => source.status_code(),
             BuildMitoEngine { source, .. } => source.status_code(),
         }
     }
@@ -27,6 +27,7 @@ use store_api::storage::RegionId;
 mod close_region;
 mod downgrade_region;
 mod open_region;
+mod repartition_regions;
 mod upgrade_region;
 
 use super::task_tracker::TaskTracker;
@@ -37,6 +38,7 @@ use crate::region_server::RegionServer;
 pub struct RegionHeartbeatResponseHandler {
     region_server: RegionServer,
     catchup_tasks: TaskTracker<()>,
+    repartition_tasks: TaskTracker<()>,
 }
 
 /// Handler of the instruction.
@@ -47,6 +49,7 @@ pub type InstructionHandler =
 pub struct HandlerContext {
     region_server: RegionServer,
     catchup_tasks: TaskTracker<()>,
+    repartition_tasks: TaskTracker<()>,
 }
 
 impl HandlerContext {
@@ -61,6 +64,7 @@ impl RegionHeartbeatResponseHandler {
         Self {
             region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         }
     }
 
@@ -81,6 +85,11 @@ impl RegionHeartbeatResponseHandler {
             Instruction::UpgradeRegion(upgrade_region) => Ok(Box::new(move |handler_context| {
                 handler_context.handle_upgrade_region_instruction(upgrade_region)
             })),
+            Instruction::RepartitionRegions(repartition_regions) => {
+                Ok(Box::new(move |handler_context| {
+                    handler_context.handle_repartition_regions_instruction(repartition_regions)
+                }))
+            }
             Instruction::InvalidateCaches(_) => InvalidHeartbeatResponseSnafu.fail(),
         }
     }
@@ -95,6 +104,7 @@ impl HeartbeatResponseHandler for RegionHeartbeatResponseHandler {
                 | Some((_, Instruction::CloseRegion { .. }))
                 | Some((_, Instruction::DowngradeRegion { .. }))
                 | Some((_, Instruction::UpgradeRegion { .. }))
+                | Some((_, Instruction::RepartitionRegions { .. }))
         )
     }
 
@@ -107,11 +117,13 @@ impl HeartbeatResponseHandler for RegionHeartbeatResponseHandler {
         let mailbox = ctx.mailbox.clone();
         let region_server = self.region_server.clone();
         let catchup_tasks = self.catchup_tasks.clone();
+        let repartition_tasks = self.repartition_tasks.clone();
         let handler = Self::build_handler(instruction)?;
         let _handle = common_runtime::spawn_global(async move {
             let reply = handler(HandlerContext {
                 region_server,
                 catchup_tasks,
+                repartition_tasks,
             })
             .await;
 
@@ -0,0 +1,574 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use api::helper::{pb_value_to_value_ref, value_to_grpc_value, ColumnDataTypeWrapper};
+use api::v1::column_def::options_from_column_schema;
+use api::v1::{ColumnSchema, Row, Rows};
+use common_meta::instruction::{InstructionReply, RepartitionRegions, RepartitionRegionsReply};
+use common_meta::wal_options_allocator::prepare_wal_options;
+use common_telemetry::{error, info};
+use datatypes::schema::Schema;
+use datatypes::value::Value;
+use datatypes::vectors::{Helper, VectorRef};
+use futures_util::future::BoxFuture;
+use futures_util::TryStreamExt;
+use partition::expr::PartitionExpr;
+use partition::repartition::RepartitionRouter;
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::metadata::RegionMetadataRef;
+use store_api::path_utils::region_dir;
+use store_api::region_engine::{RegionEngineRef, SetReadonlyResponse};
+use store_api::region_request::{
+    RegionCreateRequest, RegionDeleteRequest, RegionDropRequest, RegionPutRequest, RegionRequest,
+};
+use store_api::storage::{RegionId, RegionNumber, ScanRequest, TableId};
+
+use crate::error::{
+    ConvertBulkInsertsSnafu, ConvertColumnDataTypeSnafu, DecodePartitionExprSnafu, Error,
+    GetRegionMetadataSnafu, HandleRegionRequestSnafu, ReadRegionSnafu, RegionNotFoundSnafu,
+    RegionNotReadySnafu, Result, RouteRowSnafu, UnexpectedSnafu,
+};
+use crate::heartbeat::handler::HandlerContext;
+use crate::heartbeat::task_tracker::WaitResult;
+use crate::region_server::{CapturedWrite, WriteCapture, WriteCaptureRef};
+
+/// Stops replaying captured writes before the cutover once fewer writes than this are
+/// captured during a round of replaying.
+const CAUGHT_UP_WRITES: usize = 64;
+
+/// Target regions and the router of rows to them.
+struct Targets {
+    table_id: TableId,
+    to_regions: Vec<RegionId>,
+    router: RepartitionRouter,
+}
+
+impl HandlerContext {
+    pub(crate) fn handle_repartition_regions_instruction(
+        self,
+        repartition: RepartitionRegions,
+    ) -> BoxFuture<'static, InstructionReply> {
+        Box::pin(async move {
+            info!("Handling {repartition}");
+            let result = if repartition.cutover {
+                self.cutover(repartition).await.map(|_| true)
+            } else {
+                self.copy_region_data(repartition).await
+            };
+
+            match result {
+                Ok(ready) => InstructionReply::RepartitionRegions(RepartitionRegionsReply {
+                    ready,
+                    error: None,
+                }),
+                Err(e) => {
+                    error!(e; "Failed to repartition regions");
+                    InstructionReply::RepartitionRegions(RepartitionRegionsReply {
+                        ready: false,
+                        error: Some(format!("{e:?}")),
+                    })
+                }
+            }
+        })
+    }
+
+    /// Copies data of `from_regions` into `to_regions` in the background, returns true
+    /// once the data is copied.
+    ///
+    /// The copying task is keyed by the first source region, so a retried instruction
+    /// waits for the running task instead of starting another one.
+    async fn copy_region_data(&self, repartition: RepartitionRegions) -> Result<bool> {
+        validate(&repartition)?;
+        if self.is_copied(&repartition).await {
+            return Ok(true);
+        }
+
+        let region_id = repartition.from_regions[0];
+        let wait_for_move_timeout = repartition.wait_for_move_timeout;
+        let handler = self.clone();
+        let register_result = self
+            .repartition_tasks
+            .try_register(
+                region_id,
+                Box::pin(async move { handler.move_region_data(repartition).await }),
+            )
+            .await;
+        if register_result.is_busy() {
+            info!("Another repartition task is running for the region: {region_id}");
+        }
+
+        // Returns immediately
+        let Some(wait_for_move_timeout) = wait_for_move_timeout else {
+            return Ok(false);
+        };
+
+        // We don't care that it returns a newly registered or running task.
+        let mut watcher = register_result.into_watcher();
+        match self
+            .repartition_tasks
+            .wait(&mut watcher, wait_for_move_timeout)
+            .await
+        {
+            WaitResult::Timeout => Ok(false),
+            WaitResult::Finish(result) => result.map(|_| true),
+        }
+    }
+
+    /// Returns true if data of all source regions have been copied into target regions
+    /// and writes to them are still captured.
+    async fn is_copied(&self, repartition: &RepartitionRegions) -> bool {
+        for region_id in &repartition.from_regions {
+            let Some(capture) = self.region_server.write_capture(*region_id) else {
+                return false;
+            };
+            if capture.to_regions() != repartition.to_regions || !capture.lock().await.is_copied() {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Moves data of `from_regions` into `to_regions` while source regions stay writable.
+    ///
+    /// Writes to source regions are captured before scanning rows of source regions and
+    /// writing each row to the target region its partition expr routes it to. Captured
+    /// writes are replayed on target regions after that, and the rest of them are replayed
+    /// in the [cutover](Self::cutover).
+    ///
+    /// Writes are only blocked for a moment to wait for in-flight writes that are not
+    /// captured. Handing off SST files instead of copying rows would avoid copying at all,
+    /// but the engine can't split SST files by partition exprs yet.
+    async fn move_region_data(
+        &self,
+        RepartitionRegions {
+            from_regions,
+            to_regions,
+            partition_columns,
+            partition_exprs,
+            region_storage_path,
+            region_options,
+            region_wal_options,
+            ..
+        }: RepartitionRegions,
+    ) -> Result<()> {
+        let (engine, metadata) = self.source_region_metadata(from_regions[0]).await?;
+
+        // Target regions may contain data written by a previous attempt, recreates them
+        // so the instruction can be retried.
+        for region_id in &to_regions {
+            if self.region_server.is_writable(*region_id).is_some() {
+                self.region_server
+                    .handle_request(*region_id, RegionRequest::Drop(RegionDropRequest {}))
+                    .await?;
+            }
+            let mut options = region_options.clone();
+            prepare_wal_options(&mut options, *region_id, &region_wal_options);
+            let request = RegionRequest::Create(RegionCreateRequest {
+                engine: engine.name().to_string(),
+                column_metadatas: metadata.column_metadatas.clone(),
+                primary_key: metadata.primary_key.clone(),
+                options,
+                region_dir: region_dir(&region_storage_path, *region_id),
+            });
+            self.region_server
+                .handle_request(*region_id, request)
+                .await?;
+        }
+
+        let targets = Targets::try_new(to_regions, partition_columns, &partition_exprs)?;
+
+        let mut captures = Vec::with_capacity(from_regions.len());
+        for region_id in &from_regions {
+            let capture = Arc::new(WriteCapture::new(targets.to_regions.clone()));
+            self.region_server
+                .register_write_capture(*region_id, capture.clone());
+            self.wait_for_inflight_writes(*region_id).await?;
+            captures.push(capture);
+        }
+
+        for region_id in &from_regions {
+            self.copy_rows(&engine, &metadata, *region_id, &targets)
+                .await?;
+        }
+
+        for (region_id, capture) in from_regions.iter().zip(captures) {
+            self.catch_up(&metadata, *region_id, &capture, &targets)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops writing to source regions and replays the rest of captured writes on target
+    /// regions.
+    ///
+    /// Writes to source regions are rejected from now on, until routes of source regions
+    /// are replaced by routes of target regions.
+    async fn cutover(&self, repartition: RepartitionRegions) -> Result<()> {
+        validate(&repartition)?;
+        let RepartitionRegions {
+            from_regions,
+            to_regions,
+            partition_columns,
+            partition_exprs,
+            ..
+        } = repartition;
+        let (_, metadata) = self.source_region_metadata(from_regions[0]).await?;
+        let targets = Targets::try_new(to_regions, partition_columns, &partition_exprs)?;
+
+        for region_id in from_regions {
+            let capture = self
+                .region_server
+                .write_capture(region_id)
+                .filter(|capture| capture.to_regions() == targets.to_regions)
+                .with_context(|| UnexpectedSnafu {
+                    violated: format!("Writes to region {region_id} are not captured"),
+                })?;
+
+            // Holds the lock so no write is applied after taking the rest of writes.
+            let mut state = capture.lock().await;
+            if state.is_finished() {
+                continue;
+            }
+            ensure!(
+                state.is_copied(),
+                UnexpectedSnafu {
+                    violated: format!("Data of region {region_id} is not copied"),
+                }
+            );
+
+            if let SetReadonlyResponse::NotFound = self
+                .region_server
+                .set_readonly_gracefully(region_id)
+                .await?
+            {
+                return RegionNotFoundSnafu { region_id }.fail();
+            }
+            let writes = state
+                .take()
+                .map_err(|reason| captured_writes_lost(region_id, reason))?;
+            for write in writes {
+                self.replay_write(&metadata, region_id, write, &targets)
+                    .await?;
+            }
+            state.set_finished();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the engine and the metadata of the source region.
+    async fn source_region_metadata(
+        &self,
+        region_id: RegionId,
+    ) -> Result<(RegionEngineRef, RegionMetadataRef)> {
+        let engine = self
+            .region_server
+            .find_engine(region_id)?
+            .context(RegionNotReadySnafu { region_id })?;
+        let metadata =
+            engine
+                .get_metadata(region_id)
+                .await
+                .with_context(|_| GetRegionMetadataSnafu {
+                    engine: engine.name(),
+                    region_id,
+                })?;
+
+        Ok((engine, metadata))
+    }
+
+    /// Waits for in-flight writes to the region, which are not captured, by setting
+    /// the region read-only and then writable again.
+    async fn wait_for_inflight_writes(&self, region_id: RegionId) -> Result<()> {
+        if let SetReadonlyResponse::NotFound = self
+            .region_server
+            .set_readonly_gracefully(region_id)
+            .await?
+        {
+            return RegionNotFoundSnafu { region_id }.fail();
+        }
+        self.region_server.set_writable(region_id, true)
+    }
+
+    /// Replays captured writes on target regions until few writes are captured during
+    /// a round of replaying, then marks the data of the region as copied.
+    async fn catch_up(
+        &self,
+        metadata: &RegionMetadataRef,
+        region_id: RegionId,
+        capture: &WriteCaptureRef,
+        targets: &Targets,
+    ) -> Result<()> {
+        loop {
+            let writes = capture
+                .lock()
+                .await
+                .take()
+                .map_err(|reason| captured_writes_lost(region_id, reason))?;
+            let caught_up = writes.len() < CAUGHT_UP_WRITES;
+            for write in writes {
+                self.replay_write(metadata, region_id, write, targets)
+                    .await?;
+            }
+            if caught_up {
+                break;
+            }
+        }
+
+        capture.lock().await.set_copied();
+        Ok(())
+    }
+
+    /// Replays a write to the source region on target regions.
+    async fn replay_write(
+        &self,
+        metadata: &RegionMetadataRef,
+        region_id: RegionId,
+        write: CapturedWrite,
+        targets: &Targets,
+    ) -> Result<()> {
+        match write {
+            CapturedWrite::Put(rows) => self.write_rows(metadata, rows, false, targets).await,
+            CapturedWrite::Delete(rows) => self.write_rows(metadata, rows, true, targets).await,
+            CapturedWrite::BulkInserts(batch) => {
+                let schema = Schema::try_from(batch.schema())
+                    .context(ConvertBulkInsertsSnafu { region_id })?;
+                let columns = Helper::try_into_vectors(batch.columns())
+                    .context(ConvertBulkInsertsSnafu { region_id })?;
+                let rows = Rows {
+                    schema: rows_schema(metadata, region_id, &schema)?,
+                    rows: to_rows(&columns, batch.num_rows()),
+                };
+                self.write_rows(metadata, rows, false, targets).await
+            }
+        }
+    }
+
+    /// Reads all rows of the source region and writes them into target regions.
+    async fn copy_rows(
+        &self,
+        engine: &RegionEngineRef,
+        metadata: &RegionMetadataRef,
+        region_id: RegionId,
+        targets: &Targets,
+    ) -> Result<()> {
+        let scanner = engine
+            .handle_query(region_id, ScanRequest::default())
+            .await
+            .context(HandleRegionRequestSnafu { region_id })?;
+        let schema = rows_schema(metadata, region_id, &scanner.schema())?;
+
+        for partition in 0..scanner.properties().num_partitions() {
+            let mut stream = scanner
+                .scan_partition(partition)
+                .context(HandleRegionRequestSnafu { region_id })?;
+            while let Some(batch) = stream
+                .try_next()
+                .await
+                .context(ReadRegionSnafu { region_id })?
+            {
+                let rows = Rows {
+                    schema: schema.clone(),
+                    rows: to_rows(batch.columns(), batch.num_rows()),
+                };
+                self.write_rows(metadata, rows, false, targets).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes each row to the target region its partition expr routes it to.
+    ///
+    /// Partition columns absent from rows are routed by their default values, except that
+    /// deletes are sent to all target regions since deleting absent rows is harmless.
+    async fn write_rows(
+        &self,
+        metadata: &RegionMetadataRef,
+        rows: Rows,
+        is_delete: bool,
+        targets: &Targets,
+    ) -> Result<()> {
+        let Rows { schema, rows } = rows;
+        let partition_columns = targets
+            .router
+            .partition_columns()
+            .iter()
+            .map(|name| {
+                let index = schema.iter().position(|column| &column.column_name == name);
+                let default = metadata
+                    .column_by_name(name)
+                    .and_then(|column| column.column_schema.create_default().ok().flatten())
+                    .unwrap_or(Value::Null);
+                (index, default)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows_by_region: HashMap<RegionNumber, Vec<Row>> = HashMap::new();
+        if is_delete && partition_columns.iter().any(|(index, _)| index.is_none()) {
+            for region_id in &targets.to_regions {
+                rows_by_region.insert(region_id.region_number(), rows.clone());
+            }
+        } else {
+            for row in rows {
+                let values = partition_columns
+                    .iter()
+                    .map(|(index, default)| match index {
+                        Some(index) => Value::from(pb_value_to_value_ref(
+                            &row.values[*index],
+                            &schema[*index].datatype_extension,
+                        )),
+                        None => default.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let target = targets
+                    .router
+                    .find_region(&values)
+                    .context(RouteRowSnafu)?
+                    .with_context(|| UnexpectedSnafu {
+                        violated: format!(
+                            "No target region for row {:?} of table {}",
+                            values, targets.table_id
+                        ),
+                    })?;
+                rows_by_region.entry(target).or_default().push(row);
+            }
+        }
+
+        for (region_number, rows) in rows_by_region {
+            let target = RegionId::new(targets.table_id, region_number);
+            let rows = Rows {
+                schema: schema.clone(),
+                rows,
+            };
+            let request = if is_delete {
+                RegionRequest::Delete(RegionDeleteRequest { rows })
+            } else {
+                RegionRequest::Put(RegionPutRequest { rows })
+            };
+            self.region_server.handle_request(target, request).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Targets {
+    fn try_new(
+        to_regions: Vec<RegionId>,
+        partition_columns: Vec<String>,
+        partition_exprs: &[String],
+    ) -> Result<Self> {
+        let exprs = partition_exprs
+            .iter()
+            .map(|expr| {
+                serde_json::from_str::<PartitionExpr>(expr)
+                    .context(DecodePartitionExprSnafu { expr })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let router = RepartitionRouter::new(
+            partition_columns,
+            to_regions.iter().map(|id| id.region_number()).collect(),
+            exprs,
+        );
+
+        Ok(Self {
+            table_id: to_regions[0].table_id(),
+            to_regions,
+            router,
+        })
+    }
+}
+
+fn validate(repartition: &RepartitionRegions) -> Result<()> {
+    let RepartitionRegions {
+        from_regions,
+        to_regions,
+        partition_exprs,
+        ..
+    } = repartition;
+    ensure!(
+        !from_regions.is_empty()
+            && !to_regions.is_empty()
+            && to_regions.len() == partition_exprs.len(),
+        UnexpectedSnafu {
+            violated: format!(
+                "Invalid repartition instruction, from: {:?}, to: {:?}, exprs: {}",
+                from_regions,
+                to_regions,
+                partition_exprs.len()
+            ),
+        }
+    );
+
+    Ok(())
+}
+
+fn captured_writes_lost(region_id: RegionId, reason: String) -> Error {
+    UnexpectedSnafu {
+        violated: format!("Captured writes to region {region_id} are lost, reason: {reason}"),
+    }
+    .build()
+}
+
+/// Returns the schema of rows with columns in `schema`.
+fn rows_schema(
+    metadata: &RegionMetadataRef,
+    region_id: RegionId,
+    schema: &Schema,
+) -> Result<Vec<ColumnSchema>> {
+    schema
+        .column_schemas()
+        .iter()
+        .map(|column_schema| {
+            let column = metadata
+                .column_by_name(&column_schema.name)
+                .with_context(|| UnexpectedSnafu {
+                    violated: format!(
+                        "Column {} not found in region {}",
+                        column_schema.name, region_id
+                    ),
+                })?;
+            let (datatype, datatype_extension) =
+                ColumnDataTypeWrapper::try_from(column_schema.data_type.clone())
+                    .context(ConvertColumnDataTypeSnafu {
+                        column: &column_schema.name,
+                    })?
+                    .to_parts();
+            Ok(ColumnSchema {
+                column_name: column_schema.name.clone(),
+                datatype: datatype as i32,
+                semantic_type: column.semantic_type as i32,
+                datatype_extension,
+                options: options_from_column_schema(column_schema),
+            })
+        })
+        .collect()
+}
+
+fn to_rows(columns: &[VectorRef], num_rows: usize) -> Vec<Row> {
+    (0..num_rows)
+        .map(|row| Row {
+            values: columns
+                .iter()
+                .map(|column| value_to_grpc_value(column.get(row)))
+                .collect(),
+        })
+        .collect()
+}
@@ -139,6 +139,7 @@ mod tests {
         let handler_context = HandlerContext {
             region_server: mock_region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         };
 
         let region_id = RegionId::new(1024, 1);
@@ -180,6 +181,7 @@ mod tests {
         let handler_context = HandlerContext {
             region_server: mock_region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         };
 
         let waits = vec![None, Some(Duration::from_millis(100u64))];
@@ -221,6 +223,7 @@ mod tests {
         let handler_context = HandlerContext {
             region_server: mock_region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         };
 
         let waits = vec![None, Some(Duration::from_millis(100u64))];
@@ -267,6 +270,7 @@ mod tests {
         let handler_context = HandlerContext {
             region_server: mock_region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         };
 
         for wait_for_replay_timeout in waits {
@@ -329,6 +333,7 @@ mod tests {
         let handler_context = HandlerContext {
             region_server: mock_region_server,
             catchup_tasks: TaskTracker::new(),
+            repartition_tasks: TaskTracker::new(),
         };
 
         let reply = handler_context
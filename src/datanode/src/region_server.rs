@@ -20,11 +20,12 @@ use std::sync::{Arc, RwLock};
 use api::region::RegionResponse;
 use api::v1::region::{region_request, RegionResponse as RegionResponseV1};
 use api::v1::{ResponseHeader, Status};
-use arrow_flight::{FlightData, Ticket};
+use arrow_flight::{FlightData, FlightDescriptor, PutResult, Ticket};
 use async_trait::async_trait;
 use bytes::Bytes;
 use common_error::ext::BoxedError;
 use common_error::status_code::StatusCode;
+use common_grpc::flight::{FlightDecoder, FlightEncoder, FlightMessage};
 use common_query::request::QueryRequest;
 use common_query::OutputData;
 use common_recordbatch::SendableRecordBatchStream;
@@ -55,10 +56,10 @@ use store_api::metric_engine_consts::{
 };
 use store_api::region_engine::{RegionEngineRef, RegionRole, SetReadonlyResponse};
 use store_api::region_request::{
-    AffectedRows, RegionCloseRequest, RegionOpenRequest, RegionRequest,
+    AffectedRows, RegionBulkInsertsRequest, RegionCloseRequest, RegionOpenRequest, RegionRequest,
 };
 use store_api::storage::RegionId;
-use tonic::{Request, Response, Result as TonicResult};
+use tonic::{Request, Response, Result as TonicResult, Status as TonicStatus, Streaming};
 
 use crate::error::{
     self, BuildRegionRequestsSnafu, DataFusionSnafu, DecodeLogicalPlanSnafu,
@@ -68,6 +69,10 @@ use crate::error::{
 };
 use crate::event_listener::RegionServerEventListenerRef;
 
+mod write_capture;
+
+pub(crate) use write_capture::{CapturedWrite, WriteCapture, WriteCaptureRef};
+
 #[derive(Clone)]
 pub struct RegionServer {
     inner: Arc<RegionServerInner>,
@@ -286,6 +291,19 @@ impl RegionServer {
         }
     }
 
+    /// Starts capturing writes to the region, replaces the previous capture if any.
+    pub(crate) fn register_write_capture(&self, region_id: RegionId, capture: WriteCaptureRef) {
+        self.inner.write_captures.insert(region_id, capture);
+    }
+
+    /// Returns the capture of writes to the region.
+    pub(crate) fn write_capture(&self, region_id: RegionId) -> Option<WriteCaptureRef> {
+        self.inner
+            .write_captures
+            .get(&region_id)
+            .map(|capture| capture.clone())
+    }
+
     pub fn runtime(&self) -> Runtime {
         self.inner.runtime.clone()
     }
@@ -408,6 +426,79 @@ impl FlightCraft for RegionServer {
         let stream = Box::pin(FlightRecordBatchStream::new(result, tracing_context));
         Ok(Response::new(stream))
     }
+
+    /// Bulk inserts the record batches in the stream to a region.
+    ///
+    /// The first [FlightData] must carry a [FlightDescriptor] whose path is the region id.
+    /// The stream then sends the schema and record batches of the region. It responds
+    /// one [PutResult] with the total affected rows as the [FlightMessage::AffectedRows]
+    /// metadata after writing all batches.
+    ///
+    /// Batches are not partitioned and the frontend doesn't serve DoPut yet, so the client
+    /// must split them by regions and send them to the datanodes of the regions. The region
+    /// still writes them to the WAL as rows and only writes them to the memtable directly if
+    /// it uses the bulk memtable (`memtable.type = 'bulk'`).
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> TonicResult<Response<TonicStream<PutResult>>> {
+        let mut stream = request.into_inner();
+        let mut decoder = FlightDecoder::default();
+        let mut region_id = None;
+        let mut affected_rows = 0;
+        while let Some(flight_data) = stream.message().await? {
+            let region_id = match region_id {
+                Some(region_id) => region_id,
+                None => *region_id.insert(bulk_insert_region_id(
+                    flight_data.flight_descriptor.as_ref(),
+                )?),
+            };
+
+            match decoder
+                .try_decode(flight_data)
+                .map_err(|e| TonicStatus::invalid_argument(e.to_string()))?
+            {
+                FlightMessage::Schema(_) => {}
+                FlightMessage::Recordbatch(batch) => {
+                    let request = RegionRequest::BulkInserts(RegionBulkInsertsRequest {
+                        payload: batch.into_df_record_batch(),
+                    });
+                    let response = self
+                        .handle_request(region_id, request)
+                        .trace(info_span!(
+                            "RegionServer::handle_bulk_inserts",
+                            region_id = region_id.to_string()
+                        ))
+                        .await
+                        .map_err(BoxedError::new)
+                        .context(ExecuteGrpcRequestSnafu)?;
+                    affected_rows += response.affected_rows;
+                }
+                FlightMessage::AffectedRows(_) | FlightMessage::Metrics(_) => {
+                    return Err(TonicStatus::invalid_argument(
+                        "bulk inserts only accept schema and record batches",
+                    ));
+                }
+            }
+        }
+
+        let app_metadata = FlightEncoder::default()
+            .encode(FlightMessage::AffectedRows(affected_rows))
+            .app_metadata;
+        let stream = futures::stream::once(futures::future::ready(Ok(PutResult { app_metadata })));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Parses the region id of bulk inserts from the path of the `descriptor`.
+fn bulk_insert_region_id(descriptor: Option<&FlightDescriptor>) -> TonicResult<RegionId> {
+    descriptor
+        .and_then(|descriptor| descriptor.path.first())
+        .and_then(|path| path.parse::<u64>().ok())
+        .map(RegionId::from_u64)
+        .ok_or_else(|| {
+            TonicStatus::invalid_argument("bulk inserts require the region id in the descriptor")
+        })
 }
 
 #[derive(Clone)]
@@ -450,6 +541,8 @@ struct RegionServerInner {
     runtime: Runtime,
     event_listener: RegionServerEventListenerRef,
     table_provider_factory: TableProviderFactoryRef,
+    /// Captures of writes to regions whose data is being moved into other regions.
+    write_captures: DashMap<RegionId, WriteCaptureRef>,
 }
 
 enum CurrentEngine {
@@ -486,6 +579,7 @@ impl RegionServerInner {
             runtime,
             event_listener,
             table_provider_factory,
+            write_captures: DashMap::new(),
         }
     }
 
@@ -685,7 +779,8 @@ impl RegionServerInner {
             | RegionRequest::Alter(_)
             | RegionRequest::Flush(_)
             | RegionRequest::Compact(_)
-            | RegionRequest::Truncate(_) => RegionChange::None,
+            | RegionRequest::Truncate(_)
+            | RegionRequest::BulkInserts(_) => RegionChange::None,
             RegionRequest::Catchup(_) => RegionChange::Catchup,
         };
 
@@ -697,11 +792,20 @@ impl RegionServerInner {
         // Sets corresponding region status to registering/deregistering before the operation.
         self.set_region_status_not_ready(region_id, &engine, &region_change);
 
-        match engine
-            .handle_request(region_id, request)
-            .await
-            .with_context(|_| HandleRegionRequestSnafu { region_id })
-        {
+        let capture = self
+            .write_captures
+            .get(&region_id)
+            .map(|capture| capture.clone());
+        let result = match capture {
+            Some(capture) => {
+                capture
+                    .capture(request, |request| engine.handle_request(region_id, request))
+                    .await
+            }
+            None => engine.handle_request(region_id, request).await,
+        };
+
+        match result.with_context(|_| HandleRegionRequestSnafu { region_id }) {
             Ok(result) => {
                 // Sets corresponding region status to ready.
                 self.set_region_status_ready(region_id, engine, region_change)
@@ -789,6 +893,7 @@ impl RegionServerInner {
                 self.region_map
                     .remove(&region_id)
                     .map(|(id, engine)| engine.set_writable(id, false));
+                self.write_captures.remove(&region_id);
                 self.event_listener.on_region_deregistered(region_id);
             }
             RegionChange::Catchup => {
@@ -0,0 +1,202 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Captures writes to regions whose data is being moved into other regions.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use api::v1::Rows;
+use datatypes::arrow::record_batch::RecordBatch;
+use store_api::region_request::RegionRequest;
+use store_api::storage::RegionId;
+use tokio::sync::{Mutex, MutexGuard};
+
+/// The maximum number of writes a capture buffers. The capture gives up once it's
+/// exceeded, so an abandoned capture never exhausts the memory.
+const MAX_CAPTURED_WRITES: usize = 100_000;
+
+pub(crate) type WriteCaptureRef = Arc<WriteCapture>;
+
+/// A write applied to a captured region.
+#[derive(Debug, Clone)]
+pub(crate) enum CapturedWrite {
+    Put(Rows),
+    Delete(Rows),
+    BulkInserts(RecordBatch),
+}
+
+/// Records writes to a region in the order the region applies them, so they can be
+/// replayed on the regions that the data of the region is moved into.
+#[derive(Debug)]
+pub(crate) struct WriteCapture {
+    /// Regions to replay writes on.
+    to_regions: Vec<RegionId>,
+    /// It's held while handling a write, so writes are recorded in the order they are applied.
+    state: Mutex<CaptureState>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CaptureState {
+    writes: Vec<CapturedWrite>,
+    /// The reason why the captured writes can't be replayed.
+    invalidated: Option<String>,
+    /// Whether the data before the capture has been copied into target regions.
+    copied: bool,
+    /// Whether the region stopped accepting writes and all writes have been taken.
+    finished: bool,
+}
+
+impl WriteCapture {
+    pub(crate) fn new(to_regions: Vec<RegionId>) -> Self {
+        Self {
+            to_regions,
+            state: Mutex::new(CaptureState::default()),
+        }
+    }
+
+    pub(crate) fn to_regions(&self) -> &[RegionId] {
+        &self.to_regions
+    }
+
+    pub(crate) async fn lock(&self) -> MutexGuard<'_, CaptureState> {
+        self.state.lock().await
+    }
+
+    /// Handles the `request` by `handle` and records it once it's applied.
+    ///
+    /// Alter and truncate requests can't be replayed, they invalidate the capture.
+    pub(crate) async fn capture<F, Fut, T, E>(
+        &self,
+        request: RegionRequest,
+        handle: F,
+    ) -> Result<T, E>
+    where
+        F: FnOnce(RegionRequest) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut state = self.state.lock().await;
+        if state.finished || state.invalidated.is_some() {
+            drop(state);
+            return handle(request).await;
+        }
+
+        let write = match &request {
+            RegionRequest::Put(put) => Some(CapturedWrite::Put(put.rows.clone())),
+            RegionRequest::Delete(delete) => Some(CapturedWrite::Delete(delete.rows.clone())),
+            RegionRequest::BulkInserts(bulk) => {
+                Some(CapturedWrite::BulkInserts(bulk.payload.clone()))
+            }
+            _ => None,
+        };
+        let invalidating = matches!(
+            request,
+            RegionRequest::Alter(_) | RegionRequest::Truncate(_)
+        );
+        let request_type = request.request_type();
+
+        let response = handle(request).await?;
+        if let Some(write) = write {
+            state.push(write);
+        } else if invalidating {
+            state.invalidate(format!("{request_type} request is applied"));
+        }
+
+        Ok(response)
+    }
+}
+
+impl CaptureState {
+    fn push(&mut self, write: CapturedWrite) {
+        if self.writes.len() >= MAX_CAPTURED_WRITES {
+            self.invalidate(format!(
+                "More than {MAX_CAPTURED_WRITES} writes are captured"
+            ));
+            return;
+        }
+        self.writes.push(write);
+    }
+
+    fn invalidate(&mut self, reason: String) {
+        self.writes.clear();
+        self.invalidated = Some(reason);
+    }
+
+    /// Takes the captured writes, or returns the reason why they can't be replayed.
+    pub(crate) fn take(&mut self) -> Result<Vec<CapturedWrite>, String> {
+        match &self.invalidated {
+            Some(reason) => Err(reason.clone()),
+            None => Ok(std::mem::take(&mut self.writes)),
+        }
+    }
+
+    pub(crate) fn is_copied(&self) -> bool {
+        self.copied
+    }
+
+    pub(crate) fn set_copied(&mut self) {
+        self.copied = true;
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub(crate) fn set_finished(&mut self) {
+        self.finished = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::Rows;
+    use store_api::region_request::{RegionPutRequest, RegionTruncateRequest};
+
+    use super::*;
+
+    fn put_request() -> RegionRequest {
+        RegionRequest::Put(RegionPutRequest {
+            rows: Rows::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_capture_writes() {
+        let capture = WriteCapture::new(vec![RegionId::new(1024, 2)]);
+        capture
+            .capture(put_request(), |_| async { Ok::<_, ()>(()) })
+            .await
+            .unwrap();
+        // Failed writes are not recorded.
+        capture
+            .capture(put_request(), |_| async { Err::<(), _>(()) })
+            .await
+            .unwrap_err();
+        assert_eq!(1, capture.lock().await.take().unwrap().len());
+        assert!(capture.lock().await.take().unwrap().is_empty());
+
+        capture
+            .capture(
+                RegionRequest::Truncate(RegionTruncateRequest {}),
+                |_| async { Ok::<_, ()>(()) },
+            )
+            .await
+            .unwrap();
+        capture
+            .capture(put_request(), |_| async { Ok::<_, ()>(()) })
+            .await
+            .unwrap();
+        assert!(capture.lock().await.take().is_err());
+    }
+}
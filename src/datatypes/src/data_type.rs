@@ -33,10 +33,10 @@ use crate::types::{
     BinaryType, BooleanType, DateTimeType, DateType, Decimal128Type, DictionaryType,
     DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType, DurationSecondType,
     DurationType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
-    IntervalDayTimeType, IntervalMonthDayNanoType, IntervalType, IntervalYearMonthType, ListType,
-    NullType, StringType, TimeMillisecondType, TimeType, TimestampMicrosecondType,
+    IntervalDayTimeType, IntervalMonthDayNanoType, IntervalType, IntervalYearMonthType, JsonType,
+    ListType, NullType, StringType, TimeMillisecondType, TimeType, TimestampMicrosecondType,
     TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, TimestampType,
-    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type, VectorType,
 };
 use crate::value::Value;
 use crate::vectors::MutableVector;
@@ -81,6 +81,12 @@ pub enum ConcreteDataType {
     // Compound types:
     List(ListType),
     Dictionary(DictionaryType),
+
+    // JSON type:
+    Json(JsonType),
+
+    // Vector type:
+    Vector(VectorType),
 }
 
 impl fmt::Display for ConcreteDataType {
@@ -128,6 +134,8 @@ impl fmt::Display for ConcreteDataType {
             ConcreteDataType::Decimal128(v) => write!(f, "{}", v.name()),
             ConcreteDataType::List(v) => write!(f, "{}", v.name()),
             ConcreteDataType::Dictionary(v) => write!(f, "{}", v.name()),
+            ConcreteDataType::Json(v) => write!(f, "{}", v.name()),
+            ConcreteDataType::Vector(v) => write!(f, "{}", v.name()),
         }
     }
 }
@@ -162,6 +170,8 @@ impl ConcreteDataType {
                 | ConcreteDataType::Duration(_)
                 | ConcreteDataType::Decimal128(_)
                 | ConcreteDataType::Binary(_)
+                | ConcreteDataType::Json(_)
+                | ConcreteDataType::Vector(_)
         )
     }
 
@@ -216,6 +226,14 @@ impl ConcreteDataType {
         matches!(self, ConcreteDataType::Decimal128(_))
     }
 
+    pub fn is_json(&self) -> bool {
+        matches!(self, ConcreteDataType::Json(_))
+    }
+
+    pub fn is_vector(&self) -> bool {
+        matches!(self, ConcreteDataType::Vector(_))
+    }
+
     pub fn numerics() -> Vec<ConcreteDataType> {
         vec![
             ConcreteDataType::int8_datatype(),
@@ -309,6 +327,22 @@ impl ConcreteDataType {
         }
     }
 
+    /// Try to cast data type as a [`JsonType`].
+    pub fn as_json(&self) -> Option<JsonType> {
+        match self {
+            ConcreteDataType::Json(j) => Some(*j),
+            _ => None,
+        }
+    }
+
+    /// Try to cast data type as a [`VectorType`].
+    pub fn as_vector(&self) -> Option<VectorType> {
+        match self {
+            ConcreteDataType::Vector(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     pub fn as_decimal128(&self) -> Option<Decimal128Type> {
         match self {
             ConcreteDataType::Decimal128(d) => Some(*d),
@@ -404,7 +438,7 @@ macro_rules! impl_new_concrete_type_functions {
 
 impl_new_concrete_type_functions!(
     Null, Boolean, UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32, Float64,
-    Binary, Date, DateTime, String
+    Binary, Date, DateTime, String, Json
 );
 
 impl ConcreteDataType {
@@ -546,6 +580,14 @@ impl ConcreteDataType {
     pub fn decimal128_default_datatype() -> ConcreteDataType {
         Self::decimal128_datatype(DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE)
     }
+
+    pub fn vector_datatype(dim: u32) -> ConcreteDataType {
+        ConcreteDataType::Vector(VectorType::new(dim))
+    }
+
+    pub fn vector_default_datatype() -> ConcreteDataType {
+        Self::vector_datatype(0)
+    }
 }
 
 /// Data type abstraction.
@@ -739,6 +781,8 @@ mod tests {
         assert!(ConcreteDataType::duration_microsecond_datatype().is_stringifiable());
         assert!(ConcreteDataType::duration_nanosecond_datatype().is_stringifiable());
         assert!(ConcreteDataType::decimal128_datatype(10, 2).is_stringifiable());
+        assert!(ConcreteDataType::json_datatype().is_stringifiable());
+        assert!(ConcreteDataType::vector_datatype(3).is_stringifiable());
     }
 
     #[test]
@@ -863,6 +907,11 @@ mod tests {
             ConcreteDataType::decimal128_datatype(10, 2).to_string(),
             "Decimal(10, 2)"
         );
+        assert_eq!(ConcreteDataType::json_datatype().to_string(), "Json");
+        assert_eq!(
+            ConcreteDataType::vector_datatype(3).to_string(),
+            "Vector(3)"
+        );
         // Nested types
         assert_eq!(
             ConcreteDataType::list_datatype(ConcreteDataType::int32_datatype()).to_string(),
@@ -26,8 +26,9 @@ use snafu::{ensure, ResultExt};
 
 use crate::error::{self, DuplicateColumnSnafu, Error, ProjectArrowSchemaSnafu, Result};
 pub use crate::schema::column_schema::{
-    ColumnSchema, FulltextAnalyzer, FulltextOptions, Metadata, COMMENT_KEY, FULLTEXT_KEY,
-    TIME_INDEX_KEY,
+    ColumnSchema, FulltextAnalyzer, FulltextOptions, Metadata, SkippingIndexOptions,
+    SkippingIndexType, VectorDistanceMetric, VectorIndexOptions, COMMENT_KEY, FULLTEXT_KEY,
+    SKIPPING_INDEX_KEY, TIME_INDEX_KEY, TYPE_KEY, VECTOR_INDEX_KEY,
 };
 pub use crate::schema::constraint::ColumnDefaultConstraint;
 pub use crate::schema::raw::RawSchema;
@@ -22,6 +22,7 @@ use snafu::{ensure, ResultExt};
 use crate::data_type::{ConcreteDataType, DataType};
 use crate::error::{self, Error, Result};
 use crate::schema::constraint::ColumnDefaultConstraint;
+use crate::types::{JsonType, VectorType, JSON_TYPE_NAME};
 use crate::value::Value;
 use crate::vectors::VectorRef;
 
@@ -34,6 +35,13 @@ pub const COMMENT_KEY: &str = "greptime:storage:comment";
 const DEFAULT_CONSTRAINT_KEY: &str = "greptime:default_constraint";
 /// Key used to store fulltext options in arrow field's metadata.
 pub const FULLTEXT_KEY: &str = "greptime:fulltext";
+/// Key used to store vector index options in arrow field's metadata.
+pub const VECTOR_INDEX_KEY: &str = "greptime:vector_index";
+/// Key used to store skipping index options in arrow field's metadata.
+pub const SKIPPING_INDEX_KEY: &str = "greptime:skipping_index";
+/// Key used to store the logical type of the column in arrow field's metadata,
+/// for types sharing the same arrow type with others (e.g. json is stored as binary).
+pub const TYPE_KEY: &str = "greptime:type";
 
 /// Schema of a column, used as an immutable struct.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -262,14 +270,89 @@ impl ColumnSchema {
         );
         Ok(self)
     }
+
+    /// Retrieves the vector index options for the column.
+    pub fn vector_index_options(&self) -> Result<Option<VectorIndexOptions>> {
+        match self.metadata.get(VECTOR_INDEX_KEY) {
+            None => Ok(None),
+            Some(json) => {
+                let options =
+                    serde_json::from_str(json).context(error::DeserializeSnafu { json })?;
+                Ok(Some(options))
+            }
+        }
+    }
+
+    pub fn with_vector_index_options(mut self, options: VectorIndexOptions) -> Result<Self> {
+        ensure!(
+            self.data_type.is_vector(),
+            error::InvalidVectorSnafu {
+                msg: format!(
+                    "vector index only supports vector type, column {} is {}",
+                    self.name, self.data_type
+                ),
+            }
+        );
+        self.metadata.insert(
+            VECTOR_INDEX_KEY.to_string(),
+            serde_json::to_string(&options).context(error::SerializeSnafu)?,
+        );
+        Ok(self)
+    }
+
+    /// Retrieves the skipping index options for the column.
+    pub fn skipping_index_options(&self) -> Result<Option<SkippingIndexOptions>> {
+        match self.metadata.get(SKIPPING_INDEX_KEY) {
+            None => Ok(None),
+            Some(json) => {
+                let options =
+                    serde_json::from_str(json).context(error::DeserializeSnafu { json })?;
+                Ok(Some(options))
+            }
+        }
+    }
+
+    pub fn with_skipping_index_options(mut self, options: SkippingIndexOptions) -> Result<Self> {
+        ensure!(
+            !self.data_type.is_json() && !self.data_type.is_vector(),
+            error::InvalidSkippingIndexSnafu {
+                msg: format!(
+                    "skipping index doesn't support column {} of type {}",
+                    self.name, self.data_type
+                ),
+            }
+        );
+        ensure!(
+            options.false_positive_rate > 0.0 && options.false_positive_rate < 1.0,
+            error::InvalidSkippingIndexSnafu {
+                msg: format!(
+                    "false positive rate must be in (0, 1), given: {}",
+                    options.false_positive_rate
+                ),
+            }
+        );
+        self.metadata.insert(
+            SKIPPING_INDEX_KEY.to_string(),
+            serde_json::to_string(&options).context(error::SerializeSnafu)?,
+        );
+        Ok(self)
+    }
 }
 
 impl TryFrom<&Field> for ColumnSchema {
     type Error = Error;
 
     fn try_from(field: &Field) -> Result<ColumnSchema> {
-        let data_type = ConcreteDataType::try_from(field.data_type())?;
+        let mut data_type = ConcreteDataType::try_from(field.data_type())?;
         let mut metadata = field.metadata().clone();
+        // Overrides the data type if it's specified in the metadata.
+        if let Some(type_name) = metadata.remove(TYPE_KEY) {
+            if type_name == JSON_TYPE_NAME {
+                data_type = ConcreteDataType::Json(JsonType::default());
+            } else if let Some(vector_type) = VectorType::from_name(&type_name) {
+                data_type = ConcreteDataType::Vector(vector_type);
+            }
+        }
         let default_constraint = match metadata.remove(DEFAULT_CONSTRAINT_KEY) {
             Some(json) => {
                 Some(serde_json::from_str(&json).context(error::DeserializeSnafu { json })?)
@@ -308,6 +391,9 @@ impl TryFrom<&ColumnSchema> for Field {
                 }
             );
         }
+        if column_schema.data_type.is_json() || column_schema.data_type.is_vector() {
+            let _ = metadata.insert(TYPE_KEY.to_string(), column_schema.data_type.name());
+        }
 
         Ok(Field::new(
             &column_schema.name,
@@ -330,6 +416,9 @@ pub struct FulltextOptions {
     /// Whether the fulltext index is case-sensitive.
     #[serde(default)]
     pub case_sensitive: bool,
+    /// Tokens to remove before indexing and searching.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_words: Vec<String>,
 }
 
 /// Fulltext analyzer.
@@ -338,6 +427,11 @@ pub enum FulltextAnalyzer {
     #[default]
     English,
     Chinese,
+    Standard,
+    Whitespace,
+    Ngram,
+    Japanese,
+    Korean,
 }
 
 impl fmt::Display for FulltextAnalyzer {
@@ -345,6 +439,111 @@ impl fmt::Display for FulltextAnalyzer {
         match self {
             FulltextAnalyzer::English => write!(f, "English"),
             FulltextAnalyzer::Chinese => write!(f, "Chinese"),
+            FulltextAnalyzer::Standard => write!(f, "Standard"),
+            FulltextAnalyzer::Whitespace => write!(f, "Whitespace"),
+            FulltextAnalyzer::Ngram => write!(f, "Ngram"),
+            FulltextAnalyzer::Japanese => write!(f, "Japanese"),
+            FulltextAnalyzer::Korean => write!(f, "Korean"),
+        }
+    }
+}
+
+/// Vector index options for a column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VectorIndexOptions {
+    /// The distance metric the index is built for.
+    #[serde(default)]
+    pub metric: VectorDistanceMetric,
+    /// Max number of neighbors of each node in the HNSW graph.
+    #[serde(default = "VectorIndexOptions::default_connectivity")]
+    pub connectivity: usize,
+    /// Size of the candidate list when building the HNSW graph.
+    #[serde(default = "VectorIndexOptions::default_expansion_add")]
+    pub expansion_add: usize,
+}
+
+impl VectorIndexOptions {
+    fn default_connectivity() -> usize {
+        16
+    }
+
+    fn default_expansion_add() -> usize {
+        128
+    }
+}
+
+impl Default for VectorIndexOptions {
+    fn default() -> Self {
+        Self {
+            metric: VectorDistanceMetric::default(),
+            connectivity: Self::default_connectivity(),
+            expansion_add: Self::default_expansion_add(),
+        }
+    }
+}
+
+/// Distance metric of vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum VectorDistanceMetric {
+    /// Squared euclidean distance, i.e. `vec_l2sq_distance`.
+    #[default]
+    L2sq,
+    /// Cosine distance, i.e. `vec_cos_distance`.
+    Cosine,
+    /// Inner product, i.e. `vec_dot_product`, larger means closer.
+    InnerProduct,
+}
+
+impl fmt::Display for VectorDistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VectorDistanceMetric::L2sq => write!(f, "l2sq"),
+            VectorDistanceMetric::Cosine => write!(f, "cosine"),
+            VectorDistanceMetric::InnerProduct => write!(f, "inner_product"),
+        }
+    }
+}
+
+/// Skipping index options for a column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkippingIndexOptions {
+    /// Type of the skipping index.
+    #[serde(default)]
+    pub index_type: SkippingIndexType,
+    /// Expected false positive rate of the index.
+    #[serde(default = "SkippingIndexOptions::default_false_positive_rate")]
+    pub false_positive_rate: f64,
+}
+
+impl SkippingIndexOptions {
+    fn default_false_positive_rate() -> f64 {
+        0.01
+    }
+}
+
+impl Default for SkippingIndexOptions {
+    fn default() -> Self {
+        Self {
+            index_type: SkippingIndexType::default(),
+            false_positive_rate: Self::default_false_positive_rate(),
+        }
+    }
+}
+
+/// Type of the skipping index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SkippingIndexType {
+    /// A bloom filter for each row group.
+    #[default]
+    BloomFilter,
+}
+
+impl fmt::Display for SkippingIndexType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkippingIndexType::BloomFilter => write!(f, "bloom"),
         }
     }
 }
@@ -419,6 +618,73 @@ mod tests {
         assert_eq!(column_schema, new_column_schema);
     }
 
+    #[test]
+    fn test_column_schema_with_json_type() {
+        let column_schema = ColumnSchema::new("test", ConcreteDataType::json_datatype(), true);
+        let field = Field::try_from(&column_schema).unwrap();
+        assert_eq!(ArrowDataType::Binary, *field.data_type());
+        assert_eq!(JSON_TYPE_NAME, field.metadata().get(TYPE_KEY).unwrap());
+
+        let new_column_schema = ColumnSchema::try_from(&field).unwrap();
+        assert_eq!(column_schema, new_column_schema);
+        assert!(new_column_schema.metadata().get(TYPE_KEY).is_none());
+    }
+
+    #[test]
+    fn test_column_schema_with_skipping_index() {
+        let column_schema = ColumnSchema::new("test", ConcreteDataType::string_datatype(), true)
+            .with_skipping_index_options(SkippingIndexOptions {
+                false_positive_rate: 0.05,
+                ..Default::default()
+            })
+            .unwrap();
+        let field = Field::try_from(&column_schema).unwrap();
+        let new_column_schema = ColumnSchema::try_from(&field).unwrap();
+        assert_eq!(column_schema, new_column_schema);
+        let options = new_column_schema.skipping_index_options().unwrap().unwrap();
+        assert_eq!(SkippingIndexType::BloomFilter, options.index_type);
+        assert_eq!(0.05, options.false_positive_rate);
+
+        assert!(
+            ColumnSchema::new("test", ConcreteDataType::string_datatype(), true)
+                .with_skipping_index_options(SkippingIndexOptions {
+                    false_positive_rate: 1.0,
+                    ..Default::default()
+                })
+                .is_err()
+        );
+        assert!(
+            ColumnSchema::new("test", ConcreteDataType::json_datatype(), true)
+                .with_skipping_index_options(SkippingIndexOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_column_schema_with_vector_type() {
+        let column_schema = ColumnSchema::new("test", ConcreteDataType::vector_datatype(3), true)
+            .with_vector_index_options(VectorIndexOptions {
+                metric: VectorDistanceMetric::Cosine,
+                ..Default::default()
+            })
+            .unwrap();
+        let field = Field::try_from(&column_schema).unwrap();
+        assert_eq!(ArrowDataType::Binary, *field.data_type());
+        assert_eq!("Vector(3)", field.metadata().get(TYPE_KEY).unwrap());
+
+        let new_column_schema = ColumnSchema::try_from(&field).unwrap();
+        assert_eq!(column_schema, new_column_schema);
+        let options = new_column_schema.vector_index_options().unwrap().unwrap();
+        assert_eq!(VectorDistanceMetric::Cosine, options.metric);
+        assert_eq!(16, options.connectivity);
+
+        assert!(
+            ColumnSchema::new("test", ConcreteDataType::binary_datatype(), true)
+                .with_vector_index_options(VectorIndexOptions::default())
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_column_schema_with_duplicate_metadata() {
         let metadata = Metadata::from([(DEFAULT_CONSTRAINT_KEY.to_string(), "v1".to_string())]);
@@ -68,6 +68,9 @@ pub enum LogicalTypeId {
 
     List,
     Dictionary,
+
+    Json,
+    Vector,
 }
 
 impl LogicalTypeId {
@@ -126,6 +129,8 @@ impl LogicalTypeId {
             LogicalTypeId::DurationMicrosecond => ConcreteDataType::duration_microsecond_datatype(),
             LogicalTypeId::DurationNanosecond => ConcreteDataType::duration_nanosecond_datatype(),
             LogicalTypeId::Decimal128 => ConcreteDataType::decimal128_default_datatype(),
+            LogicalTypeId::Json => ConcreteDataType::json_datatype(),
+            LogicalTypeId::Vector => ConcreteDataType::vector_default_datatype(),
         }
     }
 }
@@ -21,12 +21,14 @@ mod decimal_type;
 mod dictionary_type;
 mod duration_type;
 mod interval_type;
+mod json_type;
 mod list_type;
 mod null_type;
 mod primitive_type;
 mod string_type;
 mod time_type;
 mod timestamp_type;
+mod vector_type;
 
 pub use binary_type::BinaryType;
 pub use boolean_type::BooleanType;
@@ -42,6 +44,10 @@ pub use duration_type::{
 pub use interval_type::{
     IntervalDayTimeType, IntervalMonthDayNanoType, IntervalType, IntervalYearMonthType,
 };
+pub use json_type::{
+    json_type_value_to_string, parse_string_to_json_type_value, JsonFormat, JsonType,
+    JSON_TYPE_NAME,
+};
 pub use list_type::ListType;
 pub use null_type::NullType;
 pub use primitive_type::{
@@ -56,3 +62,7 @@ pub use timestamp_type::{
     TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
     TimestampSecondType, TimestampType,
 };
+pub use vector_type::{
+    binlit_as_veclit, parse_string_to_vector_type_value, veclit_to_binlit,
+    vector_type_value_to_string, VectorType, VECTOR_TYPE_NAME,
+};
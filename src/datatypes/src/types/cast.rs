@@ -113,6 +113,8 @@ pub fn can_cast_type(src_value: &Value, dest_type: &ConcreteDataType) -> bool {
         ) => true,
 
         (String(_), Binary(_)) => true,
+        (Binary(_), Json(_)) => true,
+        (Binary(_), Vector(_)) => true,
 
         // temporal types cast
         // Date type
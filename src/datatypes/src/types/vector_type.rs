@@ -0,0 +1,221 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arrow::datatypes::DataType as ArrowDataType;
+use common_base::bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::data_type::DataType;
+use crate::error::{InvalidVectorSnafu, Result};
+use crate::scalars::ScalarVectorBuilder;
+use crate::type_id::LogicalTypeId;
+use crate::value::Value;
+use crate::vectors::{BinaryVectorBuilder, MutableVector};
+
+pub const VECTOR_TYPE_NAME: &str = "Vector";
+
+/// Size in bytes of an element of the vector.
+const VECTOR_ELEMENT_SIZE: usize = std::mem::size_of::<f32>();
+
+/// VectorType is a data type for fixed-dimension vectors of `f32`. Values are stored
+/// as [Value::Binary], with elements encoded in little-endian.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct VectorType {
+    pub dim: u32,
+}
+
+impl VectorType {
+    pub fn new(dim: u32) -> Self {
+        Self { dim }
+    }
+
+    /// Parses the vector type from its name, e.g. `Vector(3)`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let dim = name
+            .strip_prefix(VECTOR_TYPE_NAME)?
+            .strip_prefix('(')?
+            .strip_suffix(')')?;
+        dim.trim().parse().ok().map(Self::new)
+    }
+
+    /// Returns the size in bytes of a value of this type.
+    pub fn value_size(&self) -> usize {
+        self.dim as usize * VECTOR_ELEMENT_SIZE
+    }
+}
+
+impl DataType for VectorType {
+    fn name(&self) -> String {
+        format!("{}({})", VECTOR_TYPE_NAME, self.dim)
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Vector
+    }
+
+    fn default_value(&self) -> Value {
+        Bytes::from(vec![0; self.value_size()]).into()
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Binary
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(BinaryVectorBuilder::with_capacity(capacity))
+    }
+
+    fn try_cast(&self, from: Value) -> Option<Value> {
+        match from {
+            Value::Binary(v) if v.len() == self.value_size() => Some(Value::Binary(v)),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes the elements of a vector to the binary representation of a vector type value.
+pub fn veclit_to_binlit(vec: &[f32]) -> Vec<u8> {
+    vec.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decodes the binary representation of a vector type value to its elements.
+pub fn binlit_as_veclit(bytes: &[u8]) -> Result<Vec<f32>> {
+    ensure!(
+        bytes.len() % VECTOR_ELEMENT_SIZE == 0,
+        InvalidVectorSnafu {
+            msg: format!("invalid binary length of vector: {}", bytes.len()),
+        }
+    );
+
+    Ok(bytes
+        .chunks_exact(VECTOR_ELEMENT_SIZE)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+/// Converts a vector type value to its string representation, e.g. `[1,2,3]`.
+pub fn vector_type_value_to_string(val: &[u8], dim: u32) -> Result<String> {
+    let elements = binlit_as_veclit(val)?;
+    ensure!(
+        elements.len() == dim as usize,
+        InvalidVectorSnafu {
+            msg: format!(
+                "dimension mismatch, expect {dim}, actual {}",
+                elements.len()
+            ),
+        }
+    );
+
+    let elements = elements
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(format!("[{elements}]"))
+}
+
+/// Parses a string like `[1.0, 2.0, 3.0]` into the binary representation of a vector
+/// type value with `dim` elements.
+pub fn parse_string_to_vector_type_value(s: &str, dim: u32) -> Result<Vec<u8>> {
+    let trimmed = s.trim();
+    let Some(content) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) else {
+        return InvalidVectorSnafu {
+            msg: format!("failed to parse {s} to vector, expect the form of [e1, e2, ...]"),
+        }
+        .fail();
+    };
+
+    let content = content.trim();
+    let elements = if content.is_empty() {
+        vec![]
+    } else {
+        content
+            .split(',')
+            .map(|e| {
+                e.trim().parse::<f32>().map_err(|_| {
+                    InvalidVectorSnafu {
+                        msg: format!("failed to parse {e} in {s} to float"),
+                    }
+                    .build()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    ensure!(
+        elements.len() == dim as usize,
+        InvalidVectorSnafu {
+            msg: format!(
+                "dimension mismatch, expect {dim}, actual {}",
+                elements.len()
+            ),
+        }
+    );
+    ensure!(
+        elements.iter().all(|e| e.is_finite()),
+        InvalidVectorSnafu {
+            msg: format!("elements of vector {s} must be finite"),
+        }
+    );
+
+    Ok(veclit_to_binlit(&elements))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_type_value_roundtrip() {
+        let encoded = parse_string_to_vector_type_value("[1.0, 2.5, -3]", 3).unwrap();
+        assert_eq!(12, encoded.len());
+        assert_eq!(vec![1.0, 2.5, -3.0], binlit_as_veclit(&encoded).unwrap());
+        assert_eq!(
+            "[1,2.5,-3]",
+            vector_type_value_to_string(&encoded, 3).unwrap()
+        );
+
+        assert!(parse_string_to_vector_type_value("[1.0, 2.0]", 3).is_err());
+        assert!(parse_string_to_vector_type_value("1.0, 2.0, 3.0", 3).is_err());
+        assert!(parse_string_to_vector_type_value("[1.0, a, 3.0]", 3).is_err());
+        assert!(parse_string_to_vector_type_value("[1.0, NaN, 3.0]", 3).is_err());
+        assert!(vector_type_value_to_string(&encoded, 2).is_err());
+        assert!(binlit_as_veclit(&[0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_vector_type() {
+        let vector_type = VectorType::new(3);
+        assert_eq!("Vector(3)", vector_type.name());
+        assert_eq!(Some(vector_type), VectorType::from_name("Vector(3)"));
+        assert_eq!(None, VectorType::from_name("Vector"));
+        assert_eq!(None, VectorType::from_name("Json"));
+        assert_eq!(ArrowDataType::Binary, vector_type.as_arrow_type());
+        assert_eq!(
+            Value::Binary(Bytes::from(vec![0; 12])),
+            vector_type.default_value()
+        );
+
+        let value = Value::Binary(Bytes::from(veclit_to_binlit(&[1.0, 2.0, 3.0])));
+        assert_eq!(Some(value.clone()), vector_type.try_cast(value));
+        assert_eq!(
+            None,
+            vector_type.try_cast(Value::Binary(Bytes::from(vec![0; 8])))
+        );
+        assert_eq!(None, vector_type.try_cast(Value::Int64(1)));
+    }
+}
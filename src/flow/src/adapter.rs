@@ -26,12 +26,14 @@ use common_error::ext::BoxedError;
 use common_meta::key::TableMetadataManagerRef;
 use common_runtime::JoinHandle;
 use common_telemetry::logging::{LoggingOptions, TracingOptions};
-use common_telemetry::{debug, info, trace};
+use common_telemetry::{debug, info, trace, warn};
 use datatypes::schema::ColumnSchema;
 use datatypes::value::Value;
 use greptime_proto::v1;
 use itertools::Itertools;
 use meta_client::MetaClientOptions;
+use object_store::services::Fs;
+use object_store::ObjectStore;
 use query::QueryEngine;
 use serde::{Deserialize, Serialize};
 use servers::grpc::GrpcOptions;
@@ -44,12 +46,15 @@ use table::metadata::TableId;
 use tokio::sync::broadcast::error::TryRecvError;
 use tokio::sync::{broadcast, watch, Mutex, RwLock};
 
+use crate::adapter::checkpoint::FlowCheckpointStore;
 pub(crate) use crate::adapter::node_context::FlownodeContext;
 use crate::adapter::table_source::TableSource;
 use crate::adapter::util::column_schemas_to_proto;
 use crate::adapter::worker::{create_worker, Worker, WorkerHandle};
 use crate::compute::ErrCollector;
-use crate::error::{ExternalSnafu, InternalSnafu, TableNotFoundSnafu, UnexpectedSnafu};
+use crate::error::{
+    CheckpointObjectStoreSnafu, ExternalSnafu, InternalSnafu, TableNotFoundSnafu, UnexpectedSnafu,
+};
 use crate::expr::GlobalId;
 use crate::metrics::{
     METRIC_FLOW_INPUT_BUF_SIZE, METRIC_FLOW_INSERT_ELAPSED, METRIC_FLOW_RUN_INTERVAL_MS,
@@ -57,6 +62,7 @@ use crate::metrics::{
 use crate::repr::{self, DiffRow, Row, BATCH_SIZE};
 use crate::transform::sql_to_flow_plan;
 
+mod checkpoint;
 mod flownode_impl;
 mod parse_expr;
 #[cfg(test)]
@@ -92,6 +98,7 @@ pub struct FlownodeOptions {
     pub logging: LoggingOptions,
     pub tracing: TracingOptions,
     pub heartbeat: HeartbeatOptions,
+    pub checkpoint: FlowCheckpointOptions,
 }
 
 impl Default for FlownodeOptions {
@@ -105,12 +112,33 @@ impl Default for FlownodeOptions {
             logging: LoggingOptions::default(),
             tracing: TracingOptions::default(),
             heartbeat: HeartbeatOptions::default(),
+            checkpoint: FlowCheckpointOptions::default(),
         }
     }
 }
 
 impl Configurable for FlownodeOptions {}
 
+/// Options for checkpointing flow states
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowCheckpointOptions {
+    /// The directory to store checkpoints, checkpointing is disabled if not set
+    pub dir: Option<String>,
+    /// Interval between two checkpoints
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+impl Default for FlowCheckpointOptions {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            interval: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
 /// Arc-ed FlowNodeManager, cheaper to clone
 pub type FlowWorkerManagerRef = Arc<FlowWorkerManager>;
 
@@ -136,6 +164,10 @@ pub struct FlowWorkerManager {
     ///
     /// So that a series of event like `inserts -> flush` can be handled correctly
     flush_lock: RwLock<()>,
+    /// Store for checkpoints of flow states, `None` if checkpointing is disabled
+    checkpoint_store: Option<FlowCheckpointStore>,
+    /// Interval between two checkpoints
+    checkpoint_interval: Duration,
 }
 
 /// Building FlownodeManager
@@ -169,6 +201,8 @@ impl FlowWorkerManager {
             tick_manager,
             node_id,
             flush_lock: RwLock::new(()),
+            checkpoint_store: None,
+            checkpoint_interval: FlowCheckpointOptions::default().interval,
         }
     }
 
@@ -188,6 +222,24 @@ impl FlowWorkerManager {
     pub fn add_worker_handle(&mut self, handle: WorkerHandle) {
         self.worker_handles.push(Mutex::new(handle));
     }
+
+    /// enable checkpointing flow states if checkpoint directory is set in `opts`
+    pub fn enable_checkpoint(&mut self, opts: &FlowCheckpointOptions) -> Result<(), Error> {
+        let Some(dir) = &opts.dir else {
+            return Ok(());
+        };
+        let builder = Fs::default().root(dir);
+        let object_store = ObjectStore::new(builder)
+            .context(CheckpointObjectStoreSnafu { path: dir })?
+            .finish();
+        self.checkpoint_store = Some(FlowCheckpointStore::new(object_store));
+        self.checkpoint_interval = opts.interval;
+        info!(
+            "Flow checkpoint enabled, dir={}, interval={:?}",
+            dir, opts.interval
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -510,6 +562,7 @@ impl FlowWorkerManager {
         let default_interval = Duration::from_secs(1);
         let mut avg_spd = 0; // rows/sec
         let mut since_last_run = tokio::time::Instant::now();
+        let mut last_checkpoint = tokio::time::Instant::now();
         loop {
             // TODO(discord9): only run when new inputs arrive or scheduled to
             let row_cnt = self.run_available(true).await.unwrap_or_else(|err| {
@@ -522,6 +575,16 @@ impl FlowWorkerManager {
             };
             self.log_all_errors().await;
 
+            if self.checkpoint_store.is_some()
+                && last_checkpoint.elapsed() >= self.checkpoint_interval
+            {
+                match self.checkpoint_flows().await {
+                    Ok(cnt) => debug!("Checkpointed {} flows", cnt),
+                    Err(err) => common_telemetry::error!(err;"Checkpoint flows errors"),
+                }
+                last_checkpoint = tokio::time::Instant::now();
+            }
+
             // determine if need to shutdown
             match &shutdown.as_mut().map(|s| s.try_recv()) {
                 Some(Ok(())) => {
@@ -616,6 +679,30 @@ impl FlowWorkerManager {
         Ok(row_cnt)
     }
 
+    /// Save states of all flows to checkpoint store, do nothing if checkpointing is disabled
+    ///
+    /// Outputs are written back before taking snapshots, so that outputs derived
+    /// from the checkpointed states wouldn't be lost after recovering from them
+    ///
+    /// return numbers of flows checkpointed
+    pub async fn checkpoint_flows(&self) -> Result<usize, Error> {
+        let Some(store) = &self.checkpoint_store else {
+            return Ok(0);
+        };
+        self.send_writeback_requests().await?;
+
+        let mut cnt = 0;
+        for worker in self.worker_handles.iter() {
+            let snapshots = worker.lock().await.snapshot_flows().await?;
+            for (flow_id, snapshot) in snapshots {
+                if store.save(flow_id, snapshot).await? {
+                    cnt += 1;
+                }
+            }
+        }
+        Ok(cnt)
+    }
+
     /// send write request to related source sender
     pub async fn handle_write_request(
         &self,
@@ -649,6 +736,11 @@ impl FlowWorkerManager {
             }
         }
         self.node_context.write().await.remove_flow(flow_id);
+        if let Some(store) = &self.checkpoint_store {
+            if let Err(err) = store.remove(flow_id).await {
+                warn!(err; "Failed to remove checkpoint of flow {flow_id}");
+            }
+        }
         Ok(())
     }
 
@@ -718,6 +810,13 @@ impl FlowWorkerManager {
                     .map(|s| s.get_receiver())
             })
             .collect::<Result<Vec<_>, _>>()?;
+        let snapshot = match &self.checkpoint_store {
+            Some(store) => store.load(flow_id, &sql).await.unwrap_or_else(|err| {
+                warn!(err; "Failed to load checkpoint of flow {flow_id}, start with empty state");
+                None
+            }),
+            None => None,
+        };
         let err_collector = ErrCollector::default();
         self.flow_err_collectors
             .write()
@@ -734,6 +833,7 @@ impl FlowWorkerManager {
             expire_after,
             create_if_not_exists,
             err_collector,
+            snapshot,
         };
         handle.create_flow(create_request).await?;
         info!("Successfully create flow with id={}", flow_id);
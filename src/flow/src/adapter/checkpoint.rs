@@ -0,0 +1,189 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persist and recover the state of flows, so flows can survive a restart of flownode
+
+use std::collections::BTreeMap;
+
+use common_telemetry::warn;
+use object_store::{ErrorKind, ObjectStore};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tokio::sync::RwLock;
+
+use crate::adapter::FlowId;
+use crate::compute::DataflowSnapshot;
+use crate::error::{CheckpointObjectStoreSnafu, EncodeCheckpointSnafu, Error};
+
+/// The content of a flow's checkpoint file
+#[derive(Debug, Serialize, Deserialize)]
+struct FlowCheckpoint {
+    /// the sql the flow is created with, a checkpoint is only valid for the same sql,
+    /// since the states are identified by the order they are rendered
+    sql: String,
+    snapshot: DataflowSnapshot,
+}
+
+/// Save and load checkpoints of flows in an object store
+#[derive(Debug)]
+pub struct FlowCheckpointStore {
+    /// where checkpoints are stored
+    object_store: ObjectStore,
+    /// sql of each flow that has been loaded, used for saving checkpoint later
+    flow_sqls: RwLock<BTreeMap<FlowId, String>>,
+}
+
+impl FlowCheckpointStore {
+    pub fn new(object_store: ObjectStore) -> Self {
+        Self {
+            object_store,
+            flow_sqls: Default::default(),
+        }
+    }
+
+    /// each flow have one checkpoint file, overwritten by every new checkpoint
+    fn checkpoint_path(flow_id: FlowId) -> String {
+        format!("flow/{flow_id}/checkpoint.json")
+    }
+
+    /// where a new checkpoint is written before replacing the previous one
+    fn checkpoint_tmp_path(flow_id: FlowId) -> String {
+        format!("flow/{flow_id}/checkpoint.json.tmp")
+    }
+
+    /// Load the checkpoint of given flow, and register the flow's sql for later [`Self::save`]
+    ///
+    /// Return `None` if there is no checkpoint or the checkpoint is created with a different sql
+    pub async fn load(
+        &self,
+        flow_id: FlowId,
+        sql: &str,
+    ) -> Result<Option<DataflowSnapshot>, Error> {
+        self.flow_sqls
+            .write()
+            .await
+            .insert(flow_id, sql.to_string());
+
+        let path = Self::checkpoint_path(flow_id);
+        let data = match self.object_store.read(&path).await {
+            Ok(data) => data.to_vec(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).context(CheckpointObjectStoreSnafu { path }),
+        };
+
+        let checkpoint: FlowCheckpoint = match serde_json::from_slice(&data) {
+            Ok(checkpoint) => checkpoint,
+            Err(err) => {
+                warn!(err; "Failed to decode checkpoint of flow {flow_id} at {path}, ignore it");
+                return Ok(None);
+            }
+        };
+        if checkpoint.sql != sql {
+            warn!(
+                "Checkpoint of flow {flow_id} is created with a different sql, ignore it. checkpoint sql: {}, current sql: {}",
+                checkpoint.sql, sql
+            );
+            return Ok(None);
+        }
+        Ok(Some(checkpoint.snapshot))
+    }
+
+    /// Save the checkpoint of given flow, overwriting the previous one
+    ///
+    /// The checkpoint is written to a temporary file and then renamed, so a crash while saving
+    /// never leaves a partial checkpoint. Object stores that can't rename write objects atomically,
+    /// so the checkpoint is written in place for them.
+    ///
+    /// Return false if the flow is not registered by [`Self::load`] and nothing is saved
+    pub async fn save(&self, flow_id: FlowId, snapshot: DataflowSnapshot) -> Result<bool, Error> {
+        let Some(sql) = self.flow_sqls.read().await.get(&flow_id).cloned() else {
+            return Ok(false);
+        };
+        let checkpoint = FlowCheckpoint { sql, snapshot };
+        let data =
+            serde_json::to_vec(&checkpoint).context(EncodeCheckpointSnafu { id: flow_id })?;
+
+        let path = Self::checkpoint_path(flow_id);
+        if !self.object_store.info().full_capability().rename {
+            self.object_store
+                .write(&path, data)
+                .await
+                .context(CheckpointObjectStoreSnafu { path })?;
+            return Ok(true);
+        }
+
+        let tmp_path = Self::checkpoint_tmp_path(flow_id);
+        self.object_store
+            .write(&tmp_path, data)
+            .await
+            .context(CheckpointObjectStoreSnafu { path: &tmp_path })?;
+        self.object_store
+            .rename(&tmp_path, &path)
+            .await
+            .context(CheckpointObjectStoreSnafu { path })?;
+        Ok(true)
+    }
+
+    /// Remove the checkpoint of given flow
+    pub async fn remove(&self, flow_id: FlowId) -> Result<(), Error> {
+        self.flow_sqls.write().await.remove(&flow_id);
+
+        let path = Self::checkpoint_path(flow_id);
+        self.object_store
+            .delete(&path)
+            .await
+            .context(CheckpointObjectStoreSnafu { path })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common_test_util::temp_dir::create_temp_dir;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_load_checkpoint() {
+        let dir = create_temp_dir("test_flow_checkpoint");
+        let builder = Fs::default().root(&dir.path().to_string_lossy());
+        let store = FlowCheckpointStore::new(ObjectStore::new(builder).unwrap().finish());
+
+        let snapshot = DataflowSnapshot {
+            as_of: 42,
+            ..Default::default()
+        };
+        // not loaded yet, so nothing would be saved
+        assert!(!store.save(1, snapshot.clone()).await.unwrap());
+
+        assert_eq!(store.load(1, "SELECT 1").await.unwrap(), None);
+        assert!(store.save(1, snapshot.clone()).await.unwrap());
+        assert_eq!(
+            store.load(1, "SELECT 1").await.unwrap(),
+            Some(snapshot.clone())
+        );
+        // the temporary file is renamed to the checkpoint
+        let err = store
+            .object_store
+            .stat(&FlowCheckpointStore::checkpoint_tmp_path(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        // different sql means the checkpoint is stale
+        assert_eq!(store.load(1, "SELECT 2").await.unwrap(), None);
+
+        store.remove(1).await.unwrap();
+        assert_eq!(store.load(1, "SELECT 1").await.unwrap(), None);
+    }
+}
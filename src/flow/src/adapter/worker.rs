@@ -18,14 +18,14 @@ use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use common_telemetry::info;
+use common_telemetry::{info, warn};
 use enum_as_inner::EnumAsInner;
 use hydroflow::scheduled::graph::Hydroflow;
 use snafu::ensure;
 use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
 
 use crate::adapter::FlowId;
-use crate::compute::{Context, DataflowState, ErrCollector};
+use crate::compute::{Context, DataflowSnapshot, DataflowState, ErrCollector};
 use crate::error::{Error, FlowAlreadyExistSnafu, InternalSnafu, UnexpectedSnafu};
 use crate::expr::GlobalId;
 use crate::plan::TypedPlan;
@@ -102,6 +102,10 @@ impl<'subgraph> ActiveDataflowState<'subgraph> {
     pub fn run_available(&mut self) -> bool {
         self.state.run_available_with_schedule(&mut self.df)
     }
+
+    pub fn snapshot(&self) -> DataflowSnapshot {
+        self.state.snapshot()
+    }
 }
 
 #[derive(Debug)]
@@ -170,6 +174,22 @@ impl WorkerHandle {
         }
     }
 
+    /// take snapshots of all flows' states in the worker
+    ///
+    /// the worker handle requests one by one, so snapshots are always taken between two runs
+    pub async fn snapshot_flows(&self) -> Result<BTreeMap<FlowId, DataflowSnapshot>, Error> {
+        let ret = self.itc_client.call_with_resp(Request::Snapshot).await?;
+
+        ret.into_snapshot().map_err(|ret| {
+            InternalSnafu {
+                reason: format!(
+                    "Flow Node/Worker itc failed, expect Response::Snapshot, found {ret:?}"
+                ),
+            }
+            .build()
+        })
+    }
+
     pub async fn contains_flow(&self, flow_id: FlowId) -> Result<bool, Error> {
         let req = Request::ContainTask { flow_id };
         let ret = self.itc_client.call_with_resp(req).await?;
@@ -232,6 +252,7 @@ impl<'s> Worker<'s> {
         expire_after: Option<repr::Duration>,
         create_if_not_exists: bool,
         err_collector: ErrCollector,
+        snapshot: Option<DataflowSnapshot>,
     ) -> Result<Option<FlowId>, Error> {
         let already_exists = self.task_states.contains_key(&flow_id);
         match (already_exists, create_if_not_exists) {
@@ -256,6 +277,14 @@ impl<'s> Worker<'s> {
             let rendered = ctx.render_plan(plan)?;
             ctx.render_unbounded_sink(rendered, sink_sender);
         }
+        if let Some(snapshot) = snapshot {
+            // states are untouched if failed to restore, so just start from scratch
+            if let Err(err) = cur_task_state.state.restore(snapshot) {
+                warn!(err; "Failed to restore flow {flow_id} from checkpoint, start with empty state");
+            } else {
+                info!("Restored flow {flow_id} from checkpoint");
+            }
+        }
         self.task_states.insert(flow_id, cur_task_state);
         Ok(Some(flow_id))
     }
@@ -326,6 +355,7 @@ impl<'s> Worker<'s> {
                 expire_after,
                 create_if_not_exists,
                 err_collector,
+                snapshot,
             } => {
                 let task_create_result = self.create_flow(
                     flow_id,
@@ -337,6 +367,7 @@ impl<'s> Worker<'s> {
                     expire_after,
                     create_if_not_exists,
                     err_collector,
+                    snapshot,
                 );
                 Some(Response::Create {
                     result: task_create_result,
@@ -358,6 +389,14 @@ impl<'s> Worker<'s> {
                 let ret = self.task_states.contains_key(&flow_id);
                 Some(Response::ContainTask { result: ret })
             }
+            Request::Snapshot => {
+                let ret = self
+                    .task_states
+                    .iter()
+                    .map(|(flow_id, task_state)| (*flow_id, task_state.snapshot()))
+                    .collect();
+                Some(Response::Snapshot { result: ret })
+            }
             Request::Shutdown => return Err(()),
         };
         Ok(ret)
@@ -376,6 +415,8 @@ pub enum Request {
         expire_after: Option<repr::Duration>,
         create_if_not_exists: bool,
         err_collector: ErrCollector,
+        /// restore the flow's state from this snapshot if any
+        snapshot: Option<DataflowSnapshot>,
     },
     Remove {
         flow_id: FlowId,
@@ -388,6 +429,8 @@ pub enum Request {
     ContainTask {
         flow_id: FlowId,
     },
+    /// Take snapshots of all flows' states
+    Snapshot,
     Shutdown,
 }
 
@@ -403,6 +446,9 @@ enum Response {
     ContainTask {
         result: bool,
     },
+    Snapshot {
+        result: BTreeMap<FlowId, DataflowSnapshot>,
+    },
     RunAvail,
 }
 
@@ -518,6 +564,7 @@ mod test {
             expire_after: None,
             create_if_not_exists: true,
             err_collector: ErrCollector::default(),
+            snapshot: None,
         };
         assert_eq!(
             handle.create_flow(create_reqs).await.unwrap(),
@@ -526,6 +573,9 @@ mod test {
         tx.send((Row::empty(), 0, 0)).unwrap();
         handle.run_available(0, true).await.unwrap();
         assert_eq!(sink_rx.recv().await.unwrap().0, Row::empty());
+        let snapshots = handle.snapshot_flows().await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[&flow_id].as_of, 0);
         drop(handle);
         worker_thread_handle.join().unwrap();
     }
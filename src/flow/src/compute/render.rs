@@ -27,14 +27,16 @@ use snafu::OptionExt;
 use super::state::Scheduler;
 use crate::compute::state::DataflowState;
 use crate::compute::types::{Collection, CollectionBundle, ErrCollector, Toff};
-use crate::error::{Error, InvalidQuerySnafu, NotImplementedSnafu};
+use crate::error::{Error, InvalidQuerySnafu};
 use crate::expr::{self, GlobalId, LocalId};
 use crate::plan::{Plan, TypedPlan};
 use crate::repr::{self, DiffRow};
 
+mod join;
 mod map;
 mod reduce;
 mod src_sink;
+mod union;
 
 /// The Context for build a Operator with id of `GlobalId`
 pub struct Context<'referred, 'df> {
@@ -101,14 +103,11 @@ impl<'referred, 'df> Context<'referred, 'df> {
                 key_val_plan,
                 reduce_plan,
             } => self.render_reduce(input, key_val_plan, reduce_plan, plan.schema.typ),
-            Plan::Join { .. } => NotImplementedSnafu {
-                reason: "Join is still WIP",
-            }
-            .fail(),
-            Plan::Union { .. } => NotImplementedSnafu {
-                reason: "Union is still WIP",
-            }
-            .fail(),
+            Plan::Join { inputs, plan } => self.render_join(inputs, plan),
+            Plan::Union {
+                inputs,
+                consolidate_output,
+            } => self.render_union(inputs, consolidate_output),
         }
     }
 
@@ -246,6 +245,14 @@ mod test {
     ) -> Rc<RefCell<Vec<DiffRow>>> {
         let collection = bundle.collection;
         let _arranged = bundle.arranged.pop_first().unwrap().1;
+        get_collection_output_handle(ctx, collection)
+    }
+
+    /// Like [`get_output_handle`], but for output without arrangement, i.e. join and union
+    pub fn get_collection_output_handle(
+        ctx: &mut Context,
+        collection: Collection<DiffRow>,
+    ) -> Rc<RefCell<Vec<DiffRow>>> {
         let output = Rc::new(RefCell::new(vec![]));
         let output_inner = output.clone();
         let _subgraph = ctx.df.add_subgraph_sink(
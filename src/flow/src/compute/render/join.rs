@@ -0,0 +1,817 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Render incremental joins
+//!
+//! A linear join is rendered as a chain of binary joins, each of them keeps both of
+//! its inputs arranged by join key so that an update from one side can be joined against
+//! the accumulated state of the other side.
+//!
+//! Both inputs are kept in full arrangements, so they are checkpointed like other states.
+//!
+//! If the flow has `expire_after` set, rows of each side are expired by their event timestamp,
+//! i.e. the time index column of that side, the same way reduce expires its keys. Expired rows
+//! are removed from the state as if they were deleted, so outputs joined with them are retracted,
+//! and late updates of them are ignored.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use datatypes::value::Value;
+use hydroflow::scheduled::graph_ext::GraphExt;
+use itertools::Itertools;
+use snafu::ensure;
+
+use crate::compute::render::{Context, SubgraphArg};
+use crate::compute::types::{Collection, CollectionBundle, ErrCollector, Toff};
+use crate::error::{Error, PlanSnafu};
+use crate::expr::error::DataAlreadyExpiredSnafu;
+use crate::expr::{EvalError, ScalarExpr};
+use crate::plan::{JoinFilter, JoinKind, JoinPlan, LinearStagePlan, TypedPlan};
+use crate::repr::{Diff, DiffRow, Row, Timestamp};
+use crate::utils::{ArrangeHandler, Arrangement, KeyExpiryManager};
+
+impl<'referred, 'df> Context<'referred, 'df> {
+    const JOIN: &'static str = "join";
+    const JOIN_FILTER: &'static str = "join_filter";
+
+    /// render `Plan::Join` into executable dataflow
+    pub fn render_join(
+        &mut self,
+        inputs: Vec<TypedPlan>,
+        plan: JoinPlan,
+    ) -> Result<CollectionBundle, Error> {
+        let JoinPlan::Linear(plan) = plan;
+
+        // every input should be used exactly once, otherwise the rendered inputs would dangle
+        let used = std::iter::once(plan.source_relation)
+            .chain(plan.stage_plans.iter().map(|s| s.lookup_relation))
+            .collect_vec();
+        let used_set: BTreeSet<usize> = used.iter().copied().collect();
+        ensure!(
+            used.len() == inputs.len()
+                && used_set.len() == inputs.len()
+                && used_set.iter().all(|i| *i < inputs.len()),
+            PlanSnafu {
+                reason: format!(
+                    "Join plan should use each of the {} inputs exactly once, found {:?}",
+                    inputs.len(),
+                    used
+                ),
+            }
+        );
+
+        let arities = inputs.iter().map(|i| i.schema.typ().arity()).collect_vec();
+        let time_indexes = inputs
+            .iter()
+            .map(|i| i.schema.typ().time_index)
+            .collect_vec();
+        let mut rendered = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            rendered.push(Some(self.render_plan(input)?));
+        }
+
+        let mut stream = rendered[plan.source_relation]
+            .take()
+            .expect("Checked each input is used once");
+        let mut stream_arity = arities[plan.source_relation];
+        // only the time index of the stream side is tracked through stages
+        let mut stream_time_index = time_indexes[plan.source_relation];
+        if let Some(closure) = plan.initial_closure {
+            ensure!(
+                closure.before.input_arity == stream_arity,
+                PlanSnafu {
+                    reason: format!(
+                        "Initial closure of join expect input arity {}, found {}",
+                        closure.before.input_arity, stream_arity
+                    ),
+                }
+            );
+            stream_arity = closure.before.output_arity();
+            stream_time_index =
+                stream_time_index.and_then(|ts| closure_output_column(&closure, ts));
+            stream = self.render_join_filter(stream, closure);
+        }
+
+        for stage in plan.stage_plans {
+            let lookup = rendered[stage.lookup_relation]
+                .take()
+                .expect("Checked each input is used once");
+            let lookup_arity = arities[stage.lookup_relation];
+            let lookup_time_index = time_indexes[stage.lookup_relation];
+            ensure!(
+                stage.stream_thinning.iter().all(|col| *col < stream_arity)
+                    && stage.closure.before.input_arity
+                        == stage.stream_thinning.len() + lookup_arity,
+                PlanSnafu {
+                    reason: format!(
+                        "Join stage expect stream arity {} and lookup arity {}, found stream thinning {:?} and closure input arity {}",
+                        stream_arity,
+                        lookup_arity,
+                        stage.stream_thinning,
+                        stage.closure.before.input_arity
+                    ),
+                }
+            );
+            let output_arity = stage.closure.before.output_arity();
+            let output_time_index = stream_time_index
+                .and_then(|ts| stage.stream_thinning.iter().position(|col| *col == ts))
+                .and_then(|ts| closure_output_column(&stage.closure, ts));
+            stream = self.render_join_stage(
+                stream,
+                lookup,
+                lookup_arity,
+                (stream_time_index, lookup_time_index),
+                stage,
+            );
+            stream_arity = output_arity;
+            stream_time_index = output_time_index;
+        }
+
+        if let Some(closure) = plan.final_closure {
+            ensure!(
+                closure.before.input_arity == stream_arity,
+                PlanSnafu {
+                    reason: format!(
+                        "Final closure of join expect input arity {}, found {}",
+                        closure.before.input_arity, stream_arity
+                    ),
+                }
+            );
+            stream = self.render_join_filter(stream, closure);
+        }
+
+        Ok(stream)
+    }
+
+    /// render a binary join between the accumulated join result (the stream) and a new input (the lookup)
+    ///
+    /// `time_indexes` are the time index columns of the stream and the lookup side, which are
+    /// used to expire rows of that side if `expire_after` is set.
+    fn render_join_stage(
+        &mut self,
+        stream: CollectionBundle,
+        lookup: CollectionBundle,
+        lookup_arity: usize,
+        time_indexes: (Option<usize>, Option<usize>),
+        stage: LinearStagePlan,
+    ) -> CollectionBundle {
+        let (out_send_port, out_recv_port) = self.df.make_edge::<_, Toff>(Self::JOIN);
+
+        let now = self.compute_state.current_time_ref();
+        let err_collector = self.err_collector.clone();
+        let scheduler = self.compute_state.get_scheduler();
+        let scheduler_inner = scheduler.clone();
+
+        let stream_arrange =
+            self.new_join_arrange("stream", stage.stream_key.len(), time_indexes.0);
+        let lookup_arrange =
+            self.new_join_arrange("lookup", stage.lookup_key.len(), time_indexes.1);
+
+        let subgraph = self.df.add_subgraph_2in_out(
+            Self::JOIN,
+            stream.collection.into_inner(),
+            lookup.collection.into_inner(),
+            out_send_port,
+            move |_ctx, stream_recv, lookup_recv, send| {
+                let stream_updates = stream_recv
+                    .take_inner()
+                    .into_iter()
+                    .flat_map(|v| v.into_iter())
+                    .collect_vec();
+                let lookup_updates = lookup_recv
+                    .take_inner()
+                    .into_iter()
+                    .flat_map(|v| v.into_iter())
+                    .collect_vec();
+
+                join_subgraph(
+                    JoinArranges {
+                        stream: &mut stream_arrange.write(),
+                        lookup: &mut lookup_arrange.write(),
+                    },
+                    stream_updates,
+                    lookup_updates,
+                    &stage,
+                    lookup_arity,
+                    SubgraphArg {
+                        now: *now.borrow(),
+                        err_collector: &err_collector,
+                        scheduler: &scheduler_inner,
+                        send,
+                    },
+                );
+            },
+        );
+
+        scheduler.set_cur_subgraph(subgraph);
+
+        CollectionBundle::from_collection(Collection::from_port(out_recv_port))
+    }
+
+    /// create the full arrangement of one side of a binary join, see [`JoinArranges`] for its layout
+    ///
+    /// rows are expired by their time index if `expire_after` is set
+    fn new_join_arrange(
+        &mut self,
+        side: &str,
+        key_arity: usize,
+        time_index: Option<usize>,
+    ) -> ArrangeHandler {
+        let arrange = self
+            .compute_state
+            .new_arrange(Some(vec![Self::JOIN.to_string(), side.to_string()]));
+        arrange.set_full_arrangement(true);
+        if let (Some(time_index), Some(expire_after)) =
+            (time_index, self.compute_state.expire_after())
+        {
+            let expire_man = KeyExpiryManager::new(
+                Some(expire_after),
+                Some(ScalarExpr::Column(key_arity + time_index)),
+            );
+            arrange.write().set_expire_state(expire_man);
+        }
+        arrange
+    }
+
+    /// render the initial/final closure of a join, which filter and reshape each row
+    fn render_join_filter(
+        &mut self,
+        input: CollectionBundle,
+        filter: JoinFilter,
+    ) -> CollectionBundle {
+        let (out_send_port, out_recv_port) = self.df.make_edge::<_, Toff>(Self::JOIN_FILTER);
+        let now = self.compute_state.current_time_ref();
+        let err_collector = self.err_collector.clone();
+
+        self.df.add_subgraph_in_out(
+            Self::JOIN_FILTER,
+            input.collection.into_inner(),
+            out_send_port,
+            move |_ctx, recv, send| {
+                let now = *now.borrow();
+                let output = recv
+                    .take_inner()
+                    .into_iter()
+                    .flat_map(|v| v.into_iter())
+                    .filter_map(|(row, _ts, diff)| {
+                        err_collector
+                            .run(|| eval_join_filter(&filter, row))
+                            .flatten()
+                            .map(|row| (row, now, diff))
+                    })
+                    .collect_vec();
+                send.give(output);
+            },
+        );
+
+        CollectionBundle::from_collection(Collection::from_port(out_recv_port))
+    }
+}
+
+/// Evaluate the join key of a row
+fn eval_join_key(key_exprs: &[ScalarExpr], row: &Row) -> Result<Row, EvalError> {
+    let key: Vec<Value> = key_exprs.iter().map(|e| e.eval(&row.inner)).try_collect()?;
+    Ok(Row::new(key))
+}
+
+/// A join key with null never matches anything
+fn has_null(key: &Row) -> bool {
+    key.iter().any(|v| v.is_null())
+}
+
+/// Check the equivalences of the filter and then apply its map filter project
+fn eval_join_filter(filter: &JoinFilter, row: Row) -> Result<Option<Row>, EvalError> {
+    for equivalence in &filter.ready_equivalences {
+        let mut values = equivalence.iter().map(|e| e.eval(&row.inner));
+        if let Some(first) = values.next() {
+            let first = first?;
+            for value in values {
+                let value = value?;
+                if first.is_null() || value.is_null() || value != first {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+    let mut values = row.unpack();
+    let mut row_buf = Row::empty();
+    filter.before.evaluate_into(&mut values, &mut row_buf)
+}
+
+/// Find the output column of `filter` which is the input column `col` as is
+fn closure_output_column(filter: &JoinFilter, col: usize) -> Option<usize> {
+    filter.before.mfp.projection.iter().position(|c| *c == col)
+}
+
+/// Concat the retained columns of the stream row with the lookup row
+fn concat_row(thinning: &[usize], left: &Row, right: &Row) -> Row {
+    Row::pack(
+        thinning
+            .iter()
+            .map(|i| &left.inner[*i])
+            .chain(right.iter())
+            .cloned(),
+    )
+}
+
+fn pad_null(thinning: &[usize], left: &Row, arity: usize) -> Row {
+    Row::pack(
+        thinning
+            .iter()
+            .map(|i| left.inner[*i].clone())
+            .chain(std::iter::repeat(Value::Null).take(arity)),
+    )
+}
+
+/// Both sides of a binary join, borrowed from their arrangements.
+///
+/// Each side is a full arrangement keyed by `join_key ++ row` with an empty value, and the
+/// diff of the value is the multiplicity of the row. So rows with the same join key can be
+/// found by [`Arrangement::get_with_prefix`], and are expired the same way as keys of reduce.
+struct JoinArranges<'a> {
+    stream: &'a mut Arrangement,
+    lookup: &'a mut Arrangement,
+}
+
+/// Key a row by `join_key ++ row` to arrange it
+fn arranged_key(key: &Row, row: &Row) -> Row {
+    Row::pack(key.iter().chain(row.iter()).cloned())
+}
+
+/// Get rows with the given join key and their multiplicities from one side of a join
+fn get_rows(arrange: &Arrangement, now: Timestamp, key: &Row) -> Vec<(Row, Diff)> {
+    arrange
+        .get_with_prefix(now, key)
+        .into_iter()
+        .map(|(arranged, (_val, _ts, diff))| (Row::new(arranged.inner[key.len()..].to_vec()), diff))
+        .collect()
+}
+
+/// Get rows expired by `now` from one side of a join grouped by their join key, they are
+/// removed from the arrangement.
+fn truncate_expired_rows(
+    arrange: &mut Arrangement,
+    now: Timestamp,
+    key_arity: usize,
+) -> BTreeMap<Row, Vec<(Row, Diff)>> {
+    let mut expired: BTreeMap<Row, Vec<(Row, Diff)>> = BTreeMap::new();
+    for (mut arranged, (_val, _ts, diff)) in arrange.truncate_expired_keys(now) {
+        let row = Row::new(arranged.inner.split_off(key_arity));
+        expired.entry(arranged).or_default().push((row, diff));
+    }
+    expired
+}
+
+/// Check if the arranged row is already expired by `now`.
+///
+/// Expired rows are ignored in computation, and a simple warning is logged.
+fn is_expired(
+    arrange: &Arrangement,
+    now: Timestamp,
+    arranged: &Row,
+    err_collector: &ErrCollector,
+) -> bool {
+    let Some(expiry) = arrange.get_expire_state() else {
+        return false;
+    };
+    let Some(Some(expired_by)) = err_collector.run(|| expiry.get_expire_duration(now, arranged))
+    else {
+        return false;
+    };
+    common_telemetry::warn!(
+        "Data already expired: {}",
+        DataAlreadyExpiredSnafu { expired_by }.build()
+    );
+    true
+}
+
+/// Join rows of a binary join stage, and collect the joined rows before applying the closure
+struct StageJoiner<'a> {
+    stage: &'a LinearStagePlan,
+    lookup_arity: usize,
+    now: Timestamp,
+    output: Vec<(Row, Diff)>,
+}
+
+impl StageJoiner<'_> {
+    /// Join a stream row with the lookup side, or pad it with nulls if unmatched in a left join
+    fn join_stream_row(&mut self, lookup: &Arrangement, key: &Row, row: &Row, diff: Diff) {
+        let thinning = &self.stage.stream_thinning;
+        let matches = if has_null(key) {
+            vec![]
+        } else {
+            get_rows(lookup, self.now, key)
+        };
+        if matches.is_empty() {
+            if self.stage.kind == JoinKind::Left {
+                self.output
+                    .push((pad_null(thinning, row, self.lookup_arity), diff));
+            }
+            return;
+        }
+        for (lookup_row, lookup_diff) in matches {
+            self.output
+                .push((concat_row(thinning, row, &lookup_row), diff * lookup_diff));
+        }
+    }
+
+    /// Join lookup rows of the same key with the stream side.
+    ///
+    /// For a left join, the null padded stream rows are retracted/re-emitted if the lookup side
+    /// of that key becomes non-empty/empty, as told by `was_matched` and `is_matched`.
+    fn join_lookup_rows(
+        &mut self,
+        stream: &Arrangement,
+        key: &Row,
+        rows: &[(Row, Diff)],
+        was_matched: bool,
+        is_matched: bool,
+    ) {
+        let thinning = &self.stage.stream_thinning;
+        for (stream_row, stream_diff) in get_rows(stream, self.now, key) {
+            for (row, diff) in rows {
+                self.output
+                    .push((concat_row(thinning, &stream_row, row), stream_diff * diff));
+            }
+            if self.stage.kind == JoinKind::Left && was_matched != is_matched {
+                let padded = pad_null(thinning, &stream_row, self.lookup_arity);
+                if is_matched {
+                    self.output.push((padded, -stream_diff));
+                } else {
+                    self.output.push((padded, stream_diff));
+                }
+            }
+        }
+    }
+}
+
+/// Incrementally join the updates of both sides.
+///
+/// The output delta is `Δstream ⋈ lookup + stream' ⋈ Δlookup` where `stream'` already
+/// contains `Δstream`, so that each pair of rows is joined exactly once.
+///
+/// For a left join, stream rows without any match are padded with nulls, and such padded
+/// rows are retracted/re-emitted when the lookup side of that key becomes non-empty/empty.
+///
+/// Rows expired by `now` are removed from both sides first as if they were deleted, so the
+/// outputs joined with them are retracted. Later updates of expired rows are ignored.
+fn join_subgraph(
+    JoinArranges { stream, lookup }: JoinArranges,
+    stream_updates: Vec<DiffRow>,
+    lookup_updates: Vec<DiffRow>,
+    stage: &LinearStagePlan,
+    lookup_arity: usize,
+    SubgraphArg {
+        now,
+        err_collector,
+        scheduler: _,
+        send,
+    }: SubgraphArg,
+) {
+    let is_left = stage.kind == JoinKind::Left;
+    let mut joiner = StageJoiner {
+        stage,
+        lookup_arity,
+        now,
+        output: Vec::new(),
+    };
+
+    for (key, rows) in truncate_expired_rows(stream, now, stage.stream_key.len()) {
+        for (row, diff) in rows {
+            joiner.join_stream_row(lookup, &key, &row, -diff);
+        }
+    }
+    for (key, rows) in truncate_expired_rows(lookup, now, stage.lookup_key.len()) {
+        let rows = rows
+            .into_iter()
+            .map(|(row, diff)| (row, -diff))
+            .collect_vec();
+        let is_matched = !get_rows(lookup, now, &key).is_empty();
+        joiner.join_lookup_rows(stream, &key, &rows, true, is_matched);
+    }
+
+    for (row, _ts, diff) in stream_updates {
+        let Some(key) = err_collector.run(|| eval_join_key(&stage.stream_key, &row)) else {
+            continue;
+        };
+        // stream rows with null key are only kept for the null padded output of left join
+        if has_null(&key) && !is_left {
+            continue;
+        }
+        let arranged = arranged_key(&key, &row);
+        if is_expired(stream, now, &arranged, err_collector) {
+            continue;
+        }
+        joiner.join_stream_row(lookup, &key, &row, diff);
+        err_collector
+            .run(|| stream.apply_updates(now, vec![((arranged, Row::empty()), now, diff)]));
+    }
+
+    for (row, _ts, diff) in lookup_updates {
+        let Some(key) = err_collector.run(|| eval_join_key(&stage.lookup_key, &row)) else {
+            continue;
+        };
+        if has_null(&key) {
+            continue;
+        }
+        let arranged = arranged_key(&key, &row);
+        if is_expired(lookup, now, &arranged, err_collector) {
+            continue;
+        }
+        let was_matched = !get_rows(lookup, now, &key).is_empty();
+        err_collector
+            .run(|| lookup.apply_updates(now, vec![((arranged, Row::empty()), now, diff)]));
+        let is_matched = !get_rows(lookup, now, &key).is_empty();
+        joiner.join_lookup_rows(stream, &key, &[(row, diff)], was_matched, is_matched);
+    }
+
+    // consolidate the arranged rows of both sides, nothing is expired at this point
+    err_collector.run(|| stream.compact_to(now));
+    err_collector.run(|| lookup.compact_to(now));
+
+    // consolidate output so that a retraction and insertion of the same row cancel out
+    let mut consolidated: BTreeMap<Row, Diff> = BTreeMap::new();
+    for (row, diff) in joiner.output {
+        let Some(row) = err_collector
+            .run(|| eval_join_filter(&stage.closure, row))
+            .flatten()
+        else {
+            continue;
+        };
+        *consolidated.entry(row).or_default() += diff;
+    }
+
+    let output = consolidated
+        .into_iter()
+        .filter(|(_row, diff)| *diff != 0)
+        .map(|(row, diff)| (row, now, diff))
+        .collect_vec();
+    send.give(output);
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::data_type::ConcreteDataType;
+    use hydroflow::scheduled::graph::Hydroflow;
+
+    use super::*;
+    use crate::compute::render::test::{
+        get_collection_output_handle, harness_test_ctx, run_and_check,
+    };
+    use crate::compute::state::DataflowState;
+    use crate::expr::{self, GlobalId};
+    use crate::plan::{LinearJoinPlan, Plan};
+    use crate::repr::{ColumnType, RelationType};
+
+    fn row(id: i64, name: &str) -> Row {
+        Row::new(vec![id.into(), name.into()])
+    }
+
+    fn joined(left: (i64, &str), right: Option<(i64, &str)>) -> Row {
+        let mut ret = row(left.0, left.1);
+        match right {
+            Some((id, name)) => ret.extend([id.into(), name.into()]),
+            None => ret.extend([Value::Null, Value::Null]),
+        }
+        ret
+    }
+
+    /// join `User(1)` and `User(2)` on their first column
+    fn join_plan(kind: JoinKind, time_index: Option<usize>) -> TypedPlan {
+        let typ = RelationType::new(vec![
+            ColumnType::new(ConcreteDataType::int64_datatype(), false),
+            ColumnType::new(ConcreteDataType::string_datatype(), false),
+        ])
+        .with_time_index(time_index);
+        let get = |id| {
+            Plan::Get {
+                id: expr::Id::Global(GlobalId::User(id)),
+            }
+            .with_types(typ.clone().into_unnamed())
+        };
+        Plan::Join {
+            inputs: vec![get(1), get(2)],
+            plan: JoinPlan::Linear(LinearJoinPlan {
+                source_relation: 0,
+                source_key: None,
+                initial_closure: None,
+                stage_plans: vec![LinearStagePlan {
+                    lookup_relation: 1,
+                    stream_key: vec![ScalarExpr::Column(0)],
+                    stream_thinning: vec![0, 1],
+                    lookup_key: vec![ScalarExpr::Column(0)],
+                    kind,
+                    closure: JoinFilter::identity(4),
+                }],
+                final_closure: None,
+            }),
+        }
+        .with_types(
+            RelationType::new(vec![
+                ColumnType::new(ConcreteDataType::int64_datatype(), false),
+                ColumnType::new(ConcreteDataType::string_datatype(), false),
+                ColumnType::new(ConcreteDataType::int64_datatype(), true),
+                ColumnType::new(ConcreteDataType::string_datatype(), true),
+            ])
+            .into_unnamed(),
+        )
+    }
+
+    fn insert_join_inputs(ctx: &mut Context) {
+        let left = vec![
+            (row(1, "a"), 1, 1),
+            (row(2, "b"), 1, 1),
+            (row(3, "c"), 2, 1),
+        ];
+        let right = vec![
+            (row(1, "x"), 1, 1),
+            (row(3, "z"), 3, 1),
+            (row(1, "x"), 4, -1),
+        ];
+        let left = ctx.render_constant(left);
+        ctx.insert_global(GlobalId::User(1), left);
+        let right = ctx.render_constant(right);
+        ctx.insert_global(GlobalId::User(2), right);
+    }
+
+    #[test]
+    fn test_render_inner_join() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_join_inputs(&mut ctx);
+
+        let bundle = ctx.render_plan(join_plan(JoinKind::Inner, None)).unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        let expected = BTreeMap::from([
+            (1, vec![(joined((1, "a"), Some((1, "x"))), 1, 1)]),
+            (3, vec![(joined((3, "c"), Some((3, "z"))), 3, 1)]),
+            (4, vec![(joined((1, "a"), Some((1, "x"))), 4, -1)]),
+        ]);
+        run_and_check(&mut state, &mut df, 0..5, expected, output);
+    }
+
+    #[test]
+    fn test_render_left_join() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_join_inputs(&mut ctx);
+
+        let bundle = ctx.render_plan(join_plan(JoinKind::Left, None)).unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        let expected = BTreeMap::from([
+            (
+                1,
+                vec![
+                    (joined((1, "a"), Some((1, "x"))), 1, 1),
+                    (joined((2, "b"), None), 1, 1),
+                ],
+            ),
+            (2, vec![(joined((3, "c"), None), 2, 1)]),
+            (
+                3,
+                vec![
+                    (joined((3, "c"), None), 3, -1),
+                    (joined((3, "c"), Some((3, "z"))), 3, 1),
+                ],
+            ),
+            (
+                4,
+                vec![
+                    (joined((1, "a"), None), 4, 1),
+                    (joined((1, "a"), Some((1, "x"))), 4, -1),
+                ],
+            ),
+        ]);
+        run_and_check(&mut state, &mut df, 0..5, expected, output);
+    }
+
+    #[test]
+    fn test_render_join_expire() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        // rows are expired by their first column
+        state.set_expire_after(Some(1));
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_join_inputs(&mut ctx);
+
+        let bundle = ctx
+            .render_plan(join_plan(JoinKind::Inner, Some(0)))
+            .unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        // rows of key 1 are expired at 3 and their output is retracted,
+        // so the retraction at 4 is ignored
+        let expected = BTreeMap::from([
+            (1, vec![(joined((1, "a"), Some((1, "x"))), 1, 1)]),
+            (
+                3,
+                vec![
+                    (joined((1, "a"), Some((1, "x"))), 3, -1),
+                    (joined((3, "c"), Some((3, "z"))), 3, 1),
+                ],
+            ),
+        ]);
+        run_and_check(&mut state, &mut df, 0..5, expected, output);
+    }
+
+    #[test]
+    fn test_render_left_join_expire() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        // rows are expired by their first column
+        state.set_expire_after(Some(1));
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_join_inputs(&mut ctx);
+
+        let bundle = ctx.render_plan(join_plan(JoinKind::Left, Some(0))).unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        // expired stream rows retract their null padded output too
+        let expected = BTreeMap::from([
+            (
+                1,
+                vec![
+                    (joined((1, "a"), Some((1, "x"))), 1, 1),
+                    (joined((2, "b"), None), 1, 1),
+                ],
+            ),
+            (2, vec![(joined((3, "c"), None), 2, 1)]),
+            (
+                3,
+                vec![
+                    (joined((1, "a"), Some((1, "x"))), 3, -1),
+                    (joined((3, "c"), None), 3, -1),
+                    (joined((3, "c"), Some((3, "z"))), 3, 1),
+                ],
+            ),
+            (4, vec![(joined((2, "b"), None), 4, -1)]),
+        ]);
+        run_and_check(&mut state, &mut df, 0..5, expected, output);
+
+        // only rows of key 3 are left in the arrangements of both sides, the stream side first
+        let snapshot = state.snapshot();
+        let restored = snapshot
+            .arrangements
+            .iter()
+            .map(|arr| {
+                let mut restored = Arrangement::default();
+                restored.restore(arr.clone());
+                restored.get_with_prefix(4, &Row::empty())
+            })
+            .filter(|rows| !rows.is_empty())
+            .collect_vec();
+        assert_eq!(
+            restored,
+            vec![
+                vec![(
+                    Row::new(vec![3i64.into(), 3i64.into(), "c".into()]),
+                    (Row::empty(), 2, 1)
+                )],
+                vec![(
+                    Row::new(vec![3i64.into(), 3i64.into(), "z".into()]),
+                    (Row::empty(), 3, 1)
+                )],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_join_filter() {
+        // keep rows whose two columns are equal, and only output the first column
+        let filter = JoinFilter {
+            ready_equivalences: vec![vec![ScalarExpr::Column(0), ScalarExpr::Column(1)]],
+            before: crate::expr::MapFilterProject::new(2)
+                .project(vec![0])
+                .unwrap()
+                .into_safe(),
+        };
+        let eq = Row::new(vec![1i64.into(), 1i64.into()]);
+        let not_eq = Row::new(vec![1i64.into(), 2i64.into()]);
+        let null = Row::new(vec![Value::Null, Value::Null]);
+        assert_eq!(
+            eval_join_filter(&filter, eq).unwrap(),
+            Some(Row::new(vec![1i64.into()]))
+        );
+        assert_eq!(eval_join_filter(&filter, not_eq).unwrap(), None);
+        assert_eq!(eval_join_filter(&filter, null).unwrap(), None);
+    }
+}
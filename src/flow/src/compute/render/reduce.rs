@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::Range;
+use std::rc::Rc;
 
+use common_time::Timestamp;
 use datatypes::data_type::ConcreteDataType;
 use datatypes::value::{ListValue, Value};
 use hydroflow::scheduled::graph_ext::GraphExt;
@@ -26,9 +29,11 @@ use crate::compute::types::{Arranged, Collection, CollectionBundle, ErrCollector
 use crate::error::{Error, PlanSnafu};
 use crate::expr::error::{DataAlreadyExpiredSnafu, DataTypeSnafu, InternalSnafu};
 use crate::expr::{EvalError, ScalarExpr};
-use crate::plan::{AccumulablePlan, AggrWithIndex, KeyValPlan, ReducePlan, TypedPlan};
-use crate::repr::{self, DiffRow, KeyValDiffRow, RelationType, Row};
-use crate::utils::{ArrangeHandler, ArrangeReader, ArrangeWriter, KeyExpiryManager};
+use crate::plan::{AccumulablePlan, AggrWithIndex, KeyValPlan, ReducePlan, TypedPlan, WindowPlan};
+use crate::repr::{self, value_to_internal_ts, DiffRow, KeyValDiffRow, RelationType, Row};
+use crate::utils::{
+    ArrangeHandler, ArrangeReader, ArrangeWriter, KeyExpiryManager, SessionRows, SessionState,
+};
 
 impl<'referred, 'df> Context<'referred, 'df> {
     const REDUCE: &'static str = "reduce";
@@ -52,11 +57,17 @@ impl<'referred, 'df> Context<'referred, 'df> {
         // TODO(discord9): config global expire time from self
         let arrange_handler = self.compute_state.new_arrange(None);
 
-        if let (Some(time_index), Some(expire_after)) =
-            (output_type.time_index, self.compute_state.expire_after())
+        let window = key_val_plan.get_window_plan()?;
+        // a session can still be updated before it's closed, so its output expires by its end
+        let expire_col = match window {
+            Some(WindowPlan::Session { end_col, .. }) => Some(end_col),
+            _ => output_type.time_index,
+        };
+        if let (Some(expire_col), Some(expire_after)) =
+            (expire_col, self.compute_state.expire_after())
         {
             let expire_man =
-                KeyExpiryManager::new(Some(expire_after), Some(ScalarExpr::Column(time_index)));
+                KeyExpiryManager::new(Some(expire_after), Some(ScalarExpr::Column(expire_col)));
             arrange_handler.write().set_expire_state(expire_man);
         }
 
@@ -67,9 +78,22 @@ impl<'referred, 'df> Context<'referred, 'df> {
 
         let distinct_input = self.add_accum_distinct_input_arrange(&reduce_plan);
 
+        // session windows are recomputed from all rows in the same group
+        let session_state = matches!(window, Some(WindowPlan::Session { .. })).then(|| {
+            let state = self.compute_state.new_session_state();
+            if let Some(expire_after) = self.compute_state.expire_after() {
+                // groups are keys without the two window columns
+                state
+                    .borrow_mut()
+                    .set_expire_state(expire_after, output_key_arity - 2);
+            }
+            state
+        });
+
         let reduce_arrange = ReduceArrange {
             output_arrange: arrange_handler_inner,
             distinct_input,
+            session_state,
         };
 
         let now = self.compute_state.current_time_ref();
@@ -98,6 +122,7 @@ impl<'referred, 'df> Context<'referred, 'df> {
                     &reduce_arrange,
                     data,
                     &key_val_plan,
+                    window,
                     &reduce_plan,
                     SubgraphArg {
                         now: *now.borrow(),
@@ -158,6 +183,8 @@ pub struct ReduceArrange {
     /// The distinct input arrangement for accumulable reduce plan
     /// only used when accumulable reduce plan has distinct aggregation
     distinct_input: Option<Vec<ArrangeHandler>>,
+    /// All rows grouped by key without session window, only used when there is a session window
+    session_state: Option<Rc<RefCell<SessionState>>>,
 }
 
 /// split a row into key and val by evaluate the key and val plan
@@ -186,13 +213,17 @@ fn split_row_to_key_val(
 }
 
 /// split a row into key and val by evaluate the key and val plan
+///
+/// a row is split into multiple key-val pairs if it belongs to multiple hop windows
 fn batch_split_rows_to_key_val(
     rows: impl IntoIterator<Item = DiffRow>,
     key_val_plan: KeyValPlan,
+    window: Option<WindowPlan>,
     err_collector: ErrCollector,
 ) -> impl IntoIterator<Item = KeyValDiffRow> {
     let mut row_buf = Row::new(vec![]);
-    rows.into_iter().filter_map(
+    let err_collector_inner = err_collector.clone();
+    let key_vals = rows.into_iter().filter_map(
         move |(mut row, sys_time, diff): DiffRow| -> Option<KeyValDiffRow> {
             err_collector.run(|| {
                 let len = row.len();
@@ -215,7 +246,50 @@ fn batch_split_rows_to_key_val(
                 }
             })?
         },
-    )
+    );
+    key_vals.flat_map(move |key_val| match window {
+        Some(WindowPlan::Hop {
+            start_col,
+            end_col,
+            window_size,
+            slide,
+        }) => err_collector_inner
+            .run(|| expand_hop_windows(key_val, start_col, end_col, window_size, slide))
+            .unwrap_or_default(),
+        _ => vec![key_val],
+    })
+}
+
+/// expand a key-val pair into one per hop window, the window columns of key are expected to be
+/// the start of the last window and the end of the first window it belongs to
+fn expand_hop_windows(
+    ((key, val), sys_time, diff): KeyValDiffRow,
+    start_col: usize,
+    end_col: usize,
+    window_size: repr::Duration,
+    slide: repr::Duration,
+) -> Result<Vec<KeyValDiffRow>, EvalError> {
+    let get_ts = |col: usize| {
+        key.get(col)
+            .cloned()
+            .context(InternalSnafu {
+                reason: format!("Window column {col} not found in key {key:?}"),
+            })
+            .and_then(value_to_internal_ts)
+    };
+    let last_start = get_ts(start_col)?;
+    let first_end = get_ts(end_col)?;
+
+    let mut ret = Vec::new();
+    let mut start = first_end - window_size;
+    while start <= last_start {
+        let mut key = key.clone();
+        key.inner[start_col] = Timestamp::new_millisecond(start).into();
+        key.inner[end_col] = Timestamp::new_millisecond(start + window_size).into();
+        ret.push(((key, val.clone()), sys_time, diff));
+        start += slide;
+    }
+    Ok(ret)
 }
 
 /// reduce subgraph, reduce the input data into a single row
@@ -224,9 +298,11 @@ fn reduce_subgraph(
     ReduceArrange {
         output_arrange: arrange,
         distinct_input,
+        session_state,
     }: &ReduceArrange,
     data: impl IntoIterator<Item = DiffRow>,
     key_val_plan: &KeyValPlan,
+    window: Option<WindowPlan>,
     reduce_plan: &ReducePlan,
     SubgraphArg {
         now,
@@ -235,7 +311,37 @@ fn reduce_subgraph(
         send,
     }: SubgraphArg,
 ) {
-    let key_val = batch_split_rows_to_key_val(data, key_val_plan.clone(), err_collector.clone());
+    let key_val =
+        batch_split_rows_to_key_val(data, key_val_plan.clone(), window, err_collector.clone());
+    if let (
+        Some(WindowPlan::Session {
+            start_col,
+            end_col,
+            gap,
+        }),
+        Some(session_state),
+    ) = (window, session_state)
+    {
+        let session = SessionWindow {
+            start_col,
+            end_col,
+            gap,
+        };
+        reduce_session_subgraph(
+            arrange,
+            &mut session_state.borrow_mut(),
+            key_val,
+            &session,
+            reduce_plan,
+            SubgraphArg {
+                now,
+                err_collector,
+                scheduler,
+                send,
+            },
+        );
+        return;
+    }
     // from here for distinct reduce and accum reduce, things are drastically different
     // for distinct reduce the arrange store the output,
     // but for accum reduce the arrange store the accum state, and output is
@@ -266,6 +372,263 @@ fn reduce_subgraph(
     };
 }
 
+/// Position of session window columns in key, and the gap of session window
+#[derive(Debug, Clone, Copy)]
+struct SessionWindow {
+    start_col: usize,
+    end_col: usize,
+    gap: repr::Duration,
+}
+
+impl SessionWindow {
+    /// split key into group(key without window columns) and the timestamp of the row
+    fn split_key(&self, key: Row) -> Result<(Row, repr::Timestamp), EvalError> {
+        let ts = key.get(self.start_col).cloned().context(InternalSnafu {
+            reason: format!("Window column {} not found in key {key:?}", self.start_col),
+        })?;
+        let ts = value_to_internal_ts(ts)?;
+        let group = key
+            .inner
+            .into_iter()
+            .enumerate()
+            .filter(|(col, _)| *col != self.start_col && *col != self.end_col)
+            .map(|(_, v)| v)
+            .collect();
+        Ok((Row::new(group), ts))
+    }
+
+    /// insert window columns back into group
+    fn to_key(&self, group: &Row, start: repr::Timestamp, end: repr::Timestamp) -> Row {
+        let mut key = group.inner.clone();
+        let mut cols = [(self.start_col, start), (self.end_col, end)];
+        // insert smaller column first, so both end up at the right position
+        cols.sort_by_key(|(col, _)| *col);
+        for (col, ts) in cols {
+            key.insert(col, Timestamp::new_millisecond(ts).into());
+        }
+        Row::new(key)
+    }
+
+    /// Find ranges of timestamps whose sessions might be changed by updates at `update_ts`
+    ///
+    /// No timestamp in `rows` or `update_ts` is within `gap` outside of a range,
+    /// so every session before or after the updates is either entirely inside a range or outside of all ranges
+    fn affected_ranges(
+        &self,
+        rows: Option<&SessionRows>,
+        update_ts: &BTreeSet<repr::Timestamp>,
+    ) -> Vec<(repr::Timestamp, repr::Timestamp)> {
+        let prev = |ts: repr::Timestamp| {
+            let in_rows = rows.and_then(|rows| rows.range(..ts).next_back().map(|(t, _)| *t));
+            let in_updates = update_ts.range(..ts).next_back().copied();
+            in_rows.max(in_updates)
+        };
+        let next = |ts: repr::Timestamp| {
+            let in_rows = rows.and_then(|rows| rows.range(ts + 1..).next().map(|(t, _)| *t));
+            let in_updates = update_ts.range(ts + 1..).next().copied();
+            match (in_rows, in_updates) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        };
+
+        let mut ranges: Vec<(repr::Timestamp, repr::Timestamp)> = Vec::new();
+        for ts in update_ts {
+            if ranges.last().is_some_and(|(_, hi)| ts <= hi) {
+                continue;
+            }
+            let (mut lo, mut hi) = (*ts, *ts);
+            while let Some(p) = prev(lo).filter(|p| lo - p < self.gap) {
+                lo = p;
+            }
+            while let Some(n) = next(hi).filter(|n| n - hi < self.gap) {
+                hi = n;
+            }
+            ranges.push((lo, hi));
+        }
+        ranges
+    }
+
+    /// Split rows with timestamp in `[lo, hi]` into sessions, return `(start, end, rows)` of each session
+    fn sessions_in(
+        &self,
+        rows: &SessionRows,
+        lo: repr::Timestamp,
+        hi: repr::Timestamp,
+    ) -> Vec<(repr::Timestamp, repr::Timestamp, Vec<(Row, repr::Diff)>)> {
+        let mut sessions = Vec::new();
+        // (start, last timestamp, rows) of current session
+        let mut cur: Option<(repr::Timestamp, repr::Timestamp, Vec<(Row, repr::Diff)>)> = None;
+        for (ts, rows_at_ts) in rows.range(lo..=hi) {
+            let rows_at_ts = rows_at_ts.iter().map(|(row, diff)| (row.clone(), *diff));
+            if let Some((_, last, acc)) = cur.as_mut().filter(|(_, last, _)| ts - last < self.gap) {
+                *last = *ts;
+                acc.extend(rows_at_ts);
+            } else {
+                if let Some((start, last, acc)) = cur.take() {
+                    sessions.push((start, last + self.gap, acc));
+                }
+                cur = Some((*ts, *ts, rows_at_ts.collect()));
+            }
+        }
+        if let Some((start, last, acc)) = cur {
+            sessions.push((start, last + self.gap, acc));
+        }
+        sessions
+    }
+}
+
+/// reduce with session window, output the changes of sessions
+///
+/// Since new rows might merge sessions and deletion might split one, sessions affected by
+/// the updates are retracted and then emitted again with all their rows recomputed from scratch.
+///
+/// The output is also written to the output arrangement, closed sessions are removed from
+/// both the arrangement and the session state.
+fn reduce_session_subgraph(
+    arrange: &ArrangeHandler,
+    state: &mut SessionState,
+    kv: impl IntoIterator<Item = KeyValDiffRow>,
+    session: &SessionWindow,
+    reduce_plan: &ReducePlan,
+    SubgraphArg {
+        now,
+        err_collector,
+        scheduler: _,
+        send,
+    }: SubgraphArg,
+) {
+    let mut group_updates = BTreeMap::<Row, Vec<(repr::Timestamp, Row, repr::Diff)>>::new();
+    for ((key, val), _tick, diff) in kv {
+        let Some((group, ts)) = err_collector.run(|| session.split_key(key)) else {
+            continue;
+        };
+        if let Some(Some(expired_by)) =
+            err_collector.run(|| state.get_expire_duration(now, &group, ts))
+        {
+            // expired data is ignored in computation, and a simple warning is logged
+            common_telemetry::warn!(
+                "Data already expired: {}",
+                DataAlreadyExpiredSnafu { expired_by }.build()
+            );
+            continue;
+        }
+        group_updates
+            .entry(group)
+            .or_default()
+            .push((ts, val, diff));
+    }
+
+    // consolidate outputs, so unchanged sessions are not retracted and emitted again
+    let mut outputs = BTreeMap::<(Row, Row), repr::Diff>::new();
+    for (group, updates) in group_updates {
+        let update_ts = updates.iter().map(|(ts, _, _)| *ts).collect();
+        let ranges = session.affected_ranges(state.groups.get(&group), &update_ts);
+
+        let mut eval_sessions = |rows: Option<&SessionRows>, diff: repr::Diff| {
+            let Some(rows) = rows else {
+                return;
+            };
+            for (lo, hi) in &ranges {
+                for (start, end, session_rows) in session.sessions_in(rows, *lo, *hi) {
+                    let Some(vals) =
+                        err_collector.run(|| eval_session_aggrs(reduce_plan, &session_rows))
+                    else {
+                        continue;
+                    };
+                    let key = session.to_key(&group, start, end);
+                    *outputs.entry((key, Row::new(vals))).or_default() += diff;
+                }
+            }
+        };
+
+        eval_sessions(state.groups.get(&group), -1);
+        for (ts, val, diff) in updates {
+            err_collector.run(|| state.update(group.clone(), ts, val, diff));
+        }
+        eval_sessions(state.groups.get(&group), 1);
+    }
+
+    let updates = outputs
+        .into_iter()
+        .filter(|(_, diff)| *diff != 0)
+        .map(|(key_val, diff)| (key_val, now, diff))
+        .collect_vec();
+    let outputs = updates
+        .iter()
+        .map(|((key, val), _, diff)| {
+            let mut row = key.clone();
+            row.extend(val.iter().cloned());
+            (row, now, *diff)
+        })
+        .collect_vec();
+
+    let mut arrange = arrange.write();
+    err_collector.run(|| {
+        arrange.apply_updates(now, updates)?;
+        arrange.compact_to(now)
+    });
+    // closed sessions would never change, no need to keep them
+    let _ = arrange.truncate_expired_keys(now);
+    err_collector.run(|| state.evict_closed_sessions(now, session.gap));
+    check_no_future_updates(std::iter::once(arrange), err_collector, now);
+
+    send.give(outputs);
+}
+
+/// eval aggregations over all rows of a session from scratch
+fn eval_session_aggrs(
+    reduce_plan: &ReducePlan,
+    rows: &[(Row, repr::Diff)],
+) -> Result<Vec<Value>, EvalError> {
+    let ReducePlan::Accumulable(AccumulablePlan {
+        full_aggrs,
+        simple_aggrs,
+        distinct_aggrs,
+    }) = reduce_plan
+    else {
+        // distinct reduce only output the keys
+        return Ok(vec![]);
+    };
+    let mut output = vec![Value::Null; full_aggrs.len()];
+    let all_aggrs = simple_aggrs
+        .iter()
+        .map(|aggr| (aggr, false))
+        .chain(distinct_aggrs.iter().map(|aggr| (aggr, true)));
+    for (
+        AggrWithIndex {
+            expr,
+            input_idx,
+            output_idx,
+        },
+        distinct,
+    ) in all_aggrs
+    {
+        let col_diffs = rows.iter().map(|(row, diff)| {
+            let val = row.get(*input_idx).cloned().unwrap_or(Value::Null);
+            (val, *diff)
+        });
+        let col_diffs = if distinct {
+            let mut counts = BTreeMap::<Value, repr::Diff>::new();
+            for (val, diff) in col_diffs {
+                *counts.entry(val).or_default() += diff;
+            }
+            counts
+                .into_iter()
+                .filter(|(_, cnt)| *cnt > 0)
+                .map(|(val, _)| (val, 1))
+                .collect_vec()
+        } else {
+            col_diffs.collect_vec()
+        };
+        let (res, _accum) = expr
+            .func
+            .eval_diff_accumulable(std::iter::empty(), col_diffs)?;
+        output[*output_idx] = res;
+    }
+    Ok(output)
+}
+
 /// return distinct rows(distinct by row's key) from the input, but do not update the arrangement
 ///
 /// if the same key already exist, we only preserve the oldest value(It make sense for distinct input over key)
@@ -966,6 +1329,241 @@ mod test {
         run_and_check(&mut state, &mut df, 1..2, expected, output);
     }
 
+    /// SELECT sum(number) FROM numbers_with_ts GROUP BY hop(ts, '2 second', '1 second', '2021-07-01 00:00:00')
+    /// input table columns: number, ts
+    /// expected: window_start, window_end, sum(number)
+    #[test]
+    fn test_hop_group_by() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        const START: i64 = 1625097600000;
+        let rows = vec![
+            (1u32, START + 1000),
+            (2u32, START + 1500),
+            (3u32, START + 2000),
+        ];
+        let rows = rows
+            .into_iter()
+            .map(|(number, ts)| {
+                (
+                    Row::new(vec![number.into(), Timestamp::new_millisecond(ts).into()]),
+                    1,
+                    1,
+                )
+            })
+            .collect_vec();
+
+        let collection = ctx.render_constant(rows);
+        ctx.insert_global(GlobalId::User(1), collection);
+
+        let aggr_expr = AggregateExpr {
+            func: AggregateFunc::SumUInt32,
+            expr: ScalarExpr::Column(0),
+            distinct: false,
+        };
+        let window_size = Interval::from_month_day_nano(0, 0, 2_000_000_000);
+        let slide = Interval::from_month_day_nano(0, 0, 1_000_000_000);
+        let start_time = Some(DateTime::new(START));
+        let plan = TypedPlan {
+            schema: RelationType::new(vec![
+                ColumnType::new(CDT::datetime_datatype(), false), // window start
+                ColumnType::new(CDT::datetime_datatype(), false), // window end
+                ColumnType::new(CDT::uint64_datatype(), true),    // sum(number)
+            ])
+            .with_key(vec![1])
+            .with_time_index(Some(0))
+            .into_unnamed(),
+            plan: Plan::Reduce {
+                input: Box::new(
+                    Plan::Get {
+                        id: crate::expr::Id::Global(GlobalId::User(1)),
+                    }
+                    .with_types(
+                        RelationType::new(vec![
+                            ColumnType::new(ConcreteDataType::uint32_datatype(), false),
+                            ColumnType::new(ConcreteDataType::datetime_datatype(), false),
+                        ])
+                        .into_unnamed(),
+                    ),
+                ),
+                key_val_plan: KeyValPlan {
+                    key_plan: MapFilterProject::new(2)
+                        .map(vec![
+                            ScalarExpr::Column(1).call_unary(UnaryFunc::HopWindowFloor {
+                                window_size,
+                                slide,
+                                start_time,
+                            }),
+                            ScalarExpr::Column(1).call_unary(UnaryFunc::HopWindowCeiling {
+                                window_size,
+                                slide,
+                                start_time,
+                            }),
+                        ])
+                        .unwrap()
+                        .project(vec![2, 3])
+                        .unwrap()
+                        .into_safe(),
+                    val_plan: MapFilterProject::new(2)
+                        .project(vec![0, 1])
+                        .unwrap()
+                        .into_safe(),
+                },
+                reduce_plan: ReducePlan::Accumulable(AccumulablePlan {
+                    full_aggrs: vec![aggr_expr.clone()],
+                    simple_aggrs: vec![AggrWithIndex::new(aggr_expr.clone(), 0, 0)],
+                    distinct_aggrs: vec![],
+                }),
+            },
+        };
+
+        let bundle = ctx.render_plan(plan).unwrap();
+
+        let output = get_output_handle(&mut ctx, bundle);
+        drop(ctx);
+        let window_row = |start: i64, end: i64, sum: u64| {
+            Row::new(vec![
+                Timestamp::new_millisecond(START + start).into(),
+                Timestamp::new_millisecond(START + end).into(),
+                sum.into(),
+            ])
+        };
+        // every row belongs to two windows
+        let expected = BTreeMap::from([(
+            1,
+            vec![
+                (window_row(0, 2000, 3), 1, 1),
+                (window_row(1000, 3000, 6), 1, 1),
+                (window_row(2000, 4000, 3), 1, 1),
+            ],
+        )]);
+        run_and_check(&mut state, &mut df, 1..2, expected, output);
+    }
+
+    /// SELECT sum(number) FROM numbers_with_ts GROUP BY session(ts, '1 second')
+    /// input table columns: number, ts
+    /// expected: window_start, window_end, sum(number)
+    #[test]
+    fn test_session_group_by() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        const START: i64 = 1625097600000;
+        let rows = vec![
+            (1u32, START + 1000, 1, 1),
+            (2u32, START + 1500, 1, 1),
+            (3u32, START + 3000, 1, 1),
+            // bridge two sessions into one
+            (4u32, START + 2200, 2, 1),
+            // and split it again
+            (4u32, START + 2200, 3, -1),
+        ];
+        let rows = rows
+            .into_iter()
+            .map(|(number, ts, tick, diff)| {
+                (
+                    Row::new(vec![number.into(), Timestamp::new_millisecond(ts).into()]),
+                    tick,
+                    diff,
+                )
+            })
+            .collect_vec();
+
+        let collection = ctx.render_constant(rows);
+        ctx.insert_global(GlobalId::User(1), collection);
+
+        let aggr_expr = AggregateExpr {
+            func: AggregateFunc::SumUInt32,
+            expr: ScalarExpr::Column(0),
+            distinct: false,
+        };
+        let gap = Interval::from_month_day_nano(0, 0, 1_000_000_000);
+        let plan = TypedPlan {
+            schema: RelationType::new(vec![
+                ColumnType::new(CDT::datetime_datatype(), false), // window start
+                ColumnType::new(CDT::datetime_datatype(), false), // window end
+                ColumnType::new(CDT::uint64_datatype(), true),    // sum(number)
+            ])
+            .with_key(vec![1])
+            .with_time_index(Some(0))
+            .into_unnamed(),
+            plan: Plan::Reduce {
+                input: Box::new(
+                    Plan::Get {
+                        id: crate::expr::Id::Global(GlobalId::User(1)),
+                    }
+                    .with_types(
+                        RelationType::new(vec![
+                            ColumnType::new(ConcreteDataType::uint32_datatype(), false),
+                            ColumnType::new(ConcreteDataType::datetime_datatype(), false),
+                        ])
+                        .into_unnamed(),
+                    ),
+                ),
+                key_val_plan: KeyValPlan {
+                    key_plan: MapFilterProject::new(2)
+                        .map(vec![
+                            ScalarExpr::Column(1).call_unary(UnaryFunc::SessionWindowFloor { gap }),
+                            ScalarExpr::Column(1)
+                                .call_unary(UnaryFunc::SessionWindowCeiling { gap }),
+                        ])
+                        .unwrap()
+                        .project(vec![2, 3])
+                        .unwrap()
+                        .into_safe(),
+                    val_plan: MapFilterProject::new(2)
+                        .project(vec![0, 1])
+                        .unwrap()
+                        .into_safe(),
+                },
+                reduce_plan: ReducePlan::Accumulable(AccumulablePlan {
+                    full_aggrs: vec![aggr_expr.clone()],
+                    simple_aggrs: vec![AggrWithIndex::new(aggr_expr.clone(), 0, 0)],
+                    distinct_aggrs: vec![],
+                }),
+            },
+        };
+
+        let bundle = ctx.render_plan(plan).unwrap();
+
+        let output = get_output_handle(&mut ctx, bundle);
+        drop(ctx);
+        let session_row = |start: i64, end: i64, sum: u64| {
+            Row::new(vec![
+                Timestamp::new_millisecond(START + start).into(),
+                Timestamp::new_millisecond(START + end).into(),
+                sum.into(),
+            ])
+        };
+        let expected = BTreeMap::from([
+            (
+                1,
+                vec![
+                    (session_row(1000, 2500, 3), 1, 1),
+                    (session_row(3000, 4000, 3), 1, 1),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    (session_row(1000, 2500, 3), 2, -1),
+                    (session_row(1000, 4000, 10), 2, 1),
+                    (session_row(3000, 4000, 3), 2, -1),
+                ],
+            ),
+            (
+                3,
+                vec![
+                    (session_row(1000, 2500, 3), 3, 1),
+                    (session_row(1000, 4000, 10), 3, -1),
+                    (session_row(3000, 4000, 3), 3, 1),
+                ],
+            ),
+        ]);
+        run_and_check(&mut state, &mut df, 1..4, expected, output);
+    }
+
     /// select avg(number) from number;
     #[test]
     fn test_avg_eval() {
@@ -0,0 +1,184 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use snafu::ensure;
+
+use crate::compute::render::Context;
+use crate::compute::types::{Collection, CollectionBundle, Toff};
+use crate::error::{Error, PlanSnafu};
+use crate::plan::TypedPlan;
+use crate::repr::{self, Diff, DiffRow, Row};
+
+impl<'referred, 'df> Context<'referred, 'df> {
+    const UNION: &'static str = "union";
+
+    /// render `Plan::Union` into executable dataflow
+    ///
+    /// The output simply contains all updates from all inputs(multiset union), if `consolidate_output`
+    /// is set, updates of the same row at the same time are merged and those with zero diff are dropped
+    pub fn render_union(
+        &mut self,
+        inputs: Vec<TypedPlan>,
+        consolidate_output: bool,
+    ) -> Result<CollectionBundle, Error> {
+        ensure!(
+            !inputs.is_empty(),
+            PlanSnafu {
+                reason: "Union should have at least one input",
+            }
+        );
+
+        let mut input_ports = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let bundle = self.render_plan(input)?;
+            input_ports.push(bundle.collection.into_inner());
+        }
+
+        let (out_send_port, out_recv_port) = self.df.make_edge::<_, Toff>(Self::UNION);
+
+        self.df.add_subgraph_n_m(
+            Self::UNION,
+            input_ports,
+            vec![out_send_port],
+            move |_ctx, recvs, sends| {
+                let updates = recvs
+                    .iter()
+                    .flat_map(|recv| recv.take_inner())
+                    .flat_map(|v| v.into_iter());
+                let output = if consolidate_output {
+                    consolidate_updates(updates)
+                } else {
+                    updates.collect_vec()
+                };
+                for send in sends {
+                    send.give(output.clone());
+                }
+            },
+        );
+
+        Ok(CollectionBundle::from_collection(Collection::from_port(
+            out_recv_port,
+        )))
+    }
+}
+
+/// Merge updates of the same row at the same time, and drop those which cancel each other out
+fn consolidate_updates(updates: impl IntoIterator<Item = DiffRow>) -> Vec<DiffRow> {
+    let mut consolidated: BTreeMap<(Row, repr::Timestamp), Diff> = BTreeMap::new();
+    for (row, ts, diff) in updates {
+        *consolidated.entry((row, ts)).or_default() += diff;
+    }
+    consolidated
+        .into_iter()
+        .filter(|(_, diff)| *diff != 0)
+        .map(|((row, ts), diff)| (row, ts, diff))
+        .collect_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::data_type::ConcreteDataType;
+    use hydroflow::scheduled::graph::Hydroflow;
+
+    use super::*;
+    use crate::compute::render::test::{
+        get_collection_output_handle, harness_test_ctx, run_and_check,
+    };
+    use crate::compute::state::DataflowState;
+    use crate::expr::{self, GlobalId};
+    use crate::plan::Plan;
+    use crate::repr::{ColumnType, RelationType};
+
+    fn union_plan(consolidate_output: bool) -> TypedPlan {
+        let typ = RelationType::new(vec![ColumnType::new(
+            ConcreteDataType::int64_datatype(),
+            false,
+        )])
+        .into_unnamed();
+        let get = |id| {
+            Plan::Get {
+                id: expr::Id::Global(GlobalId::User(id)),
+            }
+            .with_types(typ.clone())
+        };
+        Plan::Union {
+            inputs: vec![get(1), get(2)],
+            consolidate_output,
+        }
+        .with_types(typ)
+    }
+
+    fn insert_union_inputs(ctx: &mut Context) {
+        let first = vec![
+            (Row::new(vec![1i64.into()]), 1, 1),
+            (Row::new(vec![2i64.into()]), 2, 1),
+        ];
+        let second = vec![
+            (Row::new(vec![1i64.into()]), 1, 1),
+            (Row::new(vec![2i64.into()]), 2, -1),
+        ];
+        let first = ctx.render_constant(first);
+        ctx.insert_global(GlobalId::User(1), first);
+        let second = ctx.render_constant(second);
+        ctx.insert_global(GlobalId::User(2), second);
+    }
+
+    #[test]
+    fn test_render_union_all() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_union_inputs(&mut ctx);
+
+        let bundle = ctx.render_plan(union_plan(false)).unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        let expected = BTreeMap::from([
+            (
+                1,
+                vec![
+                    (Row::new(vec![1i64.into()]), 1, 1),
+                    (Row::new(vec![1i64.into()]), 1, 1),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    (Row::new(vec![2i64.into()]), 2, 1),
+                    (Row::new(vec![2i64.into()]), 2, -1),
+                ],
+            ),
+        ]);
+        run_and_check(&mut state, &mut df, 0..3, expected, output);
+    }
+
+    #[test]
+    fn test_render_union_consolidate() {
+        let mut df = Hydroflow::new();
+        let mut state = DataflowState::default();
+        let mut ctx = harness_test_ctx(&mut df, &mut state);
+        insert_union_inputs(&mut ctx);
+
+        let bundle = ctx.render_plan(union_plan(true)).unwrap();
+        let output = get_collection_output_handle(&mut ctx, bundle.collection);
+        drop(ctx);
+
+        let expected = BTreeMap::from([(1, vec![(Row::new(vec![1i64.into()]), 1, 2)])]);
+        run_and_check(&mut state, &mut df, 0..3, expected, output);
+    }
+}
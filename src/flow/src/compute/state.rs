@@ -18,10 +18,15 @@ use std::rc::Rc;
 
 use hydroflow::scheduled::graph::Hydroflow;
 use hydroflow::scheduled::SubgraphId;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, ResultExt};
 
 use crate::compute::types::ErrCollector;
+use crate::error::{Error, EvalSnafu, UnexpectedSnafu};
 use crate::repr::{self, Timestamp};
-use crate::utils::{ArrangeHandler, Arrangement};
+use crate::utils::{
+    ArrangeHandler, Arrangement, ArrangementSnapshot, SessionState, SessionStateSnapshot,
+};
 
 /// input/output of a dataflow
 /// One `ComputeState` manage the input/output/schedule of one `Hydroflow`
@@ -42,10 +47,26 @@ pub struct DataflowState {
     /// save all used arrange in this dataflow, since usually there is no delete operation
     /// we can just keep track of all used arrange and schedule subgraph when they need to be updated
     arrange_used: Vec<ArrangeHandler>,
+    /// save all session window states in this dataflow, so they can be checkpointed along with arrangements
+    session_states: Vec<Rc<RefCell<SessionState>>>,
     /// the time arrangement need to be expired after a certain time in milliseconds
     expire_after: Option<Timestamp>,
 }
 
+/// A consistent snapshot of all states in a dataflow, taken between two runs of the dataflow
+///
+/// States are identified by the order they are created in, which is deterministic
+/// when rendering the same plan, so a snapshot can only be restored into a dataflow
+/// rendered from the same plan.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DataflowSnapshot {
+    /// the current time when the snapshot is taken
+    pub as_of: Timestamp,
+    pub arrangements: Vec<ArrangementSnapshot>,
+    #[serde(default)]
+    pub session_states: Vec<SessionStateSnapshot>,
+}
+
 impl DataflowState {
     pub fn new_arrange(&mut self, name: Option<Vec<String>>) -> ArrangeHandler {
         let arrange = name.map(Arrangement::new_with_name).unwrap_or_default();
@@ -59,6 +80,64 @@ impl DataflowState {
         arr
     }
 
+    pub fn new_session_state(&mut self) -> Rc<RefCell<SessionState>> {
+        let state = Rc::new(RefCell::new(SessionState::default()));
+        self.session_states.push(state.clone());
+        state
+    }
+
+    /// Take a snapshot of all states in this dataflow
+    ///
+    /// Should only be called when no subgraph is running(i.e. not inside `run_available_with_schedule`),
+    /// so that all states are at the same point of time
+    pub fn snapshot(&self) -> DataflowSnapshot {
+        DataflowSnapshot {
+            as_of: self.current_ts(),
+            arrangements: self
+                .arrange_used
+                .iter()
+                .map(|arr| arr.read().snapshot())
+                .collect(),
+            session_states: self
+                .session_states
+                .iter()
+                .map(|state| state.borrow().snapshot())
+                .collect(),
+        }
+    }
+
+    /// Restore all states from a snapshot, should be called right after rendering the dataflow
+    /// and before running it
+    pub fn restore(&mut self, snapshot: DataflowSnapshot) -> Result<(), Error> {
+        ensure!(
+            snapshot.arrangements.len() == self.arrange_used.len()
+                && snapshot.session_states.len() == self.session_states.len(),
+            UnexpectedSnafu {
+                reason: format!(
+                    "Snapshot with {} arrangements and {} session states doesn't match dataflow with {} arrangements and {} session states",
+                    snapshot.arrangements.len(),
+                    snapshot.session_states.len(),
+                    self.arrange_used.len(),
+                    self.session_states.len()
+                ),
+            }
+        );
+        for (arr, arr_snapshot) in self.arrange_used.iter().zip(snapshot.arrangements) {
+            arr.write().restore(arr_snapshot);
+        }
+        for (state, state_snapshot) in self.session_states.iter().zip(snapshot.session_states) {
+            state
+                .borrow_mut()
+                .restore(state_snapshot)
+                .context(EvalSnafu)?;
+        }
+        // time should never go backward
+        if self.current_ts() < snapshot.as_of {
+            self.set_current_ts(snapshot.as_of);
+        }
+        Ok(())
+    }
+
     /// schedule all subgraph that need to run with time <= `as_of` and run_available()
     ///
     /// return true if any subgraph actually executed
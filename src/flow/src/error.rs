@@ -206,6 +206,24 @@ pub enum Error {
         location: Location,
         name: String,
     },
+
+    #[snafu(display("Failed to access flow checkpoint at {path}"))]
+    CheckpointObjectStore {
+        path: String,
+        #[snafu(source)]
+        error: object_store::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to encode checkpoint of flow {id}"))]
+    EncodeCheckpoint {
+        id: FlowId,
+        #[snafu(source)]
+        error: serde_json::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
 }
 
 /// Result type for flow module
@@ -233,7 +251,10 @@ impl ErrorExt for Error {
                 StatusCode::Unsupported
             }
             Self::External { source, .. } => source.status_code(),
-            Self::Internal { .. } | Self::CacheRequired { .. } => StatusCode::Internal,
+            Self::Internal { .. } | Self::CacheRequired { .. } | Self::EncodeCheckpoint { .. } => {
+                StatusCode::Internal
+            }
+            Self::CheckpointObjectStore { .. } => StatusCode::StorageUnavailable,
             Self::StartServer { source, .. } | Self::ShutdownServer { source, .. } => {
                 source.status_code()
             }
@@ -55,6 +55,18 @@ pub enum UnmaterializableFunc {
         window_size: common_time::Interval,
         start_time: Option<DateTime>,
     },
+    /// Sliding window of `window_size` that starts every `slide`, a row can belong to multiple windows
+    HopWindow {
+        ts: Box<TypedExpr>,
+        window_size: common_time::Interval,
+        slide: common_time::Interval,
+        start_time: Option<DateTime>,
+    },
+    /// Window that group rows whose timestamps are less than `gap` apart from each other
+    SessionWindow {
+        ts: Box<TypedExpr>,
+        gap: common_time::Interval,
+    },
 }
 
 impl UnmaterializableFunc {
@@ -76,13 +88,23 @@ impl UnmaterializableFunc {
                 output: ConcreteDataType::timestamp_millisecond_datatype(),
                 generic_fn: GenericFn::TumbleWindow,
             },
+            Self::HopWindow { .. } => Signature {
+                input: smallvec![ConcreteDataType::timestamp_millisecond_datatype()],
+                output: ConcreteDataType::timestamp_millisecond_datatype(),
+                generic_fn: GenericFn::HopWindow,
+            },
+            Self::SessionWindow { .. } => Signature {
+                input: smallvec![ConcreteDataType::timestamp_millisecond_datatype()],
+                output: ConcreteDataType::timestamp_millisecond_datatype(),
+                generic_fn: GenericFn::SessionWindow,
+            },
         }
     }
 
     pub fn is_valid_func_name(name: &str) -> bool {
         matches!(
             name.to_lowercase().as_str(),
-            "now" | "current_schema" | "tumble"
+            "now" | "current_schema" | "tumble" | "hop" | "session"
         )
     }
 
@@ -123,6 +145,40 @@ impl UnmaterializableFunc {
                     start_time,
                 })
             }
+            "hop" => {
+                let ts = args.first().context(InvalidQuerySnafu {
+                    reason: "Hop window function requires a timestamp argument",
+                })?;
+                let window_size = parse_interval_arg(&args, 1, "Hop", "window size")?;
+                let slide = parse_interval_arg(&args, 2, "Hop", "slide")?;
+                let start_time = match args.get(3) {
+                    Some(start_time) => start_time.expr.as_literal(),
+                    None => None,
+                }
+                .map(|s| cast(s.clone(), &ConcreteDataType::datetime_datatype())).transpose().map_err(BoxedError::new).context(ExternalSnafu)?.map(|v|v.as_datetime().with_context(
+                    ||InvalidQuerySnafu {
+                        reason: format!("Hop window function requires start time argument to be a datetime describe in string, found {:?}", args.get(3))
+                    }
+                )).transpose()?;
+
+                Ok(Self::HopWindow {
+                    ts: Box::new(ts.clone()),
+                    window_size,
+                    slide,
+                    start_time,
+                })
+            }
+            "session" => {
+                let ts = args.first().context(InvalidQuerySnafu {
+                    reason: "Session window function requires a timestamp argument",
+                })?;
+                let gap = parse_interval_arg(&args, 1, "Session", "gap")?;
+
+                Ok(Self::SessionWindow {
+                    ts: Box::new(ts.clone()),
+                    gap,
+                })
+            }
             _ => InvalidQuerySnafu {
                 reason: format!("Unknown unmaterializable function: {}", name),
             }
@@ -131,6 +187,50 @@ impl UnmaterializableFunc {
     }
 }
 
+/// Parse the `idx`-th argument of a window function as a positive interval
+///
+/// TODO(discord9): since df to substrait convertor does not support interval type yet, we need to take a string and cast it to interval instead
+fn parse_interval_arg(
+    args: &[TypedExpr],
+    idx: usize,
+    func_name: &str,
+    arg_name: &str,
+) -> Result<common_time::Interval, Error> {
+    let interval = args
+        .get(idx)
+        .and_then(|expr| expr.expr.as_literal())
+        .context(InvalidQuerySnafu {
+            reason: format!("{func_name} window function requires a {arg_name} argument"),
+        })?
+        .as_string()
+        .map(|s| {
+            cast(
+                Value::from(s),
+                &ConcreteDataType::interval_month_day_nano_datatype(),
+            )
+        })
+        .transpose()
+        .map_err(BoxedError::new)
+        .context(ExternalSnafu)?
+        .and_then(|v| v.as_interval())
+        .with_context(|| InvalidQuerySnafu {
+            reason: format!(
+                "{func_name} window function requires {arg_name} argument to be a string describe a interval, found {:?}",
+                args.get(idx)
+            ),
+        })?;
+    ensure!(
+        interval.to_nanosecond() >= 1_000_000,
+        InvalidQuerySnafu {
+            reason: format!(
+                "{func_name} window function requires {arg_name} to be at least one millisecond, found {:?}",
+                interval
+            ),
+        }
+    );
+    Ok(interval)
+}
+
 /// UnaryFunc is a function that takes one argument. Also notice this enum doesn't contain function arguments,
 /// because the arguments are stored in the expression. (except `cast` function, which requires a type argument)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Hash)]
@@ -149,6 +249,31 @@ pub enum UnaryFunc {
         window_size: common_time::Interval,
         start_time: Option<DateTime>,
     },
+    /// The start of the last hop window a timestamp belongs to
+    ///
+    /// Together with [`UnaryFunc::HopWindowCeiling`] they bound all hop windows of a timestamp,
+    /// and the reduce operator expand them into one key per window
+    HopWindowFloor {
+        window_size: common_time::Interval,
+        slide: common_time::Interval,
+        start_time: Option<DateTime>,
+    },
+    /// The end of the first hop window a timestamp belongs to
+    HopWindowCeiling {
+        window_size: common_time::Interval,
+        slide: common_time::Interval,
+        start_time: Option<DateTime>,
+    },
+    /// The start of the session window consist of only the given timestamp, which is the timestamp itself
+    ///
+    /// The actual session window is determined by the reduce operator, by merging windows that overlap
+    SessionWindowFloor {
+        gap: common_time::Interval,
+    },
+    /// The end of the session window consist of only the given timestamp, which is `ts + gap`
+    SessionWindowCeiling {
+        gap: common_time::Interval,
+    },
 }
 
 impl UnaryFunc {
@@ -190,9 +315,39 @@ impl UnaryFunc {
                 output: ConcreteDataType::timestamp_millisecond_datatype(),
                 generic_fn: GenericFn::TumbleWindow,
             },
+            Self::HopWindowFloor { .. } | Self::HopWindowCeiling { .. } => Signature {
+                input: smallvec![ConcreteDataType::timestamp_millisecond_datatype()],
+                output: ConcreteDataType::timestamp_millisecond_datatype(),
+                generic_fn: GenericFn::HopWindow,
+            },
+            Self::SessionWindowFloor { .. } | Self::SessionWindowCeiling { .. } => Signature {
+                input: smallvec![ConcreteDataType::timestamp_millisecond_datatype()],
+                output: ConcreteDataType::timestamp_millisecond_datatype(),
+                generic_fn: GenericFn::SessionWindow,
+            },
         }
     }
 
+    /// Whether this function computes the start of a window
+    pub fn is_window_start(&self) -> bool {
+        matches!(
+            self,
+            Self::TumbleWindowFloor { .. }
+                | Self::HopWindowFloor { .. }
+                | Self::SessionWindowFloor { .. }
+        )
+    }
+
+    /// Whether this function computes the end of a window
+    pub fn is_window_end(&self) -> bool {
+        matches!(
+            self,
+            Self::TumbleWindowCeiling { .. }
+                | Self::HopWindowCeiling { .. }
+                | Self::SessionWindowCeiling { .. }
+        )
+    }
+
     pub fn is_valid_func_name(name: &str) -> bool {
         matches!(
             name.to_lowercase().as_str(),
@@ -341,12 +496,68 @@ impl UnaryFunc {
                     get_window_start(ts, window_size, start_time) + window_size
                 });
 
+                let ret = TimestampMillisecondVector::from(ret);
+                Ok(Arc::new(ret))
+            }
+            Self::HopWindowFloor { .. }
+            | Self::HopWindowCeiling { .. }
+            | Self::SessionWindowFloor { .. }
+            | Self::SessionWindowCeiling { .. } => {
+                let datetime_array = get_datetime_array(&arg_col)?;
+                let date_array_ref = datetime_array
+                    .as_any()
+                    .downcast_ref::<arrow::array::Date64Array>()
+                    .context({
+                        TypeMismatchSnafu {
+                            expected: ConcreteDataType::datetime_datatype(),
+                            actual: ConcreteDataType::from_arrow_type(datetime_array.data_type()),
+                        }
+                    })?;
+
+                let ret = arrow::compute::unary(date_array_ref, |ts| {
+                    self.eval_window_bound(ts)
+                        .expect("Only window functions are handled here")
+                });
+
                 let ret = TimestampMillisecondVector::from(ret);
                 Ok(Arc::new(ret))
             }
         }
     }
 
+    /// Evaluate the window bound of given timestamp(in millisecond) for hop and session window functions
+    ///
+    /// Return `None` if this function is not one of them
+    fn eval_window_bound(&self, ts: repr::Timestamp) -> Option<repr::Timestamp> {
+        // nanosecond to millisecond
+        let to_millis = |interval: &common_time::Interval| {
+            (interval.to_nanosecond() / 1_000_000) as repr::Duration
+        };
+        let ret = match self {
+            Self::HopWindowFloor {
+                slide, start_time, ..
+            } => get_window_start(ts, to_millis(slide), start_time.map(|t| t.val())),
+            Self::HopWindowCeiling {
+                window_size,
+                slide,
+                start_time,
+            } => {
+                let window_size = to_millis(window_size);
+                // the first window that contains `ts` is the first window that starts after `ts - window_size`
+                get_window_start(
+                    ts - window_size,
+                    to_millis(slide),
+                    start_time.map(|t| t.val()),
+                ) + to_millis(slide)
+                    + window_size
+            }
+            Self::SessionWindowFloor { .. } => ts,
+            Self::SessionWindowCeiling { gap } => ts + to_millis(gap),
+            _ => return None,
+        };
+        Some(ret)
+    }
+
     /// Evaluate the function with given values and expression
     ///
     /// # Arguments
@@ -436,6 +647,16 @@ impl UnaryFunc {
                 let ret = Timestamp::new_millisecond(window_end);
                 Ok(Value::from(ret))
             }
+            Self::HopWindowFloor { .. }
+            | Self::HopWindowCeiling { .. }
+            | Self::SessionWindowFloor { .. }
+            | Self::SessionWindowCeiling { .. } => {
+                let ts = get_ts_as_millisecond(arg)?;
+                let bound = self
+                    .eval_window_bound(ts)
+                    .expect("Only window functions are handled here");
+                Ok(Value::from(Timestamp::new_millisecond(bound)))
+            }
         }
     }
 }
@@ -49,7 +49,7 @@ impl TypedExpr {
 impl TypedExpr {
     /// expand multi-value expression to multiple expressions with new indices
     ///
-    /// Currently it just mean expand `TumbleWindow` to `TumbleWindowFloor` and `TumbleWindowCeiling`
+    /// Currently it just mean expand window functions(i.e. `TumbleWindow`) to the start and end of the window(i.e. `TumbleWindowFloor` and `TumbleWindowCeiling`)
     ///
     /// TODO(discord9): test if nested reduce combine with df scalar function would cause problem
     pub fn expand_multi_value(
@@ -60,20 +60,51 @@ impl TypedExpr {
         let mut ret = vec![];
         let input_arity = input_typ.column_types.len();
         for (old_idx, expr) in exprs.iter().enumerate() {
-            if let ScalarExpr::CallUnmaterializable(UnmaterializableFunc::TumbleWindow {
-                ts,
-                window_size,
-                start_time,
-            }) = &expr.expr
-            {
-                let floor = UnaryFunc::TumbleWindowFloor {
-                    window_size: *window_size,
-                    start_time: *start_time,
-                };
-                let ceil = UnaryFunc::TumbleWindowCeiling {
-                    window_size: *window_size,
-                    start_time: *start_time,
-                };
+            let window = match &expr.expr {
+                ScalarExpr::CallUnmaterializable(UnmaterializableFunc::TumbleWindow {
+                    ts,
+                    window_size,
+                    start_time,
+                }) => Some((
+                    ts,
+                    UnaryFunc::TumbleWindowFloor {
+                        window_size: *window_size,
+                        start_time: *start_time,
+                    },
+                    UnaryFunc::TumbleWindowCeiling {
+                        window_size: *window_size,
+                        start_time: *start_time,
+                    },
+                )),
+                ScalarExpr::CallUnmaterializable(UnmaterializableFunc::HopWindow {
+                    ts,
+                    window_size,
+                    slide,
+                    start_time,
+                }) => Some((
+                    ts,
+                    UnaryFunc::HopWindowFloor {
+                        window_size: *window_size,
+                        slide: *slide,
+                        start_time: *start_time,
+                    },
+                    UnaryFunc::HopWindowCeiling {
+                        window_size: *window_size,
+                        slide: *slide,
+                        start_time: *start_time,
+                    },
+                )),
+                ScalarExpr::CallUnmaterializable(UnmaterializableFunc::SessionWindow {
+                    ts,
+                    gap,
+                }) => Some((
+                    ts,
+                    UnaryFunc::SessionWindowFloor { gap: *gap },
+                    UnaryFunc::SessionWindowCeiling { gap: *gap },
+                )),
+                _ => None,
+            };
+            if let Some((ts, floor, ceil)) = window {
                 let floor = ScalarExpr::CallUnary {
                     func: floor,
                     expr: Box::new(ts.expr.clone()),
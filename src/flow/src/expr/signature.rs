@@ -65,4 +65,6 @@ pub enum GenericFn {
     Now,
     CurrentSchema,
     TumbleWindow,
+    HopWindow,
+    SessionWindow,
 }
@@ -33,6 +33,8 @@ mod server;
 mod transform;
 mod utils;
 
-pub use adapter::{FlowWorkerManager, FlowWorkerManagerRef, FlownodeOptions};
+pub use adapter::{
+    FlowCheckpointOptions, FlowWorkerManager, FlowWorkerManagerRef, FlownodeOptions,
+};
 pub use error::{Error, Result};
 pub use server::{FlownodeBuilder, FlownodeInstance, FlownodeServer, FrontendInvoker};
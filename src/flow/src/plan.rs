@@ -22,8 +22,12 @@ use std::collections::BTreeSet;
 
 use crate::error::Error;
 use crate::expr::{GlobalId, Id, LocalId, MapFilterProject, SafeMfpPlan, TypedExpr};
-use crate::plan::join::JoinPlan;
-pub(crate) use crate::plan::reduce::{AccumulablePlan, AggrWithIndex, KeyValPlan, ReducePlan};
+pub(crate) use crate::plan::join::{
+    JoinFilter, JoinKind, JoinPlan, LinearJoinPlan, LinearStagePlan,
+};
+pub(crate) use crate::plan::reduce::{
+    AccumulablePlan, AggrWithIndex, KeyValPlan, ReducePlan, WindowPlan,
+};
 use crate::repr::{DiffRow, RelationDesc};
 
 /// A plan for a dataflow component. But with type to indicate the output type of the relation.
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::expr::ScalarExpr;
+use crate::expr::{MapFilterProject, ScalarExpr};
 use crate::plan::SafeMfpPlan;
 
 /// TODO(discord9): consider impl more join strategies
@@ -21,6 +21,16 @@ pub enum JoinPlan {
     Linear(LinearJoinPlan),
 }
 
+/// The kind of join performed by a stage of a linear join.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub enum JoinKind {
+    /// Only emit rows which have a match on both sides
+    #[default]
+    Inner,
+    /// Emit every row of the stream side, and pad the lookup side with nulls if there is no match
+    Left,
+}
+
 /// Determine if a given row should stay in the output. And apply a map filter project before output the row
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct JoinFilter {
@@ -31,6 +41,16 @@ pub struct JoinFilter {
     pub before: SafeMfpPlan,
 }
 
+impl JoinFilter {
+    /// A filter which keeps every row of given arity untouched
+    pub fn identity(arity: usize) -> Self {
+        Self {
+            ready_equivalences: vec![],
+            before: MapFilterProject::new(arity).into_safe(),
+        }
+    }
+}
+
 /// A plan for the execution of a linear join.
 ///
 /// A linear join is a sequence of stages, each of which introduces
@@ -70,7 +90,9 @@ pub struct LinearStagePlan {
     pub stream_thinning: Vec<usize>,
     /// The key expressions to use for the lookup relation.
     pub lookup_key: Vec<ScalarExpr>,
-    /// The closure to apply to the concatenation of the key columns,
-    /// the stream value columns, and the lookup value colunms.
+    /// Whether unmatched rows from the stream relation are kept
+    pub kind: JoinKind,
+    /// The closure to apply to the concatenation of the stream columns retained by
+    /// `stream_thinning` and the full lookup row(or nulls if unmatched in a left join).
     pub closure: JoinFilter,
 }
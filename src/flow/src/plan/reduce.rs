@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::expr::{AggregateExpr, SafeMfpPlan};
+use snafu::ensure;
+
+use crate::error::{Error, PlanSnafu};
+use crate::expr::{AggregateExpr, SafeMfpPlan, ScalarExpr, UnaryFunc};
+use crate::repr;
 
 /// Describe how to extract key-value pair from a `Row`
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -23,6 +27,92 @@ pub struct KeyValPlan {
     pub val_plan: SafeMfpPlan,
 }
 
+/// Describe windows whose keys can't be decided by a single row alone,
+/// so the reduce operator need to further assign keys extracted by [`KeyValPlan`] to windows
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum WindowPlan {
+    /// A row belongs to every hop window between the last window starts at `start_col`
+    /// and the first window ends at `end_col` of the key, and is expanded into one key per window
+    Hop {
+        start_col: usize,
+        end_col: usize,
+        window_size: repr::Duration,
+        slide: repr::Duration,
+    },
+    /// `start_col` and `end_col` of the key hold the row's timestamp and the timestamp plus gap,
+    /// keys that only differ in them are merged if their windows overlap
+    Session {
+        start_col: usize,
+        end_col: usize,
+        gap: repr::Duration,
+    },
+}
+
+impl KeyValPlan {
+    /// Find out the hop or session window in the key plan if any
+    pub fn get_window_plan(&self) -> Result<Option<WindowPlan>, Error> {
+        let mfp = &self.key_plan.mfp;
+        let window_funcs = mfp
+            .projection
+            .iter()
+            .enumerate()
+            .filter_map(|(key_col, col)| {
+                let expr = col
+                    .checked_sub(mfp.input_arity)
+                    .and_then(|idx| mfp.expressions.get(idx))?;
+                if let ScalarExpr::CallUnary { func, .. } = expr {
+                    Some((key_col, func))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let to_millis = |interval: &common_time::Interval| {
+            (interval.to_nanosecond() / 1_000_000) as repr::Duration
+        };
+
+        let mut window = None;
+        for (start_col, func) in &window_funcs {
+            let end = |is_end: fn(&UnaryFunc) -> bool| {
+                window_funcs
+                    .iter()
+                    .find(|(_, f)| is_end(f))
+                    .map(|(col, _)| *col)
+                    .ok_or_else(|| {
+                        PlanSnafu {
+                            reason: format!("Missing window end for window start {:?}", func),
+                        }
+                        .build()
+                    })
+            };
+            let cur = match func {
+                UnaryFunc::HopWindowFloor {
+                    window_size, slide, ..
+                } => WindowPlan::Hop {
+                    start_col: *start_col,
+                    end_col: end(|f| matches!(f, UnaryFunc::HopWindowCeiling { .. }))?,
+                    window_size: to_millis(window_size),
+                    slide: to_millis(slide),
+                },
+                UnaryFunc::SessionWindowFloor { gap } => WindowPlan::Session {
+                    start_col: *start_col,
+                    end_col: end(|f| matches!(f, UnaryFunc::SessionWindowCeiling { .. }))?,
+                    gap: to_millis(gap),
+                },
+                _ => continue,
+            };
+            ensure!(
+                window.is_none(),
+                PlanSnafu {
+                    reason: "Only one hop or session window is allowed in group by",
+                }
+            );
+            window = Some(cur);
+        }
+        Ok(window)
+    }
+}
+
 /// TODO(discord9): def&impl of Hierarchical aggregates(for min/max with support to deletion) and
 /// basic aggregates(for other aggregate functions) and mixed aggregate
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -302,7 +302,7 @@ impl FlownodeBuilder {
     ///
     /// or recover all existing flow tasks if in standalone mode(nodeid is None)
     ///
-    /// TODO(discord9): persisent flow tasks with internal state
+    /// flow states are restored from checkpoints if checkpointing is enabled
     async fn recover_flows(&self, manager: &FlowWorkerManagerRef) -> Result<usize, Error> {
         let nodeid = self.opts.node_id;
         let to_be_recovered: Vec<_> = if let Some(nodeid) = nodeid {
@@ -399,12 +399,13 @@ impl FlownodeBuilder {
                 info!("Flow Worker started in new thread");
                 worker.run();
             });
-        let man = rx.await.map_err(|_e| {
+        let mut man = rx.await.map_err(|_e| {
             UnexpectedSnafu {
                 reason: "sender is dropped, failed to create flow node manager",
             }
             .build()
         })?;
+        man.enable_checkpoint(&self.opts.checkpoint)?;
         info!("Flow Node Manager started");
         Ok(man)
     }
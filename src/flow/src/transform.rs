@@ -143,23 +143,31 @@ pub async fn sql_to_flow_plan(
 
 /// register flow-specific functions to the query engine
 pub fn register_function_to_query_engine(engine: &Arc<dyn QueryEngine>) {
-    engine.register_function(Arc::new(TumbleFunction {}));
+    for name in [TUMBLE_NAME, HOP_NAME, SESSION_NAME] {
+        engine.register_function(Arc::new(WindowFunction { name }));
+    }
 }
 
+/// Placeholder for window functions like `tumble`, so they can pass the planning of query engine,
+/// they are only actually evaluated in flow's reduce operator
 #[derive(Debug)]
-pub struct TumbleFunction {}
+pub struct WindowFunction {
+    name: &'static str,
+}
 
 const TUMBLE_NAME: &str = "tumble";
+const HOP_NAME: &str = "hop";
+const SESSION_NAME: &str = "session";
 
-impl std::fmt::Display for TumbleFunction {
+impl std::fmt::Display for WindowFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", TUMBLE_NAME.to_ascii_uppercase())
+        write!(f, "{}", self.name.to_ascii_uppercase())
     }
 }
 
-impl common_function::function::Function for TumbleFunction {
+impl common_function::function::Function for WindowFunction {
     fn name(&self) -> &str {
-        TUMBLE_NAME
+        self.name
     }
 
     fn return_type(&self, _input_types: &[CDT]) -> common_query::error::Result<CDT> {
@@ -176,7 +184,10 @@ impl common_function::function::Function for TumbleFunction {
         _columns: &[datatypes::prelude::VectorRef],
     ) -> common_query::error::Result<datatypes::prelude::VectorRef> {
         UnexpectedSnafu {
-            reason: "Tumbler function is not implemented for datafusion executor",
+            reason: format!(
+                "{} function is not implemented for datafusion executor",
+                self.name
+            ),
         }
         .fail()
         .map_err(BoxedError::new)
@@ -292,7 +303,7 @@ mod test {
         let factory = query::QueryEngineFactory::new(catalog_list, None, None, None, None, false);
 
         let engine = factory.query_engine();
-        engine.register_function(Arc::new(TumbleFunction {}));
+        register_function_to_query_engine(&engine);
 
         assert_eq!("datafusion", engine.name());
         engine
@@ -272,9 +272,9 @@ fn find_time_index_in_group_exprs(group_exprs: &[TypedExpr]) -> Option<usize> {
         matches!(
             &expr.expr,
             ScalarExpr::CallUnary {
-                func: UnaryFunc::TumbleWindowFloor { .. },
+                func,
                 expr: _
-            }
+            } if func.is_window_start()
         )
     })
 }
@@ -324,6 +324,8 @@ impl TypedPlan {
             &group_exprs,
             input.schema.typ.column_types.len(),
         )?;
+        // check hop and session windows early, so invalid windows are reported when creating flow
+        key_val_plan.get_window_plan()?;
 
         // output type is group_exprs + aggr_exprs
         let output_type = {
@@ -336,14 +338,10 @@ impl TypedPlan {
             for (idx, expr) in group_exprs.iter().enumerate() {
                 output_types.push(expr.typ.clone());
                 let col_name = match &expr.expr {
-                    ScalarExpr::CallUnary {
-                        func: UnaryFunc::TumbleWindowFloor { .. },
-                        ..
-                    } => Some("window_start".to_string()),
-                    ScalarExpr::CallUnary {
-                        func: UnaryFunc::TumbleWindowCeiling { .. },
-                        ..
-                    } => {
+                    ScalarExpr::CallUnary { func, .. } if func.is_window_start() => {
+                        Some("window_start".to_string())
+                    }
+                    ScalarExpr::CallUnary { func, .. } if func.is_window_end() => {
                         auto_cols.push(idx);
                         Some("window_end".to_string())
                     }
@@ -16,15 +16,19 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use itertools::Itertools;
 use snafu::OptionExt;
-use substrait::substrait_proto_df::proto::{FilterRel, ReadRel};
+use substrait::substrait_proto_df::proto::{FilterRel, JoinRel, ReadRel, SetRel};
 use substrait_proto::proto::expression::MaskExpression;
+use substrait_proto::proto::join_rel::JoinType;
 use substrait_proto::proto::read_rel::ReadType;
 use substrait_proto::proto::rel::RelType;
+use substrait_proto::proto::set_rel::SetOp;
 use substrait_proto::proto::{plan_rel, Plan as SubPlan, ProjectRel, Rel};
 
 use crate::error::{Error, InvalidQuerySnafu, NotImplementedSnafu, PlanSnafu, UnexpectedSnafu};
-use crate::expr::{MapFilterProject, ScalarExpr, TypedExpr, UnaryFunc};
-use crate::plan::{KeyValPlan, Plan, TypedPlan};
+use crate::expr::{BinaryFunc, MapFilterProject, ScalarExpr, TypedExpr, UnaryFunc, VariadicFunc};
+use crate::plan::{
+    JoinFilter, JoinKind, JoinPlan, KeyValPlan, LinearJoinPlan, LinearStagePlan, Plan, TypedPlan,
+};
 use crate::repr::{self, RelationDesc, RelationType};
 use crate::transform::{substrait_proto, FlownodeContext, FunctionExtensions};
 
@@ -210,6 +214,151 @@ impl TypedPlan {
         }
     }
 
+    /// Convert Substrait JoinRel into a linear join with one stage,
+    /// the left input being the stream side and the right input being the lookup side
+    ///
+    /// Equalities between a left and a right column in the join condition are used as join keys,
+    /// other predicates are evaluated on the joined rows(only for inner join)
+    #[async_recursion::async_recursion]
+    pub async fn from_substrait_join(
+        ctx: &mut FlownodeContext,
+        join: &JoinRel,
+        extensions: &FunctionExtensions,
+    ) -> Result<TypedPlan, Error> {
+        let (Some(left), Some(right)) = (join.left.as_ref(), join.right.as_ref()) else {
+            return not_impl_err!("Join without both left and right input is not supported");
+        };
+        let left = TypedPlan::from_substrait_rel(ctx, left, extensions).await?;
+        let right = TypedPlan::from_substrait_rel(ctx, right, extensions).await?;
+
+        let kind = match join.join_type() {
+            JoinType::Inner => JoinKind::Inner,
+            JoinType::Left => JoinKind::Left,
+            other => return not_impl_err!("Join type {:?} is not supported", other),
+        };
+
+        let left_arity = left.schema.len()?;
+        let right_arity = right.schema.len()?;
+        let mut right_schema = right.schema.clone();
+        if kind == JoinKind::Left {
+            // unmatched rows from the left side are padded with nulls
+            for col in right_schema.typ.column_types.iter_mut() {
+                col.nullable = true;
+            }
+        }
+        let schema = left.schema.clone().concat(right_schema).without_keys();
+
+        let mut stream_key = vec![];
+        let mut lookup_key = vec![];
+        let mut residual = vec![];
+        if let Some(condition) = join.expression.as_ref() {
+            let condition = TypedExpr::from_substrait_rex(condition, &schema, extensions).await?;
+            let to_lookup_side: BTreeMap<usize, usize> = (left_arity..left_arity + right_arity)
+                .map(|i| (i, i - left_arity))
+                .collect();
+            for conjunct in split_conjunction(condition.expr) {
+                match split_equi_key(&conjunct, left_arity) {
+                    Some((stream, mut lookup)) => {
+                        lookup.permute_map(&to_lookup_side)?;
+                        stream_key.push(stream);
+                        lookup_key.push(lookup);
+                    }
+                    None => residual.push(conjunct),
+                }
+            }
+        }
+
+        let mut closure = JoinFilter::identity(left_arity + right_arity);
+        if !residual.is_empty() {
+            if kind != JoinKind::Inner {
+                return not_impl_err!(
+                    "Non-equi join condition is only supported in inner join, found {:?}",
+                    residual
+                );
+            }
+            closure.before = MapFilterProject::new(left_arity + right_arity)
+                .filter(residual)?
+                .into_safe();
+        }
+
+        let plan = Plan::Join {
+            inputs: vec![left, right],
+            plan: JoinPlan::Linear(LinearJoinPlan {
+                source_relation: 0,
+                source_key: None,
+                initial_closure: None,
+                stage_plans: vec![LinearStagePlan {
+                    lookup_relation: 1,
+                    stream_key,
+                    // the closure needs every column of the stream relation
+                    stream_thinning: (0..left_arity).collect(),
+                    lookup_key,
+                    kind,
+                    closure,
+                }],
+                final_closure: None,
+            }),
+        };
+        let joined = TypedPlan { schema, plan };
+
+        if let Some(post_filter) = join.post_join_filter.as_ref() {
+            let post_filter =
+                TypedExpr::from_substrait_rex(post_filter, &joined.schema, extensions).await?;
+            joined.filter(post_filter)
+        } else {
+            Ok(joined)
+        }
+    }
+
+    /// Convert Substrait SetRel into Flow's TypedPlan, only `UNION ALL` is supported for now
+    #[async_recursion::async_recursion]
+    pub async fn from_substrait_set(
+        ctx: &mut FlownodeContext,
+        set: &SetRel,
+        extensions: &FunctionExtensions,
+    ) -> Result<TypedPlan, Error> {
+        if set.op() != SetOp::UnionAll {
+            return not_impl_err!("Set operation {:?} is not supported", set.op());
+        }
+
+        let mut inputs = Vec::with_capacity(set.inputs.len());
+        for input in &set.inputs {
+            inputs.push(TypedPlan::from_substrait_rel(ctx, input, extensions).await?);
+        }
+        let Some(first) = inputs.first() else {
+            return plan_err!("Union without any input is not valid");
+        };
+
+        // output columns is nullable if any of the input columns is nullable
+        let mut schema = first.schema.clone().without_keys();
+        for input in inputs.iter().skip(1) {
+            let input_types = &input.schema.typ().column_types;
+            if input_types.len() != schema.typ.column_types.len() {
+                return plan_err!(
+                    "Union inputs should have the same number of columns, found {} and {}",
+                    schema.typ.column_types.len(),
+                    input_types.len()
+                );
+            }
+            for (col, input_col) in schema.typ.column_types.iter_mut().zip(input_types) {
+                if col.scalar_type != input_col.scalar_type {
+                    return plan_err!(
+                        "Union inputs should have the same column types, found {:?} and {:?}",
+                        col.scalar_type,
+                        input_col.scalar_type
+                    );
+                }
+                col.nullable |= input_col.nullable;
+            }
+        }
+
+        let plan = Plan::Union {
+            inputs,
+            consolidate_output: false,
+        };
+        Ok(TypedPlan { schema, plan })
+    }
+
     /// Convert Substrait Rel into Flow's TypedPlan
     /// TODO(discord9): SELECT DISTINCT(does it get compile with something else?)
     pub async fn from_substrait_rel(
@@ -228,11 +377,56 @@ impl TypedPlan {
             Some(RelType::Aggregate(agg)) => {
                 Self::from_substrait_agg_rel(ctx, agg, extensions).await
             }
+            Some(RelType::Join(join)) => {
+                Self::from_substrait_join(ctx, join.as_ref(), extensions).await
+            }
+            Some(RelType::Set(set)) => Self::from_substrait_set(ctx, set, extensions).await,
             _ => not_impl_err!("Unsupported relation type: {:?}", rel.rel_type),
         }
     }
 }
 
+/// split a predicate into a list of predicates which are `AND`ed together
+fn split_conjunction(expr: ScalarExpr) -> Vec<ScalarExpr> {
+    match expr {
+        ScalarExpr::CallVariadic {
+            func: VariadicFunc::And,
+            exprs,
+        } => exprs.into_iter().flat_map(split_conjunction).collect(),
+        _ => vec![expr],
+    }
+}
+
+/// if `expr` is an equality with one side only referring to the left input and the other side only
+/// referring to the right input, return the (left side, right side) expressions
+///
+/// columns of the right input start from `left_arity`
+fn split_equi_key(expr: &ScalarExpr, left_arity: usize) -> Option<(ScalarExpr, ScalarExpr)> {
+    let ScalarExpr::CallBinary {
+        func: BinaryFunc::Eq,
+        expr1,
+        expr2,
+    } = expr
+    else {
+        return None;
+    };
+    let only_left = |e: &ScalarExpr| {
+        let cols = e.get_all_ref_columns();
+        !cols.is_empty() && cols.iter().all(|c| *c < left_arity)
+    };
+    let only_right = |e: &ScalarExpr| {
+        let cols = e.get_all_ref_columns();
+        !cols.is_empty() && cols.iter().all(|c| *c >= left_arity)
+    };
+    if only_left(expr1) && only_right(expr2) {
+        Some((*expr1.clone(), *expr2.clone()))
+    } else if only_right(expr1) && only_left(expr2) {
+        Some((*expr2.clone(), *expr1.clone()))
+    } else {
+        None
+    }
+}
+
 /// if reduce_plan contains the special function like tumble floor/ceiling, add them to the proj_exprs
 /// so the effect is the window_start, window_end column are auto added to output rows
 ///
@@ -387,4 +581,84 @@ mod test {
 
         assert_eq!(flow_plan.unwrap(), expected);
     }
+
+    #[tokio::test]
+    async fn test_union_all() {
+        let engine = create_test_query_engine();
+        let sql = "SELECT number FROM numbers UNION ALL SELECT number FROM numbers_with_ts";
+        let plan = sql_to_substrait(engine.clone(), sql).await;
+
+        let mut ctx = create_test_ctx();
+        let flow_plan = TypedPlan::from_substrait_plan(&mut ctx, &plan)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            flow_plan.schema.typ().column_types,
+            vec![ColumnType::new(CDT::uint32_datatype(), false)]
+        );
+        let Plan::Union {
+            inputs,
+            consolidate_output,
+        } = flow_plan.plan
+        else {
+            panic!("Expect a union plan, found {:?}", flow_plan.plan);
+        };
+        assert!(!consolidate_output);
+        assert_eq!(
+            inputs
+                .iter()
+                .flat_map(|input| input.plan.find_used_collection())
+                .collect_vec(),
+            vec![GlobalId::User(0), GlobalId::User(1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_left_join() {
+        let engine = create_test_query_engine();
+        let sql = "SELECT numbers.number, numbers_with_ts.ts FROM numbers LEFT JOIN numbers_with_ts ON numbers.number = numbers_with_ts.number";
+        let plan = sql_to_substrait(engine.clone(), sql).await;
+
+        let mut ctx = create_test_ctx();
+        let flow_plan = TypedPlan::from_substrait_plan(&mut ctx, &plan)
+            .await
+            .unwrap();
+
+        let Plan::Mfp { input, .. } = flow_plan.plan else {
+            panic!(
+                "Expect a projection on top of join, found {:?}",
+                flow_plan.plan
+            );
+        };
+        // right side columns are nullable in a left join
+        assert_eq!(
+            input.schema.typ().column_types,
+            vec![
+                ColumnType::new(CDT::uint32_datatype(), false),
+                ColumnType::new(CDT::uint32_datatype(), true),
+                ColumnType::new(CDT::datetime_datatype(), true),
+            ]
+        );
+        let Plan::Join {
+            inputs,
+            plan: JoinPlan::Linear(plan),
+        } = input.plan
+        else {
+            panic!("Expect a join plan, found {:?}", input.plan);
+        };
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(plan.source_relation, 0);
+        assert_eq!(
+            plan.stage_plans,
+            vec![LinearStagePlan {
+                lookup_relation: 1,
+                stream_key: vec![ScalarExpr::Column(0)],
+                stream_thinning: vec![0],
+                lookup_key: vec![ScalarExpr::Column(0)],
+                kind: JoinKind::Left,
+                closure: JoinFilter::identity(3),
+            }]
+        );
+    }
 }
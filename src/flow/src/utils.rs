@@ -14,16 +14,18 @@
 
 //! utilities for managing state of dataflow execution
 
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::Bound;
 use std::sync::Arc;
 
 use common_telemetry::debug;
+use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use tokio::sync::RwLock;
 
 use crate::expr::{EvalError, ScalarExpr};
-use crate::repr::{value_to_internal_ts, DiffRow, Duration, KeyValDiffRow, Row, Timestamp};
+use crate::repr::{value_to_internal_ts, Diff, DiffRow, Duration, KeyValDiffRow, Row, Timestamp};
 
 /// A batch of updates, arranged by key
 pub type Batch = BTreeMap<Row, SmallVec<[DiffRow; 2]>>;
@@ -89,23 +91,19 @@ impl KeyExpiryManager {
         now: Timestamp,
         row: &Row,
     ) -> Result<Option<Duration>, EvalError> {
-        let Some(event_ts) = self.extract_event_ts(row)? else {
-            return Ok(None);
-        };
-
-        self.event_ts_to_key
-            .entry(event_ts)
-            .or_default()
-            .insert(row.clone());
+        self.update_event_ts(row)?;
+        self.get_expire_duration(now, row)
+    }
 
-        if let Some(expire_time) = self.compute_expiration_timestamp(now) {
-            if expire_time > event_ts {
-                // return how much time it's expired
-                return Ok(Some(expire_time - event_ts));
-            }
+    /// Update the event timestamp to key mapping without checking if the key is expired.
+    pub fn update_event_ts(&mut self, row: &Row) -> Result<(), EvalError> {
+        if let Some(event_ts) = self.extract_event_ts(row)? {
+            self.event_ts_to_key
+                .entry(event_ts)
+                .or_default()
+                .insert(row.clone());
         }
-
-        Ok(None)
+        Ok(())
     }
 
     /// Get the expire duration of a key, if it's expired by now.
@@ -425,16 +423,29 @@ impl Arrangement {
     }
 
     /// Expire keys in now that are older than expire_time, intended for reducing memory usage and limit late data arrive
-    pub fn truncate_expired_keys(&mut self, now: Timestamp) {
-        if let Some(s) = &mut self.expire_state {
-            if let Some(expired_keys) = s.remove_expired_keys(now) {
-                for key in expired_keys {
-                    for (_, batch) in self.spine.iter_mut() {
-                        batch.remove(&key);
-                    }
-                }
+    ///
+    /// Return the expired keys with their values at `now`, so that operators like join can retract
+    /// outputs derived from them.
+    pub fn truncate_expired_keys(&mut self, now: Timestamp) -> Vec<(Row, DiffRow)> {
+        let Some(expired_keys) = self
+            .expire_state
+            .as_mut()
+            .and_then(|s| s.remove_expired_keys(now))
+            .map(|keys| keys.collect::<Vec<_>>())
+        else {
+            return vec![];
+        };
+
+        let mut expired = Vec::with_capacity(expired_keys.len());
+        for key in expired_keys {
+            if let Some(val) = self.get(now, &key) {
+                expired.push((key.clone(), val));
+            }
+            for (_, batch) in self.spine.iter_mut() {
+                batch.remove(&key);
             }
         }
+        expired
     }
 
     /// Get current state of things.
@@ -489,6 +500,95 @@ impl Arrangement {
         }
         final_val
     }
+
+    /// Get current state of all keys starting with `prefix`.
+    ///
+    /// Useful for operators which arrange rows by a composite key but need to query by part of it,
+    /// i.e. join operator arrange rows by `join_key ++ row` and query them by join key.
+    pub fn get_with_prefix(&self, now: Timestamp, prefix: &Row) -> Vec<(Row, DiffRow)> {
+        let mut vals: BTreeMap<Row, Option<DiffRow>> = BTreeMap::new();
+        for batch in self.spine.values() {
+            let keys = batch
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.inner.starts_with(&prefix.inner));
+            for (key, updates) in keys {
+                let val = vals.entry(key.clone()).or_default();
+                for update in updates.iter().filter(|(_, ts, _)| *ts <= now) {
+                    *val = compact_diff_row(val.take(), update);
+                }
+            }
+        }
+        vals.into_iter()
+            .filter_map(|(key, val)| val.map(|val| (key, val)))
+            .collect()
+    }
+}
+
+/// A snapshot of the data inside an [`Arrangement`], used for checkpointing flow state.
+///
+/// Only data is included, configurations like whether it's a full arrangement and how keys expire
+/// are rebuilt when rendering the same plan again.
+///
+/// Maps keyed by [`Row`] are stored as lists, since some serialization formats(i.e. json) only accept string as key.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ArrangementSnapshot {
+    spine: Vec<(Timestamp, Vec<(Row, SmallVec<[DiffRow; 2]>)>)>,
+    event_ts_to_key: Vec<(Timestamp, Vec<Row>)>,
+    last_compaction_time: Option<Timestamp>,
+}
+
+impl Arrangement {
+    /// Take a snapshot of all updates(past, current and future) in this arrangement
+    pub fn snapshot(&self) -> ArrangementSnapshot {
+        let spine = self
+            .spine
+            .iter()
+            .map(|(ts, batch)| {
+                (
+                    *ts,
+                    batch
+                        .iter()
+                        .map(|(k, updates)| (k.clone(), updates.clone()))
+                        .collect(),
+                )
+            })
+            .collect();
+        let event_ts_to_key = self
+            .expire_state
+            .as_ref()
+            .map(|s| {
+                s.event_ts_to_key
+                    .iter()
+                    .map(|(ts, keys)| (*ts, keys.iter().cloned().collect()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        ArrangementSnapshot {
+            spine,
+            event_ts_to_key,
+            last_compaction_time: self.last_compaction_time,
+        }
+    }
+
+    /// Replace all updates in this arrangement with the ones in `snapshot`
+    ///
+    /// The expire state is only restored if this arrangement have one
+    pub fn restore(&mut self, snapshot: ArrangementSnapshot) {
+        self.spine = snapshot
+            .spine
+            .into_iter()
+            .map(|(ts, batch)| (ts, batch.into_iter().collect()))
+            .collect();
+        if let Some(s) = &mut self.expire_state {
+            s.event_ts_to_key = snapshot
+                .event_ts_to_key
+                .into_iter()
+                .map(|(ts, keys)| (ts, keys.into_iter().collect()))
+                .collect();
+        }
+        self.last_compaction_time = snapshot.last_compaction_time;
+        self.is_written |= !self.spine.is_empty();
+    }
 }
 
 fn compact_diff_row(old_row: Option<DiffRow>, new_row: &DiffRow) -> Option<DiffRow> {
@@ -577,6 +677,203 @@ impl ArrangeHandler {
     }
 }
 
+/// Rows of one group ordered by their timestamps, with their accumulated diff
+pub type SessionRows = BTreeMap<Timestamp, BTreeMap<Row, Diff>>;
+
+/// State of a reduce operator with session window, all rows are kept so sessions can be
+/// recomputed when they are merged by new rows or split by deletion.
+///
+/// Rows are grouped by key without the session window columns.
+///
+/// If an expire state is set, rows older than the expiration timestamp are rejected,
+/// and rows of sessions closed by then are removed, see [`SessionState::evict_closed_sessions`].
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SessionState {
+    pub groups: BTreeMap<Row, SessionRows>,
+    /// Tracks timestamps of rows in rows of `group ++ [timestamp]`
+    expire_state: Option<KeyExpiryManager>,
+}
+
+/// A snapshot of [`SessionState`], see [`ArrangementSnapshot`] for why maps are stored as lists
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SessionStateSnapshot {
+    groups: Vec<(Row, Vec<(Timestamp, Vec<(Row, Diff)>)>)>,
+}
+
+/// Pack the group and the timestamp of a row into a key tracked by the expire state
+fn session_expiry_key(group: &Row, ts: Timestamp) -> Row {
+    let ts = common_time::Timestamp::new_millisecond(ts).into();
+    Row::pack(group.iter().cloned().chain(std::iter::once(ts)))
+}
+
+/// Find the start of the first session which is not closed by `expire_time`,
+/// return `None` if all sessions are closed.
+///
+/// Sessions are ordered by time, so all closed sessions are before the open ones.
+fn first_open_session(
+    rows: &SessionRows,
+    gap: Duration,
+    expire_time: Timestamp,
+) -> Option<Timestamp> {
+    let mut start = None;
+    let mut last: Option<Timestamp> = None;
+    for ts in rows.keys().copied() {
+        if last.map_or(true, |last| ts - last >= gap) {
+            // the previous session ends at `last + gap`
+            if last.is_some_and(|last| last + gap > expire_time) {
+                return start;
+            }
+            start = Some(ts);
+        }
+        last = Some(ts);
+    }
+    last.filter(|last| last + gap > expire_time).and(start)
+}
+
+impl SessionState {
+    /// Set the expire state, whose event timestamp is at column `group_arity` of the tracked rows
+    pub fn set_expire_state(&mut self, expire_after: Duration, group_arity: usize) {
+        self.expire_state = Some(KeyExpiryManager::new(
+            Some(expire_after),
+            Some(ScalarExpr::Column(group_arity)),
+        ));
+    }
+
+    /// Return how long the row at `ts` is already expired by `now`, or `None` if it's not expired
+    pub fn get_expire_duration(
+        &self,
+        now: Timestamp,
+        group: &Row,
+        ts: Timestamp,
+    ) -> Result<Option<Duration>, EvalError> {
+        let Some(expire_state) = &self.expire_state else {
+            return Ok(None);
+        };
+        expire_state.get_expire_duration(now, &session_expiry_key(group, ts))
+    }
+
+    /// Accumulate `diff` of `row` at `ts` in `group`, removing entries whose diff sum up to zero
+    pub fn update(
+        &mut self,
+        group: Row,
+        ts: Timestamp,
+        row: Row,
+        diff: Diff,
+    ) -> Result<(), EvalError> {
+        if let Some(expire_state) = &mut self.expire_state {
+            expire_state.update_event_ts(&session_expiry_key(&group, ts))?;
+        }
+        let rows = self.groups.entry(group.clone()).or_default();
+        let rows_at_ts = rows.entry(ts).or_default();
+        match rows_at_ts.entry(row) {
+            Entry::Occupied(mut o) => {
+                *o.get_mut() += diff;
+                if *o.get() == 0 {
+                    o.remove();
+                }
+            }
+            Entry::Vacant(v) => {
+                if diff != 0 {
+                    v.insert(diff);
+                }
+            }
+        }
+        if rows_at_ts.is_empty() {
+            rows.remove(&ts);
+        }
+        if rows.is_empty() {
+            self.groups.remove(&group);
+        }
+        Ok(())
+    }
+
+    /// Remove rows of sessions closed by `now`, i.e. sessions ending before the expiration
+    /// timestamp, since only rows after the expiration timestamp are accepted and
+    /// none of them can be merged into those sessions.
+    ///
+    /// `gap` is the gap of the session window.
+    pub fn evict_closed_sessions(
+        &mut self,
+        now: Timestamp,
+        gap: Duration,
+    ) -> Result<(), EvalError> {
+        let Some(expire_state) = &mut self.expire_state else {
+            return Ok(());
+        };
+        let Some(expire_time) = expire_state.compute_expiration_timestamp(now) else {
+            return Ok(());
+        };
+        let Some(expired) = expire_state.remove_expired_keys(now) else {
+            return Ok(());
+        };
+        // groups with rows older than the expiration timestamp
+        let groups = expired
+            .map(|mut key| {
+                key.inner.pop();
+                key
+            })
+            .collect::<BTreeSet<_>>();
+
+        for group in groups {
+            let Some(rows) = self.groups.get_mut(&group) else {
+                continue;
+            };
+            let Some(start) = first_open_session(rows, gap, expire_time) else {
+                self.groups.remove(&group);
+                continue;
+            };
+            *rows = rows.split_off(&start);
+            // rows in open sessions are checked again later
+            for ts in rows.range(..expire_time).map(|(ts, _)| *ts) {
+                expire_state.update_event_ts(&session_expiry_key(&group, ts))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn snapshot(&self) -> SessionStateSnapshot {
+        let groups = self
+            .groups
+            .iter()
+            .map(|(group, rows)| {
+                let rows = rows
+                    .iter()
+                    .map(|(ts, rows)| (*ts, rows.clone().into_iter().collect()))
+                    .collect();
+                (group.clone(), rows)
+            })
+            .collect();
+        SessionStateSnapshot { groups }
+    }
+
+    /// Restore rows from the snapshot, the expire state is rebuilt from the rows
+    pub fn restore(&mut self, snapshot: SessionStateSnapshot) -> Result<(), EvalError> {
+        self.groups = snapshot
+            .groups
+            .into_iter()
+            .map(|(group, rows)| {
+                let rows = rows
+                    .into_iter()
+                    .map(|(ts, rows)| (ts, rows.into_iter().collect()))
+                    .collect();
+                (group, rows)
+            })
+            .collect();
+        if let Some(expire_state) = &mut self.expire_state {
+            *expire_state = KeyExpiryManager::new(
+                expire_state.key_expiration_duration,
+                expire_state.event_timestamp_from_row.clone(),
+            );
+            for (group, rows) in &self.groups {
+                for ts in rows.keys() {
+                    expire_state.update_event_ts(&session_expiry_key(group, *ts))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::borrow::Borrow;
@@ -714,7 +1011,7 @@ mod test {
             let mut arr = arr.write();
             assert_eq!(arr.spine.len(), 3);
 
-            arr.truncate_expired_keys(11);
+            assert!(arr.truncate_expired_keys(11).is_empty());
             assert_eq!(arr.spine.len(), 3);
             let key = &lit(1i64);
             assert_eq!(arr.get(11, key), Some((lit("x"), 1 /* ts */, 2 /* diff */)));
@@ -723,7 +1020,10 @@ mod test {
             let key = &lit(3i64);
             assert_eq!(arr.get(11, key), Some((lit("z"), 3 /* ts */, 2 /* diff */)));
 
-            arr.truncate_expired_keys(12);
+            assert_eq!(
+                arr.truncate_expired_keys(12),
+                vec![(lit(1i64), (lit("x"), 1 /* ts */, 2 /* diff */))]
+            );
             assert_eq!(arr.spine.len(), 3);
             let key = &lit(1i64);
             assert_eq!(arr.get(12, key), None);
@@ -859,6 +1159,62 @@ mod test {
         assert_eq!(arr.get(3, key), Some((lit(4), 3 /* ts */, 1 /* diff */)));
     }
 
+    /// test get all keys with the same prefix across batches
+    #[test]
+    fn test_get_with_prefix() {
+        let mut arr = Arrangement::default();
+        let key = |prefix: i64, suffix: &str| Row::new(vec![prefix.into(), suffix.into()]);
+        let updates: Vec<KeyValDiffRow> = vec![
+            (
+                (key(1, "a"), Row::empty()),
+                1, /* ts */
+                1, /* diff */
+            ),
+            (
+                (key(1, "b"), Row::empty()),
+                1, /* ts */
+                2, /* diff */
+            ),
+            (
+                (key(2, "c"), Row::empty()),
+                1, /* ts */
+                1, /* diff */
+            ),
+            (
+                (key(1, "a"), Row::empty()),
+                2,  /* ts */
+                -1, /* diff */
+            ),
+            (
+                (key(1, "d"), Row::empty()),
+                3, /* ts */
+                1, /* diff */
+            ),
+        ];
+        arr.apply_updates(0, updates).unwrap();
+
+        assert_eq!(
+            arr.get_with_prefix(1, &lit(1i64)),
+            vec![
+                (key(1, "a"), (Row::empty(), 1 /* ts */, 1 /* diff */)),
+                (key(1, "b"), (Row::empty(), 1 /* ts */, 2 /* diff */)),
+            ]
+        );
+        // deleted key is not included, and future updates are included once they are in the past
+        assert_eq!(
+            arr.get_with_prefix(3, &lit(1i64)),
+            vec![
+                (key(1, "b"), (Row::empty(), 1 /* ts */, 2 /* diff */)),
+                (key(1, "d"), (Row::empty(), 3 /* ts */, 1 /* diff */)),
+            ]
+        );
+        assert_eq!(
+            arr.get_with_prefix(3, &lit(2i64)),
+            vec![(key(2, "c"), (Row::empty(), 1 /* ts */, 1 /* diff */))]
+        );
+        assert!(arr.get_with_prefix(3, &lit(3i64)).is_empty());
+    }
+
     /// test if out of order updates can be sorted correctly
     #[test]
     fn test_out_of_order_apply_updates() {
@@ -883,6 +1239,86 @@ mod test {
         assert_eq!(arr.get_updates_in_range(1..7), sorted);
     }
 
+    #[test]
+    fn test_snapshot_restore() {
+        let mut arr = Arrangement::default();
+        arr.full_arrangement = true;
+        arr.expire_state = Some(KeyExpiryManager::new(Some(10), Some(ScalarExpr::Column(0))));
+        let updates = vec![
+            (kv(lit(1i64), lit("x")), 1 /* ts */, 1 /* diff */),
+            (kv(lit(2i64), lit("y")), 3 /* ts */, 1 /* diff */),
+        ];
+        arr.apply_updates(0, updates).unwrap();
+        arr.compact_to(1).unwrap();
+
+        let snapshot = arr.snapshot();
+        let encoded = serde_json::to_string(&snapshot).unwrap();
+        let decoded: ArrangementSnapshot = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(snapshot, decoded);
+
+        let mut restored = Arrangement::default();
+        restored.full_arrangement = true;
+        restored.expire_state = Some(KeyExpiryManager::new(Some(10), Some(ScalarExpr::Column(0))));
+        restored.restore(decoded);
+        assert_eq!(restored, arr);
+        assert_eq!(restored.get(3, &lit(2i64)), Some((lit("y"), 3, 1)));
+    }
+
+    #[test]
+    fn test_session_state_update() {
+        let mut state = SessionState::default();
+        let group = lit(1i64);
+        state.update(group.clone(), 10, lit("a"), 1).unwrap();
+        state.update(group.clone(), 20, lit("b"), 2).unwrap();
+        state.update(group.clone(), 10, lit("a"), -1).unwrap();
+        assert_eq!(
+            state.groups[&group],
+            BTreeMap::from([(20, BTreeMap::from([(lit("b"), 2)]))])
+        );
+
+        let encoded = serde_json::to_string(&state.snapshot()).unwrap();
+        let mut restored = SessionState::default();
+        restored
+            .restore(serde_json::from_str(&encoded).unwrap())
+            .unwrap();
+        assert_eq!(restored, state);
+
+        state.update(group.clone(), 20, lit("b"), -2).unwrap();
+        assert!(state.groups.is_empty());
+    }
+
+    #[test]
+    fn test_session_state_evict_closed_sessions() {
+        let mut state = SessionState::default();
+        // rows expire after 10ms, sessions are split by gaps of 5ms
+        state.set_expire_state(10, 1);
+        let group = lit(1i64);
+        // sessions: [0, 7), [10, 23), [30, 35)
+        for ts in [0, 2, 10, 14, 18, 30] {
+            state.update(group.clone(), ts, lit(ts), 1).unwrap();
+        }
+
+        assert_eq!(state.get_expire_duration(20, &group, 5).unwrap(), Some(5));
+        assert_eq!(state.get_expire_duration(20, &group, 10).unwrap(), None);
+
+        // the expiration timestamp is 10, only the first session is closed
+        state.evict_closed_sessions(20, 5).unwrap();
+        assert_eq!(
+            state.groups[&group].keys().copied().collect_vec(),
+            vec![10, 14, 18, 30]
+        );
+        // the expiration timestamp is 20, the second session ending at 23 is still open
+        state.evict_closed_sessions(30, 5).unwrap();
+        assert_eq!(
+            state.groups[&group].keys().copied().collect_vec(),
+            vec![10, 14, 18, 30]
+        );
+        state.evict_closed_sessions(33, 5).unwrap();
+        assert_eq!(state.groups[&group].keys().copied().collect_vec(), vec![30]);
+        state.evict_closed_sessions(45, 5).unwrap();
+        assert!(state.groups.is_empty());
+    }
+
     #[test]
     fn test_full_arrangement_get_from_first_entry() {
         let mut arr = Arrangement::default();
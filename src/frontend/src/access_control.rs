@@ -0,0 +1,955 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The built-in access control backed by users stored in the metadata kv backend,
+//! which are managed by `CREATE USER`, `GRANT`, `REVOKE` and other user statements.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use api::v1::ddl_request::Expr as DdlExpr;
+use api::v1::greptime_request::Request;
+use api::v1::query_request::Query as GrpcQuery;
+use async_trait::async_trait;
+use auth::error::{
+    AuthBackendSnafu, InvalidConfigSnafu, PermissionDeniedSnafu, Result, UserNotFoundSnafu,
+};
+use auth::{
+    auth_with_hashed_password, hash_password, Identity, Password, PermissionChecker,
+    PermissionCheckerRef, PermissionReq, PermissionResp, UserInfo, UserInfoRef, UserProvider,
+};
+use common_base::Plugins;
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_error::ext::BoxedError;
+use common_meta::key::user::{Privilege, UserManager, UserValue};
+use common_meta::kv_backend::KvBackendRef;
+use promql_parser::label::MatchOp;
+use promql_parser::parser::Expr as PromExpr;
+use promql_parser::util::{walk_expr, ExprVisitor};
+use query::promql::planner::SCHEMA_COLUMN_MATCHER;
+use session::context::QueryContext;
+use snafu::{OptionExt, ResultExt};
+use sql::dialect::Dialect;
+use sql::parser::{ParseOptions, ParserContext};
+use sql::statements::copy::{Copy, CopyDatabase, CopyTable};
+use sql::statements::statement::Statement;
+use sql::statements::tql::Tql;
+use sql::util::format_raw_object_name;
+use sqlparser::ast::{ObjectName, Query, Visit, Visitor};
+
+/// The name of the built-in user provider, the option is in format
+/// `builtin_user_provider:<root_user>=<root_password>`.
+pub const BUILTIN_USER_PROVIDER: &str = "builtin_user_provider";
+
+/// The user info authenticated by [BuiltinUserProvider].
+///
+/// It keeps a snapshot of the privileges at the time of authentication, so changes of
+/// privileges take effect on new connections.
+#[derive(Debug)]
+pub struct BuiltinUserInfo {
+    username: String,
+    superuser: bool,
+    user: UserValue,
+}
+
+impl UserInfo for BuiltinUserInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+/// The user provider that authenticates the root user configured in the option
+/// and users created by `CREATE USER`.
+pub struct BuiltinUserProvider {
+    root_user: String,
+    root_password_hash: Vec<u8>,
+    user_manager: UserManager,
+}
+
+impl BuiltinUserProvider {
+    /// Creates the provider from the option `builtin_user_provider:<root_user>=<root_password>`.
+    pub fn try_new(option: &str, kv_backend: KvBackendRef) -> Result<Self> {
+        let (root_user, root_password) = option
+            .strip_prefix(BUILTIN_USER_PROVIDER)
+            .and_then(|value| value.strip_prefix(':'))
+            .and_then(|value| value.split_once('='))
+            .filter(|(user, password)| !user.is_empty() && !password.is_empty())
+            .context(InvalidConfigSnafu {
+                value: option,
+                msg: "BuiltinUserProvider option must be in format `builtin_user_provider:<root_user>=<root_password>`",
+            })?;
+
+        Ok(Self {
+            root_user: root_user.to_string(),
+            root_password_hash: hash_password(root_password.as_bytes()),
+            user_manager: UserManager::new(kv_backend),
+        })
+    }
+}
+
+#[async_trait]
+impl UserProvider for BuiltinUserProvider {
+    fn name(&self) -> &str {
+        BUILTIN_USER_PROVIDER
+    }
+
+    async fn authenticate(&self, id: Identity<'_>, password: Password<'_>) -> Result<UserInfoRef> {
+        let Identity::UserId(username, _) = id;
+        if username == self.root_user {
+            auth_with_hashed_password(username, password, &self.root_password_hash)?;
+            return Ok(Arc::new(BuiltinUserInfo {
+                username: username.to_string(),
+                superuser: true,
+                user: UserValue::new(&[]),
+            }));
+        }
+
+        let user = self
+            .user_manager
+            .get(username)
+            .await
+            .map_err(BoxedError::new)
+            .context(AuthBackendSnafu)?
+            .context(UserNotFoundSnafu { username })?
+            .into_inner();
+        let password_hash = user
+            .password_hash()
+            .map_err(BoxedError::new)
+            .context(AuthBackendSnafu)?;
+        auth_with_hashed_password(username, password, &password_hash)?;
+
+        Ok(Arc::new(BuiltinUserInfo {
+            username: username.to_string(),
+            superuser: false,
+            user,
+        }))
+    }
+
+    async fn authorize(
+        &self,
+        _catalog: &str,
+        _schema: &str,
+        _user_info: &UserInfoRef,
+    ) -> Result<()> {
+        // Privileges are checked per request by [BuiltinPermissionChecker].
+        Ok(())
+    }
+}
+
+/// The permission checker that enforces privileges of users authenticated by
+/// [BuiltinUserProvider].
+///
+/// Unlike [PermissionChecker] plugins, it resolves unqualified names in the request
+/// against the current database of the query context.
+pub struct BuiltinPermissionChecker;
+
+pub type BuiltinPermissionCheckerRef = Arc<BuiltinPermissionChecker>;
+
+impl BuiltinPermissionChecker {
+    /// Checks whether the current user of the `query_ctx` is allowed to perform the request.
+    pub fn check_permission(&self, req: PermissionReq, query_ctx: &QueryContext) -> PermissionResp {
+        self.check(query_ctx, |checker| match req {
+            PermissionReq::SqlStatement(stmt) => checker.check_statement(stmt),
+            PermissionReq::GrpcRequest(request) => checker.check_grpc_request(request),
+            PermissionReq::PromQuery | PermissionReq::PromStoreRead => {
+                checker.check_database(checker.schema, Privilege::Read)
+            }
+            PermissionReq::Opentsdb
+            | PermissionReq::LineProtocol
+            | PermissionReq::PromStoreWrite
+            | PermissionReq::Otlp
+            | PermissionReq::LogWrite => checker.check_database(checker.schema, Privilege::Write),
+        })
+    }
+
+    /// Checks whether the current user of the `query_ctx` is allowed to evaluate the PromQL
+    /// expression, which may read from other databases by the `__schema__` matcher.
+    pub fn check_promql(&self, expr: &PromExpr, query_ctx: &QueryContext) -> PermissionResp {
+        self.check(query_ctx, |checker| checker.check_promql(expr))
+    }
+
+    fn check(
+        &self,
+        query_ctx: &QueryContext,
+        f: impl FnOnce(&PrivilegeChecker) -> bool,
+    ) -> PermissionResp {
+        let user_info = query_ctx.current_user();
+        let Some(user_info) = user_info.as_any().downcast_ref::<BuiltinUserInfo>() else {
+            return PermissionResp::Reject;
+        };
+        if user_info.superuser {
+            return PermissionResp::Allow;
+        }
+
+        let schema = query_ctx.current_schema();
+        let checker = PrivilegeChecker {
+            user: &user_info.user,
+            catalog: query_ctx.current_catalog(),
+            schema: &schema,
+            dialect: query_ctx.sql_dialect(),
+        };
+        if f(&checker) {
+            PermissionResp::Allow
+        } else {
+            PermissionResp::Reject
+        }
+    }
+}
+
+/// Checks the permission of the request by the [PermissionChecker] plugin, and by the
+/// [BuiltinPermissionChecker] if the built-in user provider is enabled.
+pub(crate) fn check_request_permission(
+    plugins: &Plugins,
+    req: PermissionReq,
+    query_ctx: &QueryContext,
+) -> Result<()> {
+    let _ = plugins
+        .get::<PermissionCheckerRef>()
+        .as_ref()
+        .check_permission(query_ctx.current_user(), req.clone())?;
+    if let Some(checker) = plugins.get::<BuiltinPermissionCheckerRef>() {
+        ensure_allowed(checker.check_permission(req, query_ctx))?;
+    }
+    Ok(())
+}
+
+/// Checks the permission of the PromQL expression by the [BuiltinPermissionChecker]
+/// if the built-in user provider is enabled.
+pub(crate) fn check_promql_permission(
+    plugins: &Plugins,
+    expr: &PromExpr,
+    query_ctx: &QueryContext,
+) -> Result<()> {
+    if let Some(checker) = plugins.get::<BuiltinPermissionCheckerRef>() {
+        ensure_allowed(checker.check_promql(expr, query_ctx))?;
+    }
+    Ok(())
+}
+
+fn ensure_allowed(resp: PermissionResp) -> Result<()> {
+    match resp {
+        PermissionResp::Allow => Ok(()),
+        PermissionResp::Reject => PermissionDeniedSnafu.fail(),
+    }
+}
+
+/// Checks privileges of a user in the current database `catalog.schema`.
+struct PrivilegeChecker<'a> {
+    user: &'a UserValue,
+    catalog: &'a str,
+    schema: &'a str,
+    /// The SQL dialect to parse queries in gRPC requests.
+    dialect: &'a (dyn Dialect + Send + Sync),
+}
+
+impl PrivilegeChecker<'_> {
+    fn check_statement(&self, stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Query(_) | Statement::Explain(_) => {
+                self.check_relations(stmt, None, Privilege::Read)
+            }
+            Statement::Insert(insert) => {
+                let target = ParserContext::canonicalize_object_name(insert.table_name().clone());
+                self.check_table(&target, Privilege::Write)
+                    && self.check_relations(stmt, Some(&target), Privilege::Read)
+            }
+            // Tables in the subqueries of `DELETE` are rare, we simply require the write
+            // privilege for all of them.
+            Statement::Delete(_) => self.check_relations(stmt, None, Privilege::Write),
+            Statement::Tql(tql) => {
+                let query = match tql {
+                    Tql::Eval(eval) => &eval.query,
+                    Tql::Explain(explain) => &explain.query,
+                    Tql::Analyze(analyze) => &analyze.query,
+                };
+                self.check_promql_query(query)
+            }
+
+            Statement::DescribeTable(s) => self.check_table(s.name(), Privilege::Read),
+            Statement::ShowCreateTable(s) => self.check_table(&s.table_name, Privilege::Read),
+            Statement::ShowCreateView(s) => self.check_table(&s.view_name, Privilege::Read),
+            Statement::ShowColumns(s) => self.check_table_in(
+                s.database.as_deref().unwrap_or(self.schema),
+                &s.table,
+                Privilege::Read,
+            ),
+            Statement::ShowIndex(s) => self.check_table_in(
+                s.database.as_deref().unwrap_or(self.schema),
+                &s.table,
+                Privilege::Read,
+            ),
+            Statement::ShowTables(s) => {
+                self.can_access_database(s.database.as_deref().unwrap_or(self.schema))
+            }
+            Statement::ShowTableStatus(s) => {
+                self.can_access_database(s.database.as_deref().unwrap_or(self.schema))
+            }
+            Statement::ShowViews(s) => {
+                self.can_access_database(s.database.as_deref().unwrap_or(self.schema))
+            }
+            Statement::ShowFlows(s) => {
+                self.can_access_database(s.database.as_deref().unwrap_or(self.schema))
+            }
+            Statement::ShowCreateFlow(s) => self
+                .flow_catalog(&s.flow_name)
+                .is_some_and(|catalog| self.user.has_any_privilege(catalog, self.schema)),
+            Statement::Use(db) => self.can_access_database(db),
+
+            Statement::CreateTable(s) => self.check_table(&s.name, Privilege::Write),
+            Statement::CreateExternalTable(s) => self.check_table(&s.name, Privilege::Write),
+            Statement::CreateTableLike(s) => {
+                self.check_table(&s.table_name, Privilege::Write)
+                    && self.check_table(&s.source_name, Privilege::Read)
+            }
+            Statement::CreateView(s) => {
+                self.check_table(&s.name, Privilege::Write)
+                    && self.check_relations(stmt, None, Privilege::Read)
+            }
+            Statement::CreateFlow(s) => {
+                self.check_table(&s.sink_table_name, Privilege::Write)
+                    && self.check_relations(stmt, None, Privilege::Read)
+            }
+            Statement::DropFlow(s) => self.flow_catalog(s.flow_name()).is_some_and(|catalog| {
+                self.has_privilege(catalog, self.schema, None, Privilege::Write)
+            }),
+            Statement::Alter(s) => self.check_table(s.table_name(), Privilege::Write),
+            Statement::DropTable(s) => s
+                .table_names()
+                .iter()
+                .all(|name| self.check_table(name, Privilege::Write)),
+            Statement::DropView(s) => self.check_table(&s.view_name, Privilege::Write),
+            Statement::TruncateTable(s) => self.check_table(s.table_name(), Privilege::Write),
+            Statement::CreateDatabase(s) => {
+                self.check_database(&format_raw_object_name(&s.name), Privilege::Write)
+            }
+            Statement::DropDatabase(s) => {
+                self.check_database(&format_raw_object_name(s.name()), Privilege::Write)
+            }
+
+            Statement::Copy(Copy::CopyTable(CopyTable::To(arg))) => {
+                self.check_table(&arg.table_name, Privilege::Read)
+            }
+            Statement::Copy(Copy::CopyTable(CopyTable::From(arg))) => {
+                self.check_table(&arg.table_name, Privilege::Write)
+            }
+            Statement::Copy(Copy::CopyDatabase(CopyDatabase::To(arg))) => {
+                self.check_database(&format_raw_object_name(&arg.database_name), Privilege::Read)
+            }
+            Statement::Copy(Copy::CopyDatabase(CopyDatabase::From(arg))) => self.check_database(
+                &format_raw_object_name(&arg.database_name),
+                Privilege::Write,
+            ),
+
+            Statement::ShowDatabases(_)
+            | Statement::ShowCharset(_)
+            | Statement::ShowCollation(_)
+            | Statement::ShowStatus(_)
+            | Statement::ShowProcesslist(_)
+            | Statement::SetVariables(_)
+            | Statement::ShowVariables(_) => true,
+
+            // Only the root user can manage users.
+            Statement::CreateUser(_)
+            | Statement::DropUser(_)
+            | Statement::AlterUser(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_) => false,
+
+            // Only the root user can kill queries.
+            Statement::KillQuery(_) => false,
+        }
+    }
+
+    fn check_grpc_request(&self, request: &Request) -> bool {
+        match request {
+            Request::Inserts(requests) => requests
+                .inserts
+                .iter()
+                .all(|r| self.check_table_in(self.schema, &r.table_name, Privilege::Write)),
+            Request::RowInserts(requests) => requests
+                .inserts
+                .iter()
+                .all(|r| self.check_table_in(self.schema, &r.table_name, Privilege::Write)),
+            Request::Deletes(requests) => requests
+                .deletes
+                .iter()
+                .all(|r| self.check_table_in(self.schema, &r.table_name, Privilege::Write)),
+            Request::RowDeletes(requests) => requests
+                .deletes
+                .iter()
+                .all(|r| self.check_table_in(self.schema, &r.table_name, Privilege::Write)),
+            Request::Query(request) => match &request.query {
+                Some(GrpcQuery::Sql(sql)) => self.check_sql(sql),
+                Some(GrpcQuery::PromRangeQuery(query)) => self.check_promql_query(&query.query),
+                // The referenced tables of a logical plan are unknown before decoding it.
+                Some(GrpcQuery::LogicalPlan(_)) => false,
+                None => true,
+            },
+            Request::Ddl(request) => match &request.expr {
+                Some(expr) => self.check_ddl_expr(expr),
+                None => true,
+            },
+        }
+    }
+
+    /// Checks the SQL in a gRPC request, which is rejected if it can't be parsed.
+    fn check_sql(&self, sql: &str) -> bool {
+        ParserContext::create_with_dialect(sql, self.dialect, ParseOptions::default())
+            .map(|stmts| stmts.iter().all(|stmt| self.check_statement(stmt)))
+            .unwrap_or(false)
+    }
+
+    /// Checks the PromQL query, which is rejected if it can't be parsed.
+    fn check_promql_query(&self, query: &str) -> bool {
+        promql_parser::parser::parse(query)
+            .map(|expr| self.check_promql(&expr))
+            .unwrap_or(false)
+    }
+
+    /// Checks the read privilege of all databases the PromQL expression reads from.
+    fn check_promql(&self, expr: &PromExpr) -> bool {
+        match collect_promql_schemas(expr) {
+            Some(schemas) => schemas.iter().all(|schema| {
+                self.check_database(schema.as_deref().unwrap_or(self.schema), Privilege::Read)
+            }),
+            None => false,
+        }
+    }
+
+    fn check_ddl_expr(&self, expr: &DdlExpr) -> bool {
+        match expr {
+            DdlExpr::CreateTable(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.table_name)
+            }
+            DdlExpr::Alter(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.table_name)
+            }
+            DdlExpr::DropTable(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.table_name)
+            }
+            DdlExpr::TruncateTable(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.table_name)
+            }
+            DdlExpr::CreateView(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.view_name)
+            }
+            DdlExpr::DropView(e) => {
+                self.check_full_table(&e.catalog_name, &e.schema_name, &e.view_name)
+            }
+            DdlExpr::CreateDatabase(e) => self.check_database(&e.schema_name, Privilege::Write),
+            DdlExpr::CreateFlow(e) => {
+                let sink_allowed = e.sink_table_name.as_ref().map_or(true, |t| {
+                    self.check_full_table(&t.catalog_name, &t.schema_name, &t.table_name)
+                });
+                sink_allowed
+                    && e.source_table_names.iter().all(|t| {
+                        self.user.has_privilege(
+                            or_default(&t.catalog_name, self.catalog),
+                            or_default(&t.schema_name, self.schema),
+                            Some(&t.table_name),
+                            Privilege::Read,
+                        )
+                    })
+            }
+            DdlExpr::DropFlow(e) => self.has_privilege(
+                or_default(&e.catalog_name, self.catalog),
+                self.schema,
+                None,
+                Privilege::Write,
+            ),
+        }
+    }
+
+    /// Checks the write privilege of a table in a DDL expr, empty names are
+    /// filled by the current database.
+    fn check_full_table(&self, catalog: &str, schema: &str, table: &str) -> bool {
+        self.user.has_privilege(
+            or_default(catalog, self.catalog),
+            or_default(schema, self.schema),
+            Some(table),
+            Privilege::Write,
+        )
+    }
+
+    /// Checks the privilege of all relations referenced by the statement except the `excluded` one.
+    fn check_relations(
+        &self,
+        stmt: &Statement,
+        excluded: Option<&ObjectName>,
+        privilege: Privilege,
+    ) -> bool {
+        collect_relations(stmt)
+            .iter()
+            .filter(|relation| Some(*relation) != excluded)
+            .all(|relation| self.check_table(relation, privilege))
+    }
+
+    fn check_table(&self, name: &ObjectName, privilege: Privilege) -> bool {
+        let idents = &name.0;
+        let (catalog, schema, table) = match &idents[..] {
+            [table] => (self.catalog, self.schema, table),
+            [schema, table] => (self.catalog, schema.value.as_str(), table),
+            [catalog, schema, table] => (catalog.value.as_str(), schema.value.as_str(), table),
+            _ => return false,
+        };
+        self.has_privilege(catalog, schema, Some(&table.value), privilege)
+    }
+
+    /// Returns the catalog of the flow, or `None` if the name is invalid.
+    ///
+    /// Flows belong to catalogs instead of databases, privileges of a flow are
+    /// checked against the current database in the catalog of the flow.
+    fn flow_catalog<'b>(&'b self, name: &'b ObjectName) -> Option<&'b str> {
+        match &name.0[..] {
+            [_] => Some(self.catalog),
+            [catalog, _] => Some(&catalog.value),
+            _ => None,
+        }
+    }
+
+    fn check_table_in(&self, schema: &str, table: &str, privilege: Privilege) -> bool {
+        self.has_privilege(self.catalog, schema, Some(table), privilege)
+    }
+
+    fn check_database(&self, schema: &str, privilege: Privilege) -> bool {
+        self.has_privilege(self.catalog, schema, None, privilege)
+    }
+
+    fn can_access_database(&self, schema: &str) -> bool {
+        schema == INFORMATION_SCHEMA_NAME || self.user.has_any_privilege(self.catalog, schema)
+    }
+
+    fn has_privilege(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: Option<&str>,
+        privilege: Privilege,
+    ) -> bool {
+        // Everyone can read the information schema.
+        (schema == INFORMATION_SCHEMA_NAME && privilege == Privilege::Read)
+            || self.user.has_privilege(catalog, schema, table, privilege)
+    }
+}
+
+fn or_default<'a>(name: &'a str, default: &'a str) -> &'a str {
+    if name.is_empty() {
+        default
+    } else {
+        name
+    }
+}
+
+/// Collects relations (tables and views) referenced by the statement,
+/// names of common table expressions visible at the reference are excluded.
+fn collect_relations(stmt: &Statement) -> Vec<ObjectName> {
+    /// Common table expressions defined by a query.
+    struct CteScope {
+        /// Names and queries of the common table expressions.
+        ctes: Vec<(String, *const Query)>,
+        /// The number of common table expressions visible now, a common table expression
+        /// is only visible to the following ones and the query body unless it's recursive.
+        visible: usize,
+        recursive: bool,
+    }
+
+    #[derive(Default)]
+    struct RelationCollector {
+        scopes: Vec<CteScope>,
+        relations: Vec<ObjectName>,
+    }
+
+    impl CteScope {
+        /// Returns the position of the query in the common table expressions.
+        fn position(&self, query: &Query) -> Option<usize> {
+            self.ctes
+                .iter()
+                .position(|(_, cte)| std::ptr::eq(*cte, query))
+        }
+    }
+
+    impl RelationCollector {
+        fn is_cte(&self, name: &str) -> bool {
+            self.scopes.iter().any(|scope| {
+                scope.ctes[..scope.visible]
+                    .iter()
+                    .any(|(cte, _)| cte == name)
+            })
+        }
+    }
+
+    impl Visitor for RelationCollector {
+        type Break = ();
+
+        fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+            if let Some(scope) = self.scopes.last_mut() {
+                if let Some(position) = scope.position(query) {
+                    scope.visible = if scope.recursive {
+                        position + 1
+                    } else {
+                        position
+                    };
+                }
+            }
+            let (ctes, recursive) = match &query.with {
+                Some(with) => (
+                    with.cte_tables
+                        .iter()
+                        .map(|cte| {
+                            let name =
+                                ParserContext::canonicalize_identifier(cte.alias.name.clone());
+                            (name.value, &*cte.query as *const Query)
+                        })
+                        .collect(),
+                    with.recursive,
+                ),
+                None => (Vec::new(), false),
+            };
+            self.scopes.push(CteScope {
+                ctes,
+                visible: 0,
+                recursive,
+            });
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+            let _ = self.scopes.pop();
+            if let Some(scope) = self.scopes.last_mut() {
+                if let Some(position) = scope.position(query) {
+                    // The body and the following common table expressions can see it.
+                    scope.visible = position + 1;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+            let relation = ParserContext::canonicalize_object_name(relation.clone());
+            if !(relation.0.len() == 1 && self.is_cte(&relation.0[0].value)) {
+                self.relations.push(relation);
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    let mut collector = RelationCollector::default();
+    let _ = stmt.visit(&mut collector);
+    collector.relations
+}
+
+/// Collects the databases read by selectors of the PromQL expression, `None` in the
+/// result stands for the current database, i.e. the selector has no `__schema__` matcher.
+///
+/// Returns `None` if any selector matches the database by an operator other than `=`.
+fn collect_promql_schemas(expr: &PromExpr) -> Option<HashSet<Option<String>>> {
+    #[derive(Default)]
+    struct SchemaCollector {
+        schemas: HashSet<Option<String>>,
+    }
+
+    impl ExprVisitor for SchemaCollector {
+        type Error = ();
+
+        fn pre_visit(&mut self, expr: &PromExpr) -> std::result::Result<bool, Self::Error> {
+            let matchers = match expr {
+                PromExpr::VectorSelector(vs) => &vs.matchers,
+                PromExpr::MatrixSelector(ms) => &ms.vs.matchers,
+                _ => return Ok(true),
+            };
+            let schema_matchers = matchers
+                .matchers
+                .iter()
+                .chain(matchers.or_matchers.iter().flatten())
+                .filter(|matcher| matcher.name == SCHEMA_COLUMN_MATCHER)
+                .collect::<Vec<_>>();
+            if schema_matchers.is_empty() {
+                let _ = self.schemas.insert(None);
+            }
+            for matcher in schema_matchers {
+                if matcher.op != MatchOp::Equal {
+                    return Err(());
+                }
+                let _ = self.schemas.insert(Some(matcher.value.clone()));
+            }
+            Ok(true)
+        }
+    }
+
+    let mut collector = SchemaCollector::default();
+    walk_expr(&mut collector, expr).ok()?;
+    Some(collector.schemas)
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::{PromRangeQuery, QueryRequest};
+    use common_meta::key::user::GrantObject;
+    use common_meta::kv_backend::memory::MemoryKvBackend;
+    use sql::dialect::GreptimeDbDialect;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default())
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    fn query_ctx(user_info: &UserInfoRef, schema: &str) -> QueryContext {
+        let query_ctx = QueryContext::with("greptime", schema);
+        query_ctx.set_current_user(user_info.clone());
+        query_ctx
+    }
+
+    fn check_req(user_info: &UserInfoRef, req: PermissionReq, schema: &str) -> bool {
+        matches!(
+            BuiltinPermissionChecker.check_permission(req, &query_ctx(user_info, schema)),
+            PermissionResp::Allow
+        )
+    }
+
+    fn check(user_info: &UserInfoRef, sql: &str) -> bool {
+        let stmt = parse(sql);
+        check_req(user_info, PermissionReq::SqlStatement(&stmt), "public")
+    }
+
+    fn check_promql(user_info: &UserInfoRef, query: &str) -> bool {
+        let expr = promql_parser::parser::parse(query).unwrap();
+        matches!(
+            BuiltinPermissionChecker.check_promql(&expr, &query_ctx(user_info, "public")),
+            PermissionResp::Allow
+        )
+    }
+
+    fn user_info(user: UserValue) -> UserInfoRef {
+        Arc::new(BuiltinUserInfo {
+            username: "alice".to_string(),
+            superuser: false,
+            user,
+        })
+    }
+
+    #[test]
+    fn test_check_statement_permission() {
+        let mut user = UserValue::new(&[]);
+        user.grant(
+            &GrantObject::Database {
+                catalog: "greptime".to_string(),
+                schema: "public".to_string(),
+            },
+            &[Privilege::Read],
+        );
+        user.grant(
+            &GrantObject::Table {
+                catalog: "greptime".to_string(),
+                schema: "db1".to_string(),
+                table: "t1".to_string(),
+            },
+            &[Privilege::Read, Privilege::Write],
+        );
+        let user_info = user_info(user);
+
+        assert!(check(&user_info, "SELECT * FROM foo"));
+        assert!(check(
+            &user_info,
+            "WITH cte AS (SELECT * FROM db1.t1) SELECT * FROM cte CROSS JOIN foo"
+        ));
+        assert!(!check(&user_info, "SELECT * FROM db1.t2"));
+        assert!(!check(
+            &user_info,
+            "SELECT * FROM foo WHERE a IN (SELECT a FROM db1.t2)"
+        ));
+        assert!(check(&user_info, "SELECT * FROM information_schema.tables"));
+        // Names of common table expressions are only visible in their own queries.
+        let check_in_db1 = |sql| {
+            let stmt = parse(sql);
+            check_req(&user_info, PermissionReq::SqlStatement(&stmt), "db1")
+        };
+        assert!(check_in_db1(
+            "WITH a AS (SELECT * FROM t1), b AS (SELECT * FROM a) SELECT * FROM b"
+        ));
+        assert!(!check_in_db1(
+            "SELECT * FROM secret WHERE EXISTS (WITH secret AS (SELECT 1) SELECT 1 FROM secret)"
+        ));
+        assert!(!check_in_db1(
+            "WITH secret AS (SELECT * FROM secret) SELECT * FROM secret"
+        ));
+        assert!(!check_in_db1(
+            "WITH a AS (SELECT * FROM b), b AS (SELECT 1) SELECT * FROM a"
+        ));
+        assert!(check(&user_info, "EXPLAIN SELECT * FROM public.foo"));
+
+        assert!(check(&user_info, "INSERT INTO db1.t1 SELECT * FROM foo"));
+        assert!(!check(&user_info, "INSERT INTO foo VALUES (1)"));
+        assert!(check(&user_info, "DELETE FROM db1.t1 WHERE a = 1"));
+        assert!(!check(&user_info, "DELETE FROM foo WHERE a = 1"));
+
+        assert!(check(&user_info, "USE db1"));
+        assert!(!check(&user_info, "USE db2"));
+        assert!(check(&user_info, "SHOW TABLES FROM db1"));
+        assert!(check(&user_info, "DESCRIBE TABLE foo"));
+        assert!(!check(&user_info, "DROP TABLE foo"));
+        assert!(check(&user_info, "TRUNCATE TABLE db1.t1"));
+        assert!(!check(&user_info, "CREATE DATABASE db2"));
+        assert!(check(&user_info, "SHOW DATABASES"));
+        assert!(check(&user_info, "SHOW CREATE FLOW greptime.f"));
+        assert!(!check(&user_info, "SHOW CREATE FLOW other.f"));
+        assert!(!check(&user_info, "DROP FLOW f"));
+
+        assert!(!check(&user_info, "CREATE USER bob IDENTIFIED BY 'pwd'"));
+        assert!(!check(&user_info, "GRANT READ ON *.* TO alice"));
+
+        let root = Arc::new(BuiltinUserInfo {
+            username: "root".to_string(),
+            superuser: true,
+            user: UserValue::new(&[]),
+        }) as UserInfoRef;
+        assert!(check(&root, "GRANT READ ON *.* TO alice"));
+        assert!(check(&root, "DROP TABLE foo"));
+    }
+
+    #[test]
+    fn test_check_protocol_permission() {
+        let mut user = UserValue::new(&[]);
+        user.grant(
+            &GrantObject::Database {
+                catalog: "greptime".to_string(),
+                schema: "public".to_string(),
+            },
+            &[Privilege::Write],
+        );
+        let user_info = user_info(user);
+
+        assert!(check_req(&user_info, PermissionReq::LineProtocol, "public"));
+        assert!(!check_req(&user_info, PermissionReq::LineProtocol, "db1"));
+        assert!(!check_req(&user_info, PermissionReq::PromQuery, "public"));
+    }
+
+    #[test]
+    fn test_check_cross_database_permission() {
+        let mut user = UserValue::new(&[]);
+        user.grant(
+            &GrantObject::Database {
+                catalog: "greptime".to_string(),
+                schema: "public".to_string(),
+            },
+            &[Privilege::Read],
+        );
+        let user_info = user_info(user);
+
+        assert!(check_promql(&user_info, "cpu"));
+        assert!(check_promql(
+            &user_info,
+            r#"cpu{__schema__="public"} / mem"#
+        ));
+        assert!(!check_promql(&user_info, r#"cpu{__schema__="db1"}"#));
+        assert!(!check_promql(
+            &user_info,
+            r#"cpu / rate(mem{__schema__="db1"}[5m])"#
+        ));
+        assert!(!check_promql(&user_info, r#"cpu{__schema__=~"db.*"}"#));
+
+        assert!(check(&user_info, "TQL EVAL (0, 10, '5s') cpu"));
+        assert!(!check(
+            &user_info,
+            r#"TQL EVAL (0, 10, '5s') cpu{__schema__="db1"}"#
+        ));
+        assert!(!check(
+            &user_info,
+            r#"TQL EXPLAIN (0, 10, '5s') cpu{__schema__="db1"}"#
+        ));
+
+        let grpc_query = |query| Request::Query(QueryRequest { query: Some(query) });
+        let check_grpc = |request: Request| {
+            check_req(&user_info, PermissionReq::GrpcRequest(&request), "public")
+        };
+        assert!(check_grpc(grpc_query(GrpcQuery::Sql(
+            "SELECT * FROM foo".to_string()
+        ))));
+        assert!(!check_grpc(grpc_query(GrpcQuery::Sql(
+            "SELECT * FROM foo; SELECT * FROM db1.bar".to_string()
+        ))));
+        assert!(!check_grpc(grpc_query(GrpcQuery::Sql(
+            "INSERT INTO foo VALUES (1)".to_string()
+        ))));
+        assert!(!check_grpc(grpc_query(GrpcQuery::PromRangeQuery(
+            PromRangeQuery {
+                query: r#"cpu{__schema__="db1"}"#.to_string(),
+                ..Default::default()
+            }
+        ))));
+        assert!(!check_grpc(grpc_query(GrpcQuery::LogicalPlan(vec![]))));
+    }
+
+    #[tokio::test]
+    async fn test_builtin_user_provider() {
+        assert!(BuiltinUserProvider::try_new(
+            "builtin_user_provider:root",
+            Arc::new(MemoryKvBackend::default())
+        )
+        .is_err());
+
+        let kv_backend = Arc::new(MemoryKvBackend::default());
+        let provider =
+            BuiltinUserProvider::try_new("builtin_user_provider:root=123456", kv_backend.clone())
+                .unwrap();
+        let user_info = provider
+            .authenticate(
+                Identity::UserId("root", None),
+                Password::PlainText("123456".to_string().into()),
+            )
+            .await
+            .unwrap();
+        assert_eq!("root", user_info.username());
+        assert!(provider
+            .authenticate(
+                Identity::UserId("root", None),
+                Password::PlainText("654321".to_string().into()),
+            )
+            .await
+            .is_err());
+
+        assert!(provider
+            .authenticate(
+                Identity::UserId("alice", None),
+                Password::PlainText("pwd".to_string().into()),
+            )
+            .await
+            .is_err());
+        UserManager::new(kv_backend)
+            .create("alice", &UserValue::new(&hash_password(b"pwd")))
+            .await
+            .unwrap();
+        let user_info = provider
+            .authenticate(
+                Identity::UserId("alice", None),
+                Password::PlainText("pwd".to_string().into()),
+            )
+            .await
+            .unwrap();
+        let user_info = user_info
+            .as_any()
+            .downcast_ref::<BuiltinUserInfo>()
+            .unwrap();
+        assert!(!user_info.superuser);
+    }
+}
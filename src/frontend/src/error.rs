@@ -237,6 +237,36 @@ pub enum Error {
     #[snafu(display("Not supported: {}", feat))]
     NotSupported { feat: String },
 
+    #[snafu(display("Query {id} is killed"))]
+    QueryKilled {
+        id: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to request frontend {addr}"))]
+    RequestFrontend {
+        addr: String,
+        #[snafu(implicit)]
+        location: Location,
+        source: client::Error,
+    },
+
+    #[snafu(display("Failed to collect record batches"))]
+    CollectRecordbatch {
+        #[snafu(implicit)]
+        location: Location,
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Invalid process list from frontend {addr}, reason: {reason}"))]
+    InvalidProcessList {
+        addr: String,
+        reason: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("SQL execution intercepted"))]
     SqlExecIntercepted {
         #[snafu(implicit)]
@@ -402,6 +432,11 @@ impl ErrorExt for Error {
 
             Error::NotSupported { .. } => StatusCode::Unsupported,
 
+            Error::QueryKilled { .. } => StatusCode::Cancelled,
+            Error::RequestFrontend { source, .. } => source.status_code(),
+            Error::CollectRecordbatch { source, .. } => source.status_code(),
+            Error::InvalidProcessList { .. } => StatusCode::Unexpected,
+
             Error::Permission { source, .. } => source.status_code(),
 
             Error::DescribeStatement { source, .. } => source.status_code(),
@@ -26,7 +26,8 @@ pub mod standalone;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
+use catalog::process_manager::{format_process_id, ProcessManagerRef};
 use catalog::CatalogManagerRef;
 use client::OutputData;
 use common_base::Plugins;
@@ -74,14 +75,16 @@ use sql::dialect::Dialect;
 use sql::parser::{ParseOptions, ParserContext};
 use sql::statements::copy::{CopyDatabase, CopyTable};
 use sql::statements::statement::Statement;
+use sql::statements::user::{Grant, GrantObject, Revoke};
 use sqlparser::ast::ObjectName;
 pub use standalone::StandaloneDatanodeManager;
 
 use self::prom_store::ExportMetricHandler;
+use crate::access_control::{check_promql_permission, check_request_permission};
 use crate::error::{
     self, Error, ExecLogicalPlanSnafu, ExecutePromqlSnafu, ExternalSnafu, ParseSqlSnafu,
-    PermissionSnafu, PlanStatementSnafu, Result, SqlExecInterceptedSnafu, StartServerSnafu,
-    TableOperationSnafu,
+    PermissionSnafu, PlanStatementSnafu, QueryKilledSnafu, Result, SqlExecInterceptedSnafu,
+    StartServerSnafu, TableOperationSnafu,
 };
 use crate::frontend::FrontendOptions;
 use crate::heartbeat::HeartbeatTask;
@@ -122,6 +125,7 @@ pub struct Instance {
     deleter: DeleterRef,
     export_metrics_task: Option<ExportMetricsTask>,
     table_metadata_manager: TableMetadataManagerRef,
+    process_manager: Option<ProcessManagerRef>,
 }
 
 impl Instance {
@@ -225,6 +229,43 @@ impl Instance {
     async fn query_statement(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<Output> {
         check_permission(self.plugins.clone(), &stmt, &query_ctx)?;
 
+        let Some(process_manager) = &self.process_manager else {
+            return self.execute_statement(stmt, query_ctx).await;
+        };
+
+        // Registers the query so that it's listed in `information_schema.process_list`
+        // and can be killed, until the output is consumed.
+        let ticket = process_manager.register_query(
+            query_ctx.current_catalog().to_string(),
+            query_ctx.current_schema(),
+            query_ctx.current_user().username().to_string(),
+            query_ctx.channel().to_string(),
+            stmt.to_string(),
+        );
+        let output = tokio::select! {
+            _ = ticket.cancelled() => {
+                return QueryKilledSnafu {
+                    id: format_process_id(process_manager.server_addr(), ticket.id()),
+                }
+                .fail();
+            }
+            output = self.execute_statement(stmt, query_ctx) => output?,
+        };
+
+        let output = match output.data {
+            OutputData::Stream(stream) => {
+                Output::new(OutputData::Stream(ticket.wrap_stream(stream)), output.meta)
+            }
+            _ => output,
+        };
+        Ok(output)
+    }
+
+    async fn execute_statement(
+        &self,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         let stmt = QueryStatement::Sql(stmt);
         self.statement_executor
             .execute_stmt(stmt, query_ctx)
@@ -246,9 +287,6 @@ impl SqlQueryHandler for Instance {
             Err(e) => return vec![Err(e)],
         };
 
-        let checker_ref = self.plugins.get::<PermissionCheckerRef>();
-        let checker = checker_ref.as_ref();
-
         match parse_stmt(query.as_ref(), query_ctx.sql_dialect())
             .and_then(|stmts| query_interceptor.post_parsing(stmts, query_ctx.clone()))
         {
@@ -263,12 +301,12 @@ impl SqlQueryHandler for Instance {
                         break;
                     }
 
-                    if let Err(e) = checker
-                        .check_permission(
-                            query_ctx.current_user(),
-                            PermissionReq::SqlStatement(&stmt),
-                        )
-                        .context(PermissionSnafu)
+                    if let Err(e) = check_request_permission(
+                        &self.plugins,
+                        PermissionReq::SqlStatement(&stmt),
+                        &query_ctx,
+                    )
+                    .context(PermissionSnafu)
                     {
                         results.push(Err(e));
                         break;
@@ -332,11 +370,12 @@ impl SqlQueryHandler for Instance {
             stmt,
             Statement::Insert(_) | Statement::Query(_) | Statement::Delete(_)
         ) {
-            self.plugins
-                .get::<PermissionCheckerRef>()
-                .as_ref()
-                .check_permission(query_ctx.current_user(), PermissionReq::SqlStatement(&stmt))
-                .context(PermissionSnafu)?;
+            check_request_permission(
+                &self.plugins,
+                PermissionReq::SqlStatement(&stmt),
+                &query_ctx,
+            )
+            .context(PermissionSnafu)?;
 
             let plan = self
                 .query_engine
@@ -388,10 +427,7 @@ impl PrometheusHandler for Instance {
             .get::<PromQueryInterceptorRef<server_error::Error>>();
         interceptor.pre_execute(query, query_ctx.clone())?;
 
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(query_ctx.current_user(), PermissionReq::PromQuery)
+        check_request_permission(&self.plugins, PermissionReq::PromQuery, &query_ctx)
             .context(AuthSnafu)?;
 
         let stmt = QueryLanguageParser::parse_promql(query, &query_ctx).with_context(|_| {
@@ -399,6 +435,11 @@ impl PrometheusHandler for Instance {
                 query: query.clone(),
             }
         })?;
+        // The query may read from other databases by the `__schema__` matcher.
+        if let QueryStatement::Promql(eval_stmt) = &stmt {
+            check_promql_permission(&self.plugins, &eval_stmt.expr, &query_ctx)
+                .context(AuthSnafu)?;
+        }
 
         let output = self
             .statement_executor
@@ -513,6 +554,7 @@ pub fn check_permission(
             validate_db_permission!(stmt, query_ctx);
         }
         Statement::ShowStatus(_stmt) => {}
+        Statement::ShowProcesslist(_) | Statement::KillQuery(_) => {}
         Statement::DescribeTable(stmt) => {
             validate_param(stmt.name(), query_ctx)?;
         }
@@ -531,6 +573,13 @@ pub fn check_permission(
         Statement::TruncateTable(stmt) => {
             validate_param(stmt.table_name(), query_ctx)?;
         }
+        // user ops won't be checked
+        Statement::CreateUser(_) | Statement::DropUser(_) | Statement::AlterUser(_) => {}
+        Statement::Grant(Grant { object, .. }) | Statement::Revoke(Revoke { object, .. }) => {
+            if let GrantObject::Table(table_name) = object {
+                validate_param(table_name, query_ctx)?;
+            }
+        }
     }
     Ok(())
 }
@@ -14,7 +14,9 @@
 
 use std::sync::Arc;
 
+use auth::UserProviderRef;
 use cache::{TABLE_FLOWNODE_SET_CACHE_NAME, TABLE_ROUTE_CACHE_NAME};
+use catalog::kvbackend::KvBackendCatalogManager;
 use catalog::CatalogManagerRef;
 use common_base::Plugins;
 use common_meta::cache::{LayeredCacheRegistryRef, TableRouteCacheRef};
@@ -35,8 +37,12 @@ use partition::manager::PartitionRuleManager;
 use pipeline::pipeline_operator::PipelineOperator;
 use query::QueryEngineFactory;
 use servers::server::ServerHandlers;
-use snafu::OptionExt;
+use snafu::{OptionExt, ResultExt};
 
+use crate::access_control::{
+    BuiltinPermissionChecker, BuiltinPermissionCheckerRef, BuiltinUserProvider,
+    BUILTIN_USER_PROVIDER,
+};
 use crate::error::{self, Result};
 use crate::frontend::FrontendOptions;
 use crate::heartbeat::HeartbeatTask;
@@ -192,6 +198,24 @@ impl FrontendBuilder {
 
         plugins.insert::<StatementExecutorRef>(statement_executor.clone());
 
+        if let Some(option) = self
+            .options
+            .user_provider
+            .as_deref()
+            .filter(|opt| opt.starts_with(BUILTIN_USER_PROVIDER))
+        {
+            let provider = BuiltinUserProvider::try_new(option, kv_backend.clone())
+                .context(error::IllegalAuthConfigSnafu)?;
+            plugins.insert::<UserProviderRef>(Arc::new(provider));
+            plugins.insert::<BuiltinPermissionCheckerRef>(Arc::new(BuiltinPermissionChecker));
+        }
+
+        let process_manager = self
+            .catalog_manager
+            .as_any()
+            .downcast_ref::<KvBackendCatalogManager>()
+            .and_then(|manager| manager.process_manager());
+
         Ok(Instance {
             options: self.options,
             catalog_manager: self.catalog_manager,
@@ -206,6 +230,7 @@ impl FrontendBuilder {
             deleter,
             export_metrics_task: None,
             table_metadata_manager: Arc::new(TableMetadataManager::new(kv_backend)),
+            process_manager,
         })
     }
 }
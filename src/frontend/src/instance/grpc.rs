@@ -17,7 +17,7 @@ use api::v1::greptime_request::Request;
 use api::v1::query_request::Query;
 use api::v1::{DeleteRequests, DropFlowExpr, InsertRequests, RowDeleteRequests, RowInsertRequests};
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use common_query::Output;
 use common_telemetry::tracing;
 use query::parser::PromQuery;
@@ -28,6 +28,7 @@ use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use table::table_name::TableName;
 
+use crate::access_control::check_request_permission;
 use crate::error::{
     Error, IncompleteGrpcRequestSnafu, NotSupportedSnafu, PermissionSnafu, Result,
     TableOperationSnafu,
@@ -44,10 +45,7 @@ impl GrpcQueryHandler for Instance {
         let interceptor = interceptor_ref.as_ref();
         interceptor.pre_execute(&request, ctx.clone())?;
 
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::GrpcRequest(&request))
+        check_request_permission(&self.plugins, PermissionReq::GrpcRequest(&request), &ctx)
             .context(PermissionSnafu)?;
 
         let output = match request {
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use client::Output;
 use common_error::ext::BoxedError;
 use servers::error::{AuthSnafu, Error};
@@ -23,6 +23,7 @@ use servers::query_handler::InfluxdbLineProtocolHandler;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
 
+use crate::access_control::check_request_permission;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -32,10 +33,7 @@ impl InfluxdbLineProtocolHandler for Instance {
         request: InfluxdbRequest,
         ctx: QueryContextRef,
     ) -> servers::error::Result<Output> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::LineProtocol)
+        check_request_permission(&self.plugins, PermissionReq::LineProtocol, &ctx)
             .context(AuthSnafu)?;
 
         let interceptor_ref = self.plugins.get::<LineProtocolInterceptorRef<Error>>();
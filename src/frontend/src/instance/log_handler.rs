@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use api::v1::RowInsertRequests;
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use client::Output;
 use common_error::ext::BoxedError;
 use pipeline::{GreptimeTransformer, Pipeline, PipelineInfo, PipelineVersion};
@@ -25,6 +25,7 @@ use servers::query_handler::LogHandler;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
 
+use crate::access_control::check_request_permission;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -34,10 +35,7 @@ impl LogHandler for Instance {
         log: RowInsertRequests,
         ctx: QueryContextRef,
     ) -> ServerResult<Output> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::LogWrite)
+        check_request_permission(&self.plugins, PermissionReq::LogWrite, &ctx)
             .context(AuthSnafu)?;
 
         self.handle_log_inserts(log, ctx).await
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use common_error::ext::BoxedError;
 use common_telemetry::tracing;
 use servers::error as server_error;
@@ -24,6 +24,7 @@ use servers::query_handler::OpentsdbProtocolHandler;
 use session::context::QueryContextRef;
 use snafu::prelude::*;
 
+use crate::access_control::check_request_permission;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -34,10 +35,7 @@ impl OpentsdbProtocolHandler for Instance {
         data_points: Vec<DataPoint>,
         ctx: QueryContextRef,
     ) -> server_error::Result<usize> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::Opentsdb)
+        check_request_permission(&self.plugins, PermissionReq::Opentsdb, &ctx)
             .context(AuthSnafu)?;
 
         let (requests, _) = data_point_to_grpc_row_insert_requests(data_points)?;
@@ -13,22 +13,25 @@
 // limitations under the License.
 
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use client::Output;
 use common_error::ext::BoxedError;
 use common_telemetry::tracing;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
 use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use servers::error::{self, AuthSnafu, Result as ServerResult};
 use servers::interceptor::{OpenTelemetryProtocolInterceptor, OpenTelemetryProtocolInterceptorRef};
 use servers::otlp;
+use servers::otlp::logs::PipelineWay;
 use servers::otlp::plugin::TraceParserRef;
-use servers::query_handler::OpenTelemetryProtocolHandler;
+use servers::query_handler::{LogHandler, OpenTelemetryProtocolHandler};
 use session::context::QueryContextRef;
 use snafu::ResultExt;
 
+use crate::access_control::check_request_permission;
 use crate::instance::Instance;
-use crate::metrics::{OTLP_METRICS_ROWS, OTLP_TRACES_ROWS};
+use crate::metrics::{OTLP_LOGS_ROWS, OTLP_METRICS_ROWS, OTLP_TRACES_ROWS};
 
 #[async_trait]
 impl OpenTelemetryProtocolHandler for Instance {
@@ -38,11 +41,7 @@ impl OpenTelemetryProtocolHandler for Instance {
         request: ExportMetricsServiceRequest,
         ctx: QueryContextRef,
     ) -> ServerResult<Output> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::Otlp)
-            .context(AuthSnafu)?;
+        check_request_permission(&self.plugins, PermissionReq::Otlp, &ctx).context(AuthSnafu)?;
 
         let interceptor_ref = self
             .plugins
@@ -64,11 +63,7 @@ impl OpenTelemetryProtocolHandler for Instance {
         request: ExportTraceServiceRequest,
         ctx: QueryContextRef,
     ) -> ServerResult<Output> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::Otlp)
-            .context(AuthSnafu)?;
+        check_request_permission(&self.plugins, PermissionReq::Otlp, &ctx).context(AuthSnafu)?;
 
         let interceptor_ref = self
             .plugins
@@ -92,4 +87,34 @@ impl OpenTelemetryProtocolHandler for Instance {
             .map_err(BoxedError::new)
             .context(error::ExecuteGrpcQuerySnafu)
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn logs(
+        &self,
+        request: ExportLogsServiceRequest,
+        pipeline: PipelineWay,
+        table_name: String,
+        ctx: QueryContextRef,
+    ) -> ServerResult<Output> {
+        check_request_permission(&self.plugins, PermissionReq::Otlp, &ctx).context(AuthSnafu)?;
+
+        let interceptor_ref = self
+            .plugins
+            .get::<OpenTelemetryProtocolInterceptorRef<servers::error::Error>>();
+        interceptor_ref.pre_execute(ctx.clone())?;
+
+        let pipeline = match pipeline {
+            PipelineWay::OtlpLog => None,
+            PipelineWay::Custom { name, version } => {
+                Some(self.get_pipeline(&name, version, ctx.clone()).await?)
+            }
+        };
+        let (requests, rows) =
+            otlp::logs::to_grpc_insert_requests(request, pipeline.as_deref(), table_name)?;
+
+        OTLP_LOGS_ROWS.inc_by(rows as u64);
+
+        // Logs are written into append-only tables like other logs.
+        self.handle_log_inserts(requests, ctx).await
+    }
 }
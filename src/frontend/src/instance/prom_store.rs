@@ -19,7 +19,7 @@ use api::prom_store::remote::read_request::ResponseType;
 use api::prom_store::remote::{Query, QueryResult, ReadRequest, ReadResponse};
 use api::v1::RowInsertRequests;
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::PermissionReq;
 use client::OutputData;
 use common_catalog::format_full_table_name;
 use common_error::ext::BoxedError;
@@ -41,6 +41,7 @@ use servers::query_handler::{
 use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 
+use crate::access_control::check_request_permission;
 use crate::error::{
     CatalogSnafu, ExecLogicalPlanSnafu, PromStoreRemoteQueryPlanSnafu, ReadTableSnafu, Result,
     TableNotFoundSnafu,
@@ -165,10 +166,7 @@ impl PromStoreProtocolHandler for Instance {
         ctx: QueryContextRef,
         with_metric_engine: bool,
     ) -> ServerResult<Output> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::PromStoreWrite)
+        check_request_permission(&self.plugins, PermissionReq::PromStoreWrite, &ctx)
             .context(AuthSnafu)?;
         let interceptor_ref = self
             .plugins
@@ -199,10 +197,7 @@ impl PromStoreProtocolHandler for Instance {
         request: ReadRequest,
         ctx: QueryContextRef,
     ) -> ServerResult<PromStoreResponse> {
-        self.plugins
-            .get::<PermissionCheckerRef>()
-            .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::PromStoreRead)
+        check_request_permission(&self.plugins, PermissionReq::PromStoreRead, &ctx)
             .context(AuthSnafu)?;
         let interceptor_ref = self
             .plugins
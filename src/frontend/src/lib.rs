@@ -14,11 +14,13 @@
 
 #![feature(assert_matches)]
 
+pub mod access_control;
 pub mod error;
 pub mod frontend;
 pub mod heartbeat;
 pub mod instance;
 pub(crate) mod metrics;
+pub mod process;
 mod script;
 pub mod server;
 pub mod service_config;
@@ -0,0 +1,165 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use catalog::information_schema::process_list::{
+    CATALOG, CHANNEL, FRONTEND, ID, QUERY, SCHEMA_NAME, START_TIME, USER_NAME,
+};
+use catalog::information_schema::PROCESS_LIST;
+use catalog::process_manager::{format_process_id, parse_process_id, FrontendClient, ProcessInfo};
+use client::{Client, Database, OutputData, RecordBatches, DEFAULT_SCHEMA_NAME};
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_error::ext::BoxedError;
+use common_grpc::channel_manager::ChannelManager;
+use common_meta::cluster::{ClusterInfo, Role};
+use datatypes::value::Value;
+use meta_client::client::MetaClient;
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{
+    CollectRecordbatchSnafu, InvalidProcessListSnafu, RequestFrontendSnafu, Result,
+};
+
+/// The [FrontendClient] that discovers frontends by metasrv and accesses their
+/// processes by SQL over gRPC.
+///
+/// The requests don't carry any credentials, so listing or killing queries on other
+/// frontends fails if their gRPC services require authentication.
+pub struct GrpcFrontendClient {
+    meta_client: Arc<MetaClient>,
+    channel_manager: ChannelManager,
+}
+
+impl GrpcFrontendClient {
+    pub fn new(meta_client: Arc<MetaClient>) -> Self {
+        Self {
+            meta_client,
+            channel_manager: ChannelManager::new(),
+        }
+    }
+
+    fn database(&self, frontend: &str, catalog: &str) -> Database {
+        let client = Client::with_manager_and_urls(self.channel_manager.clone(), [frontend]);
+        Database::new(catalog, DEFAULT_SCHEMA_NAME, client)
+    }
+
+    async fn query_processes(&self, frontend: &str, catalog: &str) -> Result<Vec<ProcessInfo>> {
+        // Filters by the frontend so that the remote frontend only lists its own queries.
+        let sql = format!(
+            "SELECT {ID}, {CATALOG}, {SCHEMA_NAME}, {USER_NAME}, {CHANNEL}, {QUERY}, {START_TIME} \
+            FROM {INFORMATION_SCHEMA_NAME}.{PROCESS_LIST} WHERE {FRONTEND} = '{}'",
+            frontend.replace('\'', "''")
+        );
+        let output = self
+            .database(frontend, catalog)
+            .sql(sql)
+            .await
+            .context(RequestFrontendSnafu { addr: frontend })?;
+        let batches = match output.data {
+            OutputData::Stream(stream) => RecordBatches::try_collect(stream)
+                .await
+                .context(CollectRecordbatchSnafu)?,
+            OutputData::RecordBatches(batches) => batches,
+            OutputData::AffectedRows(_) => {
+                return InvalidProcessListSnafu {
+                    addr: frontend,
+                    reason: "unexpected affected rows",
+                }
+                .fail()
+            }
+        };
+
+        let mut processes = vec![];
+        for batch in batches.iter() {
+            for row in batch.rows() {
+                processes.push(row_to_process(frontend, row)?);
+            }
+        }
+        Ok(processes)
+    }
+}
+
+fn row_to_process(frontend: &str, row: Vec<Value>) -> Result<ProcessInfo> {
+    let invalid_row = |reason: String| {
+        InvalidProcessListSnafu {
+            addr: frontend,
+            reason,
+        }
+        .build()
+    };
+    let string_at = |idx: usize| match &row[idx] {
+        Value::String(s) => Ok(s.as_utf8().to_string()),
+        v => Err(invalid_row(format!("expect a string, got {v:?}"))),
+    };
+
+    let display_id = string_at(0)?;
+    let (_, id) = parse_process_id(&display_id).context(InvalidProcessListSnafu {
+        addr: frontend,
+        reason: format!("invalid query id {display_id}"),
+    })?;
+    let start_timestamp_ms = match &row[6] {
+        Value::Timestamp(ts) => ts.value(),
+        v => return Err(invalid_row(format!("expect a timestamp, got {v:?}"))),
+    };
+
+    Ok(ProcessInfo {
+        id,
+        frontend: frontend.to_string(),
+        catalog: string_at(1)?,
+        schema: string_at(2)?,
+        user: string_at(3)?,
+        channel: string_at(4)?,
+        query: string_at(5)?,
+        start_timestamp_ms,
+    })
+}
+
+#[async_trait::async_trait]
+impl FrontendClient for GrpcFrontendClient {
+    async fn frontends(&self) -> std::result::Result<Vec<String>, BoxedError> {
+        let nodes = self
+            .meta_client
+            .list_nodes(Some(Role::Frontend))
+            .await
+            .map_err(BoxedError::new)?;
+        Ok(nodes.into_iter().map(|node| node.peer.addr).collect())
+    }
+
+    async fn list_processes(
+        &self,
+        frontend: &str,
+        catalog: &str,
+    ) -> std::result::Result<Vec<ProcessInfo>, BoxedError> {
+        self.query_processes(frontend, catalog)
+            .await
+            .map_err(BoxedError::new)
+    }
+
+    async fn kill_process(
+        &self,
+        frontend: &str,
+        catalog: &str,
+        id: u64,
+    ) -> std::result::Result<(), BoxedError> {
+        let sql = format!("KILL QUERY '{}'", format_process_id(frontend, id));
+        let _ = self
+            .database(frontend, catalog)
+            .sql(sql)
+            .await
+            .context(RequestFrontendSnafu { addr: frontend })
+            .map_err(BoxedError::new)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, OptionExt};
+
+use crate::bloom_filter::error::{InvalidIndexDataSnafu, Result};
+
+pub mod creator;
+pub mod error;
+
+const VERSION: u8 = 1;
+/// Maximum number of hash functions of a filter.
+const MAX_NUM_HASHES: u32 = 16;
+/// Minimum number of bits of a filter.
+const MIN_NUM_BITS: usize = 64;
+
+/// A bloom filter over byte strings.
+///
+/// Items are hashed once and the probes are derived from the hash by double hashing,
+/// so callers can hash items ahead of time via [`hash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// Creates a filter sized for `num_items` items with the expected false positive rate.
+    pub fn with_capacity(num_items: usize, false_positive_rate: f64) -> Self {
+        let n = num_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-n * false_positive_rate.ln() / (ln2 * ln2)).ceil() as usize;
+        let num_bits = num_bits.max(MIN_NUM_BITS);
+        let num_hashes = ((num_bits as f64 / n) * ln2).round() as u32;
+
+        Self {
+            num_hashes: num_hashes.clamp(1, MAX_NUM_HASHES),
+            bits: vec![0; num_bits.div_ceil(64)],
+        }
+    }
+
+    /// Inserts an item into the filter.
+    pub fn insert(&mut self, item: &[u8]) {
+        self.insert_hash(hash(item));
+    }
+
+    /// Inserts an item hashed by [`hash`] into the filter.
+    pub fn insert_hash(&mut self, hash: u64) {
+        let num_bits = self.num_bits();
+        for bit in probes(hash, self.num_hashes, num_bits) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns false if the item is definitely not in the filter.
+    pub fn contains(&self, item: &[u8]) -> bool {
+        self.contains_hash(hash(item))
+    }
+
+    /// Returns false if the item hashed by [`hash`] is definitely not in the filter.
+    pub fn contains_hash(&self, hash: u64) -> bool {
+        probes(hash, self.num_hashes, self.num_bits())
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns the number of bytes used by the filter.
+    pub fn memory_usage(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    fn num_bits(&self) -> usize {
+        self.bits.len() * 64
+    }
+}
+
+/// Hashes an item with FNV-1a.
+pub fn hash(item: &[u8]) -> u64 {
+    item.iter().fold(0xcbf29ce484222325, |h, b| {
+        (h ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Returns the bits to probe for the hash.
+fn probes(hash: u64, num_hashes: u32, num_bits: usize) -> impl Iterator<Item = usize> {
+    let h1 = mix(hash);
+    // Makes the step odd so that it never degenerates to zero.
+    let h2 = mix(hash ^ 0x9e3779b97f4a7c15) | 1;
+    (0..num_hashes as u64)
+        .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits as u64) as usize)
+}
+
+/// The finalizer of splitmix64, spreads the FNV hash over all the bits.
+fn mix(mut h: u64) -> u64 {
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d049bb133111eb);
+    h ^ (h >> 31)
+}
+
+/// Encodes filters to bytes.
+pub fn encode(filters: &[BloomFilter]) -> Vec<u8> {
+    let size = filters.iter().map(|f| f.memory_usage() + 8).sum::<usize>();
+    let mut buf = Vec::with_capacity(size + 5);
+    buf.push(VERSION);
+    put_u32(&mut buf, filters.len() as u32);
+    for filter in filters {
+        put_u32(&mut buf, filter.num_hashes);
+        put_u32(&mut buf, filter.bits.len() as u32);
+        for word in &filter.bits {
+            buf.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+    buf
+}
+
+/// Decodes filters from bytes encoded by [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<Vec<BloomFilter>> {
+    let mut reader = ByteReader { bytes, offset: 0 };
+
+    let version = reader.read_u8()?;
+    ensure!(
+        version == VERSION,
+        InvalidIndexDataSnafu {
+            reason: format!("unsupported version: {version}"),
+        }
+    );
+    let num_filters = reader.read_u32()? as usize;
+    let mut filters = Vec::with_capacity(num_filters.min(bytes.len()));
+    for _ in 0..num_filters {
+        let num_hashes = reader.read_u32()?;
+        ensure!(
+            (1..=MAX_NUM_HASHES).contains(&num_hashes),
+            InvalidIndexDataSnafu {
+                reason: format!("invalid number of hashes: {num_hashes}"),
+            }
+        );
+        let num_words = reader.read_u32()? as usize;
+        ensure!(
+            num_words > 0,
+            InvalidIndexDataSnafu {
+                reason: "empty filter",
+            }
+        );
+        let bits = (0..num_words)
+            .map(|_| reader.read_u64())
+            .collect::<Result<Vec<_>>>()?;
+        filters.push(BloomFilter { num_hashes, bits });
+    }
+
+    ensure!(
+        reader.offset == bytes.len(),
+        InvalidIndexDataSnafu {
+            reason: format!("{} trailing bytes", bytes.len() - reader.offset),
+        }
+    );
+    Ok(filters)
+}
+
+fn put_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl ByteReader<'_> {
+    fn read_u8(&mut self) -> Result<u8> {
+        let v = *self.bytes.get(self.offset).context(InvalidIndexDataSnafu {
+            reason: "unexpected end of data",
+        })?;
+        self.offset += 1;
+        Ok(v)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let v = self.read_bytes::<4>()?;
+        Ok(u32::from_le_bytes(v))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        let v = self.read_bytes::<8>()?;
+        Ok(u64::from_le_bytes(v))
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let v = self
+            .bytes
+            .get(self.offset..self.offset + N)
+            .context(InvalidIndexDataSnafu {
+                reason: "unexpected end of data",
+            })?;
+        self.offset += N;
+        Ok(v.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter_contains() {
+        let mut filter = BloomFilter::with_capacity(1000, 0.01);
+        for i in 0..1000 {
+            filter.insert(format!("item-{i}").as_bytes());
+        }
+        for i in 0..1000 {
+            assert!(filter.contains(format!("item-{i}").as_bytes()));
+        }
+
+        let false_positives = (1000..11000)
+            .filter(|i| filter.contains(format!("item-{i}").as_bytes()))
+            .count();
+        assert!(false_positives < 300, "false positives: {false_positives}");
+    }
+
+    #[test]
+    fn test_bloom_filter_empty() {
+        let filter = BloomFilter::with_capacity(0, 0.01);
+        assert_eq!(8, filter.memory_usage());
+        assert!(!filter.contains(b"hello"));
+        assert!(!filter.contains(b""));
+    }
+
+    #[test]
+    fn test_bloom_filter_encode_decode() {
+        let mut filters = vec![];
+        for n in [0, 1, 100] {
+            let mut filter = BloomFilter::with_capacity(n, 0.05);
+            for i in 0..n {
+                filter.insert(&i.to_le_bytes());
+            }
+            filters.push(filter);
+        }
+
+        let encoded = encode(&filters);
+        assert_eq!(filters, decode(&encoded).unwrap());
+        assert!(decode(&encode(&[])).unwrap().is_empty());
+
+        assert!(decode(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[2, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::bloom_filter::{encode, hash, BloomFilter};
+
+/// Creates a bloom filter for each segment of the rows, e.g. a row group of an SST.
+///
+/// Items of a segment are deduplicated before building its filter so that the filter
+/// is sized for the number of distinct items.
+pub struct BloomFilterCreator {
+    false_positive_rate: f64,
+    /// Filters of the sealed segments.
+    filters: Vec<BloomFilter>,
+    /// Hashes of the distinct items in the current segment.
+    hashes: HashSet<u64>,
+}
+
+impl BloomFilterCreator {
+    pub fn new(false_positive_rate: f64) -> Self {
+        Self {
+            false_positive_rate,
+            filters: vec![],
+            hashes: HashSet::new(),
+        }
+    }
+
+    /// Adds an item to the segment.
+    ///
+    /// Segments must be pushed in ascending order, all previous segments are sealed.
+    pub fn push(&mut self, segment: usize, item: &[u8]) {
+        debug_assert!(segment >= self.filters.len());
+        while self.filters.len() < segment {
+            self.seal();
+        }
+        self.hashes.insert(hash(item));
+    }
+
+    /// Finishes the creation and returns the encoded filters of `num_segments` segments.
+    ///
+    /// The creator is reset and can be reused afterwards.
+    pub fn finish(&mut self, num_segments: usize) -> Vec<u8> {
+        while self.filters.len() < num_segments {
+            self.seal();
+        }
+        self.hashes.clear();
+        encode(&std::mem::take(&mut self.filters))
+    }
+
+    /// Returns the memory usage of the creator in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.filters
+            .iter()
+            .map(BloomFilter::memory_usage)
+            .sum::<usize>()
+            + self.hashes.capacity() * std::mem::size_of::<u64>()
+    }
+
+    fn seal(&mut self) {
+        let mut filter = BloomFilter::with_capacity(self.hashes.len(), self.false_positive_rate);
+        for hash in self.hashes.drain() {
+            filter.insert_hash(hash);
+        }
+        self.filters.push(filter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bloom_filter::decode;
+
+    #[test]
+    fn test_bloom_filter_creator() {
+        let mut creator = BloomFilterCreator::new(0.01);
+        creator.push(0, b"a");
+        creator.push(0, b"b");
+        creator.push(0, b"a");
+        creator.push(2, b"c");
+        assert!(creator.memory_usage() > 0);
+
+        let filters = decode(&creator.finish(4)).unwrap();
+        assert_eq!(4, filters.len());
+        assert!(filters[0].contains(b"a"));
+        assert!(filters[0].contains(b"b"));
+        assert!(!filters[1].contains(b"a"));
+        assert!(filters[2].contains(b"c"));
+        assert!(!filters[3].contains(b"c"));
+    }
+}
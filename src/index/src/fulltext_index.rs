@@ -14,6 +14,7 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod analyzer;
 pub mod create;
 pub mod error;
 pub mod search;
@@ -29,13 +30,34 @@ pub struct Config {
 
     /// Whether the index should be case-sensitive.
     pub case_sensitive: bool,
+
+    /// Tokens to remove from the text, `_english_` stands for the built-in English stop words.
+    #[serde(default)]
+    pub stop_words: Vec<String>,
 }
 
 /// Analyzer to use for tokenization.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum Analyzer {
+    /// Splits the text by non-alphanumeric characters.
     #[default]
     English,
 
+    /// Segments the text by the jieba dictionary.
     Chinese,
+
+    /// Same as `English`, which is language-agnostic indeed.
+    Standard,
+
+    /// Splits the text by whitespaces only, keeps paths, URLs and IPs as a whole.
+    Whitespace,
+
+    /// Splits the text into trigrams, allows matching substrings of words.
+    Ngram,
+
+    /// Splits CJK characters into bigrams, without any dictionary.
+    Japanese,
+
+    /// Splits CJK characters into bigrams, without any dictionary.
+    Korean,
 }
@@ -0,0 +1,382 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text analyzers shared by the fulltext index creator, searcher and the
+//! query-time `matches` function, so that all of them see the same tokens.
+
+use tantivy::tokenizer::{
+    LowerCaser, SimpleTokenizer, StopWordFilter, TextAnalyzer, Token, TokenStream, Tokenizer,
+    TokenizerManager, WhitespaceTokenizer,
+};
+use tantivy_jieba::JiebaTokenizer;
+
+use crate::fulltext_index::{Analyzer, Config};
+
+/// The name of the tokenizer registered in the [`TokenizerManager`], which is used
+/// by text fields with default indexing options.
+const DEFAULT_TOKENIZER_NAME: &str = "default";
+
+/// The special stop word that expands to [`ENGLISH_STOP_WORDS`].
+pub const ENGLISH_STOP_WORDS_ALIAS: &str = "_english_";
+
+/// Stop words for English, port from Lucene's `EnglishAnalyzer`.
+const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is", "it",
+    "no", "not", "of", "on", "or", "such", "that", "the", "their", "then", "there", "these",
+    "they", "this", "to", "was", "will", "with",
+];
+
+/// The number of characters of each token produced by [`Analyzer::Ngram`].
+const NGRAM_SIZE: usize = 3;
+
+/// Builds a [`TokenizerManager`] whose default tokenizer is built from `config`.
+pub fn build_tokenizer_manager(config: &Config) -> TokenizerManager {
+    let tokenizer_manager = TokenizerManager::new();
+    tokenizer_manager.register(DEFAULT_TOKENIZER_NAME, build_text_analyzer(config));
+    tokenizer_manager
+}
+
+/// Builds a [`TextAnalyzer`] from `config`.
+pub fn build_text_analyzer(config: &Config) -> TextAnalyzer {
+    let mut builder = match config.analyzer {
+        Analyzer::English | Analyzer::Standard => {
+            TextAnalyzer::builder(SimpleTokenizer::default()).dynamic()
+        }
+        Analyzer::Chinese => TextAnalyzer::builder(JiebaTokenizer {}).dynamic(),
+        Analyzer::Whitespace => TextAnalyzer::builder(WhitespaceTokenizer::default()).dynamic(),
+        Analyzer::Ngram => TextAnalyzer::builder(NgramTokenizer).dynamic(),
+        Analyzer::Japanese | Analyzer::Korean => {
+            TextAnalyzer::builder(CjkBigramTokenizer).dynamic()
+        }
+    };
+
+    if !config.case_sensitive {
+        builder = builder.filter_dynamic(LowerCaser);
+    }
+
+    let stop_words = expand_stop_words(&config.stop_words, config.case_sensitive);
+    if !stop_words.is_empty() {
+        builder = builder.filter_dynamic(StopWordFilter::remove(stop_words));
+    }
+
+    builder.build()
+}
+
+/// Expands the [`ENGLISH_STOP_WORDS_ALIAS`] and normalizes the case of the stop words
+/// if the analyzer is case-insensitive, as they are compared with lowercased tokens.
+fn expand_stop_words(stop_words: &[String], case_sensitive: bool) -> Vec<String> {
+    let mut words = Vec::with_capacity(stop_words.len());
+    for word in stop_words {
+        if word == ENGLISH_STOP_WORDS_ALIAS {
+            words.extend(ENGLISH_STOP_WORDS.iter().map(|w| w.to_string()));
+        } else if case_sensitive {
+            words.push(word.clone());
+        } else {
+            words.push(word.to_lowercase());
+        }
+    }
+    words
+}
+
+/// `FulltextTokenizer` splits text into tokens the same way as the fulltext index
+/// built with the same [`Config`].
+pub struct FulltextTokenizer {
+    analyzer: TextAnalyzer,
+}
+
+impl FulltextTokenizer {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            analyzer: build_text_analyzer(config),
+        }
+    }
+
+    /// Tokenizes the `text` and returns the tokens with their positions.
+    pub fn tokenize(&mut self, text: &str) -> Vec<(usize, String)> {
+        let mut tokens = vec![];
+        let mut stream = self.analyzer.token_stream(text);
+        stream.process(&mut |token| tokens.push((token.position, token.text.clone())));
+        tokens
+    }
+}
+
+/// A [`TokenStream`] over pre-computed tokens.
+pub struct VecTokenStream {
+    tokens: Vec<Token>,
+    /// Index of the next token to emit.
+    next: usize,
+}
+
+impl VecTokenStream {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, next: 0 }
+    }
+}
+
+impl TokenStream for VecTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
+fn new_token(text: &str, offset_from: usize, offset_to: usize, position: usize) -> Token {
+    Token {
+        offset_from,
+        offset_to,
+        position,
+        text: text[offset_from..offset_to].to_string(),
+        position_length: 1,
+    }
+}
+
+/// Splits the text into overlapping n-grams of [`NGRAM_SIZE`] characters within each
+/// alphanumeric run, e.g. `error` becomes `err`, `rro`, `ror`. A run shorter than
+/// [`NGRAM_SIZE`] is emitted as a whole.
+///
+/// Positions of n-grams increase by one, so a phrase query with the n-grams of a
+/// keyword finds any text containing the keyword, which is useful for paths and URLs.
+#[derive(Debug, Clone)]
+pub struct NgramTokenizer;
+
+impl Tokenizer for NgramTokenizer {
+    type TokenStream<'a> = VecTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        let mut tokens = vec![];
+        for (start, end) in split_runs(text, char::is_alphanumeric) {
+            let boundaries = char_boundaries(text, start, end);
+            let num_chars = boundaries.len() - 1;
+            if num_chars <= NGRAM_SIZE {
+                tokens.push(new_token(text, start, end, tokens.len()));
+                continue;
+            }
+            for i in 0..=num_chars - NGRAM_SIZE {
+                tokens.push(new_token(
+                    text,
+                    boundaries[i],
+                    boundaries[i + NGRAM_SIZE],
+                    tokens.len(),
+                ));
+            }
+        }
+        VecTokenStream::new(tokens)
+    }
+}
+
+/// A dictionary-free tokenizer for Japanese and Korean.
+///
+/// Runs of CJK characters (Han, Hiragana, Katakana and Hangul) are split into
+/// overlapping bigrams, while other alphanumeric runs are kept as words. A single
+/// CJK character surrounded by non-CJK characters is emitted as a unigram.
+#[derive(Debug, Clone)]
+pub struct CjkBigramTokenizer;
+
+impl Tokenizer for CjkBigramTokenizer {
+    type TokenStream<'a> = VecTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        let mut tokens = vec![];
+        for (start, end) in split_runs(text, char::is_alphanumeric) {
+            let mut word_start = None;
+            let mut cjk_run = vec![];
+            for (offset, c) in text[start..end].char_indices() {
+                let offset = start + offset;
+                if is_cjk(c) {
+                    if let Some(word_start) = word_start.take() {
+                        tokens.push(new_token(text, word_start, offset, tokens.len()));
+                    }
+                    cjk_run.push(offset);
+                } else {
+                    push_bigrams(text, &cjk_run, offset, &mut tokens);
+                    cjk_run.clear();
+                    if word_start.is_none() {
+                        word_start = Some(offset);
+                    }
+                }
+            }
+            if let Some(word_start) = word_start {
+                tokens.push(new_token(text, word_start, end, tokens.len()));
+            }
+            push_bigrams(text, &cjk_run, end, &mut tokens);
+        }
+        VecTokenStream::new(tokens)
+    }
+}
+
+/// Pushes bigrams of the CJK run starting at byte offsets `run` and ending at `end`.
+fn push_bigrams(text: &str, run: &[usize], end: usize, tokens: &mut Vec<Token>) {
+    match run.len() {
+        0 => {}
+        1 => tokens.push(new_token(text, run[0], end, tokens.len())),
+        len => {
+            for i in 0..len - 1 {
+                let to = if i + 2 < len { run[i + 2] } else { end };
+                tokens.push(new_token(text, run[i], to, tokens.len()));
+            }
+        }
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' // Hangul Jamo
+        | '\u{3040}'..='\u{309F}' // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{3130}'..='\u{318F}' // Hangul Compatibility Jamo
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Halfwidth Katakana
+    )
+}
+
+/// Returns the byte ranges of the maximal runs of characters satisfying `pred`.
+fn split_runs(text: &str, pred: impl Fn(char) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = vec![];
+    let mut start = None;
+    for (offset, c) in text.char_indices() {
+        if pred(c) {
+            if start.is_none() {
+                start = Some(offset);
+            }
+        } else if let Some(start) = start.take() {
+            runs.push((start, offset));
+        }
+    }
+    if let Some(start) = start {
+        runs.push((start, text.len()));
+    }
+    runs
+}
+
+/// Returns the byte offsets of the characters in `text[start..end]`, plus `end`.
+fn char_boundaries(text: &str, start: usize, end: usize) -> Vec<usize> {
+    text[start..end]
+        .char_indices()
+        .map(|(offset, _)| start + offset)
+        .chain(Some(end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenize(config: Config, text: &str) -> Vec<String> {
+        FulltextTokenizer::new(&config)
+            .tokenize(text)
+            .into_iter()
+            .map(|(_, token)| token)
+            .collect()
+    }
+
+    #[test]
+    fn test_standard_analyzer() {
+        let config = Config {
+            analyzer: Analyzer::Standard,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config, "GET /api/v1/Users from 10.0.0.1"),
+            ["get", "api", "v1", "users", "from", "10", "0", "0", "1"]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_analyzer() {
+        let config = Config {
+            analyzer: Analyzer::Whitespace,
+            case_sensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config, "GET /api/v1/users from 10.0.0.1"),
+            ["GET", "/api/v1/users", "from", "10.0.0.1"]
+        );
+    }
+
+    #[test]
+    fn test_ngram_analyzer() {
+        let config = Config {
+            analyzer: Analyzer::Ngram,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config, "Error at /ab"),
+            ["err", "rro", "ror", "at", "ab"]
+        );
+    }
+
+    #[test]
+    fn test_cjk_bigram_analyzer() {
+        let config = Config {
+            analyzer: Analyzer::Japanese,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config.clone(), "東京都に住むAlice"),
+            ["東京", "京都", "都に", "に住", "住む", "alice"]
+        );
+        assert_eq!(tokenize(config, "猫 cat"), ["猫", "cat"]);
+
+        let config = Config {
+            analyzer: Analyzer::Korean,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config, "안녕하세요"),
+            ["안녕", "녕하", "하세", "세요"]
+        );
+    }
+
+    #[test]
+    fn test_stop_words() {
+        let config = Config {
+            stop_words: vec![ENGLISH_STOP_WORDS_ALIAS.to_string(), "Foo".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            tokenize(config, "The quick fox is at foo bar"),
+            ["quick", "fox", "bar"]
+        );
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let mut tokenizer = FulltextTokenizer::new(&Config {
+            analyzer: Analyzer::Ngram,
+            ..Default::default()
+        });
+        let positions = tokenizer
+            .tokenize("abcd xy")
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [0, 1, 2]);
+    }
+}
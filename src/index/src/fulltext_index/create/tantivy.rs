@@ -19,13 +19,12 @@ use snafu::{OptionExt, ResultExt};
 use tantivy::indexer::NoMergePolicy;
 use tantivy::schema::{Schema, STORED, TEXT};
 use tantivy::store::{Compressor, ZstdCompressor};
-use tantivy::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, TokenizerManager};
 use tantivy::{doc, Index, IndexWriter};
-use tantivy_jieba::JiebaTokenizer;
 
+use crate::fulltext_index::analyzer::build_tokenizer_manager;
 use crate::fulltext_index::create::FulltextIndexCreator;
 use crate::fulltext_index::error::{FinishedSnafu, IoSnafu, JoinSnafu, Result, TantivySnafu};
-use crate::fulltext_index::{Analyzer, Config};
+use crate::fulltext_index::Config;
 
 pub const TEXT_FIELD_NAME: &str = "greptime_fulltext_text";
 pub const ROWID_FIELD_NAME: &str = "greptime_fulltext_rowid";
@@ -61,7 +60,7 @@ impl TantivyFulltextIndexCreator {
 
         let mut index = Index::create_in_dir(path, schema).context(TantivySnafu)?;
         index.settings_mut().docstore_compression = Compressor::Zstd(ZstdCompressor::default());
-        index.set_tokenizers(Self::build_tokenizer(&config));
+        index.set_tokenizers(build_tokenizer_manager(&config));
 
         let memory_limit = Self::sanitize_memory_limit(memory_limit);
 
@@ -79,22 +78,6 @@ impl TantivyFulltextIndexCreator {
         })
     }
 
-    fn build_tokenizer(config: &Config) -> TokenizerManager {
-        let mut builder = match config.analyzer {
-            Analyzer::English => TextAnalyzer::builder(SimpleTokenizer::default()).dynamic(),
-            Analyzer::Chinese => TextAnalyzer::builder(JiebaTokenizer {}).dynamic(),
-        };
-
-        if !config.case_sensitive {
-            builder = builder.filter_dynamic(LowerCaser);
-        }
-
-        let tokenizer = builder.build();
-        let tokenizer_manager = TokenizerManager::new();
-        tokenizer_manager.register("default", tokenizer);
-        tokenizer_manager
-    }
-
     fn sanitize_memory_limit(memory_limit: usize) -> usize {
         // Port from tantivy::indexer::index_writer::{MEMORY_BUDGET_NUM_BYTES_MIN, MEMORY_BUDGET_NUM_BYTES_MAX}
         const MARGIN_IN_BYTES: usize = 1_000_000;
@@ -140,6 +123,7 @@ mod tests {
     use tantivy::TantivyDocument;
 
     use super::*;
+    use crate::fulltext_index::Analyzer;
 
     #[tokio::test]
     async fn test_creator_basic() {
@@ -221,6 +205,7 @@ mod tests {
             let config = Config {
                 case_sensitive: true,
                 analyzer: Analyzer::Chinese,
+                ..Config::default()
             };
             build_index(&texts, temp_dir.path(), config, memory_limit).await;
 
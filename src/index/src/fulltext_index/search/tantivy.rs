@@ -24,11 +24,13 @@ use tantivy::query::QueryParser;
 use tantivy::schema::{Field, Value};
 use tantivy::{Index, IndexReader, ReloadPolicy, TantivyDocument};
 
+use crate::fulltext_index::analyzer::build_tokenizer_manager;
 use crate::fulltext_index::create::{ROWID_FIELD_NAME, TEXT_FIELD_NAME};
 use crate::fulltext_index::error::{
     Result, TantivyDocNotFoundSnafu, TantivyParserSnafu, TantivySnafu,
 };
 use crate::fulltext_index::search::{FulltextIndexSearcher, RowId};
+use crate::fulltext_index::Config;
 
 /// `TantivyFulltextIndexSearcher` is a searcher using Tantivy.
 pub struct TantivyFulltextIndexSearcher {
@@ -42,10 +44,14 @@ pub struct TantivyFulltextIndexSearcher {
 
 impl TantivyFulltextIndexSearcher {
     /// Creates a new `TantivyFulltextIndexSearcher`.
-    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+    ///
+    /// The `config` must be the one used to create the index, so that queries are
+    /// tokenized in the same way as the indexed text.
+    pub fn new(path: impl AsRef<Path>, config: &Config) -> Result<Self> {
         let now = Instant::now();
 
-        let index = Index::open_in_dir(path.as_ref()).context(TantivySnafu)?;
+        let mut index = Index::open_in_dir(path.as_ref()).context(TantivySnafu)?;
+        index.set_tokenizers(build_tokenizer_manager(config));
         let reader = index
             .reader_builder()
             .reload_policy(ReloadPolicy::Manual)
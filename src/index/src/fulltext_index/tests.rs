@@ -42,9 +42,9 @@ async fn test_search(
     query: &str,
     expected: impl IntoIterator<Item = RowId>,
 ) {
-    let index_path = create_index(prefix, texts, config).await;
+    let index_path = create_index(prefix, texts, config.clone()).await;
 
-    let searcher = TantivyFulltextIndexSearcher::new(index_path.path()).unwrap();
+    let searcher = TantivyFulltextIndexSearcher::new(index_path.path(), &config).unwrap();
     let results = searcher.search(query).await.unwrap();
 
     let expected = expected.into_iter().collect::<BTreeSet<_>>();
@@ -165,3 +165,66 @@ async fn test_config_chinese_analyzer() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_config_whitespace_analyzer() {
+    test_search(
+        "test_config_whitespace_analyzer_",
+        Config {
+            analyzer: Analyzer::Whitespace,
+            ..Default::default()
+        },
+        vec![
+            "GET /api/v1/users from 10.0.0.1",
+            "GET /api/v1/users/1 from 10.0.0.2",
+        ],
+        "\"/api/v1/users\"",
+        [0],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_config_ngram_analyzer() {
+    test_search(
+        "test_config_ngram_analyzer_",
+        Config {
+            analyzer: Analyzer::Ngram,
+            ..Default::default()
+        },
+        vec!["ConnectionRefused", "connection reset", "refuse to connect"],
+        "refused",
+        [0],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_config_japanese_analyzer() {
+    test_search(
+        "test_config_japanese_analyzer_",
+        Config {
+            analyzer: Analyzer::Japanese,
+            ..Default::default()
+        },
+        vec!["東京都に住む", "京都に行く", "東北に住む"],
+        "京都",
+        [0, 1],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_config_stop_words() {
+    test_search(
+        "test_config_stop_words_",
+        Config {
+            stop_words: vec!["_english_".to_string()],
+            ..Default::default()
+        },
+        vec!["the apple", "an apple", "the banana"],
+        "the apple",
+        [0, 1],
+    )
+    .await;
+}
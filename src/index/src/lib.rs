@@ -14,5 +14,7 @@
 
 #![feature(iter_partition_in_place)]
 
+pub mod bloom_filter;
 pub mod fulltext_index;
 pub mod inverted_index;
+pub mod vector_index;
@@ -0,0 +1,101 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+pub mod error;
+pub mod hnsw;
+
+/// Row id of a vector in the index, i.e. the offset of the row in the SST file.
+pub type RowId = u32;
+
+/// Configuration for vector index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    /// Dimension of the indexed vectors.
+    pub dim: usize,
+
+    /// Metric to measure the distance between vectors.
+    pub metric: Metric,
+
+    /// Maximum number of neighbors of a node in the upper layers of the graph.
+    /// Nodes in the bottom layer can have twice as many neighbors.
+    pub connectivity: usize,
+
+    /// Number of candidates to consider when inserting a vector.
+    pub expansion_add: usize,
+}
+
+impl Config {
+    pub fn new(dim: usize, metric: Metric) -> Self {
+        Self {
+            dim,
+            metric,
+            connectivity: 16,
+            expansion_add: 128,
+        }
+    }
+}
+
+/// Metric to measure the distance between vectors.
+///
+/// Distances are always "smaller is nearer", so the inner product is negated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Metric {
+    #[default]
+    L2sq,
+
+    Cosine,
+
+    InnerProduct,
+}
+
+impl Metric {
+    /// Computes the distance between two vectors of the same dimension.
+    pub fn distance(&self, lhs: &[f32], rhs: &[f32]) -> f32 {
+        match self {
+            Metric::L2sq => lhs.iter().zip(rhs).map(|(l, r)| (l - r) * (l - r)).sum(),
+            Metric::Cosine => {
+                let norm = (dot(lhs, lhs) * dot(rhs, rhs)).sqrt();
+                if norm == 0.0 {
+                    1.0
+                } else {
+                    1.0 - dot(lhs, rhs) / norm
+                }
+            }
+            Metric::InnerProduct => -dot(lhs, rhs),
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Metric::L2sq => 0,
+            Metric::Cosine => 1,
+            Metric::InnerProduct => 2,
+        }
+    }
+
+    fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(Metric::L2sq),
+            1 => Some(Metric::Cosine),
+            2 => Some(Metric::InnerProduct),
+            _ => None,
+        }
+    }
+}
+
+fn dot(lhs: &[f32], rhs: &[f32]) -> f32 {
+    lhs.iter().zip(rhs).map(|(l, r)| l * r).sum()
+}
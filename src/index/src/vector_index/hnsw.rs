@@ -0,0 +1,607 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A Hierarchical Navigable Small World (HNSW) graph for approximate nearest neighbor search.
+//!
+//! See <https://arxiv.org/abs/1603.09320> for the details of the algorithm.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+use snafu::{ensure, OptionExt};
+
+use crate::vector_index::error::{DimensionMismatchSnafu, InvalidIndexDataSnafu, Result};
+use crate::vector_index::{Config, Metric, RowId};
+
+/// Version of the encoded index.
+const VERSION: u8 = 1;
+/// Placeholder of the entry point in an empty encoded index.
+const NO_ENTRY_POINT: u32 = u32::MAX;
+/// Upper bound of the level of a node, which is practically never reached.
+const MAX_LEVEL: usize = 16;
+
+/// A node in the graph, with its neighbors in each level it belongs to.
+#[derive(Debug)]
+struct Node {
+    row_id: RowId,
+    vector: Vec<f32>,
+    neighbors: Vec<Vec<u32>>,
+}
+
+impl Node {
+    fn level(&self) -> usize {
+        self.neighbors.len() - 1
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    node: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+/// An in-memory HNSW index that can be encoded to and decoded from bytes.
+#[derive(Debug)]
+pub struct HnswIndex {
+    config: Config,
+    nodes: Vec<Node>,
+    entry_point: Option<u32>,
+    /// State of the random generator deciding levels of nodes.
+    /// A fixed seed makes the index deterministic for the same input.
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    /// Creates an empty index.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            entry_point: None,
+            rng_state: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the number of vectors in the index.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the memory usage of the index in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| {
+                std::mem::size_of::<Node>()
+                    + node.vector.len() * std::mem::size_of::<f32>()
+                    + node
+                        .neighbors
+                        .iter()
+                        .map(|links| links.len() * std::mem::size_of::<u32>())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Adds a vector with its row id to the index.
+    pub fn add(&mut self, row_id: RowId, vector: &[f32]) -> Result<()> {
+        self.check_dim(vector)?;
+
+        let level = self.random_level();
+        let new_node = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            row_id,
+            vector: vector.to_vec(),
+            neighbors: vec![vec![]; level + 1],
+        });
+
+        let Some(mut entry) = self.entry_point else {
+            self.entry_point = Some(new_node);
+            return Ok(());
+        };
+
+        let top_level = self.nodes[entry as usize].level();
+        let mut entry_distance = self.distance_to(vector, entry);
+        for lc in (level + 1..=top_level).rev() {
+            (entry, entry_distance) = self.greedy_search(vector, entry, entry_distance, lc);
+        }
+
+        let mut entries = vec![Candidate {
+            distance: entry_distance,
+            node: entry,
+        }];
+        for lc in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(vector, &entries, self.config.expansion_add, lc);
+            let neighbors = candidates
+                .iter()
+                .take(self.max_connections(lc))
+                .map(|c| c.node)
+                .collect::<Vec<_>>();
+            for &neighbor in &neighbors {
+                self.connect(neighbor, new_node, lc);
+            }
+            self.nodes[new_node as usize].neighbors[lc] = neighbors;
+            entries = candidates;
+        }
+
+        if level > top_level {
+            self.entry_point = Some(new_node);
+        }
+
+        Ok(())
+    }
+
+    /// Searches `k` nearest vectors of `query`, returns row ids with their distances
+    /// in ascending order of distance.
+    ///
+    /// `expansion_search` is the number of candidates to consider, a larger value
+    /// leads to a better recall at the cost of speed.
+    pub fn search(
+        &self,
+        query: &[f32],
+        k: usize,
+        expansion_search: usize,
+    ) -> Result<Vec<(RowId, f32)>> {
+        self.check_dim(query)?;
+
+        let Some(mut entry) = self.entry_point else {
+            return Ok(vec![]);
+        };
+        if k == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut entry_distance = self.distance_to(query, entry);
+        for lc in (1..=self.nodes[entry as usize].level()).rev() {
+            (entry, entry_distance) = self.greedy_search(query, entry, entry_distance, lc);
+        }
+
+        let entries = [Candidate {
+            distance: entry_distance,
+            node: entry,
+        }];
+        let candidates = self.search_layer(query, &entries, expansion_search.max(k), 0);
+        Ok(candidates
+            .into_iter()
+            .take(k)
+            .map(|c| (self.nodes[c.node as usize].row_id, c.distance))
+            .collect())
+    }
+
+    /// Encodes the index to bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.memory_usage() + 32);
+        buf.push(VERSION);
+        buf.push(self.config.metric.to_u8());
+        put_u32(&mut buf, self.config.dim as u32);
+        put_u32(&mut buf, self.config.connectivity as u32);
+        put_u32(&mut buf, self.config.expansion_add as u32);
+        put_u32(&mut buf, self.entry_point.unwrap_or(NO_ENTRY_POINT));
+        put_u32(&mut buf, self.nodes.len() as u32);
+        for node in &self.nodes {
+            put_u32(&mut buf, node.row_id);
+            for v in &node.vector {
+                buf.extend_from_slice(&v.to_le_bytes());
+            }
+            put_u32(&mut buf, node.neighbors.len() as u32);
+            for links in &node.neighbors {
+                put_u32(&mut buf, links.len() as u32);
+                for link in links {
+                    put_u32(&mut buf, *link);
+                }
+            }
+        }
+        buf
+    }
+
+    /// Decodes the index from bytes encoded by [`HnswIndex::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader { bytes, offset: 0 };
+
+        let version = reader.read_u8()?;
+        ensure!(
+            version == VERSION,
+            InvalidIndexDataSnafu {
+                reason: format!("unsupported version: {version}"),
+            }
+        );
+        let metric = reader.read_u8()?;
+        let metric = Metric::from_u8(metric).context(InvalidIndexDataSnafu {
+            reason: format!("unknown metric: {metric}"),
+        })?;
+        let dim = reader.read_u32()? as usize;
+        let connectivity = reader.read_u32()? as usize;
+        let expansion_add = reader.read_u32()? as usize;
+        let entry_point = reader.read_u32()?;
+        let num_nodes = reader.read_u32()? as usize;
+
+        let mut nodes = Vec::with_capacity(num_nodes.min(bytes.len()));
+        for _ in 0..num_nodes {
+            let row_id = reader.read_u32()?;
+            let vector = (0..dim)
+                .map(|_| reader.read_u32().map(f32::from_bits))
+                .collect::<Result<Vec<_>>>()?;
+            let num_levels = reader.read_u32()? as usize;
+            ensure!(
+                (1..=MAX_LEVEL + 1).contains(&num_levels),
+                InvalidIndexDataSnafu {
+                    reason: format!("invalid number of levels: {num_levels}"),
+                }
+            );
+            let neighbors = (0..num_levels)
+                .map(|_| {
+                    let num_links = reader.read_u32()? as usize;
+                    (0..num_links)
+                        .map(|_| reader.read_u32())
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            nodes.push(Node {
+                row_id,
+                vector,
+                neighbors,
+            });
+        }
+
+        ensure!(
+            reader.offset == bytes.len(),
+            InvalidIndexDataSnafu {
+                reason: format!("{} trailing bytes", bytes.len() - reader.offset),
+            }
+        );
+        for node in &nodes {
+            for (level, links) in node.neighbors.iter().enumerate() {
+                ensure!(
+                    links.iter().all(|link| nodes
+                        .get(*link as usize)
+                        .is_some_and(|n| n.level() >= level)),
+                    InvalidIndexDataSnafu {
+                        reason: "link to a nonexistent node",
+                    }
+                );
+            }
+        }
+        let entry_point = if entry_point == NO_ENTRY_POINT {
+            ensure!(
+                nodes.is_empty(),
+                InvalidIndexDataSnafu {
+                    reason: "missing entry point",
+                }
+            );
+            None
+        } else {
+            ensure!(
+                (entry_point as usize) < nodes.len(),
+                InvalidIndexDataSnafu {
+                    reason: format!("invalid entry point: {entry_point}"),
+                }
+            );
+            Some(entry_point)
+        };
+
+        let mut index = Self::new(Config {
+            dim,
+            metric,
+            connectivity,
+            expansion_add,
+        });
+        index.nodes = nodes;
+        index.entry_point = entry_point;
+        Ok(index)
+    }
+}
+
+impl HnswIndex {
+    fn check_dim(&self, vector: &[f32]) -> Result<()> {
+        ensure!(
+            vector.len() == self.config.dim,
+            DimensionMismatchSnafu {
+                expected: self.config.dim,
+                actual: vector.len(),
+            }
+        );
+        Ok(())
+    }
+
+    fn distance_to(&self, query: &[f32], node: u32) -> f32 {
+        self.config
+            .metric
+            .distance(query, &self.nodes[node as usize].vector)
+    }
+
+    fn max_connections(&self, level: usize) -> usize {
+        let connectivity = self.config.connectivity.max(2);
+        if level == 0 {
+            connectivity * 2
+        } else {
+            connectivity
+        }
+    }
+
+    /// Draws a level from an exponentially decaying distribution, using xorshift64*.
+    fn random_level(&mut self) -> usize {
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let random = self.rng_state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+
+        // uniform in (0, 1]
+        let uniform = ((random >> 11) + 1) as f64 / (1u64 << 53) as f64;
+        let multiplier = 1.0 / (self.config.connectivity.max(2) as f64).ln();
+        ((-uniform.ln() * multiplier) as usize).min(MAX_LEVEL)
+    }
+
+    /// Moves greedily to the nearest node of `query` in the given level.
+    fn greedy_search(&self, query: &[f32], entry: u32, distance: f32, level: usize) -> (u32, f32) {
+        let (mut current, mut current_distance) = (entry, distance);
+        loop {
+            let mut changed = false;
+            for &neighbor in &self.nodes[current as usize].neighbors[level] {
+                let distance = self.distance_to(query, neighbor);
+                if distance < current_distance {
+                    current = neighbor;
+                    current_distance = distance;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return (current, current_distance);
+            }
+        }
+    }
+
+    /// Returns at most `ef` nearest nodes of `query` in the given level, in ascending
+    /// order of distance.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entries: &[Candidate],
+        ef: usize,
+        level: usize,
+    ) -> Vec<Candidate> {
+        let ef = ef.max(1);
+        let mut visited = entries.iter().map(|c| c.node).collect::<HashSet<_>>();
+        let mut candidates = entries
+            .iter()
+            .copied()
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut results = entries.iter().copied().collect::<BinaryHeap<_>>();
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if let Some(furthest) = results.peek() {
+                if results.len() >= ef && current.distance > furthest.distance {
+                    break;
+                }
+            }
+
+            for &neighbor in &self.nodes[current.node as usize].neighbors[level] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    distance: self.distance_to(query, neighbor),
+                    node: neighbor,
+                };
+                let is_closer = results
+                    .peek()
+                    .is_some_and(|furthest| candidate.distance < furthest.distance);
+                if results.len() < ef || is_closer {
+                    candidates.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Adds a link from `from` to `to` in the given level, and drops the furthest
+    /// links of `from` if it has too many.
+    fn connect(&mut self, from: u32, to: u32, level: usize) {
+        let max_connections = self.max_connections(level);
+        let node = &self.nodes[from as usize];
+        let mut links = node.neighbors[level].clone();
+        links.push(to);
+
+        if links.len() > max_connections {
+            let mut candidates = links
+                .into_iter()
+                .map(|link| Candidate {
+                    distance: self.distance_to(&node.vector, link),
+                    node: link,
+                })
+                .collect::<Vec<_>>();
+            candidates.sort_unstable();
+            links = candidates
+                .into_iter()
+                .take(max_connections)
+                .map(|c| c.node)
+                .collect();
+        }
+
+        self.nodes[from as usize].neighbors[level] = links;
+    }
+}
+
+fn put_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl ByteReader<'_> {
+    fn read_u8(&mut self) -> Result<u8> {
+        let v = *self.bytes.get(self.offset).context(InvalidIndexDataSnafu {
+            reason: "unexpected end of data",
+        })?;
+        self.offset += 1;
+        Ok(v)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let v = self
+            .bytes
+            .get(self.offset..self.offset + 4)
+            .context(InvalidIndexDataSnafu {
+                reason: "unexpected end of data",
+            })?;
+        self.offset += 4;
+        Ok(u32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generates deterministic pseudo-random vectors.
+    fn mock_vectors(num: usize, dim: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u32;
+        (0..num)
+            .map(|_| {
+                (0..dim)
+                    .map(|_| {
+                        state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                        (state >> 8) as f32 / (1 << 24) as f32 - 0.5
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn brute_force(vectors: &[Vec<f32>], query: &[f32], metric: Metric, k: usize) -> Vec<RowId> {
+        let mut distances = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (metric.distance(query, v), i as RowId))
+            .collect::<Vec<_>>();
+        distances.sort_by(|a, b| a.0.total_cmp(&b.0));
+        distances.into_iter().take(k).map(|(_, i)| i).collect()
+    }
+
+    fn build_index(vectors: &[Vec<f32>], metric: Metric) -> HnswIndex {
+        let mut index = HnswIndex::new(Config::new(vectors[0].len(), metric));
+        for (i, v) in vectors.iter().enumerate() {
+            index.add(i as RowId, v).unwrap();
+        }
+        index
+    }
+
+    #[test]
+    fn test_hnsw_search_recall() {
+        let vectors = mock_vectors(1000, 8);
+        let queries = mock_vectors(1010, 8).split_off(1000);
+
+        for metric in [Metric::L2sq, Metric::Cosine, Metric::InnerProduct] {
+            let index = build_index(&vectors, metric);
+            assert_eq!(1000, index.len());
+
+            let mut hits = 0;
+            for query in &queries {
+                let expected = brute_force(&vectors, query, metric, 10);
+                let actual = index.search(query, 10, 64).unwrap();
+                assert_eq!(10, actual.len());
+                assert!(actual.windows(2).all(|w| w[0].1 <= w[1].1));
+                hits += actual
+                    .iter()
+                    .filter(|(row_id, _)| expected.contains(row_id))
+                    .count();
+            }
+            assert!(hits >= 90, "metric: {metric:?}, hits: {hits}");
+        }
+    }
+
+    #[test]
+    fn test_hnsw_search_self() {
+        let vectors = mock_vectors(200, 4);
+        let index = build_index(&vectors, Metric::L2sq);
+        for (i, v) in vectors.iter().enumerate() {
+            let result = index.search(v, 1, 32).unwrap();
+            assert_eq!(i as RowId, result[0].0);
+            assert_eq!(0.0, result[0].1);
+        }
+    }
+
+    #[test]
+    fn test_hnsw_encode_decode() {
+        let vectors = mock_vectors(300, 4);
+        let index = build_index(&vectors, Metric::Cosine);
+        let decoded = HnswIndex::decode(&index.encode()).unwrap();
+        assert_eq!(index.config(), decoded.config());
+        assert_eq!(index.len(), decoded.len());
+        for query in vectors.iter().take(20) {
+            assert_eq!(
+                index.search(query, 5, 16).unwrap(),
+                decoded.search(query, 5, 16).unwrap()
+            );
+        }
+
+        let empty = HnswIndex::new(Config::new(4, Metric::L2sq));
+        let decoded = HnswIndex::decode(&empty.encode()).unwrap();
+        assert!(decoded.is_empty());
+        assert!(decoded.search(&[0.0; 4], 5, 16).unwrap().is_empty());
+
+        let encoded = index.encode();
+        assert!(HnswIndex::decode(&encoded[..encoded.len() - 1]).is_err());
+        assert!(HnswIndex::decode(&[]).is_err());
+    }
+
+    #[test]
+    fn test_hnsw_dimension_mismatch() {
+        let mut index = HnswIndex::new(Config::new(3, Metric::L2sq));
+        assert!(index.add(0, &[1.0, 2.0]).is_err());
+        index.add(0, &[1.0, 2.0, 3.0]).unwrap();
+        assert!(index.search(&[1.0], 1, 1).is_err());
+    }
+}
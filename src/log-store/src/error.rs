@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::sync::Arc;
 
 use common_error::ext::ErrorExt;
 use common_macro::stack_trace_debug;
@@ -321,6 +322,72 @@ pub enum Error {
         #[snafu(implicit)]
         location: Location,
     },
+
+    #[snafu(display("Failed to list wal segments in directory: {}", dir))]
+    ListSegments {
+        dir: String,
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: object_store::Error,
+    },
+
+    #[snafu(display("Failed to read wal segment: {}", path))]
+    ReadSegment {
+        path: String,
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: object_store::Error,
+    },
+
+    #[snafu(display("Failed to write wal segment: {}", path))]
+    WriteSegment {
+        path: String,
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: object_store::Error,
+    },
+
+    #[snafu(display("Failed to delete wal segments in directory: {}", dir))]
+    DeleteSegments {
+        dir: String,
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: object_store::Error,
+    },
+
+    #[snafu(display("Corrupted wal segment: {}, reason: {}", path, reason))]
+    CorruptedSegment {
+        path: String,
+        reason: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to commit a group of wal entries"))]
+    GroupCommit {
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: Arc<Error>,
+    },
+
+    #[snafu(display("The group commit worker of ObjectStoreLogStore is stopped"))]
+    GroupCommitWorkerStopped {
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to wait for the result of group commit"))]
+    WaitGroupCommit {
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: tokio::sync::oneshot::error::RecvError,
+    },
 }
 
 impl ErrorExt for Error {
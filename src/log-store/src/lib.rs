@@ -19,5 +19,6 @@
 pub mod error;
 pub mod kafka;
 pub mod metrics;
+pub mod object_store;
 pub mod raft_engine;
 pub mod test_util;
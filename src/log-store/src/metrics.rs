@@ -44,6 +44,14 @@ lazy_static! {
     pub static ref METRIC_RAFT_ENGINE_READ_BYTES_TOTAL: IntCounter = METRIC_LOGSTORE_OP_BYTES_TOTAL.with_label_values(
         &["raft-engine", "read"],
     );
+    /// Counter of bytes of the append_batch operation on the object store logstore.
+    pub static ref METRIC_OBJECT_STORE_APPEND_BATCH_BYTES_TOTAL: IntCounter = METRIC_LOGSTORE_OP_BYTES_TOTAL.with_label_values(
+        &["object-store", "append_batch"],
+    );
+    /// Counter of bytes of the read operation on the object store logstore.
+    pub static ref METRIC_OBJECT_STORE_READ_BYTES_TOTAL: IntCounter = METRIC_LOGSTORE_OP_BYTES_TOTAL.with_label_values(
+        &["object-store", "read"],
+    );
 
     /// Timer of operations on a logstore.
     pub static ref METRIC_LOGSTORE_OP_ELAPSED: HistogramVec = register_histogram_vec!(
@@ -62,4 +70,9 @@ lazy_static! {
     /// Timer of the append_batch operation on the raft-engine logstore.
     /// This timer only measures the duration of the read operation, not measures the total duration of replay.
     pub static ref METRIC_RAFT_ENGINE_READ_ELAPSED: Histogram = METRIC_LOGSTORE_OP_ELAPSED.with_label_values(&["raft-engine", "read"]);
+    /// Timer of the append_batch operation on the object store logstore.
+    pub static ref METRIC_OBJECT_STORE_APPEND_BATCH_ELAPSED: Histogram = METRIC_LOGSTORE_OP_ELAPSED.with_label_values(&["object-store", "append_batch"]);
+    /// Timer of the read operation on the object store logstore.
+    /// This timer only measures the duration of listing segments, not measures the total duration of replay.
+    pub static ref METRIC_OBJECT_STORE_READ_ELAPSED: Histogram = METRIC_LOGSTORE_OP_ELAPSED.with_label_values(&["object-store", "read"]);
 }
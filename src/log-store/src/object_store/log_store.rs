@@ -0,0 +1,651 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{hash_map, HashMap};
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_telemetry::{debug, info, warn};
+use common_wal::config::object_store::ObjectStoreWalConfig;
+use futures::StreamExt;
+use object_store::util::normalize_dir;
+use object_store::{ErrorKind, ObjectStore};
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::logstore::entry::{Entry, Id as EntryId, NaiveEntry};
+use store_api::logstore::provider::{ObjectStoreProvider, Provider};
+use store_api::logstore::{AppendBatchResponse, LogStore, SendableEntryStream};
+use store_api::storage::RegionId;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::oneshot;
+
+use crate::error::{
+    CorruptedSegmentSnafu, DeleteSegmentsSnafu, DiscontinuousLogIndexSnafu, Error,
+    GroupCommitSnafu, GroupCommitWorkerStoppedSnafu, InvalidProviderSnafu, ListSegmentsSnafu,
+    ReadSegmentSnafu, Result, WaitGroupCommitSnafu, WriteSegmentSnafu,
+};
+use crate::metrics;
+use crate::object_store::segment::{
+    decode_segment, encode_segment, parse_region_dir_name, parse_segment, region_dir, segment_path,
+    Segment,
+};
+
+/// Capacity of the channel of append requests.
+const REQUEST_CHANNEL_SIZE: usize = 128;
+/// Max number of segments written concurrently in a group commit.
+const MAX_CONCURRENT_WRITES: usize = 32;
+
+/// A log store that writes entries as segments to the object store.
+///
+/// Concurrent appends are committed in groups by a background worker, which writes one
+/// segment for each region in the group. As segments are only stored in the object store,
+/// a region can be replayed by any datanode sharing the object store, e.g. on failover.
+#[derive(Debug)]
+pub struct ObjectStoreLogStore {
+    /// The object store to write segments to.
+    object_store: ObjectStore,
+    /// The normalized directory of segments.
+    segment_dir: String,
+    /// Sender of append requests to the group commit worker.
+    sender: Sender<AppendRequest>,
+}
+
+impl ObjectStoreLogStore {
+    /// Creates an [ObjectStoreLogStore] and starts its group commit worker.
+    pub fn new(config: &ObjectStoreWalConfig, object_store: ObjectStore) -> Self {
+        let segment_dir = normalize_dir(&config.segment_dir);
+        let (sender, receiver) = mpsc::channel(REQUEST_CHANNEL_SIZE);
+        let worker = GroupCommitWorker {
+            object_store: object_store.clone(),
+            segment_dir: segment_dir.clone(),
+            max_group_bytes: config.max_group_bytes.as_bytes() as usize,
+            receiver,
+        };
+        let _handle = common_runtime::spawn_global(worker.run());
+
+        Self {
+            object_store,
+            segment_dir,
+            sender,
+        }
+    }
+
+    /// Lists segments of the region, ordered by their entry ids.
+    async fn list_segments(&self, region_id: RegionId) -> Result<Vec<Segment>> {
+        let dir = region_dir(&self.segment_dir, region_id);
+        let entries = match self.object_store.list(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).context(ListSegmentsSnafu { dir }),
+        };
+
+        let mut segments = entries
+            .iter()
+            .filter_map(|entry| parse_segment(entry.name(), entry.path()))
+            .collect::<Vec<_>>();
+        segments.sort_unstable_by_key(|segment| (segment.first_entry_id, segment.last_entry_id));
+        Ok(segments)
+    }
+}
+
+/// Reads and decodes entries of the segment.
+async fn read_segment(
+    object_store: &ObjectStore,
+    segment: &Segment,
+) -> Result<Vec<(EntryId, Vec<u8>)>> {
+    let buf = object_store
+        .read(&segment.path)
+        .await
+        .context(ReadSegmentSnafu {
+            path: &segment.path,
+        })?;
+    metrics::METRIC_OBJECT_STORE_READ_BYTES_TOTAL.inc_by(buf.len() as u64);
+    decode_segment(buf).map_err(|reason| {
+        CorruptedSegmentSnafu {
+            path: &segment.path,
+            reason,
+        }
+        .build()
+    })
+}
+
+fn object_store_provider(provider: &Provider) -> Result<&ObjectStoreProvider> {
+    provider
+        .as_object_store_provider()
+        .with_context(|| InvalidProviderSnafu {
+            expected: ObjectStoreProvider::type_name(),
+            actual: provider.type_name(),
+        })
+}
+
+#[async_trait::async_trait]
+impl LogStore for ObjectStoreLogStore {
+    type Error = Error;
+
+    async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Appends a batch of entries, the entries are committed together with entries
+    /// appended concurrently.
+    async fn append_batch(&self, entries: Vec<Entry>) -> Result<AppendBatchResponse> {
+        let size = entries
+            .iter()
+            .map(|entry| entry.estimated_size())
+            .sum::<usize>();
+        metrics::METRIC_OBJECT_STORE_APPEND_BATCH_BYTES_TOTAL.inc_by(size as u64);
+        let _timer = metrics::METRIC_OBJECT_STORE_APPEND_BATCH_ELAPSED.start_timer();
+
+        if entries.is_empty() {
+            return Ok(AppendBatchResponse::default());
+        }
+
+        // Records the last entry id for each region's entries.
+        let mut last_entry_ids: HashMap<RegionId, EntryId> = HashMap::new();
+        for entry in &entries {
+            object_store_provider(entry.provider())?;
+            let region_id = entry.region_id();
+            let entry_id = entry.entry_id();
+            match last_entry_ids.entry(region_id) {
+                hash_map::Entry::Occupied(mut o) => {
+                    let prev = *o.get();
+                    ensure!(
+                        entry_id == prev + 1,
+                        DiscontinuousLogIndexSnafu {
+                            region_id,
+                            last_index: prev,
+                            attempt_index: entry_id
+                        }
+                    );
+                    o.insert(entry_id);
+                }
+                hash_map::Entry::Vacant(v) => {
+                    v.insert(entry_id);
+                }
+            }
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let request = AppendRequest {
+            entries,
+            last_entry_ids,
+            size,
+            sender: tx,
+        };
+        if self.sender.send(request).await.is_err() {
+            warn!("The group commit worker of ObjectStoreLogStore is already exited");
+            return GroupCommitWorkerStoppedSnafu {}.fail();
+        }
+
+        rx.await.context(WaitGroupCommitSnafu)?
+    }
+
+    /// Creates a stream of entries of the region from the segments. The end of stream is
+    /// determined by the segments listed when the stream is created.
+    async fn read(
+        &self,
+        provider: &Provider,
+        entry_id: EntryId,
+    ) -> Result<SendableEntryStream<'static, Entry, Self::Error>> {
+        let region_id = object_store_provider(provider)?.region_id;
+        let _timer = metrics::METRIC_OBJECT_STORE_READ_ELAPSED.start_timer();
+
+        let segments = self
+            .list_segments(region_id)
+            .await?
+            .into_iter()
+            .filter(|segment| segment.last_entry_id >= entry_id)
+            .collect::<Vec<_>>();
+        info!(
+            "Read logstore, region: {}, start: {}, num segments: {}",
+            region_id,
+            entry_id,
+            segments.len()
+        );
+
+        let object_store = self.object_store.clone();
+        let provider = provider.clone();
+        let stream = stream!({
+            let mut next_entry_id = entry_id;
+            for segment in segments {
+                let decoded = match read_segment(&object_store, &segment).await {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                let mut entries = Vec::with_capacity(decoded.len());
+                for (id, data) in decoded {
+                    // Skips entries already read, e.g. rewritten by a retry.
+                    if id < next_entry_id {
+                        continue;
+                    }
+                    next_entry_id = id + 1;
+                    entries.push(Entry::Naive(NaiveEntry {
+                        provider: provider.clone(),
+                        region_id,
+                        entry_id: id,
+                        data,
+                    }));
+                }
+                if !entries.is_empty() {
+                    yield Ok(entries);
+                }
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    async fn create_namespace(&self, _provider: &Provider) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_namespace(&self, provider: &Provider) -> Result<()> {
+        let region_id = object_store_provider(provider)?.region_id;
+        let dir = region_dir(&self.segment_dir, region_id);
+        self.object_store
+            .remove_all(&dir)
+            .await
+            .context(DeleteSegmentsSnafu { dir })
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<Provider>> {
+        let entries = match self.object_store.list(&self.segment_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+            Err(e) => {
+                return Err(e).context(ListSegmentsSnafu {
+                    dir: &self.segment_dir,
+                })
+            }
+        };
+
+        Ok(entries
+            .iter()
+            .filter(|entry| entry.metadata().is_dir())
+            .filter_map(|entry| parse_region_dir_name(entry.name()))
+            .map(Provider::object_store_provider)
+            .collect())
+    }
+
+    /// Deletes segments whose entries are all obsolete.
+    async fn obsolete(
+        &self,
+        provider: &Provider,
+        region_id: RegionId,
+        entry_id: EntryId,
+    ) -> Result<()> {
+        object_store_provider(provider)?;
+        let paths = self
+            .list_segments(region_id)
+            .await?
+            .into_iter()
+            .filter(|segment| segment.last_entry_id <= entry_id)
+            .map(|segment| segment.path)
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let num_segments = paths.len();
+        self.object_store
+            .remove(paths)
+            .await
+            .context(DeleteSegmentsSnafu {
+                dir: region_dir(&self.segment_dir, region_id),
+            })?;
+        info!(
+            "Region {} obsoleted {} segments, compacted index: {}",
+            region_id, num_segments, entry_id
+        );
+        Ok(())
+    }
+
+    fn entry(
+        &self,
+        data: &mut Vec<u8>,
+        entry_id: EntryId,
+        region_id: RegionId,
+        provider: &Provider,
+    ) -> Result<Entry> {
+        debug_assert_eq!(
+            provider.as_object_store_provider().unwrap().region_id,
+            region_id
+        );
+        Ok(Entry::Naive(NaiveEntry {
+            provider: provider.clone(),
+            region_id,
+            entry_id,
+            data: std::mem::take(data),
+        }))
+    }
+}
+
+/// A request to append entries.
+struct AppendRequest {
+    entries: Vec<Entry>,
+    /// The last entry id of each region in `entries`.
+    last_entry_ids: HashMap<RegionId, EntryId>,
+    /// Estimated size of `entries`.
+    size: usize,
+    sender: oneshot::Sender<Result<AppendBatchResponse>>,
+}
+
+/// The worker commits append requests in groups.
+struct GroupCommitWorker {
+    object_store: ObjectStore,
+    segment_dir: String,
+    /// Max size of entries in a group.
+    max_group_bytes: usize,
+    receiver: Receiver<AppendRequest>,
+}
+
+impl GroupCommitWorker {
+    async fn run(mut self) {
+        while let Some(request) = self.receiver.recv().await {
+            // Groups the requests that arrive during the previous commit.
+            let mut group_size = request.size;
+            let mut group = vec![request];
+            while group_size < self.max_group_bytes {
+                match self.receiver.try_recv() {
+                    Ok(request) => {
+                        group_size += request.size;
+                        group.push(request);
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            self.commit(group).await;
+        }
+        debug!("The sender is dropped, GroupCommitWorker exited");
+    }
+
+    /// Writes the entries of each region in the group to a segment and notifies the requests.
+    async fn commit(&self, mut group: Vec<AppendRequest>) {
+        let mut region_entries: HashMap<RegionId, Vec<(EntryId, Vec<u8>)>> = HashMap::new();
+        for request in &mut group {
+            for entry in std::mem::take(&mut request.entries) {
+                region_entries
+                    .entry(entry.region_id())
+                    .or_default()
+                    .push((entry.entry_id(), entry.into_bytes()));
+            }
+        }
+
+        let results = futures::stream::iter(region_entries)
+            .map(|(region_id, entries)| async move {
+                let result = self.write_segment(region_id, entries).await;
+                (region_id, result.map_err(Arc::new))
+            })
+            .buffer_unordered(MAX_CONCURRENT_WRITES)
+            .collect::<HashMap<_, _>>()
+            .await;
+
+        for request in group {
+            let error = request
+                .last_entry_ids
+                .keys()
+                .find_map(|region_id| results.get(region_id)?.as_ref().err());
+            let result = match error {
+                Some(error) => Err(error.clone()).context(GroupCommitSnafu),
+                None => Ok(AppendBatchResponse {
+                    last_entry_ids: request.last_entry_ids,
+                }),
+            };
+            if request.sender.send(result).is_err() {
+                warn!("The receiver of the group commit result is dropped");
+            }
+        }
+    }
+
+    async fn write_segment(
+        &self,
+        region_id: RegionId,
+        entries: Vec<(EntryId, Vec<u8>)>,
+    ) -> Result<()> {
+        // Safety: entries of a region are never empty.
+        let first_entry_id = entries.first().unwrap().0;
+        let last_entry_id = entries.last().unwrap().0;
+        let path = segment_path(
+            &region_dir(&self.segment_dir, region_id),
+            first_entry_id,
+            last_entry_id,
+        );
+        self.object_store
+            .write(&path, encode_segment(entries))
+            .await
+            .context(WriteSegmentSnafu { path })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use common_test_util::temp_dir::{create_temp_dir, TempDir};
+    use futures::future::try_join_all;
+    use futures::TryStreamExt;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    fn new_object_store(dir: &TempDir) -> ObjectStore {
+        let builder = Fs::default().root(dir.path().to_str().unwrap());
+        ObjectStore::new(builder).unwrap().finish()
+    }
+
+    fn new_entries(region_id: RegionId, entry_ids: impl Iterator<Item = EntryId>) -> Vec<Entry> {
+        entry_ids
+            .map(|entry_id| {
+                Entry::Naive(NaiveEntry {
+                    provider: Provider::object_store_provider(region_id),
+                    region_id,
+                    entry_id,
+                    data: format!("{region_id}-{entry_id}").into_bytes(),
+                })
+            })
+            .collect()
+    }
+
+    async fn read_entries(
+        log_store: &ObjectStoreLogStore,
+        region_id: RegionId,
+        entry_id: EntryId,
+    ) -> Vec<Entry> {
+        log_store
+            .read(&Provider::object_store_provider(region_id), entry_id)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_append_and_read() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let log_store =
+            ObjectStoreLogStore::new(&ObjectStoreWalConfig::default(), new_object_store(&dir));
+
+        let region_1 = RegionId::new(1024, 1);
+        let region_2 = RegionId::new(1024, 2);
+        for i in 0..4 {
+            let mut entries = new_entries(region_1, i * 10..(i + 1) * 10);
+            entries.extend(new_entries(region_2, i * 5..(i + 1) * 5));
+            let response = log_store.append_batch(entries).await.unwrap();
+            assert_eq!(
+                response.last_entry_ids,
+                HashMap::from([(region_1, (i + 1) * 10 - 1), (region_2, (i + 1) * 5 - 1)])
+            );
+        }
+
+        assert_eq!(
+            read_entries(&log_store, region_1, 0).await,
+            new_entries(region_1, 0..40)
+        );
+        assert_eq!(
+            read_entries(&log_store, region_1, 15).await,
+            new_entries(region_1, 15..40)
+        );
+        assert_eq!(
+            read_entries(&log_store, region_2, 0).await,
+            new_entries(region_2, 0..20)
+        );
+        assert!(read_entries(&log_store, region_2, 20).await.is_empty());
+        assert!(read_entries(&log_store, RegionId::new(1024, 3), 0)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_from_another_log_store() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let config = ObjectStoreWalConfig::default();
+        let region_id = RegionId::new(1024, 1);
+
+        let log_store = ObjectStoreLogStore::new(&config, new_object_store(&dir));
+        log_store
+            .append_batch(new_entries(region_id, 0..10))
+            .await
+            .unwrap();
+
+        // Another log store sharing the object store, e.g. on the datanode the region
+        // fails over to.
+        let another = ObjectStoreLogStore::new(&config, new_object_store(&dir));
+        assert_eq!(
+            read_entries(&another, region_id, 5).await,
+            new_entries(region_id, 5..10)
+        );
+        another
+            .append_batch(new_entries(region_id, 10..12))
+            .await
+            .unwrap();
+        assert_eq!(
+            read_entries(&log_store, region_id, 0).await,
+            new_entries(region_id, 0..12)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_append() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let log_store = Arc::new(ObjectStoreLogStore::new(
+            &ObjectStoreWalConfig::default(),
+            new_object_store(&dir),
+        ));
+
+        let tasks = (0..16).map(|i| {
+            let log_store = log_store.clone();
+            async move {
+                let region_id = RegionId::new(1024, i);
+                for j in 0..4 {
+                    log_store
+                        .append_batch(new_entries(region_id, j * 8..(j + 1) * 8))
+                        .await?;
+                }
+                Ok::<_, Error>(())
+            }
+        });
+        try_join_all(tasks).await.unwrap();
+
+        for i in 0..16 {
+            let region_id = RegionId::new(1024, i);
+            assert_eq!(
+                read_entries(&log_store, region_id, 0).await,
+                new_entries(region_id, 0..32)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_obsolete() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let log_store =
+            ObjectStoreLogStore::new(&ObjectStoreWalConfig::default(), new_object_store(&dir));
+        let region_id = RegionId::new(1024, 1);
+        let provider = Provider::object_store_provider(region_id);
+        for i in 0..4 {
+            log_store
+                .append_batch(new_entries(region_id, i * 10..(i + 1) * 10))
+                .await
+                .unwrap();
+        }
+
+        // Only segments whose entries are all obsolete are deleted.
+        log_store.obsolete(&provider, region_id, 25).await.unwrap();
+        let segments = log_store.list_segments(region_id).await.unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].first_entry_id, 20);
+        assert_eq!(
+            read_entries(&log_store, region_id, 26).await,
+            new_entries(region_id, 26..40)
+        );
+
+        log_store.obsolete(&provider, region_id, 39).await.unwrap();
+        assert!(log_store.list_segments(region_id).await.unwrap().is_empty());
+        assert!(read_entries(&log_store, region_id, 40).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manage_namespace() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let log_store =
+            ObjectStoreLogStore::new(&ObjectStoreWalConfig::default(), new_object_store(&dir));
+        assert!(log_store.list_namespaces().await.unwrap().is_empty());
+
+        let region_id = RegionId::new(1024, 1);
+        let provider = Provider::object_store_provider(region_id);
+        log_store.create_namespace(&provider).await.unwrap();
+        log_store
+            .append_batch(new_entries(region_id, 0..10))
+            .await
+            .unwrap();
+        assert_eq!(
+            log_store.list_namespaces().await.unwrap(),
+            vec![provider.clone()]
+        );
+
+        log_store.delete_namespace(&provider).await.unwrap();
+        assert!(log_store.list_namespaces().await.unwrap().is_empty());
+        assert!(read_entries(&log_store, region_id, 0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_append_invalid_entries() {
+        let dir = create_temp_dir("object-store-logstore-test");
+        let log_store =
+            ObjectStoreLogStore::new(&ObjectStoreWalConfig::default(), new_object_store(&dir));
+        let region_id = RegionId::new(1024, 1);
+
+        let mut entries = new_entries(region_id, 0..2);
+        entries.extend(new_entries(region_id, 3..4));
+        let err = log_store.append_batch(entries).await.unwrap_err();
+        assert_matches!(err, Error::DiscontinuousLogIndex { .. });
+
+        let entries = vec![Entry::Naive(NaiveEntry {
+            provider: Provider::raft_engine_provider(region_id.as_u64()),
+            region_id,
+            entry_id: 0,
+            data: vec![],
+        })];
+        let err = log_store.append_batch(entries).await.unwrap_err();
+        assert_matches!(err, Error::InvalidProvider { .. });
+    }
+}
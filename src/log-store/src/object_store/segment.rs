@@ -0,0 +1,167 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Layout and encoding of wal segments.
+//!
+//! Each segment stores consecutive entries of a single region and is named by the
+//! ids of its first and last entries, i.e. `<segment_dir>/<region_id>/<first>-<last>.seg`.
+//!
+//! The content of a segment is a version byte followed by the entries, each encoded as
+//! `entry_id (u64) | length (u32) | data`, all integers in big-endian.
+
+use bytes::{Buf, BufMut};
+use object_store::util::join_dir;
+use store_api::logstore::entry::Id as EntryId;
+use store_api::storage::RegionId;
+
+/// Version of the segment encoding.
+const SEGMENT_VERSION: u8 = 1;
+/// Extension of segment files.
+const SEGMENT_EXTENSION: &str = ".seg";
+/// Size of the header of an entry.
+const ENTRY_HEADER_SIZE: usize = std::mem::size_of::<u64>() + std::mem::size_of::<u32>();
+
+/// A segment in the object store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Segment {
+    pub(crate) path: String,
+    /// Id of the first entry in the segment.
+    pub(crate) first_entry_id: EntryId,
+    /// Id of the last entry in the segment.
+    pub(crate) last_entry_id: EntryId,
+}
+
+/// Returns the directory of segments of the region.
+pub(crate) fn region_dir(segment_dir: &str, region_id: RegionId) -> String {
+    join_dir(segment_dir, &region_id.as_u64().to_string())
+}
+
+/// Parses the region id from the name of a region directory.
+pub(crate) fn parse_region_dir_name(name: &str) -> Option<RegionId> {
+    name.trim_end_matches('/')
+        .parse::<u64>()
+        .ok()
+        .map(RegionId::from_u64)
+}
+
+/// Returns the path of the segment containing entries in `[first_entry_id, last_entry_id]`.
+pub(crate) fn segment_path(
+    region_dir: &str,
+    first_entry_id: EntryId,
+    last_entry_id: EntryId,
+) -> String {
+    format!("{region_dir}{first_entry_id:020}-{last_entry_id:020}{SEGMENT_EXTENSION}")
+}
+
+/// Parses a [Segment] from the file name and path, returns `None` if it isn't a segment.
+pub(crate) fn parse_segment(name: &str, path: &str) -> Option<Segment> {
+    let (first, last) = name.strip_suffix(SEGMENT_EXTENSION)?.split_once('-')?;
+    Some(Segment {
+        path: path.to_string(),
+        first_entry_id: first.parse().ok()?,
+        last_entry_id: last.parse().ok()?,
+    })
+}
+
+/// Encodes entries into the content of a segment.
+pub(crate) fn encode_segment(entries: Vec<(EntryId, Vec<u8>)>) -> Vec<u8> {
+    let size = 1 + entries
+        .iter()
+        .map(|(_, data)| ENTRY_HEADER_SIZE + data.len())
+        .sum::<usize>();
+    let mut buf = Vec::with_capacity(size);
+    buf.put_u8(SEGMENT_VERSION);
+    for (entry_id, data) in entries {
+        buf.put_u64(entry_id);
+        buf.put_u32(data.len() as u32);
+        buf.put_slice(&data);
+    }
+    buf
+}
+
+/// Decodes entries from the content of a segment.
+pub(crate) fn decode_segment(mut buf: impl Buf) -> Result<Vec<(EntryId, Vec<u8>)>, String> {
+    if !buf.has_remaining() {
+        return Err("empty segment".to_string());
+    }
+    let version = buf.get_u8();
+    if version != SEGMENT_VERSION {
+        return Err(format!("unsupported version {version}"));
+    }
+
+    let mut entries = vec![];
+    while buf.has_remaining() {
+        if buf.remaining() < ENTRY_HEADER_SIZE {
+            return Err(format!(
+                "truncated entry header, remaining {} bytes",
+                buf.remaining()
+            ));
+        }
+        let entry_id = buf.get_u64();
+        let len = buf.get_u32() as usize;
+        if buf.remaining() < len {
+            return Err(format!(
+                "truncated entry {entry_id}, expect {len} bytes, remaining {} bytes",
+                buf.remaining()
+            ));
+        }
+        let mut data = vec![0; len];
+        buf.copy_to_slice(&mut data);
+        entries.push((entry_id, data));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_path() {
+        let region_id = RegionId::new(1024, 1);
+        let dir = region_dir("__wal/", region_id);
+        assert_eq!(dir, format!("__wal/{}/", region_id.as_u64()));
+        assert_eq!(
+            parse_region_dir_name(&format!("{}/", region_id.as_u64())),
+            Some(region_id)
+        );
+        assert_eq!(parse_region_dir_name("foo/"), None);
+
+        let path = segment_path(&dir, 1, 42);
+        let name = path.strip_prefix(&dir).unwrap();
+        assert_eq!(name, "00000000000000000001-00000000000000000042.seg");
+        assert_eq!(
+            parse_segment(name, &path),
+            Some(Segment {
+                path: path.clone(),
+                first_entry_id: 1,
+                last_entry_id: 42,
+            })
+        );
+        assert_eq!(parse_segment("1-42.tmp", &path), None);
+        assert_eq!(parse_segment("foo.seg", &path), None);
+    }
+
+    #[test]
+    fn test_encode_decode_segment() {
+        let entries = vec![(1, b"hello".to_vec()), (2, vec![]), (3, b"world".to_vec())];
+        let buf = encode_segment(entries.clone());
+        assert_eq!(decode_segment(buf.as_slice()).unwrap(), entries);
+
+        assert!(decode_segment(&buf[..buf.len() - 1]).is_err());
+        assert!(decode_segment(&buf[..5]).is_err());
+        assert!(decode_segment(&[][..]).is_err());
+        assert!(decode_segment(&[SEGMENT_VERSION + 1][..]).is_err());
+    }
+}
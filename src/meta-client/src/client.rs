@@ -27,7 +27,8 @@ use cluster::Client as ClusterClient;
 use common_error::ext::BoxedError;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
 use common_meta::cluster::{
-    ClusterInfo, MetasrvStatus, NodeInfo, NodeInfoKey, NodeStatus, Role as ClusterRole,
+    ClusterInfo, MetasrvStatus, NodeInfo, NodeInfoKey, NodeStatus, RegionMove, RegionMoveKey,
+    Role as ClusterRole,
 };
 use common_meta::ddl::{ExecutorContext, ProcedureExecutor};
 use common_meta::error::{self as meta_error, Result as MetaResult};
@@ -317,6 +318,18 @@ impl ClusterInfo for MetaClient {
 
         Ok(nodes)
     }
+
+    async fn list_region_moves(&self) -> Result<Vec<RegionMove>> {
+        let cluster_client = self.cluster_client()?;
+        let req =
+            RangeRequest::new().with_prefix(RegionMoveKey::key_prefix_with_cluster_id(self.id.0));
+        let res = cluster_client.range(req).await?;
+
+        res.kvs
+            .into_iter()
+            .map(|kv| RegionMove::try_from(kv.value).context(ConvertMetaResponseSnafu))
+            .collect()
+    }
 }
 
 impl MetaClient {
@@ -40,12 +40,12 @@ use snafu::OptionExt;
 use snafu::ResultExt;
 use tokio::net::TcpListener;
 use tokio::sync::mpsc::{self, Receiver, Sender};
-#[cfg(feature = "pg_kvbackend")]
-use tokio_postgres::NoTls;
 use tonic::transport::server::{Router, TcpIncoming};
 
 use crate::election::etcd::EtcdElection;
 #[cfg(feature = "pg_kvbackend")]
+use crate::election::postgres::{connect_postgres, PgElection};
+#[cfg(feature = "pg_kvbackend")]
 use crate::error::InvalidArgumentsSnafu;
 use crate::error::{InitExportMetricsTaskSnafu, TomlFormatSnafu};
 use crate::lock::etcd::EtcdLock;
@@ -232,10 +232,21 @@ pub async fn metasrv_builder(
         }
         #[cfg(feature = "pg_kvbackend")]
         (None, BackendImpl::PostgresStore) => {
-            let pg_client = create_postgres_client(opts).await?;
+            let postgres_url = postgres_url(opts)?;
+            let pg_client = connect_postgres(postgres_url).await?;
             let kv_backend = PgStore::with_pg_client(pg_client).await.unwrap();
-            // TODO: implement locking and leader election for pg backend.
-            (kv_backend, None, None)
+            // The election uses a dedicated connection so that keeping the leader lease
+            // alive won't be blocked by metadata operations.
+            let election_client = connect_postgres(postgres_url).await?;
+            let election = PgElection::with_pg_client(
+                &opts.server_addr,
+                election_client,
+                postgres_url.to_string(),
+                opts.store_key_prefix.clone(),
+            )
+            .await?;
+            // TODO: implement locking for pg backend.
+            (kv_backend, Some(election), None)
         }
     };
 
@@ -270,12 +281,11 @@ async fn create_etcd_client(opts: &MetasrvOptions) -> Result<Client> {
 }
 
 #[cfg(feature = "pg_kvbackend")]
-async fn create_postgres_client(opts: &MetasrvOptions) -> Result<tokio_postgres::Client> {
-    let postgres_url = opts.store_addrs.first().context(InvalidArgumentsSnafu {
-        err_msg: "empty store addrs",
-    })?;
-    let (client, _) = tokio_postgres::connect(postgres_url, NoTls)
-        .await
-        .context(error::ConnectPostgresSnafu)?;
-    Ok(client)
+fn postgres_url(opts: &MetasrvOptions) -> Result<&str> {
+    opts.store_addrs
+        .first()
+        .map(String::as_str)
+        .context(InvalidArgumentsSnafu {
+            err_msg: "empty store addrs",
+        })
 }
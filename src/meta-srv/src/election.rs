@@ -13,11 +13,12 @@
 // limitations under the License.
 
 pub mod etcd;
+#[cfg(feature = "pg_kvbackend")]
+pub mod postgres;
 
-use std::fmt;
+use std::fmt::{self, Debug};
 use std::sync::Arc;
 
-use etcd_client::LeaderKey;
 use tokio::sync::broadcast::Receiver;
 
 use crate::error::Result;
@@ -26,10 +27,43 @@ use crate::metasrv::MetasrvNodeInfo;
 pub const ELECTION_KEY: &str = "__metasrv_election";
 pub const CANDIDATES_ROOT: &str = "__metasrv_election_candidates/";
 
+/// The key that represents the leadership of an election.
+pub trait LeaderKey: Send + Sync + Debug {
+    /// Returns the election name.
+    fn name(&self) -> &[u8];
+
+    /// Returns the leader key.
+    fn key(&self) -> &[u8];
+
+    /// Returns the revision when the leadership is acquired.
+    fn revision(&self) -> i64;
+
+    /// Returns the lease id of the leadership.
+    fn lease_id(&self) -> i64;
+}
+
+impl LeaderKey for etcd_client::LeaderKey {
+    fn name(&self) -> &[u8] {
+        etcd_client::LeaderKey::name(self)
+    }
+
+    fn key(&self) -> &[u8] {
+        etcd_client::LeaderKey::key(self)
+    }
+
+    fn revision(&self) -> i64 {
+        self.rev()
+    }
+
+    fn lease_id(&self) -> i64 {
+        self.lease()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LeaderChangeMessage {
-    Elected(Arc<LeaderKey>),
-    StepDown(Arc<LeaderKey>),
+    Elected(Arc<dyn LeaderKey>),
+    StepDown(Arc<dyn LeaderKey>),
 }
 
 impl fmt::Display for LeaderChangeMessage {
@@ -47,8 +81,8 @@ impl fmt::Display for LeaderChangeMessage {
         write!(f, "LeaderKey {{ ")?;
         write!(f, "name: {}", String::from_utf8_lossy(leader_key.name()))?;
         write!(f, ", key: {}", String::from_utf8_lossy(leader_key.key()))?;
-        write!(f, ", rev: {}", leader_key.rev())?;
-        write!(f, ", lease: {}", leader_key.lease())?;
+        write!(f, ", rev: {}", leader_key.revision())?;
+        write!(f, ", lease: {}", leader_key.lease_id())?;
         write!(f, " }})")
     }
 }
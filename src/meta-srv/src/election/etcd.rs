@@ -74,16 +74,16 @@ impl EtcdElection {
                     Ok(msg) => match msg {
                         LeaderChangeMessage::Elected(key) => {
                             info!(
-                                "[{leader_ident}] is elected as leader: {:?}, lease: {}",
-                                key.name_str(),
-                                key.lease()
+                                "[{leader_ident}] is elected as leader: {}, lease: {}",
+                                String::from_utf8_lossy(key.name()),
+                                key.lease_id()
                             );
                         }
                         LeaderChangeMessage::StepDown(key) => {
                             warn!(
-                                "[{leader_ident}] is stepping down: {:?}, lease: {}",
-                                key.name_str(),
-                                key.lease()
+                                "[{leader_ident}] is stepping down: {}, lease: {}",
+                                String::from_utf8_lossy(key.name()),
+                                key.lease_id()
                             );
                         }
                     },
@@ -0,0 +1,559 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_meta::distributed_time_constants::{META_KEEP_ALIVE_INTERVAL_SECS, META_LEASE_SECS};
+use common_meta::util::get_prefix_end_key;
+use common_telemetry::{error, info, warn};
+use snafu::{ensure, OptionExt, ResultExt};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::{timeout, MissedTickBehavior};
+use tokio_postgres::{Client, NoTls};
+
+use crate::election::{Election, LeaderChangeMessage, LeaderKey, CANDIDATES_ROOT, ELECTION_KEY};
+use crate::error;
+use crate::error::Result;
+use crate::metasrv::{ElectionRef, LeaderValue, MetasrvNodeInfo};
+
+/// Separates the value and the expire time (in millis) of a lease.
+const LEASE_SEP: &str = "||__metadata_lease_sep||";
+
+const CANDIDATE_LEASE_SECS: u64 = 600;
+
+// The statements below work on the `greptime_metakv` table created by the postgres kv backend.
+const NOW_MILLIS: &str = "SELECT (EXTRACT(EPOCH FROM now()) * 1000)::BIGINT";
+
+const POINT_GET: &str = "SELECT v FROM greptime_metakv WHERE k = $1";
+
+const RANGE_SCAN: &str = "SELECT v FROM greptime_metakv WHERE k >= $1 AND k < $2";
+
+const PUT_IF_NOT_EXISTS: &str =
+    "INSERT INTO greptime_metakv VALUES ($1, $2) ON CONFLICT (k) DO NOTHING";
+
+const UPSERT: &str =
+    "INSERT INTO greptime_metakv VALUES ($1, $2) ON CONFLICT (k) DO UPDATE SET v = $2";
+
+const CAS: &str = "UPDATE greptime_metakv SET v = $3 WHERE k = $1 AND v = $2";
+
+const CAD: &str = "DELETE FROM greptime_metakv WHERE k = $1 AND v = $2";
+
+/// The leader key of the postgres election.
+///
+/// Postgres has neither revisions nor leases, so both of them are always 0.
+#[derive(Debug, Clone)]
+pub struct PgLeaderKey {
+    name: Vec<u8>,
+    key: Vec<u8>,
+}
+
+impl LeaderKey for PgLeaderKey {
+    fn name(&self) -> &[u8] {
+        &self.name
+    }
+
+    fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn revision(&self) -> i64 {
+        0
+    }
+
+    fn lease_id(&self) -> i64 {
+        0
+    }
+}
+
+/// Leader election on top of postgres.
+///
+/// The leader holds a lease row whose value contains the leader value and the expire time.
+/// The leader refreshes the lease every [META_KEEP_ALIVE_INTERVAL_SECS] and the other
+/// candidates take over the lease once it expires. All the timestamps come from the
+/// postgres server so the clocks of metasrv nodes don't matter.
+///
+/// The election reconnects to postgres once the connection is closed.
+pub struct PgElection {
+    leader_value: String,
+    postgres_url: String,
+    client: Mutex<Arc<Client>>,
+    is_leader: AtomicBool,
+    infancy: AtomicBool,
+    leader_watcher: broadcast::Sender<LeaderChangeMessage>,
+    store_key_prefix: String,
+}
+
+impl PgElection {
+    pub async fn with_pg_client<E>(
+        leader_value: E,
+        client: Client,
+        postgres_url: String,
+        store_key_prefix: String,
+    ) -> Result<ElectionRef>
+    where
+        E: AsRef<str>,
+    {
+        let leader_value: String = leader_value.as_ref().into();
+
+        let leader_ident = leader_value.clone();
+        let (tx, mut rx) = broadcast::channel(100);
+        let _handle = common_runtime::spawn_global(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(msg) => match msg {
+                        LeaderChangeMessage::Elected(key) => {
+                            info!(
+                                "[{leader_ident}] is elected as leader: {}",
+                                String::from_utf8_lossy(key.name())
+                            );
+                        }
+                        LeaderChangeMessage::StepDown(key) => {
+                            warn!(
+                                "[{leader_ident}] is stepping down: {}",
+                                String::from_utf8_lossy(key.name())
+                            );
+                        }
+                    },
+                    Err(RecvError::Lagged(_)) => {
+                        warn!("Log printing is too slow or leader changed too fast!");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(Arc::new(Self {
+            leader_value,
+            postgres_url,
+            client: Mutex::new(Arc::new(client)),
+            is_leader: AtomicBool::new(false),
+            infancy: AtomicBool::new(false),
+            leader_watcher: tx,
+            store_key_prefix,
+        }))
+    }
+
+    fn election_key(&self) -> String {
+        format!("{}{}", self.store_key_prefix, ELECTION_KEY)
+    }
+
+    fn candidate_root(&self) -> String {
+        format!("{}{}", self.store_key_prefix, CANDIDATES_ROOT)
+    }
+
+    fn candidate_key(&self) -> String {
+        format!("{}{}", self.candidate_root(), self.leader_value)
+    }
+
+    fn leader_key(&self) -> Arc<PgLeaderKey> {
+        Arc::new(PgLeaderKey {
+            name: self.election_key().into_bytes(),
+            key: self.leader_value.clone().into_bytes(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Election for PgElection {
+    type Leader = LeaderValue;
+
+    fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Relaxed)
+    }
+
+    fn in_infancy(&self) -> bool {
+        self.infancy
+            .compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    async fn register_candidate(&self, node_info: &MetasrvNodeInfo) -> Result<()> {
+        const KEEP_ALIVE_INTERVAL_SECS: u64 = CANDIDATE_LEASE_SECS / 2;
+
+        // The register info: key is the candidate key, value is its node info(addr, version, git_commit).
+        let key = self.candidate_key();
+        let node_info =
+            serde_json::to_string(node_info).with_context(|_| error::SerializeToJsonSnafu {
+                input: format!("{node_info:?}"),
+            })?;
+
+        let mut keep_alive_interval =
+            tokio::time::interval(Duration::from_secs(KEEP_ALIVE_INTERVAL_SECS));
+
+        loop {
+            let _ = keep_alive_interval.tick().await;
+            let now = self.now_millis().await?;
+            let value = encode_lease(&node_info, now + CANDIDATE_LEASE_SECS as i64 * 1000);
+            self.client()
+                .await?
+                .execute(UPSERT, &[&key, &value])
+                .await
+                .context(error::PostgresExecutionSnafu)?;
+        }
+    }
+
+    async fn all_candidates(&self) -> Result<Vec<MetasrvNodeInfo>> {
+        let root = self.candidate_root();
+        let end = String::from_utf8_lossy(&get_prefix_end_key(root.as_bytes())).to_string();
+        let rows = self
+            .client()
+            .await?
+            .query(RANGE_SCAN, &[&root, &end])
+            .await
+            .context(error::PostgresExecutionSnafu)?;
+        let now = self.now_millis().await?;
+
+        let mut nodes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let value: String = row.try_get(0).context(error::PostgresExecutionSnafu)?;
+            let (node_info, expire_time) = decode_lease(&value)?;
+            if expire_time < now {
+                continue;
+            }
+            let node = serde_json::from_str::<MetasrvNodeInfo>(node_info)
+                .with_context(|_| error::DeserializeFromJsonSnafu { input: node_info })?;
+            nodes.push(node);
+        }
+
+        Ok(nodes)
+    }
+
+    async fn campaign(&self) -> Result<()> {
+        let keep_lease_duration = Duration::from_secs(META_KEEP_ALIVE_INTERVAL_SECS);
+        let mut keep_alive_interval = tokio::time::interval(keep_lease_duration);
+        keep_alive_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let _ = keep_alive_interval.tick().await;
+
+            if !self.is_leader() {
+                if let Err(e) = self.try_acquire_lease().await {
+                    warn!(e; "Failed to acquire the leader lease");
+                }
+                continue;
+            }
+
+            // The keep alive operation MUST be done in `META_KEEP_ALIVE_INTERVAL_SECS`.
+            match timeout(keep_lease_duration, self.keep_alive()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    error!(err; "Failed to keep alive");
+                    break;
+                }
+                Err(_) => {
+                    error!("Refresh lease timeout");
+                    break;
+                }
+            }
+        }
+
+        self.step_down();
+
+        Ok(())
+    }
+
+    async fn leader(&self) -> Result<LeaderValue> {
+        if self.is_leader.load(Ordering::Relaxed) {
+            return Ok(self.leader_value.as_bytes().into());
+        }
+
+        let value = self
+            .get(&self.election_key())
+            .await?
+            .context(error::NoLeaderSnafu)?;
+        let (leader_value, expire_time) = decode_lease(&value)?;
+        ensure!(
+            expire_time >= self.now_millis().await?,
+            error::NoLeaderSnafu
+        );
+        Ok(leader_value.into())
+    }
+
+    async fn resign(&self) -> Result<()> {
+        if !self.is_leader() {
+            return Ok(());
+        }
+
+        let key = self.election_key();
+        if let Some(value) = self.get(&key).await? {
+            let (leader_value, _) = decode_lease(&value)?;
+            if leader_value == self.leader_value {
+                let _ = self
+                    .client()
+                    .await?
+                    .execute(CAD, &[&key, &value])
+                    .await
+                    .context(error::PostgresExecutionSnafu)?;
+            }
+        }
+        self.step_down();
+
+        Ok(())
+    }
+
+    fn subscribe_leader_change(&self) -> Receiver<LeaderChangeMessage> {
+        self.leader_watcher.subscribe()
+    }
+}
+
+impl PgElection {
+    /// Returns the postgres client, reconnects if the connection is closed.
+    async fn client(&self) -> Result<Arc<Client>> {
+        let mut client = self.client.lock().await;
+        if client.is_closed() {
+            warn!("The postgres connection of the election is closed, reconnecting");
+            *client = Arc::new(connect_postgres(&self.postgres_url).await?);
+        }
+        Ok(client.clone())
+    }
+
+    async fn now_millis(&self) -> Result<i64> {
+        let row = self
+            .client()
+            .await?
+            .query_one(NOW_MILLIS, &[])
+            .await
+            .context(error::PostgresExecutionSnafu)?;
+        row.try_get(0).context(error::PostgresExecutionSnafu)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let row = self
+            .client()
+            .await?
+            .query_opt(POINT_GET, &[&key])
+            .await
+            .context(error::PostgresExecutionSnafu)?;
+        row.map(|row| row.try_get(0))
+            .transpose()
+            .context(error::PostgresExecutionSnafu)
+    }
+
+    async fn compare_and_swap(&self, key: &str, expect: &str, value: &str) -> Result<bool> {
+        let updated = self
+            .client()
+            .await?
+            .execute(CAS, &[&key, &expect, &value])
+            .await
+            .context(error::PostgresExecutionSnafu)?;
+        Ok(updated == 1)
+    }
+
+    /// Tries to acquire the leader lease if it is absent or expired.
+    async fn try_acquire_lease(&self) -> Result<()> {
+        let key = self.election_key();
+        let now = self.now_millis().await?;
+        let value = encode_lease(&self.leader_value, now + META_LEASE_SECS as i64 * 1000);
+
+        let inserted = self
+            .client()
+            .await?
+            .execute(PUT_IF_NOT_EXISTS, &[&key, &value])
+            .await
+            .context(error::PostgresExecutionSnafu)?;
+        let acquired = if inserted == 1 {
+            true
+        } else if let Some(prev) = self.get(&key).await? {
+            let (_, expire_time) = decode_lease(&prev)?;
+            expire_time < now && self.compare_and_swap(&key, &prev, &value).await?
+        } else {
+            false
+        };
+
+        if acquired
+            && self
+                .is_leader
+                .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.infancy.store(true, Ordering::Relaxed);
+
+            if let Err(e) = self
+                .leader_watcher
+                .send(LeaderChangeMessage::Elected(self.leader_key()))
+            {
+                error!(e; "Failed to send leader change message");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Refreshes the leader lease, fails if the lease has been expired or taken by others.
+    async fn keep_alive(&self) -> Result<()> {
+        let key = self.election_key();
+        let prev = self.get(&key).await?.context(error::UnexpectedSnafu {
+            violated: "The leader lease is missing",
+        })?;
+        let (leader_value, expire_time) = decode_lease(&prev)?;
+        ensure!(
+            leader_value == self.leader_value,
+            error::UnexpectedSnafu {
+                violated: format!("The leader lease is held by {leader_value}"),
+            }
+        );
+
+        let now = self.now_millis().await?;
+        ensure!(
+            expire_time >= now,
+            error::UnexpectedSnafu {
+                violated: "The leader lease has expired",
+            }
+        );
+
+        let value = encode_lease(&self.leader_value, now + META_LEASE_SECS as i64 * 1000);
+        ensure!(
+            self.compare_and_swap(&key, &prev, &value).await?,
+            error::UnexpectedSnafu {
+                violated: "Failed to refresh the lease",
+            }
+        );
+
+        Ok(())
+    }
+
+    fn step_down(&self) {
+        if self
+            .is_leader
+            .compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            if let Err(e) = self
+                .leader_watcher
+                .send(LeaderChangeMessage::StepDown(self.leader_key()))
+            {
+                error!(e; "Failed to send leader change message");
+            }
+        }
+    }
+}
+
+/// Connects to postgres and drives the connection in background.
+pub(crate) async fn connect_postgres(postgres_url: &str) -> Result<Client> {
+    let (client, connection) = tokio_postgres::connect(postgres_url, NoTls)
+        .await
+        .context(error::ConnectPostgresSnafu)?;
+    let _handle = common_runtime::spawn_global(async move {
+        if let Err(e) = connection.await {
+            error!(e; "Postgres connection error");
+        }
+    });
+    Ok(client)
+}
+
+fn encode_lease(value: &str, expire_time: i64) -> String {
+    format!("{value}{LEASE_SEP}{expire_time}")
+}
+
+fn decode_lease(lease: &str) -> Result<(&str, i64)> {
+    let (value, expire_time) =
+        lease
+            .rsplit_once(LEASE_SEP)
+            .with_context(|| error::UnexpectedSnafu {
+                violated: format!("Invalid lease: {lease}"),
+            })?;
+    let expire_time = expire_time
+        .parse()
+        .ok()
+        .with_context(|| error::UnexpectedSnafu {
+            violated: format!("Invalid expire time of lease: {lease}"),
+        })?;
+    Ok((value, expire_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_lease() {
+        let lease = encode_lease("127.0.0.1:3002", 1000);
+        assert_eq!(("127.0.0.1:3002", 1000), decode_lease(&lease).unwrap());
+
+        let lease = encode_lease("", -1);
+        assert_eq!(("", -1), decode_lease(&lease).unwrap());
+
+        assert!(decode_lease("127.0.0.1:3002").is_err());
+        assert!(decode_lease(&format!("127.0.0.1:3002{LEASE_SEP}abc")).is_err());
+    }
+
+    async fn build_pg_election(leader_value: &str, prefix: &str) -> Option<ElectionRef> {
+        let endpoints = std::env::var("GT_POSTGRES_ENDPOINTS").unwrap_or_default();
+        if endpoints.is_empty() {
+            return None;
+        }
+
+        let client = connect_postgres(&endpoints).await.unwrap();
+        let _ = client
+            .execute(
+                "CREATE TABLE IF NOT EXISTS greptime_metakv(k varchar PRIMARY KEY, v varchar)",
+                &[],
+            )
+            .await;
+        Some(
+            PgElection::with_pg_client(leader_value, client, endpoints, prefix.to_string())
+                .await
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_campaign_and_resign() {
+        let prefix = "test_pg_election/";
+        let Some(first) = build_pg_election("first", prefix).await else {
+            return;
+        };
+        let second = build_pg_election("second", prefix).await.unwrap();
+
+        let mut rx = first.subscribe_leader_change();
+        let campaign = {
+            let first = first.clone();
+            tokio::spawn(async move { first.campaign().await })
+        };
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            LeaderChangeMessage::Elected(_)
+        ));
+        assert!(first.is_leader());
+        assert!(first.in_infancy());
+        assert!(!first.in_infancy());
+        assert_eq!("first", second.leader().await.unwrap().0);
+
+        // Stops campaigning before resigning, otherwise it may win the election again.
+        campaign.abort();
+        first.resign().await.unwrap();
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            LeaderChangeMessage::StepDown(_)
+        ));
+        assert!(!first.is_leader());
+
+        let mut rx = second.subscribe_leader_change();
+        let campaign = {
+            let second = second.clone();
+            tokio::spawn(async move { second.campaign().await })
+        };
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            LeaderChangeMessage::Elected(_)
+        ));
+        assert_eq!("second", first.leader().await.unwrap().0);
+        campaign.abort();
+        second.resign().await.unwrap();
+    }
+}
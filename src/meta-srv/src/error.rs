@@ -62,6 +62,36 @@ pub enum Error {
         reason: String,
     },
 
+    #[snafu(display("Another repartition procedure is running for table: {}", table_id))]
+    RepartitionRunning {
+        #[snafu(implicit)]
+        location: Location,
+        table_id: TableId,
+    },
+
+    #[snafu(display("The region repartition procedure aborted, reason: {}", reason))]
+    RepartitionAbort {
+        #[snafu(implicit)]
+        location: Location,
+        reason: String,
+    },
+
+    #[snafu(display("Timeout to move data of regions: {:?} after {:?}", regions, timeout))]
+    MoveRegionDataTimeout {
+        #[snafu(implicit)]
+        location: Location,
+        regions: Vec<RegionId>,
+        timeout: Duration,
+    },
+
+    #[snafu(display("Failed to repartition regions of table: {}", table_id))]
+    Repartition {
+        #[snafu(implicit)]
+        location: Location,
+        table_id: TableId,
+        source: partition::error::Error,
+    },
+
     #[snafu(display(
         "Another procedure is opening the region: {} on peer: {}",
         region_id,
@@ -869,6 +899,8 @@ pub enum Error {
     #[cfg(feature = "pg_kvbackend")]
     #[snafu(display("Failed to execute via postgres"))]
     PostgresExecution {
+        #[snafu(source)]
+        error: tokio_postgres::Error,
         #[snafu(implicit)]
         location: Location,
     },
@@ -977,7 +1009,11 @@ impl ErrorExt for Error {
             | Error::RegionOpeningRace { .. }
             | Error::RegionRouteNotFound { .. }
             | Error::MigrationAbort { .. }
-            | Error::MigrationRunning { .. } => StatusCode::Unexpected,
+            | Error::MigrationRunning { .. }
+            | Error::RepartitionAbort { .. }
+            | Error::RepartitionRunning { .. }
+            | Error::MoveRegionDataTimeout { .. } => StatusCode::Unexpected,
+            Error::Repartition { source, .. } => source.status_code(),
             Error::TableNotFound { .. } => StatusCode::TableNotFound,
             Error::SaveClusterInfo { source, .. }
             | Error::InvalidClusterInfoFormat { source, .. } => source.status_code(),
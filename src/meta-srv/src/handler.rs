@@ -52,6 +52,7 @@ pub mod node_stat;
 pub mod on_leader_start_handler;
 pub mod publish_heartbeat_handler;
 pub mod region_lease_handler;
+pub mod region_split_handler;
 pub mod response_header_handler;
 
 #[async_trait::async_trait]
@@ -0,0 +1,151 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::meta::{HeartbeatRequest, Role};
+use async_trait::async_trait;
+use common_telemetry::{debug, info, warn};
+use store_api::region_engine::RegionRole;
+
+use crate::error::Result;
+use crate::handler::node_stat::RegionStat;
+use crate::handler::{HandleControl, HeartbeatAccumulator, HeartbeatHandler};
+use crate::metasrv::{Context, RegionSplitOptions};
+use crate::procedure::region_repartition::manager::RegionRepartitionManagerRef;
+
+/// Submits region split procedures for hot regions reported by datanode heartbeats.
+pub struct RegionSplitHandler {
+    options: RegionSplitOptions,
+    region_repartition_manager: RegionRepartitionManagerRef,
+}
+
+impl RegionSplitHandler {
+    pub(crate) fn new(
+        options: RegionSplitOptions,
+        region_repartition_manager: RegionRepartitionManagerRef,
+    ) -> Self {
+        Self {
+            options,
+            region_repartition_manager,
+        }
+    }
+}
+
+/// Returns true if the region is a leader region that exceeds any of the thresholds.
+fn is_hot(options: &RegionSplitOptions, stat: &RegionStat) -> bool {
+    if stat.role != RegionRole::Leader {
+        return false;
+    }
+
+    let max_region_wcus = options.max_region_wcus;
+    stat.approximate_bytes.max(0) as u64 > options.max_region_size.as_bytes()
+        || (max_region_wcus > 0 && stat.wcus.max(0) as u64 > max_region_wcus)
+}
+
+#[async_trait]
+impl HeartbeatHandler for RegionSplitHandler {
+    fn is_acceptable(&self, role: Role) -> bool {
+        role == Role::Datanode
+    }
+
+    async fn handle(
+        &self,
+        _: &HeartbeatRequest,
+        _ctx: &mut Context,
+        acc: &mut HeartbeatAccumulator,
+    ) -> Result<HandleControl> {
+        let Some(stat) = acc.stat.as_ref() else {
+            return Ok(HandleControl::Continue);
+        };
+
+        let tracker = self.region_repartition_manager.tracker();
+        for region_stat in &stat.region_stats {
+            let region_id = region_stat.id;
+            // Only one repartition procedure is allowed for a table at the same time.
+            if !is_hot(&self.options, region_stat) || tracker.contains(region_id.table_id()) {
+                continue;
+            }
+
+            match self
+                .region_repartition_manager
+                .submit_auto_split_procedure(stat.cluster_id, region_id)
+                .await
+            {
+                Ok(Some(procedure_id)) => info!(
+                    "Submitted region split procedure {procedure_id} for hot region {region_id}, approximate bytes: {}, wcus: {}",
+                    region_stat.approximate_bytes, region_stat.wcus
+                ),
+                Ok(None) => debug!(
+                    "Skipped splitting hot region {region_id}, its partition range is unbounded or too narrow to split"
+                ),
+                Err(e) => warn!(e; "Failed to split hot region {region_id}"),
+            }
+        }
+
+        Ok(HandleControl::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common_base::readable_size::ReadableSize;
+    use store_api::storage::RegionId;
+
+    use super::*;
+
+    #[test]
+    fn test_is_hot() {
+        let options = RegionSplitOptions {
+            enable: true,
+            max_region_size: ReadableSize::mb(1),
+            max_region_wcus: 100,
+            move_data_timeout: Duration::from_secs(600),
+        };
+        let stat = RegionStat {
+            id: RegionId::new(1024, 1),
+            rcus: 0,
+            wcus: 0,
+            approximate_bytes: 0,
+            engine: "mito".to_string(),
+            role: RegionRole::Leader,
+        };
+        assert!(!is_hot(&options, &stat));
+
+        let large = RegionStat {
+            approximate_bytes: ReadableSize::mb(2).as_bytes() as i64,
+            ..stat.clone()
+        };
+        assert!(is_hot(&options, &large));
+        let busy = RegionStat {
+            wcus: 101,
+            ..stat.clone()
+        };
+        assert!(is_hot(&options, &busy));
+
+        // Followers are never split.
+        let follower = RegionStat {
+            role: RegionRole::Follower,
+            ..large
+        };
+        assert!(!is_hot(&options, &follower));
+
+        // No limit of wcus.
+        let options = RegionSplitOptions {
+            max_region_wcus: 0,
+            ..options
+        };
+        assert!(!is_hot(&options, &busy));
+    }
+}
@@ -56,6 +56,7 @@ use crate::lease::lookup_datanode_peer;
 use crate::lock::DistLockRef;
 use crate::procedure::region_migration::manager::RegionMigrationManagerRef;
 use crate::pubsub::{PublisherRef, SubscriptionManagerRef};
+use crate::region::balancer::RegionBalancerTickerRef;
 use crate::region::supervisor::RegionSupervisorTickerRef;
 use crate::selector::{Selector, SelectorType};
 use crate::service::mailbox::MailboxRef;
@@ -66,6 +67,9 @@ pub const TABLE_ID_SEQ: &str = "table_id";
 pub const FLOW_ID_SEQ: &str = "flow_id";
 pub const METASRV_HOME: &str = "/tmp/metasrv";
 
+/// The interval to retry registering the candidate after a failure.
+const REGISTER_CANDIDATE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 // The datastores that implements metadata kvbackend.
 #[derive(Clone, Debug, PartialEq, Serialize, Default, Deserialize, ValueEnum)]
 pub enum BackendImpl {
@@ -102,6 +106,10 @@ pub struct MetasrvOptions {
     pub procedure: ProcedureConfig,
     /// The failure detector options.
     pub failure_detector: PhiAccrualFailureDetectorOptions,
+    /// The region split options.
+    pub region_split: RegionSplitOptions,
+    /// The region balancer options.
+    pub region_balancer: RegionBalancerOptions,
     /// The datanode options.
     pub datanode: DatanodeOptions,
     /// Whether to enable telemetry.
@@ -154,6 +162,8 @@ impl Default for MetasrvOptions {
                 max_metadata_value_size: Some(ReadableSize::kb(1500)),
             },
             failure_detector: PhiAccrualFailureDetectorOptions::default(),
+            region_split: RegionSplitOptions::default(),
+            region_balancer: RegionBalancerOptions::default(),
             datanode: DatanodeOptions::default(),
             enable_telemetry: true,
             data_home: METASRV_HOME.to_string(),
@@ -177,6 +187,77 @@ pub struct MetasrvInfo {
     pub server_addr: String,
 }
 
+/// Options for splitting hot regions automatically.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegionSplitOptions {
+    /// Whether to split regions automatically.
+    pub enable: bool,
+    /// Splits the region once its approximate size exceeds this value.
+    pub max_region_size: ReadableSize,
+    /// Splits the region once its write capacity units during a heartbeat period exceed
+    /// this value. `0` means no limit.
+    pub max_region_wcus: u64,
+    /// The timeout of moving data of a region into the new regions. The split is rolled
+    /// back once it's exceeded.
+    #[serde(with = "humantime_serde")]
+    pub move_data_timeout: Duration,
+}
+
+impl Default for RegionSplitOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            max_region_size: ReadableSize::gb(8),
+            max_region_wcus: 0,
+            move_data_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Options for moving regions between datanodes automatically to balance their load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegionBalancerOptions {
+    /// Whether to balance regions automatically.
+    pub enable: bool,
+    /// Only plans region moves without executing them. Planned moves are visible in
+    /// `information_schema.region_move_plans`.
+    pub dry_run: bool,
+    /// The interval between two balancing rounds.
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// A datanode is overloaded if its load exceeds the average load by this ratio.
+    pub imbalance_threshold: f64,
+    /// The maximum number of region moves planned in a balancing round.
+    pub max_moves_per_round: usize,
+    /// The maximum number of running region migrations. No moves are submitted once
+    /// the limit is reached.
+    pub max_running_migrations: usize,
+    /// The weight of the region count in the load of a datanode.
+    pub region_count_weight: f64,
+    /// The weight of the write rate in the load of a datanode.
+    pub write_rate_weight: f64,
+    /// The weight of the disk usage in the load of a datanode.
+    pub disk_usage_weight: f64,
+}
+
+impl Default for RegionBalancerOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            dry_run: false,
+            interval: Duration::from_secs(300),
+            imbalance_threshold: 0.2,
+            max_moves_per_round: 2,
+            max_running_migrations: 4,
+            region_count_weight: 1.0,
+            write_rate_weight: 1.0,
+            disk_usage_weight: 1.0,
+        }
+    }
+}
+
 // Options for datanode.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct DatanodeOptions {
@@ -295,6 +376,7 @@ pub struct MetaStateHandler {
     greptimedb_telemetry_task: Arc<GreptimeDBTelemetryTask>,
     leader_cached_kv_backend: Arc<LeaderCachedKvBackend>,
     region_supervisor_ticker: Option<RegionSupervisorTickerRef>,
+    region_balancer_ticker: Option<RegionBalancerTickerRef>,
     state: StateRef,
 }
 
@@ -312,6 +394,10 @@ impl MetaStateHandler {
             ticker.start();
         }
 
+        if let Some(ticker) = self.region_balancer_ticker.as_ref() {
+            ticker.start();
+        }
+
         if let Err(e) = self.procedure_manager.start().await {
             error!(e; "Failed to start procedure manager");
         }
@@ -336,6 +422,10 @@ impl MetaStateHandler {
             ticker.stop();
         }
 
+        if let Some(ticker) = self.region_balancer_ticker.as_ref() {
+            ticker.stop();
+        }
+
         // Suspends reporting.
         self.greptimedb_telemetry_task.should_report(false);
 
@@ -376,6 +466,7 @@ pub struct Metasrv {
     greptimedb_telemetry_task: Arc<GreptimeDBTelemetryTask>,
     region_migration_manager: RegionMigrationManagerRef,
     region_supervisor_ticker: Option<RegionSupervisorTickerRef>,
+    region_balancer_ticker: Option<RegionBalancerTickerRef>,
 
     plugins: Plugins,
 }
@@ -408,6 +499,7 @@ impl Metasrv {
                 .start()
                 .context(StartTelemetryTaskSnafu)?;
             let region_supervisor_ticker = self.region_supervisor_ticker.clone();
+            let region_balancer_ticker = self.region_balancer_ticker.clone();
             let state_handler = MetaStateHandler {
                 greptimedb_telemetry_task,
                 subscribe_manager,
@@ -416,6 +508,7 @@ impl Metasrv {
                 state: self.state.clone(),
                 leader_cached_kv_backend: leader_cached_kv_backend.clone(),
                 region_supervisor_ticker,
+                region_balancer_ticker,
             };
             let _handle = common_runtime::spawn_global(async move {
                 loop {
@@ -458,6 +551,7 @@ impl Metasrv {
                         let res = election.register_candidate(&node_info).await;
                         if let Err(e) = res {
                             warn!(e; "Metasrv register candidate error");
+                            tokio::time::sleep(REGISTER_CANDIDATE_RETRY_INTERVAL).await;
                         }
                     }
                 });
@@ -491,6 +585,9 @@ impl Metasrv {
                 .start()
                 .await
                 .context(StartProcedureManagerSnafu)?;
+            if let Some(ticker) = self.region_balancer_ticker.as_ref() {
+                ticker.start();
+            }
         }
 
         info!("Metasrv started");
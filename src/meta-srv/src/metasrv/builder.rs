@@ -60,6 +60,7 @@ use crate::handler::mailbox_handler::MailboxHandler;
 use crate::handler::on_leader_start_handler::OnLeaderStartHandler;
 use crate::handler::publish_heartbeat_handler::PublishHeartbeatHandler;
 use crate::handler::region_lease_handler::RegionLeaseHandler;
+use crate::handler::region_split_handler::RegionSplitHandler;
 use crate::handler::response_header_handler::ResponseHeaderHandler;
 use crate::handler::{HeartbeatHandlerGroup, HeartbeatMailbox, Pushers};
 use crate::lease::MetaPeerLookupService;
@@ -70,7 +71,10 @@ use crate::metasrv::{
 };
 use crate::procedure::region_migration::manager::RegionMigrationManager;
 use crate::procedure::region_migration::DefaultContextFactory;
+use crate::procedure::region_repartition;
+use crate::procedure::region_repartition::manager::RegionRepartitionManager;
 use crate::pubsub::PublisherRef;
+use crate::region::balancer::{RegionBalancer, RegionBalancerTicker};
 use crate::region::supervisor::{
     HeartbeatAcceptor, RegionFailureDetectorControl, RegionSupervisor, RegionSupervisorTicker,
     DEFAULT_TICK_INTERVAL,
@@ -323,6 +327,37 @@ impl MetasrvBuilder {
         ));
         region_migration_manager.try_start()?;
 
+        let region_repartition_manager = Arc::new(RegionRepartitionManager::new(
+            procedure_manager.clone(),
+            region_repartition::DefaultContextFactory::new(
+                table_metadata_manager.clone(),
+                memory_region_keeper.clone(),
+                node_manager.clone(),
+                mailbox.clone(),
+                options.server_addr.clone(),
+            ),
+            options.region_split.move_data_timeout,
+        ));
+        region_repartition_manager.try_start()?;
+        let region_balancer_ticker = options.region_balancer.enable.then(|| {
+            Arc::new(RegionBalancerTicker::new(
+                options.region_balancer.interval,
+                RegionBalancer::new(
+                    options.region_balancer.clone(),
+                    meta_peer_client.clone(),
+                    in_memory.clone(),
+                    region_migration_manager.clone(),
+                    distributed_time_constants::DATANODE_LEASE_SECS,
+                ),
+            ))
+        });
+        let region_split_handler = options.region_split.enable.then(|| {
+            RegionSplitHandler::new(
+                options.region_split.clone(),
+                region_repartition_manager.clone(),
+            )
+        });
+
         let region_failover_handler = if options.enable_region_failover && is_remote_wal {
             let region_supervisor = RegionSupervisor::new(
                 rx,
@@ -396,6 +431,9 @@ impl MetasrvBuilder {
                 if let Some(publish_heartbeat_handler) = publish_heartbeat_handler {
                     group.add_handler(publish_heartbeat_handler).await;
                 }
+                if let Some(region_split_handler) = region_split_handler {
+                    group.add_handler(region_split_handler).await;
+                }
                 group.add_handler(CollectStatsHandler::default()).await;
                 group
             }
@@ -434,6 +472,7 @@ impl MetasrvBuilder {
             memory_region_keeper,
             region_migration_manager,
             region_supervisor_ticker,
+            region_balancer_ticker,
         })
     }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod region_migration;
+pub mod region_repartition;
 #[cfg(test)]
 mod tests;
 pub mod utils;
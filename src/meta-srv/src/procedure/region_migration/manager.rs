@@ -74,6 +74,11 @@ impl RegionMigrationProcedureTracker {
             .unwrap()
             .contains_key(&region_id)
     }
+
+    /// Returns the number of running procedures.
+    pub(crate) fn num_running_procedures(&self) -> usize {
+        self.running_procedures.read().unwrap().len()
+    }
 }
 
 /// The guard of running [RegionMigrationProcedureTask].
@@ -0,0 +1,535 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splits or merges regions of a table online.
+//!
+//! Source regions and target regions must be on the same datanode. The procedure asks
+//! the datanode to copy data of source regions into target regions while source regions
+//! stay writable, then downgrades source regions and asks the datanode to move the rest of
+//! writes to them. At last, it replaces routes of source regions with routes of target
+//! regions and drops source regions.
+//!
+//! Writes to source regions are only rejected from the downgrade until the routes are
+//! replaced.
+
+pub(crate) mod drop_source_regions;
+pub(crate) mod manager;
+pub(crate) mod move_region_data;
+pub(crate) mod repartition_abort;
+pub(crate) mod repartition_end;
+pub(crate) mod repartition_start;
+pub(crate) mod update_metadata;
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use api::v1::meta::MailboxMessage;
+use common_error::ext::BoxedError;
+use common_meta::instruction::{CacheIdent, Instruction};
+use common_meta::key::datanode_table::{DatanodeTableKey, DatanodeTableValue};
+use common_meta::key::table_info::TableInfoValue;
+use common_meta::key::table_route::TableRouteValue;
+use common_meta::key::{DeserializedValueWithBytes, TableMetadataManagerRef};
+use common_meta::lock_key::{CatalogLock, SchemaLock, TableLock};
+use common_meta::node_manager::NodeManagerRef;
+use common_meta::peer::Peer;
+use common_meta::region_keeper::{MemoryRegionKeeperRef, OperatingRegionGuard};
+use common_meta::ClusterId;
+use common_procedure::error::{
+    Error as ProcedureError, FromJsonSnafu, Result as ProcedureResult, ToJsonSnafu,
+};
+use common_procedure::{Context as ProcedureContext, LockKey, Procedure, Status, StringKey};
+use manager::{RegionRepartitionProcedureGuard, RegionRepartitionProcedureTracker};
+use partition::expr::PartitionExpr;
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::RegionId;
+use table::metadata::TableId;
+
+use self::repartition_start::RegionRepartitionStart;
+use crate::error::{self, Result};
+use crate::service::mailbox::{BroadcastChannel, MailboxRef};
+
+/// It's shared in each step and available even after recovering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PersistentContext {
+    /// The table catalog.
+    catalog: String,
+    /// The table schema.
+    schema: String,
+    /// The Id of the cluster.
+    cluster_id: ClusterId,
+    /// The table to repartition.
+    table_id: TableId,
+    /// The [Peer] that source regions and target regions are located on.
+    peer: Peer,
+    /// Regions to be replaced.
+    from_regions: Vec<RegionId>,
+    /// New regions, their partition expressions are in `partition_exprs`.
+    to_regions: Vec<RegionId>,
+    /// The partition columns of the table.
+    partition_columns: Vec<String>,
+    /// The partition expressions of `to_regions`.
+    partition_exprs: Vec<PartitionExpr>,
+    /// The timeout of moving data of regions.
+    #[serde(with = "humantime_serde", default = "default_move_data_timeout")]
+    move_data_timeout: Duration,
+}
+
+fn default_move_data_timeout() -> Duration {
+    Duration::from_secs(600)
+}
+
+impl PersistentContext {
+    pub fn lock_key(&self) -> Vec<StringKey> {
+        vec![
+            CatalogLock::Read(&self.catalog).into(),
+            SchemaLock::read(&self.catalog, &self.schema).into(),
+            TableLock::Write(self.table_id).into(),
+        ]
+    }
+}
+
+/// It's shared in each step and available in executing (including retrying).
+///
+/// It will be dropped if the procedure runner crashes.
+#[derive(Debug, Default)]
+pub struct VolatileContext {
+    /// Guards of target regions, they're registered in the
+    /// [MoveRegionData](crate::procedure::region_repartition::move_region_data::MoveRegionData) step
+    /// and consumed after routes of target regions were written into [TableRouteValue].
+    opening_region_guards: Vec<OperatingRegionGuard>,
+    /// `table_route` is stored via previous steps for future use.
+    table_route: Option<DeserializedValueWithBytes<TableRouteValue>>,
+    /// `table_info` is stored via previous steps for future use.
+    table_info: Option<DeserializedValueWithBytes<TableInfoValue>>,
+    /// `datanode_table` is stored via previous steps for future use.
+    datanode_table: Option<DatanodeTableValue>,
+}
+
+/// Used to generate new [Context].
+#[derive(Clone)]
+pub struct DefaultContextFactory {
+    table_metadata_manager: TableMetadataManagerRef,
+    opening_region_keeper: MemoryRegionKeeperRef,
+    node_manager: NodeManagerRef,
+    mailbox: MailboxRef,
+    server_addr: String,
+}
+
+impl DefaultContextFactory {
+    /// Returns an [`DefaultContextFactory`].
+    pub fn new(
+        table_metadata_manager: TableMetadataManagerRef,
+        opening_region_keeper: MemoryRegionKeeperRef,
+        node_manager: NodeManagerRef,
+        mailbox: MailboxRef,
+        server_addr: String,
+    ) -> Self {
+        Self {
+            table_metadata_manager,
+            opening_region_keeper,
+            node_manager,
+            mailbox,
+            server_addr,
+        }
+    }
+
+    fn new_context(self, persistent_ctx: PersistentContext) -> Context {
+        Context {
+            persistent_ctx,
+            volatile_ctx: VolatileContext::default(),
+            table_metadata_manager: self.table_metadata_manager,
+            opening_region_keeper: self.opening_region_keeper,
+            node_manager: self.node_manager,
+            mailbox: self.mailbox,
+            server_addr: self.server_addr,
+        }
+    }
+}
+
+/// The context of procedure execution.
+pub struct Context {
+    persistent_ctx: PersistentContext,
+    volatile_ctx: VolatileContext,
+    table_metadata_manager: TableMetadataManagerRef,
+    opening_region_keeper: MemoryRegionKeeperRef,
+    node_manager: NodeManagerRef,
+    mailbox: MailboxRef,
+    server_addr: String,
+}
+
+impl Context {
+    /// Returns address of meta server.
+    pub fn server_addr(&self) -> &str {
+        &self.server_addr
+    }
+
+    /// Returns the table id.
+    pub fn table_id(&self) -> TableId {
+        self.persistent_ctx.table_id
+    }
+
+    /// Returns the `table_route` of [VolatileContext] if any.
+    /// Otherwise, returns the value retrieved from remote.
+    ///
+    /// Retry:
+    /// - Failed to retrieve the metadata of table.
+    pub async fn get_table_route_value(
+        &mut self,
+    ) -> Result<&DeserializedValueWithBytes<TableRouteValue>> {
+        let table_route = match self.volatile_ctx.table_route.take() {
+            Some(table_route) => table_route,
+            None => {
+                let table_id = self.persistent_ctx.table_id;
+                self.table_metadata_manager
+                    .table_route_manager()
+                    .table_route_storage()
+                    .get_raw(table_id)
+                    .await
+                    .context(error::TableMetadataManagerSnafu)
+                    .map_err(BoxedError::new)
+                    .context(error::RetryLaterWithSourceSnafu {
+                        reason: format!("Failed to get TableRoute: {table_id}"),
+                    })?
+                    .context(error::TableRouteNotFoundSnafu { table_id })?
+            }
+        };
+
+        Ok(self.volatile_ctx.table_route.insert(table_route))
+    }
+
+    /// Removes the `table_route` of [VolatileContext], returns true if any.
+    pub fn remove_table_route_value(&mut self) -> bool {
+        let value = self.volatile_ctx.table_route.take();
+        value.is_some()
+    }
+
+    /// Returns the `table_info` of [VolatileContext] if any.
+    /// Otherwise, returns the value retrieved from remote.
+    ///
+    /// Retry:
+    /// - Failed to retrieve the metadata of table.
+    pub async fn get_table_info_value(
+        &mut self,
+    ) -> Result<&DeserializedValueWithBytes<TableInfoValue>> {
+        let table_info = match self.volatile_ctx.table_info.take() {
+            Some(table_info) => table_info,
+            None => {
+                let table_id = self.persistent_ctx.table_id;
+                self.table_metadata_manager
+                    .table_info_manager()
+                    .get(table_id)
+                    .await
+                    .context(error::TableMetadataManagerSnafu)
+                    .map_err(BoxedError::new)
+                    .context(error::RetryLaterWithSourceSnafu {
+                        reason: format!("Failed to get TableInfo: {table_id}"),
+                    })?
+                    .context(error::TableInfoNotFoundSnafu { table_id })?
+            }
+        };
+
+        Ok(self.volatile_ctx.table_info.insert(table_info))
+    }
+
+    /// Removes the `table_info` of [VolatileContext], returns true if any.
+    pub fn remove_table_info_value(&mut self) -> bool {
+        let value = self.volatile_ctx.table_info.take();
+        value.is_some()
+    }
+
+    /// Returns the `datanode_table` of [VolatileContext] if any.
+    /// Otherwise, returns the value retrieved from remote.
+    ///
+    /// Retry:
+    /// - Failed to retrieve the metadata of datanode.
+    pub async fn get_datanode_table_value(&mut self) -> Result<&DatanodeTableValue> {
+        let datanode_table = match self.volatile_ctx.datanode_table.take() {
+            Some(datanode_table) => datanode_table,
+            None => {
+                let table_id = self.persistent_ctx.table_id;
+                let datanode_id = self.persistent_ctx.peer.id;
+
+                self.table_metadata_manager
+                    .datanode_table_manager()
+                    .get(&DatanodeTableKey {
+                        datanode_id,
+                        table_id,
+                    })
+                    .await
+                    .context(error::TableMetadataManagerSnafu)
+                    .map_err(BoxedError::new)
+                    .context(error::RetryLaterWithSourceSnafu {
+                        reason: format!("Failed to get DatanodeTable: ({datanode_id},{table_id})"),
+                    })?
+                    .context(error::DatanodeTableNotFoundSnafu {
+                        table_id,
+                        datanode_id,
+                    })?
+            }
+        };
+
+        Ok(self.volatile_ctx.datanode_table.insert(datanode_table))
+    }
+
+    /// Broadcasts the invalidate table cache message.
+    pub async fn invalidate_table_cache(&self) -> Result<()> {
+        let table_id = self.table_id();
+        let instruction = Instruction::InvalidateCaches(vec![CacheIdent::TableId(table_id)]);
+
+        let msg = &MailboxMessage::json_message(
+            "Invalidate Table Cache",
+            &format!("Metasrv@{}", self.server_addr()),
+            "Frontend broadcast",
+            common_time::util::current_time_millis(),
+            &instruction,
+        )
+        .with_context(|_| error::SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+
+        self.mailbox
+            .broadcast(&BroadcastChannel::Frontend, msg)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(tag = "region_repartition_state")]
+pub(crate) trait State: Sync + Send + Debug {
+    /// Yields the next [State] and [Status].
+    async fn next(&mut self, ctx: &mut Context) -> Result<(Box<dyn State>, Status)>;
+
+    /// Returns as [Any](std::any::Any).
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Persistent data of [RegionRepartitionProcedure].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegionRepartitionDataOwned {
+    persistent_ctx: PersistentContext,
+    state: Box<dyn State>,
+}
+
+/// Persistent data of [RegionRepartitionProcedure].
+#[derive(Debug, Serialize)]
+pub struct RegionRepartitionData<'a> {
+    persistent_ctx: &'a PersistentContext,
+    state: &'a dyn State,
+}
+
+pub(crate) struct RegionRepartitionProcedure {
+    state: Box<dyn State>,
+    context: Context,
+    _guard: Option<RegionRepartitionProcedureGuard>,
+}
+
+impl RegionRepartitionProcedure {
+    const TYPE_NAME: &'static str = "metasrv-procedure::RegionRepartition";
+
+    pub fn new(
+        persistent_context: PersistentContext,
+        context_factory: DefaultContextFactory,
+        guard: Option<RegionRepartitionProcedureGuard>,
+    ) -> Self {
+        Self {
+            state: Box::new(RegionRepartitionStart),
+            context: context_factory.new_context(persistent_context),
+            _guard: guard,
+        }
+    }
+
+    fn from_json(
+        json: &str,
+        context_factory: DefaultContextFactory,
+        tracker: RegionRepartitionProcedureTracker,
+    ) -> ProcedureResult<Self> {
+        let RegionRepartitionDataOwned {
+            persistent_ctx,
+            state,
+        } = serde_json::from_str(json).context(FromJsonSnafu)?;
+
+        let guard = tracker.insert_running_procedure(persistent_ctx.table_id);
+        let context = context_factory.new_context(persistent_ctx);
+
+        Ok(Self {
+            state,
+            context,
+            _guard: guard,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Procedure for RegionRepartitionProcedure {
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+
+    async fn execute(&mut self, _ctx: &ProcedureContext) -> ProcedureResult<Status> {
+        let state = &mut self.state;
+
+        let (next, status) = state.next(&mut self.context).await.map_err(|e| {
+            if e.is_retryable() {
+                ProcedureError::retry_later(e)
+            } else {
+                ProcedureError::external(e)
+            }
+        })?;
+
+        *state = next;
+        Ok(status)
+    }
+
+    fn dump(&self) -> ProcedureResult<String> {
+        let data = RegionRepartitionData {
+            state: self.state.as_ref(),
+            persistent_ctx: &self.context.persistent_ctx,
+        };
+        serde_json::to_string(&data).context(ToJsonSnafu)
+    }
+
+    fn lock_key(&self) -> LockKey {
+        LockKey::new(self.context.persistent_ctx.lock_key())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::sync::Arc;
+
+    use common_meta::key::TableMetadataManager;
+    use common_meta::kv_backend::memory::MemoryKvBackend;
+    use common_meta::region_keeper::MemoryRegionKeeper;
+    use common_meta::sequence::SequenceBuilder;
+    use common_meta::test_util::{MockDatanodeHandler, MockDatanodeManager};
+    use partition::expr::{Operand, RestrictedOp};
+
+    use super::*;
+    use crate::procedure::region_migration::test_util::MailboxContext;
+
+    /// `TestingEnv` provides components during the tests.
+    pub struct TestingEnv {
+        pub(crate) table_metadata_manager: TableMetadataManagerRef,
+        pub(crate) mailbox_ctx: MailboxContext,
+        pub(crate) opening_region_keeper: MemoryRegionKeeperRef,
+        pub(crate) node_manager: NodeManagerRef,
+    }
+
+    impl TestingEnv {
+        pub fn new<T: MockDatanodeHandler + 'static>(handler: T) -> Self {
+            let kv_backend = Arc::new(MemoryKvBackend::new());
+            let mailbox_sequence =
+                SequenceBuilder::new("test_heartbeat_mailbox", kv_backend.clone()).build();
+
+            Self {
+                table_metadata_manager: Arc::new(TableMetadataManager::new(kv_backend)),
+                mailbox_ctx: MailboxContext::new(mailbox_sequence),
+                opening_region_keeper: Arc::new(MemoryRegionKeeper::default()),
+                node_manager: Arc::new(MockDatanodeManager::new(handler)),
+            }
+        }
+
+        pub fn context_factory(&self) -> DefaultContextFactory {
+            DefaultContextFactory::new(
+                self.table_metadata_manager.clone(),
+                self.opening_region_keeper.clone(),
+                self.node_manager.clone(),
+                self.mailbox_ctx.mailbox().clone(),
+                "localhost".to_string(),
+            )
+        }
+    }
+
+    /// Returns `column op value`.
+    pub fn new_expr(column: &str, op: RestrictedOp, value: i64) -> PartitionExpr {
+        PartitionExpr::new(
+            Operand::Column(column.to_string()),
+            op,
+            Operand::Value(datatypes::value::Value::Int64(value)),
+        )
+    }
+
+    /// Returns the context that splits region 1 of table 1024 into region 2 and 3 on datanode 1.
+    pub fn new_persistent_context() -> PersistentContext {
+        PersistentContext {
+            catalog: "greptime".into(),
+            schema: "public".into(),
+            cluster_id: 0,
+            table_id: 1024,
+            peer: Peer::empty(1),
+            from_regions: vec![RegionId::new(1024, 1)],
+            to_regions: vec![RegionId::new(1024, 2), RegionId::new(1024, 3)],
+            partition_columns: vec!["a".to_string()],
+            partition_exprs: vec![
+                new_expr("a", RestrictedOp::Lt, 100),
+                new_expr("a", RestrictedOp::GtEq, 100),
+            ],
+            move_data_timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::ddl::test_util::datanode_handler::NaiveDatanodeHandler;
+
+    use super::test_util::*;
+    use super::*;
+
+    #[test]
+    fn test_lock_key() {
+        let persistent_context = new_persistent_context();
+        let expected_keys = persistent_context.lock_key();
+
+        let env = TestingEnv::new(NaiveDatanodeHandler);
+        let procedure =
+            RegionRepartitionProcedure::new(persistent_context, env.context_factory(), None);
+
+        let key = procedure.lock_key();
+        let keys = key.keys_to_lock().cloned().collect::<Vec<_>>();
+        for key in expected_keys {
+            assert!(keys.contains(&key));
+        }
+    }
+
+    #[test]
+    fn test_data_serialization() {
+        let persistent_context = new_persistent_context();
+        let env = TestingEnv::new(NaiveDatanodeHandler);
+        let procedure = RegionRepartitionProcedure::new(
+            persistent_context.clone(),
+            env.context_factory(),
+            None,
+        );
+
+        let serialized = procedure.dump().unwrap();
+        let tracker = RegionRepartitionProcedureTracker::default();
+        let procedure = RegionRepartitionProcedure::from_json(
+            &serialized,
+            env.context_factory(),
+            tracker.clone(),
+        )
+        .unwrap();
+        assert!(tracker.contains(1024));
+        assert_eq!(persistent_context, procedure.context.persistent_ctx);
+        assert!(procedure
+            .state
+            .as_any()
+            .downcast_ref::<RegionRepartitionStart>()
+            .is_some());
+    }
+}
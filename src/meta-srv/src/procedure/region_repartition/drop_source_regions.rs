@@ -0,0 +1,131 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use api::v1::region::{
+    region_request, DropRequest as PbDropRegionRequest, RegionRequest, RegionRequestHeader,
+};
+use common_error::ext::{BoxedError, ErrorExt};
+use common_error::status_code::StatusCode;
+use common_procedure::Status;
+use common_telemetry::info;
+use common_telemetry::tracing_context::TracingContext;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use super::repartition_end::RegionRepartitionEnd;
+use crate::error::{self, Result};
+use crate::procedure::region_repartition::{Context, State};
+
+/// Drops source regions after their routes were replaced by target regions.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DropSourceRegions;
+
+#[async_trait::async_trait]
+#[typetag::serde]
+impl State for DropSourceRegions {
+    async fn next(&mut self, ctx: &mut Context) -> Result<(Box<dyn State>, Status)> {
+        self.drop_source_regions(ctx).await?;
+
+        Ok((Box::new(RegionRepartitionEnd), Status::done()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl DropSourceRegions {
+    /// Drops source regions on the datanode.
+    ///
+    /// Retry:
+    /// - Failed to drop any source region.
+    async fn drop_source_regions(&self, ctx: &Context) -> Result<()> {
+        let peer = &ctx.persistent_ctx.peer;
+        let requester = ctx.node_manager.datanode(peer).await;
+
+        for region_id in &ctx.persistent_ctx.from_regions {
+            info!("Dropping source region {region_id} on datanode {peer:?}");
+            let request = RegionRequest {
+                header: Some(RegionRequestHeader {
+                    tracing_context: TracingContext::from_current_span().to_w3c(),
+                    ..Default::default()
+                }),
+                body: Some(region_request::Body::Drop(PbDropRegionRequest {
+                    region_id: region_id.as_u64(),
+                })),
+            };
+            if let Err(err) = requester.handle(request).await {
+                if err.status_code() != StatusCode::RegionNotFound {
+                    return Err(BoxedError::new(err)).context(error::RetryLaterWithSourceSnafu {
+                        reason: format!(
+                            "Failed to drop source region {region_id} on datanode {peer:?}"
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use common_meta::ddl::test_util::datanode_handler::{
+        DatanodeWatcher, UnexpectedErrorDatanodeHandler,
+    };
+    use common_meta::peer::Peer;
+    use store_api::storage::RegionId;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::procedure::region_repartition::test_util::*;
+
+    #[tokio::test]
+    async fn test_drop_source_regions() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let env = TestingEnv::new(DatanodeWatcher(tx));
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+
+        let (next, status) = DropSourceRegions.next(&mut ctx).await.unwrap();
+        assert!(next
+            .as_any()
+            .downcast_ref::<RegionRepartitionEnd>()
+            .is_some());
+        assert!(status.is_done());
+
+        let (peer, request) = rx.try_recv().unwrap();
+        assert_eq!(Peer::empty(1), peer);
+        let Some(region_request::Body::Drop(request)) = request.body else {
+            panic!("Expect a drop region request, found {:?}", request.body);
+        };
+        assert_eq!(RegionId::new(1024, 1).as_u64(), request.region_id);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drop_source_regions_retry() {
+        let env = TestingEnv::new(UnexpectedErrorDatanodeHandler);
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+
+        let err = DropSourceRegions.next(&mut ctx).await.unwrap_err();
+        assert_matches!(err, Error::RetryLaterWithSource { .. });
+        assert!(err.is_retryable());
+    }
+}
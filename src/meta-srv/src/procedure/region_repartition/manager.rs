@@ -0,0 +1,529 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use common_meta::key::table_info::TableInfoValue;
+use common_meta::key::table_route::TableRouteValue;
+use common_meta::peer::Peer;
+use common_meta::rpc::router::RegionRoute;
+use common_meta::ClusterId;
+use common_procedure::{watcher, ProcedureId, ProcedureManagerRef, ProcedureWithId};
+use common_telemetry::{error, info};
+use datatypes::value::Value;
+use partition::expr::PartitionExpr;
+use partition::multi_dim::MultiDimPartitionRule;
+use partition::partition::{PartitionBound, PartitionDef};
+use partition::repartition::{find_split_point, split_partition_expr};
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::{RegionId, RegionNumber};
+use table::metadata::TableId;
+use table::table_name::TableName;
+
+use crate::error::{self, Result};
+use crate::procedure::region_repartition::{
+    DefaultContextFactory, PersistentContext, RegionRepartitionProcedure,
+};
+
+pub type RegionRepartitionManagerRef = Arc<RegionRepartitionManager>;
+
+/// Manager of region repartition procedure.
+pub struct RegionRepartitionManager {
+    procedure_manager: ProcedureManagerRef,
+    context_factory: DefaultContextFactory,
+    tracker: RegionRepartitionProcedureTracker,
+    /// The timeout of moving data of regions.
+    move_data_timeout: Duration,
+}
+
+/// Tracks tables that have a running repartition procedure.
+#[derive(Default, Clone)]
+pub(crate) struct RegionRepartitionProcedureTracker {
+    running_procedures: Arc<RwLock<HashSet<TableId>>>,
+}
+
+impl RegionRepartitionProcedureTracker {
+    /// Returns the [RegionRepartitionProcedureGuard] if the table isn't being repartitioned.
+    pub(crate) fn insert_running_procedure(
+        &self,
+        table_id: TableId,
+    ) -> Option<RegionRepartitionProcedureGuard> {
+        let mut procedures = self.running_procedures.write().unwrap();
+        if !procedures.insert(table_id) {
+            return None;
+        }
+
+        Some(RegionRepartitionProcedureGuard {
+            table_id,
+            running_procedures: self.running_procedures.clone(),
+        })
+    }
+
+    /// Returns true if the table is being repartitioned.
+    pub(crate) fn contains(&self, table_id: TableId) -> bool {
+        self.running_procedures.read().unwrap().contains(&table_id)
+    }
+}
+
+/// The guard of running repartition procedure.
+pub(crate) struct RegionRepartitionProcedureGuard {
+    table_id: TableId,
+    running_procedures: Arc<RwLock<HashSet<TableId>>>,
+}
+
+impl Drop for RegionRepartitionProcedureGuard {
+    fn drop(&mut self) {
+        self.running_procedures
+            .write()
+            .unwrap()
+            .remove(&self.table_id);
+    }
+}
+
+/// Replaces `from_regions` with `to_regions` on the `peer`.
+#[derive(Debug, Clone, PartialEq)]
+struct RepartitionPlan {
+    peer: Peer,
+    from_regions: Vec<RegionId>,
+    to_regions: Vec<RegionId>,
+    partition_columns: Vec<String>,
+    partition_exprs: Vec<PartitionExpr>,
+}
+
+impl Display for RepartitionPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "from_regions: {:?}, to_regions: {:?}, peer: {}",
+            self.from_regions, self.to_regions, self.peer
+        )
+    }
+}
+
+impl RegionRepartitionManager {
+    /// Returns new [`RegionRepartitionManager`]
+    pub(crate) fn new(
+        procedure_manager: ProcedureManagerRef,
+        context_factory: DefaultContextFactory,
+        move_data_timeout: Duration,
+    ) -> Self {
+        Self {
+            procedure_manager,
+            context_factory,
+            tracker: RegionRepartitionProcedureTracker::default(),
+            move_data_timeout,
+        }
+    }
+
+    /// Returns the [`RegionRepartitionProcedureTracker`].
+    pub(crate) fn tracker(&self) -> &RegionRepartitionProcedureTracker {
+        &self.tracker
+    }
+
+    /// Registers the loader of [RegionRepartitionProcedure] to the `ProcedureManager`.
+    pub(crate) fn try_start(&self) -> Result<()> {
+        let context_factory = self.context_factory.clone();
+        let tracker = self.tracker.clone();
+        self.procedure_manager
+            .register_loader(
+                RegionRepartitionProcedure::TYPE_NAME,
+                Box::new(move |json| {
+                    let context_factory = context_factory.clone();
+                    let tracker = tracker.clone();
+                    RegionRepartitionProcedure::from_json(json, context_factory, tracker)
+                        .map(|p| Box::new(p) as _)
+                }),
+            )
+            .context(error::RegisterProcedureLoaderSnafu {
+                type_name: RegionRepartitionProcedure::TYPE_NAME,
+            })
+    }
+
+    /// Submits a procedure that splits the region at the middle of its partition range.
+    ///
+    /// Returns `None` if there is no proper split point, e.g., the range of the region is
+    /// unbounded.
+    pub async fn submit_auto_split_procedure(
+        &self,
+        cluster_id: ClusterId,
+        region_id: RegionId,
+    ) -> Result<Option<ProcedureId>> {
+        let table_id = region_id.table_id();
+        let guard = self
+            .tracker
+            .insert_running_procedure(table_id)
+            .context(error::RepartitionRunningSnafu { table_id })?;
+
+        let table_route = self.retrieve_table_route(table_id).await?;
+        let region_routes = physical_region_routes(&table_route, table_id)?;
+        let route = find_region_route(region_routes, region_id)?;
+        let (_, expr) = region_partition(table_id, route)?;
+        let Some((column, value)) = find_split_point(&expr) else {
+            return Ok(None);
+        };
+        let plan = build_split_plan(region_routes, region_id, &column, &value)?;
+
+        self.submit_procedure(cluster_id, table_id, plan, guard)
+            .await
+            .map(Some)
+    }
+
+    async fn submit_procedure(
+        &self,
+        cluster_id: ClusterId,
+        table_id: TableId,
+        plan: RepartitionPlan,
+        guard: RegionRepartitionProcedureGuard,
+    ) -> Result<ProcedureId> {
+        let table_info = self.retrieve_table_info(table_id).await?;
+        let TableName {
+            catalog_name,
+            schema_name,
+            ..
+        } = table_info.table_name();
+
+        let task = plan.to_string();
+        let RepartitionPlan {
+            peer,
+            from_regions,
+            to_regions,
+            partition_columns,
+            partition_exprs,
+        } = plan;
+        let procedure = RegionRepartitionProcedure::new(
+            PersistentContext {
+                catalog: catalog_name,
+                schema: schema_name,
+                cluster_id,
+                table_id,
+                peer,
+                from_regions,
+                to_regions,
+                partition_columns,
+                partition_exprs,
+                move_data_timeout: self.move_data_timeout,
+            },
+            self.context_factory.clone(),
+            Some(guard),
+        );
+        let procedure_with_id = ProcedureWithId::with_random_id(Box::new(procedure));
+        let procedure_id = procedure_with_id.id;
+        info!("Starting region repartition procedure {procedure_id} for {task}");
+        let procedure_manager = self.procedure_manager.clone();
+        common_runtime::spawn_global(async move {
+            let watcher = &mut match procedure_manager.submit(procedure_with_id).await {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    error!(e; "Failed to submit region repartition procedure {procedure_id} for {task}");
+                    return;
+                }
+            };
+
+            if let Err(e) = watcher::wait(watcher).await {
+                error!(e; "Failed to wait region repartition procedure {procedure_id} for {task}");
+                return;
+            }
+
+            info!(
+                "Region repartition procedure {procedure_id} for {task} is finished successfully!"
+            );
+        });
+
+        Ok(procedure_id)
+    }
+
+    async fn retrieve_table_route(&self, table_id: TableId) -> Result<TableRouteValue> {
+        self.context_factory
+            .table_metadata_manager
+            .table_route_manager()
+            .table_route_storage()
+            .get(table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .context(error::TableRouteNotFoundSnafu { table_id })
+    }
+
+    async fn retrieve_table_info(&self, table_id: TableId) -> Result<TableInfoValue> {
+        let table_info = self
+            .context_factory
+            .table_metadata_manager
+            .table_info_manager()
+            .get(table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .context(error::TableInfoNotFoundSnafu { table_id })?
+            .into_inner();
+
+        Ok(table_info)
+    }
+}
+
+fn physical_region_routes(
+    table_route: &TableRouteValue,
+    table_id: TableId,
+) -> Result<&Vec<RegionRoute>> {
+    ensure!(
+        table_route.is_physical(),
+        error::UnexpectedSnafu {
+            violated: format!("Trying to repartition the logical table {table_id}"),
+        }
+    );
+
+    table_route
+        .region_routes()
+        .context(error::UnexpectedLogicalRouteTableSnafu {
+            err_msg: format!("{table_route:?} is a non-physical TableRouteValue."),
+        })
+}
+
+/// Returns the partition columns and the partition expression of the region.
+fn region_partition(
+    table_id: TableId,
+    route: &RegionRoute,
+) -> Result<(Vec<String>, PartitionExpr)> {
+    let region_id = route.region.id;
+    let partition = route
+        .region
+        .partition
+        .as_ref()
+        .with_context(|| error::UnsupportedSnafu {
+            operation: format!("Repartitioning region {region_id} without partition"),
+        })?;
+    let partition_def =
+        PartitionDef::try_from(partition).context(error::RepartitionSnafu { table_id })?;
+
+    match partition_def.partition_bounds().as_slice() {
+        [PartitionBound::Expr(expr)] => {
+            Ok((partition_def.partition_columns().clone(), expr.clone()))
+        }
+        _ => error::UnsupportedSnafu {
+            operation: format!("Repartitioning region {region_id} without partition expression"),
+        }
+        .fail(),
+    }
+}
+
+/// Returns the leader peer of the region, the region must not be downgraded.
+fn region_leader(route: &RegionRoute) -> Result<&Peer> {
+    let region_id = route.region.id;
+    ensure!(
+        !route.is_leader_downgraded(),
+        error::InvalidArgumentsSnafu {
+            err_msg: format!("The leader of region {region_id} is downgraded"),
+        }
+    );
+
+    route
+        .leader_peer
+        .as_ref()
+        .with_context(|| error::UnexpectedSnafu {
+            violated: format!("Region route leader peer of {region_id} is not found"),
+        })
+}
+
+fn find_region_route(region_routes: &[RegionRoute], region_id: RegionId) -> Result<&RegionRoute> {
+    region_routes
+        .iter()
+        .find(|route| route.region.id == region_id)
+        .context(error::RegionRouteNotFoundSnafu { region_id })
+}
+
+/// Builds the plan that replaces `from_regions` with new regions, and validates the
+/// partition rule after repartitioning.
+fn build_plan(
+    region_routes: &[RegionRoute],
+    from_regions: Vec<RegionId>,
+    partition_columns: Vec<String>,
+    partition_exprs: Vec<PartitionExpr>,
+    peer: Peer,
+) -> Result<RepartitionPlan> {
+    // Safety: callers ensure there is at least one source region.
+    let table_id = from_regions[0].table_id();
+    // New regions never reuse region numbers of existing regions.
+    let next_region_number = region_routes
+        .iter()
+        .map(|route| route.region.id.region_number())
+        .max()
+        .map_or(0, |number| number + 1);
+    let to_regions = (0..partition_exprs.len())
+        .map(|i| RegionId::new(table_id, next_region_number + i as RegionNumber))
+        .collect::<Vec<_>>();
+
+    let mut regions = vec![];
+    let mut exprs = vec![];
+    for route in region_routes {
+        if from_regions.contains(&route.region.id) {
+            continue;
+        }
+        let (_, expr) = region_partition(table_id, route)?;
+        regions.push(route.region.id.region_number());
+        exprs.push(expr);
+    }
+    regions.extend(to_regions.iter().map(|id| id.region_number()));
+    exprs.extend(partition_exprs.iter().cloned());
+    let _ = MultiDimPartitionRule::try_new(partition_columns.clone(), regions, exprs)
+        .context(error::RepartitionSnafu { table_id })?;
+
+    Ok(RepartitionPlan {
+        peer,
+        from_regions,
+        to_regions,
+        partition_columns,
+        partition_exprs,
+    })
+}
+
+/// Builds the plan that splits the region at `value` of `column`.
+fn build_split_plan(
+    region_routes: &[RegionRoute],
+    region_id: RegionId,
+    column: &str,
+    value: &Value,
+) -> Result<RepartitionPlan> {
+    let table_id = region_id.table_id();
+    let route = find_region_route(region_routes, region_id)?;
+    let peer = region_leader(route)?.clone();
+    let (partition_columns, expr) = region_partition(table_id, route)?;
+    ensure!(
+        partition_columns.iter().any(|c| c == column),
+        error::InvalidArgumentsSnafu {
+            err_msg: format!("Column {column} is not a partition column of table {table_id}"),
+        }
+    );
+
+    let (lower, upper) =
+        split_partition_expr(&expr, column, value).context(error::RepartitionSnafu { table_id })?;
+
+    build_plan(
+        region_routes,
+        vec![region_id],
+        partition_columns,
+        vec![lower, upper],
+        peer,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use common_meta::rpc::router::{Partition, Region, RegionStatus};
+    use partition::expr::RestrictedOp;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::procedure::region_repartition::test_util::new_expr;
+
+    fn new_region_route(number: RegionNumber, peer: u64, expr: PartitionExpr) -> RegionRoute {
+        let partition_def =
+            PartitionDef::new(vec!["a".to_string()], vec![PartitionBound::Expr(expr)]);
+        RegionRoute {
+            region: Region {
+                id: RegionId::new(1024, number),
+                partition: Some(Partition::try_from(partition_def).unwrap()),
+                ..Default::default()
+            },
+            leader_peer: Some(Peer::empty(peer)),
+            ..Default::default()
+        }
+    }
+
+    fn new_region_routes() -> Vec<RegionRoute> {
+        vec![
+            new_region_route(0, 1, new_expr("a", RestrictedOp::Lt, 10)),
+            new_region_route(
+                1,
+                1,
+                PartitionExpr::new(
+                    partition::expr::Operand::Expr(new_expr("a", RestrictedOp::GtEq, 10)),
+                    RestrictedOp::And,
+                    partition::expr::Operand::Expr(new_expr("a", RestrictedOp::Lt, 100)),
+                ),
+            ),
+            new_region_route(2, 2, new_expr("a", RestrictedOp::GtEq, 100)),
+        ]
+    }
+
+    #[test]
+    fn test_build_split_plan() {
+        let region_routes = new_region_routes();
+        let plan = build_split_plan(
+            &region_routes,
+            RegionId::new(1024, 1),
+            "a",
+            &Value::Int64(50),
+        )
+        .unwrap();
+        assert_eq!(Peer::empty(1), plan.peer);
+        assert_eq!(vec![RegionId::new(1024, 1)], plan.from_regions);
+        assert_eq!(
+            vec![RegionId::new(1024, 3), RegionId::new(1024, 4)],
+            plan.to_regions
+        );
+        assert_eq!(
+            vec!["a >= 10 AND a < 50", "a >= 50 AND a < 100"],
+            plan.partition_exprs
+                .iter()
+                .map(|expr| expr.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        // The split point is out of the range.
+        let err = build_split_plan(
+            &region_routes,
+            RegionId::new(1024, 1),
+            "a",
+            &Value::Int64(100),
+        )
+        .unwrap_err();
+        assert_matches!(err, Error::Repartition { .. });
+
+        // Not a partition column.
+        let err = build_split_plan(
+            &region_routes,
+            RegionId::new(1024, 1),
+            "b",
+            &Value::Int64(50),
+        )
+        .unwrap_err();
+        assert_matches!(err, Error::InvalidArguments { .. });
+
+        // The region is downgraded.
+        let mut region_routes = new_region_routes();
+        region_routes[1].set_leader_status(Some(RegionStatus::Downgraded));
+        let err = build_split_plan(
+            &region_routes,
+            RegionId::new(1024, 1),
+            "a",
+            &Value::Int64(50),
+        )
+        .unwrap_err();
+        assert_matches!(err, Error::InvalidArguments { .. });
+    }
+
+    #[test]
+    fn test_tracker() {
+        let tracker = RegionRepartitionProcedureTracker::default();
+        let guard = tracker.insert_running_procedure(1024).unwrap();
+        assert!(tracker.contains(1024));
+        assert!(tracker.insert_running_procedure(1024).is_none());
+
+        drop(guard);
+        assert!(!tracker.contains(1024));
+        assert!(tracker.insert_running_procedure(1024).is_some());
+    }
+}
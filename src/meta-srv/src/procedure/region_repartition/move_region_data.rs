@@ -0,0 +1,390 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::time::Duration;
+
+use api::v1::meta::MailboxMessage;
+use common_meta::distributed_time_constants::MAILBOX_RTT_SECS;
+use common_meta::instruction::{
+    Instruction, InstructionReply, RepartitionRegions, RepartitionRegionsReply,
+};
+use common_meta::key::datanode_table::RegionInfo;
+use common_procedure::Status;
+use common_telemetry::{error, info};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt};
+use tokio::time::Instant;
+
+use super::update_metadata::UpdateMetadata;
+use crate::error::{self, Result};
+use crate::handler::HeartbeatMailbox;
+use crate::procedure::region_repartition::{Context, State};
+use crate::service::mailbox::Channel;
+
+/// Moves data of source regions into target regions on the datanode.
+///
+/// [MoveRegionData::Copy] copies the data while source regions stay writable and goes to
+/// [UpdateMetadata::Downgrade] once the data is copied. [MoveRegionData::Cutover] stops
+/// writing to source regions and goes to [UpdateMetadata::Upgrade] once the rest of writes
+/// are moved. Both roll back the source regions on non-retryable errors.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "MoveRegionData")]
+pub enum MoveRegionData {
+    /// Copies data of source regions into target regions.
+    Copy,
+    /// Stops writing to source regions and moves the rest of writes to them.
+    Cutover,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde]
+impl State for MoveRegionData {
+    async fn next(&mut self, ctx: &mut Context) -> Result<(Box<dyn State>, Status)> {
+        let instruction = self.build_repartition_instruction(ctx).await?;
+
+        match self.move_region_data(ctx, &instruction).await {
+            Ok(()) => {
+                let next: Box<dyn State> = match self {
+                    MoveRegionData::Copy => Box::new(UpdateMetadata::Downgrade),
+                    MoveRegionData::Cutover => Box::new(UpdateMetadata::Upgrade),
+                };
+                Ok((next, Status::executing(false)))
+            }
+            Err(err) if err.is_retryable() => Err(err),
+            Err(err) => {
+                error!(err; "Failed to move data of regions {:?}, rolling back", ctx.persistent_ctx.from_regions);
+                ctx.volatile_ctx.opening_region_guards.clear();
+                Ok((Box::new(UpdateMetadata::Rollback), Status::executing(false)))
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl MoveRegionData {
+    /// The timeout of waiting for the data to be moved in a round of polling.
+    const WAIT_FOR_MOVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Returns the timeout of a repartition regions instruction.
+    ///
+    /// Equals `WAIT_FOR_MOVE_TIMEOUT` + RTT
+    fn send_repartition_regions_timeout() -> Duration {
+        Self::WAIT_FOR_MOVE_TIMEOUT + Duration::from_secs(MAILBOX_RTT_SECS)
+    }
+
+    /// Builds the repartition regions instruction.
+    ///
+    /// Abort(non-retry):
+    /// - Datanode Table is not found.
+    /// - Failed to serialize partition expressions.
+    async fn build_repartition_instruction(&self, ctx: &mut Context) -> Result<Instruction> {
+        let RegionInfo {
+            region_storage_path,
+            region_options,
+            region_wal_options,
+            ..
+        } = ctx.get_datanode_table_value().await?.region_info.clone();
+        let pc = &ctx.persistent_ctx;
+
+        let partition_exprs = pc
+            .partition_exprs
+            .iter()
+            .map(|expr| {
+                serde_json::to_string(expr).context(error::SerializeToJsonSnafu {
+                    input: expr.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Target regions share the WAL options of the first source region.
+        let wal_options = pc
+            .from_regions
+            .iter()
+            .find_map(|id| region_wal_options.get(&id.region_number()));
+        let region_wal_options = pc
+            .to_regions
+            .iter()
+            .filter_map(|id| Some((id.region_number(), wal_options?.clone())))
+            .collect();
+
+        Ok(Instruction::RepartitionRegions(RepartitionRegions {
+            from_regions: pc.from_regions.clone(),
+            to_regions: pc.to_regions.clone(),
+            partition_columns: pc.partition_columns.clone(),
+            partition_exprs,
+            region_storage_path,
+            region_options,
+            region_wal_options,
+            cutover: matches!(self, MoveRegionData::Cutover),
+            wait_for_move_timeout: Some(Self::WAIT_FOR_MOVE_TIMEOUT),
+        }))
+    }
+
+    /// Polls the datanode until the data of regions is moved.
+    ///
+    /// Abort(non-retry):
+    /// - The data isn't moved within `move_data_timeout`.
+    /// - Errors of [MoveRegionData::send_repartition_regions].
+    ///
+    /// Retry:
+    /// - Errors of [MoveRegionData::send_repartition_regions].
+    async fn move_region_data(&self, ctx: &mut Context, instruction: &Instruction) -> Result<()> {
+        let pc = &ctx.persistent_ctx;
+        let vc = &mut ctx.volatile_ctx;
+        let peer = &pc.peer;
+
+        // Registers target regions so they won't be closed by region lease.
+        if vc.opening_region_guards.is_empty() {
+            for region_id in &pc.to_regions {
+                let guard = ctx
+                    .opening_region_keeper
+                    .register(peer.id, *region_id)
+                    .context(error::RegionOpeningRaceSnafu {
+                        peer_id: peer.id,
+                        region_id: *region_id,
+                    })?;
+                vc.opening_region_guards.push(guard);
+            }
+        }
+
+        let deadline = Instant::now() + pc.move_data_timeout;
+        loop {
+            if self.send_repartition_regions(ctx, instruction).await? {
+                return Ok(());
+            }
+
+            ensure!(
+                Instant::now() < deadline,
+                error::MoveRegionDataTimeoutSnafu {
+                    regions: ctx.persistent_ctx.from_regions.clone(),
+                    timeout: ctx.persistent_ctx.move_data_timeout,
+                }
+            );
+            info!(
+                "Waiting for data of regions {:?} to be moved, state: {self:?}",
+                ctx.persistent_ctx.from_regions
+            );
+        }
+    }
+
+    /// Sends the repartition regions instruction to the datanode, returns true if the data
+    /// of regions is moved.
+    ///
+    /// Abort(non-retry):
+    /// - The Datanode is unreachable(e.g., pusher is not found).
+    /// - Unexpected instruction reply.
+    /// - Another procedure is opening the target regions.
+    ///
+    /// Retry:
+    /// - Exceeded deadline of the instruction.
+    /// - Datanode failed to move data.
+    async fn send_repartition_regions(
+        &self,
+        ctx: &Context,
+        instruction: &Instruction,
+    ) -> Result<bool> {
+        let pc = &ctx.persistent_ctx;
+        let peer = &pc.peer;
+
+        let msg = MailboxMessage::json_message(
+            &format!("Repartition regions: {:?}", pc.from_regions),
+            &format!("Meta@{}", ctx.server_addr),
+            &format!("Datanode-{}@{}", peer.id, peer.addr),
+            common_time::util::current_time_millis(),
+            instruction,
+        )
+        .with_context(|_| error::SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+
+        let ch = Channel::Datanode(peer.id);
+        let receiver = ctx
+            .mailbox
+            .send(&ch, msg, Self::send_repartition_regions_timeout())
+            .await?;
+
+        match receiver.await? {
+            Ok(msg) => {
+                let reply = HeartbeatMailbox::json_reply(&msg)?;
+                let InstructionReply::RepartitionRegions(RepartitionRegionsReply { ready, error }) =
+                    reply
+                else {
+                    return error::UnexpectedInstructionReplySnafu {
+                        mailbox_message: msg.to_string(),
+                        reason: "expect repartition regions reply",
+                    }
+                    .fail();
+                };
+
+                if error.is_some() {
+                    return error::RetryLaterSnafu {
+                        reason: format!(
+                            "Regions {:?} are not repartitioned by datanode {:?}, error: {error:?}",
+                            pc.from_regions, peer,
+                        ),
+                    }
+                    .fail();
+                }
+
+                Ok(ready)
+            }
+            Err(error::Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for repartition regions {:?} on datanode {:?}",
+                    pc.from_regions, peer,
+                );
+                error::RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use api::v1::meta::mailbox_message::Payload;
+    use common_meta::ddl::test_util::datanode_handler::NaiveDatanodeHandler;
+    use common_meta::key::table_route::TableRouteValue;
+    use common_meta::key::test_utils::new_test_table_info;
+    use common_meta::peer::Peer;
+    use common_meta::rpc::router::{Region, RegionRoute};
+    use common_time::util::current_time_millis;
+    use store_api::storage::RegionId;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::procedure::region_migration::test_util::send_mock_reply;
+    use crate::procedure::region_repartition::test_util::*;
+
+    fn new_repartition_regions_reply(
+        id: u64,
+        ready: bool,
+        error: Option<String>,
+    ) -> MailboxMessage {
+        MailboxMessage {
+            id,
+            subject: "mock".to_string(),
+            from: "datanode".to_string(),
+            to: "meta".to_string(),
+            timestamp_millis: current_time_millis(),
+            payload: Some(Payload::Json(
+                serde_json::to_string(&InstructionReply::RepartitionRegions(
+                    RepartitionRegionsReply { ready, error },
+                ))
+                .unwrap(),
+            )),
+        }
+    }
+
+    async fn prepare_table_metadata(env: &TestingEnv) {
+        let table_info = new_test_table_info(1024, vec![0, 1]).into();
+        let region_routes = [0, 1]
+            .into_iter()
+            .map(|number| RegionRoute {
+                region: Region::new_test(RegionId::new(1024, number)),
+                leader_peer: Some(Peer::empty(1)),
+                ..Default::default()
+            })
+            .collect();
+        env.table_metadata_manager
+            .create_table_metadata(
+                table_info,
+                TableRouteValue::physical(region_routes),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_moved() {
+        let mut env = TestingEnv::new(NaiveDatanodeHandler);
+        prepare_table_metadata(&env).await;
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+
+        let (tx, rx) = mpsc::channel(1);
+        env.mailbox_ctx
+            .insert_heartbeat_response_receiver(Channel::Datanode(1), tx)
+            .await;
+        // Replies not ready for the first time.
+        let polls = Arc::new(AtomicUsize::new(0));
+        let moved_polls = polls.clone();
+        send_mock_reply(env.mailbox_ctx.mailbox().clone(), rx, move |id| {
+            let ready = moved_polls.fetch_add(1, Ordering::Relaxed) > 0;
+            Ok(new_repartition_regions_reply(id, ready, None))
+        });
+
+        let (next, _) = MoveRegionData::Copy.next(&mut ctx).await.unwrap();
+        let update_metadata = next.as_any().downcast_ref::<UpdateMetadata>().unwrap();
+        assert_matches!(update_metadata, UpdateMetadata::Downgrade);
+        assert_eq!(2, polls.load(Ordering::Relaxed));
+
+        let (next, _) = MoveRegionData::Cutover.next(&mut ctx).await.unwrap();
+        let update_metadata = next.as_any().downcast_ref::<UpdateMetadata>().unwrap();
+        assert_matches!(update_metadata, UpdateMetadata::Upgrade);
+    }
+
+    #[tokio::test]
+    async fn test_move_region_data_failed() {
+        let mut env = TestingEnv::new(NaiveDatanodeHandler);
+        prepare_table_metadata(&env).await;
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+
+        let (tx, rx) = mpsc::channel(1);
+        env.mailbox_ctx
+            .insert_heartbeat_response_receiver(Channel::Datanode(1), tx)
+            .await;
+        send_mock_reply(env.mailbox_ctx.mailbox().clone(), rx, |id| {
+            Ok(new_repartition_regions_reply(
+                id,
+                false,
+                Some("test mocked".to_string()),
+            ))
+        });
+
+        let err = MoveRegionData::Copy.next(&mut ctx).await.unwrap_err();
+        assert_matches!(err, Error::RetryLater { .. });
+    }
+
+    #[tokio::test]
+    async fn test_move_region_data_timeout() {
+        let mut env = TestingEnv::new(NaiveDatanodeHandler);
+        prepare_table_metadata(&env).await;
+        let mut persistent_context = new_persistent_context();
+        persistent_context.move_data_timeout = Duration::ZERO;
+        let mut ctx = env.context_factory().new_context(persistent_context);
+
+        let (tx, rx) = mpsc::channel(1);
+        env.mailbox_ctx
+            .insert_heartbeat_response_receiver(Channel::Datanode(1), tx)
+            .await;
+        send_mock_reply(env.mailbox_ctx.mailbox().clone(), rx, |id| {
+            Ok(new_repartition_regions_reply(id, false, None))
+        });
+
+        let (next, _) = MoveRegionData::Copy.next(&mut ctx).await.unwrap();
+        let update_metadata = next.as_any().downcast_ref::<UpdateMetadata>().unwrap();
+        assert_matches!(update_metadata, UpdateMetadata::Rollback);
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use common_meta::rpc::router::RegionRoute;
+use common_procedure::Status;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use super::drop_source_regions::DropSourceRegions;
+use super::move_region_data::MoveRegionData;
+use super::repartition_abort::RegionRepartitionAbort;
+use crate::error::{self, Result};
+use crate::procedure::region_repartition::{Context, PersistentContext, State};
+
+/// The behaviors:
+///
+/// If routes of source regions have been replaced by routes of target regions, go to the
+/// [DropSourceRegions] state.
+///
+/// If any source region isn't led by the expected peer, go to the [RegionRepartitionAbort] state.
+///
+/// Otherwise go to the [MoveRegionData::Copy] state.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegionRepartitionStart;
+
+#[async_trait::async_trait]
+#[typetag::serde]
+impl State for RegionRepartitionStart {
+    async fn next(&mut self, ctx: &mut Context) -> Result<(Box<dyn State>, Status)> {
+        let table_id = ctx.table_id();
+        let table_route = ctx.get_table_route_value().await?.clone();
+        let region_routes =
+            table_route
+                .region_routes()
+                .context(error::UnexpectedLogicalRouteTableSnafu {
+                    err_msg: format!("TableRoute({table_id:?}) is a non-physical TableRouteValue."),
+                })?;
+        let pc = &ctx.persistent_ctx;
+
+        if has_repartitioned(pc, region_routes) {
+            return Ok((Box::new(DropSourceRegions), Status::executing(false)));
+        }
+
+        if let Some(reason) = invalid_source_region(pc, region_routes) {
+            return Ok((
+                Box::new(RegionRepartitionAbort::new(&reason)),
+                Status::done(),
+            ));
+        }
+
+        Ok((Box::new(MoveRegionData::Copy), Status::executing(true)))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Returns true if routes of source regions have been replaced by routes of target regions.
+pub(crate) fn has_repartitioned(pc: &PersistentContext, region_routes: &[RegionRoute]) -> bool {
+    let contains = |region_id| region_routes.iter().any(|r| r.region.id == region_id);
+
+    pc.from_regions.iter().all(|id| !contains(*id)) && pc.to_regions.iter().all(|id| contains(*id))
+}
+
+/// Returns the reason if any source region is absent or isn't led by the expected peer.
+fn invalid_source_region(pc: &PersistentContext, region_routes: &[RegionRoute]) -> Option<String> {
+    for region_id in &pc.from_regions {
+        let Some(route) = region_routes.iter().find(|r| r.region.id == *region_id) else {
+            return Some(format!("Region route of {region_id} is not found"));
+        };
+        if route
+            .leader_peer
+            .as_ref()
+            .map_or(true, |peer| peer.id != pc.peer.id)
+        {
+            return Some(format!(
+                "Invalid region leader peer of {region_id}: {:?}, expected: {:?}",
+                route.leader_peer, pc.peer
+            ));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use common_meta::ddl::test_util::datanode_handler::NaiveDatanodeHandler;
+    use common_meta::key::table_route::TableRouteValue;
+    use common_meta::key::test_utils::new_test_table_info;
+    use common_meta::peer::Peer;
+    use common_meta::rpc::router::Region;
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::procedure::region_repartition::test_util::*;
+
+    async fn next_state(leader: u64, regions: &[u32]) -> Result<Box<dyn State>> {
+        let env = TestingEnv::new(NaiveDatanodeHandler);
+        let table_info = new_test_table_info(1024, regions.to_vec()).into();
+        let region_routes = regions
+            .iter()
+            .map(|number| RegionRoute {
+                region: Region::new_test(RegionId::new(1024, *number)),
+                leader_peer: Some(Peer::empty(leader)),
+                ..Default::default()
+            })
+            .collect();
+        env.table_metadata_manager
+            .create_table_metadata(
+                table_info,
+                TableRouteValue::physical(region_routes),
+                Default::default(),
+            )
+            .await
+            .unwrap();
+
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+        let (next, _) = RegionRepartitionStart.next(&mut ctx).await?;
+        Ok(next)
+    }
+
+    #[tokio::test]
+    async fn test_repartition_start() {
+        let next = next_state(1, &[0, 1]).await.unwrap();
+        let move_region_data = next.as_any().downcast_ref::<MoveRegionData>().unwrap();
+        assert_matches!(move_region_data, MoveRegionData::Copy);
+
+        // Has been repartitioned.
+        let next = next_state(1, &[0, 2, 3]).await.unwrap();
+        assert!(next.as_any().downcast_ref::<DropSourceRegions>().is_some());
+
+        // The source region is led by another peer.
+        let mut next = next_state(2, &[0, 1]).await.unwrap();
+        assert!(next
+            .as_any()
+            .downcast_ref::<RegionRepartitionAbort>()
+            .is_some());
+        let env = TestingEnv::new(NaiveDatanodeHandler);
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+        let err = next.next(&mut ctx).await.unwrap_err();
+        assert_matches!(err, Error::RepartitionAbort { .. });
+    }
+}
@@ -0,0 +1,380 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use common_error::ext::BoxedError;
+use common_meta::key::datanode_table::RegionInfo;
+use common_meta::rpc::router::{region_distribution, Partition, Region, RegionRoute, RegionStatus};
+use common_procedure::Status;
+use common_telemetry::{info, warn};
+use partition::partition::{PartitionBound, PartitionDef};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use super::drop_source_regions::DropSourceRegions;
+use super::move_region_data::MoveRegionData;
+use super::repartition_abort::RegionRepartitionAbort;
+use super::repartition_start::has_repartitioned;
+use crate::error::{self, Result};
+use crate::procedure::region_repartition::{Context, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "UpdateMetadata")]
+pub enum UpdateMetadata {
+    /// Downgrades source regions, so the datanode stops writing to them.
+    Downgrade,
+    /// Replaces routes of source regions with routes of target regions.
+    Upgrade,
+    /// Rolls back downgraded source regions.
+    Rollback,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde]
+impl State for UpdateMetadata {
+    async fn next(&mut self, ctx: &mut Context) -> Result<(Box<dyn State>, Status)> {
+        match self {
+            UpdateMetadata::Downgrade => {
+                self.update_source_regions_status(ctx, Some(RegionStatus::Downgraded))
+                    .await?;
+
+                Ok((Box::new(MoveRegionData::Cutover), Status::executing(false)))
+            }
+            UpdateMetadata::Upgrade => {
+                self.upgrade_target_regions(ctx).await?;
+
+                if let Err(err) = ctx.invalidate_table_cache().await {
+                    warn!("Failed to broadcast the invalidate table cache message during the upgrade target regions, error: {err:?}");
+                };
+                Ok((Box::new(DropSourceRegions), Status::executing(true)))
+            }
+            UpdateMetadata::Rollback => {
+                self.update_source_regions_status(ctx, None).await?;
+
+                if let Err(err) = ctx.invalidate_table_cache().await {
+                    warn!("Failed to broadcast the invalidate table cache message during the rollback, error: {err:?}");
+                };
+                Ok((
+                    Box::new(RegionRepartitionAbort::new(
+                        "Failed to move data of source regions.",
+                    )),
+                    Status::executing(false),
+                ))
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl UpdateMetadata {
+    /// Updates the leader status of source regions led by the expected peer.
+    ///
+    /// Retry:
+    /// - Failed to update [TableRouteValue](common_meta::key::table_route::TableRouteValue).
+    /// - Failed to retrieve the metadata of table.
+    async fn update_source_regions_status(
+        &self,
+        ctx: &mut Context,
+        status: Option<RegionStatus>,
+    ) -> Result<()> {
+        let table_metadata_manager = ctx.table_metadata_manager.clone();
+        let table_id = ctx.table_id();
+        let peer_id = ctx.persistent_ctx.peer.id;
+        let from_regions = ctx.persistent_ctx.from_regions.clone();
+        let current_table_route_value = ctx.get_table_route_value().await?;
+
+        if let Err(err) = table_metadata_manager
+            .update_leader_region_status(table_id, current_table_route_value, |route| {
+                if from_regions.contains(&route.region.id)
+                    && route
+                        .leader_peer
+                        .as_ref()
+                        .is_some_and(|leader_peer| leader_peer.id == peer_id)
+                {
+                    Some(status)
+                } else {
+                    None
+                }
+            })
+            .await
+            .context(error::TableMetadataManagerSnafu)
+        {
+            ctx.remove_table_route_value();
+            return Err(BoxedError::new(err)).context(error::RetryLaterWithSourceSnafu {
+                reason: format!(
+                    "Failed to update the status of source regions {from_regions:?} to {status:?}"
+                ),
+            });
+        }
+
+        ctx.remove_table_route_value();
+
+        Ok(())
+    }
+
+    /// Returns new [Vec<RegionRoute>] that replaces source regions with target regions.
+    fn build_upgrade_region_routes(
+        &self,
+        ctx: &Context,
+        region_routes: &[RegionRoute],
+    ) -> Result<Vec<RegionRoute>> {
+        let pc = &ctx.persistent_ctx;
+        let mut new_region_routes = region_routes
+            .iter()
+            .filter(|route| !pc.from_regions.contains(&route.region.id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for (region_id, expr) in pc.to_regions.iter().zip(pc.partition_exprs.iter()) {
+            let partition_def = PartitionDef::new(
+                pc.partition_columns.clone(),
+                vec![PartitionBound::Expr(expr.clone())],
+            );
+            let partition =
+                Partition::try_from(partition_def).context(error::RepartitionSnafu {
+                    table_id: pc.table_id,
+                })?;
+            new_region_routes.push(RegionRoute {
+                region: Region {
+                    id: *region_id,
+                    partition: Some(partition),
+                    ..Default::default()
+                },
+                leader_peer: Some(pc.peer.clone()),
+                ..Default::default()
+            });
+        }
+        new_region_routes.sort_by_key(|route| route.region.id);
+
+        Ok(new_region_routes)
+    }
+
+    /// Replaces routes of source regions with routes of target regions, then updates region
+    /// numbers of the table info.
+    ///
+    /// Abort(non-retry):
+    /// - TableRoute or TableInfo is not found.
+    ///
+    /// Retry:
+    /// - Failed to update [TableRouteValue](common_meta::key::table_route::TableRouteValue).
+    /// - Failed to update [TableInfoValue](common_meta::key::table_info::TableInfoValue).
+    /// - Failed to retrieve the metadata of table.
+    async fn upgrade_target_regions(&self, ctx: &mut Context) -> Result<()> {
+        let table_metadata_manager = ctx.table_metadata_manager.clone();
+        let table_id = ctx.table_id();
+        let table_route_value = ctx.get_table_route_value().await?.clone();
+        let region_routes =
+            table_route_value
+                .region_routes()
+                .context(error::UnexpectedLogicalRouteTableSnafu {
+                    err_msg: format!("{self:?} is a non-physical TableRouteValue."),
+                })?;
+
+        let region_routes = if has_repartitioned(&ctx.persistent_ctx, region_routes) {
+            region_routes.clone()
+        } else {
+            let new_region_routes = self.build_upgrade_region_routes(ctx, region_routes)?;
+            let RegionInfo {
+                engine,
+                region_storage_path,
+                region_options,
+                region_wal_options,
+            } = ctx.get_datanode_table_value().await?.region_info.clone();
+
+            // Target regions share the WAL options of the first source region.
+            let pc = &ctx.persistent_ctx;
+            let mut new_region_wal_options = region_wal_options.clone();
+            let wal_options = pc
+                .from_regions
+                .iter()
+                .filter_map(|id| new_region_wal_options.remove(&id.region_number()))
+                .next();
+            if let Some(wal_options) = wal_options {
+                for region_id in &pc.to_regions {
+                    new_region_wal_options.insert(region_id.region_number(), wal_options.clone());
+                }
+            }
+
+            info!(
+                "Trying to update region routes to {:?} for table: {}",
+                region_distribution(&new_region_routes),
+                table_id
+            );
+            if let Err(err) = table_metadata_manager
+                .update_table_route(
+                    table_id,
+                    RegionInfo {
+                        engine,
+                        region_storage_path,
+                        region_options: region_options.clone(),
+                        region_wal_options,
+                    },
+                    &table_route_value,
+                    new_region_routes.clone(),
+                    &region_options,
+                    &new_region_wal_options,
+                )
+                .await
+                .context(error::TableMetadataManagerSnafu)
+            {
+                ctx.remove_table_route_value();
+                return Err(BoxedError::new(err)).context(error::RetryLaterWithSourceSnafu {
+                    reason: format!(
+                        "Failed to update the table route during the upgrading target regions, table: {table_id}"
+                    ),
+                });
+            }
+
+            ctx.remove_table_route_value();
+            ctx.volatile_ctx.datanode_table = None;
+            // Consumes the guards.
+            ctx.volatile_ctx.opening_region_guards.clear();
+            new_region_routes
+        };
+
+        let mut region_numbers = region_routes
+            .iter()
+            .map(|route| route.region.id.region_number())
+            .collect::<Vec<_>>();
+        region_numbers.sort_unstable();
+        let table_info_value = ctx.get_table_info_value().await?.clone();
+        if table_info_value.table_info.meta.region_numbers == region_numbers {
+            return Ok(());
+        }
+
+        let mut new_table_info = table_info_value.table_info.clone();
+        new_table_info.meta.region_numbers = region_numbers;
+        if let Err(err) = table_metadata_manager
+            .update_table_info(&table_info_value, new_table_info)
+            .await
+            .context(error::TableMetadataManagerSnafu)
+        {
+            ctx.remove_table_info_value();
+            return Err(BoxedError::new(err)).context(error::RetryLaterWithSourceSnafu {
+                reason: format!(
+                    "Failed to update the table info during the upgrading target regions, table: {table_id}"
+                ),
+            });
+        }
+        ctx.remove_table_info_value();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
+
+    use common_meta::ddl::test_util::datanode_handler::NaiveDatanodeHandler;
+    use common_meta::key::table_route::TableRouteValue;
+    use common_meta::key::test_utils::new_test_table_info;
+    use common_meta::peer::Peer;
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::procedure::region_repartition::test_util::*;
+
+    #[tokio::test]
+    async fn test_update_metadata() {
+        let env = TestingEnv::new(NaiveDatanodeHandler);
+        let table_info = new_test_table_info(1024, vec![0, 1]).into();
+        let region_routes = [0, 1]
+            .into_iter()
+            .map(|number| RegionRoute {
+                region: Region::new_test(RegionId::new(1024, number)),
+                leader_peer: Some(Peer::empty(1)),
+                ..Default::default()
+            })
+            .collect();
+        env.table_metadata_manager
+            .create_table_metadata(
+                table_info,
+                TableRouteValue::physical(region_routes),
+                HashMap::from([(0, "wal0".to_string()), (1, "wal1".to_string())]),
+            )
+            .await
+            .unwrap();
+        let mut ctx = env.context_factory().new_context(new_persistent_context());
+
+        let (next, _) = UpdateMetadata::Downgrade.next(&mut ctx).await.unwrap();
+        let move_region_data = next.as_any().downcast_ref::<MoveRegionData>().unwrap();
+        assert_matches!(move_region_data, MoveRegionData::Cutover);
+        let table_route = env
+            .table_metadata_manager
+            .table_route_manager()
+            .table_route_storage()
+            .get(1024)
+            .await
+            .unwrap()
+            .unwrap();
+        let region_routes = table_route.region_routes().unwrap();
+        assert!(!region_routes[0].is_leader_downgraded());
+        assert!(region_routes[1].is_leader_downgraded());
+
+        let (next, _) = UpdateMetadata::Upgrade.next(&mut ctx).await.unwrap();
+        assert!(next.as_any().downcast_ref::<DropSourceRegions>().is_some());
+        let table_route = env
+            .table_metadata_manager
+            .table_route_manager()
+            .table_route_storage()
+            .get(1024)
+            .await
+            .unwrap()
+            .unwrap();
+        let region_routes = table_route.region_routes().unwrap();
+        assert_eq!(
+            vec![
+                RegionId::new(1024, 0),
+                RegionId::new(1024, 2),
+                RegionId::new(1024, 3)
+            ],
+            region_routes
+                .iter()
+                .map(|route| route.region.id)
+                .collect::<Vec<_>>()
+        );
+        let partition_def =
+            PartitionDef::try_from(region_routes[1].region.partition.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            &vec![PartitionBound::Expr(new_expr(
+                "a",
+                partition::expr::RestrictedOp::Lt,
+                100
+            ))],
+            partition_def.partition_bounds()
+        );
+
+        let datanode_table = ctx.get_datanode_table_value().await.unwrap();
+        assert_eq!(vec![0, 2, 3], datanode_table.regions);
+        assert_eq!(
+            HashMap::from([
+                (0, "wal0".to_string()),
+                (2, "wal1".to_string()),
+                (3, "wal1".to_string())
+            ]),
+            datanode_table.region_info.region_wal_options
+        );
+        let table_info = ctx.get_table_info_value().await.unwrap();
+        assert_eq!(vec![0, 2, 3], table_info.table_info.meta.region_numbers);
+
+        // Upgrades again.
+        UpdateMetadata::Upgrade.next(&mut ctx).await.unwrap();
+    }
+}
@@ -0,0 +1,564 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common_meta::cluster::{RegionMove, RegionMoveKey, RegionMoveStatus};
+use common_meta::kv_backend::ResettableKvBackendRef;
+use common_meta::peer::Peer;
+use common_meta::rpc::store::{DeleteRangeRequest, PutRequest};
+use common_meta::ClusterId;
+use common_runtime::JoinHandle;
+use common_telemetry::{info, warn};
+use common_time::util::current_time_millis;
+use snafu::ResultExt;
+use store_api::region_engine::RegionRole;
+use store_api::storage::RegionId;
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::cluster::MetaPeerClientRef;
+use crate::error::{self, Result};
+use crate::handler::node_stat::Stat;
+use crate::key::{DatanodeLeaseKey, DatanodeStatValue};
+use crate::lease;
+use crate::metasrv::RegionBalancerOptions;
+use crate::procedure::region_migration::manager::RegionMigrationManagerRef;
+use crate::procedure::region_migration::RegionMigrationProcedureTask;
+
+/// The replay timeout of region migrations submitted by the balancer.
+const REPLAY_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub type RegionBalancerTickerRef = Arc<RegionBalancerTicker>;
+
+/// A background job that runs a balancing round of [`RegionBalancer`] periodically.
+pub struct RegionBalancerTicker {
+    /// The [`Option`] wrapper allows us to abort the job while dropping the ticker.
+    tick_handle: Mutex<Option<JoinHandle<()>>>,
+
+    /// The interval of tick.
+    tick_interval: Duration,
+
+    balancer: Arc<RegionBalancer>,
+}
+
+impl RegionBalancerTicker {
+    pub(crate) fn new(tick_interval: Duration, balancer: RegionBalancer) -> Self {
+        Self {
+            tick_handle: Mutex::new(None),
+            tick_interval,
+            balancer: Arc::new(balancer),
+        }
+    }
+
+    /// Starts the ticker.
+    pub fn start(&self) {
+        let mut handle = self.tick_handle.lock().unwrap();
+        if handle.is_none() {
+            let balancer = self.balancer.clone();
+            let tick_interval = self.tick_interval;
+            let ticker_loop = tokio::spawn(async move {
+                let mut interval = interval(tick_interval);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                // The first tick completes immediately, skips it to wait for heartbeats
+                // of datanodes after the metasrv becomes leader.
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    if let Err(err) = balancer.balance().await {
+                        warn!(err; "Failed to balance regions");
+                    }
+                }
+            });
+            *handle = Some(ticker_loop);
+        }
+    }
+
+    /// Stops the ticker.
+    pub fn stop(&self) {
+        let handle = self.tick_handle.lock().unwrap().take();
+        if let Some(handle) = handle {
+            handle.abort();
+            info!("The region balancer loop is stopped.");
+        }
+    }
+}
+
+impl Drop for RegionBalancerTicker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The [`RegionBalancer`] moves leader regions from overloaded datanodes to underloaded
+/// datanodes by region migrations.
+///
+/// The load of a datanode is computed from the region count, the write rate and the disk
+/// usage reported by its latest heartbeat. Planned moves are recorded in the in-memory
+/// store so they are visible in `information_schema.region_move_plans`.
+pub struct RegionBalancer {
+    options: RegionBalancerOptions,
+    meta_peer_client: MetaPeerClientRef,
+    in_memory: ResettableKvBackendRef,
+    region_migration_manager: RegionMigrationManagerRef,
+    datanode_lease_secs: u64,
+}
+
+impl RegionBalancer {
+    pub(crate) fn new(
+        options: RegionBalancerOptions,
+        meta_peer_client: MetaPeerClientRef,
+        in_memory: ResettableKvBackendRef,
+        region_migration_manager: RegionMigrationManagerRef,
+        datanode_lease_secs: u64,
+    ) -> Self {
+        Self {
+            options,
+            meta_peer_client,
+            in_memory,
+            region_migration_manager,
+            datanode_lease_secs,
+        }
+    }
+
+    /// Runs a balancing round for each cluster.
+    pub(crate) async fn balance(&self) -> Result<()> {
+        let mut clusters: HashMap<ClusterId, Vec<DatanodeStatValue>> = HashMap::new();
+        for (key, value) in self.meta_peer_client.get_all_dn_stat_kvs().await? {
+            clusters.entry(key.cluster_id).or_default().push(value);
+        }
+
+        for (cluster_id, stat_values) in clusters {
+            if let Err(err) = self.balance_cluster(cluster_id, stat_values).await {
+                warn!(err; "Failed to balance regions of cluster {cluster_id}");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn balance_cluster(
+        &self,
+        cluster_id: ClusterId,
+        stat_values: Vec<DatanodeStatValue>,
+    ) -> Result<()> {
+        let alive_datanodes =
+            lease::alive_datanodes(cluster_id, &self.meta_peer_client, self.datanode_lease_secs)
+                .await?;
+        let loads = stat_values
+            .iter()
+            .filter_map(|value| value.stats.last())
+            .filter(|stat| {
+                alive_datanodes.contains_key(&DatanodeLeaseKey {
+                    cluster_id,
+                    node_id: stat.id,
+                })
+            })
+            .map(DatanodeLoad::from)
+            .collect::<Vec<_>>();
+
+        let tracker = self.region_migration_manager.tracker();
+        let mut moves = plan_moves(&loads, &self.options, |region_id| {
+            tracker.contains(region_id)
+        });
+        if !self.options.dry_run {
+            let available = self
+                .options
+                .max_running_migrations
+                .saturating_sub(tracker.num_running_procedures());
+            moves.truncate(available);
+        }
+
+        self.clear_region_moves(cluster_id).await?;
+        for planned_move in moves {
+            let region_move = if self.options.dry_run {
+                info!("Planned region move (dry run): {planned_move:?}");
+                planned_move.into_region_move(RegionMoveStatus::DryRun, None)
+            } else {
+                self.submit_move(cluster_id, planned_move).await
+            };
+            self.save_region_move(cluster_id, region_move).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn submit_move(&self, cluster_id: ClusterId, planned_move: PlannedMove) -> RegionMove {
+        let task = RegionMigrationProcedureTask::new(
+            cluster_id,
+            planned_move.region_id,
+            planned_move.from_peer.clone(),
+            planned_move.to_peer.clone(),
+            REPLAY_TIMEOUT,
+        );
+        match self.region_migration_manager.submit_procedure(task).await {
+            Ok(procedure_id) => {
+                info!("Submitted region move: {planned_move:?}, procedure: {procedure_id:?}");
+                planned_move.into_region_move(
+                    RegionMoveStatus::Submitted,
+                    procedure_id.map(|id| id.to_string()),
+                )
+            }
+            Err(err) => {
+                warn!(err; "Failed to submit region move: {planned_move:?}");
+                planned_move.into_region_move(RegionMoveStatus::Failed, None)
+            }
+        }
+    }
+
+    /// Removes region moves planned in the previous round.
+    async fn clear_region_moves(&self, cluster_id: ClusterId) -> Result<()> {
+        let req = DeleteRangeRequest::new()
+            .with_prefix(RegionMoveKey::key_prefix_with_cluster_id(cluster_id));
+        self.in_memory
+            .delete_range(req)
+            .await
+            .context(error::SaveClusterInfoSnafu)?;
+
+        Ok(())
+    }
+
+    async fn save_region_move(&self, cluster_id: ClusterId, region_move: RegionMove) -> Result<()> {
+        let key = RegionMoveKey {
+            cluster_id,
+            region_id: region_move.region_id,
+        };
+        let put_req = PutRequest {
+            key: key.into(),
+            value: region_move
+                .try_into()
+                .context(error::InvalidClusterInfoFormatSnafu)?,
+            ..Default::default()
+        };
+        self.in_memory
+            .put(put_req)
+            .await
+            .context(error::SaveClusterInfoSnafu)?;
+
+        Ok(())
+    }
+}
+
+/// The load of a leader region.
+#[derive(Debug, Clone, PartialEq)]
+struct RegionLoad {
+    region_id: RegionId,
+    wcus: f64,
+    approximate_bytes: f64,
+}
+
+/// The load of a datanode, only leader regions are counted since follower regions
+/// can't be moved by region migrations.
+#[derive(Debug, Clone, PartialEq)]
+struct DatanodeLoad {
+    peer: Peer,
+    regions: Vec<RegionLoad>,
+}
+
+impl From<&Stat> for DatanodeLoad {
+    fn from(stat: &Stat) -> Self {
+        let regions = stat
+            .region_stats
+            .iter()
+            .filter(|region_stat| region_stat.role == RegionRole::Leader)
+            .map(|region_stat| RegionLoad {
+                region_id: region_stat.id,
+                wcus: region_stat.wcus.max(0) as f64,
+                approximate_bytes: region_stat.approximate_bytes.max(0) as f64,
+            })
+            .collect();
+
+        Self {
+            peer: Peer::new(stat.id, stat.addr.clone()),
+            regions,
+        }
+    }
+}
+
+/// A region move planned by [`plan_moves`].
+#[derive(Debug, Clone, PartialEq)]
+struct PlannedMove {
+    region_id: RegionId,
+    from_peer: Peer,
+    to_peer: Peer,
+    reason: String,
+}
+
+impl PlannedMove {
+    fn into_region_move(
+        self,
+        status: RegionMoveStatus,
+        procedure_id: Option<String>,
+    ) -> RegionMove {
+        RegionMove {
+            region_id: self.region_id.as_u64(),
+            from_peer_id: self.from_peer.id,
+            to_peer_id: self.to_peer.id,
+            reason: self.reason,
+            status,
+            procedure_id,
+            plan_time_ms: current_time_millis(),
+        }
+    }
+}
+
+/// Computes the load score of regions.
+///
+/// Each metric is normalized by its average value over datanodes, and the score is the
+/// weighted average of normalized metrics. The score of a datanode is the sum of scores of
+/// its regions, so the average score of datanodes is `1.0`.
+struct LoadScorer {
+    /// The weight and the average value of the region count, the write rate and the disk usage.
+    metrics: [(f64, f64); 3],
+    total_weight: f64,
+}
+
+impl LoadScorer {
+    fn new(loads: &[DatanodeLoad], options: &RegionBalancerOptions) -> Self {
+        let num_datanodes = loads.len().max(1) as f64;
+        let regions = loads.iter().flat_map(|load| &load.regions);
+        let mut totals = [0.0; 3];
+        for region in regions {
+            totals[0] += 1.0;
+            totals[1] += region.wcus;
+            totals[2] += region.approximate_bytes;
+        }
+
+        let weights = [
+            options.region_count_weight,
+            options.write_rate_weight,
+            options.disk_usage_weight,
+        ];
+        let mut metrics = [(0.0, 0.0); 3];
+        for ((metric, weight), total) in metrics.iter_mut().zip(weights).zip(totals) {
+            // Ignores metrics that are not reported.
+            if weight > 0.0 && total > 0.0 {
+                *metric = (weight, total / num_datanodes);
+            }
+        }
+        let total_weight = metrics.iter().map(|(weight, _)| weight).sum();
+
+        Self {
+            metrics,
+            total_weight,
+        }
+    }
+
+    fn score(&self, region: &RegionLoad) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+
+        let values = [1.0, region.wcus, region.approximate_bytes];
+        let score = self
+            .metrics
+            .iter()
+            .zip(values)
+            .filter(|((weight, _), _)| *weight > 0.0)
+            .map(|((weight, avg), value)| weight * value / avg)
+            .sum::<f64>();
+        score / self.total_weight
+    }
+}
+
+/// Plans region moves from the most loaded datanode to the least loaded datanode until no
+/// datanode is overloaded or `max_moves_per_round` moves are planned.
+///
+/// Regions that `skip` returns true are never moved.
+fn plan_moves(
+    loads: &[DatanodeLoad],
+    options: &RegionBalancerOptions,
+    skip: impl Fn(RegionId) -> bool,
+) -> Vec<PlannedMove> {
+    if loads.len() < 2 {
+        return vec![];
+    }
+
+    let scorer = LoadScorer::new(loads, options);
+    // (peer, score of the datanode, regions with their scores)
+    let mut datanodes = loads
+        .iter()
+        .map(|load| {
+            let regions = load
+                .regions
+                .iter()
+                .map(|region| (region.region_id, scorer.score(region)))
+                .collect::<Vec<_>>();
+            let score = regions.iter().map(|(_, score)| score).sum::<f64>();
+            (load.peer.clone(), score, regions)
+        })
+        .collect::<Vec<_>>();
+
+    let mut moved = HashSet::new();
+    let mut moves = vec![];
+    while moves.len() < options.max_moves_per_round {
+        // Safety: there are at least two datanodes.
+        let hot = (0..datanodes.len())
+            .max_by(|a, b| datanodes[*a].1.total_cmp(&datanodes[*b].1))
+            .unwrap();
+        let cold = (0..datanodes.len())
+            .min_by(|a, b| datanodes[*a].1.total_cmp(&datanodes[*b].1))
+            .unwrap();
+        let (hot_score, cold_score) = (datanodes[hot].1, datanodes[cold].1);
+        if hot_score <= 1.0 + options.imbalance_threshold {
+            break;
+        }
+
+        // Picks the region that makes the two datanodes closest after the move. The move
+        // must reduce the load of the hottest datanode.
+        let gap = hot_score - cold_score;
+        let candidate = datanodes[hot]
+            .2
+            .iter()
+            .enumerate()
+            .filter(|(_, (region_id, score))| {
+                *score > 0.0 && *score < gap && !moved.contains(region_id) && !skip(*region_id)
+            })
+            .min_by(|(_, (_, a)), (_, (_, b))| {
+                (gap - 2.0 * a).abs().total_cmp(&(gap - 2.0 * b).abs())
+            })
+            .map(|(i, _)| i);
+        let Some(i) = candidate else {
+            break;
+        };
+
+        let (region_id, score) = datanodes[hot].2.swap_remove(i);
+        datanodes[hot].1 -= score;
+        datanodes[cold].1 += score;
+        datanodes[cold].2.push((region_id, score));
+        moved.insert(region_id);
+        moves.push(PlannedMove {
+            region_id,
+            from_peer: datanodes[hot].0.clone(),
+            to_peer: datanodes[cold].0.clone(),
+            reason: format!(
+                "load of datanode {} is {:.2}, load of datanode {} is {:.2}",
+                datanodes[hot].0.id, hot_score, datanodes[cold].0.id, cold_score
+            ),
+        });
+    }
+
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_load(peer_id: u64, regions: &[(u32, f64, f64)]) -> DatanodeLoad {
+        DatanodeLoad {
+            peer: Peer::empty(peer_id),
+            regions: regions
+                .iter()
+                .map(|(number, wcus, bytes)| RegionLoad {
+                    region_id: RegionId::new(1024, *number),
+                    wcus: *wcus,
+                    approximate_bytes: *bytes,
+                })
+                .collect(),
+        }
+    }
+
+    fn moved_regions(moves: &[PlannedMove]) -> Vec<(u32, u64, u64)> {
+        moves
+            .iter()
+            .map(|m| (m.region_id.region_number(), m.from_peer.id, m.to_peer.id))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_moves_by_region_count() {
+        let options = RegionBalancerOptions::default();
+        let loads = vec![
+            new_load(
+                1,
+                &[(1, 0.0, 0.0), (2, 0.0, 0.0), (3, 0.0, 0.0), (4, 0.0, 0.0)],
+            ),
+            new_load(2, &[]),
+        ];
+        let moves = plan_moves(&loads, &options, |_| false);
+        assert_eq!(vec![(1, 1, 2), (4, 1, 2)], moved_regions(&moves));
+
+        // Limits the number of moves.
+        let options = RegionBalancerOptions {
+            max_moves_per_round: 1,
+            ..Default::default()
+        };
+        let moves = plan_moves(&loads, &options, |_| false);
+        assert_eq!(vec![(1, 1, 2)], moved_regions(&moves));
+
+        // Skips regions.
+        let moves = plan_moves(&loads, &options, |region_id| region_id.region_number() == 1);
+        assert_eq!(vec![(2, 1, 2)], moved_regions(&moves));
+
+        // Balanced.
+        let loads = vec![
+            new_load(1, &[(1, 0.0, 0.0), (2, 0.0, 0.0)]),
+            new_load(2, &[(3, 0.0, 0.0), (4, 0.0, 0.0)]),
+        ];
+        assert!(plan_moves(&loads, &options, |_| false).is_empty());
+
+        // Only one datanode.
+        let loads = vec![new_load(1, &[(1, 0.0, 0.0), (2, 0.0, 0.0)])];
+        assert!(plan_moves(&loads, &options, |_| false).is_empty());
+    }
+
+    #[test]
+    fn test_plan_moves_by_write_rate() {
+        let options = RegionBalancerOptions {
+            region_count_weight: 0.0,
+            disk_usage_weight: 0.0,
+            ..Default::default()
+        };
+        // Datanode 1 serves most writes.
+        let loads = vec![
+            new_load(1, &[(1, 100.0, 0.0), (2, 60.0, 0.0), (7, 20.0, 0.0)]),
+            new_load(2, &[(3, 20.0, 0.0), (4, 20.0, 0.0)]),
+            new_load(3, &[(5, 0.0, 0.0), (6, 0.0, 0.0)]),
+        ];
+        let moves = plan_moves(&loads, &options, |_| false);
+        assert_eq!(vec![(1, 1, 3)], moved_regions(&moves));
+
+        // Moving the only region doesn't reduce the load of the datanode.
+        let loads = vec![new_load(1, &[(1, 100.0, 0.0)]), new_load(2, &[])];
+        assert!(plan_moves(&loads, &options, |_| false).is_empty());
+    }
+
+    #[test]
+    fn test_load_scorer() {
+        let options = RegionBalancerOptions::default();
+        let loads = vec![
+            new_load(1, &[(1, 30.0, 100.0)]),
+            new_load(2, &[(2, 10.0, 300.0), (3, 0.0, 0.0)]),
+        ];
+        let scorer = LoadScorer::new(&loads, &options);
+        let scores = loads
+            .iter()
+            .flat_map(|load| &load.regions)
+            .map(|region| scorer.score(region))
+            .collect::<Vec<_>>();
+        // Average region count: 1.5, average wcus: 20, average bytes: 200.
+        let expected = [
+            (1.0 / 1.5 + 30.0 / 20.0 + 100.0 / 200.0) / 3.0,
+            (1.0 / 1.5 + 10.0 / 20.0 + 300.0 / 200.0) / 3.0,
+            (1.0 / 1.5) / 3.0,
+        ];
+        for (score, expected) in scores.iter().zip(expected) {
+            assert!((score - expected).abs() < 1e-9);
+        }
+        // The average score of datanodes is 1.0.
+        assert!((scores.iter().sum::<f64>() - 2.0).abs() < 1e-9);
+    }
+}
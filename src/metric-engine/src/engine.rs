@@ -154,9 +154,9 @@ impl RegionEngine for MetricEngine {
                     UnsupportedRegionRequestSnafu { request }.fail()
                 }
             }
-            RegionRequest::Delete(_) | RegionRequest::Truncate(_) => {
-                UnsupportedRegionRequestSnafu { request }.fail()
-            }
+            RegionRequest::Delete(_)
+            | RegionRequest::Truncate(_)
+            | RegionRequest::BulkInserts(_) => UnsupportedRegionRequestSnafu { request }.fail(),
             RegionRequest::Catchup(ref req) => self.inner.catchup_region(region_id, *req).await,
         };
 
@@ -349,6 +349,7 @@ impl MetadataRegion {
             output_ordering: None,
             limit: None,
             series_row_selector: None,
+            vector_search: None,
         };
         let record_batch_stream = self
             .mito
@@ -407,6 +408,7 @@ impl MetadataRegion {
             output_ordering: None,
             limit: None,
             series_row_selector: None,
+            vector_search: None,
         }
     }
 
@@ -568,6 +570,7 @@ mod test {
             output_ordering: None,
             limit: None,
             series_row_selector: None,
+            vector_search: None,
         };
         let actual_scan_request = MetadataRegion::build_read_request(key);
         assert_eq!(actual_scan_request, expected_scan_request);
@@ -22,7 +22,9 @@ use store_api::metadata::RegionMetadataRef;
 
 use crate::cache::write_cache::SstUploadRequest;
 use crate::cache::CacheManagerRef;
-use crate::config::{FulltextIndexConfig, InvertedIndexConfig};
+use crate::config::{
+    BloomFilterIndexConfig, FulltextIndexConfig, InvertedIndexConfig, VectorIndexConfig,
+};
 use crate::error::{CleanDirSnafu, DeleteIndexSnafu, DeleteSstSnafu, OpenDalSnafu, Result};
 use crate::read::Source;
 use crate::region::options::IndexOptions;
@@ -154,6 +156,8 @@ impl AccessLayer {
                 index_options: request.index_options,
                 inverted_index_config: request.inverted_index_config,
                 fulltext_index_config: request.fulltext_index_config,
+                vector_index_config: request.vector_index_config,
+                bloom_filter_index_config: request.bloom_filter_index_config,
             }
             .build()
             .await;
@@ -198,6 +202,8 @@ pub(crate) struct SstWriteRequest {
     pub(crate) index_options: IndexOptions,
     pub(crate) inverted_index_config: InvertedIndexConfig,
     pub(crate) fulltext_index_config: FulltextIndexConfig,
+    pub(crate) vector_index_config: VectorIndexConfig,
+    pub(crate) bloom_filter_index_config: BloomFilterIndexConfig,
 }
 
 pub(crate) async fn new_fs_cache_store(root: &str) -> Result<ObjectStore> {
@@ -131,6 +131,8 @@ impl WriteCache {
             index_options: write_request.index_options,
             inverted_index_config: write_request.inverted_index_config,
             fulltext_index_config: write_request.fulltext_index_config,
+            vector_index_config: write_request.vector_index_config,
+            bloom_filter_index_config: write_request.bloom_filter_index_config,
         }
         .build()
         .await;
@@ -309,6 +311,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: Default::default(),
             fulltext_index_config: Default::default(),
+            vector_index_config: Default::default(),
+            bloom_filter_index_config: Default::default(),
         };
 
         let upload_request = SstUploadRequest {
@@ -394,6 +398,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: Default::default(),
             fulltext_index_config: Default::default(),
+            vector_index_config: Default::default(),
+            bloom_filter_index_config: Default::default(),
         };
         let write_opts = WriteOptions {
             row_group_size: 512,
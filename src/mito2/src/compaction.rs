@@ -16,6 +16,7 @@ mod buckets;
 pub mod compactor;
 pub mod picker;
 mod run;
+mod stcs;
 mod task;
 #[cfg(test)]
 mod test_util;
@@ -276,6 +276,9 @@ impl Compactor for DefaultCompactor {
             let merge_mode = compaction_region.current_version.options.merge_mode();
             let inverted_index_config = compaction_region.engine_config.inverted_index.clone();
             let fulltext_index_config = compaction_region.engine_config.fulltext_index.clone();
+            let vector_index_config = compaction_region.engine_config.vector_index.clone();
+            let bloom_filter_index_config =
+                compaction_region.engine_config.bloom_filter_index.clone();
             futs.push(async move {
                 let reader = CompactionSstReaderBuilder {
                     metadata: region_metadata.clone(),
@@ -301,6 +304,8 @@ impl Compactor for DefaultCompactor {
                             index_options,
                             inverted_index_config,
                             fulltext_index_config,
+                            vector_index_config,
+                            bloom_filter_index_config,
                         },
                         &write_opts,
                     )
@@ -319,6 +324,12 @@ impl Compactor for DefaultCompactor {
                             if sst_info.index_metadata.fulltext_index.is_available() {
                                 indexes.push(IndexType::FulltextIndex);
                             }
+                            if sst_info.index_metadata.vector_index.is_available() {
+                                indexes.push(IndexType::VectorIndex);
+                            }
+                            if sst_info.index_metadata.bloom_filter_index.is_available() {
+                                indexes.push(IndexType::BloomFilterIndex);
+                            }
                             indexes
                         },
                         index_file_size: sst_info.index_metadata.file_size,
@@ -19,6 +19,7 @@ use api::v1::region::compact_request;
 use serde::{Deserialize, Serialize};
 
 use crate::compaction::compactor::CompactionRegion;
+use crate::compaction::stcs::StcsPicker;
 use crate::compaction::twcs::TwcsPicker;
 use crate::compaction::window::WindowedCompactionPicker;
 use crate::compaction::{CompactionOutput, SerializedCompactionOutput};
@@ -138,6 +139,12 @@ pub fn new_picker(
                 twcs_opts.max_inactive_window_files,
                 twcs_opts.time_window_seconds(),
             )) as Arc<_>,
+            CompactionOptions::Stcs(stcs_opts) => Arc::new(StcsPicker::new(
+                stcs_opts.min_threshold,
+                stcs_opts.max_threshold,
+                stcs_opts.min_sstable_size.as_bytes() as usize,
+                stcs_opts.time_window_seconds(),
+            )) as Arc<_>,
         }
     }
 }
@@ -0,0 +1,305 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_telemetry::{debug, info};
+use common_time::Timestamp;
+
+use crate::compaction::buckets::infer_time_bucket;
+use crate::compaction::compactor::CompactionRegion;
+use crate::compaction::picker::{Picker, PickerOutput};
+use crate::compaction::run::Item;
+use crate::compaction::twcs::{assign_to_windows, Window, LEVEL_COMPACTED};
+use crate::compaction::{get_expired_ssts, CompactionOutput};
+use crate::sst::file::FileId;
+use crate::sst::version::LevelMeta;
+
+/// A file belongs to a size tier if its size is in `[avg * BUCKET_LOW, avg * BUCKET_HIGH]`
+/// where `avg` is the average file size of the tier.
+const BUCKET_LOW: f64 = 0.5;
+const BUCKET_HIGH: f64 = 1.5;
+
+/// `StcsPicker` (size-tiered compaction strategy) groups files in the same time window
+/// into tiers of similar sizes and merges the files of a tier once there are enough of them.
+///
+/// Compared with [TwcsPicker](crate::compaction::twcs::TwcsPicker), it doesn't care about
+/// sorted runs so it amortizes the write amplification when files in a window heavily
+/// overlap, e.g. workloads with out-of-order writes or updates.
+#[derive(Debug)]
+pub struct StcsPicker {
+    min_threshold: usize,
+    max_threshold: usize,
+    min_sstable_size: usize,
+    time_window_seconds: Option<i64>,
+}
+
+impl StcsPicker {
+    pub fn new(
+        min_threshold: usize,
+        max_threshold: usize,
+        min_sstable_size: usize,
+        time_window_seconds: Option<i64>,
+    ) -> Self {
+        Self {
+            min_threshold,
+            max_threshold,
+            min_sstable_size,
+            time_window_seconds,
+        }
+    }
+
+    /// Builds at most one compaction output for each time window.
+    fn build_output(&self, time_windows: &BTreeMap<i64, Window>) -> Vec<CompactionOutput> {
+        let mut output = vec![];
+        for (window, files) in time_windows {
+            let tiers = split_into_tiers(&files.files, self.min_sstable_size);
+            let Some(inputs) = self.pick_tier(tiers) else {
+                debug!(
+                    "Skip building compaction output, current window: {}, files: {}",
+                    *window,
+                    files.files.len()
+                );
+                continue;
+            };
+
+            // we only remove deletion markers when all files in the window are compacted and no
+            // file in current window overlaps with any other window.
+            let filter_deleted = !files.overlapping && inputs.len() == files.files.len();
+            info!(
+                "Building compaction output, current window: {}, files: {}, output size: {}, remove deletion markers: {}",
+                *window,
+                files.files.len(),
+                inputs.len(),
+                filter_deleted
+            );
+            output.push(CompactionOutput {
+                output_file_id: FileId::random(),
+                output_level: LEVEL_COMPACTED,
+                inputs,
+                filter_deleted,
+                output_time_range: None,
+            });
+        }
+        output
+    }
+
+    /// Picks the tier with most files among tiers that reach the `min_threshold`.
+    /// Prefers the tier with smaller total size if there are ties.
+    fn pick_tier<T: Item>(&self, tiers: Vec<Vec<T>>) -> Option<Vec<T>> {
+        tiers
+            .into_iter()
+            .filter(|tier| tier.len() >= self.min_threshold)
+            .map(|mut tier| {
+                // Files in the tier are sorted by size so we keep the smallest files.
+                tier.truncate(self.max_threshold);
+                tier
+            })
+            .min_by_key(|tier| {
+                let total_size: usize = tier.iter().map(|f| f.size()).sum();
+                (std::cmp::Reverse(tier.len()), total_size)
+            })
+    }
+}
+
+/// Splits files into tiers of similar sizes. Files in each tier are sorted by size.
+/// All files smaller than `min_sstable_size` are put into the first tier.
+fn split_into_tiers<T: Item>(files: &[T], min_sstable_size: usize) -> Vec<Vec<T>> {
+    let mut files = files.to_vec();
+    files.sort_unstable_by_key(|f| f.size());
+
+    let mut tiers: Vec<Vec<T>> = vec![];
+    let mut tier_size = 0;
+    for file in files {
+        let size = file.size();
+        if let Some(tier) = tiers.last_mut() {
+            let avg = (tier_size / tier.len()) as f64;
+            let in_tier = size < min_sstable_size
+                || (size as f64 >= avg * BUCKET_LOW && size as f64 <= avg * BUCKET_HIGH);
+            if in_tier {
+                tier_size += size;
+                tier.push(file);
+                continue;
+            }
+        }
+        tier_size = size;
+        tiers.push(vec![file]);
+    }
+    tiers
+}
+
+impl Picker for StcsPicker {
+    fn pick(&self, compaction_region: &CompactionRegion) -> Option<PickerOutput> {
+        let region_id = compaction_region.region_id;
+        let levels = compaction_region.current_version.ssts.levels();
+        let ttl = compaction_region.current_version.options.ttl;
+        let expired_ssts = get_expired_ssts(levels, ttl, Timestamp::current_millis());
+        if !expired_ssts.is_empty() {
+            info!("Expired SSTs in region {}: {:?}", region_id, expired_ssts);
+            // here we mark expired SSTs as compacting to avoid them being picked.
+            expired_ssts.iter().for_each(|f| f.set_compacting(true));
+        }
+
+        let compaction_time_window = compaction_region
+            .current_version
+            .compaction_time_window
+            .map(|window| window.as_secs() as i64);
+        let time_window_size = compaction_time_window
+            .or(self.time_window_seconds)
+            .unwrap_or_else(|| {
+                let inferred = infer_time_bucket(levels[0].files());
+                info!(
+                    "Compaction window for region {} is not present, inferring from files: {:?}",
+                    region_id, inferred
+                );
+                inferred
+            });
+
+        let windows = assign_to_windows(
+            levels
+                .iter()
+                .flat_map(LevelMeta::files)
+                .filter(|f| !f.compacting()),
+            time_window_size,
+        );
+        let outputs = self.build_output(&windows);
+
+        if outputs.is_empty() && expired_ssts.is_empty() {
+            return None;
+        }
+
+        Some(PickerOutput {
+            outputs,
+            expired_ssts,
+            time_window_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compaction::test_util::new_file_handles;
+
+    fn sizes<T: Item>(tiers: &[Vec<T>]) -> Vec<Vec<usize>> {
+        tiers
+            .iter()
+            .map(|tier| tier.iter().map(|f| f.size()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_into_tiers() {
+        let files = new_file_handles(&[
+            (0, 999, 1),
+            (0, 999, 100),
+            (0, 999, 2),
+            (0, 999, 10),
+            (0, 999, 120),
+            (0, 999, 11),
+            (0, 999, 1000),
+        ]);
+        let tiers = split_into_tiers(&files, 0);
+        assert_eq!(
+            vec![vec![1], vec![2], vec![10, 11], vec![100, 120], vec![1000]],
+            sizes(&tiers)
+        );
+
+        // Small files are in the same tier.
+        let tiers = split_into_tiers(&files, 50);
+        assert_eq!(
+            vec![vec![1, 2, 10, 11], vec![100, 120], vec![1000]],
+            sizes(&tiers)
+        );
+
+        assert!(split_into_tiers(&new_file_handles(&[]), 0).is_empty());
+    }
+
+    #[test]
+    fn test_pick_tier() {
+        let picker = StcsPicker::new(2, 3, 0, None);
+        let tiers = split_into_tiers(
+            &new_file_handles(&[
+                (0, 999, 1),
+                (0, 999, 10),
+                (0, 999, 11),
+                (0, 999, 100),
+                (0, 999, 101),
+            ]),
+            0,
+        );
+        // prefers the tier with smaller size.
+        assert_eq!(vec![10, 11], sizes(&[picker.pick_tier(tiers).unwrap()])[0]);
+
+        let tiers = split_into_tiers(
+            &new_file_handles(&[
+                (0, 999, 10),
+                (0, 999, 11),
+                (0, 999, 100),
+                (0, 999, 101),
+                (0, 999, 102),
+                (0, 999, 103),
+            ]),
+            0,
+        );
+        // prefers the tier with more files and truncates it.
+        assert_eq!(
+            vec![100, 101, 102],
+            sizes(&[picker.pick_tier(tiers).unwrap()])[0]
+        );
+
+        let picker = StcsPicker::new(4, 32, 0, None);
+        let tiers = split_into_tiers(&new_file_handles(&[(0, 999, 10), (0, 999, 11)]), 0);
+        assert!(picker.pick_tier(tiers).is_none());
+    }
+
+    #[test]
+    fn test_build_output() {
+        let picker = StcsPicker::new(2, 32, 0, None);
+        let files = new_file_handles(&[
+            // window 1 (in seconds)
+            (0, 999, 10),
+            (0, 999, 11),
+            (0, 999, 100),
+            // window 2
+            (1000, 1999, 10),
+            (1000, 1999, 12),
+            // window 3
+            (2000, 2999, 10),
+        ]);
+        let windows = assign_to_windows(files.iter(), 1);
+        let outputs = picker.build_output(&windows);
+        assert_eq!(2, outputs.len());
+
+        assert_eq!(
+            vec![10, 11],
+            outputs[0]
+                .inputs
+                .iter()
+                .map(|f| f.size())
+                .collect::<Vec<_>>()
+        );
+        // Not all files in the window are compacted.
+        assert!(!outputs[0].filter_deleted);
+        assert_eq!(
+            vec![10, 12],
+            outputs[1]
+                .inputs
+                .iter()
+                .map(|f| f.size())
+                .collect::<Vec<_>>()
+        );
+        assert!(outputs[1].filter_deleted);
+    }
+}
@@ -29,7 +29,7 @@ use crate::compaction::{get_expired_ssts, CompactionOutput};
 use crate::sst::file::{overlaps, FileHandle, FileId, Level};
 use crate::sst::version::LevelMeta;
 
-const LEVEL_COMPACTED: Level = 1;
+pub(crate) const LEVEL_COMPACTED: Level = 1;
 
 /// `TwcsPicker` picks files of which the max timestamp are in the same time window as compaction
 /// candidates.
@@ -194,12 +194,12 @@ impl Picker for TwcsPicker {
     }
 }
 
-struct Window {
+pub(crate) struct Window {
     start: Timestamp,
     end: Timestamp,
-    files: Vec<FileHandle>,
+    pub(crate) files: Vec<FileHandle>,
     time_window: i64,
-    overlapping: bool,
+    pub(crate) overlapping: bool,
 }
 
 impl Window {
@@ -230,7 +230,7 @@ impl Window {
 }
 
 /// Assigns files to windows with predefined window size (in seconds) by their max timestamps.
-fn assign_to_windows<'a>(
+pub(crate) fn assign_to_windows<'a>(
     files: impl Iterator<Item = &'a FileHandle>,
     time_window_size: i64,
 ) -> BTreeMap<i64, Window> {
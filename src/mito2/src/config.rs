@@ -119,6 +119,10 @@ pub struct MitoConfig {
     pub inverted_index: InvertedIndexConfig,
     /// Full-text index configs.
     pub fulltext_index: FulltextIndexConfig,
+    /// Vector index configs.
+    pub vector_index: VectorIndexConfig,
+    /// Bloom filter index configs.
+    pub bloom_filter_index: BloomFilterIndexConfig,
 
     /// Memtable config
     pub memtable: MemtableConfig,
@@ -151,6 +155,8 @@ impl Default for MitoConfig {
             index: IndexConfig::default(),
             inverted_index: InvertedIndexConfig::default(),
             fulltext_index: FulltextIndexConfig::default(),
+            vector_index: VectorIndexConfig::default(),
+            bloom_filter_index: BloomFilterIndexConfig::default(),
             memtable: MemtableConfig::default(),
         };
 
@@ -487,6 +493,53 @@ impl FulltextIndexConfig {
     }
 }
 
+/// Configuration options for the vector index.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct VectorIndexConfig {
+    /// Whether to create the index on flush: automatically or never.
+    pub create_on_flush: Mode,
+    /// Whether to create the index on compaction: automatically or never.
+    pub create_on_compaction: Mode,
+    /// Whether to apply the index on query: automatically or never.
+    pub apply_on_query: Mode,
+    /// Number of candidates to consider when searching the index.
+    pub expansion_search: usize,
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        Self {
+            create_on_flush: Mode::Auto,
+            create_on_compaction: Mode::Auto,
+            apply_on_query: Mode::Auto,
+            expansion_search: 64,
+        }
+    }
+}
+
+/// Configuration options for the bloom filter index.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct BloomFilterIndexConfig {
+    /// Whether to create the index on flush: automatically or never.
+    pub create_on_flush: Mode,
+    /// Whether to create the index on compaction: automatically or never.
+    pub create_on_compaction: Mode,
+    /// Whether to apply the index on query: automatically or never.
+    pub apply_on_query: Mode,
+}
+
+impl Default for BloomFilterIndexConfig {
+    fn default() -> Self {
+        Self {
+            create_on_flush: Mode::Auto,
+            create_on_compaction: Mode::Auto,
+            apply_on_query: Mode::Auto,
+        }
+    }
+}
+
 /// Divide cpu num by a non-zero `divisor` and returns at least 1.
 fn divide_num_cpus(divisor: usize) -> usize {
     debug_assert!(divisor > 0);
@@ -266,7 +266,7 @@ fn prepare_batch_open_requests(
                     .or_default()
                     .push((region_id, request));
             }
-            WalOptions::RaftEngine => {
+            WalOptions::RaftEngine | WalOptions::ObjectStore => {
                 remaining_regions.push((region_id, request));
             }
         }
@@ -403,7 +403,16 @@ impl EngineInner {
         region_id: RegionId,
         request: RegionRequest,
     ) -> Result<AffectedRows> {
-        let (request, receiver) = WorkerRequest::try_from_region_request(region_id, request)?;
+        // Bulk inserts need the region schema to build the rows for the WAL.
+        let region_metadata = if matches!(request, RegionRequest::BulkInserts(_)) {
+            self.workers
+                .get_region(region_id)
+                .map(|region| region.metadata())
+        } else {
+            None
+        };
+        let (request, receiver) =
+            WorkerRequest::try_from_region_request(region_id, request, region_metadata)?;
         self.workers.submit_to_worker(region_id, request).await?;
 
         receiver.await.context(RecvSnafu)?
@@ -434,6 +443,9 @@ impl EngineInner {
         .with_parallelism(scan_parallelism)
         .with_ignore_inverted_index(self.config.inverted_index.apply_on_query.disabled())
         .with_ignore_fulltext_index(self.config.fulltext_index.apply_on_query.disabled())
+        .with_ignore_vector_index(self.config.vector_index.apply_on_query.disabled())
+        .with_ignore_bloom_filter_index(self.config.bloom_filter_index.apply_on_query.disabled())
+        .with_vector_index_expansion_search(self.config.vector_index.expansion_search)
         .with_start_time(query_start);
 
         Ok(scan_region)
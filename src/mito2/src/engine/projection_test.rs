@@ -79,6 +79,7 @@ async fn test_scan_projection() {
         output_ordering: None,
         limit: None,
         series_row_selector: None,
+        vector_search: None,
     };
     let stream = engine.scan_to_stream(region_id, request).await.unwrap();
     let batches = RecordBatches::try_collect(stream).await.unwrap();
@@ -829,6 +829,59 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to retrieve vector index options from column metadata"))]
+    VectorIndexOptions {
+        #[snafu(implicit)]
+        location: Location,
+        source: datatypes::error::Error,
+        column_name: String,
+    },
+
+    #[snafu(display("Failed to push vector to vector index"))]
+    VectorIndexPush {
+        source: index::vector_index::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to read vector index blob"))]
+    ReadVectorIndexBlob {
+        #[snafu(source)]
+        error: std::io::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to apply vector index"))]
+    ApplyVectorIndex {
+        source: index::vector_index::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to retrieve bloom filter index options from column metadata"))]
+    BloomFilterIndexOptions {
+        #[snafu(implicit)]
+        location: Location,
+        source: datatypes::error::Error,
+        column_name: String,
+    },
+
+    #[snafu(display("Failed to read bloom filter index blob"))]
+    ReadBloomFilterIndexBlob {
+        #[snafu(source)]
+        error: std::io::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to apply bloom filter index"))]
+    ApplyBloomFilterIndex {
+        source: index::bloom_filter::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("SST file {} does not contain valid stats info", file_path))]
     StatsNotPresent {
         file_path: String,
@@ -979,6 +1032,14 @@ impl ErrorExt for Error {
             FulltextPushText { source, .. }
             | FulltextFinish { source, .. }
             | ApplyFulltextIndex { source, .. } => source.status_code(),
+            VectorIndexOptions { source, .. } => source.status_code(),
+            VectorIndexPush { source, .. } | ApplyVectorIndex { source, .. } => {
+                source.status_code()
+            }
+            ReadVectorIndexBlob { .. } => StatusCode::StorageUnavailable,
+            BloomFilterIndexOptions { source, .. } => source.status_code(),
+            ApplyBloomFilterIndex { source, .. } => source.status_code(),
+            ReadBloomFilterIndexBlob { .. } => StatusCode::StorageUnavailable,
             DecodeStats { .. } | StatsNotPresent { .. } => StatusCode::Internal,
             RegionBusy { .. } => StatusCode::RegionBusy,
         }
@@ -349,6 +349,8 @@ impl RegionFlushTask {
                 index_options: self.index_options.clone(),
                 inverted_index_config: self.engine_config.inverted_index.clone(),
                 fulltext_index_config: self.engine_config.fulltext_index.clone(),
+                vector_index_config: self.engine_config.vector_index.clone(),
+                bloom_filter_index_config: self.engine_config.bloom_filter_index.clone(),
             };
             let Some(sst_info) = self
                 .access_layer
@@ -374,6 +376,12 @@ impl RegionFlushTask {
                     if sst_info.index_metadata.fulltext_index.is_available() {
                         indexes.push(IndexType::FulltextIndex);
                     }
+                    if sst_info.index_metadata.vector_index.is_available() {
+                        indexes.push(IndexType::VectorIndex);
+                    }
+                    if sst_info.index_metadata.bloom_filter_index.is_available() {
+                        indexes.push(IndexType::BloomFilterIndex);
+                    }
                     indexes
                 },
                 index_file_size: sst_info.index_metadata.file_size,
@@ -28,6 +28,7 @@ use table::predicate::Predicate;
 use crate::config::MitoConfig;
 use crate::error::Result;
 use crate::flush::WriteBufferManagerRef;
+use crate::memtable::bulk::BulkMemtableBuilder;
 use crate::memtable::key_values::KeyValue;
 pub use crate::memtable::key_values::KeyValues;
 use crate::memtable::partition_tree::{PartitionTreeConfig, PartitionTreeMemtableBuilder};
@@ -278,6 +279,11 @@ impl MemtableBuilderProvider {
                     self.write_buffer_manager.clone(),
                 ))
             }
+            Some(MemtableOptions::Bulk) => Arc::new(BulkMemtableBuilder::new(
+                self.write_buffer_manager.clone(),
+                dedup,
+                merge_mode,
+            )),
             None => self.default_memtable_builder(dedup, merge_mode),
         }
     }
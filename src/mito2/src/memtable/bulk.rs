@@ -14,26 +14,182 @@
 
 //! Memtable implementation for bulk load
 
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use store_api::metadata::RegionMetadataRef;
 use store_api::storage::ColumnId;
 use table::predicate::Predicate;
 
 use crate::error::Result;
-use crate::memtable::bulk::part::BulkPart;
+use crate::flush::WriteBufferManagerRef;
+use crate::memtable::bulk::part::{
+    record_batches_to_batches, BufferedRows, BulkPart, BulkPartEncoder, KeyValueBuffer,
+};
 use crate::memtable::key_values::KeyValue;
 use crate::memtable::{
-    BoxedBatchIterator, KeyValues, Memtable, MemtableId, MemtableRange, MemtableRef, MemtableStats,
+    AllocTracker, BoxedBatchIterator, IterBuilder, KeyValues, Memtable, MemtableBuilder,
+    MemtableId, MemtableRange, MemtableRangeContext, MemtableRef, MemtableStats,
 };
+use crate::read::dedup::LastNonNullIter;
+use crate::read::Batch;
+use crate::region::options::MergeMode;
+use crate::sst::parquet::format::ReadFormat;
 
-#[allow(unused)]
 pub(crate) mod part;
 
+/// Max number of rows written by [Memtable::write] and [Memtable::write_one] that
+/// [BulkMemtable] buffers before encoding them to a part.
+const MAX_BUFFERED_ROWS: usize = 8192;
+
+/// Builder to build [BulkMemtable].
+#[derive(Debug, Default)]
+pub struct BulkMemtableBuilder {
+    write_buffer_manager: Option<WriteBufferManagerRef>,
+    dedup: bool,
+    merge_mode: MergeMode,
+}
+
+impl BulkMemtableBuilder {
+    /// Creates a new builder with specific `write_buffer_manager`.
+    pub fn new(
+        write_buffer_manager: Option<WriteBufferManagerRef>,
+        dedup: bool,
+        merge_mode: MergeMode,
+    ) -> Self {
+        Self {
+            write_buffer_manager,
+            dedup,
+            merge_mode,
+        }
+    }
+}
+
+impl MemtableBuilder for BulkMemtableBuilder {
+    fn build(&self, id: MemtableId, metadata: &RegionMetadataRef) -> MemtableRef {
+        Arc::new(BulkMemtable::new(
+            metadata.clone(),
+            id,
+            self.write_buffer_manager.clone(),
+            self.dedup,
+            self.merge_mode,
+        ))
+    }
+}
+
+/// Memtable that stores rows in sorted and encoded [BulkPart]s.
+///
+/// Rows written by [Memtable::write] and [Memtable::write_one] are buffered and encoded
+/// to parts in batch, so the memtable can accept writes from both the WAL and bulk inserts.
 #[derive(Debug)]
 pub struct BulkMemtable {
     id: MemtableId,
-    parts: RwLock<Vec<BulkPart>>,
+    region_metadata: RegionMetadataRef,
+    encoder: BulkPartEncoder,
+    /// Rows not encoded to parts yet.
+    ///
+    /// Readers lock it before reading `parts` so they won't miss rows moving to `parts`.
+    buffer: Mutex<KeyValueBuffer>,
+    parts: RwLock<Vec<Arc<BulkPart>>>,
+    alloc_tracker: AllocTracker,
+    max_timestamp: AtomicI64,
+    min_timestamp: AtomicI64,
+    dedup: bool,
+    merge_mode: MergeMode,
+    /// Total written rows in memtable. This also includes deleted and duplicated rows.
+    num_rows: AtomicUsize,
+}
+
+impl BulkMemtable {
+    pub fn new(
+        region_metadata: RegionMetadataRef,
+        id: MemtableId,
+        write_buffer_manager: Option<WriteBufferManagerRef>,
+        dedup: bool,
+        merge_mode: MergeMode,
+    ) -> Self {
+        let dedup = if merge_mode == MergeMode::LastNonNull {
+            false
+        } else {
+            dedup
+        };
+        let encoder = BulkPartEncoder::new(region_metadata.clone(), dedup);
+        Self {
+            id,
+            buffer: Mutex::new(encoder.new_buffer()),
+            encoder,
+            region_metadata,
+            parts: RwLock::new(vec![]),
+            alloc_tracker: AllocTracker::new(write_buffer_manager),
+            max_timestamp: AtomicI64::new(i64::MIN),
+            min_timestamp: AtomicI64::new(i64::MAX),
+            dedup,
+            merge_mode,
+            num_rows: Default::default(),
+        }
+    }
+
+    /// Adds the part to the memtable.
+    fn add_part(&self, part: BulkPart) {
+        self.alloc_tracker.on_allocation(part.estimated_size());
+
+        let mut parts = self.parts.write().unwrap();
+        parts.push(Arc::new(part));
+    }
+
+    fn update_stats(&self, num_rows: usize, min_timestamp: i64, max_timestamp: i64) {
+        self.max_timestamp
+            .fetch_max(max_timestamp, Ordering::Relaxed);
+        self.min_timestamp
+            .fetch_min(min_timestamp, Ordering::Relaxed);
+        self.num_rows.fetch_add(num_rows, Ordering::Relaxed);
+    }
+
+    /// Pushes the `key_value` to the `buffer`.
+    fn buffer_key_value(&self, buffer: &mut KeyValueBuffer, key_value: &KeyValue) -> Result<()> {
+        self.encoder.push_key_value(buffer, key_value)?;
+        // Safety: We checked the schema in the write request.
+        let ts = key_value
+            .timestamp()
+            .as_timestamp()
+            .unwrap()
+            .unwrap()
+            .value();
+        self.update_stats(1, ts, ts);
+        Ok(())
+    }
+
+    /// Encodes rows in the `buffer` to a part if the buffer is full.
+    fn maybe_encode_buffer(&self, buffer: &mut KeyValueBuffer) -> Result<()> {
+        if buffer.num_rows() >= MAX_BUFFERED_ROWS {
+            self.encode_buffer(buffer)?;
+        }
+        Ok(())
+    }
+
+    /// Encodes all rows in the `buffer` to a part.
+    fn encode_buffer(&self, buffer: &mut KeyValueBuffer) -> Result<()> {
+        if let Some(part) = self.encoder.encode_buffer(buffer)? {
+            self.add_part(part);
+        }
+        Ok(())
+    }
+
+    /// Returns the parts and a snapshot of buffered rows.
+    fn snapshot(&self) -> (Vec<Arc<BulkPart>>, Option<BufferedRows>) {
+        let buffer = self.buffer.lock().unwrap();
+        let parts = self.parts.read().unwrap().clone();
+        (parts, self.encoder.snapshot_buffer(&buffer))
+    }
+
+    fn maybe_merge_non_null(&self, batches: Vec<Batch>) -> BoxedBatchIterator {
+        let iter = batches.into_iter().map(Ok);
+        if self.merge_mode == MergeMode::LastNonNull {
+            Box::new(LastNonNullIter::new(iter))
+        } else {
+            Box::new(iter)
+        }
+    }
 }
 
 impl Memtable for BulkMemtable {
@@ -41,52 +197,356 @@ impl Memtable for BulkMemtable {
         self.id
     }
 
-    fn write(&self, _kvs: &KeyValues) -> Result<()> {
-        unimplemented!()
+    fn write(&self, kvs: &KeyValues) -> Result<()> {
+        // Large mutations are encoded to parts directly.
+        if kvs.num_rows() >= MAX_BUFFERED_ROWS {
+            if let Some(part) = self
+                .encoder
+                .encode_mutations(std::slice::from_ref(&kvs.mutation))?
+            {
+                self.write_bulk(part)?;
+            }
+            return Ok(());
+        }
+
+        let mut buffer = self.buffer.lock().unwrap();
+        for kv in kvs.iter() {
+            self.buffer_key_value(&mut buffer, &kv)?;
+        }
+        self.maybe_encode_buffer(&mut buffer)
     }
 
-    fn write_one(&self, _key_value: KeyValue) -> Result<()> {
-        unimplemented!()
+    fn write_one(&self, key_value: KeyValue) -> Result<()> {
+        let mut buffer = self.buffer.lock().unwrap();
+        self.buffer_key_value(&mut buffer, &key_value)?;
+        self.maybe_encode_buffer(&mut buffer)
     }
 
     fn write_bulk(&self, fragment: BulkPart) -> Result<()> {
-        let mut parts = self.parts.write().unwrap();
-        parts.push(fragment);
+        let meta = fragment.metadata();
+        self.update_stats(meta.num_rows, meta.min_timestamp, meta.max_timestamp);
+        self.add_part(fragment);
         Ok(())
     }
 
     fn iter(
         &self,
-        _projection: Option<&[ColumnId]>,
+        projection: Option<&[ColumnId]>,
         _predicate: Option<Predicate>,
     ) -> Result<BoxedBatchIterator> {
-        todo!()
+        // Parts may overlap with each other so we merge them into one sorted batch.
+        let (parts, buffered) = self.snapshot();
+        let Some(record_batch) = self.encoder.merge_parts(&parts, buffered.as_ref())? else {
+            return Ok(Box::new(std::iter::empty()));
+        };
+        let batches = record_batches_to_batches(
+            &new_read_format(&self.region_metadata, projection),
+            &[record_batch],
+        )?;
+
+        Ok(self.maybe_merge_non_null(batches))
     }
 
     fn ranges(
         &self,
-        _projection: Option<&[ColumnId]>,
+        projection: Option<&[ColumnId]>,
         _predicate: Option<Predicate>,
     ) -> Vec<MemtableRange> {
-        todo!()
+        // Each part and the buffered rows are read as a range. The reader merges and dedups
+        // rows in these ranges.
+        let projection: Option<Arc<[ColumnId]>> = projection.map(Arc::from);
+        let (parts, buffered) = self.snapshot();
+        parts
+            .into_iter()
+            .map(BulkIterSource::Part)
+            .chain(buffered.map(|rows| BulkIterSource::Buffered(Arc::new(rows))))
+            .map(|source| {
+                let builder = Box::new(BulkIterBuilder {
+                    region_metadata: self.region_metadata.clone(),
+                    source,
+                    projection: projection.clone(),
+                    merge_mode: self.merge_mode,
+                });
+                let context = Arc::new(MemtableRangeContext::new(self.id, builder));
+                MemtableRange::new(context)
+            })
+            .collect()
     }
 
     fn is_empty(&self) -> bool {
-        self.parts.read().unwrap().is_empty()
+        self.buffer.lock().unwrap().num_rows() == 0 && self.parts.read().unwrap().is_empty()
     }
 
     fn freeze(&self) -> Result<()> {
+        self.encode_buffer(&mut self.buffer.lock().unwrap())?;
+        self.alloc_tracker.done_allocating();
+
         Ok(())
     }
 
     fn stats(&self) -> MemtableStats {
-        todo!()
+        let estimated_bytes = self.alloc_tracker.bytes_allocated();
+
+        if self.is_empty() {
+            return MemtableStats {
+                estimated_bytes,
+                time_range: None,
+                num_rows: 0,
+            };
+        }
+        let ts_type = self
+            .region_metadata
+            .time_index_column()
+            .column_schema
+            .data_type
+            .clone()
+            .as_timestamp()
+            .expect("Timestamp column must have timestamp type");
+        let max_timestamp = ts_type.create_timestamp(self.max_timestamp.load(Ordering::Relaxed));
+        let min_timestamp = ts_type.create_timestamp(self.min_timestamp.load(Ordering::Relaxed));
+        MemtableStats {
+            estimated_bytes,
+            time_range: Some((min_timestamp, max_timestamp)),
+            num_rows: self.num_rows.load(Ordering::Relaxed),
+        }
     }
 
-    fn fork(&self, id: MemtableId, _metadata: &RegionMetadataRef) -> MemtableRef {
-        Arc::new(Self {
+    fn fork(&self, id: MemtableId, metadata: &RegionMetadataRef) -> MemtableRef {
+        Arc::new(BulkMemtable::new(
+            metadata.clone(),
             id,
-            parts: RwLock::new(vec![]),
-        })
+            self.alloc_tracker.write_buffer_manager(),
+            self.dedup,
+            self.merge_mode,
+        ))
+    }
+}
+
+/// Returns the [ReadFormat] to read columns in `projection`, `None` means reading all columns.
+fn new_read_format(metadata: &RegionMetadataRef, projection: Option<&[ColumnId]>) -> ReadFormat {
+    match projection {
+        Some(projection) => ReadFormat::new(metadata.clone(), projection.iter().copied()),
+        None => ReadFormat::new(
+            metadata.clone(),
+            metadata.column_metadatas.iter().map(|c| c.column_id),
+        ),
+    }
+}
+
+/// Rows to read by a [BulkIterBuilder].
+enum BulkIterSource {
+    Part(Arc<BulkPart>),
+    Buffered(Arc<BufferedRows>),
+}
+
+/// Builds the iterator to read a [BulkPart] or buffered rows.
+struct BulkIterBuilder {
+    region_metadata: RegionMetadataRef,
+    source: BulkIterSource,
+    projection: Option<Arc<[ColumnId]>>,
+    merge_mode: MergeMode,
+}
+
+impl IterBuilder for BulkIterBuilder {
+    fn build(&self) -> Result<BoxedBatchIterator> {
+        let read_format = new_read_format(&self.region_metadata, self.projection.as_deref());
+        let batches = match &self.source {
+            BulkIterSource::Part(part) => part.read_batches(&read_format)?,
+            BulkIterSource::Buffered(rows) => {
+                record_batches_to_batches(&read_format, &[rows.sort()?])?
+            }
+        };
+        let iter = batches.into_iter().map(Ok);
+
+        if self.merge_mode == MergeMode::LastNonNull {
+            Ok(Box::new(LastNonNullIter::new(iter)))
+        } else {
+            Ok(Box::new(iter))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::v1::OpType;
+    use datatypes::arrow::array::{
+        ArrayRef, Float64Array, Int64Array, StringArray, TimestampMillisecondArray, UInt32Array,
+    };
+    use datatypes::arrow::record_batch::RecordBatch;
+
+    use super::*;
+    use crate::test_util::memtable_util::{
+        build_key_values_with_ts_seq_values, collect_iter_timestamps, metadata_for_test,
+    };
+
+    fn write_rows(memtable: &BulkMemtable, k0: &str, timestamps: &[i64], sequence: u64) {
+        let kvs = build_key_values_with_ts_seq_values(
+            &memtable.region_metadata,
+            k0.to_string(),
+            0,
+            timestamps.iter().copied(),
+            timestamps.iter().map(|v| Some(*v as f64)),
+            sequence,
+        );
+        memtable.write(&kvs).unwrap();
+    }
+
+    #[test]
+    fn test_bulk_memtable_write_iter() {
+        let metadata = metadata_for_test();
+        let memtable = BulkMemtable::new(metadata, 1, None, true, MergeMode::LastRow);
+        assert!(memtable.is_empty());
+        assert!(memtable.stats().time_range().is_none());
+
+        write_rows(&memtable, "b", &[3, 1], 0);
+        write_rows(&memtable, "a", &[2, 1], 2);
+        // Overwrites the row of key `b` at 1.
+        write_rows(&memtable, "b", &[1], 4);
+
+        let stats = memtable.stats();
+        assert_eq!(5, stats.num_rows());
+        let (min, max) = stats.time_range().unwrap();
+        assert_eq!(1, min.value());
+        assert_eq!(3, max.value());
+
+        let iter = memtable.iter(None, None).unwrap();
+        let batches = iter.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(2, batches.len());
+        assert_eq!(vec![1, 2], collect_batch_timestamps(&batches[0]));
+        assert_eq!(vec![1, 3], collect_batch_timestamps(&batches[1]));
+        assert_eq!(
+            vec![4, 0],
+            batches[1].sequences().as_arrow().values().to_vec()
+        );
+
+        // Rows in the buffer are in the same range.
+        let ranges = memtable.ranges(None, None);
+        assert_eq!(1, ranges.len());
+        let mut timestamps = vec![];
+        for range in ranges {
+            timestamps.extend(collect_iter_timestamps(range.build_iter().unwrap()));
+        }
+        timestamps.sort_unstable();
+        assert_eq!(vec![1, 1, 2, 3], timestamps);
+    }
+
+    #[test]
+    fn test_bulk_memtable_encode_buffered_rows() {
+        let metadata = metadata_for_test();
+        let memtable = BulkMemtable::new(metadata, 1, None, true, MergeMode::LastRow);
+
+        // Writes rows in small batches.
+        let batch_size = 64;
+        let num_rows = MAX_BUFFERED_ROWS * 2 + 10;
+        for start in (0..num_rows).step_by(batch_size) {
+            let end = num_rows.min(start + batch_size);
+            let timestamps = (start..end).map(|v| v as i64).collect::<Vec<_>>();
+            write_rows(&memtable, "a", &timestamps, start as u64);
+        }
+        assert_eq!(num_rows, memtable.stats().num_rows());
+        // Buffered rows are encoded into a part once the buffer is full.
+        assert_eq!(2, memtable.parts.read().unwrap().len());
+        let ranges = memtable.ranges(None, None);
+        assert_eq!(3, ranges.len());
+        let num_read = ranges
+            .into_iter()
+            .map(|range| collect_iter_timestamps(range.build_iter().unwrap()).len())
+            .sum::<usize>();
+        assert_eq!(num_rows, num_read);
+
+        // Large mutations are encoded into a part directly.
+        let timestamps = (num_rows..num_rows + MAX_BUFFERED_ROWS)
+            .map(|v| v as i64)
+            .collect::<Vec<_>>();
+        write_rows(&memtable, "b", &timestamps, num_rows as u64);
+        assert_eq!(3, memtable.parts.read().unwrap().len());
+        assert_eq!(4, memtable.ranges(None, None).len());
+
+        memtable.freeze().unwrap();
+        assert_eq!(4, memtable.parts.read().unwrap().len());
+        assert_eq!(4, memtable.ranges(None, None).len());
+        let iter = memtable.iter(None, None).unwrap();
+        let num_read = iter.map(|batch| batch.unwrap().num_rows()).sum::<usize>();
+        assert_eq!(num_rows + MAX_BUFFERED_ROWS, num_read);
+    }
+
+    #[test]
+    fn test_bulk_memtable_write_one_and_projection() {
+        let metadata = metadata_for_test();
+        let memtable = BulkMemtable::new(metadata, 1, None, true, MergeMode::LastRow);
+        let kvs = build_key_values_with_ts_seq_values(
+            &memtable.region_metadata,
+            "a".to_string(),
+            0,
+            [2, 1].into_iter(),
+            [Some(2.0), Some(1.0)].into_iter(),
+            0,
+        );
+        for kv in kvs.iter() {
+            memtable.write_one(kv).unwrap();
+        }
+
+        // Only reads the field `v0`.
+        let iter = memtable.iter(Some(&[3]), None).unwrap();
+        let batches = iter.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(vec![1, 2], collect_batch_timestamps(&batches[0]));
+        assert_eq!(1, batches[0].fields().len());
+        assert_eq!(3, batches[0].fields()[0].column_id);
+    }
+
+    #[test]
+    fn test_bulk_memtable_write_bulk() {
+        let metadata = metadata_for_test();
+        let memtable = BulkMemtable::new(metadata.clone(), 1, None, true, MergeMode::LastRow);
+        let columns: HashMap<_, ArrayRef> = HashMap::from([
+            (
+                "k0",
+                Arc::new(StringArray::from(vec!["b", "a", "a"])) as ArrayRef,
+            ),
+            ("k1", Arc::new(UInt32Array::from(vec![0, 0, 0]))),
+            (
+                "ts",
+                Arc::new(TimestampMillisecondArray::from(vec![1, 2, 1])),
+            ),
+            ("v0", Arc::new(Int64Array::from(vec![1, 2, 3]))),
+            ("v1", Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0]))),
+        ]);
+        let payload = RecordBatch::try_from_iter(metadata.column_metadatas.iter().map(|c| {
+            (
+                c.column_schema.name.clone(),
+                columns[c.column_schema.name.as_str()].clone(),
+            )
+        }))
+        .unwrap();
+        let part = memtable
+            .encoder
+            .encode_record_batch(&payload, 10)
+            .unwrap()
+            .unwrap();
+        memtable.write_bulk(part).unwrap();
+
+        let batches = memtable
+            .iter(None, None)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(2, batches.len());
+        assert_eq!(vec![1, 2], collect_batch_timestamps(&batches[0]));
+        assert_eq!(
+            vec![12, 11],
+            batches[0].sequences().as_arrow().values().to_vec()
+        );
+        assert_eq!(
+            vec![OpType::Put as u8; 2],
+            batches[0].op_types().as_arrow().values().to_vec()
+        );
+        assert_eq!(vec![1], collect_batch_timestamps(&batches[1]));
+    }
+
+    fn collect_batch_timestamps(batch: &Batch) -> Vec<i64> {
+        collect_iter_timestamps(Box::new(std::iter::once(Ok(batch.clone()))))
     }
 }
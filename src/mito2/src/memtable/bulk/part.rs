@@ -13,17 +13,20 @@
 // limitations under the License.
 
 //! Bulk part encoder/decoder.
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
 use std::sync::Arc;
 
-use api::v1::Mutation;
+use api::v1::{Mutation, OpType};
+use bytes::Bytes;
 use common_time::timestamp::TimeUnit;
 use datafusion::arrow::array::{TimestampNanosecondArray, UInt64Builder};
 use datatypes::arrow;
 use datatypes::arrow::array::{
-    Array, ArrayRef, BinaryBuilder, DictionaryArray, RecordBatch, TimestampMicrosecondArray,
-    TimestampMillisecondArray, TimestampSecondArray, UInt32Array, UInt64Array, UInt8Array,
-    UInt8Builder,
+    Array, ArrayRef, BinaryBuilder, BooleanArray, DictionaryArray, RecordBatch,
+    TimestampMicrosecondArray, TimestampMillisecondArray, TimestampSecondArray, UInt32Array,
+    UInt64Array, UInt8Array, UInt8Builder,
 };
 use datatypes::arrow::compute::TakeOptions;
 use datatypes::arrow::datatypes::{DataType as ArrowDataType, SchemaRef};
@@ -31,32 +34,136 @@ use datatypes::arrow_array::BinaryArray;
 use datatypes::data_type::DataType;
 use datatypes::prelude::{MutableVector, ScalarVectorBuilder, Vector};
 use datatypes::types::TimestampType;
+use datatypes::vectors::Helper;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use parquet::arrow::ArrowWriter;
 use parquet::data_type::AsBytes;
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 use store_api::metadata::RegionMetadataRef;
+use store_api::storage::SequenceNumber;
 
-use crate::error::{ComputeArrowSnafu, EncodeMemtableSnafu, NewRecordBatchSnafu, Result};
-use crate::memtable::key_values::KeyValuesRef;
+use crate::error::{
+    ComputeArrowSnafu, ConvertVectorSnafu, EncodeMemtableSnafu, InvalidRequestSnafu,
+    NewRecordBatchSnafu, ReadDataPartSnafu, Result,
+};
+use crate::memtable::key_values::{KeyValue, KeyValuesRef};
 use crate::read::Batch;
 use crate::row_converter::{McmpRowCodec, RowCodec};
-use crate::sst::parquet::format::PrimaryKeyArray;
+use crate::sst::parquet::format::{PrimaryKeyArray, ReadFormat, FIXED_POS_COLUMN_NUM};
 use crate::sst::to_sst_arrow_schema;
 
+/// Rows encoded in the SST format and sorted by primary key, timestamp and sequence.
 #[derive(Debug)]
 pub struct BulkPart {
-    data: Vec<u8>,
+    data: Bytes,
     metadata: BulkPartMeta,
 }
 
 impl BulkPart {
     pub fn new(data: Vec<u8>, metadata: BulkPartMeta) -> Self {
-        Self { data, metadata }
+        Self {
+            data: Bytes::from(data),
+            metadata,
+        }
     }
 
     pub(crate) fn metadata(&self) -> &BulkPartMeta {
         &self.metadata
     }
+
+    /// Returns the size of the encoded data.
+    pub(crate) fn estimated_size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Encodes a record batch in the SST format to a part.
+    ///
+    /// Rows in the `batch` must be sorted.
+    pub(crate) fn try_from_sorted_batch(
+        batch: &RecordBatch,
+        min_timestamp: i64,
+        max_timestamp: i64,
+    ) -> Result<BulkPart> {
+        let mut data = Vec::new();
+        {
+            let mut writer = ArrowWriter::try_new(&mut data, batch.schema(), None)
+                .context(EncodeMemtableSnafu)?;
+            writer.write(batch).context(EncodeMemtableSnafu)?;
+            let _metadata = writer.finish().context(EncodeMemtableSnafu)?;
+        }
+
+        Ok(BulkPart::new(
+            data,
+            BulkPartMeta {
+                num_rows: batch.num_rows(),
+                max_timestamp,
+                min_timestamp,
+            },
+        ))
+    }
+
+    /// Decodes the part to record batches in the SST format.
+    pub(crate) fn read(&self) -> Result<Vec<RecordBatch>> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(self.data.clone())
+            .context(ReadDataPartSnafu)?
+            .build()
+            .context(ReadDataPartSnafu)?;
+        reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context(ComputeArrowSnafu)
+    }
+
+    /// Splits the part by the key of each row's timestamp, returns parts with their keys.
+    ///
+    /// `partition_key` computes the key from the timestamp value in `timestamp_unit`.
+    pub(crate) fn split_by_timestamp<K, F>(
+        &self,
+        timestamp_unit: TimeUnit,
+        mut partition_key: F,
+    ) -> Result<Vec<(K, BulkPart)>>
+    where
+        K: Eq + Hash + Copy,
+        F: FnMut(i64) -> Result<K>,
+    {
+        let record_batches = self.read()?;
+        let Some(first) = record_batches.first() else {
+            return Ok(Vec::new());
+        };
+        let record_batch = arrow::compute::concat_batches(&first.schema(), &record_batches)
+            .context(ComputeArrowSnafu)?;
+        let ts_index = record_batch.num_columns() - FIXED_POS_COLUMN_NUM;
+
+        let keys = timestamp_array_to_iter(timestamp_unit, record_batch.column(ts_index))
+            .map(|ts| partition_key(*ts))
+            .collect::<Result<Vec<_>>>()?;
+        let mut visited = HashSet::new();
+        let mut parts = Vec::new();
+        for key in &keys {
+            if !visited.insert(*key) {
+                continue;
+            }
+
+            // Filtering keeps the order of rows so the new part is still sorted.
+            let mask = BooleanArray::from(keys.iter().map(|k| k == key).collect::<Vec<_>>());
+            let batch = arrow::compute::filter_record_batch(&record_batch, &mask)
+                .context(ComputeArrowSnafu)?;
+            let (min_ts, max_ts) = timestamp_array_to_iter(timestamp_unit, batch.column(ts_index))
+                .fold((i64::MAX, i64::MIN), |(min_ts, max_ts), ts| {
+                    (min_ts.min(*ts), max_ts.max(*ts))
+                });
+            parts.push((
+                *key,
+                BulkPart::try_from_sorted_batch(&batch, min_ts, max_ts)?,
+            ));
+        }
+
+        Ok(parts)
+    }
+
+    /// Decodes the part to [Batch]es with the projection of `read_format`.
+    pub(crate) fn read_batches(&self, read_format: &ReadFormat) -> Result<Vec<Batch>> {
+        record_batches_to_batches(read_format, &self.read()?)
+    }
 }
 
 #[derive(Debug)]
@@ -76,6 +183,7 @@ impl Default for BulkPartMeta {
     }
 }
 
+#[derive(Debug)]
 pub struct BulkPartEncoder {
     metadata: RegionMetadataRef,
     arrow_schema: SchemaRef,
@@ -84,36 +192,225 @@ pub struct BulkPartEncoder {
 }
 
 impl BulkPartEncoder {
-    /// Encodes mutations to a [BulkPart], returns true if encoded data has been written to `dest`.
-    fn encode_mutations(&self, mutations: &[Mutation], dest: &mut BulkPart) -> Result<bool> {
+    pub(crate) fn new(metadata: RegionMetadataRef, dedup: bool) -> BulkPartEncoder {
+        let arrow_schema = to_sst_arrow_schema(&metadata);
+        let pk_encoder = McmpRowCodec::new_with_primary_keys(&metadata);
+        Self {
+            metadata,
+            arrow_schema,
+            pk_encoder,
+            dedup,
+        }
+    }
+
+    /// Encodes mutations to a [BulkPart], returns `None` if mutations don't have any row.
+    pub(crate) fn encode_mutations(&self, mutations: &[Mutation]) -> Result<Option<BulkPart>> {
         let Some((arrow_record_batch, min_ts, max_ts)) =
-            mutations_to_record_batch(mutations, &self.metadata, &self.pk_encoder, false)?
+            mutations_to_record_batch(mutations, &self.metadata, &self.pk_encoder, self.dedup)?
         else {
-            return Ok(false);
+            return Ok(None);
         };
 
-        let arrow_schema = arrow_record_batch.schema();
-        {
-            let mut writer = ArrowWriter::try_new(&mut dest.data, arrow_schema, None)
-                .context(EncodeMemtableSnafu)?;
-            writer
-                .write(&arrow_record_batch)
-                .context(EncodeMemtableSnafu)?;
-            let _metadata = writer.finish().context(EncodeMemtableSnafu)?;
+        BulkPart::try_from_sorted_batch(&arrow_record_batch, min_ts, max_ts).map(Some)
+    }
+
+    /// Returns a new buffer to encode rows in batch.
+    pub(crate) fn new_buffer(&self) -> KeyValueBuffer {
+        KeyValueBuffer::new(&self.metadata, 0)
+    }
+
+    /// Pushes the `key_value` to the `buffer`.
+    pub(crate) fn push_key_value(
+        &self,
+        buffer: &mut KeyValueBuffer,
+        key_value: &KeyValue,
+    ) -> Result<()> {
+        buffer.push(&self.pk_encoder, key_value)
+    }
+
+    /// Encodes rows in the `buffer` to a [BulkPart] and resets the `buffer`, returns `None`
+    /// if the `buffer` is empty.
+    pub(crate) fn encode_buffer(&self, buffer: &mut KeyValueBuffer) -> Result<Option<BulkPart>> {
+        let Some((arrow_record_batch, min_ts, max_ts)) =
+            buffer.finish(self.arrow_schema.clone(), self.dedup)?
+        else {
+            return Ok(None);
+        };
+
+        BulkPart::try_from_sorted_batch(&arrow_record_batch, min_ts, max_ts).map(Some)
+    }
+
+    /// Takes a snapshot of rows in the `buffer` without resetting it, returns `None` if
+    /// the `buffer` is empty.
+    pub(crate) fn snapshot_buffer(&self, buffer: &KeyValueBuffer) -> Option<BufferedRows> {
+        if buffer.num_rows() == 0 {
+            return None;
+        }
+
+        Some(BufferedRows {
+            encoded_primary_keys: buffer.pk_builder.finish_cloned(),
+            timestamp_unit: buffer.timestamp_unit,
+            timestamp: buffer.ts_vector.to_vector_cloned().to_arrow_array(),
+            sequence: buffer.sequence_builder.finish_cloned(),
+            op_type: buffer.op_type_builder.finish_cloned(),
+            fields: buffer
+                .field_builders
+                .iter()
+                .map(|f| f.to_vector_cloned().to_arrow_array())
+                .collect(),
+            dedup: self.dedup,
+            arrow_schema: self.arrow_schema.clone(),
+        })
+    }
+
+    /// Encodes rows in the `payload` to a [BulkPart], returns `None` if the `payload` is empty.
+    ///
+    /// The `payload` must contain all columns of the region. Rows are put with sequences
+    /// starting from `sequence`.
+    pub(crate) fn encode_record_batch(
+        &self,
+        payload: &RecordBatch,
+        sequence: SequenceNumber,
+    ) -> Result<Option<BulkPart>> {
+        let num_rows = payload.num_rows();
+        if num_rows == 0 {
+            return Ok(None);
+        }
+
+        let column_by_name = |name: &str| {
+            payload
+                .column_by_name(name)
+                .cloned()
+                .with_context(|| InvalidRequestSnafu {
+                    region_id: self.metadata.region_id,
+                    reason: format!("column {name} is missing in the bulk payload"),
+                })
+        };
+
+        let pk_vectors = self
+            .metadata
+            .primary_key_columns()
+            .map(|column| {
+                let array = column_by_name(&column.column_schema.name)?;
+                Helper::try_into_vector(array).context(ConvertVectorSnafu)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut pk_builder = BinaryBuilder::with_capacity(num_rows, 0);
+        let mut pk_buffer = vec![];
+        for row in 0..num_rows {
+            pk_buffer.clear();
+            self.pk_encoder.encode_to_vec(
+                pk_vectors.iter().map(|vector| vector.get_ref(row)),
+                &mut pk_buffer,
+            )?;
+            pk_builder.append_value(pk_buffer.as_bytes());
+        }
+
+        let time_index = self.metadata.time_index_column();
+        let fields = self
+            .metadata
+            .field_columns()
+            .map(|column| column_by_name(&column.column_schema.name))
+            .collect::<Result<Vec<_>>>()?;
+        let sorter = ArraysSorter {
+            encoded_primary_keys: pk_builder.finish(),
+            timestamp_unit: timestamp_unit(&self.metadata),
+            timestamp: column_by_name(&time_index.column_schema.name)?,
+            sequence: UInt64Array::from_iter_values(sequence..sequence + num_rows as u64),
+            op_type: UInt8Array::from_value(OpType::Put as u8, num_rows),
+            fields: fields.into_iter(),
+            dedup: self.dedup,
+            arrow_schema: self.arrow_schema.clone(),
+        };
+        let (arrow_record_batch, min_ts, max_ts) = sorter.sort()?;
+
+        BulkPart::try_from_sorted_batch(&arrow_record_batch, min_ts, max_ts).map(Some)
+    }
+
+    /// Merges all rows in `parts` and the `buffered` rows into one sorted record batch
+    /// in the SST format.
+    ///
+    /// Returns `None` if they don't have any row.
+    pub(crate) fn merge_parts(
+        &self,
+        parts: &[Arc<BulkPart>],
+        buffered: Option<&BufferedRows>,
+    ) -> Result<Option<RecordBatch>> {
+        let mut record_batches = Vec::with_capacity(parts.len() + 1);
+        for part in parts {
+            record_batches.extend(part.read()?);
+        }
+        if let Some(buffered) = buffered {
+            record_batches.push(buffered.sort()?);
+        }
+        let record_batch = arrow::compute::concat_batches(&self.arrow_schema, &record_batches)
+            .context(ComputeArrowSnafu)?;
+        if record_batch.num_rows() == 0 {
+            return Ok(None);
         }
 
-        dest.metadata = BulkPartMeta {
-            num_rows: arrow_record_batch.num_rows(),
-            max_timestamp: max_ts,
-            min_timestamp: min_ts,
+        // Dictionaries of primary keys in different parts are different, so we sort
+        // the encoded primary keys and build a new dictionary.
+        let columns = record_batch.columns();
+        let num_fields = columns.len() - FIXED_POS_COLUMN_NUM;
+        let encoded_primary_keys =
+            arrow::compute::cast(&columns[num_fields + 1], &ArrowDataType::Binary)
+                .context(ComputeArrowSnafu)?;
+        // Safety: the record batch is in the SST format.
+        let sorter = ArraysSorter {
+            encoded_primary_keys: encoded_primary_keys
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .unwrap()
+                .clone(),
+            timestamp_unit: timestamp_unit(&self.metadata),
+            timestamp: columns[num_fields].clone(),
+            sequence: columns[num_fields + 2]
+                .as_any()
+                .downcast_ref::<UInt64Array>()
+                .unwrap()
+                .clone(),
+            op_type: columns[num_fields + 3]
+                .as_any()
+                .downcast_ref::<UInt8Array>()
+                .unwrap()
+                .clone(),
+            fields: columns[..num_fields].iter().cloned(),
+            dedup: self.dedup,
+            arrow_schema: self.arrow_schema.clone(),
         };
-        Ok(true)
+
+        sorter.sort().map(|(batch, _, _)| Some(batch))
     }
+}
 
-    /// Decodes [BulkPart] to [Batch]es.
-    fn decode_to_batches(&self, _part: &BulkPart, _dest: &mut VecDeque<Batch>) -> Result<()> {
-        todo!()
+/// Converts record batches in the SST format to [Batch]es with the projection of `read_format`.
+pub(crate) fn record_batches_to_batches(
+    read_format: &ReadFormat,
+    record_batches: &[RecordBatch],
+) -> Result<Vec<Batch>> {
+    let mut batches = Vec::new();
+    let mut buffer = VecDeque::new();
+    for record_batch in record_batches {
+        let projected = record_batch
+            .project(read_format.projection_indices())
+            .context(ComputeArrowSnafu)?;
+        read_format.convert_record_batch(&projected, &mut buffer)?;
+        batches.extend(buffer.drain(..));
     }
+    Ok(batches)
+}
+
+/// Returns the time unit of the time index.
+fn timestamp_unit(metadata: &RegionMetadataRef) -> TimeUnit {
+    // safety: timestamp column must be valid.
+    metadata
+        .time_index_column()
+        .column_schema
+        .data_type
+        .as_timestamp()
+        .unwrap()
+        .unit()
 }
 
 /// Converts mutations to record batches.
@@ -132,63 +429,136 @@ fn mutations_to_record_batch(
         return Ok(None);
     }
 
-    let mut pk_builder = BinaryBuilder::with_capacity(total_rows, 0);
-
-    let mut ts_vector: Box<dyn MutableVector> = metadata
-        .time_index_column()
-        .column_schema
-        .data_type
-        .create_mutable_vector(total_rows);
-    let mut sequence_builder = UInt64Builder::with_capacity(total_rows);
-    let mut op_type_builder = UInt8Builder::with_capacity(total_rows);
-
-    let mut field_builders: Vec<Box<dyn MutableVector>> = metadata
-        .field_columns()
-        .map(|f| f.column_schema.data_type.create_mutable_vector(total_rows))
-        .collect();
-
-    let mut pk_buffer = vec![];
+    let mut buffer = KeyValueBuffer::new(metadata, total_rows);
     for m in mutations {
         let Some(key_values) = KeyValuesRef::new(metadata, m) else {
             continue;
         };
 
         for row in key_values.iter() {
-            pk_buffer.clear();
-            pk_encoder.encode_to_vec(row.primary_keys(), &mut pk_buffer)?;
-            pk_builder.append_value(pk_buffer.as_bytes());
-            ts_vector.push_value_ref(row.timestamp());
-            sequence_builder.append_value(row.sequence());
-            op_type_builder.append_value(row.op_type() as u8);
-            for (builder, field) in field_builders.iter_mut().zip(row.fields()) {
-                builder.push_value_ref(field);
-            }
+            buffer.push(pk_encoder, &row)?;
         }
     }
 
-    let arrow_schema = to_sst_arrow_schema(metadata);
-    // safety: timestamp column must be valid, and values must not be None.
-    let timestamp_unit = metadata
-        .time_index_column()
-        .column_schema
-        .data_type
-        .as_timestamp()
-        .unwrap()
-        .unit();
-    let sorter = ArraysSorter {
-        encoded_primary_keys: pk_builder.finish(),
-        timestamp_unit,
-        timestamp: ts_vector.to_vector().to_arrow_array(),
-        sequence: sequence_builder.finish(),
-        op_type: op_type_builder.finish(),
-        fields: field_builders
-            .iter_mut()
-            .map(|f| f.to_vector().to_arrow_array()),
-        dedup,
-        arrow_schema,
-    };
-
-    sorter.sort().map(Some)
+    buffer.finish(to_sst_arrow_schema(metadata), dedup)
+}
+
+/// Buffers key values in columns.
+pub(crate) struct KeyValueBuffer {
+    timestamp_unit: TimeUnit,
+    pk_buffer: Vec<u8>,
+    pk_builder: BinaryBuilder,
+    ts_vector: Box<dyn MutableVector>,
+    sequence_builder: UInt64Builder,
+    op_type_builder: UInt8Builder,
+    field_builders: Vec<Box<dyn MutableVector>>,
+}
+
+impl KeyValueBuffer {
+    fn new(metadata: &RegionMetadataRef, capacity: usize) -> KeyValueBuffer {
+        KeyValueBuffer {
+            timestamp_unit: timestamp_unit(metadata),
+            pk_buffer: vec![],
+            pk_builder: BinaryBuilder::with_capacity(capacity, 0),
+            ts_vector: metadata
+                .time_index_column()
+                .column_schema
+                .data_type
+                .create_mutable_vector(capacity),
+            sequence_builder: UInt64Builder::with_capacity(capacity),
+            op_type_builder: UInt8Builder::with_capacity(capacity),
+            field_builders: metadata
+                .field_columns()
+                .map(|f| f.column_schema.data_type.create_mutable_vector(capacity))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of buffered rows.
+    pub(crate) fn num_rows(&self) -> usize {
+        self.ts_vector.len()
+    }
+
+    fn push(&mut self, pk_encoder: &McmpRowCodec, row: &KeyValue) -> Result<()> {
+        self.pk_buffer.clear();
+        pk_encoder.encode_to_vec(row.primary_keys(), &mut self.pk_buffer)?;
+        self.pk_builder.append_value(self.pk_buffer.as_bytes());
+        self.ts_vector.push_value_ref(row.timestamp());
+        self.sequence_builder.append_value(row.sequence());
+        self.op_type_builder.append_value(row.op_type() as u8);
+        for (builder, field) in self.field_builders.iter_mut().zip(row.fields()) {
+            builder.push_value_ref(field);
+        }
+        Ok(())
+    }
+
+    /// Sorts buffered rows and converts them to a record batch, returns `None` if
+    /// the buffer is empty.
+    ///
+    /// It resets the buffer.
+    fn finish(
+        &mut self,
+        arrow_schema: SchemaRef,
+        dedup: bool,
+    ) -> Result<Option<(RecordBatch, i64, i64)>> {
+        if self.ts_vector.is_empty() {
+            return Ok(None);
+        }
+
+        let sorter = ArraysSorter {
+            encoded_primary_keys: self.pk_builder.finish(),
+            timestamp_unit: self.timestamp_unit,
+            timestamp: self.ts_vector.to_vector().to_arrow_array(),
+            sequence: self.sequence_builder.finish(),
+            op_type: self.op_type_builder.finish(),
+            fields: self
+                .field_builders
+                .iter_mut()
+                .map(|f| f.to_vector().to_arrow_array()),
+            dedup,
+            arrow_schema,
+        };
+
+        sorter.sort().map(Some)
+    }
+}
+
+impl fmt::Debug for KeyValueBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyValueBuffer")
+            .field("num_rows", &self.num_rows())
+            .finish()
+    }
+}
+
+/// A snapshot of rows in a [KeyValueBuffer].
+#[derive(Debug)]
+pub(crate) struct BufferedRows {
+    encoded_primary_keys: BinaryArray,
+    timestamp_unit: TimeUnit,
+    timestamp: ArrayRef,
+    sequence: UInt64Array,
+    op_type: UInt8Array,
+    fields: Vec<ArrayRef>,
+    dedup: bool,
+    arrow_schema: SchemaRef,
+}
+
+impl BufferedRows {
+    /// Sorts rows and converts them to a record batch in the SST format.
+    pub(crate) fn sort(&self) -> Result<RecordBatch> {
+        let sorter = ArraysSorter {
+            encoded_primary_keys: self.encoded_primary_keys.clone(),
+            timestamp_unit: self.timestamp_unit,
+            timestamp: self.timestamp.clone(),
+            sequence: self.sequence.clone(),
+            op_type: self.op_type.clone(),
+            fields: self.fields.iter().cloned(),
+            dedup: self.dedup,
+            arrow_schema: self.arrow_schema.clone(),
+        };
+        sorter.sort().map(|(batch, _, _)| batch)
+    }
 }
 
 struct ArraysSorter<I> {
@@ -29,7 +29,7 @@ use store_api::metadata::RegionMetadataRef;
 use crate::error::{InvalidRequestSnafu, Result};
 use crate::memtable::key_values::KeyValue;
 use crate::memtable::version::SmallMemtableVec;
-use crate::memtable::{KeyValues, MemtableBuilderRef, MemtableId, MemtableRef};
+use crate::memtable::{BulkPart, KeyValues, MemtableBuilderRef, MemtableId, MemtableRef};
 
 /// A partition holds rows with timestamps between `[min, max)`.
 #[derive(Debug, Clone)]
@@ -294,48 +294,101 @@ impl TimePartitions {
         // the same partition.
         let mut inner = self.inner.lock().unwrap();
         for (part_start, key_values) in missing_parts {
-            let part_pos = match inner
-                .parts
-                .iter()
-                .position(|part| part.time_range.unwrap().min_timestamp == part_start)
-            {
-                Some(pos) => pos,
-                None => {
-                    let range = PartTimeRange::from_start_duration(part_start, part_duration)
-                        .with_context(|| InvalidRequestSnafu {
-                            region_id: self.metadata.region_id,
-                            reason: format!(
-                                "Partition time range for {part_start:?} is out of bound, bucket size: {part_duration:?}",
-                            ),
-                        })?;
-                    let memtable = self
-                        .builder
-                        .build(inner.alloc_memtable_id(), &self.metadata);
-                    debug!(
-                        "Create time partition {:?} for region {}, duration: {:?}, memtable_id: {}, parts_total: {}",
-                        range,
-                        self.metadata.region_id,
-                        part_duration,
-                        memtable.id(),
-                        inner.parts.len() + 1
-                    );
-                    let pos = inner.parts.len();
-                    inner.parts.push(TimePartition {
-                        memtable,
-                        time_range: Some(range),
-                    });
-                    pos
-                }
-            };
-
-            let memtable = &inner.parts[part_pos].memtable;
+            let partition = self.get_or_create_partition(&mut inner, part_start, part_duration)?;
             for kv in key_values {
-                memtable.write_one(kv)?;
+                partition.memtable.write_one(kv)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a bulk part to memtables.
+    ///
+    /// It splits the part and creates new partitions if necessary.
+    pub fn write_bulk(&self, part: BulkPart) -> Result<()> {
+        // Safety: The time index must be a timestamp column.
+        let time_type = self
+            .metadata
+            .time_index_column()
+            .column_schema
+            .data_type
+            .as_timestamp()
+            .unwrap();
+        let min_ts = time_type.create_timestamp(part.metadata().min_timestamp);
+        let max_ts = time_type.create_timestamp(part.metadata().max_timestamp);
+
+        // Checks whether all rows belongs to a single part.
+        let parts = self.list_partitions();
+        for partition in parts.iter().rev() {
+            if partition.contains_timestamp(min_ts) && partition.contains_timestamp(max_ts) {
+                return partition.memtable.write_bulk(part);
             }
         }
 
+        // Slow path: We have to split the bulk part by partitions.
+        // If part duration is `None` then there is always one partition and all rows
+        // will be put in that partition.
+        debug_assert!(self.part_duration.is_some());
+        let part_duration = self.part_duration.unwrap();
+        let split_parts = part.split_by_timestamp(time_type.unit(), |value| {
+            let ts = time_type.create_timestamp(value);
+            partition_start_timestamp(ts, part_duration).with_context(|| InvalidRequestSnafu {
+                region_id: self.metadata.region_id,
+                reason: format!("timestamp {ts:?} and bucket {part_duration:?} are out of range"),
+            })
+        })?;
+
+        let mut inner = self.inner.lock().unwrap();
+        for (part_start, part) in split_parts {
+            let partition = self.get_or_create_partition(&mut inner, part_start, part_duration)?;
+            partition.memtable.write_bulk(part)?;
+        }
+
         Ok(())
     }
+
+    /// Returns the partition starts from `part_start`, creates the partition if it
+    /// doesn't exist.
+    fn get_or_create_partition(
+        &self,
+        inner: &mut PartitionsInner,
+        part_start: Timestamp,
+        part_duration: Duration,
+    ) -> Result<TimePartition> {
+        if let Some(partition) = inner
+            .parts
+            .iter()
+            .find(|part| part.time_range.unwrap().min_timestamp == part_start)
+        {
+            return Ok(partition.clone());
+        }
+
+        let range = PartTimeRange::from_start_duration(part_start, part_duration)
+            .with_context(|| InvalidRequestSnafu {
+                region_id: self.metadata.region_id,
+                reason: format!(
+                    "Partition time range for {part_start:?} is out of bound, bucket size: {part_duration:?}",
+                ),
+            })?;
+        let memtable = self
+            .builder
+            .build(inner.alloc_memtable_id(), &self.metadata);
+        debug!(
+            "Create time partition {:?} for region {}, duration: {:?}, memtable_id: {}, parts_total: {}",
+            range,
+            self.metadata.region_id,
+            part_duration,
+            memtable.id(),
+            inner.parts.len() + 1
+        );
+        let partition = TimePartition {
+            memtable,
+            time_range: Some(range),
+        };
+        inner.parts.push(partition.clone());
+        Ok(partition)
+    }
 }
 
 /// Computes the start timestamp of the partition for `ts`.
@@ -415,6 +468,8 @@ struct PartitionToWrite<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memtable::bulk::part::BulkPartEncoder;
+    use crate::memtable::bulk::BulkMemtableBuilder;
     use crate::memtable::partition_tree::PartitionTreeMemtableBuilder;
     use crate::test_util::memtable_util::{self, collect_iter_timestamps};
 
@@ -548,4 +603,54 @@ mod tests {
             parts[1].time_range.unwrap().max_timestamp
         );
     }
+
+    #[test]
+    fn test_write_bulk_multi_parts() {
+        let metadata = memtable_util::metadata_for_test();
+        let builder = Arc::new(BulkMemtableBuilder::default());
+        let partitions =
+            TimePartitions::new(metadata.clone(), builder, 0, Some(Duration::from_secs(5)));
+        let encoder = BulkPartEncoder::new(metadata.clone(), true);
+
+        let kvs = memtable_util::build_key_values(
+            &metadata,
+            "hello".to_string(),
+            0,
+            &[2000, 7000, 1000, 12000],
+            0, // sequence 0, 1, 2, 3
+        );
+        let part = encoder
+            .encode_mutations(std::slice::from_ref(&kvs.mutation))
+            .unwrap()
+            .unwrap();
+        // It splits the part into 3 partitions.
+        partitions.write_bulk(part).unwrap();
+        assert_eq!(3, partitions.num_partitions());
+
+        let kvs = memtable_util::build_key_values(
+            &metadata,
+            "hello".to_string(),
+            0,
+            &[3000, 4000],
+            4, // sequence 4, 5
+        );
+        let part = encoder
+            .encode_mutations(std::slice::from_ref(&kvs.mutation))
+            .unwrap()
+            .unwrap();
+        // Writes to the existing partition.
+        partitions.write_bulk(part).unwrap();
+        assert_eq!(3, partitions.num_partitions());
+
+        let mut parts = partitions.list_partitions();
+        parts.sort_unstable_by_key(|part| part.time_range.unwrap().min_timestamp);
+        let timestamps: Vec<_> = parts
+            .iter()
+            .map(|part| collect_iter_timestamps(part.memtable.iter(None, None).unwrap()))
+            .collect();
+        assert_eq!(
+            vec![vec![1000, 2000, 3000, 4000], vec![7000], vec![12000]],
+            timestamps
+        );
+    }
 }
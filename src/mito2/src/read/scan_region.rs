@@ -34,6 +34,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use crate::access_layer::AccessLayerRef;
 use crate::cache::file_cache::FileCacheRef;
 use crate::cache::CacheManagerRef;
+use crate::config::VectorIndexConfig;
 use crate::error::Result;
 use crate::memtable::{MemtableRange, MemtableRef};
 use crate::metrics::READ_SST_COUNT;
@@ -45,10 +46,13 @@ use crate::read::{Batch, Source};
 use crate::region::options::MergeMode;
 use crate::region::version::VersionRef;
 use crate::sst::file::{overlaps, FileHandle, FileMeta};
+use crate::sst::index::bloom_filter::applier::builder::BloomFilterIndexApplierBuilder;
+use crate::sst::index::bloom_filter::applier::BloomFilterIndexApplierRef;
 use crate::sst::index::fulltext_index::applier::builder::FulltextIndexApplierBuilder;
 use crate::sst::index::fulltext_index::applier::FulltextIndexApplierRef;
 use crate::sst::index::inverted_index::applier::builder::InvertedIndexApplierBuilder;
 use crate::sst::index::inverted_index::applier::InvertedIndexApplierRef;
+use crate::sst::index::vector_index::applier::{VectorIndexApplier, VectorIndexApplierRef};
 use crate::sst::parquet::file_range::FileRange;
 use crate::sst::parquet::reader::ReaderMetrics;
 
@@ -170,6 +174,12 @@ pub(crate) struct ScanRegion {
     ignore_inverted_index: bool,
     /// Whether to ignore fulltext index.
     ignore_fulltext_index: bool,
+    /// Whether to ignore vector index.
+    ignore_vector_index: bool,
+    /// Whether to ignore bloom filter index.
+    ignore_bloom_filter_index: bool,
+    /// Number of candidates to consider when searching the vector index.
+    vector_index_expansion_search: usize,
     /// Start time of the scan task.
     start_time: Option<Instant>,
 }
@@ -190,6 +200,9 @@ impl ScanRegion {
             parallelism: ScanParallism::default(),
             ignore_inverted_index: false,
             ignore_fulltext_index: false,
+            ignore_vector_index: false,
+            ignore_bloom_filter_index: false,
+            vector_index_expansion_search: VectorIndexConfig::default().expansion_search,
             start_time: None,
         }
     }
@@ -215,6 +228,27 @@ impl ScanRegion {
         self
     }
 
+    /// Sets whether to ignore vector index.
+    #[must_use]
+    pub(crate) fn with_ignore_vector_index(mut self, ignore: bool) -> Self {
+        self.ignore_vector_index = ignore;
+        self
+    }
+
+    /// Sets whether to ignore bloom filter index.
+    #[must_use]
+    pub(crate) fn with_ignore_bloom_filter_index(mut self, ignore: bool) -> Self {
+        self.ignore_bloom_filter_index = ignore;
+        self
+    }
+
+    /// Sets the number of candidates to consider when searching the vector index.
+    #[must_use]
+    pub(crate) fn with_vector_index_expansion_search(mut self, expansion_search: usize) -> Self {
+        self.vector_index_expansion_search = expansion_search;
+        self
+    }
+
     #[must_use]
     pub(crate) fn with_start_time(mut self, now: Instant) -> Self {
         self.start_time = Some(now);
@@ -294,6 +328,8 @@ impl ScanRegion {
 
         let inverted_index_applier = self.build_invereted_index_applier();
         let fulltext_index_applier = self.build_fulltext_index_applier();
+        let vector_index_applier = self.build_vector_index_applier(&time_range);
+        let bloom_filter_index_applier = self.build_bloom_filter_index_applier();
         let predicate = Predicate::new(self.request.filters.clone());
         // The mapper always computes projected column ids as the schema of SSTs may change.
         let mapper = match &self.request.projection {
@@ -309,6 +345,8 @@ impl ScanRegion {
             .with_cache(self.cache_manager)
             .with_inverted_index_applier(inverted_index_applier)
             .with_fulltext_index_applier(fulltext_index_applier)
+            .with_vector_index_applier(vector_index_applier)
+            .with_bloom_filter_index_applier(bloom_filter_index_applier)
             .with_parallelism(self.parallelism)
             .with_start_time(self.start_time)
             .with_append_mode(self.version.options.append_mode)
@@ -394,6 +432,60 @@ impl ScanRegion {
         .flatten()
         .map(Arc::new)
     }
+
+    /// Use the latest schema to build the bloom filter index applier.
+    fn build_bloom_filter_index_applier(&self) -> Option<BloomFilterIndexApplierRef> {
+        if self.ignore_bloom_filter_index {
+            return None;
+        }
+
+        BloomFilterIndexApplierBuilder::new(
+            self.access_layer.region_dir().to_string(),
+            self.access_layer.object_store().clone(),
+            self.access_layer.puffin_manager_factory().clone(),
+            self.version.metadata.as_ref(),
+        )
+        .build(&self.request.filters)
+        .inspect_err(|err| warn!(err; "Failed to build bloom filter index applier"))
+        .ok()
+        .flatten()
+        .map(Arc::new)
+    }
+
+    /// Use the latest schema to build the vector index applier.
+    ///
+    /// The index only finds the nearest rows in each SST, so it's only applied when
+    /// no rows are filtered out, and only to append mode regions as the nearest rows
+    /// might be overwritten by rows in other SSTs otherwise.
+    fn build_vector_index_applier(
+        &self,
+        time_range: &TimestampRange,
+    ) -> Option<VectorIndexApplierRef> {
+        if self.ignore_vector_index || !self.version.options.append_mode {
+            return None;
+        }
+        let search = self.request.vector_search.as_ref()?;
+        if !self.request.filters.is_empty() || time_range != &TimestampRange::min_to_max() {
+            return None;
+        }
+
+        let column = self.version.metadata.column_by_name(&search.column_name)?;
+        let vector_type = column.column_schema.data_type.as_vector()?;
+        if vector_type.dim as usize != search.query.len() {
+            return None;
+        }
+
+        Some(Arc::new(VectorIndexApplier::new(
+            self.access_layer.region_dir().to_string(),
+            self.access_layer.object_store().clone(),
+            self.access_layer.puffin_manager_factory().clone(),
+            column.column_id,
+            search.query.clone(),
+            search.metric,
+            search.k,
+            self.vector_index_expansion_search,
+        )))
+    }
 }
 
 /// Config for parallel scan.
@@ -439,6 +531,8 @@ pub(crate) struct ScanInput {
     /// Index appliers.
     inverted_index_applier: Option<InvertedIndexApplierRef>,
     fulltext_index_applier: Option<FulltextIndexApplierRef>,
+    vector_index_applier: Option<VectorIndexApplierRef>,
+    bloom_filter_index_applier: Option<BloomFilterIndexApplierRef>,
     /// Start time of the query.
     pub(crate) query_start: Option<Instant>,
     /// The region is using append mode.
@@ -467,6 +561,8 @@ impl ScanInput {
             parallelism: ScanParallism::default(),
             inverted_index_applier: None,
             fulltext_index_applier: None,
+            vector_index_applier: None,
+            bloom_filter_index_applier: None,
             query_start: None,
             append_mode: false,
             filter_deleted: true,
@@ -544,6 +640,26 @@ impl ScanInput {
         self
     }
 
+    /// Sets vector index applier.
+    #[must_use]
+    pub(crate) fn with_vector_index_applier(
+        mut self,
+        applier: Option<VectorIndexApplierRef>,
+    ) -> Self {
+        self.vector_index_applier = applier;
+        self
+    }
+
+    /// Sets bloom filter index applier.
+    #[must_use]
+    pub(crate) fn with_bloom_filter_index_applier(
+        mut self,
+        applier: Option<BloomFilterIndexApplierRef>,
+    ) -> Self {
+        self.bloom_filter_index_applier = applier;
+        self
+    }
+
     /// Sets start time of the query.
     #[must_use]
     pub(crate) fn with_start_time(mut self, now: Option<Instant>) -> Self {
@@ -621,6 +737,8 @@ impl ScanInput {
                 .cache(self.cache_manager.clone())
                 .inverted_index_applier(self.inverted_index_applier.clone())
                 .fulltext_index_applier(self.fulltext_index_applier.clone())
+                .vector_index_applier(self.vector_index_applier.clone())
+                .bloom_filter_index_applier(self.bloom_filter_index_applier.clone())
                 .expected_metadata(Some(self.mapper.metadata().clone()))
                 .build_reader_input(&mut reader_metrics)
                 .await;
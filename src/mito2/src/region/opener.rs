@@ -284,6 +284,7 @@ impl RegionOpener {
         match wal_options {
             WalOptions::RaftEngine => Provider::raft_engine_provider(self.region_id.as_u64()),
             WalOptions::Kafka(options) => Provider::kafka_provider(options.topic.to_string()),
+            WalOptions::ObjectStore => Provider::object_store_provider(self.region_id),
         }
     }
 
@@ -520,7 +521,12 @@ where
                 .as_ref()
                 .map(|rows| rows.rows.len())
                 .unwrap_or(0);
-            region_write_ctx.push_mutation(mutation.op_type, mutation.rows, OptionOutputTx::none());
+            region_write_ctx.push_mutation(
+                mutation.op_type,
+                mutation.rows,
+                None,
+                OptionOutputTx::none(),
+            );
         }
 
         // set next_entry_id and write to memtable.
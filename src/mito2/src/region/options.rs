@@ -85,6 +85,17 @@ impl RegionOptions {
                 }
             );
         }
+        if let CompactionOptions::Stcs(stcs) = &self.compaction {
+            ensure!(
+                stcs.min_threshold >= 2 && stcs.min_threshold <= stcs.max_threshold,
+                InvalidRegionOptionsSnafu {
+                    reason: format!(
+                        "invalid stcs thresholds, min: {}, max: {}",
+                        stcs.min_threshold, stcs.max_threshold
+                    ),
+                }
+            );
+        }
         Ok(())
     }
 
@@ -156,24 +167,30 @@ pub enum CompactionOptions {
     /// Time window compaction strategy.
     #[serde(with = "prefix_twcs")]
     Twcs(TwcsOptions),
+    /// Size-tiered compaction strategy within time windows.
+    #[serde(with = "prefix_stcs")]
+    Stcs(StcsOptions),
 }
 
 impl CompactionOptions {
     pub(crate) fn time_window(&self) -> Option<Duration> {
         match self {
             CompactionOptions::Twcs(opts) => opts.time_window,
+            CompactionOptions::Stcs(opts) => opts.time_window,
         }
     }
 
     pub(crate) fn remote_compaction(&self) -> bool {
         match self {
             CompactionOptions::Twcs(opts) => opts.remote_compaction,
+            CompactionOptions::Stcs(opts) => opts.remote_compaction,
         }
     }
 
     pub(crate) fn fallback_to_local(&self) -> bool {
         match self {
             CompactionOptions::Twcs(opts) => opts.fallback_to_local,
+            CompactionOptions::Stcs(opts) => opts.fallback_to_local,
         }
     }
 }
@@ -242,6 +259,59 @@ impl Default for TwcsOptions {
     }
 }
 
+/// Size-tiered compaction options.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StcsOptions {
+    /// Min num of similar sized files in a time window to trigger a compaction.
+    #[serde_as(as = "DisplayFromStr")]
+    pub min_threshold: usize,
+    /// Max num of files to compact in one compaction.
+    #[serde_as(as = "DisplayFromStr")]
+    pub max_threshold: usize,
+    /// Files smaller than this size are put into the same tier.
+    pub min_sstable_size: ReadableSize,
+    /// Compaction time window defined when creating tables.
+    #[serde(with = "humantime_serde")]
+    pub time_window: Option<Duration>,
+    /// Whether to use remote compaction.
+    #[serde_as(as = "DisplayFromStr")]
+    pub remote_compaction: bool,
+    /// Whether to fall back to local compaction if remote compaction fails.
+    #[serde_as(as = "DisplayFromStr")]
+    pub fallback_to_local: bool,
+}
+
+with_prefix!(prefix_stcs "compaction.stcs.");
+
+impl StcsOptions {
+    /// Returns time window in second resolution.
+    pub fn time_window_seconds(&self) -> Option<i64> {
+        self.time_window.and_then(|window| {
+            let window_secs = window.as_secs();
+            if window_secs == 0 {
+                None
+            } else {
+                window_secs.try_into().ok()
+            }
+        })
+    }
+}
+
+impl Default for StcsOptions {
+    fn default() -> Self {
+        Self {
+            min_threshold: 4,
+            max_threshold: 32,
+            min_sstable_size: ReadableSize::mb(50),
+            time_window: None,
+            remote_compaction: false,
+            fallback_to_local: true,
+        }
+    }
+}
+
 /// We need to define a new struct without enum fields as `#[serde(default)]` does not
 /// support external tagging.
 #[serde_as]
@@ -313,6 +383,10 @@ pub enum MemtableOptions {
     TimeSeries,
     #[serde(with = "prefix_partition_tree")]
     PartitionTree(PartitionTreeOptions),
+    /// Memtable that stores rows in columnar parts, which accepts bulk inserts.
+    ///
+    /// Bulk inserts are still written to the WAL as rows.
+    Bulk,
 }
 
 with_prefix!(prefix_partition_tree "memtable.partition_tree.");
@@ -501,6 +575,45 @@ mod tests {
         assert_eq!(expect, options);
     }
 
+    #[test]
+    fn test_with_stcs_compaction() {
+        let map = make_map(&[
+            ("compaction.type", "stcs"),
+            ("compaction.stcs.min_threshold", "2"),
+            ("compaction.stcs.max_threshold", "8"),
+            ("compaction.stcs.min_sstable_size", "16MB"),
+            ("compaction.stcs.time_window", "1d"),
+        ]);
+        let options = RegionOptions::try_from(&map).unwrap();
+        let expect = RegionOptions {
+            compaction: CompactionOptions::Stcs(StcsOptions {
+                min_threshold: 2,
+                max_threshold: 8,
+                min_sstable_size: ReadableSize::mb(16),
+                time_window: Some(Duration::from_secs(3600 * 24)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(expect, options);
+        assert_eq!(
+            Some(Duration::from_secs(3600 * 24)),
+            options.compaction.time_window()
+        );
+
+        let map = make_map(&[
+            ("compaction.type", "stcs"),
+            ("compaction.stcs.min_threshold", "8"),
+            ("compaction.stcs.max_threshold", "4"),
+        ]);
+        let err = RegionOptions::try_from(&map).unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let map = make_map(&[("compaction.type", "no_such_compaction")]);
+        let err = RegionOptions::try_from(&map).unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
     fn test_with_wal_options(wal_options: &WalOptions) -> bool {
         let encoded_wal_options = serde_json::to_string(&wal_options).unwrap();
         let map = make_map(&[(WAL_OPTIONS_KEY, &encoded_wal_options)]);
@@ -562,6 +675,14 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(expect, options);
+
+        let map = make_map(&[("memtable.type", "bulk")]);
+        let options = RegionOptions::try_from(&map).unwrap();
+        let expect = RegionOptions {
+            memtable: Some(MemtableOptions::Bulk),
+            ..Default::default()
+        };
+        assert_eq!(expect, options);
     }
 
     #[test]
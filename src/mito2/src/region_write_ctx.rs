@@ -16,13 +16,16 @@ use std::mem;
 use std::sync::Arc;
 
 use api::v1::{Mutation, OpType, Rows, WalEntry};
+use datatypes::arrow::record_batch::RecordBatch;
 use snafu::ResultExt;
 use store_api::logstore::provider::Provider;
 use store_api::logstore::LogStore;
 use store_api::storage::{RegionId, SequenceNumber};
 
 use crate::error::{Error, Result, WriteGroupSnafu};
+use crate::memtable::bulk::part::BulkPartEncoder;
 use crate::memtable::KeyValues;
+use crate::region::options::{MemtableOptions, MergeMode};
 use crate::region::version::{VersionControlData, VersionControlRef, VersionRef};
 use crate::request::OptionOutputTx;
 use crate::wal::{EntryId, WalWriter};
@@ -91,6 +94,10 @@ pub(crate) struct RegionWriteCtx {
     ///
     /// The i-th notify is for i-th mutation.
     notifiers: Vec<WriteNotify>,
+    /// Record batches of bulk inserts.
+    ///
+    /// The i-th payload is for i-th mutation.
+    bulk_payloads: Vec<Option<RecordBatch>>,
     /// The write operation is failed and we should not write to the mutable memtable.
     failed: bool,
 
@@ -124,6 +131,7 @@ impl RegionWriteCtx {
             wal_entry: WalEntry::default(),
             provider,
             notifiers: Vec::new(),
+            bulk_payloads: Vec::new(),
             failed: false,
             put_num: 0,
             delete_num: 0,
@@ -131,7 +139,15 @@ impl RegionWriteCtx {
     }
 
     /// Push mutation to the context.
-    pub(crate) fn push_mutation(&mut self, op_type: i32, rows: Option<Rows>, tx: OptionOutputTx) {
+    ///
+    /// The `bulk_payload` is the record batch of a bulk insert, which has the same rows as `rows`.
+    pub(crate) fn push_mutation(
+        &mut self,
+        op_type: i32,
+        rows: Option<Rows>,
+        bulk_payload: Option<RecordBatch>,
+        tx: OptionOutputTx,
+    ) {
         let num_rows = rows.as_ref().map(|rows| rows.rows.len()).unwrap_or(0);
         self.wal_entry.mutations.push(Mutation {
             op_type,
@@ -142,6 +158,7 @@ impl RegionWriteCtx {
         let notify = WriteNotify::new(tx, num_rows);
         // Notifiers are 1:1 map to mutations.
         self.notifiers.push(notify);
+        self.bulk_payloads.push(bulk_payload);
 
         // Increase sequence number.
         self.next_sequence += num_rows as u64;
@@ -198,9 +215,35 @@ impl RegionWriteCtx {
         }
 
         let mutable = &self.version.memtables.mutable;
+        // Only the bulk memtable accepts bulk parts.
+        let bulk_encoder =
+            (self.version.options.memtable == Some(MemtableOptions::Bulk)).then(|| {
+                let dedup = self.version.options.need_dedup()
+                    && self.version.options.merge_mode() != MergeMode::LastNonNull;
+                BulkPartEncoder::new(self.version.metadata.clone(), dedup)
+            });
         // Takes mutations from the wal entry.
         let mutations = mem::take(&mut self.wal_entry.mutations);
-        for (mutation, notify) in mutations.into_iter().zip(&mut self.notifiers) {
+        let bulk_payloads = mem::take(&mut self.bulk_payloads);
+        for ((mutation, bulk_payload), notify) in mutations
+            .into_iter()
+            .zip(bulk_payloads)
+            .zip(&mut self.notifiers)
+        {
+            if let (Some(encoder), Some(payload)) = (&bulk_encoder, bulk_payload) {
+                // Writes the record batch to the memtable directly.
+                let result = encoder
+                    .encode_record_batch(&payload, mutation.sequence)
+                    .and_then(|part| match part {
+                        Some(part) => mutable.write_bulk(part),
+                        None => Ok(()),
+                    });
+                if let Err(e) = result {
+                    notify.err = Some(Arc::new(e));
+                }
+                continue;
+            }
+
             // Write mutation to the memtable.
             let Some(kvs) = KeyValues::new(&self.version.metadata, mutation) else {
                 continue;
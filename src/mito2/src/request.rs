@@ -23,9 +23,11 @@ use api::helper::{
     ColumnDataTypeWrapper,
 };
 use api::v1::column_def::options_from_column_schema;
-use api::v1::{ColumnDataType, ColumnSchema, OpType, Rows, SemanticType, Value};
+use api::v1::{ColumnDataType, ColumnSchema, OpType, Row, Rows, SemanticType, Value};
 use common_telemetry::info;
+use datatypes::arrow::record_batch::RecordBatch;
 use datatypes::prelude::DataType;
+use datatypes::vectors::Helper;
 use prometheus::HistogramTimer;
 use prost::Message;
 use smallvec::SmallVec;
@@ -41,8 +43,8 @@ use store_api::storage::{RegionId, SequenceNumber};
 use tokio::sync::oneshot::{self, Receiver, Sender};
 
 use crate::error::{
-    CompactRegionSnafu, ConvertColumnDataTypeSnafu, CreateDefaultSnafu, Error, FillDefaultSnafu,
-    FlushRegionSnafu, InvalidRequestSnafu, Result,
+    CompactRegionSnafu, ConvertColumnDataTypeSnafu, ConvertVectorSnafu, CreateDefaultSnafu, Error,
+    FillDefaultSnafu, FlushRegionSnafu, InvalidRequestSnafu, RegionNotFoundSnafu, Result,
 };
 use crate::manifest::action::RegionEdit;
 use crate::memtable::MemtableId;
@@ -63,6 +65,11 @@ pub struct WriteRequest {
     name_to_index: HashMap<String, usize>,
     /// Whether each column has null.
     has_null: Vec<bool>,
+    /// Record batch of a bulk insert request, which has the same content as `rows`.
+    ///
+    /// The region writes the batch to the memtable directly if the memtable supports
+    /// bulk parts, `rows` are still used to write the WAL.
+    pub(crate) bulk_payload: Option<RecordBatch>,
 }
 
 impl WriteRequest {
@@ -112,9 +119,86 @@ impl WriteRequest {
             rows,
             name_to_index,
             has_null,
+            bulk_payload: None,
         })
     }
 
+    /// Creates a put request from the record batch of a bulk insert request.
+    ///
+    /// The memtable takes the record batch directly, but the batch is still converted
+    /// to rows for the WAL. The WAL entry only has row mutations and has no columnar
+    /// (e.g. Arrow IPC) payload yet, so bulk inserts pay the same WAL encoding cost as
+    /// puts until the WAL entry gains one.
+    pub(crate) fn try_from_bulk(
+        region_id: RegionId,
+        payload: RecordBatch,
+        metadata: &RegionMetadata,
+    ) -> Result<WriteRequest> {
+        let arrow_schema = payload.schema();
+        let mut schema = Vec::with_capacity(payload.num_columns());
+        let mut vectors = Vec::with_capacity(payload.num_columns());
+        for (field, array) in arrow_schema.fields().iter().zip(payload.columns()) {
+            let column =
+                metadata
+                    .column_by_name(field.name())
+                    .with_context(|| InvalidRequestSnafu {
+                        region_id,
+                        reason: format!("unknown column {}", field.name()),
+                    })?;
+            ensure!(
+                column.column_schema.data_type.as_arrow_type() == *field.data_type(),
+                InvalidRequestSnafu {
+                    region_id,
+                    reason: format!(
+                        "column {} expect type {:?}, given: {}",
+                        column.column_schema.name,
+                        column.column_schema.data_type,
+                        field.data_type()
+                    ),
+                }
+            );
+            let (datatype, datatype_ext) =
+                ColumnDataTypeWrapper::try_from(column.column_schema.data_type.clone())
+                    .with_context(|_| ConvertColumnDataTypeSnafu {
+                        reason: format!(
+                            "no protobuf type for column {} ({:?})",
+                            column.column_schema.name, column.column_schema.data_type
+                        ),
+                    })?
+                    .to_parts();
+            schema.push(ColumnSchema {
+                column_name: column.column_schema.name.clone(),
+                datatype: datatype as i32,
+                semantic_type: column.semantic_type as i32,
+                datatype_extension: datatype_ext,
+                options: options_from_column_schema(&column.column_schema),
+            });
+            vectors.push(Helper::try_into_vector(array).context(ConvertVectorSnafu)?);
+        }
+
+        let mut rows = Vec::with_capacity(payload.num_rows());
+        for row_idx in 0..payload.num_rows() {
+            let values = vectors
+                .iter()
+                .zip(&schema)
+                .map(|(vector, column)| {
+                    to_proto_value(vector.get(row_idx)).with_context(|| InvalidRequestSnafu {
+                        region_id,
+                        reason: format!(
+                            "column {} has value that can't be written to the WAL",
+                            column.column_name
+                        ),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(Row { values });
+        }
+
+        let mut request = WriteRequest::new(region_id, OpType::Put, Rows { schema, rows })?;
+        request.bulk_payload = Some(payload);
+        Ok(request)
+    }
+
     /// Returns estimated size of the request.
     pub(crate) fn estimated_size(&self) -> usize {
         let row_size = self
@@ -233,6 +317,9 @@ impl WriteRequest {
     pub(crate) fn fill_missing_columns(&mut self, metadata: &RegionMetadata) -> Result<()> {
         debug_assert_eq!(self.region_id, metadata.region_id);
 
+        // The bulk payload doesn't contain the missing columns, so we write the rows instead.
+        self.bulk_payload = None;
+
         for column in &metadata.column_metadatas {
             if !self.name_to_index.contains_key(&column.column_schema.name) {
                 self.fill_column(column)?;
@@ -515,9 +602,12 @@ impl WorkerRequest {
     }
 
     /// Converts request from a [RegionRequest].
+    ///
+    /// The `region_metadata` is required to convert [RegionRequest::BulkInserts].
     pub(crate) fn try_from_region_request(
         region_id: RegionId,
         value: RegionRequest,
+        region_metadata: Option<RegionMetadataRef>,
     ) -> Result<(WorkerRequest, Receiver<Result<AffectedRows>>)> {
         let (sender, receiver) = oneshot::channel();
         let worker_request = match value {
@@ -535,6 +625,15 @@ impl WorkerRequest {
                     request: write_request,
                 })
             }
+            RegionRequest::BulkInserts(v) => {
+                let region_metadata = region_metadata.context(RegionNotFoundSnafu { region_id })?;
+                let write_request =
+                    WriteRequest::try_from_bulk(region_id, v.payload, &region_metadata)?;
+                WorkerRequest::Write(SenderWriteRequest {
+                    sender: sender.into(),
+                    request: write_request,
+                })
+            }
             RegionRequest::Create(v) => WorkerRequest::Ddl(SenderDdlRequest {
                 region_id,
                 sender: sender.into(),
@@ -784,6 +883,7 @@ pub(crate) struct RegionEditResult {
 mod tests {
     use api::v1::value::ValueData;
     use api::v1::{Row, SemanticType};
+    use datatypes::arrow::array::{Int32Array, Int64Array, TimestampMillisecondArray};
     use datatypes::prelude::ConcreteDataType;
     use datatypes::schema::ColumnDefaultConstraint;
     use store_api::metadata::RegionMetadataBuilder;
@@ -912,6 +1012,69 @@ mod tests {
         request.check_schema(&metadata).unwrap();
     }
 
+    #[test]
+    fn test_write_request_from_bulk() {
+        let metadata = new_region_metadata();
+        let payload = RecordBatch::try_new(
+            metadata.schema.arrow_schema().clone(),
+            vec![
+                Arc::new(TimestampMillisecondArray::from(vec![1, 2])),
+                Arc::new(Int64Array::from(vec![Some(3), None])),
+            ],
+        )
+        .unwrap();
+
+        let request =
+            WriteRequest::try_from_bulk(RegionId::new(1, 1), payload.clone(), &metadata).unwrap();
+        request.check_schema(&metadata).unwrap();
+        assert_eq!(Some(payload), request.bulk_payload);
+        assert_eq!(
+            vec![
+                Row {
+                    values: vec![ts_ms_value(1), i64_value(3)],
+                },
+                Row {
+                    values: vec![ts_ms_value(2), Value { value_data: None }],
+                },
+            ],
+            request.rows.rows
+        );
+    }
+
+    #[test]
+    fn test_write_request_from_bulk_unknown_column() {
+        let metadata = new_region_metadata();
+        let schema = Arc::new(datatypes::arrow::datatypes::Schema::new(vec![
+            datatypes::arrow::datatypes::Field::new(
+                "c0",
+                datatypes::arrow::datatypes::DataType::Int64,
+                true,
+            ),
+        ]));
+        let payload =
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap();
+
+        let err = WriteRequest::try_from_bulk(RegionId::new(1, 1), payload, &metadata).unwrap_err();
+        check_invalid_request(&err, "unknown column c0");
+    }
+
+    #[test]
+    fn test_write_request_from_bulk_column_type() {
+        let metadata = new_region_metadata();
+        let schema = Arc::new(datatypes::arrow::datatypes::Schema::new(vec![
+            datatypes::arrow::datatypes::Field::new(
+                "k0",
+                datatypes::arrow::datatypes::DataType::Int32,
+                true,
+            ),
+        ]));
+        let payload =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1]))]).unwrap();
+
+        let err = WriteRequest::try_from_bulk(RegionId::new(1, 1), payload, &metadata).unwrap_err();
+        check_invalid_request(&err, "column k0 expect type Int64(Int64Type), given: Int32");
+    }
+
     #[test]
     fn test_column_type() {
         let rows = Rows {
@@ -68,7 +68,9 @@ impl SortField {
             ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => 9,
             ConcreteDataType::Float32(_) => 5,
             ConcreteDataType::Float64(_) => 9,
-            ConcreteDataType::Binary(_) => 11,
+            ConcreteDataType::Binary(_)
+            | ConcreteDataType::Json(_)
+            | ConcreteDataType::Vector(_) => 11,
             ConcreteDataType::String(_) => 11, // a non-empty string takes at least 11 bytes.
             ConcreteDataType::Date(_) => 5,
             ConcreteDataType::DateTime(_) => 9,
@@ -110,6 +112,14 @@ impl SortField {
                         }
                     }
                 )*
+                    // Json and vector values are stored as binary.
+                    ConcreteDataType::Json(_) | ConcreteDataType::Vector(_) => {
+                        value
+                            .as_binary()
+                            .context(FieldTypeMismatchSnafu)?
+                            .serialize($serializer)
+                            .context(SerializeFieldSnafu)?;
+                    }
                     ConcreteDataType::Timestamp(_) => {
                         let timestamp = value.as_timestamp().context(FieldTypeMismatchSnafu)?;
                         timestamp
@@ -169,7 +179,7 @@ impl SortField {
                             Ok(Value::from(Option::<$f>::deserialize(deserializer).context(error::DeserializeFieldSnafu)?))
                         }
                     )*
-                    ConcreteDataType::Binary(_) => Ok(Value::from(
+                    ConcreteDataType::Binary(_) | ConcreteDataType::Json(_) | ConcreteDataType::Vector(_) => Ok(Value::from(
                         Option::<Vec<u8>>::deserialize(deserializer)
                             .context(error::DeserializeFieldSnafu)?
                             .map(Bytes::from),
@@ -237,7 +247,9 @@ impl SortField {
             ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => 9,
             ConcreteDataType::Float32(_) => 5,
             ConcreteDataType::Float64(_) => 9,
-            ConcreteDataType::Binary(_) => {
+            ConcreteDataType::Binary(_)
+            | ConcreteDataType::Json(_)
+            | ConcreteDataType::Vector(_) => {
                 // Now the encoder encode binary as a list of bytes so we can't use
                 // skip bytes.
                 let pos_before = deserializer.position();
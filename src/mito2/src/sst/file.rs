@@ -141,6 +141,10 @@ pub enum IndexType {
     InvertedIndex,
     /// Full-text index.
     FulltextIndex,
+    /// Vector index.
+    VectorIndex,
+    /// Bloom filter index.
+    BloomFilterIndex,
 }
 
 impl FileMeta {
@@ -150,6 +154,13 @@ impl FileMeta {
     pub fn fulltext_index_available(&self) -> bool {
         self.available_indexes.contains(&IndexType::FulltextIndex)
     }
+    pub fn vector_index_available(&self) -> bool {
+        self.available_indexes.contains(&IndexType::VectorIndex)
+    }
+    pub fn bloom_filter_index_available(&self) -> bool {
+        self.available_indexes
+            .contains(&IndexType::BloomFilterIndex)
+    }
 }
 
 /// Handle to a SST file.
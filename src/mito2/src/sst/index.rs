@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod bloom_filter;
 pub(crate) mod fulltext_index;
 mod indexer;
 pub(crate) mod intermediate;
@@ -19,6 +20,7 @@ pub(crate) mod inverted_index;
 pub(crate) mod puffin_manager;
 mod statistics;
 mod store;
+pub(crate) mod vector_index;
 
 use std::num::NonZeroUsize;
 
@@ -29,17 +31,23 @@ use store_api::metadata::RegionMetadataRef;
 use store_api::storage::{ColumnId, RegionId};
 
 use crate::access_layer::OperationType;
-use crate::config::{FulltextIndexConfig, InvertedIndexConfig};
+use crate::config::{
+    BloomFilterIndexConfig, FulltextIndexConfig, InvertedIndexConfig, VectorIndexConfig,
+};
 use crate::metrics::INDEX_CREATE_MEMORY_USAGE;
 use crate::read::Batch;
 use crate::region::options::IndexOptions;
 use crate::sst::file::FileId;
+use crate::sst::index::bloom_filter::creator::BloomFilterIndexer;
 use crate::sst::index::fulltext_index::creator::FulltextIndexer;
 use crate::sst::index::intermediate::IntermediateManager;
 use crate::sst::index::inverted_index::creator::InvertedIndexer;
+use crate::sst::index::vector_index::creator::VectorIndexer;
 
 pub(crate) const TYPE_INVERTED_INDEX: &str = "inverted_index";
 pub(crate) const TYPE_FULLTEXT_INDEX: &str = "fulltext_index";
+pub(crate) const TYPE_VECTOR_INDEX: &str = "vector_index";
+pub(crate) const TYPE_BLOOM_FILTER_INDEX: &str = "bloom_filter_index";
 
 /// Output of the index creation.
 #[derive(Debug, Clone, Default)]
@@ -50,6 +58,10 @@ pub struct IndexOutput {
     pub inverted_index: InvertedIndexOutput,
     /// Fulltext index output.
     pub fulltext_index: FulltextIndexOutput,
+    /// Vector index output.
+    pub vector_index: VectorIndexOutput,
+    /// Bloom filter index output.
+    pub bloom_filter_index: BloomFilterIndexOutput,
 }
 
 /// Base output of the index creation.
@@ -73,6 +85,10 @@ impl IndexBaseOutput {
 pub type InvertedIndexOutput = IndexBaseOutput;
 /// Output of the fulltext index creation.
 pub type FulltextIndexOutput = IndexBaseOutput;
+/// Output of the vector index creation.
+pub type VectorIndexOutput = IndexBaseOutput;
+/// Output of the bloom filter index creation.
+pub type BloomFilterIndexOutput = IndexBaseOutput;
 
 /// The index creator that hides the error handling details.
 #[derive(Default)]
@@ -86,6 +102,10 @@ pub struct Indexer {
     last_mem_inverted_index: usize,
     fulltext_indexer: Option<FulltextIndexer>,
     last_mem_fulltext_index: usize,
+    vector_indexer: Option<VectorIndexer>,
+    last_mem_vector_index: usize,
+    bloom_filter_indexer: Option<BloomFilterIndexer>,
+    last_mem_bloom_filter_index: usize,
 }
 
 impl Indexer {
@@ -129,6 +149,24 @@ impl Indexer {
             .with_label_values(&[TYPE_FULLTEXT_INDEX])
             .add(fulltext_mem as i64 - self.last_mem_fulltext_index as i64);
         self.last_mem_fulltext_index = fulltext_mem;
+
+        let vector_mem = self
+            .vector_indexer
+            .as_ref()
+            .map_or(0, |creator| creator.memory_usage());
+        INDEX_CREATE_MEMORY_USAGE
+            .with_label_values(&[TYPE_VECTOR_INDEX])
+            .add(vector_mem as i64 - self.last_mem_vector_index as i64);
+        self.last_mem_vector_index = vector_mem;
+
+        let bloom_filter_mem = self
+            .bloom_filter_indexer
+            .as_ref()
+            .map_or(0, |creator| creator.memory_usage());
+        INDEX_CREATE_MEMORY_USAGE
+            .with_label_values(&[TYPE_BLOOM_FILTER_INDEX])
+            .add(bloom_filter_mem as i64 - self.last_mem_bloom_filter_index as i64);
+        self.last_mem_bloom_filter_index = bloom_filter_mem;
     }
 }
 
@@ -143,6 +181,8 @@ pub(crate) struct IndexerBuilder<'a> {
     pub(crate) index_options: IndexOptions,
     pub(crate) inverted_index_config: InvertedIndexConfig,
     pub(crate) fulltext_index_config: FulltextIndexConfig,
+    pub(crate) vector_index_config: VectorIndexConfig,
+    pub(crate) bloom_filter_index_config: BloomFilterIndexConfig,
 }
 
 impl<'a> IndexerBuilder<'a> {
@@ -158,7 +198,13 @@ impl<'a> IndexerBuilder<'a> {
 
         indexer.inverted_indexer = self.build_inverted_indexer();
         indexer.fulltext_indexer = self.build_fulltext_indexer().await;
-        if indexer.inverted_indexer.is_none() && indexer.fulltext_indexer.is_none() {
+        indexer.vector_indexer = self.build_vector_indexer();
+        indexer.bloom_filter_indexer = self.build_bloom_filter_indexer();
+        if indexer.inverted_indexer.is_none()
+            && indexer.fulltext_indexer.is_none()
+            && indexer.vector_indexer.is_none()
+            && indexer.bloom_filter_indexer.is_none()
+        {
             indexer.abort().await;
             return Indexer::default();
         }
@@ -276,6 +322,98 @@ impl<'a> IndexerBuilder<'a> {
 
         None
     }
+
+    fn build_vector_indexer(&self) -> Option<VectorIndexer> {
+        let create = match self.op_type {
+            OperationType::Flush => self.vector_index_config.create_on_flush.auto(),
+            OperationType::Compact => self.vector_index_config.create_on_compaction.auto(),
+        };
+
+        if !create {
+            debug!(
+                "Skip creating vector index due to config, region_id: {}, file_id: {}",
+                self.metadata.region_id, self.file_id,
+            );
+            return None;
+        }
+
+        let err = match VectorIndexer::new(self.metadata) {
+            Ok(creator) => {
+                if creator.is_none() {
+                    debug!(
+                        "Skip creating vector index due to no columns require indexing, region_id: {}, file_id: {}",
+                        self.metadata.region_id, self.file_id,
+                    );
+                }
+                return creator;
+            }
+            Err(err) => err,
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to create vector indexer, region_id: {}, file_id: {}, err: {}",
+                self.metadata.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to create vector indexer, region_id: {}, file_id: {}",
+                self.metadata.region_id, self.file_id,
+            );
+        }
+
+        None
+    }
+
+    fn build_bloom_filter_indexer(&self) -> Option<BloomFilterIndexer> {
+        let create = match self.op_type {
+            OperationType::Flush => self.bloom_filter_index_config.create_on_flush.auto(),
+            OperationType::Compact => self.bloom_filter_index_config.create_on_compaction.auto(),
+        };
+
+        if !create {
+            debug!(
+                "Skip creating bloom filter index due to config, region_id: {}, file_id: {}",
+                self.metadata.region_id, self.file_id,
+            );
+            return None;
+        }
+
+        let Some(row_group_size) = NonZeroUsize::new(self.row_group_size) else {
+            warn!(
+                "Row group size is 0, skip creating index, region_id: {}, file_id: {}",
+                self.metadata.region_id, self.file_id,
+            );
+            return None;
+        };
+
+        let err = match BloomFilterIndexer::new(self.metadata, row_group_size) {
+            Ok(creator) => {
+                if creator.is_none() {
+                    debug!(
+                        "Skip creating bloom filter index due to no columns require indexing, region_id: {}, file_id: {}",
+                        self.metadata.region_id, self.file_id,
+                    );
+                }
+                return creator;
+            }
+            Err(err) => err,
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to create bloom filter indexer, region_id: {}, file_id: {}, err: {}",
+                self.metadata.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to create bloom filter indexer, region_id: {}, file_id: {}",
+                self.metadata.region_id, self.file_id,
+            );
+        }
+
+        None
+    }
 }
 
 #[cfg(test)]
@@ -291,7 +429,7 @@ mod tests {
     use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
 
     use super::*;
-    use crate::config::{FulltextIndexConfig, Mode};
+    use crate::config::{FulltextIndexConfig, Mode, VectorIndexConfig};
 
     struct MetaConfig {
         with_tag: bool,
@@ -384,6 +522,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: InvertedIndexConfig::default(),
             fulltext_index_config: FulltextIndexConfig::default(),
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -416,6 +556,8 @@ mod tests {
                 ..Default::default()
             },
             fulltext_index_config: FulltextIndexConfig::default(),
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -437,6 +579,8 @@ mod tests {
                 create_on_compaction: Mode::Disable,
                 ..Default::default()
             },
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -466,6 +610,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: InvertedIndexConfig::default(),
             fulltext_index_config: FulltextIndexConfig::default(),
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -488,6 +634,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: InvertedIndexConfig::default(),
             fulltext_index_config: FulltextIndexConfig::default(),
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -517,6 +665,8 @@ mod tests {
             index_options: IndexOptions::default(),
             inverted_index_config: InvertedIndexConfig::default(),
             fulltext_index_config: FulltextIndexConfig::default(),
+            vector_index_config: VectorIndexConfig::default(),
+            bloom_filter_index_config: BloomFilterIndexConfig::default(),
         }
         .build()
         .await;
@@ -0,0 +1,141 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod builder;
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use futures::AsyncReadExt;
+use index::bloom_filter::{decode, hash, BloomFilter};
+use object_store::ObjectStore;
+use puffin::puffin_manager::{BlobGuard, PuffinManager, PuffinReader};
+use snafu::ResultExt;
+use store_api::storage::ColumnId;
+
+use crate::error::{
+    ApplyBloomFilterIndexSnafu, PuffinBuildReaderSnafu, PuffinReadBlobSnafu,
+    ReadBloomFilterIndexBlobSnafu, Result,
+};
+use crate::metrics::INDEX_APPLY_ELAPSED;
+use crate::sst::file::FileId;
+use crate::sst::index::bloom_filter::INDEX_BLOB_TYPE;
+use crate::sst::index::puffin_manager::PuffinManagerFactory;
+use crate::sst::index::TYPE_BLOOM_FILTER_INDEX;
+use crate::sst::location;
+
+/// `BloomFilterIndexApplier` finds the row groups of the provided SST files that may
+/// contain rows matching the equality predicates.
+pub struct BloomFilterIndexApplier {
+    /// The root directory of the region.
+    region_dir: String,
+
+    /// Store responsible for accessing index files.
+    store: ObjectStore,
+
+    /// The puffin manager factory.
+    puffin_manager_factory: PuffinManagerFactory,
+
+    /// Predicates on the indexed columns, each with the encoded values the column
+    /// may be equal to. A row group matches if it matches all the predicates.
+    predicates: Vec<(ColumnId, Vec<Vec<u8>>)>,
+}
+
+pub type BloomFilterIndexApplierRef = Arc<BloomFilterIndexApplier>;
+
+impl BloomFilterIndexApplier {
+    /// Creates a new `BloomFilterIndexApplier`.
+    pub fn new(
+        region_dir: String,
+        store: ObjectStore,
+        puffin_manager_factory: PuffinManagerFactory,
+        predicates: Vec<(ColumnId, Vec<Vec<u8>>)>,
+    ) -> Self {
+        Self {
+            region_dir,
+            store,
+            puffin_manager_factory,
+            predicates,
+        }
+    }
+
+    /// Returns the row groups of the specified SST file that may match the predicates.
+    ///
+    /// Returns `None` if the index can't be applied, e.g. none of the columns
+    /// are indexed in the file.
+    pub async fn apply(
+        &self,
+        file_id: FileId,
+        num_row_groups: usize,
+    ) -> Result<Option<BTreeSet<usize>>> {
+        let _timer = INDEX_APPLY_ELAPSED
+            .with_label_values(&[TYPE_BLOOM_FILTER_INDEX])
+            .start_timer();
+
+        let mut row_groups: Option<BTreeSet<usize>> = None;
+        for (column_id, values) in &self.predicates {
+            let Some(filters) = self.load_filters(file_id, *column_id).await? else {
+                continue;
+            };
+            // The index doesn't match the SST, e.g. it's built with another row group size.
+            if filters.len() != num_row_groups {
+                continue;
+            }
+
+            let hashes = values.iter().map(|v| hash(v)).collect::<Vec<_>>();
+            let matched = filters
+                .iter()
+                .enumerate()
+                .filter(|(_, filter)| hashes.iter().any(|h| filter.contains_hash(*h)))
+                .map(|(row_group, _)| row_group);
+            row_groups = Some(match row_groups {
+                Some(row_groups) => matched.filter(|rg| row_groups.contains(rg)).collect(),
+                None => matched.collect(),
+            });
+        }
+
+        Ok(row_groups)
+    }
+
+    /// Returns `None` if the index of the column not found.
+    async fn load_filters(
+        &self,
+        file_id: FileId,
+        column_id: ColumnId,
+    ) -> Result<Option<Vec<BloomFilter>>> {
+        let puffin_manager = self.puffin_manager_factory.build(self.store.clone());
+        let file_path = location::index_file_path(&self.region_dir, file_id);
+
+        let blob = match puffin_manager
+            .reader(&file_path)
+            .await
+            .context(PuffinBuildReaderSnafu)?
+            .blob(&format!("{INDEX_BLOB_TYPE}-{column_id}"))
+            .await
+        {
+            Ok(blob) => blob,
+            Err(puffin::error::Error::BlobNotFound { .. }) => return Ok(None),
+            Err(err) => return Err(err).context(PuffinReadBlobSnafu),
+        };
+
+        let mut buf = Vec::new();
+        blob.reader()
+            .await
+            .context(PuffinBuildReaderSnafu)?
+            .read_to_end(&mut buf)
+            .await
+            .context(ReadBloomFilterIndexBlobSnafu)?;
+        decode(&buf).context(ApplyBloomFilterIndexSnafu).map(Some)
+    }
+}
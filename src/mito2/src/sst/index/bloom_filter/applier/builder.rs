@@ -0,0 +1,331 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use datafusion_common::ScalarValue;
+use datafusion_expr::expr::InList;
+use datafusion_expr::{BinaryExpr, Expr, Operator};
+use datatypes::data_type::ConcreteDataType;
+use datatypes::value::Value;
+use object_store::ObjectStore;
+use store_api::metadata::RegionMetadata;
+use store_api::storage::ColumnId;
+
+use crate::error::Result;
+use crate::row_converter::SortField;
+use crate::sst::index::bloom_filter::applier::BloomFilterIndexApplier;
+use crate::sst::index::inverted_index::codec::IndexValueCodec;
+use crate::sst::index::puffin_manager::PuffinManagerFactory;
+
+/// `BloomFilterIndexApplierBuilder` is a builder for `BloomFilterIndexApplier`.
+pub struct BloomFilterIndexApplierBuilder<'a> {
+    region_dir: String,
+    store: ObjectStore,
+    puffin_manager_factory: PuffinManagerFactory,
+    metadata: &'a RegionMetadata,
+    /// Encoded values each column may be equal to.
+    predicates: HashMap<ColumnId, Vec<Vec<u8>>>,
+}
+
+impl<'a> BloomFilterIndexApplierBuilder<'a> {
+    /// Creates a new `BloomFilterIndexApplierBuilder`.
+    pub fn new(
+        region_dir: String,
+        store: ObjectStore,
+        puffin_manager_factory: PuffinManagerFactory,
+        metadata: &'a RegionMetadata,
+    ) -> Self {
+        Self {
+            region_dir,
+            store,
+            puffin_manager_factory,
+            metadata,
+            predicates: HashMap::new(),
+        }
+    }
+
+    /// Builds `BloomFilterIndexApplier` from the given expressions.
+    ///
+    /// Only equality and `IN` predicates on columns with a skipping index are applied.
+    pub fn build(mut self, exprs: &[Expr]) -> Result<Option<BloomFilterIndexApplier>> {
+        for expr in exprs {
+            self.traverse_and_collect(expr)?;
+        }
+
+        Ok((!self.predicates.is_empty()).then(|| {
+            BloomFilterIndexApplier::new(
+                self.region_dir,
+                self.store,
+                self.puffin_manager_factory,
+                self.predicates.into_iter().collect(),
+            )
+        }))
+    }
+
+    /// Recursively traverses expressions to collect predicates.
+    fn traverse_and_collect(&mut self, expr: &Expr) -> Result<()> {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::And,
+                right,
+            }) => {
+                self.traverse_and_collect(left)?;
+                self.traverse_and_collect(right)?;
+            }
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => {
+                let (column, lit) = match (Self::column_name(left), Self::column_name(right)) {
+                    (Some(column), None) => (column, right),
+                    (None, Some(column)) => (column, left),
+                    _ => return Ok(()),
+                };
+                if let Some(lit) = Self::nonnull_lit(lit) {
+                    self.add_predicate(column, &[lit])?;
+                }
+            }
+            Expr::InList(InList {
+                expr,
+                list,
+                negated: false,
+            }) => {
+                let Some(column) = Self::column_name(expr) else {
+                    return Ok(());
+                };
+                let Some(lits) = list
+                    .iter()
+                    .map(Self::nonnull_lit)
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    return Ok(());
+                };
+                self.add_predicate(column, &lits)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Adds a predicate that the column equals to one of the literals.
+    ///
+    /// The predicate is ignored if the column is not indexed or a literal can't be
+    /// converted to the type of the column.
+    fn add_predicate(&mut self, column_name: &str, lits: &[&ScalarValue]) -> Result<()> {
+        let Some(column) = self.metadata.column_by_name(column_name) else {
+            return Ok(());
+        };
+        if !matches!(column.column_schema.skipping_index_options(), Ok(Some(_))) {
+            return Ok(());
+        }
+
+        let data_type = &column.column_schema.data_type;
+        let mut values = Vec::with_capacity(lits.len());
+        for lit in lits {
+            let Some(value) = Self::encode_lit(lit, data_type)? else {
+                return Ok(());
+            };
+            values.push(value);
+        }
+
+        match self.predicates.get_mut(&column.column_id) {
+            // Both predicates must hold, keeps the values satisfying both.
+            Some(existing) => existing.retain(|v| values.contains(v)),
+            None => {
+                self.predicates.insert(column.column_id, values);
+            }
+        }
+        Ok(())
+    }
+
+    /// Helper function to get a non-null literal.
+    fn nonnull_lit(expr: &Expr) -> Option<&ScalarValue> {
+        match expr {
+            Expr::Literal(lit) if !lit.is_null() => Some(lit),
+            _ => None,
+        }
+    }
+
+    /// Helper function to get the column name of a column expression.
+    fn column_name(expr: &Expr) -> Option<&str> {
+        match expr {
+            Expr::Column(column) => Some(&column.name),
+            _ => None,
+        }
+    }
+
+    /// Helper function to encode a literal the same way as the index creator.
+    ///
+    /// Returns `None` if the literal can't be converted to the type of the column.
+    fn encode_lit(lit: &ScalarValue, data_type: &ConcreteDataType) -> Result<Option<Vec<u8>>> {
+        let Ok(value) = Value::try_from(lit.clone()) else {
+            return Ok(None);
+        };
+        let value = if value.data_type() == *data_type {
+            value
+        } else {
+            match datatypes::types::cast(value, data_type) {
+                Ok(value) if !value.is_null() => value,
+                _ => return Ok(None),
+            }
+        };
+
+        let mut bytes = vec![];
+        let field = SortField::new(data_type.clone());
+        IndexValueCodec::encode_nonnull_value(value.as_value_ref(), &field, &mut bytes)?;
+        Ok(Some(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::SemanticType;
+    use datafusion_expr::{col, lit};
+    use datatypes::schema::{ColumnSchema, SkippingIndexOptions};
+    use object_store::services::Memory;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
+    use store_api::storage::RegionId;
+
+    use super::*;
+
+    fn mock_metadata() -> RegionMetadata {
+        let mut builder = RegionMetadataBuilder::new(RegionId::new(1, 2));
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "indexed",
+                    ConcreteDataType::int64_datatype(),
+                    true,
+                )
+                .with_skipping_index_options(SkippingIndexOptions::default())
+                .unwrap(),
+                semantic_type: SemanticType::Field,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "not_indexed",
+                    ConcreteDataType::int64_datatype(),
+                    true,
+                ),
+                semantic_type: SemanticType::Field,
+                column_id: 2,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 3,
+            });
+
+        builder.build().unwrap()
+    }
+
+    fn new_builder(metadata: &RegionMetadata) -> BloomFilterIndexApplierBuilder<'_> {
+        let (_d, factory) = PuffinManagerFactory::new_for_test_block("test_bloom_filter_builder_");
+        BloomFilterIndexApplierBuilder::new(
+            "test".to_string(),
+            ObjectStore::new(Memory::default()).unwrap().finish(),
+            factory,
+            metadata,
+        )
+    }
+
+    fn encoded(v: i64) -> Vec<u8> {
+        BloomFilterIndexApplierBuilder::encode_lit(
+            &ScalarValue::Int64(Some(v)),
+            &ConcreteDataType::int64_datatype(),
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn predicates(exprs: &[Expr]) -> Option<Vec<(ColumnId, Vec<Vec<u8>>)>> {
+        let metadata = mock_metadata();
+        let mut builder = new_builder(&metadata);
+        for expr in exprs {
+            builder.traverse_and_collect(expr).unwrap();
+        }
+        (!builder.predicates.is_empty()).then(|| builder.predicates.into_iter().collect())
+    }
+
+    #[test]
+    fn test_collect_eq_and_in_list() {
+        assert_eq!(
+            Some(vec![(1, vec![encoded(1)])]),
+            predicates(&[col("indexed").eq(lit(1i64))])
+        );
+        assert_eq!(
+            Some(vec![(1, vec![encoded(1)])]),
+            predicates(&[lit(1i64).eq(col("indexed"))])
+        );
+        assert_eq!(
+            Some(vec![(1, vec![encoded(1), encoded(2)])]),
+            predicates(&[col("indexed").in_list(vec![lit(1i64), lit(2i64)], false)])
+        );
+        // Literals are casted to the type of the column.
+        assert_eq!(
+            Some(vec![(1, vec![encoded(3)])]),
+            predicates(&[col("indexed").eq(lit(3i32))])
+        );
+    }
+
+    #[test]
+    fn test_collect_conjunction() {
+        assert_eq!(
+            Some(vec![(1, vec![encoded(2)])]),
+            predicates(&[col("indexed")
+                .in_list(vec![lit(1i64), lit(2i64)], false)
+                .and(col("indexed").eq(lit(2i64)))])
+        );
+        assert_eq!(
+            Some(vec![(1, vec![encoded(2)])]),
+            predicates(&[
+                col("indexed").in_list(vec![lit(1i64), lit(2i64)], false),
+                col("indexed").in_list(vec![lit(2i64), lit(3i64)], false),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_collect_unsupported() {
+        assert_eq!(None, predicates(&[col("not_indexed").eq(lit(1i64))]));
+        assert_eq!(None, predicates(&[col("nonexistent").eq(lit(1i64))]));
+        assert_eq!(None, predicates(&[col("indexed").gt(lit(1i64))]));
+        assert_eq!(
+            None,
+            predicates(&[col("indexed").in_list(vec![lit(1i64)], true)])
+        );
+        assert_eq!(
+            None,
+            predicates(&[
+                col("indexed").in_list(vec![lit(1i64), lit(ScalarValue::Int64(None))], false)
+            ])
+        );
+        assert_eq!(
+            None,
+            predicates(&[col("indexed")
+                .eq(lit(1i64))
+                .or(col("indexed").eq(lit(2i64)))])
+        );
+        assert_eq!(None, predicates(&[col("indexed").eq(lit("foo"))]));
+    }
+}
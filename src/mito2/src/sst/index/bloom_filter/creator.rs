@@ -0,0 +1,417 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+use api::v1::SemanticType;
+use futures::io::Cursor;
+use index::bloom_filter::creator::BloomFilterCreator;
+use puffin::puffin_manager::{PuffinWriter, PutOptions};
+use snafu::{ensure, ResultExt};
+use store_api::metadata::RegionMetadataRef;
+use store_api::storage::ColumnId;
+
+use crate::error::{
+    BloomFilterIndexOptionsSnafu, OperateAbortedIndexSnafu, PuffinAddBlobSnafu, Result,
+};
+use crate::read::Batch;
+use crate::row_converter::SortField;
+use crate::sst::index::bloom_filter::INDEX_BLOB_TYPE;
+use crate::sst::index::inverted_index::codec::{IndexValueCodec, IndexValuesCodec};
+use crate::sst::index::puffin_manager::SstPuffinWriter;
+use crate::sst::index::statistics::{ByteCount, RowCount, Statistics};
+use crate::sst::index::TYPE_BLOOM_FILTER_INDEX;
+
+/// `BloomFilterIndexer` is responsible for creating bloom filter indexes for SST files.
+///
+/// A bloom filter is built for each row group of the SST so that row groups can be
+/// pruned by equality predicates.
+pub struct BloomFilterIndexer {
+    /// Creators for each column.
+    creators: HashMap<ColumnId, SingleCreator>,
+    /// Decoder of the primary key, only present if some tag columns require indexing.
+    codec: Option<IndexValuesCodec>,
+    /// Number of rows in a row group of the SST.
+    row_group_size: usize,
+    /// Number of rows pushed.
+    num_rows: usize,
+    /// Buffer for the encoded value.
+    value_buf: Vec<u8>,
+    /// Whether the index creation was aborted.
+    aborted: bool,
+    /// Statistics of index creation.
+    stats: Statistics,
+}
+
+impl BloomFilterIndexer {
+    /// Creates a new `BloomFilterIndexer`, returns `None` if no column requires indexing.
+    pub fn new(metadata: &RegionMetadataRef, row_group_size: NonZeroUsize) -> Result<Option<Self>> {
+        let mut creators = HashMap::new();
+        let mut index_tags = false;
+
+        for column in &metadata.column_metadatas {
+            let options = column.column_schema.skipping_index_options().context(
+                BloomFilterIndexOptionsSnafu {
+                    column_name: &column.column_schema.name,
+                },
+            )?;
+            let Some(options) = options else {
+                continue;
+            };
+
+            index_tags |= column.semantic_type == SemanticType::Tag;
+            creators.insert(
+                column.column_id,
+                SingleCreator {
+                    column_id: column.column_id,
+                    field: SortField::new(column.column_schema.data_type.clone()),
+                    creator: BloomFilterCreator::new(options.false_positive_rate),
+                },
+            );
+        }
+
+        let codec =
+            index_tags.then(|| IndexValuesCodec::from_tag_columns(metadata.primary_key_columns()));
+        Ok((!creators.is_empty()).then(move || Self {
+            creators,
+            codec,
+            row_group_size: row_group_size.get(),
+            num_rows: 0,
+            value_buf: vec![],
+            aborted: false,
+            stats: Statistics::new(TYPE_BLOOM_FILTER_INDEX),
+        }))
+    }
+
+    /// Updates the index with the given batch.
+    pub async fn update(&mut self, batch: &Batch) -> Result<()> {
+        ensure!(!self.aborted, OperateAbortedIndexSnafu);
+
+        if let Err(update_err) = self.do_update(batch) {
+            self.do_abort();
+            return Err(update_err);
+        }
+
+        Ok(())
+    }
+
+    /// Finalizes the index creation.
+    pub async fn finish(
+        &mut self,
+        puffin_writer: &mut SstPuffinWriter,
+    ) -> Result<(RowCount, ByteCount)> {
+        ensure!(!self.aborted, OperateAbortedIndexSnafu);
+
+        match self.do_finish(puffin_writer).await {
+            Ok(()) => Ok((self.stats.row_count(), self.stats.byte_count())),
+            Err(finish_err) => {
+                self.do_abort();
+                Err(finish_err)
+            }
+        }
+    }
+
+    /// Aborts the index creation.
+    pub async fn abort(&mut self) -> Result<()> {
+        if !self.aborted {
+            self.do_abort();
+        }
+        Ok(())
+    }
+
+    /// Returns the memory usage of the index creator.
+    pub fn memory_usage(&self) -> usize {
+        self.creators
+            .values()
+            .map(|c| c.creator.memory_usage())
+            .sum()
+    }
+
+    /// Returns IDs of columns that the creator is responsible for.
+    pub fn column_ids(&self) -> impl Iterator<Item = ColumnId> + '_ {
+        self.creators.keys().copied()
+    }
+}
+
+impl BloomFilterIndexer {
+    fn do_update(&mut self, batch: &Batch) -> Result<()> {
+        let mut guard = self.stats.record_update();
+
+        let num_rows = batch.num_rows();
+        guard.inc_row_count(num_rows);
+        if num_rows == 0 {
+            return Ok(());
+        }
+
+        if let Some(codec) = &self.codec {
+            // All rows in the batch share the same tag values, pushes them to every
+            // row group the batch spans.
+            let first_row_group = self.num_rows / self.row_group_size;
+            let last_row_group = (self.num_rows + num_rows - 1) / self.row_group_size;
+            for ((column_id, _), field, value) in codec.decode(batch.primary_key())? {
+                let (Some(creator), Some(value)) = (self.creators.get_mut(column_id), value) else {
+                    continue;
+                };
+                self.value_buf.clear();
+                IndexValueCodec::encode_nonnull_value(
+                    value.as_value_ref(),
+                    field,
+                    &mut self.value_buf,
+                )?;
+                for row_group in first_row_group..=last_row_group {
+                    creator.creator.push(row_group, &self.value_buf);
+                }
+            }
+        }
+
+        for column in batch.fields() {
+            let Some(creator) = self.creators.get_mut(&column.column_id) else {
+                continue;
+            };
+            for i in 0..num_rows {
+                let value = column.data.get_ref(i);
+                if value.is_null() {
+                    continue;
+                }
+                self.value_buf.clear();
+                IndexValueCodec::encode_nonnull_value(value, &creator.field, &mut self.value_buf)?;
+                let row_group = (self.num_rows + i) / self.row_group_size;
+                creator.creator.push(row_group, &self.value_buf);
+            }
+        }
+        self.num_rows += num_rows;
+
+        Ok(())
+    }
+
+    async fn do_finish(&mut self, puffin_writer: &mut SstPuffinWriter) -> Result<()> {
+        let mut guard = self.stats.record_finish();
+
+        let num_row_groups = self.num_rows.div_ceil(self.row_group_size);
+        let mut written_bytes = 0;
+        for creator in self.creators.values_mut() {
+            written_bytes += creator.finish(puffin_writer, num_row_groups).await?;
+        }
+
+        guard.inc_byte_count(written_bytes);
+        Ok(())
+    }
+
+    fn do_abort(&mut self) {
+        let _guard = self.stats.record_cleanup();
+
+        self.aborted = true;
+        // The filters are in memory, dropping them is enough.
+        self.creators.clear();
+    }
+}
+
+/// `SingleCreator` is a creator for a single column.
+struct SingleCreator {
+    /// Column ID.
+    column_id: ColumnId,
+    /// Data type of the column to encode values.
+    field: SortField,
+    /// The bloom filters being built.
+    creator: BloomFilterCreator,
+}
+
+impl SingleCreator {
+    async fn finish(
+        &mut self,
+        puffin_writer: &mut SstPuffinWriter,
+        num_row_groups: usize,
+    ) -> Result<ByteCount> {
+        let key = format!("{INDEX_BLOB_TYPE}-{}", self.column_id);
+        puffin_writer
+            .put_blob(
+                &key,
+                Cursor::new(self.creator.finish(num_row_groups)),
+                PutOptions::default(),
+            )
+            .await
+            .context(PuffinAddBlobSnafu)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datafusion_expr::{col, lit, Expr};
+    use datatypes::schema::{ColumnSchema, SkippingIndexOptions};
+    use datatypes::value::ValueRef;
+    use datatypes::vectors::{Int64Vector, UInt64Vector, UInt8Vector};
+    use object_store::services::Memory;
+    use object_store::ObjectStore;
+    use puffin::puffin_manager::PuffinManager;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
+    use store_api::storage::{ConcreteDataType, RegionId};
+
+    use super::*;
+    use crate::read::BatchColumn;
+    use crate::row_converter::{McmpRowCodec, RowCodec};
+    use crate::sst::file::FileId;
+    use crate::sst::index::bloom_filter::applier::builder::BloomFilterIndexApplierBuilder;
+    use crate::sst::index::puffin_manager::PuffinManagerFactory;
+    use crate::sst::location;
+
+    fn mock_region_metadata(with_index: bool) -> RegionMetadataRef {
+        let mut tag_schema = ColumnSchema::new("tag", ConcreteDataType::string_datatype(), true);
+        let mut field_schema = ColumnSchema::new("field", ConcreteDataType::int64_datatype(), true);
+        if with_index {
+            tag_schema = tag_schema
+                .with_skipping_index_options(SkippingIndexOptions::default())
+                .unwrap();
+            field_schema = field_schema
+                .with_skipping_index_options(SkippingIndexOptions::default())
+                .unwrap();
+        }
+
+        let mut builder = RegionMetadataBuilder::new(RegionId::new(1, 2));
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema: tag_schema,
+                semantic_type: SemanticType::Tag,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: field_schema,
+                semantic_type: SemanticType::Field,
+                column_id: 2,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 3,
+            })
+            .primary_key(vec![1]);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    fn new_batch(tag: &str, fields: Vec<Option<i64>>) -> Batch {
+        let codec = McmpRowCodec::new(vec![SortField::new(ConcreteDataType::string_datatype())]);
+        let primary_key = codec.encode([ValueRef::String(tag)].into_iter()).unwrap();
+
+        let num_rows = fields.len();
+        Batch::new(
+            primary_key,
+            Arc::new(UInt64Vector::from_iter_values(
+                (0..num_rows).map(|n| n as u64),
+            )),
+            Arc::new(UInt64Vector::from_iter_values(
+                std::iter::repeat(0).take(num_rows),
+            )),
+            Arc::new(UInt8Vector::from_iter_values(
+                std::iter::repeat(1).take(num_rows),
+            )),
+            vec![BatchColumn {
+                column_id: 2,
+                data: Arc::new(Int64Vector::from(fields)),
+            }],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bloom_filter_indexer_no_required() {
+        let metadata = mock_region_metadata(false);
+        let row_group_size = NonZeroUsize::new(2).unwrap();
+        assert!(BloomFilterIndexer::new(&metadata, row_group_size)
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bloom_filter_index_basic() {
+        let (_d, factory) =
+            PuffinManagerFactory::new_for_test_async("test_bloom_filter_index_basic_").await;
+        let region_dir = "region0".to_string();
+        let sst_file_id = FileId::random();
+        let file_path = location::index_file_path(&region_dir, sst_file_id);
+        let object_store = ObjectStore::new(Memory::default()).unwrap().finish();
+        let metadata = mock_region_metadata(true);
+
+        // Row groups: [a: 1, 2], [a: 3, b: 4], [b: null]
+        let row_group_size = NonZeroUsize::new(2).unwrap();
+        let mut indexer = BloomFilterIndexer::new(&metadata, row_group_size)
+            .unwrap()
+            .unwrap();
+        let mut column_ids = indexer.column_ids().collect::<Vec<_>>();
+        column_ids.sort_unstable();
+        assert_eq!(vec![1, 2], column_ids);
+        indexer
+            .update(&new_batch("a", vec![Some(1), Some(2), Some(3)]))
+            .await
+            .unwrap();
+        indexer
+            .update(&new_batch("b", vec![Some(4), None]))
+            .await
+            .unwrap();
+        assert!(indexer.memory_usage() > 0);
+
+        let puffin_manager = factory.build(object_store.clone());
+        let mut writer = puffin_manager.writer(&file_path).await.unwrap();
+        let (row_count, byte_count) = indexer.finish(&mut writer).await.unwrap();
+        assert_eq!(5, row_count);
+        assert!(byte_count > 0);
+        writer.finish().await.unwrap();
+
+        let cases: Vec<(Expr, Vec<usize>)> = vec![
+            (col("tag").eq(lit("a")), vec![0, 1]),
+            (col("tag").eq(lit("b")), vec![1, 2]),
+            (col("tag").eq(lit("c")), vec![]),
+            (col("field").eq(lit(4i64)), vec![1]),
+            (
+                col("field").in_list(vec![lit(1i64), lit(3i64)], false),
+                vec![0, 1],
+            ),
+            (
+                col("tag").eq(lit("b")).and(col("field").eq(lit(1i64))),
+                vec![],
+            ),
+        ];
+        for (expr, expected) in cases {
+            let applier = BloomFilterIndexApplierBuilder::new(
+                region_dir.clone(),
+                object_store.clone(),
+                factory.clone(),
+                &metadata,
+            )
+            .build(&[expr.clone()])
+            .unwrap()
+            .unwrap();
+            let row_groups = applier.apply(sst_file_id, 3).await.unwrap().unwrap();
+            assert_eq!(
+                expected,
+                row_groups.into_iter().collect::<Vec<_>>(),
+                "expr: {expr}"
+            );
+        }
+
+        // The number of row groups doesn't match the index.
+        let applier =
+            BloomFilterIndexApplierBuilder::new(region_dir, object_store, factory, &metadata)
+                .build(&[col("tag").eq(lit("a"))])
+                .unwrap()
+                .unwrap();
+        assert!(applier.apply(sst_file_id, 4).await.unwrap().is_none());
+    }
+}
@@ -12,7 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use datatypes::schema::{FulltextAnalyzer, FulltextOptions};
+use index::fulltext_index::{Analyzer, Config};
+
 pub(crate) mod applier;
 pub(crate) mod creator;
 
 const INDEX_BLOB_TYPE: &str = "greptime-fulltext-index-v1";
+
+/// Converts the fulltext options of a column to the config of its fulltext index.
+fn index_config(options: &FulltextOptions) -> Config {
+    let analyzer = match options.analyzer {
+        FulltextAnalyzer::English => Analyzer::English,
+        FulltextAnalyzer::Chinese => Analyzer::Chinese,
+        FulltextAnalyzer::Standard => Analyzer::Standard,
+        FulltextAnalyzer::Whitespace => Analyzer::Whitespace,
+        FulltextAnalyzer::Ngram => Analyzer::Ngram,
+        FulltextAnalyzer::Japanese => Analyzer::Japanese,
+        FulltextAnalyzer::Korean => Analyzer::Korean,
+    };
+    Config {
+        analyzer,
+        case_sensitive: options.case_sensitive,
+        stop_words: options.stop_words.clone(),
+    }
+}
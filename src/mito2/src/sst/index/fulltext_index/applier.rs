@@ -16,6 +16,7 @@ use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use index::fulltext_index::search::{FulltextIndexSearcher, RowId, TantivyFulltextIndexSearcher};
+use index::fulltext_index::Config;
 use object_store::ObjectStore;
 use puffin::puffin_manager::{DirGuard, PuffinManager, PuffinReader};
 use snafu::ResultExt;
@@ -36,8 +37,8 @@ pub struct FulltextIndexApplier {
     /// The root directory of the region.
     region_dir: String,
 
-    /// Queries to apply to the index.
-    queries: Vec<(ColumnId, String)>,
+    /// Queries to apply to the index, with the index config of the column.
+    queries: Vec<(ColumnId, String, Config)>,
 
     /// The puffin manager factory.
     puffin_manager_factory: PuffinManagerFactory,
@@ -53,7 +54,7 @@ impl FulltextIndexApplier {
     pub fn new(
         region_dir: String,
         store: ObjectStore,
-        queries: Vec<(ColumnId, String)>,
+        queries: Vec<(ColumnId, String, Config)>,
         puffin_manager_factory: PuffinManagerFactory,
     ) -> Self {
         Self {
@@ -73,7 +74,7 @@ impl FulltextIndexApplier {
         let mut inited = false;
         let mut row_ids = BTreeSet::new();
 
-        for (column_id, query) in &self.queries {
+        for (column_id, query, config) in &self.queries {
             let dir = self.index_dir_path(file_id, *column_id).await?;
             let path = match &dir {
                 Some(dir) => dir.path(),
@@ -84,7 +85,7 @@ impl FulltextIndexApplier {
             };
 
             let searcher =
-                TantivyFulltextIndexSearcher::new(path).context(ApplyFulltextIndexSnafu)?;
+                TantivyFulltextIndexSearcher::new(path, config).context(ApplyFulltextIndexSnafu)?;
             let result = searcher
                 .search(query)
                 .await
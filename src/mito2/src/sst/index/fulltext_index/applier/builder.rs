@@ -20,6 +20,7 @@ use store_api::storage::{ColumnId, ConcreteDataType};
 
 use crate::error::Result;
 use crate::sst::index::fulltext_index::applier::FulltextIndexApplier;
+use crate::sst::index::fulltext_index::index_config;
 use crate::sst::index::puffin_manager::PuffinManagerFactory;
 
 /// `FulltextIndexApplierBuilder` is a builder for `FulltextIndexApplier`.
@@ -51,7 +52,13 @@ impl<'a> FulltextIndexApplierBuilder<'a> {
         let mut queries = Vec::with_capacity(exprs.len());
         for expr in exprs {
             if let Some((column_id, query)) = Self::expr_to_query(self.metadata, expr) {
-                queries.push((column_id, query));
+                let config = self
+                    .metadata
+                    .column_by_id(column_id)
+                    .and_then(|c| c.column_schema.fulltext_options().ok().flatten())
+                    .map(|options| index_config(&options))
+                    .unwrap_or_default();
+                queries.push((column_id, query, config));
             }
         }
 
@@ -72,7 +79,8 @@ impl<'a> FulltextIndexApplierBuilder<'a> {
         if f.name() != "matches" {
             return None;
         }
-        if f.args.len() != 2 {
+        // The optional third argument carries the fulltext options of the column.
+        if f.args.len() != 2 && f.args.len() != 3 {
             return None;
         }
 
@@ -163,6 +171,30 @@ mod tests {
         assert_eq!(query, "foo".to_string());
     }
 
+    #[test]
+    fn test_expr_to_query_with_options() {
+        let metadata = mock_metadata();
+
+        let expr = Expr::ScalarFunction(ScalarFunction {
+            args: vec![
+                Expr::Column(Column {
+                    name: "text".to_string(),
+                    relation: None,
+                }),
+                Expr::Literal(ScalarValue::Utf8(Some("foo".to_string()))),
+                Expr::Literal(ScalarValue::Utf8(Some(
+                    r#"{"enable":true,"analyzer":"Ngram","case-sensitive":false}"#.to_string(),
+                ))),
+            ],
+            func: matches_func(),
+        });
+
+        let (column_id, query) =
+            FulltextIndexApplierBuilder::expr_to_query(&metadata, &expr).unwrap();
+        assert_eq!(column_id, 1);
+        assert_eq!(query, "foo".to_string());
+    }
+
     #[test]
     fn test_expr_to_query_wrong_num_args() {
         let metadata = mock_metadata();
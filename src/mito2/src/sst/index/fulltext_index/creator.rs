@@ -16,9 +16,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use common_telemetry::warn;
-use datatypes::schema::FulltextAnalyzer;
 use index::fulltext_index::create::{FulltextIndexCreator, TantivyFulltextIndexCreator};
-use index::fulltext_index::{Analyzer, Config};
 use puffin::blob_metadata::CompressionCodec;
 use puffin::puffin_manager::{PuffinWriter, PutOptions};
 use snafu::{ensure, ResultExt};
@@ -32,7 +30,7 @@ use crate::error::{
 };
 use crate::read::Batch;
 use crate::sst::file::FileId;
-use crate::sst::index::fulltext_index::INDEX_BLOB_TYPE;
+use crate::sst::index::fulltext_index::{index_config, INDEX_BLOB_TYPE};
 use crate::sst::index::intermediate::IntermediateManager;
 use crate::sst::index::puffin_manager::SstPuffinWriter;
 use crate::sst::index::statistics::{ByteCount, RowCount, Statistics};
@@ -79,13 +77,7 @@ impl FulltextIndexer {
             let column_id = column.column_id;
             let intm_path = intermediate_manager.fulltext_path(region_id, sst_file_id, column_id);
 
-            let config = Config {
-                analyzer: match options.analyzer {
-                    FulltextAnalyzer::English => Analyzer::English,
-                    FulltextAnalyzer::Chinese => Analyzer::Chinese,
-                },
-                case_sensitive: options.case_sensitive,
-            };
+            let config = index_config(&options);
 
             let creator = TantivyFulltextIndexCreator::new(&intm_path, config, mem_limit)
                 .await
@@ -333,6 +325,7 @@ mod tests {
                     enable: true,
                     analyzer: FulltextAnalyzer::English,
                     case_sensitive: true,
+                    ..Default::default()
                 })
                 .unwrap(),
                 semantic_type: SemanticType::Field,
@@ -348,6 +341,7 @@ mod tests {
                     enable: true,
                     analyzer: FulltextAnalyzer::English,
                     case_sensitive: false,
+                    ..Default::default()
                 })
                 .unwrap(),
                 semantic_type: SemanticType::Field,
@@ -363,6 +357,7 @@ mod tests {
                     enable: true,
                     analyzer: FulltextAnalyzer::Chinese,
                     case_sensitive: false,
+                    ..Default::default()
                 })
                 .unwrap(),
                 semantic_type: SemanticType::Field,
@@ -482,7 +477,16 @@ mod tests {
                 object_store.clone(),
                 queries
                     .into_iter()
-                    .map(|(a, b)| (a, b.to_string()))
+                    .map(|(column_id, query)| {
+                        let options = region_metadata
+                            .column_by_id(column_id)
+                            .unwrap()
+                            .column_schema
+                            .fulltext_options()
+                            .unwrap()
+                            .unwrap();
+                        (column_id, query.to_string(), index_config(&options))
+                    })
                     .collect(),
                 factory.clone(),
             );
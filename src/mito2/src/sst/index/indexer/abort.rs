@@ -20,6 +20,8 @@ impl Indexer {
     pub(crate) async fn do_abort(&mut self) {
         self.do_abort_inverted_index().await;
         self.do_abort_fulltext_index().await;
+        self.do_abort_vector_index().await;
+        self.do_abort_bloom_filter_index().await;
         self.puffin_manager = None;
     }
 
@@ -64,4 +66,46 @@ impl Indexer {
             );
         }
     }
+
+    async fn do_abort_vector_index(&mut self) {
+        let Some(mut indexer) = self.vector_indexer.take() else {
+            return;
+        };
+        let Err(err) = indexer.abort().await else {
+            return;
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to abort vector index, region_id: {}, file_id: {}, err: {}",
+                self.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to abort vector index, region_id: {}, file_id: {}",
+                self.region_id, self.file_id,
+            );
+        }
+    }
+
+    async fn do_abort_bloom_filter_index(&mut self) {
+        let Some(mut indexer) = self.bloom_filter_indexer.take() else {
+            return;
+        };
+        let Err(err) = indexer.abort().await else {
+            return;
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to abort bloom filter index, region_id: {}, file_id: {}, err: {}",
+                self.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to abort bloom filter index, region_id: {}, file_id: {}",
+                self.region_id, self.file_id,
+            );
+        }
+    }
 }
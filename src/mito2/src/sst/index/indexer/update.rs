@@ -29,6 +29,12 @@ impl Indexer {
         if !self.do_update_fulltext_index(batch).await {
             self.do_abort().await;
         }
+        if !self.do_update_vector_index(batch).await {
+            self.do_abort().await;
+        }
+        if !self.do_update_bloom_filter_index(batch).await {
+            self.do_abort().await;
+        }
     }
 
     /// Returns false if the update failed.
@@ -80,4 +86,54 @@ impl Indexer {
 
         false
     }
+
+    /// Returns false if the update failed.
+    async fn do_update_vector_index(&mut self, batch: &Batch) -> bool {
+        let Some(creator) = self.vector_indexer.as_mut() else {
+            return true;
+        };
+
+        let Err(err) = creator.update(batch).await else {
+            return true;
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to update vector index, region_id: {}, file_id: {}, err: {}",
+                self.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to update vector index, region_id: {}, file_id: {}",
+                self.region_id, self.file_id,
+            );
+        }
+
+        false
+    }
+
+    /// Returns false if the update failed.
+    async fn do_update_bloom_filter_index(&mut self, batch: &Batch) -> bool {
+        let Some(creator) = self.bloom_filter_indexer.as_mut() else {
+            return true;
+        };
+
+        let Err(err) = creator.update(batch).await else {
+            return true;
+        };
+
+        if cfg!(any(test, feature = "test")) {
+            panic!(
+                "Failed to update bloom filter index, region_id: {}, file_id: {}, err: {}",
+                self.region_id, self.file_id, err
+            );
+        } else {
+            warn!(
+                err; "Failed to update bloom filter index, region_id: {}, file_id: {}",
+                self.region_id, self.file_id,
+            );
+        }
+
+        false
+    }
 }
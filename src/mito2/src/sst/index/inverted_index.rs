@@ -13,7 +13,7 @@
 // limitations under the License.
 
 pub(crate) mod applier;
-mod codec;
+pub(crate) mod codec;
 pub(crate) mod creator;
 
 const INDEX_BLOB_TYPE: &str = "greptime-inverted-index-v1";
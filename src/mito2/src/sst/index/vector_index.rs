@@ -0,0 +1,30 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use datatypes::schema::VectorDistanceMetric;
+use index::vector_index::Metric;
+
+pub(crate) mod applier;
+pub(crate) mod creator;
+
+const INDEX_BLOB_TYPE: &str = "greptime-vector-index-v1";
+
+/// Converts the metric in column options to the metric of the index.
+fn to_index_metric(metric: VectorDistanceMetric) -> Metric {
+    match metric {
+        VectorDistanceMetric::L2sq => Metric::L2sq,
+        VectorDistanceMetric::Cosine => Metric::Cosine,
+        VectorDistanceMetric::InnerProduct => Metric::InnerProduct,
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use datatypes::schema::VectorDistanceMetric;
+use futures::AsyncReadExt;
+use index::vector_index::hnsw::HnswIndex;
+use index::vector_index::RowId;
+use object_store::ObjectStore;
+use puffin::puffin_manager::{BlobGuard, PuffinManager, PuffinReader};
+use snafu::ResultExt;
+use store_api::storage::ColumnId;
+
+use crate::error::{
+    ApplyVectorIndexSnafu, PuffinBuildReaderSnafu, PuffinReadBlobSnafu, ReadVectorIndexBlobSnafu,
+    Result,
+};
+use crate::metrics::INDEX_APPLY_ELAPSED;
+use crate::sst::file::FileId;
+use crate::sst::index::puffin_manager::PuffinManagerFactory;
+use crate::sst::index::vector_index::{to_index_metric, INDEX_BLOB_TYPE};
+use crate::sst::index::TYPE_VECTOR_INDEX;
+use crate::sst::location;
+
+/// `VectorIndexApplier` searches the nearest rows of a query vector in the vector index
+/// of the provided SST files.
+pub struct VectorIndexApplier {
+    /// The root directory of the region.
+    region_dir: String,
+
+    /// Store responsible for accessing index files.
+    store: ObjectStore,
+
+    /// The puffin manager factory.
+    puffin_manager_factory: PuffinManagerFactory,
+
+    /// The vector column to search.
+    column_id: ColumnId,
+
+    /// The query vector.
+    query: Vec<f32>,
+
+    /// The metric to measure distances, the index is only applied if it's built
+    /// for the same metric.
+    metric: VectorDistanceMetric,
+
+    /// Number of nearest rows to search.
+    k: usize,
+
+    /// Number of candidates to consider when searching.
+    expansion_search: usize,
+}
+
+pub type VectorIndexApplierRef = Arc<VectorIndexApplier>;
+
+impl VectorIndexApplier {
+    /// Creates a new `VectorIndexApplier`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        region_dir: String,
+        store: ObjectStore,
+        puffin_manager_factory: PuffinManagerFactory,
+        column_id: ColumnId,
+        query: Vec<f32>,
+        metric: VectorDistanceMetric,
+        k: usize,
+        expansion_search: usize,
+    ) -> Self {
+        Self {
+            region_dir,
+            store,
+            puffin_manager_factory,
+            column_id,
+            query,
+            metric,
+            k,
+            expansion_search,
+        }
+    }
+
+    /// Searches the `k` nearest rows in the vector index of the specified SST file.
+    ///
+    /// Returns `None` if the index can't be applied, e.g. the index is not found or
+    /// it's built for another metric.
+    pub async fn apply(&self, file_id: FileId) -> Result<Option<BTreeSet<RowId>>> {
+        let _timer = INDEX_APPLY_ELAPSED
+            .with_label_values(&[TYPE_VECTOR_INDEX])
+            .start_timer();
+
+        let Some(index) = self.load_index(file_id).await? else {
+            return Ok(None);
+        };
+        if index.config().metric != to_index_metric(self.metric) {
+            return Ok(None);
+        }
+
+        let result = index
+            .search(&self.query, self.k, self.expansion_search)
+            .context(ApplyVectorIndexSnafu)?;
+        Ok(Some(result.into_iter().map(|(row_id, _)| row_id).collect()))
+    }
+
+    /// Returns `None` if the index not found.
+    async fn load_index(&self, file_id: FileId) -> Result<Option<HnswIndex>> {
+        let puffin_manager = self.puffin_manager_factory.build(self.store.clone());
+        let file_path = location::index_file_path(&self.region_dir, file_id);
+
+        let blob = match puffin_manager
+            .reader(&file_path)
+            .await
+            .context(PuffinBuildReaderSnafu)?
+            .blob(&format!("{INDEX_BLOB_TYPE}-{}", self.column_id))
+            .await
+        {
+            Ok(blob) => blob,
+            Err(puffin::error::Error::BlobNotFound { .. }) => return Ok(None),
+            Err(err) => return Err(err).context(PuffinReadBlobSnafu),
+        };
+
+        let mut buf = Vec::new();
+        blob.reader()
+            .await
+            .context(PuffinBuildReaderSnafu)?
+            .read_to_end(&mut buf)
+            .await
+            .context(ReadVectorIndexBlobSnafu)?;
+        HnswIndex::decode(&buf)
+            .context(ApplyVectorIndexSnafu)
+            .map(Some)
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use datatypes::types::binlit_as_veclit;
+use datatypes::value::ValueRef;
+use futures::io::Cursor;
+use index::vector_index::hnsw::HnswIndex;
+use index::vector_index::{Config, RowId};
+use puffin::puffin_manager::{PuffinWriter, PutOptions};
+use snafu::{ensure, ResultExt};
+use store_api::metadata::RegionMetadataRef;
+use store_api::storage::ColumnId;
+
+use crate::error::{
+    FieldTypeMismatchSnafu, OperateAbortedIndexSnafu, PuffinAddBlobSnafu, Result,
+    VectorIndexOptionsSnafu, VectorIndexPushSnafu,
+};
+use crate::read::Batch;
+use crate::sst::index::puffin_manager::SstPuffinWriter;
+use crate::sst::index::statistics::{ByteCount, RowCount, Statistics};
+use crate::sst::index::vector_index::{to_index_metric, INDEX_BLOB_TYPE};
+use crate::sst::index::TYPE_VECTOR_INDEX;
+
+/// `VectorIndexer` is responsible for creating vector indexes for SST files.
+pub struct VectorIndexer {
+    /// Creators for each column.
+    creators: HashMap<ColumnId, SingleCreator>,
+    /// Whether the index creation was aborted.
+    aborted: bool,
+    /// Statistics of index creation.
+    stats: Statistics,
+}
+
+impl VectorIndexer {
+    /// Creates a new `VectorIndexer`, returns `None` if no column requires indexing.
+    pub fn new(metadata: &RegionMetadataRef) -> Result<Option<Self>> {
+        let mut creators = HashMap::new();
+
+        for column in &metadata.column_metadatas {
+            let Some(vector_type) = column.column_schema.data_type.as_vector() else {
+                continue;
+            };
+            let options =
+                column
+                    .column_schema
+                    .vector_index_options()
+                    .context(VectorIndexOptionsSnafu {
+                        column_name: &column.column_schema.name,
+                    })?;
+            let Some(options) = options else {
+                continue;
+            };
+
+            let config = Config {
+                dim: vector_type.dim as usize,
+                metric: to_index_metric(options.metric),
+                connectivity: options.connectivity,
+                expansion_add: options.expansion_add,
+            };
+            creators.insert(
+                column.column_id,
+                SingleCreator {
+                    column_id: column.column_id,
+                    index: HnswIndex::new(config),
+                    next_row_id: 0,
+                },
+            );
+        }
+
+        Ok((!creators.is_empty()).then(move || Self {
+            creators,
+            aborted: false,
+            stats: Statistics::new(TYPE_VECTOR_INDEX),
+        }))
+    }
+
+    /// Updates the index with the given batch.
+    pub async fn update(&mut self, batch: &Batch) -> Result<()> {
+        ensure!(!self.aborted, OperateAbortedIndexSnafu);
+
+        if let Err(update_err) = self.do_update(batch) {
+            self.do_abort();
+            return Err(update_err);
+        }
+
+        Ok(())
+    }
+
+    /// Finalizes the index creation.
+    pub async fn finish(
+        &mut self,
+        puffin_writer: &mut SstPuffinWriter,
+    ) -> Result<(RowCount, ByteCount)> {
+        ensure!(!self.aborted, OperateAbortedIndexSnafu);
+
+        match self.do_finish(puffin_writer).await {
+            Ok(()) => Ok((self.stats.row_count(), self.stats.byte_count())),
+            Err(finish_err) => {
+                self.do_abort();
+                Err(finish_err)
+            }
+        }
+    }
+
+    /// Aborts the index creation.
+    pub async fn abort(&mut self) -> Result<()> {
+        if !self.aborted {
+            self.do_abort();
+        }
+        Ok(())
+    }
+
+    /// Returns the memory usage of the index creator.
+    pub fn memory_usage(&self) -> usize {
+        self.creators.values().map(|c| c.index.memory_usage()).sum()
+    }
+
+    /// Returns IDs of columns that the creator is responsible for.
+    pub fn column_ids(&self) -> impl Iterator<Item = ColumnId> + '_ {
+        self.creators.keys().copied()
+    }
+}
+
+impl VectorIndexer {
+    fn do_update(&mut self, batch: &Batch) -> Result<()> {
+        let mut guard = self.stats.record_update();
+        guard.inc_row_count(batch.num_rows());
+
+        for creator in self.creators.values_mut() {
+            creator.update(batch)?;
+        }
+
+        Ok(())
+    }
+
+    async fn do_finish(&mut self, puffin_writer: &mut SstPuffinWriter) -> Result<()> {
+        let mut guard = self.stats.record_finish();
+
+        let mut written_bytes = 0;
+        for creator in self.creators.values_mut() {
+            written_bytes += creator.finish(puffin_writer).await?;
+        }
+
+        guard.inc_byte_count(written_bytes);
+        Ok(())
+    }
+
+    fn do_abort(&mut self) {
+        let _guard = self.stats.record_cleanup();
+
+        self.aborted = true;
+        // The index is in memory, dropping it is enough.
+        self.creators.clear();
+    }
+}
+
+/// `SingleCreator` is a creator for a single column.
+struct SingleCreator {
+    /// Column ID.
+    column_id: ColumnId,
+    /// The index being built.
+    index: HnswIndex,
+    /// Row id of the next row to push, i.e. the number of rows pushed.
+    next_row_id: RowId,
+}
+
+impl SingleCreator {
+    fn update(&mut self, batch: &Batch) -> Result<()> {
+        let num_rows = batch.num_rows();
+        let vector_column = batch
+            .fields()
+            .iter()
+            .find(|c| c.column_id == self.column_id);
+        // Rows without a vector (null or column not found) are not indexed,
+        // but still take row ids so that row ids are aligned with rows in the SST.
+        if let Some(column) = vector_column {
+            for i in 0..num_rows {
+                let ValueRef::Binary(bytes) = column.data.get_ref(i) else {
+                    continue;
+                };
+                let vector = binlit_as_veclit(bytes).context(FieldTypeMismatchSnafu)?;
+                self.index
+                    .add(self.next_row_id + i as RowId, &vector)
+                    .context(VectorIndexPushSnafu)?;
+            }
+        }
+        self.next_row_id += num_rows as RowId;
+
+        Ok(())
+    }
+
+    async fn finish(&mut self, puffin_writer: &mut SstPuffinWriter) -> Result<ByteCount> {
+        let key = format!("{INDEX_BLOB_TYPE}-{}", self.column_id);
+        puffin_writer
+            .put_blob(
+                &key,
+                Cursor::new(self.index.encode()),
+                PutOptions::default(),
+            )
+            .await
+            .context(PuffinAddBlobSnafu)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::SemanticType;
+    use datatypes::data_type::DataType;
+    use datatypes::schema::{ColumnSchema, VectorDistanceMetric, VectorIndexOptions};
+    use datatypes::types::veclit_to_binlit;
+    use datatypes::vectors::{UInt64Vector, UInt8Vector};
+    use object_store::services::Memory;
+    use object_store::ObjectStore;
+    use puffin::puffin_manager::PuffinManager;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
+    use store_api::storage::{ConcreteDataType, RegionId};
+
+    use super::*;
+    use crate::read::BatchColumn;
+    use crate::sst::file::FileId;
+    use crate::sst::index::puffin_manager::PuffinManagerFactory;
+    use crate::sst::index::vector_index::applier::VectorIndexApplier;
+    use crate::sst::location;
+
+    fn mock_region_metadata(with_index: bool) -> RegionMetadataRef {
+        let mut column_schema =
+            ColumnSchema::new("embedding", ConcreteDataType::vector_datatype(2), true);
+        if with_index {
+            column_schema = column_schema
+                .with_vector_index_options(VectorIndexOptions {
+                    metric: VectorDistanceMetric::L2sq,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let mut builder = RegionMetadataBuilder::new(RegionId::new(1, 2));
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema,
+                semantic_type: SemanticType::Field,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 2,
+            });
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    fn new_batch(rows: &[Option<[f32; 2]>]) -> Batch {
+        let mut vectors = ConcreteDataType::vector_datatype(2).create_mutable_vector(0);
+        for row in rows {
+            match row {
+                Some(v) => vectors.push_value_ref(ValueRef::Binary(&veclit_to_binlit(v))),
+                None => vectors.push_null(),
+            }
+        }
+
+        let num_rows = rows.len();
+        Batch::new(
+            vec![],
+            Arc::new(UInt64Vector::from_iter_values(
+                (0..num_rows).map(|n| n as u64),
+            )),
+            Arc::new(UInt64Vector::from_iter_values(
+                std::iter::repeat(0).take(num_rows),
+            )),
+            Arc::new(UInt8Vector::from_iter_values(
+                std::iter::repeat(1).take(num_rows),
+            )),
+            vec![BatchColumn {
+                column_id: 1,
+                data: vectors.to_vector(),
+            }],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_vector_indexer_no_required() {
+        let metadata = mock_region_metadata(false);
+        assert!(VectorIndexer::new(&metadata).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_vector_index_basic() {
+        let (_d, factory) =
+            PuffinManagerFactory::new_for_test_async("test_vector_index_basic_").await;
+        let region_dir = "region0".to_string();
+        let sst_file_id = FileId::random();
+        let file_path = location::index_file_path(&region_dir, sst_file_id);
+        let object_store = ObjectStore::new(Memory::default()).unwrap().finish();
+        let metadata = mock_region_metadata(true);
+
+        let mut indexer = VectorIndexer::new(&metadata).unwrap().unwrap();
+        assert_eq!(vec![1], indexer.column_ids().collect::<Vec<_>>());
+        indexer
+            .update(&new_batch(&[Some([0.0, 0.0]), None, Some([1.0, 1.0])]))
+            .await
+            .unwrap();
+        indexer
+            .update(&new_batch(&[Some([5.0, 5.0]), Some([0.5, 0.5])]))
+            .await
+            .unwrap();
+        assert!(indexer.memory_usage() > 0);
+
+        let puffin_manager = factory.build(object_store.clone());
+        let mut writer = puffin_manager.writer(&file_path).await.unwrap();
+        let (row_count, byte_count) = indexer.finish(&mut writer).await.unwrap();
+        assert_eq!(5, row_count);
+        assert!(byte_count > 0);
+        writer.finish().await.unwrap();
+
+        let applier = VectorIndexApplier::new(
+            region_dir.clone(),
+            object_store.clone(),
+            factory.clone(),
+            1,
+            vec![0.6, 0.6],
+            VectorDistanceMetric::L2sq,
+            2,
+            16,
+        );
+        let row_ids = applier.apply(sst_file_id).await.unwrap().unwrap();
+        assert_eq!(row_ids, vec![2, 4].into_iter().collect());
+
+        // The index is built for another metric.
+        let applier = VectorIndexApplier::new(
+            region_dir,
+            object_store,
+            factory,
+            1,
+            vec![0.6, 0.6],
+            VectorDistanceMetric::Cosine,
+            2,
+            16,
+        );
+        assert!(applier.apply(sst_file_id).await.unwrap().is_none());
+    }
+}
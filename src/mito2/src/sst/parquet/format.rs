@@ -56,7 +56,7 @@ pub(crate) type PrimaryKeyArray = DictionaryArray<UInt32Type>;
 /// Number of columns that have fixed positions.
 ///
 /// Contains: time index and internal columns.
-const FIXED_POS_COLUMN_NUM: usize = 4;
+pub(crate) const FIXED_POS_COLUMN_NUM: usize = 4;
 
 /// Helper for writing the SST format.
 pub(crate) struct WriteFormat {
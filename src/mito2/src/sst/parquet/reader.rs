@@ -54,8 +54,10 @@ use crate::read::prune::{PruneReader, Source};
 use crate::read::{Batch, BatchReader};
 use crate::row_converter::{McmpRowCodec, SortField};
 use crate::sst::file::FileHandle;
+use crate::sst::index::bloom_filter::applier::BloomFilterIndexApplierRef;
 use crate::sst::index::fulltext_index::applier::FulltextIndexApplierRef;
 use crate::sst::index::inverted_index::applier::InvertedIndexApplierRef;
+use crate::sst::index::vector_index::applier::VectorIndexApplierRef;
 use crate::sst::parquet::file_range::{FileRangeContext, FileRangeContextRef};
 use crate::sst::parquet::format::ReadFormat;
 use crate::sst::parquet::metadata::MetadataLoader;
@@ -86,6 +88,8 @@ pub struct ParquetReaderBuilder {
     /// Index appliers.
     inverted_index_applier: Option<InvertedIndexApplierRef>,
     fulltext_index_applier: Option<FulltextIndexApplierRef>,
+    vector_index_applier: Option<VectorIndexApplierRef>,
+    bloom_filter_index_applier: Option<BloomFilterIndexApplierRef>,
     /// Expected metadata of the region while reading the SST.
     /// This is usually the latest metadata of the region. The reader use
     /// it get the correct column id of a column by name.
@@ -109,6 +113,8 @@ impl ParquetReaderBuilder {
             cache_manager: None,
             inverted_index_applier: None,
             fulltext_index_applier: None,
+            vector_index_applier: None,
+            bloom_filter_index_applier: None,
             expected_metadata: None,
         }
     }
@@ -163,6 +169,26 @@ impl ParquetReaderBuilder {
         self
     }
 
+    /// Attaches the vector index applier to the builder.
+    #[must_use]
+    pub(crate) fn vector_index_applier(
+        mut self,
+        index_applier: Option<VectorIndexApplierRef>,
+    ) -> Self {
+        self.vector_index_applier = index_applier;
+        self
+    }
+
+    /// Attaches the bloom filter index applier to the builder.
+    #[must_use]
+    pub(crate) fn bloom_filter_index_applier(
+        mut self,
+        index_applier: Option<BloomFilterIndexApplierRef>,
+    ) -> Self {
+        self.bloom_filter_index_applier = index_applier;
+        self
+    }
+
     /// Attaches the expected metadata to the builder.
     #[must_use]
     pub fn expected_metadata(mut self, expected_metadata: Option<RegionMetadataRef>) -> Self {
@@ -359,6 +385,12 @@ impl ParquetReaderBuilder {
 
         let mut output = (0..num_row_groups).map(|i| (i, None)).collect();
 
+        self.prune_row_groups_by_vector_index(row_group_size, parquet_meta, &mut output, metrics)
+            .await;
+        if output.is_empty() {
+            return output;
+        }
+
         self.prune_row_groups_by_fulltext_index(row_group_size, parquet_meta, &mut output, metrics)
             .await;
         if output.is_empty() {
@@ -372,6 +404,12 @@ impl ParquetReaderBuilder {
             return output;
         }
 
+        self.prune_row_groups_by_bloom_filter_index(parquet_meta, &mut output, metrics)
+            .await;
+        if output.is_empty() {
+            return output;
+        }
+
         if !inverted_filtered {
             self.prune_row_groups_by_minmax(read_format, parquet_meta, &mut output, metrics);
         }
@@ -428,6 +466,111 @@ impl ParquetReaderBuilder {
         true
     }
 
+    /// Prunes row groups by vector index, only the nearest rows of the query vector
+    /// are kept. Returns `true` if the row groups are pruned.
+    async fn prune_row_groups_by_vector_index(
+        &self,
+        row_group_size: usize,
+        parquet_meta: &ParquetMetaData,
+        output: &mut BTreeMap<usize, Option<RowSelection>>,
+        metrics: &mut ReaderFilterMetrics,
+    ) -> bool {
+        let Some(index_applier) = &self.vector_index_applier else {
+            return false;
+        };
+        if !self.file_handle.meta_ref().vector_index_available() {
+            return false;
+        }
+
+        let apply_res = match index_applier.apply(self.file_handle.file_id()).await {
+            Ok(Some(res)) => res,
+            Ok(None) => return false,
+            Err(err) => {
+                if cfg!(any(test, feature = "test")) {
+                    panic!(
+                        "Failed to apply vector index, region_id: {}, file_id: {}, err: {}",
+                        self.file_handle.region_id(),
+                        self.file_handle.file_id(),
+                        err
+                    );
+                } else {
+                    warn!(
+                        err; "Failed to apply vector index, region_id: {}, file_id: {}",
+                        self.file_handle.region_id(), self.file_handle.file_id()
+                    );
+                }
+
+                return false;
+            }
+        };
+
+        let row_group_to_row_ids =
+            Self::group_row_ids(apply_res, row_group_size, parquet_meta.num_row_groups());
+        Self::prune_row_groups_by_rows(
+            parquet_meta,
+            row_group_to_row_ids,
+            output,
+            &mut metrics.num_row_groups_vector_index_filtered,
+            &mut metrics.num_rows_in_row_group_vector_index_filtered,
+        );
+
+        true
+    }
+
+    /// Prunes row groups by bloom filter index, row groups that definitely don't
+    /// contain the values of the predicates are removed. Returns `true` if the row
+    /// groups are pruned.
+    async fn prune_row_groups_by_bloom_filter_index(
+        &self,
+        parquet_meta: &ParquetMetaData,
+        output: &mut BTreeMap<usize, Option<RowSelection>>,
+        metrics: &mut ReaderFilterMetrics,
+    ) -> bool {
+        let Some(index_applier) = &self.bloom_filter_index_applier else {
+            return false;
+        };
+        if !self.file_handle.meta_ref().bloom_filter_index_available() {
+            return false;
+        }
+
+        let apply_res = match index_applier
+            .apply(self.file_handle.file_id(), parquet_meta.num_row_groups())
+            .await
+        {
+            Ok(Some(res)) => res,
+            Ok(None) => return false,
+            Err(err) => {
+                if cfg!(any(test, feature = "test")) {
+                    panic!(
+                        "Failed to apply bloom filter index, region_id: {}, file_id: {}, err: {}",
+                        self.file_handle.region_id(),
+                        self.file_handle.file_id(),
+                        err
+                    );
+                } else {
+                    warn!(
+                        err; "Failed to apply bloom filter index, region_id: {}, file_id: {}",
+                        self.file_handle.region_id(), self.file_handle.file_id()
+                    );
+                }
+
+                return false;
+            }
+        };
+
+        output.retain(|row_group, _| {
+            if apply_res.contains(row_group) {
+                return true;
+            }
+            metrics.num_row_groups_bloom_filter_index_filtered += 1;
+            metrics.num_rows_in_row_group_bloom_filter_index_filtered +=
+                parquet_meta.row_group(*row_group).num_rows() as usize;
+            false
+        });
+
+        true
+    }
+
     /// Groups row IDs into row groups, with each group's row IDs starting from 0.
     fn group_row_ids(
         row_ids: BTreeSet<u32>,
@@ -738,6 +881,10 @@ pub(crate) struct ReaderFilterMetrics {
     pub(crate) num_row_groups_inverted_index_filtered: usize,
     /// Number of row groups filtered by min-max index.
     pub(crate) num_row_groups_min_max_filtered: usize,
+    /// Number of row groups filtered by vector index.
+    pub(crate) num_row_groups_vector_index_filtered: usize,
+    /// Number of row groups filtered by bloom filter index.
+    pub(crate) num_row_groups_bloom_filter_index_filtered: usize,
     /// Number of rows filtered by precise filter.
     pub(crate) num_rows_precise_filtered: usize,
     /// Number of rows in row group before filtering.
@@ -746,6 +893,10 @@ pub(crate) struct ReaderFilterMetrics {
     pub(crate) num_rows_in_row_group_fulltext_index_filtered: usize,
     /// Number of rows in row group filtered by inverted index.
     pub(crate) num_rows_in_row_group_inverted_index_filtered: usize,
+    /// Number of rows in row group filtered by vector index.
+    pub(crate) num_rows_in_row_group_vector_index_filtered: usize,
+    /// Number of rows in row group filtered by bloom filter index.
+    pub(crate) num_rows_in_row_group_bloom_filter_index_filtered: usize,
 }
 
 impl ReaderFilterMetrics {
@@ -755,12 +906,19 @@ impl ReaderFilterMetrics {
         self.num_row_groups_fulltext_index_filtered += other.num_row_groups_fulltext_index_filtered;
         self.num_row_groups_inverted_index_filtered += other.num_row_groups_inverted_index_filtered;
         self.num_row_groups_min_max_filtered += other.num_row_groups_min_max_filtered;
+        self.num_row_groups_vector_index_filtered += other.num_row_groups_vector_index_filtered;
+        self.num_row_groups_bloom_filter_index_filtered +=
+            other.num_row_groups_bloom_filter_index_filtered;
         self.num_rows_precise_filtered += other.num_rows_precise_filtered;
         self.num_rows_in_row_group_before_filtering += other.num_rows_in_row_group_before_filtering;
         self.num_rows_in_row_group_fulltext_index_filtered +=
             other.num_rows_in_row_group_fulltext_index_filtered;
         self.num_rows_in_row_group_inverted_index_filtered +=
             other.num_rows_in_row_group_inverted_index_filtered;
+        self.num_rows_in_row_group_vector_index_filtered +=
+            other.num_rows_in_row_group_vector_index_filtered;
+        self.num_rows_in_row_group_bloom_filter_index_filtered +=
+            other.num_rows_in_row_group_bloom_filter_index_filtered;
     }
 
     /// Reports metrics.
@@ -777,6 +935,12 @@ impl ReaderFilterMetrics {
         READ_ROW_GROUPS_TOTAL
             .with_label_values(&["minmax_index_filtered"])
             .inc_by(self.num_row_groups_min_max_filtered as u64);
+        READ_ROW_GROUPS_TOTAL
+            .with_label_values(&["vector_index_filtered"])
+            .inc_by(self.num_row_groups_vector_index_filtered as u64);
+        READ_ROW_GROUPS_TOTAL
+            .with_label_values(&["bloom_filter_index_filtered"])
+            .inc_by(self.num_row_groups_bloom_filter_index_filtered as u64);
         PRECISE_FILTER_ROWS_TOTAL
             .with_label_values(&["parquet"])
             .inc_by(self.num_rows_precise_filtered as u64);
@@ -789,6 +953,12 @@ impl ReaderFilterMetrics {
         READ_ROWS_IN_ROW_GROUP_TOTAL
             .with_label_values(&["inverted_index_filtered"])
             .inc_by(self.num_rows_in_row_group_inverted_index_filtered as u64);
+        READ_ROWS_IN_ROW_GROUP_TOTAL
+            .with_label_values(&["vector_index_filtered"])
+            .inc_by(self.num_rows_in_row_group_vector_index_filtered as u64);
+        READ_ROWS_IN_ROW_GROUP_TOTAL
+            .with_label_values(&["bloom_filter_index_filtered"])
+            .inc_by(self.num_rows_in_row_group_bloom_filter_index_filtered as u64);
     }
 }
 
@@ -104,9 +104,9 @@ impl<S: LogStore> Wal<S> {
         region_id: RegionId,
     ) -> Box<dyn WalEntryReader> {
         match provider {
-            Provider::RaftEngine(_) => Box::new(LogStoreEntryReader::new(
-                LogStoreRawEntryReader::new(self.store.clone()),
-            )),
+            Provider::RaftEngine(_) | Provider::ObjectStore(_) => Box::new(
+                LogStoreEntryReader::new(LogStoreRawEntryReader::new(self.store.clone())),
+            ),
             Provider::Kafka(_) => Box::new(LogStoreEntryReader::new(RegionRawEntryReader::new(
                 LogStoreRawEntryReader::new(self.store.clone()),
                 region_id,
@@ -122,7 +122,7 @@ impl<S: LogStore> Wal<S> {
         namespace: &'a Provider,
     ) -> Result<WalEntryStream<'a>> {
         match namespace {
-            Provider::RaftEngine(_) => {
+            Provider::RaftEngine(_) | Provider::ObjectStore(_) => {
                 LogStoreEntryReader::new(LogStoreRawEntryReader::new(self.store.clone()))
                     .read(namespace, start_id)
             }
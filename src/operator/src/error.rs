@@ -313,6 +313,34 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("User `{name}` already exists"))]
+    UserAlreadyExists {
+        name: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("User `{name}` not found"))]
+    UserNotFound {
+        name: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("User `{name}` was modified concurrently, please retry"))]
+    UserModified {
+        name: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to access user metadata"))]
+    UserMetadata {
+        source: common_meta::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Schema `{name}` is read-only"))]
     SchemaReadOnly {
         name: String,
@@ -386,6 +414,20 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Invalid query id `{id}`"))]
+    InvalidProcessId {
+        id: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Query `{id}` not found"))]
+    ProcessNotFound {
+        id: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Not supported: {}", feat))]
     NotSupported { feat: String },
 
@@ -776,6 +818,7 @@ impl ErrorExt for Error {
             | Error::SchemaNotFound { .. }
             | Error::SchemaExists { .. }
             | Error::SchemaInUse { .. }
+            | Error::UserAlreadyExists { .. }
             | Error::ColumnNotFound { .. }
             | Error::BuildRegex { .. }
             | Error::InvalidSchema { .. }
@@ -808,6 +851,14 @@ impl ErrorExt for Error {
 
             Error::TableMetadataManager { source, .. } => source.status_code(),
 
+            Error::UserNotFound { .. } => StatusCode::UserNotFound,
+            Error::UserModified { .. } => StatusCode::Unexpected,
+            Error::UserMetadata { source, .. } => source.status_code(),
+
+            Error::InvalidProcessId { .. } | Error::ProcessNotFound { .. } => {
+                StatusCode::InvalidArguments
+            }
+
             Error::ParseSql { source, .. } => source.status_code(),
 
             Error::InvalidateTableCache { source, .. } => source.status_code(),
@@ -18,9 +18,11 @@ mod copy_table_to;
 mod ddl;
 mod describe;
 mod dml;
+mod process;
 mod set;
 mod show;
 mod tql;
+mod user;
 
 use std::sync::Arc;
 
@@ -31,6 +33,7 @@ use common_meta::cache::TableRouteCacheRef;
 use common_meta::cache_invalidator::CacheInvalidatorRef;
 use common_meta::ddl::ProcedureExecutorRef;
 use common_meta::key::flow::{FlowMetadataManager, FlowMetadataManagerRef};
+use common_meta::key::user::{UserManager, UserManagerRef};
 use common_meta::key::view_info::{ViewInfoManager, ViewInfoManagerRef};
 use common_meta::key::{TableMetadataManager, TableMetadataManagerRef};
 use common_meta::kv_backend::KvBackendRef;
@@ -72,6 +75,7 @@ pub struct StatementExecutor {
     table_metadata_manager: TableMetadataManagerRef,
     flow_metadata_manager: FlowMetadataManagerRef,
     view_info_manager: ViewInfoManagerRef,
+    user_manager: UserManagerRef,
     partition_manager: PartitionRuleManagerRef,
     cache_invalidator: CacheInvalidatorRef,
     inserter: InserterRef,
@@ -96,6 +100,7 @@ impl StatementExecutor {
             table_metadata_manager: Arc::new(TableMetadataManager::new(kv_backend.clone())),
             flow_metadata_manager: Arc::new(FlowMetadataManager::new(kv_backend.clone())),
             view_info_manager: Arc::new(ViewInfoManager::new(kv_backend.clone())),
+            user_manager: Arc::new(UserManager::new(kv_backend.clone())),
             partition_manager: Arc::new(PartitionRuleManager::new(kv_backend, table_route_cache)),
             cache_invalidator,
             inserter,
@@ -276,7 +281,14 @@ impl StatementExecutor {
             }
             Statement::ShowIndex(show_index) => self.show_index(show_index, query_ctx).await,
             Statement::ShowStatus(_) => self.show_status(query_ctx).await,
+            Statement::ShowProcesslist(stmt) => self.show_processlist(stmt, query_ctx).await,
             Statement::Use(db) => self.use_database(db, query_ctx).await,
+            Statement::CreateUser(stmt) => self.create_user(stmt).await,
+            Statement::DropUser(stmt) => self.drop_user(stmt).await,
+            Statement::AlterUser(stmt) => self.alter_user(stmt).await,
+            Statement::Grant(stmt) => self.grant(stmt, query_ctx).await,
+            Statement::Revoke(stmt) => self.revoke(stmt, query_ctx).await,
+            Statement::KillQuery(stmt) => self.kill_query(stmt, query_ctx).await,
         }
     }
 
@@ -0,0 +1,69 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use catalog::kvbackend::KvBackendCatalogManager;
+use catalog::process_manager::parse_process_id;
+use common_query::Output;
+use common_telemetry::tracing;
+use session::context::QueryContextRef;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::kill::KillQuery;
+use sql::statements::show::ShowProcesslist;
+
+use super::StatementExecutor;
+use crate::error::{
+    CatalogSnafu, ExecuteStatementSnafu, InvalidProcessIdSnafu, NotSupportedSnafu,
+    ProcessNotFoundSnafu, Result,
+};
+
+impl StatementExecutor {
+    #[tracing::instrument(skip_all)]
+    pub(super) async fn show_processlist(
+        &self,
+        stmt: ShowProcesslist,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        query::sql::show_processlist(stmt, &self.query_engine, &self.catalog_manager, query_ctx)
+            .await
+            .context(ExecuteStatementSnafu)
+    }
+
+    /// Kills the query of the current catalog by its id, the query may run on other frontends.
+    #[tracing::instrument(skip_all)]
+    pub(super) async fn kill_query(
+        &self,
+        stmt: KillQuery,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let process_manager = self
+            .catalog_manager
+            .as_any()
+            .downcast_ref::<KvBackendCatalogManager>()
+            .and_then(|manager| manager.process_manager())
+            .context(NotSupportedSnafu {
+                feat: "KILL QUERY on this node",
+            })?;
+
+        let (frontend, id) =
+            parse_process_id(&stmt.id).context(InvalidProcessIdSnafu { id: &stmt.id })?;
+        let frontend = frontend.unwrap_or_else(|| process_manager.server_addr());
+        let killed = process_manager
+            .kill(frontend, query_ctx.current_catalog(), id)
+            .await
+            .context(CatalogSnafu)?;
+        ensure!(killed, ProcessNotFoundSnafu { id: &stmt.id });
+
+        Ok(Output::new_with_affected_rows(1))
+    }
+}
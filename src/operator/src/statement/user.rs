@@ -0,0 +1,145 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_error::ext::BoxedError;
+use common_meta::key::user::{GrantObject, Privilege, UserValue};
+use common_query::Output;
+use common_telemetry::info;
+use session::context::QueryContextRef;
+use session::table_name::table_idents_to_full_name;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::user::{self as sql_user, AlterUser, CreateUser, DropUser, Grant, Revoke};
+
+use super::StatementExecutor;
+use crate::error::{
+    ExternalSnafu, Result, UserAlreadyExistsSnafu, UserMetadataSnafu, UserModifiedSnafu,
+    UserNotFoundSnafu,
+};
+
+impl StatementExecutor {
+    pub async fn create_user(&self, stmt: CreateUser) -> Result<Output> {
+        let value = UserValue::new(&auth::hash_password(stmt.password.as_bytes()));
+        let created = self
+            .user_manager
+            .create(&stmt.name, &value)
+            .await
+            .context(UserMetadataSnafu)?;
+        ensure!(
+            created || stmt.if_not_exists,
+            UserAlreadyExistsSnafu { name: &stmt.name }
+        );
+        if created {
+            info!("User {} is created", stmt.name);
+        }
+        Ok(Output::new_with_affected_rows(0))
+    }
+
+    pub async fn drop_user(&self, stmt: DropUser) -> Result<Output> {
+        let deleted = self
+            .user_manager
+            .delete(&stmt.name)
+            .await
+            .context(UserMetadataSnafu)?;
+        ensure!(
+            deleted || stmt.if_exists,
+            UserNotFoundSnafu { name: &stmt.name }
+        );
+        if deleted {
+            info!("User {} is dropped", stmt.name);
+        }
+        Ok(Output::new_with_affected_rows(0))
+    }
+
+    pub async fn alter_user(&self, stmt: AlterUser) -> Result<Output> {
+        let password_hash = auth::hash_password(stmt.password.as_bytes());
+        self.update_user(&stmt.name, |value| value.set_password_hash(&password_hash))
+            .await
+    }
+
+    pub async fn grant(&self, stmt: Grant, query_ctx: QueryContextRef) -> Result<Output> {
+        let object = to_grant_object(&stmt.object, &query_ctx)?;
+        let privileges = to_privileges(&stmt.privileges);
+        self.update_user(&stmt.user, |value| value.grant(&object, &privileges))
+            .await
+    }
+
+    pub async fn revoke(&self, stmt: Revoke, query_ctx: QueryContextRef) -> Result<Output> {
+        let object = to_grant_object(&stmt.object, &query_ctx)?;
+        let privileges = to_privileges(&stmt.privileges);
+        self.update_user(&stmt.user, |value| value.revoke(&object, &privileges))
+            .await
+    }
+
+    /// Applies `f` to the user value and writes it back if the user isn't
+    /// modified by others in the meantime.
+    async fn update_user<F>(&self, name: &str, f: F) -> Result<Output>
+    where
+        F: FnOnce(&mut UserValue),
+    {
+        let current = self
+            .user_manager
+            .get(name)
+            .await
+            .context(UserMetadataSnafu)?
+            .context(UserNotFoundSnafu { name })?;
+        let mut new_value = current.get_inner_ref().clone();
+        f(&mut new_value);
+
+        let updated = self
+            .user_manager
+            .update(name, &current, &new_value)
+            .await
+            .context(UserMetadataSnafu)?;
+        ensure!(updated, UserModifiedSnafu { name });
+        Ok(Output::new_with_affected_rows(0))
+    }
+}
+
+fn to_privileges(privileges: &[sql_user::Privilege]) -> Vec<Privilege> {
+    privileges
+        .iter()
+        .map(|p| match p {
+            sql_user::Privilege::Read => Privilege::Read,
+            sql_user::Privilege::Write => Privilege::Write,
+        })
+        .collect()
+}
+
+/// Resolves the object in `GRANT` and `REVOKE` statements by the current catalog and schema.
+fn to_grant_object(
+    object: &sql_user::GrantObject,
+    query_ctx: &QueryContextRef,
+) -> Result<GrantObject> {
+    let object = match object {
+        sql_user::GrantObject::Global => GrantObject::Global,
+        sql_user::GrantObject::Database(schema) => GrantObject::Database {
+            catalog: query_ctx.current_catalog().to_string(),
+            schema: schema
+                .as_ref()
+                .map(|s| s.value.clone())
+                .unwrap_or_else(|| query_ctx.current_schema()),
+        },
+        sql_user::GrantObject::Table(table_name) => {
+            let (catalog, schema, table) = table_idents_to_full_name(table_name, query_ctx)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            GrantObject::Table {
+                catalog,
+                schema,
+                table,
+            }
+        }
+    };
+    Ok(object)
+}
@@ -193,6 +193,20 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display(
+        "Invalid split point {} on column {} for partition expr: {}",
+        value,
+        column,
+        expr
+    ))]
+    InvalidSplitPoint {
+        expr: PartitionExpr,
+        column: String,
+        value: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Unexpected: {err_msg}"))]
     Unexpected {
         err_msg: String,
@@ -210,6 +224,7 @@ impl ErrorExt for Error {
             Error::ConjunctExprWithNonExpr { .. }
             | Error::UnclosedValue { .. }
             | Error::InvalidExpr { .. }
+            | Error::InvalidSplitPoint { .. }
             | Error::UndefinedColumn { .. } => StatusCode::InvalidArguments,
 
             Error::FindRegion { .. }
@@ -23,6 +23,7 @@ pub mod manager;
 pub mod multi_dim;
 pub mod partition;
 pub mod range;
+pub mod repartition;
 pub mod splitter;
 
 pub use crate::partition::{PartitionRule, PartitionRuleRef};
@@ -52,26 +52,42 @@ impl MultiDimPartitionRule {
         regions: Vec<RegionNumber>,
         exprs: Vec<PartitionExpr>,
     ) -> Result<Self> {
+        let rule = Self::new_unchecked(partition_columns, regions, exprs);
+
+        let mut checker = RuleChecker::new(&rule);
+        checker.check()?;
+
+        Ok(rule)
+    }
+
+    /// Creates the rule without checking whether the expressions cover all values.
+    pub(crate) fn new_unchecked(
+        partition_columns: Vec<String>,
+        regions: Vec<RegionNumber>,
+        exprs: Vec<PartitionExpr>,
+    ) -> Self {
         let name_to_index = partition_columns
             .iter()
             .enumerate()
             .map(|(i, name)| (name.clone(), i))
             .collect::<HashMap<_, _>>();
 
-        let rule = Self {
+        Self {
             partition_columns,
             name_to_index,
             regions,
             exprs,
-        };
-
-        let mut checker = RuleChecker::new(&rule);
-        checker.check()?;
-
-        Ok(rule)
+        }
     }
 
     fn find_region(&self, values: &[Value]) -> Result<RegionNumber> {
+        // return the default region number if no expr matches
+        Ok(self.find_matched_region(values)?.unwrap_or(0))
+    }
+
+    /// Returns the region whose expression matches the `values`, or `None` if no
+    /// expression matches.
+    pub(crate) fn find_matched_region(&self, values: &[Value]) -> Result<Option<RegionNumber>> {
         ensure!(
             values.len() == self.partition_columns.len(),
             error::RegionKeysSizeSnafu {
@@ -82,24 +98,23 @@ impl MultiDimPartitionRule {
 
         for (region_index, expr) in self.exprs.iter().enumerate() {
             if self.evaluate_expr(expr, values)? {
-                return Ok(self.regions[region_index]);
+                return Ok(Some(self.regions[region_index]));
             }
         }
 
-        // return the default region number
-        Ok(0)
+        Ok(None)
     }
 
     fn evaluate_expr(&self, expr: &PartitionExpr, values: &[Value]) -> Result<bool> {
         match (expr.lhs.as_ref(), expr.rhs.as_ref()) {
             (Operand::Column(name), Operand::Value(r)) => {
-                let index = self.name_to_index.get(name).unwrap();
-                let l = &values[*index];
+                let index = self.column_index(name)?;
+                let l = &values[index];
                 Self::perform_op(l, &expr.op, r)
             }
             (Operand::Value(l), Operand::Column(name)) => {
-                let index = self.name_to_index.get(name).unwrap();
-                let r = &values[*index];
+                let index = self.column_index(name)?;
+                let r = &values[index];
                 Self::perform_op(l, &expr.op, r)
             }
             (Operand::Expr(lhs), Operand::Expr(rhs)) => {
@@ -115,6 +130,15 @@ impl MultiDimPartitionRule {
         }
     }
 
+    fn column_index(&self, name: &str) -> Result<usize> {
+        self.name_to_index
+            .get(name)
+            .copied()
+            .with_context(|| UndefinedColumnSnafu {
+                column: name.to_string(),
+            })
+    }
+
     fn perform_op(lhs: &Value, op: &RestrictedOp, rhs: &Value) -> Result<bool> {
         let result = match op {
             RestrictedOp::Eq => lhs.eq(rhs),
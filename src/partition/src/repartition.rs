@@ -0,0 +1,387 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rewrites partition expressions of regions that are split.
+
+use datatypes::value::Value;
+use snafu::{ensure, OptionExt};
+use store_api::storage::RegionNumber;
+
+use crate::error::{InvalidSplitPointSnafu, Result};
+use crate::expr::{Operand, PartitionExpr, RestrictedOp};
+use crate::multi_dim::MultiDimPartitionRule;
+use crate::PartitionRule;
+
+/// Bounds of a column, each bound is a value and whether it's inclusive.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ColumnRange {
+    lower: Option<(Value, bool)>,
+    upper: Option<(Value, bool)>,
+}
+
+/// Ranges of columns in a partition expression, in the order of their first appearance.
+#[derive(Debug, Clone, PartialEq)]
+struct Ranges(Vec<(String, ColumnRange)>);
+
+impl Ranges {
+    /// Extracts ranges from a conjunction of comparisons between columns and values.
+    /// Returns `None` if the expression is not such a conjunction.
+    fn try_from_expr(expr: &PartitionExpr) -> Option<Self> {
+        let mut ranges = Ranges(vec![]);
+        ranges.collect(expr)?;
+        Some(ranges)
+    }
+
+    fn collect(&mut self, expr: &PartitionExpr) -> Option<()> {
+        let (column, op, value) = match (expr.lhs.as_ref(), &expr.op, expr.rhs.as_ref()) {
+            (Operand::Expr(lhs), RestrictedOp::And, Operand::Expr(rhs)) => {
+                self.collect(lhs)?;
+                return self.collect(rhs);
+            }
+            (Operand::Column(column), op, Operand::Value(value)) => (column, op.clone(), value),
+            (Operand::Value(value), op, Operand::Column(column)) => (column, flip(op)?, value),
+            _ => return None,
+        };
+
+        let range = self.range_mut(column);
+        let (bound, inclusive) = match op {
+            RestrictedOp::Lt => (&mut range.upper, false),
+            RestrictedOp::LtEq => (&mut range.upper, true),
+            RestrictedOp::Gt => (&mut range.lower, false),
+            RestrictedOp::GtEq => (&mut range.lower, true),
+            _ => return None,
+        };
+        // Duplicated bounds on the same side are not supported.
+        if bound.is_some() {
+            return None;
+        }
+        *bound = Some((value.clone(), inclusive));
+        Some(())
+    }
+
+    fn get(&self, column: &str) -> Option<&ColumnRange> {
+        self.0
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, range)| range)
+    }
+
+    fn range_mut(&mut self, column: &str) -> &mut ColumnRange {
+        let index = match self.0.iter().position(|(name, _)| name == column) {
+            Some(index) => index,
+            None => {
+                self.0.push((column.to_string(), ColumnRange::default()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[index].1
+    }
+
+    /// Converts ranges back to a partition expression. Returns `None` if there is no bound.
+    fn to_expr(&self) -> Option<PartitionExpr> {
+        let mut exprs = vec![];
+        for (column, range) in &self.0 {
+            if let Some((value, inclusive)) = &range.lower {
+                let op = if *inclusive {
+                    RestrictedOp::GtEq
+                } else {
+                    RestrictedOp::Gt
+                };
+                exprs.push(compare(column, op, value));
+            }
+            if let Some((value, inclusive)) = &range.upper {
+                let op = if *inclusive {
+                    RestrictedOp::LtEq
+                } else {
+                    RestrictedOp::Lt
+                };
+                exprs.push(compare(column, op, value));
+            }
+        }
+
+        exprs.into_iter().reduce(|lhs, rhs| {
+            PartitionExpr::new(Operand::Expr(lhs), RestrictedOp::And, Operand::Expr(rhs))
+        })
+    }
+}
+
+/// Swaps the operands of a comparison, e.g. `10 < a` is the same as `a > 10`.
+fn flip(op: &RestrictedOp) -> Option<RestrictedOp> {
+    match op {
+        RestrictedOp::Lt => Some(RestrictedOp::Gt),
+        RestrictedOp::LtEq => Some(RestrictedOp::GtEq),
+        RestrictedOp::Gt => Some(RestrictedOp::Lt),
+        RestrictedOp::GtEq => Some(RestrictedOp::LtEq),
+        _ => None,
+    }
+}
+
+fn compare(column: &str, op: RestrictedOp, value: &Value) -> PartitionExpr {
+    PartitionExpr::new(
+        Operand::Column(column.to_string()),
+        op,
+        Operand::Value(value.clone()),
+    )
+}
+
+/// Splits the partition expression of a region at `value` of `column`. The first returned
+/// expression contains rows whose `column` is less than `value` and the second one contains
+/// the rest.
+///
+/// Only conjunctions of ranges like `a >= 10 AND a < 20 AND b < 'x'` can be split, and the
+/// `value` must be inside the range of `column` so neither of the new regions is empty.
+pub fn split_partition_expr(
+    expr: &PartitionExpr,
+    column: &str,
+    value: &Value,
+) -> Result<(PartitionExpr, PartitionExpr)> {
+    let invalid_split_point = || InvalidSplitPointSnafu {
+        expr: expr.clone(),
+        column,
+        value: value.to_string(),
+    };
+    let ranges = Ranges::try_from_expr(expr).with_context(invalid_split_point)?;
+
+    let range = ranges.get(column).cloned().unwrap_or_default();
+    let above_lower = range
+        .lower
+        .as_ref()
+        .map_or(true, |(lower, _)| value > lower);
+    let below_upper = range
+        .upper
+        .as_ref()
+        .map_or(true, |(upper, _)| value < upper);
+    ensure!(above_lower && below_upper, invalid_split_point());
+
+    let mut lower = ranges.clone();
+    lower.range_mut(column).upper = Some((value.clone(), false));
+    let mut upper = ranges;
+    upper.range_mut(column).lower = Some((value.clone(), true));
+
+    // Safety: both of them have the bound on the split point.
+    Ok((lower.to_expr().unwrap(), upper.to_expr().unwrap()))
+}
+
+/// Finds a point that splits the partition expression into two halves.
+///
+/// Returns the first column whose range has integer bounds on both sides and the middle of
+/// that range, or `None` if there is no such column.
+///
+/// Unbounded edges are not handled: a range like `a < 10`, e.g. the first or the last
+/// region of a table, has no middle without knowing the distribution of values, so such
+/// regions are never split at a found point.
+pub fn find_split_point(expr: &PartitionExpr) -> Option<(String, Value)> {
+    let ranges = Ranges::try_from_expr(expr)?;
+    ranges.0.iter().find_map(|(column, range)| {
+        let (lower, lower_inclusive) = range.lower.as_ref()?;
+        let (upper, upper_inclusive) = range.upper.as_ref()?;
+        // Normalizes the range to `[start, end)`.
+        let start = integer_of(lower)? + if *lower_inclusive { 0 } else { 1 };
+        let end = integer_of(upper)? + if *upper_inclusive { 1 } else { 0 };
+        let middle = start + (end - start) / 2;
+        if middle <= start || middle >= end {
+            return None;
+        }
+        Some((column.clone(), integer_like(lower, middle)?))
+    })
+}
+
+fn integer_of(value: &Value) -> Option<i128> {
+    match value {
+        Value::Int8(v) => Some(*v as i128),
+        Value::Int16(v) => Some(*v as i128),
+        Value::Int32(v) => Some(*v as i128),
+        Value::Int64(v) => Some(*v as i128),
+        Value::UInt8(v) => Some(*v as i128),
+        Value::UInt16(v) => Some(*v as i128),
+        Value::UInt32(v) => Some(*v as i128),
+        Value::UInt64(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+/// Returns a value of the same integer type as `like`.
+fn integer_like(like: &Value, v: i128) -> Option<Value> {
+    let value = match like {
+        Value::Int8(_) => Value::Int8(v.try_into().ok()?),
+        Value::Int16(_) => Value::Int16(v.try_into().ok()?),
+        Value::Int32(_) => Value::Int32(v.try_into().ok()?),
+        Value::Int64(_) => Value::Int64(v.try_into().ok()?),
+        Value::UInt8(_) => Value::UInt8(v.try_into().ok()?),
+        Value::UInt16(_) => Value::UInt16(v.try_into().ok()?),
+        Value::UInt32(_) => Value::UInt32(v.try_into().ok()?),
+        Value::UInt64(_) => Value::UInt64(v.try_into().ok()?),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Routes rows of source regions to target regions during repartitioning.
+///
+/// Unlike the partition rule of a table, expressions of target regions only cover the
+/// values of the source regions, so rows matching no target region are reported as `None`.
+#[derive(Debug)]
+pub struct RepartitionRouter {
+    rule: MultiDimPartitionRule,
+}
+
+impl RepartitionRouter {
+    pub fn new(
+        partition_columns: Vec<String>,
+        regions: Vec<RegionNumber>,
+        exprs: Vec<PartitionExpr>,
+    ) -> Self {
+        Self {
+            rule: MultiDimPartitionRule::new_unchecked(partition_columns, regions, exprs),
+        }
+    }
+
+    pub fn partition_columns(&self) -> Vec<String> {
+        self.rule.partition_columns()
+    }
+
+    /// Finds the target region of a row by values of the partition columns.
+    pub fn find_region(&self, values: &[Value]) -> Result<Option<RegionNumber>> {
+        self.rule.find_matched_region(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use super::*;
+    use crate::error::Error;
+
+    fn and(lhs: PartitionExpr, rhs: PartitionExpr) -> PartitionExpr {
+        PartitionExpr::new(Operand::Expr(lhs), RestrictedOp::And, Operand::Expr(rhs))
+    }
+
+    fn int(v: i64) -> Value {
+        Value::Int64(v)
+    }
+
+    #[test]
+    fn test_split_partition_expr() {
+        // a >= 10 AND a < 20
+        let expr = and(
+            compare("a", RestrictedOp::GtEq, &int(10)),
+            compare("a", RestrictedOp::Lt, &int(20)),
+        );
+        let (lower, upper) = split_partition_expr(&expr, "a", &int(15)).unwrap();
+        assert_eq!("a >= 10 AND a < 15", lower.to_string());
+        assert_eq!("a >= 15 AND a < 20", upper.to_string());
+
+        // 10 <= a AND b < 'x', splits on an unbounded side of another column.
+        let expr = and(
+            PartitionExpr::new(
+                Operand::Value(int(10)),
+                RestrictedOp::LtEq,
+                Operand::Column("a".to_string()),
+            ),
+            compare("b", RestrictedOp::Lt, &Value::String("x".into())),
+        );
+        let (lower, upper) = split_partition_expr(&expr, "b", &Value::String("m".into())).unwrap();
+        assert_eq!("a >= 10 AND b < m", lower.to_string());
+        assert_eq!("a >= 10 AND b >= m AND b < x", upper.to_string());
+
+        // Adds the bound of a column that is not in the expr.
+        let expr = compare("a", RestrictedOp::Lt, &int(20));
+        let (lower, upper) = split_partition_expr(&expr, "c", &int(0)).unwrap();
+        assert_eq!("a < 20 AND c < 0", lower.to_string());
+        assert_eq!("a < 20 AND c >= 0", upper.to_string());
+    }
+
+    #[test]
+    fn test_split_partition_expr_invalid() {
+        let expr = and(
+            compare("a", RestrictedOp::GtEq, &int(10)),
+            compare("a", RestrictedOp::Lt, &int(20)),
+        );
+        for value in [5, 10, 20, 30] {
+            let err = split_partition_expr(&expr, "a", &int(value)).unwrap_err();
+            assert_matches!(err, Error::InvalidSplitPoint { .. });
+        }
+
+        let expr = PartitionExpr::new(
+            Operand::Expr(compare("a", RestrictedOp::Lt, &int(10))),
+            RestrictedOp::Or,
+            Operand::Expr(compare("a", RestrictedOp::Gt, &int(20))),
+        );
+        let err = split_partition_expr(&expr, "a", &int(5)).unwrap_err();
+        assert_matches!(err, Error::InvalidSplitPoint { .. });
+
+        let expr = compare("a", RestrictedOp::Eq, &int(10));
+        let err = split_partition_expr(&expr, "a", &int(5)).unwrap_err();
+        assert_matches!(err, Error::InvalidSplitPoint { .. });
+    }
+
+    #[test]
+    fn test_find_split_point() {
+        let expr = and(
+            compare("a", RestrictedOp::GtEq, &int(10)),
+            compare("a", RestrictedOp::Lt, &int(20)),
+        );
+        assert_eq!(Some(("a".to_string(), int(15))), find_split_point(&expr));
+
+        // Skips unbounded columns.
+        let expr = and(
+            compare("a", RestrictedOp::Lt, &int(20)),
+            and(
+                compare("b", RestrictedOp::Gt, &Value::UInt32(0)),
+                compare("b", RestrictedOp::LtEq, &Value::UInt32(3)),
+            ),
+        );
+        assert_eq!(
+            Some(("b".to_string(), Value::UInt32(2))),
+            find_split_point(&expr)
+        );
+
+        // The range is too narrow to split.
+        let expr = and(
+            compare("a", RestrictedOp::GtEq, &int(10)),
+            compare("a", RestrictedOp::Lt, &int(11)),
+        );
+        assert_eq!(None, find_split_point(&expr));
+
+        let expr = compare("b", RestrictedOp::Lt, &Value::String("x".into()));
+        assert_eq!(None, find_split_point(&expr));
+
+        // Unbounded edges.
+        let expr = compare("a", RestrictedOp::Lt, &int(10));
+        assert_eq!(None, find_split_point(&expr));
+        let expr = compare("a", RestrictedOp::GtEq, &int(10));
+        assert_eq!(None, find_split_point(&expr));
+    }
+
+    #[test]
+    fn test_split_keeps_rule_valid() {
+        let columns = vec!["a".to_string()];
+        let first = compare("a", RestrictedOp::Lt, &int(10));
+        let second = compare("a", RestrictedOp::GtEq, &int(10));
+        let (lower, upper) = split_partition_expr(&second, "a", &int(100)).unwrap();
+
+        MultiDimPartitionRule::try_new(
+            columns.clone(),
+            vec![0, 2, 3],
+            vec![first.clone(), lower.clone(), upper.clone()],
+        )
+        .unwrap();
+
+        let router = RepartitionRouter::new(columns, vec![2, 3], vec![lower, upper]);
+        assert_eq!(Some(2), router.find_region(&[int(10)]).unwrap());
+        assert_eq!(Some(3), router.find_region(&[int(100)]).unwrap());
+        assert_eq!(None, router.find_region(&[int(5)]).unwrap());
+    }
+}
@@ -14,6 +14,7 @@
 
 use auth::UserProviderRef;
 use common_base::Plugins;
+use frontend::access_control::BUILTIN_USER_PROVIDER;
 use frontend::error::{IllegalAuthConfigSnafu, Result};
 use frontend::frontend::FrontendOptions;
 use snafu::ResultExt;
@@ -23,7 +24,13 @@ pub async fn setup_frontend_plugins(
     plugins: &mut Plugins,
     fe_opts: &FrontendOptions,
 ) -> Result<()> {
-    if let Some(user_provider) = fe_opts.user_provider.as_ref() {
+    // The built-in user provider is set up by the frontend builder since it
+    // relies on the metadata kv backend.
+    if let Some(user_provider) = fe_opts
+        .user_provider
+        .as_ref()
+        .filter(|opt| !opt.starts_with(BUILTIN_USER_PROVIDER))
+    {
         let provider =
             auth::user_provider_from_option(user_provider).context(IllegalAuthConfigSnafu)?;
         plugins.insert::<UserProviderRef>(provider);
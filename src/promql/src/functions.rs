@@ -15,11 +15,14 @@
 mod aggr_over_time;
 mod changes;
 mod deriv;
+mod ensure_unique;
 mod extrapolate_rate;
+mod format_value;
 mod holt_winters;
 mod idelta;
 mod predict_linear;
 mod quantile;
+mod quantile_aggr;
 mod resets;
 #[cfg(test)]
 mod test_util;
@@ -33,11 +36,14 @@ use datafusion::arrow::array::{ArrayRef, Float64Array, TimestampMillisecondArray
 use datafusion::error::DataFusionError;
 use datafusion::physical_plan::ColumnarValue;
 pub use deriv::Deriv;
+pub use ensure_unique::EnsureUnique;
 pub use extrapolate_rate::{Delta, Increase, Rate};
+pub use format_value::FormatValue;
 pub use holt_winters::HoltWinters;
 pub use idelta::IDelta;
 pub use predict_linear::PredictLinear;
 pub use quantile::QuantileOverTime;
+pub use quantile_aggr::QuantileAccumulator;
 pub use resets::Resets;
 
 pub(crate) fn extract_array(columnar_value: &ColumnarValue) -> Result<ArrayRef, DataFusionError> {
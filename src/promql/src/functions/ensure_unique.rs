@@ -0,0 +1,114 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{BooleanArray, Int64Array};
+use datafusion::common::{DataFusionError, ScalarValue};
+use datafusion::logical_expr::{ScalarUDF, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datafusion_expr::create_udf;
+use datatypes::arrow::datatypes::DataType;
+
+/// Checks that the series of the "one" side in a many-to-one or one-to-many vector
+/// matching are unique on the matching labels. The input is the number of series that
+/// have the same matching labels at the same timestamp. It fails like Prometheus if
+/// any of them is larger than 1, otherwise it returns `true`.
+#[derive(Debug)]
+pub struct EnsureUnique;
+
+impl EnsureUnique {
+    pub const fn name() -> &'static str {
+        "prom_ensure_unique"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        create_udf(
+            Self::name(),
+            vec![DataType::Int64],
+            Arc::new(DataType::Boolean),
+            Volatility::Immutable,
+            Arc::new(Self::calc) as _,
+        )
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        match &input[0] {
+            ColumnarValue::Array(array) => {
+                let counts = array.as_any().downcast_ref::<Int64Array>().ok_or_else(|| {
+                    DataFusionError::Execution(format!(
+                        "{}: expect Int64 as input, found {}",
+                        Self::name(),
+                        array.data_type()
+                    ))
+                })?;
+                if counts.iter().flatten().any(|count| count > 1) {
+                    return Err(Self::many_to_many_error());
+                }
+                Ok(ColumnarValue::Array(Arc::new(BooleanArray::from(vec![
+                    true;
+                    counts.len()
+                ]))))
+            }
+            ColumnarValue::Scalar(ScalarValue::Int64(count)) => {
+                if count.is_some_and(|count| count > 1) {
+                    return Err(Self::many_to_many_error());
+                }
+                Ok(ColumnarValue::Scalar(ScalarValue::Boolean(Some(true))))
+            }
+            ColumnarValue::Scalar(value) => Err(DataFusionError::Execution(format!(
+                "{}: expect Int64 as input, found {}",
+                Self::name(),
+                value.data_type()
+            ))),
+        }
+    }
+
+    fn many_to_many_error() -> DataFusionError {
+        DataFusionError::Execution(
+            "many-to-many matching not allowed: matching labels must be unique on one side"
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ensure_unique() {
+        let input = vec![ColumnarValue::Array(Arc::new(Int64Array::from(vec![
+            Some(1),
+            None,
+            Some(1),
+        ])))];
+        let ColumnarValue::Array(result) = EnsureUnique::calc(&input).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            &BooleanArray::from(vec![true, true, true]),
+            result.as_any().downcast_ref::<BooleanArray>().unwrap()
+        );
+
+        let input = vec![ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 2])))];
+        let err = EnsureUnique::calc(&input).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("many-to-many matching not allowed"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Float64Array, StringArray};
+use datafusion::common::{DataFusionError, ScalarValue};
+use datafusion::logical_expr::{ScalarUDF, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datafusion_expr::create_udf;
+use datatypes::arrow::datatypes::DataType;
+
+/// Formats sample values to strings like Prometheus does when it uses them as label
+/// values, e.g. in `count_values`. It's `strconv.FormatFloat(v, 'f', -1, 64)` in
+/// Prometheus, so `1.0` is formatted as `1` rather than `1.0`.
+#[derive(Debug)]
+pub struct FormatValue;
+
+impl FormatValue {
+    pub const fn name() -> &'static str {
+        "prom_format_value"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        create_udf(
+            Self::name(),
+            vec![DataType::Float64],
+            Arc::new(DataType::Utf8),
+            Volatility::Immutable,
+            Arc::new(Self::calc) as _,
+        )
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        match &input[0] {
+            ColumnarValue::Array(array) => {
+                let values = array
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .ok_or_else(|| {
+                        DataFusionError::Execution(format!(
+                            "{}: expect Float64 as input, found {}",
+                            Self::name(),
+                            array.data_type()
+                        ))
+                    })?;
+                let result = values
+                    .iter()
+                    .map(|value| value.map(format_value))
+                    .collect::<StringArray>();
+                Ok(ColumnarValue::Array(Arc::new(result)))
+            }
+            ColumnarValue::Scalar(ScalarValue::Float64(value)) => Ok(ColumnarValue::Scalar(
+                ScalarValue::Utf8(value.map(format_value)),
+            )),
+            ColumnarValue::Scalar(value) => Err(DataFusionError::Execution(format!(
+                "{}: expect Float64 as input, found {}",
+                Self::name(),
+                value.data_type()
+            ))),
+        }
+    }
+}
+
+/// The shortest decimal representation without exponent, which is the same as
+/// formatting with `'f'` and precision `-1` in Go.
+fn format_value(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_values() {
+        let input = vec![ColumnarValue::Array(Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            Some(-2.5),
+            Some(0.1),
+            Some(1e21),
+            Some(f64::INFINITY),
+            Some(f64::NEG_INFINITY),
+            Some(f64::NAN),
+            None,
+        ])))];
+        let ColumnarValue::Array(result) = FormatValue::calc(&input).unwrap() else {
+            unreachable!()
+        };
+        let result = result
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Some("1"),
+                Some("-2.5"),
+                Some("0.1"),
+                Some("1000000000000000000000"),
+                Some("+Inf"),
+                Some("-Inf"),
+                Some("NaN"),
+                None,
+            ],
+            result
+        );
+    }
+}
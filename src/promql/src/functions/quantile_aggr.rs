@@ -0,0 +1,163 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, ListArray};
+use datafusion::common::{DataFusionError, ScalarValue};
+use datafusion::logical_expr::{Accumulator, AggregateUDF, Volatility};
+use datafusion_expr::create_udaf;
+use datatypes::arrow::datatypes::{DataType, Field};
+
+use crate::functions::quantile::quantile_impl;
+
+/// The `quantile` aggregator of PromQL, which calculates the φ-quantile of values across
+/// series in the same group.
+pub struct QuantileAccumulator {
+    quantile: f64,
+    values: Vec<f64>,
+}
+
+impl QuantileAccumulator {
+    pub const fn name() -> &'static str {
+        "prom_quantile"
+    }
+
+    fn new(quantile: f64) -> Self {
+        Self {
+            quantile,
+            values: vec![],
+        }
+    }
+
+    pub fn udaf(quantile: f64) -> Arc<AggregateUDF> {
+        Arc::new(create_udaf(
+            Self::name(),
+            vec![DataType::Float64],
+            Arc::new(DataType::Float64),
+            Volatility::Immutable,
+            Arc::new(move |_| Ok(Box::new(Self::new(quantile)))),
+            Arc::new(vec![DataType::List(Arc::new(Field::new(
+                "item",
+                DataType::Float64,
+                true,
+            )))]),
+        ))
+    }
+
+    fn extend_values(&mut self, array: &ArrayRef) -> Result<(), DataFusionError> {
+        let array = array
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect Float64 as value array's type, found {}",
+                    Self::name(),
+                    array.data_type()
+                ))
+            })?;
+        self.values.extend(array.iter().flatten());
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for QuantileAccumulator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuantileAccumulator")
+            .field("quantile", &self.quantile)
+            .finish()
+    }
+}
+
+impl Accumulator for QuantileAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<(), DataFusionError> {
+        self.extend_values(&values[0])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue, DataFusionError> {
+        if self.values.is_empty() {
+            return Ok(ScalarValue::Float64(None));
+        }
+        Ok(ScalarValue::Float64(quantile_impl(
+            &self.values,
+            self.quantile,
+        )))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.values.capacity() * std::mem::size_of::<f64>()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>, DataFusionError> {
+        let values = self
+            .values
+            .iter()
+            .map(|v| ScalarValue::Float64(Some(*v)))
+            .collect::<Vec<_>>();
+        Ok(vec![ScalarValue::List(ScalarValue::new_list(
+            &values,
+            &DataType::Float64,
+        ))])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<(), DataFusionError> {
+        let lists = states[0]
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect List as state array's type, found {}",
+                    Self::name(),
+                    states[0].data_type()
+                ))
+            })?;
+        for values in lists.iter().flatten() {
+            self.extend_values(&values)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile_accumulator() {
+        let mut acc = QuantileAccumulator::new(0.5);
+        assert_eq!(ScalarValue::Float64(None), acc.evaluate().unwrap());
+
+        acc.update_batch(&[Arc::new(Float64Array::from(vec![
+            Some(4.0),
+            None,
+            Some(1.0),
+        ]))])
+        .unwrap();
+
+        let mut other = QuantileAccumulator::new(0.5);
+        other
+            .update_batch(&[Arc::new(Float64Array::from(vec![3.0, 2.0]))])
+            .unwrap();
+        let state = other.state().unwrap();
+        let state_array = state[0].to_array().unwrap();
+        acc.merge_batch(&[state_array]).unwrap();
+
+        assert_eq!(ScalarValue::Float64(Some(2.5)), acc.evaluate().unwrap());
+
+        let mut acc = QuantileAccumulator::new(0.75);
+        acc.update_batch(&[Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]))])
+            .unwrap();
+        assert_eq!(ScalarValue::Float64(Some(4.0)), acc.evaluate().unwrap());
+    }
+}
@@ -322,6 +322,20 @@ pub enum Error {
         #[snafu(implicit)]
         location: Location,
     },
+
+    #[snafu(display("Failed to get vector index options"))]
+    GetVectorIndexOptions {
+        source: datatypes::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to get skipping index options"))]
+    GetSkippingIndexOptions {
+        source: datatypes::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -373,7 +387,9 @@ impl ErrorExt for Error {
             MissingTableMutationHandler { .. } => StatusCode::Unexpected,
             GetRegionMetadata { .. } => StatusCode::RegionNotReady,
             TableReadOnly { .. } => StatusCode::Unsupported,
-            GetFulltextOptions { source, .. } => source.status_code(),
+            GetFulltextOptions { source, .. }
+            | GetVectorIndexOptions { source, .. }
+            | GetSkippingIndexOptions { source, .. } => source.status_code(),
         }
     }
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod count_wildcard;
+pub mod fulltext_options;
 pub mod parallelize_scan;
 pub mod remove_duplicate;
 pub mod scan_hint;
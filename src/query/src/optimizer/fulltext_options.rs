@@ -0,0 +1,166 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_function::scalars::matches::{MATCHES_NAME, MATCHES_SCORE_NAME};
+use datafusion::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRewriter};
+use datafusion_common::{Column, DFSchemaRef, Result};
+use datafusion_expr::{lit, Expr, LogicalPlan};
+use datafusion_optimizer::analyzer::AnalyzerRule;
+use datatypes::schema::FULLTEXT_KEY;
+
+/// FulltextOptionsRule appends the fulltext options of the column to the `matches`
+/// and `matches_score` functions, so that they tokenize the text by the same analyzer
+/// as the fulltext index of the column.
+pub struct FulltextOptionsRule;
+
+impl AnalyzerRule for FulltextOptionsRule {
+    fn analyze(&self, plan: LogicalPlan, _config: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform(|plan| {
+            let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+            let mut rewriter = FulltextOptionsRewriter {
+                schemas: inputs.iter().map(|input| input.schema().clone()).collect(),
+                transformed: false,
+            };
+            let exprs = plan
+                .expressions()
+                .into_iter()
+                .map(|e| e.rewrite(&mut rewriter).map(|x| x.data))
+                .collect::<Result<Vec<_>>>()?;
+            if !rewriter.transformed {
+                return Ok(Transformed::no(plan));
+            }
+            plan.with_new_exprs(exprs, inputs).map(Transformed::yes)
+        })
+        .map(|x| x.data)
+    }
+
+    fn name(&self) -> &str {
+        "FulltextOptionsRule"
+    }
+}
+
+struct FulltextOptionsRewriter {
+    /// Schemas of the inputs of the plan node to rewrite.
+    schemas: Vec<DFSchemaRef>,
+    /// Whether any expression is rewritten.
+    transformed: bool,
+}
+
+impl FulltextOptionsRewriter {
+    fn fulltext_options(&self, column: &Column) -> Option<String> {
+        self.schemas
+            .iter()
+            .find_map(|schema| schema.field_from_column(column).ok())
+            .and_then(|field| field.metadata().get(FULLTEXT_KEY).cloned())
+    }
+}
+
+impl TreeNodeRewriter for FulltextOptionsRewriter {
+    type Node = Expr;
+
+    /// Appends the fulltext options to `matches(<column>, <pattern>)` and
+    /// `matches_score(<column>, <pattern>)` if the column has any.
+    fn f_up(&mut self, expr: Expr) -> Result<Transformed<Expr>> {
+        let Expr::ScalarFunction(mut func) = expr else {
+            return Ok(Transformed::no(expr));
+        };
+        if ![MATCHES_NAME, MATCHES_SCORE_NAME].contains(&func.name()) || func.args.len() != 2 {
+            return Ok(Transformed::no(Expr::ScalarFunction(func)));
+        }
+        let options = match &func.args[0] {
+            Expr::Column(column) => self.fulltext_options(column),
+            _ => None,
+        };
+        let Some(options) = options else {
+            return Ok(Transformed::no(Expr::ScalarFunction(func)));
+        };
+
+        func.args.push(lit(options));
+        self.transformed = true;
+        Ok(Transformed::yes(Expr::ScalarFunction(func)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use arrow::datatypes::{DataType, SchemaRef};
+    use arrow_schema::{Field, Schema};
+    use common_function::function_registry::FUNCTION_REGISTRY;
+    use common_function::scalars::udf::create_udf;
+    use datafusion::datasource::{provider_as_source, MemTable};
+    use datafusion_expr::{col, LogicalPlanBuilder, ScalarUDF};
+    use session::context::QueryContext;
+
+    use super::*;
+
+    const OPTIONS: &str = r#"{"enable":true,"analyzer":"Ngram","case-sensitive":false}"#;
+
+    fn matches(args: Vec<Expr>) -> Expr {
+        let func = FUNCTION_REGISTRY.get_function(MATCHES_NAME).unwrap();
+        let udf: ScalarUDF = create_udf(func, QueryContext::arc(), Default::default()).into();
+        udf.call(args)
+    }
+
+    fn filter_plan(predicate: Expr) -> LogicalPlan {
+        let schema = Schema::new(vec![
+            Field::new("msg", DataType::Utf8, true).with_metadata(HashMap::from([(
+                FULLTEXT_KEY.to_string(),
+                OPTIONS.to_string(),
+            )])),
+            Field::new("host", DataType::Utf8, true),
+        ]);
+        let table = MemTable::try_new(SchemaRef::from(schema), vec![]).unwrap();
+        LogicalPlanBuilder::scan("t", provider_as_source(Arc::new(table)), None)
+            .unwrap()
+            .filter(predicate)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn predicate_args(plan: &LogicalPlan) -> Vec<Expr> {
+        let LogicalPlan::Filter(filter) = plan else {
+            panic!("expect a filter, got {plan:?}");
+        };
+        let Expr::ScalarFunction(func) = &filter.predicate else {
+            panic!("expect a function, got {:?}", filter.predicate);
+        };
+        func.args.clone()
+    }
+
+    #[test]
+    fn test_append_fulltext_options() {
+        let plan = filter_plan(matches(vec![col("msg"), lit("error")]));
+        let plan = FulltextOptionsRule
+            .analyze(plan, &ConfigOptions::default())
+            .unwrap();
+        assert_eq!(
+            predicate_args(&plan),
+            vec![col("t.msg"), lit("error"), lit(OPTIONS)]
+        );
+    }
+
+    #[test]
+    fn test_skip_column_without_fulltext_options() {
+        let plan = filter_plan(matches(vec![col("host"), lit("error")]));
+        let plan = FulltextOptionsRule
+            .analyze(plan, &ConfigOptions::default())
+            .unwrap();
+        assert_eq!(predicate_args(&plan), vec![col("t.host"), lit("error")]);
+    }
+}
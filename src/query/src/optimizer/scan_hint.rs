@@ -16,14 +16,19 @@ use std::collections::HashSet;
 
 use api::v1::SemanticType;
 use arrow_schema::SortOptions;
+use common_function::scalars::vector::{
+    VEC_COS_DISTANCE_NAME, VEC_DOT_PRODUCT_NAME, VEC_L2SQ_DISTANCE_NAME,
+};
 use common_recordbatch::OrderOption;
 use datafusion::datasource::DefaultTableSource;
 use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion, TreeNodeVisitor};
-use datafusion_common::{Column, Result as DataFusionResult};
+use datafusion_common::{Column, Result as DataFusionResult, ScalarValue};
 use datafusion_expr::expr::Sort;
 use datafusion_expr::{utils, Expr, LogicalPlan};
 use datafusion_optimizer::{OptimizerConfig, OptimizerRule};
-use store_api::storage::TimeSeriesRowSelector;
+use datatypes::schema::VectorDistanceMetric;
+use datatypes::types::{binlit_as_veclit, parse_string_to_vector_type_value};
+use store_api::storage::{TimeSeriesRowSelector, VectorSearchRequest};
 
 use crate::dummy_catalog::DummyTableProvider;
 
@@ -32,6 +37,8 @@ use crate::dummy_catalog::DummyTableProvider;
 /// - the nearest order requirement to the leaf table scan node as ordering hint.
 /// - the group by columns when all aggregate functions are `last_value` as
 ///   time series row selector hint.
+/// - the vector distance and the limit when the query only fetches the nearest
+///   rows of a query vector as vector search hint.
 ///
 /// [`ScanRequest`]: store_api::storage::ScanRequest
 pub struct ScanHintRule;
@@ -96,6 +103,14 @@ impl ScanHintRule {
                             );
                         }
 
+                        // set vector search hint, the hint is only valid if the plan
+                        // has a single table scan
+                        if let Some(hint) = &visitor.vector_search
+                            && visitor.num_table_scans == 1
+                        {
+                            Self::set_vector_search_hint(adapter, hint);
+                        }
+
                         transformed = true;
                     }
                 }
@@ -159,6 +174,71 @@ impl ScanHintRule {
             adapter.with_time_series_selector_hint(TimeSeriesRowSelector::LastRow);
         }
     }
+
+    fn set_vector_search_hint(adapter: &DummyTableProvider, hint: &VectorSearchHint) {
+        let Expr::ScalarFunction(func) = &hint.distance else {
+            return;
+        };
+        // The index always returns the nearest rows, i.e. rows with the smallest distance
+        // or the largest dot product.
+        let (metric, asc) = match func.func.name() {
+            VEC_L2SQ_DISTANCE_NAME => (VectorDistanceMetric::L2sq, true),
+            VEC_COS_DISTANCE_NAME => (VectorDistanceMetric::Cosine, true),
+            VEC_DOT_PRODUCT_NAME => (VectorDistanceMetric::InnerProduct, false),
+            _ => return,
+        };
+        // Rows without vectors are not in the index, so they must be sorted last.
+        if hint.asc != asc || hint.nulls_first {
+            return;
+        }
+
+        let (column, literal) = match func.args.as_slice() {
+            [Expr::Column(column), Expr::Literal(literal)]
+            | [Expr::Literal(literal), Expr::Column(column)] => (column, literal),
+            _ => return,
+        };
+        let region_metadata = adapter.region_metadata();
+        let Some(dim) = region_metadata
+            .column_by_name(&column.name)
+            .and_then(|c| c.column_schema.data_type.as_vector())
+            .map(|t| t.dim)
+        else {
+            return;
+        };
+        let query = match literal {
+            ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+                parse_string_to_vector_type_value(s, dim)
+                    .ok()
+                    .and_then(|bytes| binlit_as_veclit(&bytes).ok())
+            }
+            ScalarValue::Binary(Some(bytes)) | ScalarValue::LargeBinary(Some(bytes)) => {
+                binlit_as_veclit(bytes)
+                    .ok()
+                    .filter(|v| v.len() == dim as usize)
+            }
+            _ => None,
+        };
+        let Some(query) = query else {
+            return;
+        };
+
+        adapter.with_vector_search_hint(VectorSearchRequest {
+            column_name: column.name.clone(),
+            query,
+            metric,
+            k: hint.k,
+        });
+    }
+}
+
+/// The vector distance that the query sorts by and the number of rows to fetch.
+struct VectorSearchHint {
+    /// The expression to sort by, columns are resolved to the aliased expressions
+    /// while traversing down the projections.
+    distance: Expr,
+    asc: bool,
+    nulls_first: bool,
+    k: usize,
 }
 
 /// Traverse and fetch hints.
@@ -170,6 +250,13 @@ struct ScanHintVisitor {
     /// This field stores saved `group_by` columns when all aggregate functions are `last_value`
     /// and the `order_by` column which should be time index.
     ts_row_selector: Option<(HashSet<Column>, Column)>,
+    /// Number of rows (including the skipped rows) to fetch from the closest limit.
+    fetch: Option<usize>,
+    /// The vector distance to sort by when there is nothing but projections between
+    /// the sort plan and the leaf node.
+    vector_search: Option<VectorSearchHint>,
+    /// Number of table scans in the plan.
+    num_table_scans: usize,
 }
 
 impl TreeNodeVisitor<'_> for ScanHintVisitor {
@@ -187,6 +274,8 @@ impl TreeNodeVisitor<'_> for ScanHintVisitor {
             self.order_expr = Some(exprs);
         }
 
+        self.collect_vector_search(node);
+
         // Get time series row selector from aggr plan
         if let LogicalPlan::Aggregate(aggregate) = node {
             let mut is_all_last_value = !aggregate.aggr_expr.is_empty();
@@ -274,7 +363,48 @@ impl TreeNodeVisitor<'_> for ScanHintVisitor {
 
 impl ScanHintVisitor {
     fn need_rewrite(&self) -> bool {
-        self.order_expr.is_some() || self.ts_row_selector.is_some()
+        self.order_expr.is_some() || self.ts_row_selector.is_some() || self.vector_search.is_some()
+    }
+
+    fn collect_vector_search(&mut self, node: &LogicalPlan) {
+        match node {
+            LogicalPlan::Limit(limit) => {
+                self.fetch = limit.fetch.map(|fetch| fetch + limit.skip);
+            }
+            LogicalPlan::Sort(sort) => {
+                self.vector_search = None;
+                let fetch = sort.fetch.or(self.fetch.take());
+                if let (Some(k), [Expr::Sort(sort_expr)]) = (fetch, sort.expr.as_slice()) {
+                    self.vector_search = Some(VectorSearchHint {
+                        distance: (*sort_expr.expr).clone(),
+                        asc: sort_expr.asc,
+                        nulls_first: sort_expr.nulls_first,
+                        k,
+                    });
+                }
+            }
+            LogicalPlan::Projection(projection) => {
+                if let Some(hint) = &mut self.vector_search
+                    && let Expr::Column(column) = &hint.distance
+                {
+                    let aliased = projection.expr.iter().find_map(|expr| match expr {
+                        Expr::Alias(alias) if alias.name == column.name => {
+                            Some((*alias.expr).clone())
+                        }
+                        _ => None,
+                    });
+                    if let Some(aliased) = aliased {
+                        hint.distance = aliased;
+                    }
+                }
+            }
+            LogicalPlan::TableScan(_) => self.num_table_scans += 1,
+            _ => {
+                // Other plans like filters may change the rows to sort.
+                self.fetch = None;
+                self.vector_search = None;
+            }
+        }
     }
 }
 
@@ -282,14 +412,50 @@ impl ScanHintVisitor {
 mod test {
     use std::sync::Arc;
 
+    use common_function::function_registry::FUNCTION_REGISTRY;
+    use common_function::scalars::udf::create_udf;
     use datafusion_expr::expr::{AggregateFunction, AggregateFunctionDefinition};
-    use datafusion_expr::{col, LogicalPlanBuilder};
+    use datafusion_expr::{col, lit, LogicalPlanBuilder, ScalarUDF};
     use datafusion_optimizer::OptimizerContext;
     use datafusion_physical_expr::expressions::LastValue;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::ColumnSchema;
+    use session::context::QueryContext;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
     use store_api::storage::RegionId;
 
     use super::*;
-    use crate::optimizer::test_util::mock_table_provider;
+    use crate::optimizer::test_util::{mock_table_provider, MetaRegionEngine};
+
+    /// Mocks a [DummyTableProvider] with schema `ts: timestamp, v: vector(2)`.
+    fn mock_vector_table_provider() -> DummyTableProvider {
+        let region_id = RegionId::new(1, 1);
+        let mut builder = RegionMetadataBuilder::new(region_id);
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("v", ConcreteDataType::vector_datatype(2), true),
+                semantic_type: SemanticType::Field,
+                column_id: 2,
+            });
+        let metadata = Arc::new(builder.build().unwrap());
+        let engine = Arc::new(MetaRegionEngine::with_metadata(metadata.clone()));
+        DummyTableProvider::new(region_id, engine, metadata)
+    }
+
+    fn vector_function(name: &str, args: Vec<Expr>) -> Expr {
+        let func = FUNCTION_REGISTRY.get_function(name).unwrap();
+        let udf: ScalarUDF = create_udf(func, QueryContext::arc(), Default::default()).into();
+        udf.call(args)
+    }
 
     #[test]
     fn set_order_hint() {
@@ -352,4 +518,91 @@ mod test {
         let scan_req = provider.scan_request();
         let _ = scan_req.series_row_selector.unwrap();
     }
+
+    #[test]
+    fn set_vector_search_hint() {
+        let provider = Arc::new(mock_vector_table_provider());
+        let table_source = Arc::new(DefaultTableSource::new(provider.clone()));
+        let distance = vector_function(VEC_L2SQ_DISTANCE_NAME, vec![col("v"), lit("[1.0, 2.0]")]);
+        let plan = LogicalPlanBuilder::scan("t", table_source, None)
+            .unwrap()
+            .project(vec![col("ts"), distance.alias("d")])
+            .unwrap()
+            .sort(vec![col("d").sort(true, false)])
+            .unwrap()
+            .limit(1, Some(3))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let context = OptimizerContext::default();
+        ScanHintRule.try_optimize(&plan, &context).unwrap();
+
+        let scan_req = provider.scan_request();
+        assert_eq!(
+            VectorSearchRequest {
+                column_name: "v".to_string(),
+                query: vec![1.0, 2.0],
+                metric: VectorDistanceMetric::L2sq,
+                k: 4,
+            },
+            scan_req.vector_search.unwrap()
+        );
+    }
+
+    #[test]
+    fn not_set_vector_search_hint() {
+        let plans: Vec<Box<dyn Fn(LogicalPlanBuilder) -> LogicalPlanBuilder>> = vec![
+            // no limit
+            Box::new(|builder| {
+                let distance =
+                    vector_function(VEC_COS_DISTANCE_NAME, vec![col("v"), lit("[1.0, 2.0]")]);
+                builder.sort(vec![distance.sort(true, false)]).unwrap()
+            }),
+            // the farthest rows
+            Box::new(|builder| {
+                let distance =
+                    vector_function(VEC_DOT_PRODUCT_NAME, vec![col("v"), lit("[1.0, 2.0]")]);
+                builder
+                    .sort(vec![distance.sort(true, false)])
+                    .unwrap()
+                    .limit(0, Some(3))
+                    .unwrap()
+            }),
+            // filter before sort
+            Box::new(|builder| {
+                let distance =
+                    vector_function(VEC_L2SQ_DISTANCE_NAME, vec![col("v"), lit("[1.0, 2.0]")]);
+                builder
+                    .filter(col("v").is_not_null())
+                    .unwrap()
+                    .sort(vec![distance.sort(true, false)])
+                    .unwrap()
+                    .limit(0, Some(3))
+                    .unwrap()
+            }),
+            // dimension mismatch
+            Box::new(|builder| {
+                let distance =
+                    vector_function(VEC_L2SQ_DISTANCE_NAME, vec![col("v"), lit("[1.0]")]);
+                builder
+                    .sort(vec![distance.sort(true, false)])
+                    .unwrap()
+                    .limit(0, Some(3))
+                    .unwrap()
+            }),
+        ];
+
+        for build in plans {
+            let provider = Arc::new(mock_vector_table_provider());
+            let table_source = Arc::new(DefaultTableSource::new(provider.clone()));
+            let builder = LogicalPlanBuilder::scan("t", table_source, None).unwrap();
+            let plan = build(builder).build().unwrap();
+
+            let context = OptimizerContext::default();
+            ScanHintRule.try_optimize(&plan, &context).unwrap();
+
+            assert!(provider.scan_request().vector_search.is_none());
+        }
+    }
 }
@@ -24,18 +24,19 @@ use datafusion::datasource::DefaultTableSource;
 use datafusion::execution::context::SessionState;
 use datafusion::functions_aggregate::sum;
 use datafusion::logical_expr::expr::{
-    AggregateFunction, AggregateFunctionDefinition, Alias, ScalarFunction,
+    AggregateFunction, AggregateFunctionDefinition, Alias, ScalarFunction, WindowFunction,
 };
-use datafusion::logical_expr::expr_rewriter::normalize_cols;
+use datafusion::logical_expr::expr_rewriter::{normalize_col, normalize_cols};
 use datafusion::logical_expr::{
-    AggregateFunction as AggregateFunctionEnum, BinaryExpr, Cast, Extension, LogicalPlan,
-    LogicalPlanBuilder, Operator, ScalarUDF as ScalarUdfDef,
+    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltInWindowFunction, Cast, Extension,
+    LogicalPlan, LogicalPlanBuilder, Operator, ScalarUDF as ScalarUdfDef, WindowFrame,
+    WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition,
 };
 use datafusion::prelude as df_prelude;
 use datafusion::prelude::{Column, Expr as DfExpr, JoinType};
 use datafusion::scalar::ScalarValue;
 use datafusion::sql::TableReference;
-use datafusion_expr::utils::conjunction;
+use datafusion_expr::utils::{conjunction, disjunction};
 use datatypes::arrow::datatypes::{DataType as ArrowDataType, TimeUnit as ArrowTimeUnit};
 use datatypes::data_type::ConcreteDataType;
 use itertools::Itertools;
@@ -44,9 +45,10 @@ use promql::extension_plan::{
     RangeManipulate, ScalarCalculate, SeriesDivide, SeriesNormalize, UnionDistinctOn,
 };
 use promql::functions::{
-    AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, Deriv, HoltWinters, IDelta,
-    Increase, LastOverTime, MaxOverTime, MinOverTime, PredictLinear, PresentOverTime,
-    QuantileOverTime, Rate, Resets, StddevOverTime, StdvarOverTime, SumOverTime,
+    AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, Deriv, EnsureUnique, FormatValue,
+    HoltWinters, IDelta, Increase, LastOverTime, MaxOverTime, MinOverTime, PredictLinear,
+    PresentOverTime, QuantileAccumulator, QuantileOverTime, Rate, Resets, StddevOverTime,
+    StdvarOverTime, SumOverTime,
 };
 use promql_parser::label::{MatchOp, Matcher, Matchers, METRIC_NAME};
 use promql_parser::parser::token::TokenType;
@@ -61,11 +63,12 @@ use table::table::adapter::DfTableProviderAdapter;
 
 use crate::promql::error::{
     CatalogSnafu, ColumnNotFoundSnafu, CombineTableColumnMismatchSnafu, DataFusionPlanningSnafu,
-    ExpectRangeSelectorSnafu, FunctionInvalidArgumentSnafu, MultiFieldsNotSupportedSnafu,
-    MultipleMetricMatchersSnafu, MultipleVectorSnafu, NoMetricMatcherSnafu, PromqlPlanNodeSnafu,
-    Result, TableNameNotFoundSnafu, TimeIndexNotFoundSnafu, UnexpectedPlanExprSnafu,
-    UnexpectedTokenSnafu, UnknownTableSnafu, UnsupportedExprSnafu, UnsupportedMatcherOpSnafu,
-    UnsupportedVectorMatchSnafu, ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
+    ExpectRangeSelectorSnafu, FunctionInvalidArgumentSnafu, InvalidLabelNameSnafu,
+    InvalidRegularExpressionSnafu, MultiFieldsNotSupportedSnafu, MultipleMetricMatchersSnafu,
+    MultipleVectorSnafu, NoMetricMatcherSnafu, PromqlPlanNodeSnafu, Result, TableNameNotFoundSnafu,
+    TimeIndexNotFoundSnafu, UnexpectedPlanExprSnafu, UnexpectedTokenSnafu, UnknownTableSnafu,
+    UnsupportedExprSnafu, UnsupportedMatcherOpSnafu, UnsupportedVectorMatchSnafu,
+    ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
 };
 
 /// `time()` function in PromQL.
@@ -76,6 +79,10 @@ const SCALAR_FUNCTION: &str = "scalar";
 const SPECIAL_HISTOGRAM_QUANTILE: &str = "histogram_quantile";
 /// `vector` function in PromQL
 const SPECIAL_VECTOR_FUNCTION: &str = "vector";
+/// `label_replace` function in PromQL
+const LABEL_REPLACE_FUNCTION: &str = "label_replace";
+/// `label_join` function in PromQL
+const LABEL_JOIN_FUNCTION: &str = "label_join";
 /// `le` column for conventional histogram.
 const LE_COLUMN_NAME: &str = "le";
 
@@ -88,7 +95,7 @@ const DEFAULT_FIELD_COLUMN: &str = "value";
 const FIELD_COLUMN_MATCHER: &str = "__field__";
 
 /// Special modifier for cross schema query
-const SCHEMA_COLUMN_MATCHER: &str = "__schema__";
+pub const SCHEMA_COLUMN_MATCHER: &str = "__schema__";
 
 #[derive(Default, Debug, Clone)]
 struct PromPlannerContext {
@@ -175,10 +182,9 @@ impl PromPlanner {
             PromExpr::Paren(ParenExpr { expr }) => {
                 self.prom_expr_to_plan(*expr.clone(), session_state).await?
             }
-            PromExpr::Subquery(SubqueryExpr { .. }) => UnsupportedExprSnafu {
-                name: "Prom Subquery",
+            PromExpr::Subquery(expr) => {
+                self.prom_subquery_expr_to_plan(session_state, expr).await?
             }
-            .fail()?,
             PromExpr::NumberLiteral(lit) => self.prom_number_lit_to_plan(lit)?,
             PromExpr::StringLiteral(lit) => self.prom_string_lit_to_plan(lit)?,
             PromExpr::VectorSelector(selector) => {
@@ -201,19 +207,26 @@ impl PromPlanner {
         let AggregateExpr {
             op,
             expr,
-            // TODO(ruihang): support param
-            param: _param,
+            param,
             modifier,
         } = aggr_expr;
 
         let input = self.prom_expr_to_plan(*expr.clone(), session_state).await?;
 
+        match op.id() {
+            token::T_TOPK | token::T_BOTTOMK => {
+                return self.prom_topk_bottomk_to_plan(*op, param, modifier, input)
+            }
+            token::T_COUNT_VALUES => return self.prom_count_values_to_plan(param, modifier, input),
+            _ => {}
+        }
+
         // calculate columns to group by
         // Need to append time index column into group by columns
         let group_exprs = self.agg_modifier_to_col(input.schema(), modifier)?;
 
         // convert op and value columns to aggregate exprs
-        let aggr_exprs = self.create_aggregate_exprs(*op, &input)?;
+        let aggr_exprs = self.create_aggregate_exprs(*op, param, &input)?;
 
         // create plan
         let group_sort_expr = group_exprs
@@ -229,6 +242,213 @@ impl PromPlanner {
             .context(DataFusionPlanningSnafu)
     }
 
+    /// Plans `topk` and `bottomk`. They select series rather than aggregating them, so
+    /// series are ranked by a window partitioned by the group and time index, and all
+    /// labels of the selected series are kept.
+    fn prom_topk_bottomk_to_plan(
+        &mut self,
+        op: TokenType,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let is_topk = op.id() == token::T_TOPK;
+        let fn_name = if is_topk { "topk" } else { "bottomk" };
+        let k = param
+            .as_deref()
+            .and_then(Self::try_build_float_literal)
+            .with_context(|| FunctionInvalidArgumentSnafu {
+                fn_name: fn_name.to_string(),
+            })?;
+
+        // `agg_modifier_to_col` changes the tag columns in context, but the selected
+        // series keep their labels.
+        let tag_columns = self.ctx.tag_columns.clone();
+        let partition_exprs = self.agg_modifier_to_col(input.schema(), modifier)?;
+        self.ctx.tag_columns = tag_columns;
+
+        // series with the same value are ranked by their labels, so ties are broken
+        // deterministically
+        let tie_breaker_exprs = self
+            .ctx
+            .tag_columns
+            .iter()
+            .map(|tag| DfExpr::Column(Column::from_name(tag)).sort(true, false))
+            .collect::<Vec<_>>();
+        // each field is ranked separately like an individual series
+        let rank_exprs = self
+            .ctx
+            .field_columns
+            .iter()
+            .map(|col| {
+                let mut order_by =
+                    vec![DfExpr::Column(Column::from_name(col)).sort(!is_topk, false)];
+                order_by.extend(tie_breaker_exprs.iter().cloned());
+                DfExpr::WindowFunction(WindowFunction {
+                    fun: WindowFunctionDefinition::BuiltInWindowFunction(
+                        BuiltInWindowFunction::RowNumber,
+                    ),
+                    args: vec![],
+                    partition_by: partition_exprs.clone(),
+                    order_by,
+                    window_frame: WindowFrame::new_bounds(
+                        WindowFrameUnits::Rows,
+                        WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+                        WindowFrameBound::CurrentRow,
+                    ),
+                    null_treatment: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        // rank columns are named after the normalized window exprs
+        let rank_exprs = normalize_cols(rank_exprs, &input).context(DataFusionPlanningSnafu)?;
+        let rank_filters = rank_exprs
+            .iter()
+            .map(|expr| {
+                let rank_column = expr.display_name().context(DataFusionPlanningSnafu)?;
+                Ok(DfExpr::Column(Column::from_name(rank_column))
+                    .lt_eq(df_prelude::lit(k.max(0.0) as u64)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // remove the rank columns from the output, and set values of fields that are not
+        // selected to null
+        let is_multi_field = self.ctx.field_columns.len() > 1;
+        let mut project_exprs = self.create_tag_column_exprs()?;
+        project_exprs.push(self.create_time_index_column_expr()?);
+        for (col, filter) in self.ctx.field_columns.iter().zip(rank_filters.iter()) {
+            let value = DfExpr::Column(Column::from_name(col));
+            if is_multi_field {
+                let expr = df_prelude::when(filter.clone(), value)
+                    .end()
+                    .context(DataFusionPlanningSnafu)?;
+                project_exprs.push(expr.alias(col));
+            } else {
+                project_exprs.push(value);
+            }
+        }
+
+        let filter = disjunction(rank_filters).with_context(|| ValueNotFoundSnafu {
+            table: self.ctx.table_name.clone().unwrap_or_default(),
+        })?;
+        LogicalPlanBuilder::from(input)
+            .window(rank_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .filter(filter)
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(self.create_tag_and_time_index_column_sort_exprs()?)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Plans `count_values`, which counts series of the same value in each group and
+    /// outputs the value as a new label.
+    fn prom_count_values_to_plan(
+        &mut self,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let label = match param.as_deref() {
+            Some(PromExpr::StringLiteral(StringLiteral { val })) => val.clone(),
+            _ => {
+                return FunctionInvalidArgumentSnafu {
+                    fn_name: "count_values",
+                }
+                .fail()
+            }
+        };
+        let field_column =
+            self.ctx
+                .field_columns
+                .first()
+                .cloned()
+                .with_context(|| ValueNotFoundSnafu {
+                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                })?;
+        let time_index_column =
+            self.ctx
+                .time_index_column
+                .clone()
+                .with_context(|| TimeIndexNotFoundSnafu {
+                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                })?;
+        // the value label can only override labels
+        ensure!(
+            label != time_index_column && !self.ctx.field_columns.contains(&label),
+            FunctionInvalidArgumentSnafu {
+                fn_name: "count_values",
+            }
+        );
+
+        let mut group_exprs = self.agg_modifier_to_col(input.schema(), modifier)?;
+        // the value label overrides the existing label with the same name
+        group_exprs.retain(|expr| !matches!(expr, DfExpr::Column(col) if col.name == label));
+        self.ctx.tag_columns.retain(|col| *col != label);
+
+        // add the value label to input
+        let mut project_exprs = input
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|col| col.name != label)
+            .map(DfExpr::Column)
+            .collect::<Vec<_>>();
+        // format the value like Prometheus, e.g. `1` rather than `1.0`
+        project_exprs.push(
+            DfExpr::ScalarFunction(ScalarFunction {
+                func: Arc::new(FormatValue::scalar_udf()),
+                args: vec![DfExpr::Cast(Cast {
+                    expr: Box::new(DfExpr::Column(Column::from_name(&field_column))),
+                    data_type: ArrowDataType::Float64,
+                })],
+            })
+            .alias(&label),
+        );
+        // group by the value label before the time index, like other labels
+        let time_index_pos = group_exprs
+            .iter()
+            .position(|expr| matches!(expr, DfExpr::Column(col) if col.name == time_index_column))
+            .unwrap_or(group_exprs.len());
+        group_exprs.insert(time_index_pos, DfExpr::Column(Column::from_name(&label)));
+        self.ctx.tag_columns.push(label);
+
+        let count_expr = DfExpr::AggregateFunction(AggregateFunction {
+            func_def: AggregateFunctionDefinition::BuiltIn(AggregateFunctionEnum::Count),
+            args: vec![DfExpr::Column(Column::from_name(field_column))],
+            distinct: false,
+            filter: None,
+            order_by: None,
+            null_treatment: None,
+        });
+
+        let input = LogicalPlanBuilder::from(input)
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let normalized_expr =
+            normalize_cols(vec![count_expr.clone()], &input).context(DataFusionPlanningSnafu)?;
+        self.ctx.field_columns = vec![normalized_expr[0]
+            .display_name()
+            .context(DataFusionPlanningSnafu)?];
+
+        let group_sort_expr = group_exprs
+            .clone()
+            .into_iter()
+            .map(|expr| expr.sort(true, false));
+        LogicalPlanBuilder::from(input)
+            .aggregate(group_exprs, vec![count_expr])
+            .context(DataFusionPlanningSnafu)?
+            .sort(group_sort_expr)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
     async fn prom_unary_expr_to_plan(
         &mut self,
         session_state: &SessionState,
@@ -321,7 +541,13 @@ impl PromPlanner {
                     Ok(binary_expr)
                 };
                 if is_comparison_op && !should_return_bool {
-                    self.filter_on_field_column(input, bin_expr_builder)
+                    let qualifier = self.ctx.table_name.clone().map(TableReference::bare);
+                    self.filter_on_field_column(
+                        input,
+                        qualifier,
+                        &self.ctx.field_columns,
+                        bin_expr_builder,
+                    )
                 } else {
                     self.projection_for_each_field_column(input, bin_expr_builder)
                 }
@@ -350,7 +576,13 @@ impl PromPlanner {
                     Ok(binary_expr)
                 };
                 if is_comparison_op && !should_return_bool {
-                    self.filter_on_field_column(input, bin_expr_builder)
+                    let qualifier = self.ctx.table_name.clone().map(TableReference::bare);
+                    self.filter_on_field_column(
+                        input,
+                        qualifier,
+                        &self.ctx.field_columns,
+                        bin_expr_builder,
+                    )
                 } else {
                     self.projection_for_each_field_column(input, bin_expr_builder)
                 }
@@ -385,6 +617,23 @@ impl PromPlanner {
                     );
                 }
 
+                // one-to-many and many-to-one matching
+                if let Some(modifier) = modifier
+                    && matches!(
+                        modifier.card,
+                        VectorMatchCardinality::ManyToOne(_) | VectorMatchCardinality::OneToMany(_)
+                    )
+                {
+                    return self.group_join_on_non_field_columns(
+                        left_input,
+                        right_input,
+                        left_context,
+                        right_context,
+                        *op,
+                        modifier,
+                    );
+                }
+
                 // normal join
                 if left_table_ref == right_table_ref {
                     // rename table references to avoid ambiguity
@@ -402,6 +651,15 @@ impl PromPlanner {
                         self.ctx.table_name = Some("rhs".to_string());
                     }
                 }
+                // The comparison keeps values of the left operand. If only the right operand
+                // has tags, e.g. `scalar(...) > host`, it keeps values of the right operand.
+                let (value_table_ref, value_field_columns) = if left_context.tag_columns.is_empty()
+                    && !right_context.tag_columns.is_empty()
+                {
+                    (right_table_ref.clone(), right_field_columns.clone())
+                } else {
+                    (left_table_ref.clone(), left_field_columns.clone())
+                };
                 let mut field_columns = left_field_columns.iter().zip(right_field_columns.iter());
                 let join_plan = self.join_on_non_field_columns(
                     left_input,
@@ -437,7 +695,13 @@ impl PromPlanner {
                     Ok(binary_expr)
                 };
                 if is_comparison_op && !should_return_bool {
-                    self.filter_on_field_column(join_plan, bin_expr_builder)
+                    let qualifier = Some(value_table_ref).filter(|t| !t.table().is_empty());
+                    self.filter_on_field_column(
+                        join_plan,
+                        qualifier,
+                        &value_field_columns,
+                        bin_expr_builder,
+                    )
                 } else {
                     self.projection_for_each_field_column(join_plan, bin_expr_builder)
                 }
@@ -564,6 +828,95 @@ impl PromPlanner {
         }))
     }
 
+    /// Plans a subquery like `expr[range:step] offset d`.
+    ///
+    /// The inner `expr` is evaluated as a range query at the subquery step, then its result
+    /// is manipulated by [RangeManipulate] like the output of a matrix selector.
+    async fn prom_subquery_expr_to_plan(
+        &mut self,
+        session_state: &SessionState,
+        subquery_expr: &SubqueryExpr,
+    ) -> Result<LogicalPlan> {
+        let SubqueryExpr {
+            expr,
+            range,
+            offset,
+            step,
+            ..
+        } = subquery_expr;
+        ensure!(!range.is_zero(), ZeroRangeSelectorSnafu);
+        let range_ms = range.as_millis() as Millisecond;
+        let offset_ms = match offset {
+            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
+            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
+            None => 0,
+        };
+        // The step defaults to the interval of the query.
+        let step_ms = match step {
+            Some(step) if !step.is_zero() => step.as_millis() as Millisecond,
+            _ => self.ctx.interval,
+        };
+
+        // Evaluates the inner expression at timestamps aligned with the step, from the first
+        // one after `start - offset - range` to `end - offset`.
+        let (start, end, interval) = (self.ctx.start, self.ctx.end, self.ctx.interval);
+        let min_ts = start - offset_ms - range_ms;
+        let mut inner_start = min_ts.div_euclid(step_ms) * step_ms;
+        if inner_start < min_ts {
+            inner_start += step_ms;
+        }
+        self.ctx.start = inner_start;
+        self.ctx.end = end - offset_ms;
+        self.ctx.interval = step_ms;
+        let input = self.prom_expr_to_plan(*expr.clone(), session_state).await;
+        self.ctx.start = start;
+        self.ctx.end = end;
+        self.ctx.interval = interval;
+        let input = input?;
+
+        // Divides the result of the inner expression into series and biases the timestamps
+        // by the offset, as what `selector_to_series_normalize_plan` does for selectors.
+        let time_index_column =
+            self.ctx
+                .time_index_column
+                .clone()
+                .with_context(|| TimeIndexNotFoundSnafu {
+                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                })?;
+        let sort_plan = LogicalPlanBuilder::from(input)
+            .sort(self.create_tag_and_time_index_column_sort_exprs()?)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let divide_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesDivide::new(self.ctx.tag_columns.clone(), sort_plan)),
+        });
+        let normalize = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesNormalize::new(
+                offset_ms,
+                &time_index_column,
+                false,
+                divide_plan,
+            )),
+        });
+
+        self.ctx.range = Some(range_ms);
+        let manipulate = RangeManipulate::new(
+            self.ctx.start,
+            self.ctx.end,
+            self.ctx.interval,
+            range_ms,
+            time_index_column,
+            self.ctx.field_columns.clone(),
+            normalize,
+        )
+        .context(DataFusionPlanningSnafu)?;
+
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(manipulate),
+        }))
+    }
+
     async fn prom_call_expr_to_plan(
         &mut self,
         session_state: &SessionState,
@@ -577,6 +930,10 @@ impl PromPlanner {
             }
             SPECIAL_VECTOR_FUNCTION => return self.create_vector_plan(args).await,
             SCALAR_FUNCTION => return self.create_scalar_plan(args, session_state).await,
+            LABEL_REPLACE_FUNCTION => {
+                return self.create_label_replace_plan(args, session_state).await
+            }
+            LABEL_JOIN_FUNCTION => return self.create_label_join_plan(args, session_state).await,
             _ => {}
         }
 
@@ -808,9 +1165,11 @@ impl PromPlanner {
                 .filter(|col| result_set.contains(col))
                 .collect();
 
-            let exprs = result_set
-                .into_iter()
-                .map(|col| DfExpr::Column(col.into()))
+            let exprs = self
+                .ctx
+                .field_columns
+                .iter()
+                .map(|col| DfExpr::Column(Column::from_name(col)))
                 .chain(self.create_tag_column_exprs()?)
                 .chain(Some(self.create_time_index_column_expr()?))
                 .collect::<Vec<_>>();
@@ -1433,6 +1792,7 @@ impl PromPlanner {
     fn create_aggregate_exprs(
         &mut self,
         op: TokenType,
+        param: &Option<Box<PromExpr>>,
         input_plan: &LogicalPlan,
     ) -> Result<Vec<DfExpr>> {
         let aggr = match op.id() {
@@ -1448,11 +1808,14 @@ impl PromPlanner {
             token::T_STDVAR => {
                 AggregateFunctionDefinition::BuiltIn(AggregateFunctionEnum::VariancePop)
             }
-            token::T_TOPK | token::T_BOTTOMK | token::T_COUNT_VALUES | token::T_QUANTILE => {
-                UnsupportedExprSnafu {
-                    name: format!("{op:?}"),
-                }
-                .fail()?
+            token::T_QUANTILE => {
+                let phi = param
+                    .as_deref()
+                    .and_then(Self::try_build_float_literal)
+                    .context(FunctionInvalidArgumentSnafu {
+                        fn_name: "quantile",
+                    })?;
+                AggregateFunctionDefinition::UDF(QuantileAccumulator::udaf(phi))
             }
             _ => UnexpectedTokenSnafu { token: op }.fail()?,
         };
@@ -1622,6 +1985,171 @@ impl PromPlanner {
         Ok(scalar_plan)
     }
 
+    /// Create a [LABEL_REPLACE_FUNCTION] plan.
+    ///
+    /// `label_replace(v, dst, replacement, src, regex)` matches `regex` against the value
+    /// of label `src`. If it matches, the value of label `dst` is replaced by the expanded
+    /// `replacement`. Otherwise the series is returned unchanged.
+    async fn create_label_replace_plan(
+        &mut self,
+        args: &PromFunctionArgs,
+        session_state: &SessionState,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            args.len() == 5,
+            FunctionInvalidArgumentSnafu {
+                fn_name: LABEL_REPLACE_FUNCTION
+            }
+        );
+        let literals = Self::string_literal_args(LABEL_REPLACE_FUNCTION, &args.args[1..])?;
+        let (dst, replacement, src, regex) =
+            (&literals[0], &literals[1], &literals[2], &literals[3]);
+        Self::ensure_label_name(dst)?;
+        // the regex is fully anchored like Prometheus
+        let pattern = format!("^(?:{regex})$");
+        let _ = regex::Regex::new(&pattern).context(InvalidRegularExpressionSnafu {
+            regex: regex.clone(),
+        })?;
+
+        let input = self
+            .prom_expr_to_plan(args.args[0].as_ref().clone(), session_state)
+            .await?;
+
+        let src_expr = self.label_value_expr(src)?;
+        let is_match = DfExpr::BinaryExpr(BinaryExpr {
+            left: Box::new(src_expr.clone()),
+            op: Operator::RegexMatch,
+            right: Box::new(df_prelude::lit(pattern.clone())),
+        });
+        let replaced = DfExpr::ScalarFunction(ScalarFunction {
+            func: datafusion_functions::regex::regexp_replace(),
+            args: vec![
+                src_expr,
+                df_prelude::lit(pattern),
+                df_prelude::lit(replacement.clone()),
+            ],
+        });
+        let dst_expr = df_prelude::when(is_match, replaced)
+            .otherwise(self.label_value_expr(dst)?)
+            .context(DataFusionPlanningSnafu)?;
+
+        self.project_label_column(input, dst, dst_expr)
+    }
+
+    /// Create a [LABEL_JOIN_FUNCTION] plan.
+    ///
+    /// `label_join(v, dst, separator, src_1, src_2, ...)` joins the values of all the `src`
+    /// labels with `separator` and stores the result in label `dst`.
+    async fn create_label_join_plan(
+        &mut self,
+        args: &PromFunctionArgs,
+        session_state: &SessionState,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            args.len() >= 3,
+            FunctionInvalidArgumentSnafu {
+                fn_name: LABEL_JOIN_FUNCTION
+            }
+        );
+        let literals = Self::string_literal_args(LABEL_JOIN_FUNCTION, &args.args[1..])?;
+        let (dst, separator, srcs) = (&literals[0], &literals[1], &literals[2..]);
+        Self::ensure_label_name(dst)?;
+
+        let input = self
+            .prom_expr_to_plan(args.args[0].as_ref().clone(), session_state)
+            .await?;
+
+        let mut concat_args = Vec::with_capacity(srcs.len() + 1);
+        concat_args.push(df_prelude::lit(separator.clone()));
+        for src in srcs {
+            concat_args.push(self.label_value_expr(src)?);
+        }
+        let dst_expr = DfExpr::ScalarFunction(ScalarFunction {
+            func: datafusion_functions::string::concat_ws(),
+            args: concat_args,
+        });
+
+        self.project_label_column(input, dst, dst_expr)
+    }
+
+    /// Extract string literals from the arguments of function `fn_name`.
+    fn string_literal_args(fn_name: &str, args: &[Box<PromExpr>]) -> Result<Vec<String>> {
+        args.iter()
+            .map(|arg| match arg.as_ref() {
+                PromExpr::StringLiteral(StringLiteral { val }) => Ok(val.clone()),
+                _ => FunctionInvalidArgumentSnafu { fn_name }.fail(),
+            })
+            .collect()
+    }
+
+    /// Check the given label name is valid in Prometheus, i.e. `[a-zA-Z_][a-zA-Z0-9_]*`.
+    fn ensure_label_name(name: &str) -> Result<()> {
+        let mut chars = name.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        ensure!(valid, InvalidLabelNameSnafu { name });
+        Ok(())
+    }
+
+    /// Build an expression evaluates to the value of the given label. An absent label
+    /// is treated as an empty string, like Prometheus.
+    fn label_value_expr(&self, label: &str) -> Result<DfExpr> {
+        if !self.ctx.tag_columns.iter().any(|tag| tag == label) {
+            return Ok(df_prelude::lit(""));
+        }
+        let column = DfExpr::Column(Column::from_name(label));
+        df_prelude::when(column.clone().is_null(), df_prelude::lit(""))
+            .otherwise(column)
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Project the input plan with label `dst` rewritten to `dst_expr`. Other columns
+    /// are kept as is. An empty label value means the label is absent.
+    fn project_label_column(
+        &mut self,
+        input: LogicalPlan,
+        dst: &str,
+        dst_expr: DfExpr,
+    ) -> Result<LogicalPlan> {
+        let dst_expr = DfExpr::ScalarFunction(ScalarFunction {
+            func: datafusion_functions::core::nullif(),
+            args: vec![dst_expr, df_prelude::lit("")],
+        })
+        .alias(dst);
+
+        let mut exprs = Vec::with_capacity(self.ctx.tag_columns.len() + 2);
+        for tag in &self.ctx.tag_columns {
+            if tag != dst {
+                exprs.push(DfExpr::Column(Column::from_name(tag)));
+            }
+        }
+        exprs.push(dst_expr);
+        exprs.push(self.create_time_index_column_expr()?);
+        exprs.extend(
+            self.ctx
+                .field_columns
+                .iter()
+                .map(|field| DfExpr::Column(Column::from_name(field))),
+        );
+
+        let mut builder = LogicalPlanBuilder::from(input)
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?;
+        // keep the output columns qualified by the table name
+        if let Ok(table_ref) = self.table_ref()
+            && !table_ref.table().is_empty()
+        {
+            builder = builder.alias(table_ref).context(DataFusionPlanningSnafu)?;
+        }
+
+        if !self.ctx.tag_columns.iter().any(|tag| tag == dst) {
+            self.ctx.tag_columns.push(dst.to_string());
+        }
+        builder.build().context(DataFusionPlanningSnafu)
+    }
+
     /// Try to build a DataFusion Literal Expression from PromQL Expr, return
     /// `None` if the input is not a literal expression.
     fn try_build_literal_expr(expr: &PromExpr) -> Option<DfExpr> {
@@ -1813,28 +2341,282 @@ impl PromPlanner {
             .context(DataFusionPlanningSnafu)
     }
 
-    /// Build a set operator (AND/OR/UNLESS)
-    fn set_op_on_non_field_columns(
-        &mut self,
-        left: LogicalPlan,
-        mut right: LogicalPlan,
-        left_context: PromPlannerContext,
-        right_context: PromPlannerContext,
-        op: TokenType,
-        modifier: &Option<BinModifier>,
+    /// Fails the query at execution if series of `input` aren't unique on the matching
+    /// labels, i.e. more than one series have the same matching labels at a timestamp.
+    /// The output has the same columns as `input`.
+    fn ensure_unique_on_matching_labels(
+        input: LogicalPlan,
+        matching_labels: &[String],
+        time_index: &str,
     ) -> Result<LogicalPlan> {
-        let mut left_tag_col_set = left_context
-            .tag_columns
-            .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
-        let mut right_tag_col_set = right_context
-            .tag_columns
+        let partition_by = matching_labels
             .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
-
-        if matches!(op.id(), token::T_LOR) {
+            .map(|label| label.as_str())
+            .chain(Some(time_index))
+            .map(|col| DfExpr::Column(Column::from_name(col)))
+            .collect();
+        let count_expr = DfExpr::WindowFunction(WindowFunction {
+            fun: WindowFunctionDefinition::AggregateFunction(AggregateFunctionEnum::Count),
+            args: vec![DfExpr::Column(Column::from_name(time_index))],
+            partition_by,
+            order_by: vec![],
+            window_frame: WindowFrame::new(None),
+            null_treatment: None,
+        });
+        // the count column is named after the normalized window expr
+        let count_expr = normalize_col(count_expr, &input).context(DataFusionPlanningSnafu)?;
+        let count_column = count_expr.display_name().context(DataFusionPlanningSnafu)?;
+        let check_expr = DfExpr::ScalarFunction(ScalarFunction {
+            func: Arc::new(EnsureUnique::scalar_udf()),
+            args: vec![DfExpr::Column(Column::from_name(count_column))],
+        });
+        let project_exprs = input
+            .schema()
+            .columns()
+            .into_iter()
+            .map(DfExpr::Column)
+            .collect::<Vec<_>>();
+
+        LogicalPlanBuilder::from(input)
+            .window(vec![count_expr])
+            .context(DataFusionPlanningSnafu)?
+            .filter(check_expr)
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Build a join for binary operations with `group_left` or `group_right` modifiers, i.e.
+    /// many-to-one and one-to-many vector matching.
+    ///
+    /// Each series of the "many" side is joined with the series of the "one" side that has
+    /// the same matching labels at the same timestamp. The result keeps labels of the "many"
+    /// side, and copies the extra labels listed in the `group_*` modifier from the "one" side.
+    fn group_join_on_non_field_columns(
+        &mut self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        left_context: PromPlannerContext,
+        right_context: PromPlannerContext,
+        op: TokenType,
+        modifier: &BinModifier,
+    ) -> Result<LogicalPlan> {
+        let (many_is_left, extra_labels) = match &modifier.card {
+            VectorMatchCardinality::ManyToOne(labels) => (true, labels),
+            VectorMatchCardinality::OneToMany(labels) => (false, labels),
+            card => {
+                return UnsupportedVectorMatchSnafu { name: card.clone() }.fail();
+            }
+        };
+        ensure!(
+            left_context.field_columns.len() == right_context.field_columns.len(),
+            CombineTableColumnMismatchSnafu {
+                left: left_context.field_columns.clone(),
+                right: right_context.field_columns.clone(),
+            }
+        );
+
+        // labels to match series of two sides
+        let right_tag_col_set = right_context.tag_columns.iter().collect::<HashSet<_>>();
+        let common_tags = left_context
+            .tag_columns
+            .iter()
+            .filter(|tag| right_tag_col_set.contains(tag));
+        let matching_labels = match &modifier.matching {
+            Some(LabelModifier::Include(on)) => common_tags
+                .filter(|tag| on.labels.contains(tag))
+                .cloned()
+                .collect::<Vec<_>>(),
+            Some(LabelModifier::Exclude(ignoring)) => common_tags
+                .filter(|tag| !ignoring.labels.contains(tag))
+                .cloned()
+                .collect(),
+            None => common_tags.cloned().collect(),
+        };
+
+        let left_table_ref = TableReference::bare("lhs");
+        let right_table_ref = TableReference::bare("rhs");
+        let left_time_index = left_context
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "lhs" })?;
+        let right_time_index = right_context
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "rhs" })?;
+        let join_keys = |table_ref: &TableReference, time_index: &String| {
+            matching_labels
+                .iter()
+                .chain(Some(time_index))
+                .map(|col| Column::new(Some(table_ref.clone()), col))
+                .collect::<Vec<_>>()
+        };
+        let left_keys = join_keys(&left_table_ref, &left_time_index);
+        let right_keys = join_keys(&right_table_ref, &right_time_index);
+
+        // each series of the "many" side can match at most one series of the "one" side
+        let (left, right) = if many_is_left {
+            let right =
+                Self::ensure_unique_on_matching_labels(right, &matching_labels, &right_time_index)?;
+            (left, right)
+        } else {
+            let left =
+                Self::ensure_unique_on_matching_labels(left, &matching_labels, &left_time_index)?;
+            (left, right)
+        };
+
+        let right = LogicalPlanBuilder::from(right)
+            .alias(right_table_ref.clone())
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let join_plan = LogicalPlanBuilder::from(left)
+            .alias(left_table_ref.clone())
+            .context(DataFusionPlanningSnafu)?
+            .join(right, JoinType::Inner, (left_keys, right_keys), None)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        let (many_context, many_table_ref, one_context, one_table_ref) = if many_is_left {
+            (
+                left_context.clone(),
+                &left_table_ref,
+                right_context.clone(),
+                &right_table_ref,
+            )
+        } else {
+            (
+                right_context.clone(),
+                &right_table_ref,
+                left_context.clone(),
+                &left_table_ref,
+            )
+        };
+        let time_index = many_context
+            .time_index_column
+            .clone()
+            .expect("time index is checked above");
+
+        // labels of the "many" side, with extra labels copied from the "one" side
+        let mut output_tags = many_context
+            .tag_columns
+            .iter()
+            .filter(|tag| !extra_labels.labels.contains(tag))
+            .map(|tag| (many_table_ref, tag.clone()))
+            .collect::<Vec<_>>();
+        for label in &extra_labels.labels {
+            if one_context.tag_columns.contains(label) {
+                output_tags.push((one_table_ref, label.clone()));
+            }
+        }
+        let mut project_exprs = output_tags
+            .iter()
+            .map(|(table_ref, tag)| {
+                DfExpr::Column(Column::new(Some((*table_ref).clone()), tag)).alias(tag)
+            })
+            .collect::<Vec<_>>();
+        project_exprs.push(
+            DfExpr::Column(Column::new(Some(many_table_ref.clone()), &time_index))
+                .alias(&time_index),
+        );
+
+        // comparison operators without `bool` filter series and keep values of the left side
+        let should_return_bool = modifier.return_bool;
+        let is_comparison_op = Self::is_token_a_comparison_op(op);
+        let binary_expr_builder = Self::prom_token_to_binary_expr_builder(op)?;
+        let is_multi_field = left_context.field_columns.len() > 1;
+        let mut filter_exprs = vec![];
+        let mut field_columns = Vec::with_capacity(left_context.field_columns.len());
+        for (left_field, right_field) in left_context
+            .field_columns
+            .iter()
+            .zip(right_context.field_columns.iter())
+        {
+            let left_col = DfExpr::Column(Column::new(Some(left_table_ref.clone()), left_field));
+            let right_col = DfExpr::Column(Column::new(Some(right_table_ref.clone()), right_field));
+            let mut binary_expr = binary_expr_builder(left_col.clone(), right_col)?;
+            if is_comparison_op && !should_return_bool {
+                // each field is filtered separately if there are multiple fields
+                let value = if is_multi_field {
+                    df_prelude::when(binary_expr.clone(), left_col)
+                        .end()
+                        .context(DataFusionPlanningSnafu)?
+                } else {
+                    left_col
+                };
+                filter_exprs.push(binary_expr);
+                project_exprs.push(value.alias(left_field));
+                field_columns.push(left_field.clone());
+            } else {
+                if is_comparison_op {
+                    binary_expr = DfExpr::Cast(Cast {
+                        expr: Box::new(binary_expr),
+                        data_type: ArrowDataType::Float64,
+                    });
+                }
+                let name = binary_expr
+                    .display_name()
+                    .context(DataFusionPlanningSnafu)?;
+                project_exprs.push(binary_expr.alias(&name));
+                field_columns.push(name);
+            }
+        }
+
+        let mut builder = LogicalPlanBuilder::from(join_plan);
+        if let Some(filter_expr) = disjunction(filter_exprs) {
+            builder = builder
+                .filter(filter_expr)
+                .context(DataFusionPlanningSnafu)?;
+        }
+        // qualify the output with the table name of the "many" side
+        let table_name = many_context
+            .table_name
+            .clone()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| many_table_ref.to_string());
+        let plan = builder
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .alias(TableReference::bare(table_name.clone()))
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        self.ctx = many_context;
+        self.ctx.table_name = Some(table_name);
+        self.ctx.schema_name = None;
+        self.ctx.time_index_column = Some(time_index);
+        self.ctx.tag_columns = output_tags.into_iter().map(|(_, tag)| tag).collect();
+        self.ctx.field_columns = field_columns;
+
+        Ok(plan)
+    }
+
+    /// Build a set operator (AND/OR/UNLESS)
+    fn set_op_on_non_field_columns(
+        &mut self,
+        left: LogicalPlan,
+        mut right: LogicalPlan,
+        left_context: PromPlannerContext,
+        right_context: PromPlannerContext,
+        op: TokenType,
+        modifier: &Option<BinModifier>,
+    ) -> Result<LogicalPlan> {
+        let mut left_tag_col_set = left_context
+            .tag_columns
+            .iter()
+            .cloned()
+            .collect::<HashSet<_>>();
+        let mut right_tag_col_set = right_context
+            .tag_columns
+            .iter()
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        if matches!(op.id(), token::T_LOR) {
             return self.or_operator(
                 left,
                 right,
@@ -2187,30 +2969,78 @@ impl PromPlanner {
             .context(DataFusionPlanningSnafu)
     }
 
-    /// Build a filter plan that filter on value column. Notice that only one value column
-    /// is expected.
+    /// Build a filter plan that filter on value column.
+    ///
+    /// If there are multiple value columns, each of them is filtered like an individual
+    /// series. Values that don't satisfy the condition are set to null, and rows whose
+    /// values are all filtered out are removed. The output tag, time index and value
+    /// columns are taken from `input_field_columns` qualified by `input_table_ref`, which
+    /// is the left operand after a join.
     fn filter_on_field_column<F>(
         &self,
         input: LogicalPlan,
+        input_table_ref: Option<TableReference>,
+        input_field_columns: &[String],
         mut name_to_expr: F,
     ) -> Result<LogicalPlan>
     where
         F: FnMut(&String) -> Result<DfExpr>,
     {
         ensure!(
-            self.ctx.field_columns.len() == 1,
-            UnsupportedExprSnafu {
-                name: "filter on multi-value input"
+            !self.ctx.field_columns.is_empty(),
+            ValueNotFoundSnafu {
+                table: self.ctx.table_name.clone().unwrap_or_default(),
             }
         );
 
-        let field_column_filter = name_to_expr(&self.ctx.field_columns[0])?;
+        if self.ctx.field_columns.len() == 1 {
+            let field_column_filter = name_to_expr(&self.ctx.field_columns[0])?;
+            return LogicalPlanBuilder::from(input)
+                .filter(field_column_filter)
+                .context(DataFusionPlanningSnafu)?
+                .build()
+                .context(DataFusionPlanningSnafu);
+        }
 
-        LogicalPlanBuilder::from(input)
-            .filter(field_column_filter)
+        let field_column_filters = self
+            .ctx
+            .field_columns
+            .iter()
+            .map(&mut name_to_expr)
+            .collect::<Result<Vec<_>>>()?;
+        let mut exprs = self
+            .ctx
+            .tag_columns
+            .iter()
+            .chain(self.ctx.time_index_column.iter())
+            .map(|col| DfExpr::Column(Column::new(input_table_ref.clone(), col)))
+            .collect::<Vec<_>>();
+        for ((field, input_field), filter) in self
+            .ctx
+            .field_columns
+            .iter()
+            .zip(input_field_columns.iter())
+            .zip(field_column_filters.iter())
+        {
+            let value = DfExpr::Column(Column::new(input_table_ref.clone(), input_field));
+            let expr = df_prelude::when(filter.clone(), value)
+                .end()
+                .context(DataFusionPlanningSnafu)?;
+            exprs.push(expr.alias(field));
+        }
+
+        let mut builder = LogicalPlanBuilder::from(input)
+            .filter(disjunction(field_column_filters).expect("field columns are not empty"))
             .context(DataFusionPlanningSnafu)?
-            .build()
-            .context(DataFusionPlanningSnafu)
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?;
+        // keep the output columns qualified by the table name
+        if let Ok(table_ref) = self.table_ref()
+            && !table_ref.table().is_empty()
+        {
+            builder = builder.alias(table_ref).context(DataFusionPlanningSnafu)?;
+        }
+        builder.build().context(DataFusionPlanningSnafu)
     }
 
     /// Generate an expr like `date_part("hour", <TIME_INDEX>)`. Caller should ensure the
@@ -2394,6 +3224,43 @@ mod test {
         assert_eq!(plan.display_indent_schema().to_string(), expected);
     }
 
+    #[tokio::test]
+    async fn subquery() {
+        let prom_expr =
+            parser::parse("max_over_time(rate(some_metric[5m])[1h:1m] offset 30s)").unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let table_provider = build_test_table_provider(
+            &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+            1,
+            1,
+        )
+        .await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent_schema().to_string();
+
+        // outer range manipulation
+        assert!(plan_str.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[3600000]"
+        ));
+        assert!(plan_str.contains("PromSeriesNormalize: offset=[30000]"));
+        // the inner expression is evaluated at the subquery step, from the first aligned
+        // timestamp after `start - offset - range`.
+        assert!(plan_str.contains(
+            "PromRangeManipulate: req range=[-3600000..99970000], interval=[60000], eval range=[300000]"
+        ));
+    }
+
     #[tokio::test]
     async fn single_abs() {
         do_single_instant_function_call("abs", "abs").await;
@@ -2564,6 +3431,265 @@ mod test {
     //         },
     //     },
     // },
+    async fn plan_with_params(promql: &str) -> LogicalPlan {
+        plan_with_params_and_fields(promql, 1).await
+    }
+
+    async fn plan_with_params_and_fields(promql: &str, num_field: usize) -> LogicalPlan {
+        let eval_stmt = EvalStmt {
+            expr: parser::parse(promql).unwrap(),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+        let table_provider = build_test_table_provider(
+            &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+            2,
+            num_field,
+        )
+        .await;
+        PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+            .await
+            .unwrap()
+    }
+
+    fn output_columns(plan: &LogicalPlan) -> Vec<String> {
+        plan.schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn aggregators_with_param() {
+        // topk and bottomk keep all labels of the selected series
+        let plan = plan_with_params("topk by (tag_1) (3, some_metric)").await;
+        assert_eq!(
+            vec!["tag_0", "tag_1", "timestamp", "field_0"],
+            output_columns(&plan)
+        );
+        // ties are broken by labels
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(plan_str.contains(
+            "ROW_NUMBER() PARTITION BY [some_metric.tag_1, some_metric.timestamp] ORDER BY [some_metric.field_0 DESC NULLS LAST, some_metric.tag_0 ASC NULLS LAST, some_metric.tag_1 ASC NULLS LAST]"
+        ), "{plan_str}");
+        assert!(plan_str.contains("<= UInt64(3)"), "{plan_str}");
+
+        let plan = plan_with_params("bottomk without (tag_1) (1, some_metric)").await;
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(plan_str.contains(
+            "ROW_NUMBER() PARTITION BY [some_metric.tag_0, some_metric.timestamp] ORDER BY [some_metric.field_0 ASC NULLS LAST, some_metric.tag_0 ASC NULLS LAST, some_metric.tag_1 ASC NULLS LAST]"
+        ), "{plan_str}");
+
+        let plan = plan_with_params("quantile by (tag_1) (0.9, some_metric)").await;
+        assert_eq!(
+            vec!["tag_1", "timestamp", "prom_quantile(some_metric.field_0)"],
+            output_columns(&plan)
+        );
+
+        // the value label overrides the existing one
+        let plan = plan_with_params("count_values by (tag_1) (\"tag_1\", some_metric)").await;
+        assert_eq!(
+            vec!["tag_1", "timestamp", "COUNT(some_metric.field_0)"],
+            output_columns(&plan)
+        );
+        let plan = plan_with_params("count_values without (tag_1) (\"value\", some_metric)").await;
+        assert_eq!(
+            vec!["tag_0", "value", "timestamp", "COUNT(some_metric.field_0)"],
+            output_columns(&plan)
+        );
+        let plan = plan_with_params("count_values (\"value\", some_metric)").await;
+        assert_eq!(
+            vec!["value", "timestamp", "COUNT(some_metric.field_0)"],
+            output_columns(&plan)
+        );
+        // the value is formatted like Prometheus, e.g. `1` rather than `1.0`
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(
+            plan_str.contains("prom_format_value(CAST(some_metric.field_0 AS Float64)) AS value"),
+            "{plan_str}"
+        );
+
+        // each field is ranked separately
+        let plan = plan_with_params_and_fields("topk(1, some_metric)", 2).await;
+        assert_eq!(
+            vec!["tag_0", "tag_1", "timestamp", "field_0", "field_1"],
+            output_columns(&plan)
+        );
+        let plan_str = plan.display_indent_schema().to_string();
+        for field in ["field_0", "field_1"] {
+            let rank = format!(
+                "ROW_NUMBER() PARTITION BY [some_metric.timestamp] ORDER BY [some_metric.{field} DESC NULLS LAST, some_metric.tag_0 ASC NULLS LAST, some_metric.tag_1 ASC NULLS LAST]"
+            );
+            assert!(
+                plan_str.contains(&format!("CASE WHEN {rank}")),
+                "{plan_str}"
+            );
+            assert!(
+                plan_str.contains(&format!("THEN some_metric.{field} END AS {field}")),
+                "{plan_str}"
+            );
+        }
+
+        // param is required to be a literal
+        let table_provider = build_test_table_provider(
+            &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+            2,
+            1,
+        )
+        .await;
+        let eval_stmt = EvalStmt {
+            expr: parser::parse("topk(scalar(some_metric), some_metric)").unwrap(),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+        assert!(
+            PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn group_left_and_group_right() {
+        let cases = [
+            (
+                "some_metric * on(tag_0) group_left(tag_1) some_alt_metric",
+                vec!["tag_0", "tag_1", "timestamp", "lhs.field_0 * rhs.field_0"],
+                "Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp",
+                "some_alt_metric",
+            ),
+            (
+                "some_metric / ignoring(tag_1) group_right some_alt_metric",
+                vec!["tag_0", "tag_1", "timestamp", "lhs.field_0 / rhs.field_0"],
+                "Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp",
+                "some_metric",
+            ),
+            (
+                "some_metric > on(tag_0) group_left some_alt_metric",
+                vec!["tag_0", "tag_1", "timestamp", "field_0"],
+                "Filter: lhs.field_0 > rhs.field_0",
+                "some_alt_metric",
+            ),
+        ];
+
+        for (query, expected_columns, expected_plan, one_side) in cases {
+            let eval_stmt = EvalStmt {
+                expr: parser::parse(query).unwrap(),
+                start: UNIX_EPOCH,
+                end: UNIX_EPOCH
+                    .checked_add(Duration::from_secs(100_000))
+                    .unwrap(),
+                interval: Duration::from_secs(5),
+                lookback_delta: Duration::from_secs(1),
+            };
+            let table_provider = build_test_table_provider(
+                &[
+                    (DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string()),
+                    (
+                        DEFAULT_SCHEMA_NAME.to_string(),
+                        "some_alt_metric".to_string(),
+                    ),
+                ],
+                2,
+                1,
+            )
+            .await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+                .await
+                .unwrap();
+            let columns = plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>();
+            assert_eq!(expected_columns, columns, "{query}");
+            let plan_str = plan.display_indent_schema().to_string();
+            assert!(plan_str.contains(expected_plan), "{query}\n{plan_str}");
+            // series of the "one" side must be unique on the matching labels
+            let unique_check = format!(
+                "Filter: prom_ensure_unique(COUNT({one_side}.timestamp) PARTITION BY [{one_side}.tag_0, {one_side}.timestamp]"
+            );
+            assert!(plan_str.contains(&unique_check), "{query}\n{plan_str}");
+        }
+    }
+
+    #[tokio::test]
+    async fn label_replace_and_label_join() {
+        // rewrite an existing label
+        let plan = plan_with_params(
+            "label_replace(some_metric, \"tag_0\", \"$1\", \"tag_1\", \"(.*)-suffix\")",
+        )
+        .await;
+        assert_eq!(
+            vec!["tag_1", "tag_0", "timestamp", "field_0"],
+            output_columns(&plan)
+        );
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(plan_str.contains("regexp_replace"), "{plan_str}");
+        assert!(plan_str.contains("^(?:(.*)-suffix)$"), "{plan_str}");
+
+        // add a new label
+        let plan = plan_with_params(
+            "label_join(some_metric, \"joined\", \"-\", \"tag_0\", \"tag_1\", \"absent\")",
+        )
+        .await;
+        assert_eq!(
+            vec!["tag_0", "tag_1", "joined", "timestamp", "field_0"],
+            output_columns(&plan)
+        );
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(plan_str.contains("concat_ws"), "{plan_str}");
+
+        // the new label can be used by following expressions
+        let plan = plan_with_params(
+            "count by (joined) (label_join(some_metric, \"joined\", \",\", \"tag_0\"))",
+        )
+        .await;
+        assert_eq!(
+            vec!["joined", "timestamp", "COUNT(some_metric.field_0)"],
+            output_columns(&plan)
+        );
+    }
+
+    #[tokio::test]
+    async fn label_replace_invalid_args() {
+        let cases = [
+            "label_replace(some_metric, \"tag_0\", \"$1\", \"tag_1\", \"(.*\")",
+            "label_replace(some_metric, \"0tag\", \"$1\", \"tag_1\", \"(.*)\")",
+            "label_join(some_metric, \"tag-0\", \",\", \"tag_1\")",
+        ];
+        for query in cases {
+            let eval_stmt = EvalStmt {
+                expr: parser::parse(query).unwrap(),
+                start: UNIX_EPOCH,
+                end: UNIX_EPOCH
+                    .checked_add(Duration::from_secs(100_000))
+                    .unwrap(),
+                interval: Duration::from_secs(5),
+                lookback_delta: Duration::from_secs(1),
+            };
+            let table_provider = build_test_table_provider(
+                &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+                2,
+                1,
+            )
+            .await;
+            let result =
+                PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state()).await;
+            assert!(result.is_err(), "{query}");
+        }
+    }
+
     async fn do_aggregate_expr_plan(fn_name: &str, plan_name: &str) {
         let prom_expr = parser::parse(&format!(
             "{fn_name} by (tag_1)(some_metric{{tag_0!=\"bar\"}})",
@@ -2921,6 +4047,131 @@ mod test {
         indie_query_plan_compare(query, expected).await;
     }
 
+    #[tokio::test]
+    async fn filter_on_multi_field_columns() {
+        let cases = [
+            // each field is filtered separately
+            (
+                "some_metric > 0.5",
+                vec!["tag_0", "timestamp", "field_0", "field_1"],
+                "Filter: some_metric.field_0 > Float64(0.5) OR some_metric.field_1 > Float64(0.5)",
+            ),
+            // pick a field by `__field__` matcher
+            (
+                r#"some_metric{__field__="field_1"} > 0.5"#,
+                vec!["field_1", "tag_0", "timestamp"],
+                "Filter: some_metric.field_1 > Float64(0.5)",
+            ),
+        ];
+
+        for (query, expected_columns, expected_plan) in cases {
+            let eval_stmt = EvalStmt {
+                expr: parser::parse(query).unwrap(),
+                start: UNIX_EPOCH,
+                end: UNIX_EPOCH
+                    .checked_add(Duration::from_secs(100_000))
+                    .unwrap(),
+                interval: Duration::from_secs(5),
+                lookback_delta: Duration::from_secs(1),
+            };
+            let table_provider = build_test_table_provider(
+                &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+                1,
+                2,
+            )
+            .await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+                .await
+                .unwrap();
+            assert_eq!(expected_columns, output_columns(&plan), "{query}");
+            let plan_str = plan.display_indent_schema().to_string();
+            assert!(plan_str.contains(expected_plan), "{query}\n{plan_str}");
+        }
+
+        // values not satisfying the condition are set to null
+        let eval_stmt = EvalStmt {
+            expr: parser::parse("some_metric > 0.5").unwrap(),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+        let table_provider = build_test_table_provider(
+            &[(DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string())],
+            1,
+            2,
+        )
+        .await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent_schema().to_string();
+        assert!(
+            plan_str.contains(
+                "CASE WHEN some_metric.field_0 > Float64(0.5) THEN some_metric.field_0 END AS field_0"
+            ),
+            "{plan_str}"
+        );
+
+        // vector-vector comparisons keep values of the left operand
+        let cases = [
+            (
+                "some_metric > some_metric",
+                "Filter: lhs.field_0 > rhs.field_0 OR lhs.field_1 > rhs.field_1",
+                "CASE WHEN lhs.field_0 > rhs.field_0 THEN lhs.field_0 END AS field_0",
+            ),
+            (
+                "some_metric < some_alt_metric",
+                "Filter: some_metric.field_0 < some_alt_metric.field_0 OR some_metric.field_1 < some_alt_metric.field_1",
+                "CASE WHEN some_metric.field_1 < some_alt_metric.field_1 THEN some_metric.field_1 END AS field_1",
+            ),
+            (
+                "some_metric > on(tag_0) group_left some_alt_metric",
+                "Filter: lhs.field_0 > rhs.field_0 OR lhs.field_1 > rhs.field_1",
+                "CASE WHEN lhs.field_1 > rhs.field_1 THEN lhs.field_1 END AS field_1",
+            ),
+        ];
+        for (query, expected_filter, expected_projection) in cases {
+            let eval_stmt = EvalStmt {
+                expr: parser::parse(query).unwrap(),
+                start: UNIX_EPOCH,
+                end: UNIX_EPOCH
+                    .checked_add(Duration::from_secs(100_000))
+                    .unwrap(),
+                interval: Duration::from_secs(5),
+                lookback_delta: Duration::from_secs(1),
+            };
+            let table_provider = build_test_table_provider(
+                &[
+                    (DEFAULT_SCHEMA_NAME.to_string(), "some_metric".to_string()),
+                    (
+                        DEFAULT_SCHEMA_NAME.to_string(),
+                        "some_alt_metric".to_string(),
+                    ),
+                ],
+                1,
+                2,
+            )
+            .await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt, &build_session_state())
+                .await
+                .unwrap();
+            assert_eq!(
+                vec!["tag_0", "timestamp", "field_0", "field_1"],
+                output_columns(&plan),
+                "{query}"
+            );
+            let plan_str = plan.display_indent_schema().to_string();
+            assert!(plan_str.contains(expected_filter), "{query}\n{plan_str}");
+            assert!(
+                plan_str.contains(expected_projection),
+                "{query}\n{plan_str}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn value_matcher() {
         // template
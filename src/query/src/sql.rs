@@ -18,8 +18,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use catalog::information_schema::{
-    columns, flows, key_column_usage, schemata, tables, CHARACTER_SETS, COLLATIONS, COLUMNS, FLOWS,
-    KEY_COLUMN_USAGE, SCHEMATA, TABLES, VIEWS,
+    columns, flows, key_column_usage, process_list, schemata, tables, CHARACTER_SETS, COLLATIONS,
+    COLUMNS, FLOWS, KEY_COLUMN_USAGE, PROCESS_LIST, SCHEMATA, TABLES, VIEWS,
 };
 use catalog::CatalogManagerRef;
 use common_catalog::consts::{
@@ -54,8 +54,8 @@ use sql::ast::Ident;
 use sql::parser::ParserContext;
 use sql::statements::create::{CreateFlow, CreateView, Partitions};
 use sql::statements::show::{
-    ShowColumns, ShowDatabases, ShowFlows, ShowIndex, ShowKind, ShowTableStatus, ShowTables,
-    ShowVariables, ShowViews,
+    ShowColumns, ShowDatabases, ShowFlows, ShowIndex, ShowKind, ShowProcesslist, ShowTableStatus,
+    ShowTables, ShowVariables, ShowViews,
 };
 use sql::statements::statement::Statement;
 use sqlparser::ast::ObjectName;
@@ -91,6 +91,14 @@ const NO_STR: &str = "NO";
 const PRI_KEY: &str = "PRI";
 const TIME_INDEX: &str = "TIME INDEX";
 
+/// SHOW PROCESSLIST columns
+const PROCESS_ID_COLUMN: &str = "Id";
+const PROCESS_USER_COLUMN: &str = "User";
+const PROCESS_CHANNEL_COLUMN: &str = "Channel";
+const PROCESS_DB_COLUMN: &str = "Db";
+const PROCESS_START_TIME_COLUMN: &str = "Start_time";
+const PROCESS_INFO_COLUMN: &str = "Info";
+
 /// SHOW index columns
 const INDEX_TABLE_COLUMN: &str = "Table";
 const INDEX_NONT_UNIQUE_COLUMN: &str = "Non_unique";
@@ -791,6 +799,38 @@ pub async fn show_flows(
     .await
 }
 
+/// Execute [`ShowProcesslist`] statement and return the [`Output`] if success.
+pub async fn show_processlist(
+    _stmt: ShowProcesslist,
+    query_engine: &QueryEngineRef,
+    catalog_manager: &CatalogManagerRef,
+    query_ctx: QueryContextRef,
+) -> Result<Output> {
+    let projects = vec![
+        (process_list::ID, PROCESS_ID_COLUMN),
+        (process_list::USER_NAME, PROCESS_USER_COLUMN),
+        (process_list::CHANNEL, PROCESS_CHANNEL_COLUMN),
+        (process_list::SCHEMA_NAME, PROCESS_DB_COLUMN),
+        (process_list::START_TIME, PROCESS_START_TIME_COLUMN),
+        (process_list::QUERY, PROCESS_INFO_COLUMN),
+    ];
+    let sort = vec![col(process_list::START_TIME).sort(true, true)];
+
+    query_from_information_schema_table(
+        query_engine,
+        catalog_manager,
+        query_ctx,
+        PROCESS_LIST,
+        vec![],
+        projects,
+        vec![],
+        None,
+        sort,
+        ShowKind::All,
+    )
+    .await
+}
+
 pub fn show_create_flow(
     flow_name: ObjectName,
     flow_val: FlowInfoValue,
@@ -26,14 +26,20 @@ use sql::dialect::GreptimeDbDialect;
 use sql::parser::ParserContext;
 use sql::statements::create::{Column, ColumnExtensions, CreateTable, TIME_INDEX};
 use sql::statements::{self, OptionMap};
-use sql::{COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE};
+use sql::{
+    COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE,
+    COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS, COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE,
+    COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE, COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY,
+    COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD, COLUMN_VECTOR_INDEX_OPT_KEY_METRIC,
+};
 use sqlparser::ast::KeyOrIndexDisplay;
 use store_api::metric_engine_consts::{is_metric_engine, is_metric_engine_internal_column};
 use table::metadata::{TableInfoRef, TableMeta};
 use table::requests::{FILE_TABLE_META_KEY, TTL_KEY, WRITE_BUFFER_SIZE_KEY};
 
 use crate::error::{
-    ConvertSqlTypeSnafu, ConvertSqlValueSnafu, GetFulltextOptionsSnafu, Result, SqlSnafu,
+    ConvertSqlTypeSnafu, ConvertSqlValueSnafu, GetFulltextOptionsSnafu,
+    GetSkippingIndexOptionsSnafu, GetVectorIndexOptionsSnafu, Result, SqlSnafu,
 };
 
 fn create_sql_options(table_meta: &TableMeta) -> OptionMap {
@@ -97,7 +103,7 @@ fn create_column(column_schema: &ColumnSchema, quote_style: char) -> Result<Colu
         .context(GetFulltextOptionsSnafu)?
         && opt.enable
     {
-        let map = HashMap::from([
+        let mut map = HashMap::from([
             (
                 COLUMN_FULLTEXT_OPT_KEY_ANALYZER.to_string(),
                 opt.analyzer.to_string(),
@@ -107,9 +113,53 @@ fn create_column(column_schema: &ColumnSchema, quote_style: char) -> Result<Colu
                 opt.case_sensitive.to_string(),
             ),
         ]);
+        if !opt.stop_words.is_empty() {
+            map.insert(
+                COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS.to_string(),
+                opt.stop_words.join(","),
+            );
+        }
         extensions.fulltext_options = Some(map.into());
     }
 
+    if let Some(opt) = column_schema
+        .vector_index_options()
+        .context(GetVectorIndexOptionsSnafu)?
+    {
+        let map = HashMap::from([
+            (
+                COLUMN_VECTOR_INDEX_OPT_KEY_METRIC.to_string(),
+                opt.metric.to_string(),
+            ),
+            (
+                COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY.to_string(),
+                opt.connectivity.to_string(),
+            ),
+            (
+                COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD.to_string(),
+                opt.expansion_add.to_string(),
+            ),
+        ]);
+        extensions.vector_index_options = Some(map.into());
+    }
+
+    if let Some(opt) = column_schema
+        .skipping_index_options()
+        .context(GetSkippingIndexOptionsSnafu)?
+    {
+        let map = HashMap::from([
+            (
+                COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE.to_string(),
+                opt.index_type.to_string(),
+            ),
+            (
+                COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE.to_string(),
+                opt.false_positive_rate.to_string(),
+            ),
+        ]);
+        extensions.skipping_index_options = Some(map.into());
+    }
+
     Ok(Column {
         column_def: ColumnDef {
             name: Ident::with_quote(quote_style, name),
@@ -254,6 +254,36 @@ pub enum Error {
         error: prost::DecodeError,
     },
 
+    #[snafu(display("Failed to decode Loki request"))]
+    DecodeLokiRequest {
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: prost::DecodeError,
+    },
+
+    #[snafu(display("Invalid Loki labels: {}, reason: {}", labels, reason))]
+    InvalidLokiLabels {
+        labels: String,
+        reason: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to build column options"))]
+    BuildColumnOptions {
+        #[snafu(implicit)]
+        location: Location,
+        source: api::error::Error,
+    },
+
+    #[snafu(display("Invalid OTLP JSON request: {}", reason))]
+    InvalidOtlpJsonRequest {
+        reason: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
     #[snafu(display("Failed to decompress snappy prometheus remote request"))]
     DecompressSnappyPromRemoteRequest {
         #[snafu(implicit)]
@@ -657,6 +687,9 @@ impl ErrorExt for Error {
             | InvalidOpentsdbJsonRequest { .. }
             | DecodePromRemoteRequest { .. }
             | DecodeOtlpRequest { .. }
+            | InvalidOtlpJsonRequest { .. }
+            | DecodeLokiRequest { .. }
+            | InvalidLokiLabels { .. }
             | CompressPromRemoteRequest { .. }
             | DecompressSnappyPromRemoteRequest { .. }
             | DecompressZstdPromRemoteRequest { .. }
@@ -681,6 +714,8 @@ impl ErrorExt for Error {
             | PromSeriesWrite { source, .. }
             | OtlpMetricsWrite { source, .. } => source.status_code(),
 
+            BuildColumnOptions { source, .. } => source.status_code(),
+
             Hyper { .. } => StatusCode::Unknown,
             TlsRequired { .. } => StatusCode::Unknown,
             Auth { source, .. } => source.status_code(),
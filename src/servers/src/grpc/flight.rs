@@ -47,6 +47,17 @@ pub trait FlightCraft: Send + Sync + 'static {
         &self,
         request: Request<Ticket>,
     ) -> TonicResult<Response<TonicStream<FlightData>>>;
+
+    /// Writes the record batches in the stream, e.g. bulk inserts to a region.
+    ///
+    /// Only the datanode serves it for now. The frontend doesn't route batches to
+    /// regions, so clients have to write to the datanodes of the regions directly.
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> TonicResult<Response<TonicStream<PutResult>>> {
+        Err(Status::unimplemented("Not yet implemented"))
+    }
 }
 
 pub type FlightCraftRef = Arc<dyn FlightCraft>;
@@ -67,6 +78,13 @@ impl FlightCraft for FlightCraftRef {
     ) -> TonicResult<Response<TonicStream<FlightData>>> {
         (**self).do_get(request).await
     }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> TonicResult<Response<TonicStream<PutResult>>> {
+        (**self).do_put(request).await
+    }
 }
 
 #[async_trait]
@@ -120,9 +138,9 @@ impl<T: FlightCraft> FlightService for FlightCraftWrapper<T> {
 
     async fn do_put(
         &self,
-        _: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> TonicResult<Response<Self::DoPutStream>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        self.0.do_put(request).await
     }
 
     type DoExchangeStream = TonicStream<FlightData>;
@@ -74,10 +74,13 @@ use crate::query_handler::{
 use crate::server::Server;
 
 pub mod authorize;
+pub mod elasticsearch;
+pub mod elasticsearch;
 pub mod event;
 pub mod handler;
 pub mod header;
 pub mod influxdb;
+pub mod loki;
 pub mod mem_prof;
 pub mod opentsdb;
 pub mod otlp;
@@ -597,10 +600,20 @@ impl HttpServerBuilder {
         validator: Option<LogValidatorRef>,
     ) -> Self {
         Self {
-            router: self.router.nest(
-                &format!("/{HTTP_API_VERSION}/events"),
-                HttpServer::route_log(handler, validator),
-            ),
+            router: self
+                .router
+                .nest(
+                    &format!("/{HTTP_API_VERSION}/events"),
+                    HttpServer::route_log(handler.clone(), validator.clone()),
+                )
+                .nest(
+                    &format!("/{HTTP_API_VERSION}/loki"),
+                    HttpServer::route_loki(handler.clone(), validator.clone()),
+                )
+                .nest(
+                    &format!("/{HTTP_API_VERSION}/elasticsearch"),
+                    HttpServer::route_elasticsearch(handler, validator),
+                ),
             ..self
         }
     }
@@ -754,6 +767,49 @@ impl HttpServer {
             })
     }
 
+    fn route_loki<S>(
+        log_handler: LogHandlerRef,
+        log_validator: Option<LogValidatorRef>,
+    ) -> Router<S> {
+        Router::new()
+            .route("/api/v1/push", routing::post(loki::loki_ingest))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(handle_error))
+                    .layer(RequestDecompressionLayer::new()),
+            )
+            .with_state(LogState {
+                log_handler,
+                log_validator,
+            })
+    }
+
+    fn route_elasticsearch<S>(
+        log_handler: LogHandlerRef,
+        log_validator: Option<LogValidatorRef>,
+    ) -> Router<S> {
+        Router::new()
+            .route(
+                "/",
+                routing::get(elasticsearch::handle_get_version)
+                    .head(elasticsearch::handle_get_version),
+            )
+            .route("/_bulk", routing::post(elasticsearch::handle_bulk_api))
+            .route(
+                "/:index/_bulk",
+                routing::post(elasticsearch::handle_bulk_api_with_index),
+            )
+            .layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(handle_error))
+                    .layer(RequestDecompressionLayer::new()),
+            )
+            .with_state(LogState {
+                log_handler,
+                log_validator,
+            })
+    }
+
     fn route_sql<S>(api_state: ApiState) -> ApiRouter<S> {
         ApiRouter::new()
             .api_route(
@@ -855,6 +911,7 @@ impl HttpServer {
         Router::new()
             .route("/v1/metrics", routing::post(otlp::metrics))
             .route("/v1/traces", routing::post(otlp::traces))
+            .route("/v1/logs", routing::post(otlp::logs))
             .with_state(otlp_handler)
     }
 
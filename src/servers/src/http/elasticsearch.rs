@@ -0,0 +1,452 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Elasticsearch compatible APIs, so that log shippers like Filebeat, Logstash and
+//! Vector can use GreptimeDB as an Elasticsearch sink.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use api::v1::{Row, RowInsertRequest, RowInsertRequests, Rows};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use common_error::ext::ErrorExt;
+use common_telemetry::tracing;
+use pipeline::util::to_pipeline_version;
+use pipeline::{GreptimeTransformer, Pipeline};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use session::context::{Channel, QueryContext, QueryContextRef};
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{InvalidParameterSnafu, ParseJsonSnafu, PipelineSnafu, Result};
+use crate::http::event::LogState;
+use crate::metrics::{
+    METRIC_ELASTICSEARCH_LOGS_DOCS_COUNT, METRIC_ELASTICSEARCH_LOGS_INGESTION_ELAPSED,
+    METRIC_FAILURE_VALUE, METRIC_SUCCESS_VALUE,
+};
+
+/// The Elasticsearch version GreptimeDB claims to be compatible with.
+const ELASTICSEARCH_VERSION: &str = "8.16.0";
+
+/// Shippers check this header to ensure the server is an Elasticsearch server.
+static ELASTIC_PRODUCT_HEADER: HeaderName = HeaderName::from_static("x-elastic-product");
+static ELASTIC_PRODUCT_HEADER_VALUE: HeaderValue = HeaderValue::from_static("Elasticsearch");
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BulkQueryParams {
+    /// Name of the pipeline to transform documents, like the ingest pipeline of
+    /// Elasticsearch.
+    pub pipeline: Option<String>,
+    /// Version of the pipeline, the latest version is used if not specified.
+    pub version: Option<String>,
+}
+
+/// Handles the `GET /` request, which is used by shippers to check the version of
+/// the Elasticsearch server.
+#[axum_macros::debug_handler]
+pub async fn handle_get_version() -> impl IntoResponse {
+    let body = json!({
+        "name": "GreptimeDB",
+        "cluster_name": "GreptimeDB",
+        "cluster_uuid": "",
+        "version": {
+            "number": ELASTICSEARCH_VERSION,
+            "build_flavor": "default",
+            "lucene_version": "",
+            "minimum_wire_compatibility_version": "7.17.0",
+            "minimum_index_compatibility_version": "7.0.0",
+        },
+        "tagline": "You Know, for Search",
+    });
+    (elastic_headers(), Json(body))
+}
+
+/// Handles the `POST /_bulk` request.
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip_all, fields(protocol = "elasticsearch", request_type = "bulk"))]
+pub async fn handle_bulk_api(
+    State(log_state): State<LogState>,
+    Query(params): Query<BulkQueryParams>,
+    Extension(query_ctx): Extension<QueryContext>,
+    payload: String,
+) -> Result<impl IntoResponse> {
+    do_handle_bulk_api(log_state, None, params, query_ctx, payload).await
+}
+
+/// Handles the `POST /{index}/_bulk` request, the index is the default table of
+/// documents.
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip_all, fields(protocol = "elasticsearch", request_type = "bulk"))]
+pub async fn handle_bulk_api_with_index(
+    State(log_state): State<LogState>,
+    Path(index): Path<String>,
+    Query(params): Query<BulkQueryParams>,
+    Extension(query_ctx): Extension<QueryContext>,
+    payload: String,
+) -> Result<impl IntoResponse> {
+    do_handle_bulk_api(log_state, Some(index), params, query_ctx, payload).await
+}
+
+async fn do_handle_bulk_api(
+    log_state: LogState,
+    default_index: Option<String>,
+    params: BulkQueryParams,
+    mut query_ctx: QueryContext,
+    payload: String,
+) -> Result<impl IntoResponse> {
+    let start = Instant::now();
+    query_ctx.set_channel(Channel::Http);
+    let query_ctx = Arc::new(query_ctx);
+    let db = query_ctx.get_db_string();
+
+    let pipeline_name = params.pipeline.context(InvalidParameterSnafu {
+        reason: "pipeline is required",
+    })?;
+    let version = to_pipeline_version(params.version).context(PipelineSnafu)?;
+    let mut items = parse_bulk_request(&payload, default_index.as_deref())?;
+
+    let pipeline = log_state
+        .log_handler
+        .get_pipeline(&pipeline_name, version, query_ctx.clone())
+        .await?;
+
+    // Documents of the same index are written into the same table.
+    let mut indexes: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, item) in items.iter().enumerate() {
+        if item.error.is_none()
+            && let Some(index) = &item.index
+        {
+            indexes.entry(index.clone()).or_default().push(i);
+        }
+    }
+
+    for (index, item_ids) in indexes {
+        let mut rows = Vec::with_capacity(item_ids.len());
+        let mut row_item_ids = Vec::with_capacity(item_ids.len());
+        for i in item_ids {
+            let doc = items[i].doc.take().unwrap_or_default();
+            match transform_document(&pipeline, doc) {
+                Ok(row) => {
+                    rows.push(row);
+                    row_item_ids.push(i);
+                }
+                Err(reason) => {
+                    items[i].error = Some(BulkItemError::new(
+                        400,
+                        "document_parsing_exception",
+                        reason,
+                    ))
+                }
+            }
+        }
+        if rows.is_empty() {
+            continue;
+        }
+
+        let num_rows = rows.len();
+        let result = insert_rows(&log_state, &pipeline, index, rows, query_ctx.clone()).await;
+        match result {
+            Ok(()) => {
+                METRIC_ELASTICSEARCH_LOGS_DOCS_COUNT
+                    .with_label_values(&[db.as_str()])
+                    .inc_by(num_rows as u64);
+            }
+            Err(e) => {
+                let reason = e.output_msg();
+                for i in row_item_ids {
+                    items[i].error = Some(BulkItemError::new(
+                        500,
+                        "greptime_exception",
+                        reason.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let errors = items.iter().any(|item| item.error.is_some());
+    METRIC_ELASTICSEARCH_LOGS_INGESTION_ELAPSED
+        .with_label_values(&[
+            db.as_str(),
+            if errors {
+                METRIC_FAILURE_VALUE
+            } else {
+                METRIC_SUCCESS_VALUE
+            },
+        ])
+        .observe(start.elapsed().as_secs_f64());
+
+    let body = json!({
+        "took": start.elapsed().as_millis() as u64,
+        "errors": errors,
+        "items": items.iter().map(BulkItem::to_response).collect::<Vec<_>>(),
+    });
+    Ok((elastic_headers(), Json(body)))
+}
+
+fn elastic_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        ELASTIC_PRODUCT_HEADER.clone(),
+        ELASTIC_PRODUCT_HEADER_VALUE.clone(),
+    );
+    headers
+}
+
+fn transform_document(
+    pipeline: &Pipeline<GreptimeTransformer>,
+    doc: Value,
+) -> std::result::Result<Row, String> {
+    let mut intermediate_state = pipeline.init_intermediate_state();
+    pipeline.prepare(doc, &mut intermediate_state)?;
+    pipeline.exec_mut(&mut intermediate_state)
+}
+
+async fn insert_rows(
+    log_state: &LogState,
+    pipeline: &Pipeline<GreptimeTransformer>,
+    table_name: String,
+    rows: Vec<Row>,
+    query_ctx: QueryContextRef,
+) -> Result<()> {
+    let requests = RowInsertRequests {
+        inserts: vec![RowInsertRequest {
+            rows: Some(Rows {
+                rows,
+                schema: pipeline.schemas().clone(),
+            }),
+            table_name,
+        }],
+    };
+    log_state
+        .log_handler
+        .insert_logs(requests, query_ctx)
+        .await
+        .map(|_| ())
+}
+
+/// An operation in the bulk request.
+#[derive(Debug)]
+struct BulkItem {
+    /// The action of the operation, e.g. `index`, `create`.
+    action: String,
+    index: Option<String>,
+    id: Option<String>,
+    /// The document to write.
+    doc: Option<Value>,
+    error: Option<BulkItemError>,
+}
+
+#[derive(Debug)]
+struct BulkItemError {
+    status: u16,
+    error_type: &'static str,
+    reason: String,
+}
+
+impl BulkItemError {
+    fn new(status: u16, error_type: &'static str, reason: impl Into<String>) -> Self {
+        Self {
+            status,
+            error_type,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl BulkItem {
+    fn to_response(&self) -> Value {
+        let mut result = Map::new();
+        result.insert(
+            "_index".to_string(),
+            Value::String(self.index.clone().unwrap_or_default()),
+        );
+        result.insert(
+            "_id".to_string(),
+            Value::String(self.id.clone().unwrap_or_default()),
+        );
+        match &self.error {
+            Some(error) => {
+                result.insert("status".to_string(), Value::from(error.status));
+                result.insert(
+                    "error".to_string(),
+                    json!({
+                        "type": error.error_type,
+                        "reason": error.reason,
+                    }),
+                );
+            }
+            None => {
+                result.insert("_version".to_string(), Value::from(1));
+                result.insert("result".to_string(), Value::from("created"));
+                result.insert("status".to_string(), Value::from(201));
+            }
+        }
+        let mut response = Map::new();
+        response.insert(self.action.clone(), Value::Object(result));
+        Value::Object(response)
+    }
+}
+
+/// Parses the NDJSON body of the bulk request, each operation consists of an
+/// action line and an optional document line.
+///
+/// Only `index` and `create` actions are supported, other actions are parsed but
+/// responded with errors.
+fn parse_bulk_request(payload: &str, default_index: Option<&str>) -> Result<Vec<BulkItem>> {
+    let mut items = vec![];
+    let mut lines = payload.lines().filter(|line| !line.trim().is_empty());
+    while let Some(line) = lines.next() {
+        let action_line: Value = serde_json::from_str(line).context(ParseJsonSnafu)?;
+        let Some((action, meta)) = action_line
+            .as_object()
+            .filter(|obj| obj.len() == 1)
+            .and_then(|obj| obj.iter().next())
+        else {
+            return InvalidParameterSnafu {
+                reason: format!("malformed action line: {line}"),
+            }
+            .fail();
+        };
+
+        let index = meta
+            .get("_index")
+            .and_then(Value::as_str)
+            .or(default_index)
+            .map(|s| s.to_string());
+        let id = meta.get("_id").map(|id| match id {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        });
+        let mut item = BulkItem {
+            action: action.clone(),
+            index,
+            id,
+            doc: None,
+            error: None,
+        };
+
+        match action.as_str() {
+            "index" | "create" | "update" => {
+                let doc_line = lines.next().with_context(|| InvalidParameterSnafu {
+                    reason: format!("missing document of action: {line}"),
+                })?;
+                if action == "update" {
+                    item.error = Some(BulkItemError::new(
+                        400,
+                        "illegal_argument_exception",
+                        "update action is not supported",
+                    ));
+                } else {
+                    item.doc = Some(serde_json::from_str(doc_line).context(ParseJsonSnafu)?);
+                }
+            }
+            "delete" => {
+                item.error = Some(BulkItemError::new(
+                    400,
+                    "illegal_argument_exception",
+                    "delete action is not supported",
+                ));
+            }
+            _ => {
+                return InvalidParameterSnafu {
+                    reason: format!("unknown action {action}"),
+                }
+                .fail();
+            }
+        }
+        if item.index.is_none() && item.error.is_none() {
+            item.error = Some(BulkItemError::new(
+                400,
+                "action_request_validation_exception",
+                "index is missing",
+            ));
+        }
+
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bulk_request() {
+        let payload = r#"
+{"index": {"_index": "logs", "_id": "1"}}
+{"message": "hello"}
+{"create": {"_id": 2}}
+{"message": "world"}
+{"delete": {"_index": "logs", "_id": "1"}}
+{"update": {"_index": "logs", "_id": "1"}}
+{"doc": {"message": "updated"}}
+"#;
+        let items = parse_bulk_request(payload, Some("default")).unwrap();
+        assert_eq!(4, items.len());
+
+        assert_eq!("index", items[0].action);
+        assert_eq!(Some("logs"), items[0].index.as_deref());
+        assert_eq!(Some("1"), items[0].id.as_deref());
+        assert_eq!(Some(json!({"message": "hello"})), items[0].doc);
+        assert!(items[0].error.is_none());
+
+        assert_eq!("create", items[1].action);
+        assert_eq!(Some("default"), items[1].index.as_deref());
+        assert_eq!(Some("2"), items[1].id.as_deref());
+        assert!(items[1].error.is_none());
+
+        assert_eq!("delete", items[2].action);
+        assert_eq!(400, items[2].error.as_ref().unwrap().status);
+        assert_eq!("update", items[3].action);
+        assert_eq!(400, items[3].error.as_ref().unwrap().status);
+
+        // index is missing
+        let items = parse_bulk_request("{\"index\": {}}\n{}", None).unwrap();
+        assert_eq!(400, items[0].error.as_ref().unwrap().status);
+
+        // malformed requests
+        assert!(parse_bulk_request("{\"index\": {}}", None).is_err());
+        assert!(parse_bulk_request("{\"unknown\": {}}\n{}", None).is_err());
+        assert!(parse_bulk_request("{\"index\": {}, \"create\": {}}\n{}", None).is_err());
+        assert!(parse_bulk_request("not json", None).is_err());
+    }
+
+    #[test]
+    fn test_bulk_item_response() {
+        let mut item = BulkItem {
+            action: "create".to_string(),
+            index: Some("logs".to_string()),
+            id: None,
+            doc: None,
+            error: None,
+        };
+        assert_eq!(
+            json!({"create": {"_index": "logs", "_id": "", "_version": 1, "result": "created", "status": 201}}),
+            item.to_response()
+        );
+
+        item.error = Some(BulkItemError::new(400, "document_parsing_exception", "bad"));
+        assert_eq!(
+            json!({"create": {"_index": "logs", "_id": "", "status": 400, "error": {"type": "document_parsing_exception", "reason": "bad"}}}),
+            item.to_response()
+        );
+    }
+}
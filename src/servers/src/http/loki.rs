@@ -0,0 +1,531 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use api::v1::column_def::options_from_fulltext;
+use api::v1::value::ValueData;
+use api::v1::{ColumnDataType, RowInsertRequests};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Extension;
+use bytes::Bytes;
+use common_grpc::precision::Precision;
+use common_query::prelude::GREPTIME_TIMESTAMP;
+use common_query::{Output, OutputData};
+use common_telemetry::tracing;
+use datatypes::schema::FulltextOptions;
+use prost::Message;
+use serde_json::Value;
+use session::context::{Channel, QueryContext};
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{
+    BuildColumnOptionsSnafu, DecodeLokiRequestSnafu, InvalidLokiLabelsSnafu, InvalidParameterSnafu,
+    ParseJsonSnafu, Result,
+};
+use crate::http::event::LogState;
+use crate::http::header::constants::GREPTIME_LOG_TABLE_NAME_HEADER_NAME;
+use crate::http::header::write_cost_header_map;
+use crate::metrics::{
+    METRIC_FAILURE_VALUE, METRIC_LOKI_LOGS_INGESTION_COUNTER, METRIC_LOKI_LOGS_INGESTION_ELAPSED,
+    METRIC_SUCCESS_VALUE,
+};
+use crate::prom_store::snappy_decompress;
+use crate::row_writer::{self, MultiTableData, TableData};
+
+/// The default table to write Loki logs.
+pub const LOKI_TABLE_NAME: &str = "loki_logs";
+/// The column of log lines, which is fulltext indexed.
+pub const LOKI_LINE_COLUMN: &str = "line";
+/// The column of structured metadata of log lines, stored as a JSON string.
+pub const LOKI_STRUCTURED_METADATA_COLUMN: &str = "structured_metadata";
+
+const APPROXIMATE_COLUMN_COUNT: usize = 8;
+
+/// The push request of Loki, see
+/// <https://github.com/grafana/loki/blob/main/pkg/push/push.proto>.
+#[derive(Clone, PartialEq, Message)]
+pub struct PushRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub streams: Vec<StreamAdapter>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct StreamAdapter {
+    /// Labels of the stream, in the form of `{key="value", ...}`.
+    #[prost(string, tag = "1")]
+    pub labels: String,
+    #[prost(message, repeated, tag = "2")]
+    pub entries: Vec<EntryAdapter>,
+    #[prost(uint64, tag = "3")]
+    pub hash: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct EntryAdapter {
+    #[prost(message, optional, tag = "1")]
+    pub timestamp: Option<Timestamp>,
+    #[prost(string, tag = "2")]
+    pub line: String,
+    #[prost(message, repeated, tag = "3")]
+    pub structured_metadata: Vec<LabelPairAdapter>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct LabelPairAdapter {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+/// The `google.protobuf.Timestamp` message.
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+/// Labels of a stream.
+type Labels = Vec<(String, String)>;
+
+/// A log line of a stream.
+struct LokiEntry {
+    timestamp_nanos: i64,
+    line: String,
+    structured_metadata: BTreeMap<String, String>,
+}
+
+/// Handles the Loki push request, the request is either snappy compressed protobuf
+/// or JSON.
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip_all, fields(protocol = "loki", request_type = "push"))]
+pub async fn loki_ingest(
+    State(log_state): State<LogState>,
+    Extension(mut query_ctx): Extension<QueryContext>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse> {
+    query_ctx.set_channel(Channel::Loki);
+    let query_ctx = Arc::new(query_ctx);
+    let db = query_ctx.get_db_string();
+    let exec_timer = Instant::now();
+
+    let table_name = match headers.get(GREPTIME_LOG_TABLE_NAME_HEADER_NAME) {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .context(InvalidParameterSnafu {
+                reason: format!("invalid value of header {GREPTIME_LOG_TABLE_NAME_HEADER_NAME}"),
+            })?
+            .to_string(),
+        None => LOKI_TABLE_NAME.to_string(),
+    };
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+
+    let streams = if is_json {
+        decode_json_request(&body)?
+    } else {
+        decode_protobuf_request(&body)?
+    };
+    let (requests, rows) = to_grpc_insert_requests(table_name, streams)?;
+
+    let output = log_state.log_handler.insert_logs(requests, query_ctx).await;
+    if let Ok(Output {
+        data: OutputData::AffectedRows(_),
+        ..
+    }) = &output
+    {
+        METRIC_LOKI_LOGS_INGESTION_COUNTER
+            .with_label_values(&[db.as_str()])
+            .inc_by(rows as u64);
+        METRIC_LOKI_LOGS_INGESTION_ELAPSED
+            .with_label_values(&[db.as_str(), METRIC_SUCCESS_VALUE])
+            .observe(exec_timer.elapsed().as_secs_f64());
+    } else {
+        METRIC_LOKI_LOGS_INGESTION_ELAPSED
+            .with_label_values(&[db.as_str(), METRIC_FAILURE_VALUE])
+            .observe(exec_timer.elapsed().as_secs_f64());
+    }
+    let output = output?;
+
+    // Loki responds the push request with 204 No Content.
+    Ok((
+        StatusCode::NO_CONTENT,
+        write_cost_header_map(output.meta.cost),
+    ))
+}
+
+/// Decodes the snappy compressed protobuf request into streams of labels and entries.
+fn decode_protobuf_request(body: &[u8]) -> Result<Vec<(Labels, Vec<LokiEntry>)>> {
+    let buf = snappy_decompress(body)?;
+    let request = PushRequest::decode(&buf[..]).context(DecodeLokiRequestSnafu)?;
+
+    request
+        .streams
+        .into_iter()
+        .map(|stream| {
+            let labels = parse_labels(&stream.labels)?;
+            let entries = stream
+                .entries
+                .into_iter()
+                .map(|entry| LokiEntry {
+                    timestamp_nanos: entry
+                        .timestamp
+                        .map(|ts| ts.seconds * 1_000_000_000 + ts.nanos as i64)
+                        .unwrap_or_default(),
+                    line: entry.line,
+                    structured_metadata: entry
+                        .structured_metadata
+                        .into_iter()
+                        .map(|pair| (pair.name, pair.value))
+                        .collect(),
+                })
+                .collect();
+            Ok((labels, entries))
+        })
+        .collect()
+}
+
+/// Decodes the JSON request in the form of
+/// `{"streams": [{"stream": {"label": "value"}, "values": [["<ns>", "<line>", {<metadata>}]]}]}`.
+fn decode_json_request(body: &[u8]) -> Result<Vec<(Labels, Vec<LokiEntry>)>> {
+    let request: Value = serde_json::from_slice(body).context(ParseJsonSnafu)?;
+    let invalid = |reason: &str| {
+        InvalidParameterSnafu {
+            reason: format!("invalid Loki JSON request: {reason}"),
+        }
+        .build()
+    };
+
+    let streams = request
+        .get("streams")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("expect streams array"))?;
+    let mut result = Vec::with_capacity(streams.len());
+    for stream in streams {
+        let labels = match stream.get("stream") {
+            Some(Value::Object(labels)) => labels
+                .iter()
+                .map(|(k, v)| {
+                    v.as_str()
+                        .map(|v| (k.clone(), v.to_string()))
+                        .ok_or_else(|| invalid("expect string label values"))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+            Some(_) => return Err(invalid("expect stream object")),
+        };
+
+        let values = stream
+            .get("values")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("expect values array"))?;
+        let mut entries = Vec::with_capacity(values.len());
+        for value in values {
+            let value = value
+                .as_array()
+                .filter(|v| v.len() == 2 || v.len() == 3)
+                .ok_or_else(|| invalid("expect [timestamp, line, metadata?] values"))?;
+            let timestamp_nanos = value[0]
+                .as_str()
+                .and_then(|ts| ts.parse::<i64>().ok())
+                .ok_or_else(|| invalid("expect timestamps in nanoseconds as strings"))?;
+            let line = value[1]
+                .as_str()
+                .ok_or_else(|| invalid("expect lines as strings"))?
+                .to_string();
+            let structured_metadata = match value.get(2) {
+                Some(Value::Object(metadata)) => metadata
+                    .iter()
+                    .map(|(k, v)| {
+                        v.as_str()
+                            .map(|v| (k.clone(), v.to_string()))
+                            .ok_or_else(|| invalid("expect string metadata values"))
+                    })
+                    .collect::<Result<_>>()?,
+                None => BTreeMap::new(),
+                Some(_) => return Err(invalid("expect metadata object")),
+            };
+            entries.push(LokiEntry {
+                timestamp_nanos,
+                line,
+                structured_metadata,
+            });
+        }
+
+        result.push((labels, entries));
+    }
+
+    Ok(result)
+}
+
+/// Parses labels in the form of `{key="value", ...}`.
+fn parse_labels(labels: &str) -> Result<Labels> {
+    let invalid = |reason: &str| {
+        InvalidLokiLabelsSnafu {
+            labels,
+            reason: reason.to_string(),
+        }
+        .build()
+    };
+
+    let content = labels
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| invalid("labels must be enclosed in braces"))?;
+
+    let mut result = vec![];
+    let mut chars = content.chars().peekable();
+    loop {
+        // skip separators
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut name = String::new();
+        while let Some(c) = chars.next_if(|c| *c != '=') {
+            name.push(c);
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid("empty label name"));
+        }
+        if chars.next() != Some('=') {
+            return Err(invalid("expect '=' after label name"));
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.next() != Some('"') {
+            return Err(invalid("expect quoted label value"));
+        }
+
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c) => value.push(c),
+                    None => return Err(invalid("unterminated label value")),
+                },
+                Some(c) => value.push(c),
+                None => return Err(invalid("unterminated label value")),
+            }
+        }
+        result.push((name, value));
+    }
+
+    Ok(result)
+}
+
+/// Converts streams into row insert requests, labels of streams are written as tags.
+///
+/// Returns `InsertRequests` and total number of rows to ingest
+fn to_grpc_insert_requests(
+    table_name: String,
+    streams: Vec<(Labels, Vec<LokiEntry>)>,
+) -> Result<(RowInsertRequests, usize)> {
+    let num_rows = streams.iter().map(|(_, entries)| entries.len()).sum();
+    let mut multi_table_writer = MultiTableData::default();
+    let one_table_writer = multi_table_writer.get_or_default_table_data(
+        table_name,
+        APPROXIMATE_COLUMN_COUNT,
+        num_rows,
+    );
+
+    for (labels, entries) in streams {
+        for entry in entries {
+            write_entry_to_row(one_table_writer, &labels, entry)?;
+        }
+    }
+
+    let (mut requests, rows) = multi_table_writer.into_row_insert_requests();
+    // Log lines are searched by fulltext queries.
+    let line_options = options_from_fulltext(&FulltextOptions {
+        enable: true,
+        ..Default::default()
+    })
+    .context(BuildColumnOptionsSnafu)?;
+    for column in requests
+        .inserts
+        .iter_mut()
+        .filter_map(|insert| insert.rows.as_mut())
+        .flat_map(|rows| rows.schema.iter_mut())
+        .filter(|column| column.column_name == LOKI_LINE_COLUMN)
+    {
+        column.options = line_options.clone();
+    }
+
+    Ok((requests, rows))
+}
+
+fn write_entry_to_row(
+    writer: &mut TableData,
+    labels: &[(String, String)],
+    entry: LokiEntry,
+) -> Result<()> {
+    let mut row = writer.alloc_one_row();
+    row_writer::write_tags(writer, labels.iter().cloned(), &mut row)?;
+
+    let mut fields = vec![(
+        LOKI_LINE_COLUMN.to_string(),
+        ColumnDataType::String,
+        ValueData::StringValue(entry.line),
+    )];
+    if !entry.structured_metadata.is_empty() {
+        fields.push((
+            LOKI_STRUCTURED_METADATA_COLUMN.to_string(),
+            ColumnDataType::String,
+            ValueData::StringValue(
+                serde_json::to_string(&entry.structured_metadata).unwrap_or_default(),
+            ),
+        ));
+    }
+    row_writer::write_fields(writer, fields.into_iter(), &mut row)?;
+    row_writer::write_ts_to_nanos(
+        writer,
+        GREPTIME_TIMESTAMP,
+        Some(entry.timestamp_nanos),
+        Precision::Nanosecond,
+        &mut row,
+    )?;
+
+    writer.add_row(row);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::SemanticType;
+
+    use super::*;
+    use crate::prom_store::snappy_compress;
+
+    #[test]
+    fn test_parse_labels() {
+        assert_eq!(
+            vec![
+                ("job".to_string(), "varlogs".to_string()),
+                ("path".to_string(), "a \"quoted\" \\path".to_string()),
+            ],
+            parse_labels(r#"{job="varlogs", path="a \"quoted\" \\path"}"#).unwrap()
+        );
+        assert!(parse_labels("{}").unwrap().is_empty());
+        assert!(parse_labels(r#"job="varlogs""#).is_err());
+        assert!(parse_labels(r#"{job=varlogs}"#).is_err());
+        assert!(parse_labels(r#"{job="varlogs}"#).is_err());
+        assert!(parse_labels(r#"{="varlogs"}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_requests() {
+        let request = PushRequest {
+            streams: vec![StreamAdapter {
+                labels: r#"{job="varlogs"}"#.to_string(),
+                entries: vec![EntryAdapter {
+                    timestamp: Some(Timestamp {
+                        seconds: 1,
+                        nanos: 2,
+                    }),
+                    line: "hello".to_string(),
+                    structured_metadata: vec![LabelPairAdapter {
+                        name: "trace_id".to_string(),
+                        value: "abc".to_string(),
+                    }],
+                }],
+                hash: 0,
+            }],
+        };
+        let body = snappy_compress(&request.encode_to_vec()).unwrap();
+        let protobuf_streams = decode_protobuf_request(&body).unwrap();
+
+        let body = r#"{"streams": [{"stream": {"job": "varlogs"}, "values": [["1000000002", "hello", {"trace_id": "abc"}]]}]}"#;
+        let json_streams = decode_json_request(body.as_bytes()).unwrap();
+
+        for streams in [protobuf_streams, json_streams] {
+            assert_eq!(1, streams.len());
+            let (labels, entries) = &streams[0];
+            assert_eq!(&vec![("job".to_string(), "varlogs".to_string())], labels);
+            assert_eq!(1_000_000_002, entries[0].timestamp_nanos);
+            assert_eq!("hello", entries[0].line);
+            assert_eq!("abc", entries[0].structured_metadata["trace_id"]);
+        }
+
+        assert!(decode_json_request(br#"{"streams": [{"values": [["x", "hello"]]}]}"#).is_err());
+        assert!(decode_json_request(br#"{"stream": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let streams = vec![
+            (
+                vec![("job".to_string(), "a".to_string())],
+                vec![LokiEntry {
+                    timestamp_nanos: 1,
+                    line: "line1".to_string(),
+                    structured_metadata: BTreeMap::new(),
+                }],
+            ),
+            (
+                vec![
+                    ("job".to_string(), "b".to_string()),
+                    ("host".to_string(), "h".to_string()),
+                ],
+                vec![LokiEntry {
+                    timestamp_nanos: 2,
+                    line: "line2".to_string(),
+                    structured_metadata: BTreeMap::new(),
+                }],
+            ),
+        ];
+        let (requests, rows) =
+            to_grpc_insert_requests(LOKI_TABLE_NAME.to_string(), streams).unwrap();
+        assert_eq!(2, rows);
+
+        let insert = &requests.inserts[0];
+        assert_eq!(LOKI_TABLE_NAME, insert.table_name);
+        let schema = &insert.rows.as_ref().unwrap().schema;
+        let columns = schema
+            .iter()
+            .map(|c| (c.column_name.as_str(), c.semantic_type))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("job", SemanticType::Tag as i32),
+                (LOKI_LINE_COLUMN, SemanticType::Field as i32),
+                (GREPTIME_TIMESTAMP, SemanticType::Timestamp as i32),
+                ("host", SemanticType::Tag as i32),
+            ],
+            columns
+        );
+        let line_column = schema
+            .iter()
+            .find(|c| c.column_name == LOKI_LINE_COLUMN)
+            .unwrap();
+        assert!(api::v1::column_def::contains_fulltext(&line_column.options));
+    }
+}
@@ -15,23 +15,32 @@
 use std::sync::Arc;
 
 use axum::extract::{RawBody, State};
-use axum::http::header;
+use axum::http::{header, HeaderMap, HeaderValue};
 use axum::response::IntoResponse;
 use axum::Extension;
 use common_telemetry::tracing;
 use hyper::Body;
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
 use opentelemetry_proto::tonic::collector::metrics::v1::{
     ExportMetricsServiceRequest, ExportMetricsServiceResponse,
 };
 use opentelemetry_proto::tonic::collector::trace::v1::{
     ExportTraceServiceRequest, ExportTraceServiceResponse,
 };
+use pipeline::util::to_pipeline_version;
 use prost::Message;
 use session::context::{Channel, QueryContext};
 use snafu::prelude::*;
 
+use super::header::constants::{
+    GREPTIME_LOG_PIPELINE_NAME_HEADER_NAME, GREPTIME_LOG_PIPELINE_VERSION_HEADER_NAME,
+    GREPTIME_LOG_TABLE_NAME_HEADER_NAME,
+};
 use super::header::{write_cost_header_map, CONTENT_TYPE_PROTOBUF};
-use crate::error::{self, Result};
+use crate::error::{self, InvalidParameterSnafu, PipelineSnafu, Result};
+use crate::otlp::logs::{self, PipelineWay, LOG_TABLE_NAME};
 use crate::query_handler::OpenTelemetryProtocolHandlerRef;
 
 #[axum_macros::debug_handler]
@@ -130,3 +139,98 @@ impl IntoResponse for OtlpTracesResponse {
         (header_map, self.resp_body.encode_to_vec()).into_response()
     }
 }
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip_all, fields(protocol = "otlp", request_type = "logs"))]
+pub async fn logs(
+    State(handler): State<OpenTelemetryProtocolHandlerRef>,
+    Extension(mut query_ctx): Extension<QueryContext>,
+    headers: HeaderMap,
+    RawBody(body): RawBody,
+) -> Result<OtlpLogsResponse> {
+    let db = query_ctx.get_db_string();
+    query_ctx.set_channel(Channel::Otlp);
+    let query_ctx = Arc::new(query_ctx);
+    let _timer = crate::metrics::METRIC_HTTP_OPENTELEMETRY_LOGS_ELAPSED
+        .with_label_values(&[db.as_str()])
+        .start_timer();
+
+    let pipeline = match header_str(&headers, GREPTIME_LOG_PIPELINE_NAME_HEADER_NAME)? {
+        Some(name) => {
+            let version = header_str(&headers, GREPTIME_LOG_PIPELINE_VERSION_HEADER_NAME)?
+                .map(|v| v.to_string());
+            PipelineWay::Custom {
+                name: name.to_string(),
+                version: to_pipeline_version(version).context(PipelineSnafu)?,
+            }
+        }
+        None => PipelineWay::OtlpLog,
+    };
+    let table_name = header_str(&headers, GREPTIME_LOG_TABLE_NAME_HEADER_NAME)?
+        .unwrap_or(LOG_TABLE_NAME)
+        .to_string();
+    // OTLP/HTTP requests are either binary protobuf or JSON encoded, and responses
+    // must use the same encoding as the requests.
+    let is_json = header_str(&headers, header::CONTENT_TYPE.as_str())?
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    let request = parse_logs_body(body, is_json).await?;
+
+    handler
+        .logs(request, pipeline, table_name, query_ctx)
+        .await
+        .map(|o| OtlpLogsResponse {
+            resp_body: ExportLogsServiceResponse {
+                partial_success: None,
+            },
+            write_cost: o.meta.cost,
+            is_json,
+        })
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>> {
+    headers
+        .get(name)
+        .map(|value| {
+            value.to_str().map_err(|_| {
+                InvalidParameterSnafu {
+                    reason: format!("invalid value of header {name}"),
+                }
+                .build()
+            })
+        })
+        .transpose()
+}
+
+async fn parse_logs_body(body: Body, is_json: bool) -> Result<ExportLogsServiceRequest> {
+    let buf = hyper::body::to_bytes(body)
+        .await
+        .context(error::HyperSnafu)?;
+    if is_json {
+        logs::json::decode_request(&buf)
+    } else {
+        ExportLogsServiceRequest::decode(&buf[..]).context(error::DecodeOtlpRequestSnafu)
+    }
+}
+
+pub struct OtlpLogsResponse {
+    resp_body: ExportLogsServiceResponse,
+    write_cost: usize,
+    is_json: bool,
+}
+
+impl IntoResponse for OtlpLogsResponse {
+    fn into_response(self) -> axum::response::Response {
+        let mut header_map = write_cost_header_map(self.write_cost);
+        if self.is_json {
+            // The response has no partial success, which is an empty object in JSON.
+            header_map.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            (header_map, "{}").into_response()
+        } else {
+            header_map.insert(header::CONTENT_TYPE, CONTENT_TYPE_PROTOBUF.clone());
+            (header_map, self.resp_body.encode_to_vec()).into_response()
+        }
+    }
+}
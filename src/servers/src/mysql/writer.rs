@@ -21,6 +21,7 @@ use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
 use common_telemetry::{debug, error};
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::SchemaRef;
+use datatypes::types::{json_type_value_to_string, vector_type_value_to_string};
 use futures::StreamExt;
 use opensrv_mysql::{
     Column, ColumnFlags, ColumnType, ErrorKind, OkResponse, QueryResultWriter, RowWriter,
@@ -193,7 +194,7 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
         query_context: QueryContextRef,
     ) -> Result<()> {
         for row in recordbatch.rows() {
-            for value in row.into_iter() {
+            for (value, column) in row.into_iter().zip(recordbatch.schema.column_schemas()) {
                 match value {
                     Value::Null => row_writer.write_col(None::<u8>)?,
                     Value::Boolean(v) => row_writer.write_col(v as i8)?,
@@ -208,7 +209,25 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                     Value::Float32(v) => row_writer.write_col(v.0)?,
                     Value::Float64(v) => row_writer.write_col(v.0)?,
                     Value::String(v) => row_writer.write_col(v.as_utf8())?,
-                    Value::Binary(v) => row_writer.write_col(v.deref())?,
+                    Value::Binary(v) => match &column.data_type {
+                        ConcreteDataType::Json(j) => {
+                            let json = json_type_value_to_string(&v, &j.format).map_err(|e| {
+                                Error::Internal {
+                                    err_msg: format!("Failed to convert json value: {e}"),
+                                }
+                            })?;
+                            row_writer.write_col(json)?
+                        }
+                        ConcreteDataType::Vector(d) => {
+                            let vector = vector_type_value_to_string(&v, d.dim).map_err(|e| {
+                                Error::Internal {
+                                    err_msg: format!("Failed to convert vector value: {e}"),
+                                }
+                            })?;
+                            row_writer.write_col(vector)?
+                        }
+                        _ => row_writer.write_col(v.deref())?,
+                    },
                     Value::Date(v) => row_writer.write_col(v.to_chrono_date())?,
                     // convert datetime and timestamp to timezone of current connection
                     Value::DateTime(v) => row_writer.write_col(
@@ -281,6 +300,8 @@ pub(crate) fn create_mysql_column(
         ConcreteDataType::Interval(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
         ConcreteDataType::Duration(_) => Ok(ColumnType::MYSQL_TYPE_TIME),
         ConcreteDataType::Decimal128(_) => Ok(ColumnType::MYSQL_TYPE_DECIMAL),
+        ConcreteDataType::Json(_) => Ok(ColumnType::MYSQL_TYPE_JSON),
+        ConcreteDataType::Vector(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
         _ => error::UnsupportedDataTypeSnafu {
             data_type,
             reason: "not implemented",
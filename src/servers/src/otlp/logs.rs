@@ -0,0 +1,436 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::value::ValueData;
+use api::v1::{ColumnDataType, RowInsertRequest, RowInsertRequests, Rows};
+use common_grpc::precision::Precision;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::InstrumentationScope;
+use opentelemetry_proto::tonic::logs::v1::LogRecord;
+use pipeline::error::PipelineTransformSnafu;
+use pipeline::{GreptimeTransformer, Pipeline, PipelineVersion};
+use serde_json::{Map, Value};
+use snafu::ResultExt;
+
+use super::trace::attributes::{Attributes, OtlpAnyValue};
+use super::trace::span::bytes_to_hex_string;
+use crate::error::{PipelineSnafu, Result};
+use crate::row_writer::{self, MultiTableData, TableData};
+
+pub mod json;
+
+const APPROXIMATE_COLUMN_COUNT: usize = 16;
+pub const LOG_TABLE_NAME: &str = "opentelemetry_logs";
+
+/// The time index column of the logs table.
+pub const LOG_TIMESTAMP_COLUMN: &str = "timestamp";
+
+/// How to transform OTLP logs into rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineWay {
+    /// Maps each log record into a row of the built-in logs table schema.
+    OtlpLog,
+    /// Transforms log records by the pipeline with the given name and version.
+    Custom {
+        name: String,
+        version: PipelineVersion,
+    },
+}
+
+/// A log record with its resource and scope.
+struct OtlpLogRecord {
+    resource_attributes: Attributes,
+    resource_schema_url: String,
+    scope_name: String,
+    scope_version: String,
+    scope_attributes: Attributes,
+    scope_schema_url: String,
+    record: LogRecord,
+}
+
+impl OtlpLogRecord {
+    /// Returns the time of the log record, falls back to the observed time if the
+    /// event time is unknown.
+    fn timestamp(&self) -> u64 {
+        if self.record.time_unix_nano != 0 {
+            self.record.time_unix_nano
+        } else {
+            self.record.observed_time_unix_nano
+        }
+    }
+
+    /// Converts the log record into a JSON object, which is the input of pipelines.
+    fn into_json(self) -> Value {
+        let timestamp = self.timestamp();
+        let OtlpLogRecord {
+            resource_attributes,
+            resource_schema_url,
+            scope_name,
+            scope_version,
+            scope_attributes,
+            scope_schema_url,
+            record,
+        } = self;
+
+        let body = record
+            .body
+            .as_ref()
+            .map(|body| serde_json::to_value(OtlpAnyValue::from(body)).unwrap_or_default())
+            .unwrap_or_default();
+        let attributes_to_json =
+            |attrs: Attributes| serde_json::to_value(attrs).unwrap_or_default();
+
+        let fields = [
+            (LOG_TIMESTAMP_COLUMN, Value::from(timestamp)),
+            (
+                "observed_timestamp",
+                Value::from(record.observed_time_unix_nano),
+            ),
+            (
+                "trace_id",
+                Value::String(bytes_to_hex_string(&record.trace_id)),
+            ),
+            (
+                "span_id",
+                Value::String(bytes_to_hex_string(&record.span_id)),
+            ),
+            ("trace_flags", Value::from(record.flags)),
+            ("severity_text", Value::String(record.severity_text)),
+            ("severity_number", Value::from(record.severity_number)),
+            ("body", body),
+            (
+                "log_attributes",
+                attributes_to_json(Attributes::from(record.attributes)),
+            ),
+            (
+                "resource_attributes",
+                attributes_to_json(resource_attributes),
+            ),
+            ("resource_schema_url", Value::String(resource_schema_url)),
+            ("scope_name", Value::String(scope_name)),
+            ("scope_version", Value::String(scope_version)),
+            ("scope_attributes", attributes_to_json(scope_attributes)),
+            ("scope_schema_url", Value::String(scope_schema_url)),
+        ];
+        Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<Map<_, _>>(),
+        )
+    }
+}
+
+/// Flattens the log records with their resources and scopes.
+///
+/// See
+/// <https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/logs/v1/logs.proto>
+/// for data structure of OTLP logs.
+fn parse(request: ExportLogsServiceRequest) -> Vec<OtlpLogRecord> {
+    let mut records = vec![];
+    for resource_logs in request.resource_logs {
+        let resource_attributes = Attributes::from(
+            resource_logs
+                .resource
+                .map(|r| r.attributes)
+                .unwrap_or_default(),
+        );
+        for scope_logs in resource_logs.scope_logs {
+            let InstrumentationScope {
+                name,
+                version,
+                attributes,
+                ..
+            } = scope_logs.scope.unwrap_or_default();
+            let scope_attributes = Attributes::from(attributes);
+            for record in scope_logs.log_records {
+                records.push(OtlpLogRecord {
+                    resource_attributes: resource_attributes.clone(),
+                    resource_schema_url: resource_logs.schema_url.clone(),
+                    scope_name: name.clone(),
+                    scope_version: version.clone(),
+                    scope_attributes: scope_attributes.clone(),
+                    scope_schema_url: scope_logs.schema_url.clone(),
+                    record,
+                });
+            }
+        }
+    }
+    records
+}
+
+/// Convert OpenTelemetry logs to GreptimeDB row insert requests.
+///
+/// Log records are transformed by the `pipeline` if provided, otherwise they are
+/// mapped into the built-in logs table schema.
+///
+/// Returns `InsertRequests` and total number of rows to ingest
+pub fn to_grpc_insert_requests(
+    request: ExportLogsServiceRequest,
+    pipeline: Option<&Pipeline<GreptimeTransformer>>,
+    table_name: String,
+) -> Result<(RowInsertRequests, usize)> {
+    let records = parse(request);
+    match pipeline {
+        Some(pipeline) => {
+            let rows = transform_by_pipeline(pipeline, records)?;
+            let len = rows.rows.len();
+            let insert_request = RowInsertRequest {
+                rows: Some(rows),
+                table_name,
+            };
+            Ok((
+                RowInsertRequests {
+                    inserts: vec![insert_request],
+                },
+                len,
+            ))
+        }
+        None => {
+            let mut multi_table_writer = MultiTableData::default();
+            let one_table_writer = multi_table_writer.get_or_default_table_data(
+                table_name,
+                APPROXIMATE_COLUMN_COUNT,
+                records.len(),
+            );
+            for record in records {
+                write_log_to_row(one_table_writer, record)?;
+            }
+
+            Ok(multi_table_writer.into_row_insert_requests())
+        }
+    }
+}
+
+fn transform_by_pipeline(
+    pipeline: &Pipeline<GreptimeTransformer>,
+    records: Vec<OtlpLogRecord>,
+) -> Result<Rows> {
+    let mut intermediate_state = pipeline.init_intermediate_state();
+    let mut rows = Vec::with_capacity(records.len());
+    for record in records {
+        pipeline
+            .prepare(record.into_json(), &mut intermediate_state)
+            .map_err(|reason| PipelineTransformSnafu { reason }.build())
+            .context(PipelineSnafu)?;
+        let row = pipeline
+            .exec_mut(&mut intermediate_state)
+            .map_err(|reason| PipelineTransformSnafu { reason }.build())
+            .context(PipelineSnafu)?;
+        rows.push(row);
+        pipeline.reset_intermediate_state(&mut intermediate_state);
+    }
+
+    Ok(Rows {
+        rows,
+        schema: pipeline.schemas().clone(),
+    })
+}
+
+fn write_log_to_row(writer: &mut TableData, log: OtlpLogRecord) -> Result<()> {
+    let mut row = writer.alloc_one_row();
+    let timestamp = log.timestamp();
+    let OtlpLogRecord {
+        resource_attributes,
+        resource_schema_url,
+        scope_name,
+        scope_version,
+        scope_attributes,
+        scope_schema_url,
+        record,
+    } = log;
+
+    let body = record
+        .body
+        .as_ref()
+        .map(|body| OtlpAnyValue::from(body).to_string())
+        .unwrap_or_default();
+    let str_fields_iter = vec![
+        ("trace_id", bytes_to_hex_string(&record.trace_id)),
+        ("span_id", bytes_to_hex_string(&record.span_id)),
+        ("severity_text", record.severity_text),
+        ("body", body),
+        (
+            "log_attributes",
+            Attributes::from(record.attributes).to_string(),
+        ),
+        ("resource_attributes", resource_attributes.to_string()),
+        ("resource_schema_url", resource_schema_url),
+        ("scope_name", scope_name),
+        ("scope_version", scope_version),
+        ("scope_attributes", scope_attributes.to_string()),
+        ("scope_schema_url", scope_schema_url),
+    ]
+    .into_iter()
+    .map(|(col, val)| {
+        (
+            col.to_string(),
+            ColumnDataType::String,
+            ValueData::StringValue(val),
+        )
+    });
+    let other_fields_iter = vec![
+        (
+            "observed_timestamp".to_string(),
+            ColumnDataType::TimestampNanosecond,
+            ValueData::TimestampNanosecondValue(record.observed_time_unix_nano as i64),
+        ),
+        (
+            "trace_flags".to_string(),
+            ColumnDataType::Uint32,
+            ValueData::U32Value(record.flags),
+        ),
+        (
+            "severity_number".to_string(),
+            ColumnDataType::Int32,
+            ValueData::I32Value(record.severity_number),
+        ),
+    ]
+    .into_iter();
+
+    row_writer::write_fields(writer, str_fields_iter, &mut row)?;
+    row_writer::write_fields(writer, other_fields_iter, &mut row)?;
+    row_writer::write_ts_to_nanos(
+        writer,
+        LOG_TIMESTAMP_COLUMN,
+        Some(timestamp as i64),
+        Precision::Nanosecond,
+        &mut row,
+    )?;
+
+    writer.add_row(row);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::value::ValueData;
+    use opentelemetry_proto::tonic::common::v1::any_value::Value as AnyValueValue;
+    use opentelemetry_proto::tonic::common::v1::{AnyValue, KeyValue};
+    use opentelemetry_proto::tonic::logs::v1::{ResourceLogs, ScopeLogs};
+    use opentelemetry_proto::tonic::resource::v1::Resource;
+
+    use super::*;
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(AnyValueValue::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn mock_request() -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![key_value("service.name", "greptime")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "scope".to_string(),
+                        version: "0.1".to_string(),
+                        attributes: vec![],
+                        dropped_attributes_count: 0,
+                    }),
+                    log_records: vec![
+                        LogRecord {
+                            time_unix_nano: 1000,
+                            observed_time_unix_nano: 2000,
+                            severity_number: 9,
+                            severity_text: "INFO".to_string(),
+                            body: Some(AnyValue {
+                                value: Some(AnyValueValue::StringValue("hello".to_string())),
+                            }),
+                            attributes: vec![key_value("path", "/v1/logs")],
+                            dropped_attributes_count: 0,
+                            flags: 1,
+                            trace_id: vec![0x12, 0xab],
+                            span_id: vec![0x34],
+                        },
+                        LogRecord {
+                            observed_time_unix_nano: 3000,
+                            ..Default::default()
+                        },
+                    ],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let (requests, rows) =
+            to_grpc_insert_requests(mock_request(), None, LOG_TABLE_NAME.to_string()).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(1, requests.inserts.len());
+
+        let insert = &requests.inserts[0];
+        assert_eq!(LOG_TABLE_NAME, insert.table_name);
+        let rows = insert.rows.as_ref().unwrap();
+        let value_of = |row: usize, column: &str| {
+            let index = rows
+                .schema
+                .iter()
+                .position(|c| c.column_name == column)
+                .unwrap();
+            rows.rows[row].values[index].value_data.clone()
+        };
+
+        assert_eq!(
+            Some(ValueData::StringValue("12ab".to_string())),
+            value_of(0, "trace_id")
+        );
+        assert_eq!(
+            Some(ValueData::StringValue("hello".to_string())),
+            value_of(0, "body")
+        );
+        assert_eq!(
+            Some(ValueData::StringValue(r#"{"path":"/v1/logs"}"#.to_string())),
+            value_of(0, "log_attributes")
+        );
+        assert_eq!(
+            Some(ValueData::StringValue(
+                r#"{"service.name":"greptime"}"#.to_string()
+            )),
+            value_of(1, "resource_attributes")
+        );
+        assert_eq!(
+            Some(ValueData::TimestampNanosecondValue(1000)),
+            value_of(0, LOG_TIMESTAMP_COLUMN)
+        );
+        // Falls back to the observed time.
+        assert_eq!(
+            Some(ValueData::TimestampNanosecondValue(3000)),
+            value_of(1, LOG_TIMESTAMP_COLUMN)
+        );
+    }
+
+    #[test]
+    fn test_log_record_into_json() {
+        let record = parse(mock_request()).into_iter().next().unwrap();
+        let json = record.into_json();
+        assert_eq!(json["timestamp"], 1000);
+        assert_eq!(json["severity_text"], "INFO");
+        assert_eq!(json["body"], "hello");
+        assert_eq!(json["log_attributes"]["path"], "/v1/logs");
+        assert_eq!(json["resource_attributes"]["service.name"], "greptime");
+        assert_eq!(json["scope_name"], "scope");
+    }
+}
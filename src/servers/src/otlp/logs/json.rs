@@ -0,0 +1,380 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoder of OTLP/JSON encoded logs requests.
+//!
+//! The JSON encoding follows the
+//! [protobuf JSON mapping](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding)
+//! with the exceptions required by OTLP: `traceId` and `spanId` are hex strings
+//! instead of base64 strings, and enums are integers.
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::any_value::Value as AnyValueValue;
+use opentelemetry_proto::tonic::common::v1::{
+    AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use serde_json::{Map, Value};
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{InvalidOtlpJsonRequestSnafu, ParseJsonSnafu, Result};
+
+/// Decodes an OTLP/JSON encoded [ExportLogsServiceRequest].
+pub fn decode_request(bytes: &[u8]) -> Result<ExportLogsServiceRequest> {
+    let value: Value = serde_json::from_slice(bytes).context(ParseJsonSnafu)?;
+    let object = as_object(&value, "request")?;
+
+    Ok(ExportLogsServiceRequest {
+        resource_logs: decode_list(object, "resourceLogs", decode_resource_logs)?,
+    })
+}
+
+fn decode_resource_logs(value: &Value) -> Result<ResourceLogs> {
+    let object = as_object(value, "resourceLogs")?;
+    let resource = field(object, "resource")
+        .map(|v| {
+            let object = as_object(v, "resource")?;
+            Ok(Resource {
+                attributes: decode_list(object, "attributes", decode_key_value)?,
+                dropped_attributes_count: decode_u32(object, "droppedAttributesCount")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(ResourceLogs {
+        resource,
+        scope_logs: decode_list(object, "scopeLogs", decode_scope_logs)?,
+        schema_url: decode_string(object, "schemaUrl")?,
+    })
+}
+
+fn decode_scope_logs(value: &Value) -> Result<ScopeLogs> {
+    let object = as_object(value, "scopeLogs")?;
+    let scope = field(object, "scope")
+        .map(|v| {
+            let object = as_object(v, "scope")?;
+            Ok(InstrumentationScope {
+                name: decode_string(object, "name")?,
+                version: decode_string(object, "version")?,
+                attributes: decode_list(object, "attributes", decode_key_value)?,
+                dropped_attributes_count: decode_u32(object, "droppedAttributesCount")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(ScopeLogs {
+        scope,
+        log_records: decode_list(object, "logRecords", decode_log_record)?,
+        schema_url: decode_string(object, "schemaUrl")?,
+    })
+}
+
+fn decode_log_record(value: &Value) -> Result<LogRecord> {
+    let object = as_object(value, "logRecords")?;
+
+    Ok(LogRecord {
+        time_unix_nano: decode_u64(object, "timeUnixNano")?,
+        observed_time_unix_nano: decode_u64(object, "observedTimeUnixNano")?,
+        severity_number: decode_u64(object, "severityNumber")? as i32,
+        severity_text: decode_string(object, "severityText")?,
+        body: field(object, "body").map(decode_any_value).transpose()?,
+        attributes: decode_list(object, "attributes", decode_key_value)?,
+        dropped_attributes_count: decode_u32(object, "droppedAttributesCount")?,
+        flags: decode_u32(object, "flags")?,
+        trace_id: decode_hex(object, "traceId")?,
+        span_id: decode_hex(object, "spanId")?,
+    })
+}
+
+fn decode_key_value(value: &Value) -> Result<KeyValue> {
+    let object = as_object(value, "attributes")?;
+
+    Ok(KeyValue {
+        key: decode_string(object, "key")?,
+        value: field(object, "value").map(decode_any_value).transpose()?,
+    })
+}
+
+fn decode_any_value(value: &Value) -> Result<AnyValue> {
+    let object = as_object(value, "value")?;
+    let Some((key, value)) = object.iter().next() else {
+        return Ok(AnyValue { value: None });
+    };
+
+    let value = match key.as_str() {
+        "stringValue" | "string_value" => AnyValueValue::StringValue(as_string(value, key)?),
+        "boolValue" | "bool_value" => {
+            AnyValueValue::BoolValue(value.as_bool().with_context(|| {
+                InvalidOtlpJsonRequestSnafu {
+                    reason: format!("expect a bool for {key}, got {value}"),
+                }
+            })?)
+        }
+        "intValue" | "int_value" => AnyValueValue::IntValue(as_i64(value, key)?),
+        "doubleValue" | "double_value" => AnyValueValue::DoubleValue(as_f64(value, key)?),
+        "bytesValue" | "bytes_value" => {
+            AnyValueValue::BytesValue(BASE64_STANDARD.decode(as_string(value, key)?).map_err(
+                |e| {
+                    InvalidOtlpJsonRequestSnafu {
+                        reason: format!("invalid base64 string for {key}: {e}"),
+                    }
+                    .build()
+                },
+            )?)
+        }
+        "arrayValue" | "array_value" => AnyValueValue::ArrayValue(ArrayValue {
+            values: decode_list(as_object(value, key)?, "values", decode_any_value)?,
+        }),
+        "kvlistValue" | "kvlist_value" => AnyValueValue::KvlistValue(KeyValueList {
+            values: decode_list(as_object(value, key)?, "values", decode_key_value)?,
+        }),
+        _ => {
+            return InvalidOtlpJsonRequestSnafu {
+                reason: format!("unknown value type {key}"),
+            }
+            .fail()
+        }
+    };
+
+    Ok(AnyValue { value: Some(value) })
+}
+
+/// Returns the field by its lowerCamelCase name, the original snake_case name is
+/// also accepted as the protobuf JSON mapping does.
+fn field<'a>(object: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    object
+        .get(name)
+        .or_else(|| object.get(&to_snake_case(name)))
+        .filter(|v| !v.is_null())
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('_');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn decode_list<T>(
+    object: &Map<String, Value>,
+    name: &str,
+    decode: impl Fn(&Value) -> Result<T>,
+) -> Result<Vec<T>> {
+    match field(object, name) {
+        Some(Value::Array(values)) => values.iter().map(decode).collect(),
+        Some(value) => InvalidOtlpJsonRequestSnafu {
+            reason: format!("expect an array for {name}, got {value}"),
+        }
+        .fail(),
+        None => Ok(vec![]),
+    }
+}
+
+fn decode_string(object: &Map<String, Value>, name: &str) -> Result<String> {
+    field(object, name)
+        .map(|v| as_string(v, name))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn decode_u64(object: &Map<String, Value>, name: &str) -> Result<u64> {
+    let Some(value) = field(object, name) else {
+        return Ok(0);
+    };
+    // 64 bit integers are encoded as strings in the JSON mapping, but numbers are
+    // also accepted.
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+    .with_context(|| InvalidOtlpJsonRequestSnafu {
+        reason: format!("expect an unsigned integer for {name}, got {value}"),
+    })
+}
+
+fn decode_u32(object: &Map<String, Value>, name: &str) -> Result<u32> {
+    let value = decode_u64(object, name)?;
+    u32::try_from(value)
+        .ok()
+        .with_context(|| InvalidOtlpJsonRequestSnafu {
+            reason: format!("{name} {value} overflows u32"),
+        })
+}
+
+fn decode_hex(object: &Map<String, Value>, name: &str) -> Result<Vec<u8>> {
+    let s = decode_string(object, name)?;
+    let invalid = || {
+        InvalidOtlpJsonRequestSnafu {
+            reason: format!("invalid hex string for {name}: {s}"),
+        }
+        .build()
+    };
+    if s.len() % 2 != 0 {
+        return Err(invalid());
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            s.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or_else(&invalid)
+        })
+        .collect()
+}
+
+fn as_object<'a>(value: &'a Value, name: &str) -> Result<&'a Map<String, Value>> {
+    value
+        .as_object()
+        .with_context(|| InvalidOtlpJsonRequestSnafu {
+            reason: format!("expect an object for {name}, got {value}"),
+        })
+}
+
+fn as_string(value: &Value, name: &str) -> Result<String> {
+    value
+        .as_str()
+        .map(|s| s.to_string())
+        .with_context(|| InvalidOtlpJsonRequestSnafu {
+            reason: format!("expect a string for {name}, got {value}"),
+        })
+}
+
+fn as_i64(value: &Value, name: &str) -> Result<i64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_i64(),
+        _ => None,
+    }
+    .with_context(|| InvalidOtlpJsonRequestSnafu {
+        reason: format!("expect an integer for {name}, got {value}"),
+    })
+}
+
+fn as_f64(value: &Value, name: &str) -> Result<f64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_f64(),
+        _ => None,
+    }
+    .with_context(|| InvalidOtlpJsonRequestSnafu {
+        reason: format!("expect a number for {name}, got {value}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_request() {
+        let json = r#"{
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [{"key": "service.name", "value": {"stringValue": "greptime"}}]
+                },
+                "scopeLogs": [{
+                    "scope": {"name": "scope", "version": "0.1"},
+                    "logRecords": [{
+                        "timeUnixNano": "1544712660300000000",
+                        "observedTimeUnixNano": 1544712660300000001,
+                        "severityNumber": 10,
+                        "severityText": "Information",
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": "eee19b7ec3c1b174",
+                        "body": {"stringValue": "Example log record"},
+                        "attributes": [
+                            {"key": "int", "value": {"intValue": "42"}},
+                            {"key": "double", "value": {"doubleValue": 0.5}},
+                            {"key": "bool", "value": {"boolValue": true}},
+                            {"key": "bytes", "value": {"bytesValue": "aGVsbG8="}},
+                            {"key": "array", "value": {"arrayValue": {"values": [{"stringValue": "a"}]}}},
+                            {"key": "map", "value": {"kvlistValue": {"values": [{"key": "k", "value": {"intValue": 1}}]}}}
+                        ]
+                    }]
+                }]
+            }]
+        }"#;
+
+        let request = decode_request(json.as_bytes()).unwrap();
+        let resource_logs = &request.resource_logs[0];
+        assert_eq!(
+            "service.name",
+            resource_logs.resource.as_ref().unwrap().attributes[0].key
+        );
+        let scope_logs = &resource_logs.scope_logs[0];
+        assert_eq!("scope", scope_logs.scope.as_ref().unwrap().name);
+
+        let record = &scope_logs.log_records[0];
+        assert_eq!(1544712660300000000, record.time_unix_nano);
+        assert_eq!(1544712660300000001, record.observed_time_unix_nano);
+        assert_eq!(10, record.severity_number);
+        assert_eq!("Information", record.severity_text);
+        assert_eq!(
+            vec![
+                0x5b, 0x8e, 0xff, 0xf7, 0x98, 0x03, 0x81, 0x03, 0xd2, 0x69, 0xb6, 0x33, 0x81, 0x3f,
+                0xc6, 0x0c
+            ],
+            record.trace_id
+        );
+        assert_eq!(
+            vec![0xee, 0xe1, 0x9b, 0x7e, 0xc3, 0xc1, 0xb1, 0x74],
+            record.span_id
+        );
+        assert_eq!(
+            Some(AnyValueValue::StringValue("Example log record".to_string())),
+            record.body.as_ref().unwrap().value
+        );
+
+        let values = record
+            .attributes
+            .iter()
+            .map(|kv| kv.value.as_ref().unwrap().value.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(AnyValueValue::IntValue(42), values[0]);
+        assert_eq!(AnyValueValue::DoubleValue(0.5), values[1]);
+        assert_eq!(AnyValueValue::BoolValue(true), values[2]);
+        assert_eq!(AnyValueValue::BytesValue(b"hello".to_vec()), values[3]);
+        assert!(matches!(&values[4], AnyValueValue::ArrayValue(v) if v.values.len() == 1));
+        assert!(matches!(&values[5], AnyValueValue::KvlistValue(v) if v.values[0].key == "k"));
+    }
+
+    #[test]
+    fn test_decode_invalid_request() {
+        assert!(decode_request(b"[]").is_err());
+        assert!(decode_request(br#"{"resourceLogs": {}}"#).is_err());
+        assert!(decode_request(
+            br#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [{"traceId": "xyz"}]}]}]}"#
+        )
+        .is_err());
+        assert!(decode_request(
+            br#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [{"body": {"unknownValue": 1}}]}]}]}"#
+        )
+        .is_err());
+
+        // Unknown fields are ignored.
+        let request = decode_request(br#"{"resourceLogs": [], "unknown": 1}"#).unwrap();
+        assert!(request.resource_logs.is_empty());
+    }
+}
@@ -144,8 +144,8 @@ where
         .map(move |row| {
             row.and_then(|row| {
                 let mut encoder = DataRowEncoder::new(pg_schema_ref.clone());
-                for value in row.iter() {
-                    encode_value(&query_ctx, value, &mut encoder)?;
+                for (value, column) in row.iter().zip(schema.column_schemas()) {
+                    encode_value(&query_ctx, value, &mut encoder, &column.data_type)?;
                 }
                 encoder.finish()
             })
@@ -25,7 +25,7 @@ use common_time::Interval;
 use datafusion_common::ScalarValue;
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::Schema;
-use datatypes::types::TimestampType;
+use datatypes::types::{json_type_value_to_string, vector_type_value_to_string, TimestampType};
 use pgwire::api::portal::{Format, Portal};
 use pgwire::api::results::{DataRowEncoder, FieldInfo};
 use pgwire::api::Type;
@@ -62,6 +62,7 @@ pub(super) fn encode_value(
     query_ctx: &QueryContextRef,
     value: &Value,
     builder: &mut DataRowEncoder,
+    datatype: &ConcreteDataType,
 ) -> PgWireResult<()> {
     match value {
         Value::Null => builder.encode_field(&None::<&i8>),
@@ -77,13 +78,27 @@ pub(super) fn encode_value(
         Value::Float32(v) => builder.encode_field(&v.0),
         Value::Float64(v) => builder.encode_field(&v.0),
         Value::String(v) => builder.encode_field(&v.as_utf8()),
-        Value::Binary(v) => {
-            let bytea_output = query_ctx.configuration_parameter().postgres_bytea_output();
-            match *bytea_output {
-                PGByteaOutputValue::ESCAPE => builder.encode_field(&EscapeOutputBytea(v.deref())),
-                PGByteaOutputValue::HEX => builder.encode_field(&HexOutputBytea(v.deref())),
+        Value::Binary(v) => match datatype {
+            ConcreteDataType::Json(j) => {
+                let json = json_type_value_to_string(v, &j.format)
+                    .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+                builder.encode_field(&json)
             }
-        }
+            ConcreteDataType::Vector(d) => {
+                let vector = vector_type_value_to_string(v, d.dim)
+                    .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+                builder.encode_field(&vector)
+            }
+            _ => {
+                let bytea_output = query_ctx.configuration_parameter().postgres_bytea_output();
+                match *bytea_output {
+                    PGByteaOutputValue::ESCAPE => {
+                        builder.encode_field(&EscapeOutputBytea(v.deref()))
+                    }
+                    PGByteaOutputValue::HEX => builder.encode_field(&HexOutputBytea(v.deref())),
+                }
+            }
+        },
         Value::Date(v) => {
             if let Some(date) = v.to_chrono_date() {
                 let (style, order) = *query_ctx.configuration_parameter().pg_datetime_style();
@@ -154,6 +169,8 @@ pub(super) fn type_gt_to_pg(origin: &ConcreteDataType) -> Result<Type> {
         &ConcreteDataType::Time(_) => Ok(Type::TIME),
         &ConcreteDataType::Interval(_) => Ok(Type::INTERVAL),
         &ConcreteDataType::Decimal128(_) => Ok(Type::NUMERIC),
+        &ConcreteDataType::Json(_) => Ok(Type::JSON),
+        &ConcreteDataType::Vector(_) => Ok(Type::VARCHAR),
         &ConcreteDataType::Duration(_)
         | &ConcreteDataType::List(_)
         | &ConcreteDataType::Dictionary(_) => server_error::UnsupportedDataTypeSnafu {
@@ -582,6 +599,7 @@ mod test {
     use std::sync::Arc;
 
     use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::types::{parse_string_to_json_type_value, JsonFormat};
     use datatypes::value::ListValue;
     use pgwire::api::results::{FieldFormat, FieldInfo};
     use pgwire::api::Type;
@@ -776,6 +794,7 @@ mod test {
                 Type::INTERVAL,
                 FieldFormat::Text,
             ),
+            FieldInfo::new("jsons".into(), None, None, Type::JSON, FieldFormat::Text),
         ];
 
         let values = vec![
@@ -813,13 +832,22 @@ mod test {
             .into();
         let mut builder = DataRowEncoder::new(Arc::new(schema));
         for i in values.iter() {
-            encode_value(&query_context, i, &mut builder).unwrap();
+            encode_value(&query_context, i, &mut builder, &i.data_type()).unwrap();
         }
+        let json = parse_string_to_json_type_value(r#"{"a":1}"#, &JsonFormat::Jsonb).unwrap();
+        encode_value(
+            &query_context,
+            &Value::Binary(json.into()),
+            &mut builder,
+            &ConcreteDataType::json_datatype(),
+        )
+        .unwrap();
 
         let err = encode_value(
             &query_context,
             &Value::List(ListValue::new(vec![], ConcreteDataType::int16_datatype())),
             &mut builder,
+            &ConcreteDataType::list_datatype(ConcreteDataType::int16_datatype()),
         )
         .unwrap_err();
         match err {
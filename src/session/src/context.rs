@@ -320,6 +320,7 @@ pub enum Channel {
     Grpc = 6,
     Influx = 7,
     Opentsdb = 8,
+    Loki = 9,
 }
 
 impl From<u32> for Channel {
@@ -333,6 +334,7 @@ impl From<u32> for Channel {
             6 => Self::Grpc,
             7 => Self::Influx,
             8 => Self::Opentsdb,
+            9 => Self::Loki,
 
             _ => Self::Unknown,
         }
@@ -360,6 +362,7 @@ impl Display for Channel {
             Channel::Grpc => write!(f, "grpc"),
             Channel::Influx => write!(f, "influx"),
             Channel::Opentsdb => write!(f, "opentsdb"),
+            Channel::Loki => write!(f, "loki"),
             Channel::Unknown => write!(f, "unknown"),
         }
     }
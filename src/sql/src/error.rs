@@ -332,6 +332,34 @@ pub enum Error {
         #[snafu(implicit)]
         location: Location,
     },
+
+    #[snafu(display("Invalid vector index option: {}", msg))]
+    VectorIndexInvalidOption {
+        msg: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to set vector index option"))]
+    SetVectorIndexOption {
+        source: datatypes::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Invalid skipping index option: {}", msg))]
+    SkippingIndexInvalidOption {
+        msg: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Failed to set skipping index option"))]
+    SetSkippingIndexOption {
+        source: datatypes::error::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -367,7 +395,9 @@ impl ErrorExt for Error {
             | InvalidInterval { .. }
             | InvalidUnaryOp { .. }
             | UnsupportedUnaryOp { .. }
-            | FulltextInvalidOption { .. } => StatusCode::InvalidArguments,
+            | FulltextInvalidOption { .. }
+            | VectorIndexInvalidOption { .. }
+            | SkippingIndexInvalidOption { .. } => StatusCode::InvalidArguments,
 
             SerializeColumnDefaultConstraint { source, .. } => source.status_code(),
             ConvertToGrpcDataType { source, .. } => source.status_code(),
@@ -376,6 +406,9 @@ impl ErrorExt for Error {
 
             PermissionDenied { .. } => StatusCode::PermissionDenied,
             SetFulltextOption { .. } => StatusCode::Unexpected,
+            SetVectorIndexOption { source, .. } | SetSkippingIndexOption { source, .. } => {
+                source.status_code()
+            }
         }
     }
 
@@ -159,6 +159,12 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::SET => self.parse_set_variables(),
 
+                    Keyword::GRANT => self.parse_grant(),
+
+                    Keyword::REVOKE => self.parse_revoke(),
+
+                    Keyword::KILL => self.parse_kill(),
+
                     Keyword::NoKeyword
                         if w.value.to_uppercase() == tql_parser::TQL && w.quote_style.is_none() =>
                     {
@@ -23,10 +23,12 @@ pub(crate) mod error;
 pub(crate) mod execute_parser;
 pub(crate) mod explain_parser;
 pub(crate) mod insert_parser;
+pub(crate) mod kill_parser;
 pub(crate) mod prepare_parser;
 pub(crate) mod query_parser;
 pub(crate) mod set_var_parser;
 pub(crate) mod show_parser;
 pub(crate) mod tql_parser;
 pub(crate) mod truncate_parser;
+pub(crate) mod user_parser;
 pub(crate) mod utils;
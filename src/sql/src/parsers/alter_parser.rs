@@ -25,6 +25,12 @@ use crate::statements::statement::Statement;
 
 impl<'a> ParserContext<'a> {
     pub(crate) fn parse_alter(&mut self) -> Result<Statement> {
+        if let Token::Word(w) = self.parser.peek_nth_token(1).token {
+            if w.keyword == Keyword::USER {
+                return self.parse_alter_user();
+            }
+        }
+
         let alter_table = self.parse_alter_table().context(error::SyntaxSnafu)?;
         Ok(Statement::Alter(alter_table))
     }
@@ -60,11 +60,39 @@ fn validate_database_option(key: &str) -> bool {
 
 pub const COLUMN_FULLTEXT_OPT_KEY_ANALYZER: &str = "analyzer";
 pub const COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE: &str = "case_sensitive";
+pub const COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS: &str = "stop_words";
 
 fn validate_column_fulltext_option(key: &str) -> bool {
     [
         COLUMN_FULLTEXT_OPT_KEY_ANALYZER,
         COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE,
+        COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS,
+    ]
+    .contains(&key)
+}
+
+pub const VECTOR: &str = "VECTOR";
+pub const COLUMN_VECTOR_INDEX_OPT_KEY_METRIC: &str = "metric";
+pub const COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY: &str = "connectivity";
+pub const COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD: &str = "expansion_add";
+
+fn validate_column_vector_index_option(key: &str) -> bool {
+    [
+        COLUMN_VECTOR_INDEX_OPT_KEY_METRIC,
+        COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY,
+        COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD,
+    ]
+    .contains(&key)
+}
+
+pub const SKIPPING: &str = "SKIPPING";
+pub const COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE: &str = "type";
+pub const COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE: &str = "false_positive_rate";
+
+fn validate_column_skipping_index_option(key: &str) -> bool {
+    [
+        COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE,
+        COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE,
     ]
     .contains(&key)
 }
@@ -106,6 +134,11 @@ impl<'a> ParserContext<'a> {
                     self.parse_create_view(false)
                 }
 
+                Keyword::USER => {
+                    let _ = self.parser.next_token();
+                    self.parse_create_user()
+                }
+
                 Keyword::NoKeyword => {
                     let _ = self.parser.next_token();
                     let uppercase = w.value.to_uppercase();
@@ -729,11 +762,111 @@ impl<'a> ParserContext<'a> {
 
             column_extensions.fulltext_options = Some(options.into());
             Ok(true)
+        } else if Self::parse_vector_index_keywords(parser) {
+            ensure!(
+                column_extensions.vector_index_options.is_none(),
+                InvalidColumnOptionSnafu {
+                    name: column_name.to_string(),
+                    msg: "duplicated VECTOR INDEX option",
+                }
+            );
+
+            let column_type = get_unalias_type(column_type);
+            let data_type = sql_data_type_to_concrete_data_type(&column_type)?;
+            ensure!(
+                data_type.is_vector(),
+                InvalidColumnOptionSnafu {
+                    name: column_name.to_string(),
+                    msg: "VECTOR INDEX only supports vector type",
+                }
+            );
+
+            let options = parser
+                .parse_options(Keyword::WITH)
+                .context(error::SyntaxSnafu)?
+                .into_iter()
+                .map(parse_option_string)
+                .collect::<Result<HashMap<String, String>>>()?;
+
+            for key in options.keys() {
+                ensure!(
+                    validate_column_vector_index_option(key),
+                    InvalidColumnOptionSnafu {
+                        name: column_name.to_string(),
+                        msg: format!("invalid VECTOR INDEX option: {key}"),
+                    }
+                );
+            }
+
+            column_extensions.vector_index_options = Some(options.into());
+            Ok(true)
+        } else if Self::parse_index_keywords(parser, SKIPPING) {
+            ensure!(
+                column_extensions.skipping_index_options.is_none(),
+                InvalidColumnOptionSnafu {
+                    name: column_name.to_string(),
+                    msg: "duplicated SKIPPING INDEX option",
+                }
+            );
+
+            let column_type = get_unalias_type(column_type);
+            let data_type = sql_data_type_to_concrete_data_type(&column_type)?;
+            ensure!(
+                !data_type.is_json() && !data_type.is_vector(),
+                InvalidColumnOptionSnafu {
+                    name: column_name.to_string(),
+                    msg: "SKIPPING INDEX doesn't support json and vector types",
+                }
+            );
+
+            let options = parser
+                .parse_options(Keyword::WITH)
+                .context(error::SyntaxSnafu)?
+                .into_iter()
+                .map(parse_option_string)
+                .collect::<Result<HashMap<String, String>>>()?;
+
+            for key in options.keys() {
+                ensure!(
+                    validate_column_skipping_index_option(key),
+                    InvalidColumnOptionSnafu {
+                        name: column_name.to_string(),
+                        msg: format!("invalid SKIPPING INDEX option: {key}"),
+                    }
+                );
+            }
+
+            column_extensions.skipping_index_options = Some(options.into());
+            Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// Consumes `VECTOR INDEX` and returns true if the next tokens are `VECTOR INDEX`.
+    fn parse_vector_index_keywords(parser: &mut Parser<'a>) -> bool {
+        Self::parse_index_keywords(parser, VECTOR)
+    }
+
+    /// Consumes `{prefix} INDEX` and returns true if the next tokens are `{prefix} INDEX`.
+    fn parse_index_keywords(parser: &mut Parser<'a>, prefix: &str) -> bool {
+        let is_prefix = matches!(
+            &parser.peek_token().token,
+            Token::Word(w) if w.value.eq_ignore_ascii_case(prefix)
+        );
+        let is_index = matches!(
+            &parser.peek_nth_token(1).token,
+            Token::Word(w) if w.keyword == Keyword::INDEX
+        );
+        if is_prefix && is_index {
+            let _ = parser.next_token();
+            let _ = parser.next_token();
+            true
+        } else {
+            false
+        }
+    }
+
     fn parse_optional_table_constraint(&mut self) -> Result<Option<TableConstraint>> {
         let name = if self.parser.parse_keyword(Keyword::CONSTRAINT) {
             let raw_name = self.parse_identifier().context(SyntaxSnafu)?;
@@ -2015,6 +2148,31 @@ CREATE TABLE log (
         } else {
             panic!("should be create_table statement");
         }
+
+        let sql4 = r"
+CREATE TABLE log (
+    ts TIMESTAMP TIME INDEX,
+    msg STRING FULLTEXT WITH (analyzer='Ngram', stop_words='_english_,foo')
+)";
+        let result4 = ParserContext::create_with_dialect(
+            sql4,
+            &GreptimeDbDialect {},
+            ParseOptions::default(),
+        )
+        .unwrap();
+
+        if let Statement::CreateTable(c) = &result4[0] {
+            c.columns.iter().for_each(|col| {
+                if col.name().value == "msg" {
+                    let options = col.extensions.fulltext_options.as_ref().unwrap();
+                    assert_eq!(options.len(), 2);
+                    assert_eq!(options.get("analyzer").unwrap(), "Ngram");
+                    assert_eq!(options.get("stop_words").unwrap(), "_english_,foo");
+                }
+            });
+        } else {
+            panic!("should be create_table statement");
+        }
     }
 
     #[test]
@@ -2065,6 +2223,119 @@ CREATE TABLE log (
             .contains("invalid FULLTEXT option"));
     }
 
+    #[test]
+    fn test_parse_create_table_vector_index_options() {
+        let sql = r"
+CREATE TABLE embeddings (
+    ts TIMESTAMP TIME INDEX,
+    v VECTOR(3) VECTOR INDEX WITH (metric='cosine', connectivity='8'),
+)";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default())
+                .unwrap();
+
+        if let Statement::CreateTable(c) = &result[0] {
+            let col = c
+                .columns
+                .iter()
+                .find(|col| col.name().value == "v")
+                .unwrap();
+            let options = col.extensions.vector_index_options.as_ref().unwrap();
+            assert_eq!(options.len(), 2);
+            assert_eq!(
+                options.get(COLUMN_VECTOR_INDEX_OPT_KEY_METRIC).unwrap(),
+                "cosine"
+            );
+            assert_eq!(
+                options
+                    .get(COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY)
+                    .unwrap(),
+                "8"
+            );
+        } else {
+            panic!("should be create_table statement");
+        }
+
+        let sql = r"
+CREATE TABLE embeddings (
+    ts TIMESTAMP TIME INDEX,
+    v BLOB VECTOR INDEX,
+)";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("VECTOR INDEX only supports vector type"));
+
+        let sql = r"
+CREATE TABLE embeddings (
+    ts TIMESTAMP TIME INDEX,
+    v VECTOR(3) VECTOR INDEX WITH (invalid_option='1'),
+)";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("invalid VECTOR INDEX option"));
+    }
+
+    #[test]
+    fn test_parse_create_table_skipping_index_options() {
+        let sql = r"
+CREATE TABLE traces (
+    ts TIMESTAMP TIME INDEX,
+    trace_id STRING SKIPPING INDEX WITH (false_positive_rate='0.05'),
+    span_id STRING SKIPPING INDEX,
+)";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default())
+                .unwrap();
+
+        if let Statement::CreateTable(c) = &result[0] {
+            let col = c
+                .columns
+                .iter()
+                .find(|col| col.name().value == "trace_id")
+                .unwrap();
+            let options = col.extensions.skipping_index_options.as_ref().unwrap();
+            assert_eq!(options.len(), 1);
+            assert_eq!(
+                options
+                    .get(COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE)
+                    .unwrap(),
+                "0.05"
+            );
+
+            let col = c
+                .columns
+                .iter()
+                .find(|col| col.name().value == "span_id")
+                .unwrap();
+            assert!(col
+                .extensions
+                .skipping_index_options
+                .as_ref()
+                .unwrap()
+                .is_empty());
+        } else {
+            panic!("should be create_table statement");
+        }
+
+        let sql = r"
+CREATE TABLE traces (
+    ts TIMESTAMP TIME INDEX,
+    trace_id STRING SKIPPING INDEX WITH (invalid_option='1'),
+)";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("invalid SKIPPING INDEX option"));
+    }
+
     #[test]
     fn test_parse_create_view_with_columns() {
         let sql = "CREATE VIEW test () AS SELECT * FROM NUMBERS";
@@ -30,6 +30,7 @@ impl<'a> ParserContext<'a> {
                 Keyword::TABLE => self.parse_drop_table(),
                 Keyword::VIEW => self.parse_drop_view(),
                 Keyword::SCHEMA | Keyword::DATABASE => self.parse_drop_database(),
+                Keyword::USER => self.parse_drop_user(),
                 Keyword::NoKeyword => {
                     let uppercase = w.value.to_uppercase();
                     match uppercase.as_str() {
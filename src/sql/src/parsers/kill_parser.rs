@@ -0,0 +1,80 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result, SyntaxSnafu};
+use crate::parser::ParserContext;
+use crate::statements::kill::KillQuery;
+use crate::statements::statement::Statement;
+
+/// `KILL QUERY {id | 'frontend/id'}`
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_kill(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        self.parser
+            .expect_keyword(Keyword::QUERY)
+            .context(SyntaxSnafu)?;
+
+        let id = match self.parser.next_token().token {
+            Token::Number(id, _) => id,
+            Token::SingleQuotedString(id) => id,
+            unexpected => {
+                return error::UnexpectedTokenSnafu {
+                    expected: "a query id",
+                    actual: unexpected.to_string(),
+                }
+                .fail()
+            }
+        };
+        Ok(Statement::KillQuery(KillQuery { id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+    use crate::parser::ParseOptions;
+
+    fn parse(sql: &str) -> Result<Statement> {
+        ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default())
+            .map(|mut stmts| stmts.pop().unwrap())
+    }
+
+    #[test]
+    fn test_parse_kill_query() {
+        let stmt = parse("KILL QUERY 42").unwrap();
+        assert_eq!(
+            Statement::KillQuery(KillQuery {
+                id: "42".to_string()
+            }),
+            stmt
+        );
+
+        let stmt = parse("kill query '127.0.0.1:4001/42'").unwrap();
+        assert_eq!(
+            Statement::KillQuery(KillQuery {
+                id: "127.0.0.1:4001/42".to_string()
+            }),
+            stmt
+        );
+        assert_eq!("KILL QUERY '127.0.0.1:4001/42'", stmt.to_string());
+
+        assert!(parse("KILL 42").is_err());
+        assert!(parse("KILL QUERY foo").is_err());
+    }
+}
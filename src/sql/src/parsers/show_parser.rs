@@ -22,7 +22,8 @@ use crate::error::{
 use crate::parser::ParserContext;
 use crate::statements::show::{
     ShowColumns, ShowCreateFlow, ShowCreateTable, ShowCreateView, ShowDatabases, ShowFlows,
-    ShowIndex, ShowKind, ShowStatus, ShowTableStatus, ShowTables, ShowVariables, ShowViews,
+    ShowIndex, ShowKind, ShowProcesslist, ShowStatus, ShowTableStatus, ShowTables, ShowVariables,
+    ShowViews,
 };
 use crate::statements::statement::Statement;
 
@@ -104,6 +105,8 @@ impl<'a> ParserContext<'a> {
             Ok(Statement::ShowVariables(ShowVariables { variable }))
         } else if self.consume_token("STATUS") {
             Ok(Statement::ShowStatus(ShowStatus {}))
+        } else if self.consume_token("PROCESSLIST") {
+            Ok(Statement::ShowProcesslist(ShowProcesslist {}))
         } else {
             self.unsupported(self.peek_token_as_string())
         }
@@ -1045,4 +1048,15 @@ mod tests {
         );
         assert_eq!(sql, stmts[0].to_string());
     }
+
+    #[test]
+    pub fn test_show_processlist() {
+        let sql = "SHOW PROCESSLIST";
+        let result =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default());
+        let stmts = result.unwrap();
+        assert_eq!(1, stmts.len());
+        assert_eq!(stmts[0], Statement::ShowProcesslist(ShowProcesslist {}));
+        assert_eq!(sql, stmts[0].to_string());
+    }
 }
@@ -0,0 +1,303 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+use sqlparser::ast::ObjectName;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result, SyntaxSnafu};
+use crate::parser::ParserContext;
+use crate::statements::statement::Statement;
+use crate::statements::user::{
+    AlterUser, CreateUser, DropUser, Grant, GrantObject, Privilege, Revoke,
+};
+
+const IDENTIFIED: &str = "IDENTIFIED";
+const READ: &str = "READ";
+const WRITE: &str = "WRITE";
+
+/// Parser for user management statements:
+/// - `CREATE USER [IF NOT EXISTS] user IDENTIFIED BY 'password'`
+/// - `DROP USER [IF EXISTS] user`
+/// - `ALTER USER user IDENTIFIED BY 'password'`
+/// - `GRANT privilege [, ...] ON object TO user`
+/// - `REVOKE privilege [, ...] ON object FROM user`
+impl<'a> ParserContext<'a> {
+    /// Parses `CREATE USER`, the `CREATE USER` keywords are consumed.
+    pub(crate) fn parse_create_user(&mut self) -> Result<Statement> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_user_name()?;
+        let password = self.parse_identified_by()?;
+        Ok(Statement::CreateUser(CreateUser {
+            name,
+            password,
+            if_not_exists,
+        }))
+    }
+
+    /// Parses `DROP USER`, the `DROP` keyword is consumed.
+    pub(crate) fn parse_drop_user(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parse_user_name()?;
+        Ok(Statement::DropUser(DropUser { name, if_exists }))
+    }
+
+    /// Parses `ALTER USER`.
+    pub(crate) fn parse_alter_user(&mut self) -> Result<Statement> {
+        self.parser
+            .expect_keywords(&[Keyword::ALTER, Keyword::USER])
+            .context(SyntaxSnafu)?;
+        let name = self.parse_user_name()?;
+        let password = self.parse_identified_by()?;
+        Ok(Statement::AlterUser(AlterUser { name, password }))
+    }
+
+    /// Parses `GRANT`.
+    pub(crate) fn parse_grant(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let (privileges, object) = self.parse_privileges_on_object()?;
+        self.parser
+            .expect_keyword(Keyword::TO)
+            .context(SyntaxSnafu)?;
+        let user = self.parse_user_name()?;
+        Ok(Statement::Grant(Grant {
+            privileges,
+            object,
+            user,
+        }))
+    }
+
+    /// Parses `REVOKE`.
+    pub(crate) fn parse_revoke(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let (privileges, object) = self.parse_privileges_on_object()?;
+        self.parser
+            .expect_keyword(Keyword::FROM)
+            .context(SyntaxSnafu)?;
+        let user = self.parse_user_name()?;
+        Ok(Statement::Revoke(Revoke {
+            privileges,
+            object,
+            user,
+        }))
+    }
+
+    /// Parses a user name, which is either an identifier or a single quoted string.
+    /// User names are case sensitive.
+    fn parse_user_name(&mut self) -> Result<String> {
+        match self.parser.next_token().token {
+            Token::Word(w) => Ok(w.value),
+            Token::SingleQuotedString(s) => Ok(s),
+            unexpected => error::UnexpectedTokenSnafu {
+                expected: "a user name",
+                actual: unexpected.to_string(),
+            }
+            .fail(),
+        }
+    }
+
+    /// Parses `IDENTIFIED BY 'password'`.
+    fn parse_identified_by(&mut self) -> Result<String> {
+        if !self.consume_token(IDENTIFIED) {
+            return self.expected(IDENTIFIED, self.parser.peek_token());
+        }
+        self.parser
+            .expect_keyword(Keyword::BY)
+            .context(SyntaxSnafu)?;
+        match self.parser.next_token().token {
+            Token::SingleQuotedString(s) => Ok(s),
+            unexpected => error::UnexpectedTokenSnafu {
+                expected: "a quoted password string",
+                actual: unexpected.to_string(),
+            }
+            .fail(),
+        }
+    }
+
+    /// Parses `privilege [, ...] ON object`.
+    fn parse_privileges_on_object(&mut self) -> Result<(Vec<Privilege>, GrantObject)> {
+        let mut privileges = vec![];
+        loop {
+            if self.consume_token(READ) {
+                privileges.push(Privilege::Read);
+            } else if self.consume_token(WRITE) {
+                privileges.push(Privilege::Write);
+            } else if self.parser.parse_keyword(Keyword::ALL) {
+                let _ = self.parser.parse_keyword(Keyword::PRIVILEGES);
+                privileges.extend([Privilege::Read, Privilege::Write]);
+            } else {
+                return self.expected("READ, WRITE or ALL", self.parser.peek_token());
+            }
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        privileges.dedup();
+
+        self.parser
+            .expect_keyword(Keyword::ON)
+            .context(SyntaxSnafu)?;
+        let object = self.parse_grant_object()?;
+        Ok((privileges, object))
+    }
+
+    /// Parses the object of privileges: `*.*`, `*`, `db.*` or `[db.]table`.
+    fn parse_grant_object(&mut self) -> Result<GrantObject> {
+        if self.parser.consume_token(&Token::Mul) {
+            if self.parser.consume_token(&Token::Period) {
+                self.parser.expect_token(&Token::Mul).context(SyntaxSnafu)?;
+                return Ok(GrantObject::Global);
+            }
+            return Ok(GrantObject::Database(None));
+        }
+
+        let mut idents = vec![self.parse_identifier().context(SyntaxSnafu)?];
+        while self.parser.consume_token(&Token::Period) {
+            if self.parser.consume_token(&Token::Mul) {
+                if idents.len() != 1 {
+                    return error::UnexpectedTokenSnafu {
+                        expected: "a database name before '.*'",
+                        actual: ObjectName(idents).to_string(),
+                    }
+                    .fail();
+                }
+                let db = Self::canonicalize_identifier(idents.pop().unwrap());
+                return Ok(GrantObject::Database(Some(db)));
+            }
+            idents.push(self.parse_identifier().context(SyntaxSnafu)?);
+        }
+        Ok(GrantObject::Table(Self::canonicalize_object_name(
+            ObjectName(idents),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::Ident;
+
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+    use crate::parser::ParseOptions;
+
+    fn parse(sql: &str) -> Result<Statement> {
+        ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}, ParseOptions::default())
+            .map(|mut stmts| stmts.pop().unwrap())
+    }
+
+    #[test]
+    fn test_parse_create_drop_alter_user() {
+        let stmt = parse("CREATE USER IF NOT EXISTS Alice IDENTIFIED BY 'pwd'").unwrap();
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "Alice".to_string(),
+                password: "pwd".to_string(),
+                if_not_exists: true,
+            }),
+            stmt
+        );
+        assert_eq!(
+            "CREATE USER IF NOT EXISTS 'Alice' IDENTIFIED BY '******'",
+            stmt.to_string()
+        );
+
+        let stmt = parse("create user 'bob' identified by 'pwd'").unwrap();
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "bob".to_string(),
+                password: "pwd".to_string(),
+                if_not_exists: false,
+            }),
+            stmt
+        );
+
+        assert!(parse("CREATE USER bob").is_err());
+        assert!(parse("CREATE USER bob IDENTIFIED BY pwd").is_err());
+
+        let stmt = parse("DROP USER IF EXISTS bob").unwrap();
+        assert_eq!(
+            Statement::DropUser(DropUser {
+                name: "bob".to_string(),
+                if_exists: true,
+            }),
+            stmt
+        );
+
+        let stmt = parse("ALTER USER 'bob' IDENTIFIED BY 'new_pwd'").unwrap();
+        assert_eq!(
+            Statement::AlterUser(AlterUser {
+                name: "bob".to_string(),
+                password: "new_pwd".to_string(),
+            }),
+            stmt
+        );
+        assert_eq!("ALTER USER 'bob' IDENTIFIED BY '******'", stmt.to_string());
+    }
+
+    #[test]
+    fn test_parse_grant_revoke() {
+        let stmt = parse("GRANT READ, WRITE ON *.* TO bob").unwrap();
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Read, Privilege::Write],
+                object: GrantObject::Global,
+                user: "bob".to_string(),
+            }),
+            stmt
+        );
+        assert_eq!("GRANT READ, WRITE ON *.* TO 'bob'", stmt.to_string());
+
+        let stmt = parse("GRANT ALL PRIVILEGES ON MyDb.* TO 'bob'").unwrap();
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Read, Privilege::Write],
+                object: GrantObject::Database(Some(Ident::new("mydb"))),
+                user: "bob".to_string(),
+            }),
+            stmt
+        );
+
+        let stmt = parse("GRANT read ON * TO bob").unwrap();
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Read],
+                object: GrantObject::Database(None),
+                user: "bob".to_string(),
+            }),
+            stmt
+        );
+
+        let stmt = parse("REVOKE WRITE ON db.Metrics FROM bob").unwrap();
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                privileges: vec![Privilege::Write],
+                object: GrantObject::Table(ObjectName(vec![
+                    Ident::new("db"),
+                    Ident::new("metrics")
+                ])),
+                user: "bob".to_string(),
+            }),
+            stmt
+        );
+        assert_eq!("REVOKE WRITE ON db.metrics FROM 'bob'", stmt.to_string());
+
+        assert!(parse("GRANT SELECT ON *.* TO bob").is_err());
+        assert!(parse("GRANT READ ON *.* FROM bob").is_err());
+        assert!(parse("REVOKE READ ON a.b.* FROM bob").is_err());
+    }
+}
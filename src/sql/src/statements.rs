@@ -20,6 +20,7 @@ pub mod describe;
 pub mod drop;
 pub mod explain;
 pub mod insert;
+pub mod kill;
 mod option_map;
 pub mod query;
 pub mod set_variables;
@@ -28,6 +29,7 @@ pub mod statement;
 pub mod tql;
 mod transform;
 pub mod truncate;
+pub mod user;
 
 use std::str::FromStr;
 
@@ -41,20 +43,24 @@ use common_time::Timestamp;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::constraint::{CURRENT_TIMESTAMP, CURRENT_TIMESTAMP_FN};
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, COMMENT_KEY};
-use datatypes::types::{cast, TimestampType};
+use datatypes::types::{
+    cast, parse_string_to_json_type_value, parse_string_to_vector_type_value, TimestampType,
+    VECTOR_TYPE_NAME,
+};
 use datatypes::value::{OrderedF32, OrderedF64, Value};
 use snafu::{ensure, OptionExt, ResultExt};
 use sqlparser::ast::{ExactNumberInfo, UnaryOperator};
 
 use crate::ast::{
-    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, TimezoneInfo,
-    Value as SqlValue,
+    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, Ident, ObjectName,
+    TimezoneInfo, Value as SqlValue,
 };
 use crate::error::{
     self, ColumnTypeMismatchSnafu, ConvertSqlValueSnafu, ConvertToGrpcDataTypeSnafu,
     ConvertValueSnafu, InvalidCastSnafu, InvalidSqlValueSnafu, InvalidUnaryOpSnafu,
     ParseSqlValueSnafu, Result, SerializeColumnDefaultConstraintSnafu, SetFulltextOptionSnafu,
-    TimestampOverflowSnafu, UnsupportedDefaultValueSnafu, UnsupportedUnaryOpSnafu,
+    SetSkippingIndexOptionSnafu, SetVectorIndexOptionSnafu, TimestampOverflowSnafu,
+    UnsupportedDefaultValueSnafu, UnsupportedUnaryOpSnafu,
 };
 use crate::statements::create::Column;
 pub use crate::statements::option_map::OptionMap;
@@ -123,6 +129,20 @@ fn parse_string_to_value(
             }
         }
         ConcreteDataType::Binary(_) => Ok(Value::Binary(s.as_bytes().into())),
+        ConcreteDataType::Json(j) => match parse_string_to_json_type_value(&s, &j.format) {
+            Ok(json) => Ok(Value::Binary(json.into())),
+            Err(_) => ParseSqlValueSnafu {
+                msg: format!("Failed to parse {s} to Json value"),
+            }
+            .fail(),
+        },
+        ConcreteDataType::Vector(v) => match parse_string_to_vector_type_value(&s, v.dim) {
+            Ok(vector) => Ok(Value::Binary(vector.into())),
+            Err(e) => ParseSqlValueSnafu {
+                msg: format!("Failed to parse {s} to Vector value: {e}"),
+            }
+            .fail(),
+        },
         _ => {
             unreachable!()
         }
@@ -467,6 +487,18 @@ pub fn column_to_schema(
             .context(SetFulltextOptionSnafu)?;
     }
 
+    if let Some(options) = column.extensions.build_vector_index_options()? {
+        column_schema = column_schema
+            .with_vector_index_options(options)
+            .context(SetVectorIndexOptionSnafu)?;
+    }
+
+    if let Some(options) = column.extensions.build_skipping_index_options()? {
+        column_schema = column_schema
+            .with_skipping_index_options(options)
+            .context(SetSkippingIndexOptionSnafu)?;
+    }
+
     Ok(column_schema)
 }
 
@@ -547,6 +579,24 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
         | SqlDataType::Blob(_)
         | SqlDataType::Bytea
         | SqlDataType::Varbinary(_) => Ok(ConcreteDataType::binary_datatype()),
+        SqlDataType::JSON => Ok(ConcreteDataType::json_datatype()),
+        SqlDataType::Custom(name, modifiers)
+            if name.0.len() == 1 && name.0[0].value.eq_ignore_ascii_case(VECTOR_TYPE_NAME) =>
+        {
+            match modifiers.as_slice() {
+                [dim] => match dim.parse::<u32>() {
+                    Ok(dim) if dim > 0 => Ok(ConcreteDataType::vector_datatype(dim)),
+                    _ => error::SqlTypeNotSupportedSnafu {
+                        t: data_type.clone(),
+                    }
+                    .fail(),
+                },
+                _ => error::SqlTypeNotSupportedSnafu {
+                    t: data_type.clone(),
+                }
+                .fail(),
+            }
+        }
         SqlDataType::Datetime(_) => Ok(ConcreteDataType::datetime_datatype()),
         SqlDataType::Timestamp(precision, _) => Ok(precision
             .as_ref()
@@ -603,6 +653,11 @@ pub fn concrete_data_type_to_sql_data_type(data_type: &ConcreteDataType) -> Resu
         )),
         ConcreteDataType::Interval(_) => Ok(SqlDataType::Interval),
         ConcreteDataType::Binary(_) => Ok(SqlDataType::Varbinary(None)),
+        ConcreteDataType::Json(_) => Ok(SqlDataType::JSON),
+        ConcreteDataType::Vector(v) => Ok(SqlDataType::Custom(
+            ObjectName(vec![Ident::new(VECTOR_TYPE_NAME)]),
+            vec![v.dim.to_string()],
+        )),
         ConcreteDataType::Decimal128(d) => Ok(SqlDataType::Decimal(
             ExactNumberInfo::PrecisionAndScale(d.precision() as u64, d.scale() as u64),
         )),
@@ -640,14 +695,17 @@ mod tests {
     use common_time::timestamp::TimeUnit;
     use common_time::timezone::set_default_timezone;
     use datatypes::schema::FulltextAnalyzer;
-    use datatypes::types::BooleanType;
+    use datatypes::types::{binlit_as_veclit, json_type_value_to_string, BooleanType, JsonFormat};
     use datatypes::value::OrderedFloat;
 
     use super::*;
     use crate::ast::TimezoneInfo;
     use crate::statements::create::ColumnExtensions;
     use crate::statements::ColumnOption;
-    use crate::{COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE};
+    use crate::{
+        COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE,
+        COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS,
+    };
 
     fn check_type(sql_type: SqlDataType, data_type: ConcreteDataType) {
         assert_eq!(
@@ -696,6 +754,19 @@ mod tests {
             SqlDataType::Varbinary(None),
             ConcreteDataType::binary_datatype(),
         );
+        check_type(SqlDataType::JSON, ConcreteDataType::json_datatype());
+        check_type(
+            SqlDataType::Custom(
+                ObjectName(vec![Ident::new("VECTOR")]),
+                vec!["3".to_string()],
+            ),
+            ConcreteDataType::vector_datatype(3),
+        );
+        assert!(sql_data_type_to_concrete_data_type(&SqlDataType::Custom(
+            ObjectName(vec![Ident::new("VECTOR")]),
+            vec!["0".to_string()],
+        ))
+        .is_err());
         check_type(
             SqlDataType::UnsignedBigInt(None),
             ConcreteDataType::uint64_datatype(),
@@ -1036,6 +1107,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_json_literal() {
+        let value = parse_string_to_value(
+            "json_col",
+            r#"{"a": 1, "b": [true, null]}"#.to_string(),
+            &ConcreteDataType::json_datatype(),
+            None,
+        )
+        .unwrap();
+        let Value::Binary(json) = value else {
+            unreachable!()
+        };
+        assert_eq!(
+            r#"{"a":1,"b":[true,null]}"#,
+            json_type_value_to_string(&json, &JsonFormat::Jsonb).unwrap()
+        );
+
+        assert!(parse_string_to_value(
+            "json_col",
+            "{invalid".to_string(),
+            &ConcreteDataType::json_datatype(),
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_vector_literal() {
+        let value = parse_string_to_value(
+            "vec_col",
+            "[1.0, 2.0, 3.0]".to_string(),
+            &ConcreteDataType::vector_datatype(3),
+            None,
+        )
+        .unwrap();
+        let Value::Binary(vector) = value else {
+            unreachable!()
+        };
+        assert_eq!(vec![1.0, 2.0, 3.0], binlit_as_veclit(&vector).unwrap());
+
+        assert!(parse_string_to_value(
+            "vec_col",
+            "[1.0, 2.0]".to_string(),
+            &ConcreteDataType::vector_datatype(3),
+            None,
+        )
+        .is_err());
+    }
+
     #[test]
     pub fn test_parse_column_default_constraint() {
         let bool_value = sqlparser::ast::Value::Boolean(true);
@@ -1382,6 +1502,8 @@ mod tests {
                     ])
                     .into(),
                 ),
+                vector_index_options: None,
+                skipping_index_options: None,
             },
         };
 
@@ -1393,6 +1515,57 @@ mod tests {
         assert!(fulltext_options.case_sensitive);
     }
 
+    #[test]
+    fn test_column_to_schema_with_fulltext_analyzer() {
+        let column = Column {
+            column_def: ColumnDef {
+                name: "col".into(),
+                data_type: SqlDataType::Text,
+                collation: None,
+                options: vec![],
+            },
+            extensions: ColumnExtensions {
+                fulltext_options: Some(
+                    HashMap::from_iter([
+                        (
+                            COLUMN_FULLTEXT_OPT_KEY_ANALYZER.to_string(),
+                            "whitespace".to_string(),
+                        ),
+                        (
+                            COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS.to_string(),
+                            "_english_, GET".to_string(),
+                        ),
+                    ])
+                    .into(),
+                ),
+                vector_index_options: None,
+                skipping_index_options: None,
+            },
+        };
+
+        let column_schema = column_to_schema(&column, false, None).unwrap();
+        let fulltext_options = column_schema.fulltext_options().unwrap().unwrap();
+        assert_eq!(fulltext_options.analyzer, FulltextAnalyzer::Whitespace);
+        assert!(!fulltext_options.case_sensitive);
+        assert_eq!(fulltext_options.stop_words, vec!["_english_", "GET"]);
+
+        let column = Column {
+            extensions: ColumnExtensions {
+                fulltext_options: Some(
+                    HashMap::from_iter([(
+                        COLUMN_FULLTEXT_OPT_KEY_ANALYZER.to_string(),
+                        "Thai".to_string(),
+                    )])
+                    .into(),
+                ),
+                vector_index_options: None,
+                skipping_index_options: None,
+            },
+            ..column
+        };
+        assert!(column_to_schema(&column, false, None).is_err());
+    }
+
     #[test]
     pub fn test_parse_placeholder_value() {
         assert!(sql_value_to_value(
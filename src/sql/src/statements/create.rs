@@ -15,16 +15,27 @@
 use std::fmt::{Display, Formatter};
 
 use common_catalog::consts::FILE_ENGINE;
-use datatypes::schema::{FulltextAnalyzer, FulltextOptions};
+use datatypes::schema::{
+    FulltextAnalyzer, FulltextOptions, SkippingIndexOptions, SkippingIndexType,
+    VectorDistanceMetric, VectorIndexOptions,
+};
 use itertools::Itertools;
 use sqlparser::ast::{ColumnOptionDef, DataType, Expr, Query};
 use sqlparser_derive::{Visit, VisitMut};
 
 use crate::ast::{ColumnDef, Ident, ObjectName, TableConstraint, Value as SqlValue};
-use crate::error::{FulltextInvalidOptionSnafu, Result};
+use crate::error::{
+    FulltextInvalidOptionSnafu, Result, SkippingIndexInvalidOptionSnafu,
+    VectorIndexInvalidOptionSnafu,
+};
 use crate::statements::statement::Statement;
 use crate::statements::OptionMap;
-use crate::{COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE};
+use crate::{
+    COLUMN_FULLTEXT_OPT_KEY_ANALYZER, COLUMN_FULLTEXT_OPT_KEY_CASE_SENSITIVE,
+    COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS, COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE,
+    COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE, COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY,
+    COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD, COLUMN_VECTOR_INDEX_OPT_KEY_METRIC,
+};
 
 const LINE_SEP: &str = ",\n";
 const COMMA_SEP: &str = ", ";
@@ -108,6 +119,10 @@ pub struct Column {
 pub struct ColumnExtensions {
     /// Fulltext options.
     pub fulltext_options: Option<OptionMap>,
+    /// Vector index options.
+    pub vector_index_options: Option<OptionMap>,
+    /// Skipping index options.
+    pub skipping_index_options: Option<OptionMap>,
 }
 
 impl Column {
@@ -143,6 +158,22 @@ impl Display for Column {
                 write!(f, " FULLTEXT")?;
             }
         }
+        if let Some(vector_index_options) = &self.extensions.vector_index_options {
+            if !vector_index_options.is_empty() {
+                let options = vector_index_options.kv_pairs();
+                write!(f, " VECTOR INDEX WITH({})", format_list_comma!(options))?;
+            } else {
+                write!(f, " VECTOR INDEX")?;
+            }
+        }
+        if let Some(skipping_index_options) = &self.extensions.skipping_index_options {
+            if !skipping_index_options.is_empty() {
+                let options = skipping_index_options.kv_pairs();
+                write!(f, " SKIPPING INDEX WITH({})", format_list_comma!(options))?;
+            } else {
+                write!(f, " SKIPPING INDEX")?;
+            }
+        }
         Ok(())
     }
 }
@@ -161,9 +192,17 @@ impl ColumnExtensions {
             match analyzer.to_ascii_lowercase().as_str() {
                 "english" => fulltext.analyzer = FulltextAnalyzer::English,
                 "chinese" => fulltext.analyzer = FulltextAnalyzer::Chinese,
+                "standard" => fulltext.analyzer = FulltextAnalyzer::Standard,
+                "whitespace" => fulltext.analyzer = FulltextAnalyzer::Whitespace,
+                "ngram" => fulltext.analyzer = FulltextAnalyzer::Ngram,
+                "japanese" => fulltext.analyzer = FulltextAnalyzer::Japanese,
+                "korean" => fulltext.analyzer = FulltextAnalyzer::Korean,
                 _ => {
                     return FulltextInvalidOptionSnafu {
-                        msg: format!("{analyzer}, expected: 'English' | 'Chinese'"),
+                        msg: format!(
+                            "{analyzer}, expected: 'English' | 'Chinese' | 'Standard' | \
+                            'Whitespace' | 'Ngram' | 'Japanese' | 'Korean'"
+                        ),
                     }
                     .fail();
                 }
@@ -181,9 +220,94 @@ impl ColumnExtensions {
                 }
             }
         }
+        if let Some(stop_words) = options.get(COLUMN_FULLTEXT_OPT_KEY_STOP_WORDS) {
+            fulltext.stop_words = stop_words
+                .split(',')
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
 
         Ok(Some(fulltext))
     }
+
+    pub fn build_vector_index_options(&self) -> Result<Option<VectorIndexOptions>> {
+        let Some(options) = self.vector_index_options.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut vector_index = VectorIndexOptions::default();
+        if let Some(metric) = options.get(COLUMN_VECTOR_INDEX_OPT_KEY_METRIC) {
+            match metric.to_ascii_lowercase().as_str() {
+                "l2sq" => vector_index.metric = VectorDistanceMetric::L2sq,
+                "cosine" => vector_index.metric = VectorDistanceMetric::Cosine,
+                "inner_product" => vector_index.metric = VectorDistanceMetric::InnerProduct,
+                _ => {
+                    return VectorIndexInvalidOptionSnafu {
+                        msg: format!("{metric}, expected: 'l2sq' | 'cosine' | 'inner_product'"),
+                    }
+                    .fail();
+                }
+            }
+        }
+        if let Some(connectivity) = options.get(COLUMN_VECTOR_INDEX_OPT_KEY_CONNECTIVITY) {
+            vector_index.connectivity = match connectivity.parse() {
+                Ok(v) if v >= 2 => v,
+                _ => {
+                    return VectorIndexInvalidOptionSnafu {
+                        msg: format!("{connectivity}, expected: an integer no less than 2"),
+                    }
+                    .fail();
+                }
+            };
+        }
+        if let Some(expansion_add) = options.get(COLUMN_VECTOR_INDEX_OPT_KEY_EXPANSION_ADD) {
+            vector_index.expansion_add = match expansion_add.parse() {
+                Ok(v) if v >= 1 => v,
+                _ => {
+                    return VectorIndexInvalidOptionSnafu {
+                        msg: format!("{expansion_add}, expected: a positive integer"),
+                    }
+                    .fail();
+                }
+            };
+        }
+
+        Ok(Some(vector_index))
+    }
+
+    pub fn build_skipping_index_options(&self) -> Result<Option<SkippingIndexOptions>> {
+        let Some(options) = self.skipping_index_options.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut skipping_index = SkippingIndexOptions::default();
+        if let Some(index_type) = options.get(COLUMN_SKIPPING_INDEX_OPT_KEY_TYPE) {
+            match index_type.to_ascii_lowercase().as_str() {
+                "bloom" => skipping_index.index_type = SkippingIndexType::BloomFilter,
+                _ => {
+                    return SkippingIndexInvalidOptionSnafu {
+                        msg: format!("{index_type}, expected: 'bloom'"),
+                    }
+                    .fail();
+                }
+            }
+        }
+        if let Some(rate) = options.get(COLUMN_SKIPPING_INDEX_OPT_KEY_FALSE_POSITIVE_RATE) {
+            skipping_index.false_positive_rate = match rate.parse() {
+                Ok(v) if v > 0.0 && v < 1.0 => v,
+                _ => {
+                    return SkippingIndexInvalidOptionSnafu {
+                        msg: format!("{rate}, expected: a number between 0 and 1"),
+                    }
+                    .fail();
+                }
+            };
+        }
+
+        Ok(Some(skipping_index))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Visit, VisitMut)]
@@ -0,0 +1,32 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use sqlparser_derive::{Visit, VisitMut};
+
+/// `KILL QUERY id` statement.
+///
+/// The `id` is either `{frontend}/{id}` shown in `information_schema.process_list`,
+/// or a bare number for the queries on the current frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct KillQuery {
+    pub id: String,
+}
+
+impl Display for KillQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KILL QUERY '{}'", self.id.escape_default())
+    }
+}
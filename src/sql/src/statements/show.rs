@@ -261,6 +261,16 @@ impl Display for ShowStatus {
     }
 }
 
+/// SQL structure for "SHOW PROCESSLIST"
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct ShowProcesslist {}
+
+impl Display for ShowProcesslist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SHOW PROCESSLIST")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
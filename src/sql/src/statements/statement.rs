@@ -28,14 +28,17 @@ use crate::statements::describe::DescribeTable;
 use crate::statements::drop::{DropDatabase, DropFlow, DropTable, DropView};
 use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
+use crate::statements::kill::KillQuery;
 use crate::statements::query::Query;
 use crate::statements::set_variables::SetVariables;
 use crate::statements::show::{
     ShowColumns, ShowCreateFlow, ShowCreateTable, ShowCreateView, ShowDatabases, ShowFlows,
-    ShowIndex, ShowKind, ShowStatus, ShowTableStatus, ShowTables, ShowVariables, ShowViews,
+    ShowIndex, ShowKind, ShowProcesslist, ShowStatus, ShowTableStatus, ShowTables, ShowVariables,
+    ShowViews,
 };
 use crate::statements::tql::Tql;
 use crate::statements::truncate::TruncateTable;
+use crate::statements::user::{AlterUser, CreateUser, DropUser, Grant, Revoke};
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[allow(clippy::large_enum_variant)]
@@ -93,6 +96,8 @@ pub enum Statement {
     ShowCreateView(ShowCreateView),
     // SHOW STATUS
     ShowStatus(ShowStatus),
+    // SHOW PROCESSLIST
+    ShowProcesslist(ShowProcesslist),
     // SHOW VIEWS
     ShowViews(ShowViews),
     // DESCRIBE TABLE
@@ -110,6 +115,18 @@ pub enum Statement {
     ShowVariables(ShowVariables),
     // USE
     Use(String),
+    // CREATE USER
+    CreateUser(CreateUser),
+    // DROP USER
+    DropUser(DropUser),
+    // ALTER USER
+    AlterUser(AlterUser),
+    // GRANT
+    Grant(Grant),
+    // REVOKE
+    Revoke(Revoke),
+    // KILL QUERY
+    KillQuery(KillQuery),
 }
 
 impl Display for Statement {
@@ -139,6 +156,7 @@ impl Display for Statement {
             Statement::ShowCreateView(s) => s.fmt(f),
             Statement::ShowViews(s) => s.fmt(f),
             Statement::ShowStatus(s) => s.fmt(f),
+            Statement::ShowProcesslist(s) => s.fmt(f),
             Statement::DescribeTable(s) => s.fmt(f),
             Statement::Explain(s) => s.fmt(f),
             Statement::Copy(s) => s.fmt(f),
@@ -154,6 +172,12 @@ impl Display for Statement {
             }
             Statement::CreateView(s) => s.fmt(f),
             Statement::Use(s) => s.fmt(f),
+            Statement::CreateUser(s) => s.fmt(f),
+            Statement::DropUser(s) => s.fmt(f),
+            Statement::AlterUser(s) => s.fmt(f),
+            Statement::Grant(s) => s.fmt(f),
+            Statement::Revoke(s) => s.fmt(f),
+            Statement::KillQuery(s) => s.fmt(f),
         }
     }
 }
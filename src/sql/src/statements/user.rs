@@ -0,0 +1,163 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser_derive::{Visit, VisitMut};
+
+/// The placeholder of passwords when displaying statements.
+const MASKED_PASSWORD: &str = "******";
+
+/// `CREATE USER [IF NOT EXISTS] user IDENTIFIED BY 'password'` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct CreateUser {
+    pub name: String,
+    pub password: String,
+    pub if_not_exists: bool,
+}
+
+impl Display for CreateUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CREATE USER ")?;
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ")?;
+        }
+        write!(
+            f,
+            "'{}' IDENTIFIED BY '{MASKED_PASSWORD}'",
+            self.name.escape_default()
+        )
+    }
+}
+
+/// `DROP USER [IF EXISTS] user` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct DropUser {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl Display for DropUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DROP USER ")?;
+        if self.if_exists {
+            f.write_str("IF EXISTS ")?;
+        }
+        write!(f, "'{}'", self.name.escape_default())
+    }
+}
+
+/// `ALTER USER user IDENTIFIED BY 'password'` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct AlterUser {
+    pub name: String,
+    pub password: String,
+}
+
+impl Display for AlterUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ALTER USER '{}' IDENTIFIED BY '{MASKED_PASSWORD}'",
+            self.name.escape_default()
+        )
+    }
+}
+
+/// The privilege in `GRANT` and `REVOKE` statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Visit, VisitMut)]
+pub enum Privilege {
+    Read,
+    Write,
+}
+
+impl Display for Privilege {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Privilege::Read => f.write_str("READ"),
+            Privilege::Write => f.write_str("WRITE"),
+        }
+    }
+}
+
+/// The object in `GRANT` and `REVOKE` statements.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub enum GrantObject {
+    /// `*.*`
+    Global,
+    /// `db.*`, or `*` for the current database.
+    Database(Option<Ident>),
+    /// `[db.]table`
+    Table(ObjectName),
+}
+
+impl Display for GrantObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantObject::Global => f.write_str("*.*"),
+            GrantObject::Database(Some(db)) => write!(f, "{db}.*"),
+            GrantObject::Database(None) => f.write_str("*"),
+            GrantObject::Table(table) => write!(f, "{table}"),
+        }
+    }
+}
+
+fn format_privileges(privileges: &[Privilege]) -> String {
+    privileges
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `GRANT privilege [, ...] ON object TO user` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct Grant {
+    pub privileges: Vec<Privilege>,
+    pub object: GrantObject,
+    pub user: String,
+}
+
+impl Display for Grant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "GRANT {} ON {} TO '{}'",
+            format_privileges(&self.privileges),
+            self.object,
+            self.user.escape_default()
+        )
+    }
+}
+
+/// `REVOKE privilege [, ...] ON object FROM user` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Visit, VisitMut)]
+pub struct Revoke {
+    pub privileges: Vec<Privilege>,
+    pub object: GrantObject,
+    pub user: String,
+}
+
+impl Display for Revoke {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "REVOKE {} ON {} FROM '{}'",
+            format_privileges(&self.privileges),
+            self.object,
+            self.user.escape_default()
+        )
+    }
+}
@@ -57,11 +57,29 @@ impl RaftEngineProvider {
     }
 }
 
+// The Provider of object store log store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectStoreProvider {
+    pub region_id: RegionId,
+}
+
+impl ObjectStoreProvider {
+    pub fn new(region_id: RegionId) -> Self {
+        Self { region_id }
+    }
+
+    /// Returns the type name.
+    pub fn type_name() -> &'static str {
+        "ObjectStoreProvider"
+    }
+}
+
 /// The Provider of LogStore
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Provider {
     RaftEngine(RaftEngineProvider),
     Kafka(Arc<KafkaProvider>),
+    ObjectStore(ObjectStoreProvider),
 }
 
 impl Display for Provider {
@@ -71,6 +89,7 @@ impl Display for Provider {
                 write!(f, "region: {}", RegionId::from_u64(provider.id))
             }
             Provider::Kafka(provider) => write!(f, "topic: {}", provider.topic),
+            Provider::ObjectStore(provider) => write!(f, "region: {}", provider.region_id),
         }
     }
 }
@@ -84,11 +103,16 @@ impl Provider {
         Provider::Kafka(Arc::new(KafkaProvider { topic }))
     }
 
+    pub fn object_store_provider(region_id: RegionId) -> Provider {
+        Provider::ObjectStore(ObjectStoreProvider { region_id })
+    }
+
     /// Returns the type name.
     pub fn type_name(&self) -> &'static str {
         match self {
             Provider::RaftEngine(_) => RaftEngineProvider::type_name(),
             Provider::Kafka(_) => KafkaProvider::type_name(),
+            Provider::ObjectStore(_) => ObjectStoreProvider::type_name(),
         }
     }
 
@@ -107,4 +131,12 @@ impl Provider {
         }
         None
     }
+
+    /// Returns the reference of [`ObjectStoreProvider`] if it's the type of [`LogStoreProvider::ObjectStore`].
+    pub fn as_object_store_provider(&self) -> Option<&ObjectStoreProvider> {
+        if let Provider::ObjectStore(ns) = self {
+            return Some(ns);
+        }
+        None
+    }
 }
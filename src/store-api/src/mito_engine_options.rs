@@ -34,6 +34,12 @@ pub fn is_mito_engine_option_key(key: &str) -> bool {
         "compaction.twcs.time_window",
         "compaction.twcs.remote_compaction",
         "compaction.twcs.fallback_to_local",
+        "compaction.stcs.min_threshold",
+        "compaction.stcs.max_threshold",
+        "compaction.stcs.min_sstable_size",
+        "compaction.stcs.time_window",
+        "compaction.stcs.remote_compaction",
+        "compaction.stcs.fallback_to_local",
         "storage",
         "index.inverted_index.ignore_column_ids",
         "index.inverted_index.segment_row_count",
@@ -63,6 +69,7 @@ mod tests {
             "compaction.twcs.max_inactive_window_runs"
         ));
         assert!(is_mito_engine_option_key("compaction.twcs.time_window"));
+        assert!(is_mito_engine_option_key("compaction.stcs.min_threshold"));
         assert!(is_mito_engine_option_key("storage"));
         assert!(is_mito_engine_option_key(
             "index.inverted_index.ignore_column_ids"
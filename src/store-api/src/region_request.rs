@@ -24,6 +24,7 @@ use api::v1::region::{
 };
 use api::v1::{self, Rows, SemanticType};
 pub use common_base::AffectedRows;
+use datatypes::arrow::record_batch::RecordBatch;
 use datatypes::data_type::ConcreteDataType;
 use snafu::{ensure, OptionExt};
 use strum::IntoStaticStr;
@@ -49,6 +50,7 @@ pub enum RegionRequest {
     Compact(RegionCompactRequest),
     Truncate(RegionTruncateRequest),
     Catchup(RegionCatchupRequest),
+    BulkInserts(RegionBulkInsertsRequest),
 }
 
 impl RegionRequest {
@@ -237,6 +239,16 @@ pub struct RegionDeleteRequest {
     pub rows: Rows,
 }
 
+/// Request to put a batch of rows in the columnar format to a region.
+///
+/// Only the memtable takes the batch as is. The WAL has no columnar payload, so the
+/// engine still converts the batch to rows for the WAL.
+#[derive(Debug)]
+pub struct RegionBulkInsertsRequest {
+    /// Rows to put. Names of the columns must match the columns in the region.
+    pub payload: RecordBatch,
+}
+
 #[derive(Debug, Clone)]
 pub struct RegionCreateRequest {
     /// Region engine name
@@ -689,6 +701,7 @@ impl fmt::Display for RegionRequest {
             RegionRequest::Compact(_) => write!(f, "Compact"),
             RegionRequest::Truncate(_) => write!(f, "Truncate"),
             RegionRequest::Catchup(_) => write!(f, "Catchup"),
+            RegionRequest::BulkInserts(_) => write!(f, "BulkInserts"),
         }
     }
 }
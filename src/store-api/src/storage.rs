@@ -25,5 +25,5 @@ pub use datatypes::schema::{
 };
 
 pub use self::descriptors::*;
-pub use self::requests::{ScanRequest, TimeSeriesRowSelector};
+pub use self::requests::{ScanRequest, TimeSeriesRowSelector, VectorSearchRequest};
 pub use self::types::SequenceNumber;
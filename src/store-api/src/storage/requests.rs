@@ -14,6 +14,7 @@
 
 use common_recordbatch::OrderOption;
 use datafusion_expr::expr::Expr;
+use datatypes::schema::VectorDistanceMetric;
 use strum::Display;
 
 /// A hint on how to select rows from a time-series.
@@ -23,6 +24,38 @@ pub enum TimeSeriesRowSelector {
     LastRow,
 }
 
+/// A hint to search the `k` nearest rows of a query vector, e.g. from
+/// `ORDER BY vec_l2sq_distance(v, '[1.0, 2.0]') LIMIT k`.
+///
+/// It's only a hint for pruning, the data source may return more rows.
+#[derive(Clone, Debug)]
+pub struct VectorSearchRequest {
+    /// Name of the vector column.
+    pub column_name: String,
+    /// The query vector.
+    pub query: Vec<f32>,
+    /// The metric to measure the distance.
+    pub metric: VectorDistanceMetric,
+    /// Number of nearest rows to search.
+    pub k: usize,
+}
+
+impl PartialEq for VectorSearchRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.column_name == other.column_name
+            && self.metric == other.metric
+            && self.k == other.k
+            && self.query.len() == other.query.len()
+            && self
+                .query
+                .iter()
+                .zip(&other.query)
+                .all(|(l, r)| l.to_bits() == r.to_bits())
+    }
+}
+
+impl Eq for VectorSearchRequest {}
+
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct ScanRequest {
     /// Indices of columns to read, `None` to read all columns. This indices is
@@ -39,4 +72,6 @@ pub struct ScanRequest {
     pub limit: Option<usize>,
     /// Optional hint to select rows from time-series.
     pub series_row_selector: Option<TimeSeriesRowSelector>,
+    /// Optional hint to search the nearest rows of a vector.
+    pub vector_search: Option<VectorSearchRequest>,
 }
@@ -858,6 +858,17 @@ apply_on_query = "auto"
 mem_threshold_on_create = "auto"
 compress = true
 
+[region_engine.mito.vector_index]
+create_on_flush = "auto"
+create_on_compaction = "auto"
+apply_on_query = "auto"
+expansion_search = 64
+
+[region_engine.mito.bloom_filter_index]
+create_on_flush = "auto"
+create_on_compaction = "auto"
+apply_on_query = "auto"
+
 [region_engine.mito.memtable]
 type = "time_series"
 
@@ -18,6 +18,16 @@ SHOW CREATE TABLE log_with_opts;
 DROP TABLE log_with_opts;
 
 
+CREATE TABLE log_with_analyzer (
+    ts TIMESTAMP TIME INDEX,
+    msg TEXT FULLTEXT WITH (analyzer='Ngram', stop_words='_english_,foo'),
+);
+
+SHOW CREATE TABLE log_with_analyzer;
+
+DROP TABLE log_with_analyzer;
+
+
 CREATE TABLE log_multi_fulltext_cols (
     ts TIMESTAMP TIME INDEX,
     msg TINYTEXT FULLTEXT,
@@ -0,0 +1,86 @@
+CREATE TABLE orders (
+    order_id INT,
+    user_id INT,
+    ts TIMESTAMP,
+    PRIMARY KEY(order_id),
+    TIME INDEX(ts)
+);
+
+CREATE TABLE users (
+    user_id INT,
+    user_name STRING,
+    ts TIMESTAMP,
+    PRIMARY KEY(user_id),
+    TIME INDEX(ts)
+);
+
+CREATE TABLE order_users (
+    order_id INT,
+    user_name STRING,
+    ts TIMESTAMP,
+    update_at TIMESTAMP,
+    PRIMARY KEY(order_id),
+    TIME INDEX(ts)
+);
+
+CREATE TABLE order_users_left (
+    order_id INT,
+    user_name STRING,
+    ts TIMESTAMP,
+    update_at TIMESTAMP,
+    PRIMARY KEY(order_id),
+    TIME INDEX(ts)
+);
+
+CREATE FLOW join_orders_users
+SINK TO order_users
+AS
+SELECT orders.order_id, users.user_name, orders.ts FROM orders JOIN users ON orders.user_id = users.user_id;
+
+CREATE FLOW left_join_orders_users
+SINK TO order_users_left
+AS
+SELECT orders.order_id, users.user_name, orders.ts FROM orders LEFT JOIN users ON orders.user_id = users.user_id;
+
+INSERT INTO users VALUES
+    (1, 'alice', '2021-07-01 00:00:00'),
+    (2, 'bob', '2021-07-01 00:00:00');
+
+INSERT INTO orders VALUES
+    (1, 1, '2021-07-01 00:00:01'),
+    (2, 2, '2021-07-01 00:00:02'),
+    (3, 3, '2021-07-01 00:00:03');
+
+SELECT flush_flow('join_orders_users')<=1;
+
+SELECT flush_flow('left_join_orders_users')<=1;
+
+SELECT order_id, user_name, ts FROM order_users ORDER BY order_id;
+
+-- order 3 has no matching user yet
+SELECT order_id, user_name, ts FROM order_users_left ORDER BY order_id;
+
+-- a late row of the lookup side is joined with existing rows of the stream side
+INSERT INTO users VALUES (3, 'carol', '2021-07-01 00:00:04');
+
+SELECT flush_flow('join_orders_users')<=1;
+
+SELECT order_id, user_name, ts FROM order_users ORDER BY order_id;
+
+INSERT INTO orders VALUES (4, 1, '2021-07-01 00:00:05');
+
+SELECT flush_flow('join_orders_users')<=1;
+
+SELECT order_id, user_name, ts FROM order_users ORDER BY order_id;
+
+DROP FLOW join_orders_users;
+
+DROP FLOW left_join_orders_users;
+
+DROP TABLE orders;
+
+DROP TABLE users;
+
+DROP TABLE order_users;
+
+DROP TABLE order_users_left;
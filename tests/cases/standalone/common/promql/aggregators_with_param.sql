@@ -0,0 +1,42 @@
+CREATE TABLE host (
+  ts timestamp(3) time index,
+  host STRING PRIMARY KEY,
+  val DOUBLE,
+);
+
+-- host2 and host3 tie at 0s, host1 and host2 tie at 5s
+INSERT INTO TABLE host VALUES
+    (0,    'host1', 1),
+    (0,    'host2', 2),
+    (0,    'host3', 2),
+    (5000, 'host1', 3),
+    (5000, 'host2', 3),
+    (5000, 'host3', 1);
+
+-- series of the same value are ranked by their labels
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') topk(1, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') topk(2, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') bottomk(1, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') bottomk(2, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') quantile(0.5, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') quantile(0.25, host);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') count_values("value", host);
+
+-- the value label overrides the existing label
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (0, 5, '5s') count_values("host", host);
+
+DROP TABLE host;
@@ -0,0 +1,38 @@
+CREATE TABLE host (
+  ts timestamp(3) time index,
+  host STRING PRIMARY KEY,
+  val DOUBLE,
+);
+
+INSERT INTO TABLE host VALUES
+    (0,     'host1', 1),
+    (0,     'host2', 2),
+    (5000,  'host1', 3),
+    (5000,  'host2', 4),
+    (10000, 'host1', 5),
+    (10000, 'host2', 6),
+    (15000, 'host1', 7),
+    (15000, 'host2', 8);
+
+-- the inner expression is evaluated at 0s, 5s, 10s and 15s
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') max_over_time(host[10s:5s]);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') avg_over_time(host[10s:5s]);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') count_over_time(host[10s:5s]);
+
+-- the step defaults to the interval of the query
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') avg_over_time(host[10s:]);
+
+-- the inner expression is evaluated at 0s and 10s
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') count_over_time(host[10s:10s]);
+
+-- SQLNESS SORT_RESULT 3 1
+TQL EVAL (10, 15, '5s') max_over_time(host[10s:5s] offset 5s);
+
+DROP TABLE host;
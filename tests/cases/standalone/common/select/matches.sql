@@ -54,4 +54,25 @@ select fox from fox where matches(fox, 'over -(fox AND jumps)') order by ts;
 
 select fox from fox where matches(fox, 'over AND -(-(fox OR jumps))') order by ts;
 
+select ts, matches_score(fox, 'fox OR lazy') as s from fox order by s desc, ts limit 3;
+
 drop table fox;
+
+-- BM25 of the fulltext index prefers the short row, but `matches_score` prefers the row
+-- with more occurrences, scans with and without the index must return the same rows.
+create table logs (
+    ts timestamp time index,
+    msg string fulltext,
+) with ('append_mode' = 'true');
+
+insert into logs values
+    (1, 'error'),
+    (2, 'error error and a long message with many more words than the other one');
+
+select ts, matches_score(msg, 'error') as s from logs order by s desc limit 1;
+
+select flush_table('logs');
+
+select ts, matches_score(msg, 'error') as s from logs order by s desc limit 1;
+
+drop table logs;